@@ -0,0 +1,537 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::application::agent_runtime::status::{detect_waiting_rule, hook_waiting_excerpt};
+use crate::application::agent_runtime::transcript::transcripts_dir;
+use crate::application::agent_runtime::{
+    CommandExecutionMode, SessionActivity, SessionExecutionResult, StatusRules,
+    capture_session_output, detect_status_with_session_override,
+    execute_launch_request_with_result_for_mode, execute_restart_workspace_in_pane_with_result,
+    execute_stop_workspace_with_result_for_mode, infer_workspace_permission_mode,
+    launch_request_for_workspace, session_name_for_workspace_ref,
+    tmux_capture_error_indicates_missing_session, trimmed_nonempty,
+};
+use crate::application::workspace_lifecycle::{
+    WORKSPACE_INIT_COMMAND_FILE, WORKSPACE_LAUNCH_PROMPT_FILE, WORKSPACE_PERMISSION_MODE_FILE,
+};
+use crate::domain::{PermissionMode, Task, Workspace, WorkspaceStatus};
+use crate::infrastructure::config::{GroveConfig, ProjectConfig, SandboxConfig, ThemeName};
+use crate::infrastructure::paths::refer_to_same_location;
+use crate::infrastructure::process::{execute_command, stderr_or_status};
+
+const AGENT_ENV_SEPARATOR: char = ';';
+const STATUS_CAPTURE_LINES: usize = 120;
+
+/// A task worktree addressed as `<task>[/<repo>]` from outside the TUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentTarget {
+    pub label: String,
+    pub workspace: Workspace,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentTargetError {
+    UnknownTask(String),
+    UnknownRepository {
+        task: String,
+        repository: String,
+    },
+    AmbiguousRepository {
+        task: String,
+        repositories: Vec<String>,
+    },
+}
+
+pub fn agent_target_error_message(error: &AgentTargetError) -> String {
+    match error {
+        AgentTargetError::UnknownTask(task) => format!("unknown task '{task}'"),
+        AgentTargetError::UnknownRepository { task, repository } => {
+            format!("task '{task}' has no repository '{repository}'")
+        }
+        AgentTargetError::AmbiguousRepository { task, repositories } => format!(
+            "task '{task}' has several repositories, pick one of: {}",
+            repositories
+                .iter()
+                .map(|repository| format!("{task}/{repository}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Resolves `<task>[/<repo>]`. Without a repository every worktree of the
+/// task is returned; callers that act on a single agent reject more than one.
+pub fn resolve_agent_targets(
+    tasks: &[Task],
+    selector: &str,
+) -> Result<Vec<AgentTarget>, AgentTargetError> {
+    let (task_selector, repository_selector) = match selector.split_once('/') {
+        Some((task, repository)) => (task, Some(repository)),
+        None => (selector, None),
+    };
+    let task = tasks
+        .iter()
+        .find(|task| task.slug == task_selector)
+        .or_else(|| tasks.iter().find(|task| task.name == task_selector))
+        .ok_or_else(|| AgentTargetError::UnknownTask(task_selector.to_string()))?;
+
+    let targets = task
+        .worktrees
+        .iter()
+        .filter(|worktree| {
+            repository_selector.is_none_or(|repository| worktree.repository_name == repository)
+        })
+        .map(|worktree| AgentTarget {
+            label: format!("{}/{}", task.slug, worktree.repository_name),
+            workspace: Workspace::from_task_worktree(task, worktree),
+        })
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return Err(AgentTargetError::UnknownRepository {
+            task: task.slug.clone(),
+            repository: repository_selector.unwrap_or_default().to_string(),
+        });
+    }
+    Ok(targets)
+}
+
+pub fn resolve_single_agent_target(
+    tasks: &[Task],
+    selector: &str,
+) -> Result<AgentTarget, AgentTargetError> {
+    let mut targets = resolve_agent_targets(tasks, selector)?;
+    if targets.len() > 1 {
+        return Err(AgentTargetError::AmbiguousRepository {
+            task: selector.to_string(),
+            repositories: targets
+                .iter()
+                .filter_map(|target| target.workspace.project_name.clone())
+                .collect(),
+        });
+    }
+    Ok(targets.remove(0))
+}
+
+pub fn parse_agent_env_vars(raw: &str) -> Result<Vec<(String, String)>, String> {
+    let mut parsed = Vec::new();
+    for segment in raw
+        .split([AGENT_ENV_SEPARATOR, '\n'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+    {
+        let Some((raw_key, raw_value)) = segment.split_once('=') else {
+            return Err(format!("'{segment}' must be KEY=VALUE"));
+        };
+        let key = raw_key.trim();
+        if !env_var_key_is_valid(key) {
+            return Err(format!("invalid env key '{key}'"));
+        }
+        let value = raw_value.trim();
+        if value.is_empty() {
+            return Err(format!("env '{key}' cannot have empty value"));
+        }
+        parsed.push((key.to_string(), value.to_string()));
+    }
+    Ok(parsed)
+}
+
+fn env_var_key_is_valid(key: &str) -> bool {
+    let mut chars = key.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        return false;
+    }
+    chars.all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+fn project_for_workspace<'a>(
+    projects: &'a [ProjectConfig],
+    workspace: &Workspace,
+) -> Option<&'a ProjectConfig> {
+    let project_path = workspace.project_path.as_ref()?;
+    projects
+        .iter()
+        .find(|project| refer_to_same_location(&project.path, project_path))
+}
+
+pub fn project_agent_env_for_workspace(
+    projects: &[ProjectConfig],
+    workspace: &Workspace,
+) -> Result<Vec<(String, String)>, String> {
+    let Some(project) = project_for_workspace(projects, workspace) else {
+        return Ok(Vec::new());
+    };
+    parse_agent_env_vars(
+        project
+            .defaults
+            .agent_env
+            .entries_for(&workspace.agent)
+            .join("; ")
+            .as_str(),
+    )
+}
+
+pub fn project_sandbox_for_workspace(
+    projects: &[ProjectConfig],
+    workspace: &Workspace,
+) -> Option<SandboxConfig> {
+    project_for_workspace(projects, workspace)?
+        .defaults
+        .sandbox
+        .clone()
+}
+
+fn read_marker(workspace_path: &Path, marker: &str) -> Option<String> {
+    let raw = fs::read_to_string(workspace_path.join(marker)).ok()?;
+    trimmed_nonempty(raw.as_str())
+}
+
+pub fn workspace_launch_prompt(workspace_path: &Path) -> Option<String> {
+    read_marker(workspace_path, WORKSPACE_LAUNCH_PROMPT_FILE)
+}
+
+pub fn workspace_permission_mode(
+    workspace: &Workspace,
+    fallback: PermissionMode,
+) -> PermissionMode {
+    read_marker(&workspace.path, WORKSPACE_PERMISSION_MODE_FILE)
+        .and_then(|marker| PermissionMode::from_marker(marker.as_str()))
+        .or_else(|| infer_workspace_permission_mode(&workspace.agent, &workspace.path))
+        .unwrap_or(fallback)
+}
+
+pub fn workspace_init_command(projects: &[ProjectConfig], workspace: &Workspace) -> Option<String> {
+    read_marker(&workspace.path, WORKSPACE_INIT_COMMAND_FILE).or_else(|| {
+        project_for_workspace(projects, workspace)
+            .and_then(|project| trimmed_nonempty(project.defaults.workspace_init_command.as_str()))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StartAgentOptions {
+    pub prompt: Option<String>,
+    pub permission_mode: Option<PermissionMode>,
+    /// Task root that holds the session transcript when transcripts are enabled.
+    pub task_root: Option<PathBuf>,
+    /// Theme applied to the agent's tmux session.
+    pub theme_name: ThemeName,
+}
+
+pub fn start_agent(
+    config: &GroveConfig,
+    workspace: &Workspace,
+    options: StartAgentOptions,
+) -> SessionExecutionResult {
+    let permission_mode = options
+        .permission_mode
+        .unwrap_or_else(|| workspace_permission_mode(workspace, config.launch_permission_mode));
+    let failed = |error: String| SessionExecutionResult {
+        workspace_name: workspace.name.clone(),
+        workspace_path: workspace.path.clone(),
+        session_name: session_name_for_workspace_ref(workspace),
+        result: Err(error),
+    };
+    let agent_env = match project_agent_env_for_workspace(&config.projects, workspace) {
+        Ok(agent_env) => agent_env,
+        Err(error) => return failed(format!("invalid project agent env: {error}")),
+    };
+    let mut request = launch_request_for_workspace(
+        workspace,
+        options
+            .prompt
+            .or_else(|| workspace_launch_prompt(&workspace.path)),
+        options.theme_name,
+        workspace_init_command(&config.projects, workspace),
+        permission_mode,
+        agent_env,
+        None,
+    );
+    request.sandbox = project_sandbox_for_workspace(&config.projects, workspace);
+    request.status_hooks = config.status_hooks.enabled;
+    if config.transcripts.enabled {
+        request.transcript_dir = options.task_root.as_deref().map(transcripts_dir);
+    }
+    execute_launch_request_with_result_for_mode(&request, CommandExecutionMode::Process)
+}
+
+pub fn stop_agent(workspace: &Workspace) -> SessionExecutionResult {
+    execute_stop_workspace_with_result_for_mode(workspace, CommandExecutionMode::Process)
+}
+
+pub fn restart_agent(config: &GroveConfig, workspace: &Workspace) -> SessionExecutionResult {
+    let agent_env = match project_agent_env_for_workspace(&config.projects, workspace) {
+        Ok(agent_env) => agent_env,
+        Err(error) => {
+            return SessionExecutionResult {
+                workspace_name: workspace.name.clone(),
+                workspace_path: workspace.path.clone(),
+                session_name: session_name_for_workspace_ref(workspace),
+                result: Err(format!("invalid project agent env: {error}")),
+            };
+        }
+    };
+    let permission_mode = workspace_permission_mode(workspace, config.launch_permission_mode);
+    execute_restart_workspace_in_pane_with_result(
+        workspace,
+        permission_mode,
+        agent_env,
+        project_sandbox_for_workspace(&config.projects, workspace),
+        config.status_hooks.enabled,
+    )
+}
+
+/// Pastes `text` into the agent pane, then presses Enter when `submit` is set.
+///
+/// The text goes through a buffer named after the session and this process, so
+/// concurrent sends never paste each other's prompt.
+pub fn send_to_agent(session_name: &str, text: &str, submit: bool) -> Result<(), String> {
+    let buffer_name = send_buffer_name(session_name, std::process::id());
+    let mut load_child = Command::new("tmux")
+        .args(["load-buffer", "-b", buffer_name.as_str(), "-"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("tmux load-buffer failed: {error}"))?;
+    if let Some(stdin) = load_child.stdin.as_mut() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|error| format!("tmux load-buffer failed: {error}"))?;
+    }
+    let load_status = load_child
+        .wait()
+        .map_err(|error| format!("tmux load-buffer failed: {error}"))?;
+    if !load_status.success() {
+        return Err(format!(
+            "tmux load-buffer failed for '{session_name}': exit status {load_status}"
+        ));
+    }
+
+    for command in send_commands(session_name, buffer_name.as_str(), submit) {
+        execute_command(command.as_slice())
+            .map_err(|error| format!("tmux send failed for '{session_name}': {error}"))?;
+    }
+    Ok(())
+}
+
+fn send_buffer_name(session_name: &str, pid: u32) -> String {
+    format!("grove-{session_name}-{pid}")
+}
+
+fn send_commands(session_name: &str, buffer_name: &str, submit: bool) -> Vec<Vec<String>> {
+    let mut commands = vec![vec![
+        "tmux".to_string(),
+        "paste-buffer".to_string(),
+        "-b".to_string(),
+        buffer_name.to_string(),
+        "-d".to_string(),
+        "-p".to_string(),
+        "-t".to_string(),
+        session_name.to_string(),
+    ]];
+    if submit {
+        commands.push(vec![
+            "tmux".to_string(),
+            "send-keys".to_string(),
+            "-t".to_string(),
+            session_name.to_string(),
+            "Enter".to_string(),
+        ]);
+    }
+    commands
+}
+
+pub fn capture_agent(
+    session_name: &str,
+    scrollback_lines: usize,
+    include_escape_sequences: bool,
+) -> Result<String, String> {
+    capture_session_output(session_name, scrollback_lines, include_escape_sequences)
+        .map_err(|error| error.to_string())
+}
+
+pub fn agent_session_exists(session_name: &str) -> Result<bool, String> {
+    let output = Command::new("tmux")
+        .args(["has-session", "-t", session_name])
+        .output()
+        .map_err(|error| format!("tmux has-session failed: {error}"))?;
+    if output.status.success() {
+        return Ok(true);
+    }
+    let stderr = stderr_or_status(&output);
+    if tmux_capture_error_indicates_missing_session(stderr.as_str()) {
+        return Ok(false);
+    }
+    Err(format!("tmux has-session failed: {stderr}"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentStatusReport {
+    pub session_name: String,
+    pub running: bool,
+    pub status: WorkspaceStatus,
+    pub waiting_prompt: Option<String>,
+}
+
+pub fn agent_status(
+    workspace: &Workspace,
+    rules: &StatusRules,
+) -> Result<AgentStatusReport, String> {
+    let session_name = session_name_for_workspace_ref(workspace);
+    let running = agent_session_exists(&session_name)?;
+    let output = if running {
+        capture_agent(&session_name, STATUS_CAPTURE_LINES, false)?
+    } else {
+        String::new()
+    };
+    Ok(agent_status_from_output(
+        workspace,
+        rules,
+        session_name,
+        running,
+        output.as_str(),
+    ))
+}
+
+fn agent_status_from_output(
+    workspace: &Workspace,
+    rules: &StatusRules,
+    session_name: String,
+    running: bool,
+    output: &str,
+) -> AgentStatusReport {
+    let status = detect_status_with_session_override(
+        rules,
+        output,
+        SessionActivity::Idle,
+        workspace.is_main,
+        running,
+        workspace.supported_agent,
+        &workspace.agent,
+        &workspace.path,
+        &session_name,
+    );
+    let waiting_prompt = if status == WorkspaceStatus::Waiting {
+        hook_waiting_excerpt(&workspace.path).or_else(|| {
+            detect_waiting_rule(rules, &workspace.agent, output)
+                .map(|rule_match| rule_match.matched)
+        })
+    } else {
+        None
+    };
+    AgentStatusReport {
+        session_name,
+        running,
+        status,
+        waiting_prompt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AgentTargetError, parse_agent_env_vars, resolve_agent_targets, resolve_single_agent_target,
+        send_buffer_name, send_commands,
+    };
+    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use std::path::PathBuf;
+
+    fn task_fixture(slug: &str, repositories: &[&str]) -> Task {
+        let worktrees = repositories
+            .iter()
+            .map(|repository| {
+                Worktree::try_new(
+                    repository.to_string(),
+                    PathBuf::from(format!("/repos/{repository}")),
+                    PathBuf::from(format!("/tmp/tasks/{slug}/{repository}")),
+                    slug.to_string(),
+                    AgentType::Codex,
+                    WorkspaceStatus::Idle,
+                )
+                .expect("worktree should be valid")
+            })
+            .collect();
+        Task::try_new(
+            slug.to_string(),
+            slug.to_string(),
+            PathBuf::from(format!("/tmp/tasks/{slug}")),
+            slug.to_string(),
+            worktrees,
+        )
+        .expect("task should be valid")
+    }
+
+    #[test]
+    fn resolve_agent_targets_reads_task_and_repository() {
+        let tasks = vec![task_fixture("flaky", &["api", "web"])];
+
+        let targets = resolve_agent_targets(&tasks, "flaky").expect("task should resolve");
+        assert_eq!(targets.len(), 2);
+
+        let target = resolve_single_agent_target(&tasks, "flaky/web").expect("repo resolves");
+        assert_eq!(target.label, "flaky/web");
+        assert_eq!(target.workspace.path, PathBuf::from("/tmp/tasks/flaky/web"));
+        assert_eq!(target.workspace.task_slug.as_deref(), Some("flaky"));
+    }
+
+    #[test]
+    fn resolve_single_agent_target_rejects_ambiguous_and_unknown_targets() {
+        let tasks = vec![task_fixture("flaky", &["api", "web"])];
+
+        assert!(matches!(
+            resolve_single_agent_target(&tasks, "flaky"),
+            Err(AgentTargetError::AmbiguousRepository { .. })
+        ));
+        assert_eq!(
+            resolve_single_agent_target(&tasks, "missing"),
+            Err(AgentTargetError::UnknownTask("missing".to_string()))
+        );
+        assert!(matches!(
+            resolve_single_agent_target(&tasks, "flaky/docs"),
+            Err(AgentTargetError::UnknownRepository { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_agent_env_vars_accepts_separators_and_rejects_bad_keys() {
+        assert_eq!(
+            parse_agent_env_vars("A=1; B_2=two\nC=3"),
+            Ok(vec![
+                ("A".to_string(), "1".to_string()),
+                ("B_2".to_string(), "two".to_string()),
+                ("C".to_string(), "3".to_string()),
+            ])
+        );
+        assert!(parse_agent_env_vars("1BAD=x").is_err());
+        assert!(parse_agent_env_vars("EMPTY=").is_err());
+        assert!(parse_agent_env_vars("missing-equals").is_err());
+    }
+
+    #[test]
+    fn send_commands_paste_then_submit() {
+        let buffer_name = send_buffer_name("grove-wt-flaky-api", 4242);
+        assert_eq!(buffer_name, "grove-grove-wt-flaky-api-4242");
+        assert_eq!(
+            send_commands("grove-wt-flaky-api", buffer_name.as_str(), true),
+            vec![
+                vec![
+                    "tmux",
+                    "paste-buffer",
+                    "-b",
+                    "grove-grove-wt-flaky-api-4242",
+                    "-d",
+                    "-p",
+                    "-t",
+                    "grove-wt-flaky-api"
+                ],
+                vec!["tmux", "send-keys", "-t", "grove-wt-flaky-api", "Enter"],
+            ]
+        );
+        assert_eq!(
+            send_commands("grove-wt-flaky-api", buffer_name.as_str(), false).len(),
+            1
+        );
+    }
+}
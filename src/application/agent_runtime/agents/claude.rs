@@ -1,12 +1,241 @@
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+use crate::application::agent_runtime::status_hooks::{
+    AgentHookEventKind, HookPayloadSource, STATUS_EVENT_FILE, hook_event_command,
+};
+use crate::application::agent_runtime::usage::{
+    TokenUsage, UNKNOWN_MODEL, UsageLineState, UsageSample, parse_timestamp_ms,
+};
 use crate::domain::{PermissionMode, WorkspaceStatus};
 
-use super::shared;
+use super::{AgentDescriptor, shared};
+use crate::application::agent_runtime::RestartExitInput;
+
+pub(super) struct ClaudeDescriptor;
+
+impl AgentDescriptor for ClaudeDescriptor {
+    fn launch_command(&self, permission_mode: PermissionMode) -> String {
+        match permission_mode {
+            PermissionMode::Unsafe => "claude --dangerously-skip-permissions".to_string(),
+            PermissionMode::Auto => "claude --enable-auto-mode".to_string(),
+            PermissionMode::Default => "claude".to_string(),
+        }
+    }
+
+    fn resume_command_with_permission_mode(
+        &self,
+        command: &str,
+        permission_mode: PermissionMode,
+    ) -> String {
+        match permission_mode {
+            PermissionMode::Default => command.to_string(),
+            PermissionMode::Auto => {
+                super::command_with_program_flag(command, "claude", "--enable-auto-mode")
+            }
+            PermissionMode::Unsafe => super::command_with_program_flag(
+                command,
+                "claude",
+                "--dangerously-skip-permissions",
+            ),
+        }
+    }
+
+    fn extract_resume_command(&self, output: &str) -> Option<String> {
+        extract_resume_command(output)
+    }
+
+    fn restart_exit_input(&self) -> Option<RestartExitInput> {
+        Some(RestartExitInput::Literal("/exit".to_string()))
+    }
+
+    fn infer_permission_mode_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<PermissionMode> {
+        infer_permission_mode_in_home(workspace_path, home_dir)
+    }
+
+    fn status_observation_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatusObservation> {
+        status_observation_in_home(workspace_path, home_dir, activity_threshold)
+    }
+
+    fn detect_session_status_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatus> {
+        detect_session_status_in_home(workspace_path, home_dir, activity_threshold)
+    }
+
+    fn latest_attention_marker_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<String> {
+        latest_attention_marker_in_home(workspace_path, home_dir)
+    }
+
+    fn session_log_file_in_home(&self, workspace_path: &Path, home_dir: &Path) -> Option<PathBuf> {
+        let workspace_path = shared::absolute_path(workspace_path)?;
+        let project_dir = home_dir
+            .join(".claude")
+            .join("projects")
+            .join(project_dir_name(&workspace_path));
+        shared::find_recent_jsonl_files(&project_dir, Some("agent-"))?
+            .into_iter()
+            .next()
+    }
+
+    fn usage_session_files_in_home(&self, workspace_path: &Path, home_dir: &Path) -> Vec<PathBuf> {
+        usage_session_files_in_home(workspace_path, home_dir)
+    }
+
+    fn parse_usage_line(&self, line: &str, state: &mut UsageLineState) -> Option<UsageSample> {
+        parse_usage_line(line, state)
+    }
+
+    fn install_status_hooks(&self, workspace_path: &Path, event_file: &Path) -> Result<(), String> {
+        install_status_hooks(workspace_path, event_file)
+    }
+
+    fn uninstall_status_hooks(&self, workspace_path: &Path) -> Result<(), String> {
+        uninstall_status_hooks(workspace_path)
+    }
+}
+
+const SETTINGS_LOCAL_FILE: &str = ".claude/settings.local.json";
+const STATUS_HOOK_EVENTS: [(&str, AgentHookEventKind); 5] = [
+    ("UserPromptSubmit", AgentHookEventKind::Working),
+    ("PreToolUse", AgentHookEventKind::Working),
+    ("PostToolUse", AgentHookEventKind::Working),
+    ("Notification", AgentHookEventKind::Notification),
+    ("Stop", AgentHookEventKind::TurnComplete),
+];
+
+/// Merges Grove's status hooks into the worktree's local settings, keeping
+/// every hook the user configured.
+fn install_status_hooks(workspace_path: &Path, event_file: &Path) -> Result<(), String> {
+    let path = workspace_path.join(SETTINGS_LOCAL_FILE);
+    let settings = read_local_settings(path.as_path())?.unwrap_or_else(|| json!({}));
+    let updated = settings_with_status_hooks(settings.clone(), event_file)
+        .map_err(|error| format!("{}: {error}", path.display()))?;
+    if updated == settings {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("create {} failed: {error}", parent.display()))?;
+    }
+    let encoded = serde_json::to_string_pretty(&updated).map_err(|error| error.to_string())?;
+    fs::write(&path, format!("{encoded}\n"))
+        .map_err(|error| format!("write {} failed: {error}", path.display()))
+}
+
+/// Takes Grove's status hooks back out of the worktree's local settings,
+/// deleting the file when nothing else is left in it.
+fn uninstall_status_hooks(workspace_path: &Path) -> Result<(), String> {
+    let path = workspace_path.join(SETTINGS_LOCAL_FILE);
+    let Some(settings) = read_local_settings(path.as_path())? else {
+        return Ok(());
+    };
+    let updated = settings_without_status_hooks(settings.clone());
+    if updated == settings {
+        return Ok(());
+    }
+
+    if updated == json!({}) {
+        return fs::remove_file(&path)
+            .map_err(|error| format!("remove {} failed: {error}", path.display()));
+    }
+    let encoded = serde_json::to_string_pretty(&updated).map_err(|error| error.to_string())?;
+    fs::write(&path, format!("{encoded}\n"))
+        .map_err(|error| format!("write {} failed: {error}", path.display()))
+}
+
+fn read_local_settings(path: &Path) -> Result<Option<Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str::<Value>(raw.as_str())
+            .map(Some)
+            .map_err(|error| format!("{} is not valid JSON: {error}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(format!("read {} failed: {error}", path.display())),
+    }
+}
+
+fn settings_without_status_hooks(mut settings: Value) -> Value {
+    let Some(hooks) = settings.get_mut("hooks").and_then(Value::as_object_mut) else {
+        return settings;
+    };
+    for (event_name, _) in STATUS_HOOK_EVENTS {
+        let Some(matchers) = hooks.get_mut(event_name).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        matchers.retain(|matcher| !is_grove_status_matcher(matcher));
+        if matchers.is_empty() {
+            hooks.remove(event_name);
+        }
+    }
+    if hooks.is_empty()
+        && let Some(settings) = settings.as_object_mut()
+    {
+        settings.remove("hooks");
+    }
+    settings
+}
+
+fn settings_with_status_hooks(mut settings: Value, event_file: &Path) -> Result<Value, String> {
+    let hooks = settings
+        .as_object_mut()
+        .ok_or_else(|| "settings are not a JSON object".to_string())?
+        .entry("hooks")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "`hooks` is not a JSON object".to_string())?;
+
+    for (event_name, kind) in STATUS_HOOK_EVENTS {
+        let matchers = hooks
+            .entry(event_name)
+            .or_insert_with(|| json!([]))
+            .as_array_mut()
+            .ok_or_else(|| format!("`hooks.{event_name}` is not a JSON array"))?;
+        // Replaces the entry of an earlier install, which may name another path.
+        matchers.retain(|matcher| !is_grove_status_matcher(matcher));
+        let command = hook_event_command(event_file, kind, HookPayloadSource::Stdin);
+        let mut matcher = json!({ "hooks": [{ "type": "command", "command": command }] });
+        if matches!(event_name, "PreToolUse" | "PostToolUse") {
+            matcher["matcher"] = json!("*");
+        }
+        matchers.push(matcher);
+    }
+
+    Ok(settings)
+}
+
+fn is_grove_status_matcher(matcher: &Value) -> bool {
+    matcher
+        .get("hooks")
+        .and_then(Value::as_array)
+        .is_some_and(|hooks| {
+            hooks.iter().any(|hook| {
+                hook.get("command")
+                    .and_then(Value::as_str)
+                    .is_some_and(|command| command.contains(STATUS_EVENT_FILE))
+            })
+        })
+}
 
 pub(super) fn extract_resume_command(output: &str) -> Option<String> {
     let mut found = None;
@@ -90,6 +319,7 @@ pub(super) fn status_observation_in_home(
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             });
         }
 
@@ -131,6 +361,76 @@ pub(super) fn latest_attention_marker_in_home(
     None
 }
 
+/// Main session logs plus the sidechain and subagent logs, which bill tokens too.
+fn usage_session_files_in_home(workspace_path: &Path, home_dir: &Path) -> Vec<PathBuf> {
+    let Some(workspace_path) = shared::absolute_path(workspace_path) else {
+        return Vec::new();
+    };
+    let project_dir = home_dir
+        .join(".claude")
+        .join("projects")
+        .join(project_dir_name(&workspace_path));
+    let mut files = shared::find_recent_jsonl_files(&project_dir, None).unwrap_or_default();
+    let subagent_files = files
+        .iter()
+        .filter_map(|session_file| session_file.file_stem())
+        .flat_map(|session_stem| {
+            shared::find_recent_jsonl_files(&project_dir.join(session_stem).join("subagents"), None)
+                .unwrap_or_default()
+        })
+        .collect::<Vec<PathBuf>>();
+    files.extend(subagent_files);
+    files
+}
+
+fn parse_usage_line(line: &str, state: &mut UsageLineState) -> Option<UsageSample> {
+    if !line.contains("\"usage\"") {
+        return None;
+    }
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    if value.get("type").and_then(Value::as_str) != Some("assistant") {
+        return None;
+    }
+    let message = value.get("message")?;
+    let usage = message.get("usage")?;
+    let count = |field: &str| usage.get(field).and_then(Value::as_u64).unwrap_or(0);
+    let tokens = TokenUsage {
+        input_tokens: count("input_tokens"),
+        output_tokens: count("output_tokens"),
+        cache_read_tokens: count("cache_read_input_tokens"),
+        cache_write_tokens: count("cache_creation_input_tokens"),
+    };
+    if tokens.is_empty() {
+        return None;
+    }
+    // The usage is repeated on every content block line of a response.
+    let response_id = message.get("id").and_then(Value::as_str).map(|message_id| {
+        let request_id = value
+            .get("requestId")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        format!("{message_id}:{request_id}")
+    });
+    if let Some(response_id) = response_id.as_ref()
+        && !state.seen_responses.insert(response_id.clone())
+    {
+        return None;
+    }
+    Some(UsageSample {
+        response_id,
+        model: message
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or(UNKNOWN_MODEL)
+            .to_string(),
+        ts_ms: value
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(parse_timestamp_ms),
+        tokens,
+    })
+}
+
 pub(crate) fn project_dir_name(abs_path: &Path) -> String {
     abs_path
         .to_string_lossy()
@@ -164,6 +464,7 @@ fn last_message_observation(path: &Path) -> Option<WorkspaceStatusObservation> {
                 status: WorkspaceStatus::Active,
                 recent_activity: false,
                 waiting_excerpt: None,
+                waiting_attention: None,
             });
         }
         if message_type == "assistant" {
@@ -171,6 +472,7 @@ fn last_message_observation(path: &Path) -> Option<WorkspaceStatusObservation> {
                 status: WorkspaceStatus::Waiting,
                 recent_activity: false,
                 waiting_excerpt: shared::best_effort_excerpt_from_json_value(&value),
+                waiting_attention: None,
             });
         }
     }
@@ -184,9 +486,103 @@ mod tests {
     use std::time::Duration;
 
     use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+    use crate::application::agent_runtime::usage::UsageLineState;
     use crate::test_support::unique_test_dir;
 
-    use super::{project_dir_name, status_observation_in_home};
+    use super::{
+        SETTINGS_LOCAL_FILE, install_status_hooks, parse_usage_line, project_dir_name,
+        status_observation_in_home, uninstall_status_hooks,
+    };
+
+    #[test]
+    fn usage_lines_sharing_a_message_id_count_once() {
+        let line = |text: &str| {
+            format!(
+                r#"{{"type":"assistant","requestId":"req-1","message":{{"id":"msg-1","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"{text}"}}],"usage":{{"input_tokens":10,"output_tokens":5}}}}}}"#
+            )
+        };
+        let mut state = UsageLineState::default();
+
+        let first = parse_usage_line(&line("thinking"), &mut state).expect("first block counts");
+        assert_eq!(first.tokens.output_tokens, 5);
+        assert!(parse_usage_line(&line("answer"), &mut state).is_none());
+    }
+
+    #[test]
+    fn status_hooks_merge_into_local_settings_and_replace_earlier_installs() {
+        let root = unique_test_dir("claude-status-hooks");
+        let settings_path = root.join(SETTINGS_LOCAL_FILE);
+        fs::create_dir_all(settings_path.parent().expect("settings parent"))
+            .expect("claude dir should exist");
+        fs::write(
+            &settings_path,
+            r#"{"permissions":{"allow":["Bash(cargo test)"]},"hooks":{"Stop":[{"hooks":[{"type":"command","command":"say done"}]}]}}"#,
+        )
+        .expect("settings should write");
+        let event_file = root.join(".grove/agent-event");
+
+        install_status_hooks(&root, &event_file).expect("hooks should install");
+        install_status_hooks(&root, &event_file).expect("reinstall should succeed");
+
+        let settings: serde_json::Value = serde_json::from_str(
+            fs::read_to_string(&settings_path)
+                .expect("settings should read")
+                .as_str(),
+        )
+        .expect("settings should stay valid JSON");
+        assert_eq!(settings["permissions"]["allow"][0], "Bash(cargo test)");
+        let stop = settings["hooks"]["Stop"]
+            .as_array()
+            .expect("stop hooks should be an array");
+        assert_eq!(stop.len(), 2);
+        assert_eq!(stop[0]["hooks"][0]["command"], "say done");
+        assert!(
+            stop[1]["hooks"][0]["command"]
+                .as_str()
+                .is_some_and(|command| command.contains("turn_complete"))
+        );
+        assert_eq!(settings["hooks"]["PreToolUse"][0]["matcher"], "*");
+        assert_eq!(
+            settings["hooks"]["Notification"].as_array().map(Vec::len),
+            Some(1)
+        );
+
+        fs::write(&settings_path, "not json").expect("settings should write");
+        assert!(install_status_hooks(&root, &event_file).is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn uninstalling_status_hooks_restores_user_settings() {
+        let root = unique_test_dir("claude-status-hooks-uninstall");
+        let settings_path = root.join(SETTINGS_LOCAL_FILE);
+        fs::create_dir_all(settings_path.parent().expect("settings parent"))
+            .expect("claude dir should exist");
+        let user_settings = serde_json::json!({
+            "permissions": {"allow": ["Bash(cargo test)"]},
+            "hooks": {"Stop": [{"hooks": [{"type": "command", "command": "say done"}]}]},
+        });
+        fs::write(&settings_path, user_settings.to_string()).expect("settings should write");
+        let event_file = root.join(".grove/agent-event");
+
+        install_status_hooks(&root, &event_file).expect("hooks should install");
+        uninstall_status_hooks(&root).expect("hooks should uninstall");
+        let settings: serde_json::Value = serde_json::from_str(
+            fs::read_to_string(&settings_path)
+                .expect("settings should read")
+                .as_str(),
+        )
+        .expect("settings should stay valid JSON");
+        assert_eq!(settings, user_settings);
+
+        fs::remove_file(&settings_path).expect("settings should delete");
+        install_status_hooks(&root, &event_file).expect("hooks should install");
+        uninstall_status_hooks(&root).expect("hooks should uninstall");
+        assert!(!settings_path.exists());
+        uninstall_status_hooks(&root).expect("uninstall without settings is a no-op");
+
+        let _ = fs::remove_dir_all(root);
+    }
 
     #[test]
     fn session_signal_claude_extracts_waiting_excerpt_from_message_content() {
@@ -214,6 +610,7 @@ mod tests {
                 status: crate::domain::WorkspaceStatus::Waiting,
                 recent_activity: false,
                 waiting_excerpt: Some("approve command".to_string()),
+                waiting_attention: None,
             })
         );
     }
@@ -244,6 +641,7 @@ mod tests {
                 status: crate::domain::WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             })
         );
     }
@@ -9,10 +9,18 @@ use std::time::{Duration, Instant, SystemTime};
 
 use serde::Deserialize;
 
+use crate::application::agent_runtime::launch_plan::shell_quote;
 use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+use crate::application::agent_runtime::status_hooks::{
+    AgentHookEventKind, HookPayloadSource, hook_event_command,
+};
+use crate::application::agent_runtime::usage::{
+    TokenUsage, UNKNOWN_MODEL, UsageLineState, UsageSample, parse_timestamp_ms,
+};
 use crate::domain::{PermissionMode, WorkspaceStatus};
 
-use super::shared;
+use super::{AgentDescriptor, shared};
+use crate::application::agent_runtime::RestartExitInput;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct SessionLookupKey {
@@ -69,6 +77,110 @@ struct ResponsePayload<'a> {
     role: Option<&'a str>,
 }
 
+pub(super) struct CodexDescriptor;
+
+impl AgentDescriptor for CodexDescriptor {
+    fn launch_command(&self, permission_mode: PermissionMode) -> String {
+        match permission_mode {
+            PermissionMode::Unsafe => {
+                "codex --dangerously-bypass-approvals-and-sandbox".to_string()
+            }
+            PermissionMode::Default | PermissionMode::Auto => "codex".to_string(),
+        }
+    }
+
+    fn resume_command_with_permission_mode(
+        &self,
+        command: &str,
+        permission_mode: PermissionMode,
+    ) -> String {
+        match permission_mode {
+            PermissionMode::Default | PermissionMode::Auto => command.to_string(),
+            PermissionMode::Unsafe => super::command_with_program_flag(
+                command,
+                "codex",
+                "--dangerously-bypass-approvals-and-sandbox",
+            ),
+        }
+    }
+
+    fn extract_resume_command(&self, output: &str) -> Option<String> {
+        extract_resume_command(output)
+    }
+
+    fn restart_exit_input(&self) -> Option<RestartExitInput> {
+        Some(RestartExitInput::Named("C-c".to_string()))
+    }
+
+    fn infer_permission_mode_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<PermissionMode> {
+        infer_permission_mode_in_home(workspace_path, home_dir)
+    }
+
+    fn status_observation_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatusObservation> {
+        status_observation_in_home(workspace_path, home_dir, activity_threshold)
+    }
+
+    fn detect_session_status_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatus> {
+        detect_session_status_in_home(workspace_path, home_dir, activity_threshold)
+    }
+
+    fn latest_attention_marker_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<String> {
+        latest_attention_marker_in_home(workspace_path, home_dir)
+    }
+
+    fn session_log_file_in_home(&self, workspace_path: &Path, home_dir: &Path) -> Option<PathBuf> {
+        find_session_for_path_cached(&home_dir.join(".codex").join("sessions"), workspace_path)
+    }
+
+    fn usage_session_files_in_home(&self, workspace_path: &Path, home_dir: &Path) -> Vec<PathBuf> {
+        usage_session_files_in_home(workspace_path, home_dir)
+    }
+
+    fn parse_usage_line(&self, line: &str, state: &mut UsageLineState) -> Option<UsageSample> {
+        parse_usage_line(line, state)
+    }
+
+    fn command_with_status_hooks(&self, command: &str, event_file: &Path) -> String {
+        super::command_with_program_flag(command, "codex", notify_flag(event_file).as_str())
+    }
+}
+
+/// `-c notify=[...]`; Codex runs the program after every turn with the
+/// event JSON appended as the last argument.
+fn notify_flag(event_file: &Path) -> String {
+    let script = hook_event_command(
+        event_file,
+        AgentHookEventKind::TurnComplete,
+        HookPayloadSource::FirstArgument,
+    );
+    let toml_string =
+        |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let notify = ["sh", "-c", script.as_str(), "grove-status"]
+        .iter()
+        .map(|argument| toml_string(argument))
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("-c {}", shell_quote(format!("notify=[{notify}]").as_str()))
+}
+
 fn session_lookup_cache() -> &'static Mutex<HashMap<SessionLookupKey, SessionLookupCacheEntry>> {
     static CACHE: OnceLock<Mutex<HashMap<SessionLookupKey, SessionLookupCacheEntry>>> =
         OnceLock::new();
@@ -149,6 +261,7 @@ pub(super) fn status_observation_in_home(
             status: WorkspaceStatus::Active,
             recent_activity: true,
             waiting_excerpt: None,
+            waiting_attention: None,
         });
     }
 
@@ -267,9 +380,25 @@ fn cache_test_guard() -> MutexGuard<'static, ()> {
 }
 
 fn find_session_for_path(sessions_dir: &Path, workspace_path: &Path) -> Option<PathBuf> {
+    session_files_for_path(sessions_dir, workspace_path)
+        .into_iter()
+        .reduce(|best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map(|(path, _)| path)
+}
+
+/// Every session log under `sessions_dir` started in `workspace_path`, with its modification time.
+fn session_files_for_path(
+    sessions_dir: &Path,
+    workspace_path: &Path,
+) -> Vec<(PathBuf, SystemTime)> {
     let mut pending = vec![sessions_dir.to_path_buf()];
-    let mut best_path: Option<PathBuf> = None;
-    let mut best_time: Option<SystemTime> = None;
+    let mut matches = Vec::new();
 
     while let Some(dir) = pending.pop() {
         let entries = match fs::read_dir(dir) {
@@ -309,18 +438,75 @@ fn find_session_for_path(sessions_dir: &Path, workspace_path: &Path) -> Option<P
             if !shared::cwd_matches(&cwd, workspace_path) {
                 continue;
             }
-            let replace = match best_time {
-                Some(current_best) => modified > current_best,
-                None => true,
+            matches.push((path, modified));
+        }
+    }
+
+    matches
+}
+
+fn usage_session_files_in_home(workspace_path: &Path, home_dir: &Path) -> Vec<PathBuf> {
+    let Some(workspace_path) = shared::absolute_path(workspace_path) else {
+        return Vec::new();
+    };
+    let sessions_dir = home_dir.join(".codex").join("sessions");
+    session_files_for_path(&sessions_dir, &workspace_path)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Codex logs running session totals, so each `token_count` event is turned into the delta since the previous one.
+fn parse_usage_line(line: &str, state: &mut UsageLineState) -> Option<UsageSample> {
+    if !line.contains("token_count") && !line.contains("turn_context") {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    let payload = value.get("payload")?;
+    match value.get("type").and_then(serde_json::Value::as_str)? {
+        "turn_context" => {
+            if let Some(model) = payload.get("model").and_then(serde_json::Value::as_str) {
+                state.model = Some(model.to_string());
+            }
+            None
+        }
+        "event_msg"
+            if payload.get("type").and_then(serde_json::Value::as_str) == Some("token_count") =>
+        {
+            let total = payload.get("info")?.get("total_token_usage")?;
+            let count = |field: &str| {
+                total
+                    .get(field)
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(0)
+            };
+            let cached_input_tokens = count("cached_input_tokens");
+            let cumulative = TokenUsage {
+                input_tokens: count("input_tokens").saturating_sub(cached_input_tokens),
+                output_tokens: count("output_tokens"),
+                cache_read_tokens: cached_input_tokens,
+                cache_write_tokens: 0,
             };
-            if replace {
-                best_time = Some(modified);
-                best_path = Some(path);
+            let tokens = cumulative.saturating_sub(state.cumulative);
+            state.cumulative = cumulative;
+            if tokens.is_empty() {
+                return None;
             }
+            Some(UsageSample {
+                response_id: None,
+                model: state
+                    .model
+                    .clone()
+                    .unwrap_or_else(|| UNKNOWN_MODEL.to_string()),
+                ts_ms: value
+                    .get("timestamp")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(parse_timestamp_ms),
+                tokens,
+            })
         }
+        _ => None,
     }
-
-    best_path
 }
 
 fn get_session_cwd_cached(path: &Path, modified_at: SystemTime) -> Option<PathBuf> {
@@ -379,6 +565,7 @@ fn get_last_message_observation(path: &Path) -> Option<WorkspaceStatusObservatio
                     status: WorkspaceStatus::Waiting,
                     recent_activity: false,
                     waiting_excerpt: excerpt,
+                    waiting_attention: None,
                 });
             }
             Some((WorkspaceStatus::Active, _)) => {
@@ -386,6 +573,7 @@ fn get_last_message_observation(path: &Path) -> Option<WorkspaceStatusObservatio
                     status: WorkspaceStatus::Active,
                     recent_activity: false,
                     waiting_excerpt: None,
+                    waiting_attention: None,
                 });
             }
             _ => continue,
@@ -474,6 +662,49 @@ mod tests {
         fs::write(path, line).expect("session file should be written");
     }
 
+    #[test]
+    fn status_hook_notify_flag_reports_turn_complete_through_shell_and_toml() {
+        let root = unique_test_dir("codex-status-notify");
+        fs::create_dir_all(root.join(".grove")).expect("grove dir should exist");
+        let event_file = root.join(".grove/agent-event");
+
+        let command = CodexDescriptor.command_with_status_hooks("codex", &event_file);
+        let quoted = command
+            .strip_prefix("codex -c ")
+            .expect("notify flag should follow the program");
+        let output = std::process::Command::new("sh")
+            .args(["-c", format!("printf '%s' {quoted}").as_str()])
+            .output()
+            .expect("sh should run");
+        let config: toml::Table = toml::from_str(
+            String::from_utf8(output.stdout)
+                .expect("config should be utf-8")
+                .as_str(),
+        )
+        .expect("notify override should be valid TOML");
+        let notify = config["notify"]
+            .as_array()
+            .expect("notify should be an array")
+            .iter()
+            .map(|value| value.as_str().expect("argument should be a string"))
+            .collect::<Vec<&str>>();
+        let status = std::process::Command::new(notify[0])
+            .args(&notify[1..])
+            .arg(r#"{"type":"agent-turn-complete","last-assistant-message":"Done."}"#)
+            .status()
+            .expect("notify program should run");
+        assert!(status.success());
+
+        let event = fs::read_to_string(&event_file).expect("event should be written");
+        assert!(event.starts_with("turn_complete "));
+        assert!(event.contains("\"last-assistant-message\":\"Done.\""));
+        assert_eq!(
+            CodexDescriptor.command_with_status_hooks(command.as_str(), &event_file),
+            command
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
     fn write_waiting_message(path: &Path) {
         fs::write(
             path,
@@ -672,6 +903,7 @@ mod tests {
                     status: WorkspaceStatus::Waiting,
                     recent_activity: false,
                     waiting_excerpt: Some("approve command".to_string()),
+                    waiting_attention: None,
                 }
             )
         );
@@ -705,8 +937,82 @@ mod tests {
                     status: WorkspaceStatus::Active,
                     recent_activity: true,
                     waiting_excerpt: None,
+                    waiting_attention: None,
                 }
             )
         );
     }
+
+    #[test]
+    fn usage_lines_turn_running_totals_into_deltas() {
+        let mut state = UsageLineState::default();
+        assert_eq!(
+            parse_usage_line(
+                "{\"type\":\"turn_context\",\"payload\":{\"model\":\"gpt-5-codex\"}}",
+                &mut state,
+            ),
+            None
+        );
+
+        let first = parse_usage_line(
+            "{\"timestamp\":\"2026-03-01T10:00:00.000Z\",\"type\":\"event_msg\",\"payload\":{\"type\":\"token_count\",\"info\":{\"total_token_usage\":{\"input_tokens\":1000,\"cached_input_tokens\":400,\"output_tokens\":50}}}}",
+            &mut state,
+        )
+        .expect("first token count should produce a sample");
+        assert_eq!(first.model, "gpt-5-codex");
+        assert_eq!(
+            first.tokens,
+            TokenUsage {
+                input_tokens: 600,
+                output_tokens: 50,
+                cache_read_tokens: 400,
+                cache_write_tokens: 0,
+            }
+        );
+        assert!(first.ts_ms.is_some());
+
+        let repeated = "{\"type\":\"event_msg\",\"payload\":{\"type\":\"token_count\",\"info\":{\"total_token_usage\":{\"input_tokens\":1000,\"cached_input_tokens\":400,\"output_tokens\":50}}}}";
+        assert_eq!(parse_usage_line(repeated, &mut state), None);
+
+        let second = parse_usage_line(
+            "{\"type\":\"event_msg\",\"payload\":{\"type\":\"token_count\",\"info\":{\"total_token_usage\":{\"input_tokens\":1500,\"cached_input_tokens\":900,\"output_tokens\":80}}}}",
+            &mut state,
+        )
+        .expect("second token count should produce a sample");
+        assert_eq!(
+            second.tokens,
+            TokenUsage {
+                input_tokens: 0,
+                output_tokens: 30,
+                cache_read_tokens: 500,
+                cache_write_tokens: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn usage_session_files_include_every_matching_session() {
+        reset_caches_for_test();
+        let root = unique_test_dir("codex-usage-files");
+        let home = root.join("home");
+        let workspace_path = root.join("ws").join("feature-usage");
+        let other_path = root.join("ws").join("other");
+        let sessions_dir = home.join(".codex").join("sessions").join("2026").join("03");
+        fs::create_dir_all(&workspace_path).expect("workspace should exist");
+        fs::create_dir_all(&sessions_dir).expect("sessions directory should exist");
+
+        write_codex_session_file(&sessions_dir.join("rollout-1.jsonl"), &workspace_path);
+        write_codex_session_file(&sessions_dir.join("rollout-2.jsonl"), &workspace_path);
+        write_codex_session_file(&sessions_dir.join("rollout-3.jsonl"), &other_path);
+
+        let mut files = usage_session_files_in_home(&workspace_path, &home);
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                sessions_dir.join("rollout-1.jsonl"),
+                sessions_dir.join("rollout-2.jsonl"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,412 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::application::agent_runtime::RestartExitInput;
+use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+use crate::domain::{
+    AgentRegistry, CustomAgent, CustomAgentProfile, CustomAgentSessionLog, PermissionMode,
+    WorkspaceStatus,
+};
+use crate::infrastructure::config::AgentDefinitionConfig;
+
+use super::{AgentDescriptor, shared};
+
+/// The built-in agents plus the config-defined ones, which launch, restart
+/// and report status like the built-ins.
+pub fn build_agent_registry(
+    definitions: &[AgentDefinitionConfig],
+) -> Result<AgentRegistry, String> {
+    let custom = definitions
+        .iter()
+        .map(|definition| profile_from_definition(definition).map(CustomAgent::new))
+        .collect::<Result<Vec<_>, _>>()?;
+    AgentRegistry::new(custom)
+}
+
+pub(super) fn profile_from_definition(
+    definition: &AgentDefinitionConfig,
+) -> Result<CustomAgentProfile, String> {
+    let id = definition.id.trim();
+    let Some(command) = trimmed(definition.command.as_str()) else {
+        return Err(format!("agent '{id}' is missing a command"));
+    };
+    let resume_pattern = definition.resume_pattern.as_deref().and_then(trimmed);
+    if let Some(pattern) = resume_pattern.as_deref() {
+        Regex::new(pattern)
+            .map_err(|error| format!("agent '{id}' resume_pattern is invalid: {error}"))?;
+    }
+
+    Ok(CustomAgentProfile {
+        marker: id.to_string(),
+        label: definition.label.trim().to_string(),
+        command,
+        auto_flag: definition
+            .permission_flags
+            .auto
+            .as_deref()
+            .and_then(trimmed),
+        unsafe_flag: definition
+            .permission_flags
+            .unsafe_mode
+            .as_deref()
+            .and_then(trimmed),
+        resume_pattern,
+        resume_command: definition.resume_command.as_deref().and_then(trimmed),
+        exit_input: definition.exit_input.as_deref().and_then(trimmed),
+        exit_key: definition.exit_key.as_deref().and_then(trimmed),
+        session_log: definition
+            .session_log
+            .as_ref()
+            .map(|session_log| CustomAgentSessionLog {
+                dir: session_log.dir.clone(),
+                role_field: session_log.role_field.clone(),
+                user_value: session_log.user_value.clone(),
+                assistant_value: session_log.assistant_value.clone(),
+            }),
+    })
+}
+
+/// Fails for an agent the config no longer defines.
+pub(super) fn ensure_defined(profile: &CustomAgentProfile) -> Result<(), String> {
+    if profile.command.is_empty() {
+        return Err(format!(
+            "agent '{}' is not defined in config",
+            profile.marker
+        ));
+    }
+    Ok(())
+}
+
+fn program(profile: &CustomAgentProfile) -> &str {
+    profile
+        .command
+        .split_whitespace()
+        .next()
+        .unwrap_or(profile.command.as_str())
+}
+
+fn permission_flag(profile: &CustomAgentProfile, permission_mode: PermissionMode) -> Option<&str> {
+    match permission_mode {
+        PermissionMode::Default => None,
+        PermissionMode::Auto => profile.auto_flag.as_deref(),
+        PermissionMode::Unsafe => profile.unsafe_flag.as_deref(),
+    }
+}
+
+fn session_file(
+    profile: &CustomAgentProfile,
+    workspace_path: &Path,
+    home_dir: &Path,
+) -> Option<PathBuf> {
+    let session_log = profile.session_log.as_ref()?;
+    let dir = session_log_dir(session_log.dir.as_str(), workspace_path, home_dir)?;
+    shared::find_recent_jsonl_files(&dir, None)?
+        .into_iter()
+        .next()
+}
+
+impl AgentDescriptor for CustomAgentProfile {
+    fn launch_command(&self, permission_mode: PermissionMode) -> String {
+        match permission_flag(self, permission_mode) {
+            Some(flag) => format!("{} {flag}", self.command),
+            None => self.command.clone(),
+        }
+    }
+
+    fn resume_command_with_permission_mode(
+        &self,
+        command: &str,
+        permission_mode: PermissionMode,
+    ) -> String {
+        match permission_flag(self, permission_mode) {
+            Some(flag) => super::command_with_program_flag(command, program(self), flag),
+            None => command.to_string(),
+        }
+    }
+
+    fn extract_resume_command(&self, output: &str) -> Option<String> {
+        let Some(pattern) = self.resume_pattern.as_deref() else {
+            // Without a resume pattern a restart relaunches the agent fresh.
+            return Some(self.command.clone());
+        };
+        let pattern = Regex::new(pattern).ok()?;
+
+        let captures = pattern.captures_iter(output).last()?;
+        let session = captures
+            .name("session")
+            .or_else(|| captures.get(1))
+            .and_then(|session| super::normalize_resume_session_id(session.as_str()));
+        match (self.resume_command.as_deref(), session) {
+            (Some(template), Some(session)) => Some(template.replace("{session}", &session)),
+            (Some(_), None) => None,
+            (None, _) => trimmed(captures.get(0)?.as_str()),
+        }
+    }
+
+    fn restart_exit_input(&self) -> Option<RestartExitInput> {
+        if let Some(text) = self.exit_input.as_ref() {
+            return Some(RestartExitInput::Literal(text.clone()));
+        }
+
+        self.exit_key.clone().map(RestartExitInput::Named)
+    }
+
+    fn infer_permission_mode_in_home(
+        &self,
+        _workspace_path: &Path,
+        _home_dir: &Path,
+    ) -> Option<PermissionMode> {
+        None
+    }
+
+    fn status_observation_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatusObservation> {
+        let session_log = self.session_log.as_ref()?;
+        let session_file = session_file(self, workspace_path, home_dir)?;
+        if shared::is_file_recently_modified(&session_file, activity_threshold) {
+            return Some(WorkspaceStatusObservation {
+                status: WorkspaceStatus::Active,
+                recent_activity: true,
+                waiting_excerpt: None,
+                waiting_attention: None,
+            });
+        }
+
+        last_message_observation(&session_file, session_log)
+    }
+
+    fn latest_attention_marker_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<String> {
+        let session_log = self.session_log.as_ref()?;
+        let session_file = session_file(self, workspace_path, home_dir)?;
+        let (is_assistant, marker) = shared::get_last_message_marker_jsonl(
+            &session_file,
+            session_log.role_field.as_str(),
+            session_log.user_value.as_str(),
+            session_log.assistant_value.as_str(),
+            super::super::SESSION_STATUS_TAIL_BYTES,
+        )?;
+        is_assistant.then_some(marker)
+    }
+
+    fn session_log_file_in_home(&self, workspace_path: &Path, home_dir: &Path) -> Option<PathBuf> {
+        session_file(self, workspace_path, home_dir)
+    }
+}
+
+fn trimmed(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(trimmed.to_string())
+}
+
+fn session_log_dir(template: &str, workspace_path: &Path, home_dir: &Path) -> Option<PathBuf> {
+    let workspace_path = shared::absolute_path(workspace_path)?;
+    let expanded = template
+        .replace(
+            "{workspace_dir_name}",
+            super::claude::project_dir_name(&workspace_path).as_str(),
+        )
+        .replace("{workspace}", workspace_path.to_string_lossy().as_ref());
+    if expanded == "~" {
+        return Some(home_dir.to_path_buf());
+    }
+    if let Some(remainder) = expanded.strip_prefix("~/") {
+        return Some(home_dir.join(remainder));
+    }
+
+    Some(PathBuf::from(expanded))
+}
+
+fn last_message_observation(
+    path: &Path,
+    session_log: &CustomAgentSessionLog,
+) -> Option<WorkspaceStatusObservation> {
+    let lines = shared::read_tail_lines(path, super::super::SESSION_STATUS_TAIL_BYTES)?;
+    for line in lines.iter().rev() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(trimmed) else {
+            continue;
+        };
+        let Some(role) = value
+            .get(session_log.role_field.as_str())
+            .and_then(Value::as_str)
+        else {
+            continue;
+        };
+        if role == session_log.user_value {
+            return Some(WorkspaceStatusObservation {
+                status: WorkspaceStatus::Active,
+                recent_activity: false,
+                waiting_excerpt: None,
+                waiting_attention: None,
+            });
+        }
+        if role == session_log.assistant_value {
+            return Some(WorkspaceStatusObservation {
+                status: WorkspaceStatus::Waiting,
+                recent_activity: false,
+                waiting_excerpt: shared::best_effort_excerpt_from_json_value(&value),
+                waiting_attention: None,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::application::agent_runtime::RestartExitInput;
+    use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+    use crate::domain::{PermissionMode, WorkspaceStatus};
+    use crate::infrastructure::config::{
+        AgentDefinitionConfig, AgentPermissionFlagsConfig, AgentSessionLogConfig,
+    };
+    use crate::test_support::unique_test_dir;
+
+    use super::{AgentDescriptor, profile_from_definition};
+
+    fn aider_definition() -> AgentDefinitionConfig {
+        AgentDefinitionConfig {
+            id: "aider".to_string(),
+            label: "Aider".to_string(),
+            command: "aider --no-auto-commits".to_string(),
+            permission_flags: AgentPermissionFlagsConfig {
+                auto: None,
+                unsafe_mode: Some("--yes-always".to_string()),
+            },
+            resume_pattern: Some(r"aider --restore (?P<session>\S+)".to_string()),
+            resume_command: Some("aider --restore {session}".to_string()),
+            exit_input: Some("/exit".to_string()),
+            exit_key: None,
+            session_log: None,
+        }
+    }
+
+    #[test]
+    fn config_agent_builds_launch_command_with_permission_flags() {
+        let descriptor =
+            profile_from_definition(&aider_definition()).expect("definition should be valid");
+
+        assert_eq!(
+            descriptor.launch_command(PermissionMode::Default),
+            "aider --no-auto-commits"
+        );
+        assert_eq!(
+            descriptor.launch_command(PermissionMode::Auto),
+            "aider --no-auto-commits"
+        );
+        assert_eq!(
+            descriptor.launch_command(PermissionMode::Unsafe),
+            "aider --no-auto-commits --yes-always"
+        );
+        assert_eq!(
+            descriptor.resume_command_with_permission_mode(
+                "aider --restore abc-123",
+                PermissionMode::Unsafe
+            ),
+            "aider --yes-always --restore abc-123"
+        );
+    }
+
+    #[test]
+    fn config_agent_extracts_last_resume_command_from_pattern() {
+        let descriptor =
+            profile_from_definition(&aider_definition()).expect("definition should be valid");
+        let output = "bye\nrun aider --restore old-1 to continue\nrun aider --restore new-2.\n";
+
+        assert_eq!(
+            descriptor.extract_resume_command(output),
+            Some("aider --restore new-2".to_string())
+        );
+        assert_eq!(descriptor.extract_resume_command("nothing here"), None);
+    }
+
+    #[test]
+    fn config_agent_without_resume_pattern_relaunches_command() {
+        let mut definition = aider_definition();
+        definition.resume_pattern = None;
+        definition.exit_input = None;
+        definition.exit_key = Some("C-d".to_string());
+        let descriptor = profile_from_definition(&definition).expect("definition should be valid");
+
+        assert_eq!(
+            descriptor.extract_resume_command(""),
+            Some("aider --no-auto-commits".to_string())
+        );
+        assert!(matches!(
+            descriptor.restart_exit_input(),
+            Some(RestartExitInput::Named(key)) if key == "C-d"
+        ));
+    }
+
+    #[test]
+    fn config_agent_rejects_missing_command_and_invalid_pattern() {
+        let mut definition = aider_definition();
+        definition.command = "  ".to_string();
+        assert!(profile_from_definition(&definition).is_err());
+
+        let mut definition = aider_definition();
+        definition.resume_pattern = Some("aider (".to_string());
+        assert!(profile_from_definition(&definition).is_err());
+    }
+
+    #[test]
+    fn config_agent_reads_status_from_session_log() {
+        let root = unique_test_dir("custom-agent-session-log");
+        let home = root.join("home");
+        let workspace_path = root.join("ws").join("feature-gamma");
+        let log_dir = workspace_path.join(".agent-logs");
+        fs::create_dir_all(&home).expect("home directory should exist");
+        fs::create_dir_all(&log_dir).expect("log directory should exist");
+        fs::write(
+            log_dir.join("session.jsonl"),
+            "{\"role\":\"human\",\"text\":\"go\"}\n{\"role\":\"bot\",\"text\":\"need approval\"}\n",
+        )
+        .expect("session file should be written");
+
+        let mut definition = aider_definition();
+        definition.session_log = Some(AgentSessionLogConfig {
+            dir: "{workspace}/.agent-logs".to_string(),
+            role_field: "role".to_string(),
+            user_value: "human".to_string(),
+            assistant_value: "bot".to_string(),
+        });
+        let descriptor = profile_from_definition(&definition).expect("definition should be valid");
+
+        assert_eq!(
+            descriptor.status_observation_in_home(&workspace_path, &home, Duration::from_secs(0)),
+            Some(WorkspaceStatusObservation {
+                status: WorkspaceStatus::Waiting,
+                recent_activity: false,
+                waiting_excerpt: Some("need approval".to_string()),
+                waiting_attention: None,
+            })
+        );
+        assert!(
+            descriptor
+                .latest_attention_marker_in_home(&workspace_path, &home)
+                .is_some()
+        );
+    }
+}
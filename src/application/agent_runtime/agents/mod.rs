@@ -1,124 +1,267 @@
 mod claude;
 mod codex;
+mod custom;
 mod shared;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+use crate::application::agent_runtime::usage::{UsageLineState, UsageSample};
 use crate::domain::{AgentType, PermissionMode, WorkspaceStatus};
 
 #[cfg(test)]
 pub(super) use claude::project_dir_name as claude_project_dir_name;
+pub use custom::build_agent_registry;
 
-pub(super) fn restart_exit_input(agent: AgentType) -> Option<super::RestartExitInput> {
+/// Agent-specific launch, restart and status behavior.
+///
+/// Built-in agents implement this directly; config-defined agents are backed by
+/// their `CustomAgentProfile`.
+pub(super) trait AgentDescriptor: Send + Sync {
+    fn launch_command(&self, permission_mode: PermissionMode) -> String;
+
+    fn resume_command_with_permission_mode(
+        &self,
+        command: &str,
+        permission_mode: PermissionMode,
+    ) -> String;
+
+    fn extract_resume_command(&self, output: &str) -> Option<String>;
+
+    fn restart_exit_input(&self) -> Option<super::RestartExitInput>;
+
+    fn infer_permission_mode_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<PermissionMode>;
+
+    fn status_observation_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatusObservation>;
+
+    fn detect_session_status_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+        activity_threshold: Duration,
+    ) -> Option<WorkspaceStatus> {
+        self.status_observation_in_home(workspace_path, home_dir, activity_threshold)
+            .map(|observation| observation.status)
+    }
+
+    fn latest_attention_marker_in_home(
+        &self,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> Option<String>;
+
+    /// The session log the agent is currently writing for `workspace_path`.
+    fn session_log_file_in_home(
+        &self,
+        _workspace_path: &Path,
+        _home_dir: &Path,
+    ) -> Option<PathBuf> {
+        None
+    }
+
+    /// Session logs of `workspace_path` that record token usage.
+    fn usage_session_files_in_home(
+        &self,
+        _workspace_path: &Path,
+        _home_dir: &Path,
+    ) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    fn parse_usage_line(&self, _line: &str, _state: &mut UsageLineState) -> Option<UsageSample> {
+        None
+    }
+
+    /// Writes hook configuration under `workspace_path` that reports status
+    /// events to `event_file`. Agents without config file hooks do nothing.
+    fn install_status_hooks(
+        &self,
+        _workspace_path: &Path,
+        _event_file: &Path,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Removes what `install_status_hooks` wrote, keeping the user's own settings.
+    fn uninstall_status_hooks(&self, _workspace_path: &Path) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// `command` with the flags that make the agent report status events.
+    fn command_with_status_hooks(&self, command: &str, _event_file: &Path) -> String {
+        command.to_string()
+    }
+}
+
+fn with_descriptor<R>(agent: &AgentType, run: impl FnOnce(&dyn AgentDescriptor) -> R) -> R {
+    match agent {
+        AgentType::Claude => run(&claude::ClaudeDescriptor),
+        AgentType::Codex => run(&codex::CodexDescriptor),
+        AgentType::Custom(agent) => run(agent.profile()),
+    }
+}
+
+/// Fails for a config-defined agent the config no longer defines.
+pub(super) fn ensure_defined(agent: &AgentType) -> Result<(), String> {
     match agent {
-        AgentType::Claude => Some(super::RestartExitInput::Literal("/exit")),
-        AgentType::Codex => Some(super::RestartExitInput::Named("C-c")),
+        AgentType::Claude | AgentType::Codex => Ok(()),
+        AgentType::Custom(agent) => custom::ensure_defined(agent.profile()),
     }
 }
 
+pub(super) fn default_command(agent: &AgentType, permission_mode: PermissionMode) -> String {
+    with_descriptor(agent, |descriptor| {
+        descriptor.launch_command(permission_mode)
+    })
+}
+
+pub(super) fn restart_exit_input(agent: &AgentType) -> Option<super::RestartExitInput> {
+    with_descriptor(agent, |descriptor| descriptor.restart_exit_input())
+}
+
 pub(super) fn resume_command_with_permission_mode(
-    agent: AgentType,
+    agent: &AgentType,
     command: &str,
     permission_mode: PermissionMode,
 ) -> String {
-    match permission_mode {
-        PermissionMode::Default => command.to_string(),
-        PermissionMode::Auto => match agent {
-            AgentType::Claude => {
-                if command.contains("--enable-auto-mode") {
-                    return command.to_string();
-                }
-                if let Some(remainder) = command.strip_prefix("claude ") {
-                    return format!("claude --enable-auto-mode {remainder}");
-                }
-                command.to_string()
-            }
-            _ => command.to_string(),
-        },
-        PermissionMode::Unsafe => match agent {
-            AgentType::Claude => {
-                if command.contains("--dangerously-skip-permissions") {
-                    return command.to_string();
-                }
-                if let Some(remainder) = command.strip_prefix("claude ") {
-                    return format!("claude --dangerously-skip-permissions {remainder}");
-                }
-                command.to_string()
-            }
-            AgentType::Codex => {
-                if command.contains("--dangerously-bypass-approvals-and-sandbox") {
-                    return command.to_string();
-                }
-                if let Some(remainder) = command.strip_prefix("codex ") {
-                    return format!("codex --dangerously-bypass-approvals-and-sandbox {remainder}");
-                }
-                command.to_string()
-            }
-        },
-    }
-}
-
-pub(super) fn extract_resume_command(agent: AgentType, output: &str) -> Option<String> {
-    match agent {
-        AgentType::Claude => claude::extract_resume_command(output),
-        AgentType::Codex => codex::extract_resume_command(output),
-    }
+    with_descriptor(agent, |descriptor| {
+        descriptor.resume_command_with_permission_mode(command, permission_mode)
+    })
+}
+
+pub(super) fn extract_resume_command(agent: &AgentType, output: &str) -> Option<String> {
+    with_descriptor(agent, |descriptor| {
+        descriptor.extract_resume_command(output)
+    })
 }
 
 pub(super) fn infer_permission_mode_in_home(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<PermissionMode> {
-    match agent {
-        AgentType::Claude => claude::infer_permission_mode_in_home(workspace_path, home_dir),
-        AgentType::Codex => codex::infer_permission_mode_in_home(workspace_path, home_dir),
-    }
+    with_descriptor(agent, |descriptor| {
+        descriptor.infer_permission_mode_in_home(workspace_path, home_dir)
+    })
 }
 
 pub(super) fn detect_session_status_in_home(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     home_dir: &Path,
     activity_threshold: Duration,
 ) -> Option<WorkspaceStatus> {
-    match agent {
-        AgentType::Claude => {
-            claude::detect_session_status_in_home(workspace_path, home_dir, activity_threshold)
-        }
-        AgentType::Codex => {
-            codex::detect_session_status_in_home(workspace_path, home_dir, activity_threshold)
-        }
-    }
+    with_descriptor(agent, |descriptor| {
+        descriptor.detect_session_status_in_home(workspace_path, home_dir, activity_threshold)
+    })
 }
 
 pub(super) fn latest_attention_marker_in_home(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<String> {
-    match agent {
-        AgentType::Claude => claude::latest_attention_marker_in_home(workspace_path, home_dir),
-        AgentType::Codex => codex::latest_attention_marker_in_home(workspace_path, home_dir),
-    }
+    with_descriptor(agent, |descriptor| {
+        descriptor.latest_attention_marker_in_home(workspace_path, home_dir)
+    })
 }
 
 pub(super) fn status_observation_in_home(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     home_dir: &Path,
     activity_threshold: Duration,
 ) -> Option<WorkspaceStatusObservation> {
-    match agent {
-        AgentType::Claude => {
-            claude::status_observation_in_home(workspace_path, home_dir, activity_threshold)
-        }
-        AgentType::Codex => {
-            codex::status_observation_in_home(workspace_path, home_dir, activity_threshold)
-        }
+    with_descriptor(agent, |descriptor| {
+        descriptor.status_observation_in_home(workspace_path, home_dir, activity_threshold)
+    })
+}
+
+/// Latest JSON record of the agent's session log for `workspace_path`.
+pub(super) fn latest_session_record_in_home(
+    agent: &AgentType,
+    workspace_path: &Path,
+    home_dir: &Path,
+) -> Option<serde_json::Value> {
+    let session_file = with_descriptor(agent, |descriptor| {
+        descriptor.session_log_file_in_home(workspace_path, home_dir)
+    })?;
+    shared::last_json_record(&session_file, super::SESSION_STATUS_TAIL_BYTES)
+}
+
+pub(super) fn usage_session_files_in_home(
+    agent: &AgentType,
+    workspace_path: &Path,
+    home_dir: &Path,
+) -> Vec<PathBuf> {
+    with_descriptor(agent, |descriptor| {
+        descriptor.usage_session_files_in_home(workspace_path, home_dir)
+    })
+}
+
+pub(super) fn parse_usage_line(
+    agent: &AgentType,
+    line: &str,
+    state: &mut UsageLineState,
+) -> Option<UsageSample> {
+    with_descriptor(agent, |descriptor| descriptor.parse_usage_line(line, state))
+}
+
+pub(super) fn install_status_hooks(
+    agent: &AgentType,
+    workspace_path: &Path,
+    event_file: &Path,
+) -> Result<(), String> {
+    with_descriptor(agent, |descriptor| {
+        descriptor.install_status_hooks(workspace_path, event_file)
+    })
+}
+
+pub(super) fn uninstall_status_hooks(
+    agent: &AgentType,
+    workspace_path: &Path,
+) -> Result<(), String> {
+    with_descriptor(agent, |descriptor| {
+        descriptor.uninstall_status_hooks(workspace_path)
+    })
+}
+
+pub(super) fn command_with_status_hooks(
+    agent: &AgentType,
+    command: &str,
+    event_file: &Path,
+) -> String {
+    with_descriptor(agent, |descriptor| {
+        descriptor.command_with_status_hooks(command, event_file)
+    })
+}
+
+/// Inserts `flag` right after the leading `program` token unless already present.
+pub(super) fn command_with_program_flag(command: &str, program: &str, flag: &str) -> String {
+    if command.contains(flag) {
+        return command.to_string();
     }
+    if command == program {
+        return format!("{program} {flag}");
+    }
+    if let Some(remainder) = command
+        .strip_prefix(program)
+        .and_then(|remainder| remainder.strip_prefix(' '))
+    {
+        return format!("{program} {flag} {remainder}");
+    }
+
+    command.to_string()
 }
 
 #[cfg(test)]
@@ -134,6 +134,13 @@ pub(super) fn read_tail_lines(path: &Path, max_bytes: usize) -> Option<Vec<Strin
     Some(lines)
 }
 
+pub(super) fn last_json_record(path: &Path, tail_bytes: usize) -> Option<Value> {
+    read_tail_lines(path, tail_bytes)?
+        .iter()
+        .rev()
+        .find_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+}
+
 pub(super) fn marker_for_session_line(path: &Path, line: &str) -> Option<String> {
     let modified = fs::metadata(path)
         .and_then(|metadata| metadata.modified())
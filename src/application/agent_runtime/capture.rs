@@ -15,6 +15,36 @@ pub fn tmux_capture_error_indicates_missing_session(error: &str) -> bool {
         || lower.contains("session not found")
 }
 
+pub fn capture_session_output(
+    target_session: &str,
+    scrollback_lines: usize,
+    include_escape_sequences: bool,
+) -> std::io::Result<String> {
+    let mut args = vec![
+        "capture-pane".to_string(),
+        "-p".to_string(),
+        "-N".to_string(),
+    ];
+    if include_escape_sequences {
+        args.push("-e".to_string());
+    }
+    args.push("-t".to_string());
+    args.push(target_session.to_string());
+    args.push("-S".to_string());
+    args.push(format!("-{scrollback_lines}"));
+
+    let output = std::process::Command::new("tmux").args(args).output()?;
+    if !output.status.success() {
+        let stderr = crate::infrastructure::process::stderr_or_status(&output);
+        return Err(std::io::Error::other(format!(
+            "tmux capture-pane failed for '{target_session}': {stderr}"
+        )));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|error| std::io::Error::other(format!("tmux output utf8 decode failed: {error}")))
+}
+
 pub(crate) fn evaluate_capture_change(
     previous: Option<&OutputDigest>,
     raw_output: &str,
@@ -0,0 +1,180 @@
+//! Project-defined tabs that run a long-lived command, e.g. a dev server.
+//!
+//! The command runs under a small `sh` loop inside the tab's shell. The loop
+//! applies the restart policy and records each exit code in the session's
+//! `@grove_tab_exit` option, which is empty while the command runs.
+
+use crate::domain::Workspace;
+use crate::infrastructure::config::{CommandTabConfig, CommandTabRestart};
+
+use super::launch_plan::shell_quote;
+use super::sessions::{sanitize_workspace_name, session_name_for_workspace_ref};
+
+pub const TMUX_TAB_EXIT_KEY: &str = "@grove_tab_exit";
+/// Exit code recorded when the loop is interrupted, as a shell reports SIGINT.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+pub fn command_tab_session_name(workspace: &Workspace, template_name: &str) -> String {
+    format!(
+        "{}-cmd-{}",
+        session_name_for_workspace_ref(workspace),
+        sanitize_workspace_name(template_name)
+    )
+}
+
+/// Shell command typed into the tab's session to run `template`.
+pub fn command_tab_shell_command(
+    session_name: &str,
+    template: &CommandTabConfig,
+) -> Result<String, String> {
+    if template.command.trim().is_empty() {
+        return Err(format!("command tab '{}' has no command", template.name));
+    }
+    let record_exit = |code: &str| {
+        format!(
+            "tmux set-option -t {} {TMUX_TAB_EXIT_KEY} {code}",
+            shell_quote(session_name)
+        )
+    };
+
+    let mut steps = Vec::new();
+    for (key, value) in &template.env {
+        if !is_env_key(key) {
+            return Err(format!(
+                "command tab '{}' has invalid env name '{key}'",
+                template.name
+            ));
+        }
+        steps.push(format!("export {key}={}", shell_quote(value)));
+    }
+    steps.push(format!(
+        "trap {} INT TERM",
+        shell_quote(
+            format!(
+                "{}; exit {INTERRUPTED_EXIT_CODE}",
+                record_exit(INTERRUPTED_EXIT_CODE.to_string().as_str())
+            )
+            .as_str()
+        )
+    ));
+    let restart = match template.restart {
+        CommandTabRestart::Never => "break",
+        CommandTabRestart::OnFailure => "[ \"$code\" -ne 0 ] || break",
+        CommandTabRestart::Always => ":",
+    };
+    steps.push(format!(
+        "while :; do {}; sh -c {}; code=$?; {}; {restart}; sleep 1; done",
+        record_exit("''"),
+        shell_quote(template.command.as_str()),
+        record_exit("\"$code\""),
+    ));
+
+    Ok(format!("sh -c {}", shell_quote(steps.join("; ").as_str())))
+}
+
+/// Exit code in a `@grove_tab_exit` value, `None` while the command runs.
+pub fn parse_command_tab_exit(value: &str) -> Option<i32> {
+    value.trim().parse().ok()
+}
+
+fn is_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::process::Command;
+
+    use crate::infrastructure::config::{CommandTabConfig, CommandTabRestart};
+    use crate::test_support::unique_test_dir;
+
+    use super::{command_tab_shell_command, parse_command_tab_exit};
+
+    fn template(command: &str, restart: CommandTabRestart) -> CommandTabConfig {
+        CommandTabConfig {
+            name: "dev".to_string(),
+            command: command.to_string(),
+            env: [("GREETING".to_string(), "it's on".to_string())]
+                .into_iter()
+                .collect(),
+            restart,
+            auto_start: false,
+        }
+    }
+
+    #[test]
+    fn command_tab_records_exit_codes_and_restarts_on_failure() {
+        let root = unique_test_dir("command-tab");
+        let bin = root.join("bin");
+        fs::create_dir_all(&bin).expect("bin dir should exist");
+        let fake_tmux = bin.join("tmux");
+        fs::write(
+            &fake_tmux,
+            format!(
+                "#!/bin/sh\necho \"$*\" >> {}\n",
+                root.join("tmux.log").display()
+            ),
+        )
+        .expect("fake tmux should write");
+        fs::set_permissions(&fake_tmux, fs::Permissions::from_mode(0o755))
+            .expect("fake tmux should be executable");
+
+        // Fails once, then succeeds, so on-failure runs it twice.
+        let command = command_tab_shell_command(
+            "grove-ws-app-cmd-dev",
+            &template(
+                "echo \"$GREETING\" >> out.txt; [ -f ran ] || { touch ran; exit 3; }",
+                CommandTabRestart::OnFailure,
+            ),
+        )
+        .expect("command should build");
+        let path = format!(
+            "{}:{}",
+            bin.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let status = Command::new("sh")
+            .args(["-c", command.as_str()])
+            .current_dir(&root)
+            .env("PATH", path)
+            .status()
+            .expect("sh should run");
+        assert!(status.success());
+
+        assert_eq!(
+            fs::read_to_string(root.join("out.txt")).expect("output should exist"),
+            "it's on\nit's on\n"
+        );
+        let log = fs::read_to_string(root.join("tmux.log")).expect("tmux log should exist");
+        let exits = log
+            .lines()
+            .map(|line| {
+                line.trim_start_matches("set-option -t grove-ws-app-cmd-dev @grove_tab_exit")
+            })
+            .map(str::trim)
+            .collect::<Vec<&str>>();
+        assert_eq!(exits, vec!["", "3", "", "0"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn command_tab_rejects_invalid_templates() {
+        assert!(command_tab_shell_command("s", &template("  ", CommandTabRestart::Never)).is_err());
+        let mut invalid_env = template("true", CommandTabRestart::Never);
+        invalid_env
+            .env
+            .insert("NOT-VALID".to_string(), "1".to_string());
+        assert!(command_tab_shell_command("s", &invalid_env).is_err());
+
+        assert_eq!(parse_command_tab_exit(""), None);
+        assert_eq!(parse_command_tab_exit("0"), Some(0));
+        assert_eq!(parse_command_tab_exit("127\n"), Some(127));
+    }
+}
@@ -5,12 +5,14 @@ use std::path::Path;
 use crate::domain::{Task, Workspace};
 
 use super::launch_plan::{
-    build_launch_plan, build_shell_launch_plan, build_task_launch_plan, stop_plan,
+    build_launch_plan, build_shell_launch_plan, build_task_launch_plan, ensure_agent_command,
+    stop_plan,
 };
 use super::sessions::{
     session_name_for_task, session_name_for_task_worktree, session_name_for_workspace_in_project,
     session_name_for_workspace_ref,
 };
+use super::status_hooks::{install_status_hooks, uninstall_status_hooks};
 use super::{
     LaunchPlan, LaunchRequest, LauncherScript, SessionExecutionResult, ShellLaunchRequest,
     TaskLaunchRequest,
@@ -35,7 +37,11 @@ pub fn execute_launch_request_with_result_for_mode(
     let workspace_path = request.workspace_path.clone();
     let launch_plan = build_launch_plan(request);
     let session_name = launch_plan.session_name.clone();
-    let result = execute_launch_plan_for_mode(&launch_plan, mode);
+    let result = ensure_agent_command(&request.agent).and_then(|()| {
+        // Without hooks, status falls back to scraping the pane.
+        let _ = install_status_hooks(&request.agent, &workspace_path, request.status_hooks);
+        execute_launch_plan_for_mode(&launch_plan, mode)
+    });
     SessionExecutionResult {
         workspace_name,
         workspace_path,
@@ -52,6 +58,7 @@ pub fn execute_task_launch_request_with_result_for_mode(
     let workspace_path = request.task_root.clone();
     let launch_plan = build_task_launch_plan(request);
     let session_name = launch_plan.session_name.clone();
+    let _ = install_status_hooks(&request.agent, &workspace_path, request.status_hooks);
     let result = execute_launch_plan_for_mode(&launch_plan, mode);
     SessionExecutionResult {
         workspace_name,
@@ -80,6 +87,10 @@ pub fn execute_stop_workspace_with_result_for_mode(
     let session_name = session_name_for_workspace_ref(workspace);
     let commands = stop_plan(&session_name);
     let result = execute_commands_for_mode(&commands, mode);
+    if result.is_ok() {
+        // The next launch installs them again.
+        let _ = uninstall_status_hooks(&workspace_path);
+    }
     SessionExecutionResult {
         workspace_name,
         workspace_path,
@@ -99,6 +110,9 @@ pub fn execute_stop_task_with_result_for_mode(
     let session_name = session_name_for_task(task_slug);
     let commands = stop_plan(&session_name);
     let result = execute_commands_for_mode(&commands, mode);
+    if result.is_ok() {
+        let _ = uninstall_status_hooks(&workspace_path);
+    }
     SessionExecutionResult {
         workspace_name,
         workspace_path,
@@ -307,6 +321,9 @@ fn session_name_matches_base_session(base_session_name: &str, session_name: &str
             .is_some_and(|ordinal| {
                 !ordinal.is_empty() && ordinal.bytes().all(|b| b.is_ascii_digit())
             })
+        || suffix
+            .strip_prefix("-cmd-")
+            .is_some_and(|template| !template.is_empty())
 }
 
 pub fn workspace_session_name_matches(
@@ -409,7 +426,7 @@ mod tests {
     use std::fs;
     use std::path::PathBuf;
 
-    use crate::domain::{AgentType, PermissionMode, WorkspaceStatus};
+    use crate::domain::{AgentType, CustomAgent, PermissionMode, WorkspaceStatus};
     use crate::test_support::unique_test_dir;
 
     use super::super::{LaunchPlan, LaunchRequest, LauncherScript};
@@ -504,11 +521,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: Some(120),
             capture_rows: Some(40),
+            transcript_dir: None,
+            status_hooks: true,
         };
         let result = execute_launch_request_with_result_for_mode(
             &request,
@@ -526,6 +547,43 @@ mod tests {
         assert!(result.result.is_err());
     }
 
+    #[test]
+    fn launching_an_undefined_custom_agent_fails_without_running_it() {
+        let request = LaunchRequest {
+            session_name: None,
+            task_slug: None,
+            project_name: None,
+            workspace_name: "auth-flow".to_string(),
+            workspace_path: PathBuf::from("/repos/project.one/worktrees/auth-flow"),
+            agent: AgentType::Custom(CustomAgent::undefined("never-configured")),
+            theme_name: crate::infrastructure::config::ThemeName::default(),
+            prompt: None,
+            resume_command: None,
+            workspace_init_command: None,
+            permission_mode: PermissionMode::Default,
+            agent_env: Vec::new(),
+            sandbox: None,
+            capture_cols: None,
+            capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
+        };
+        let mut commands = Vec::new();
+        let result = execute_launch_request_with_result_for_mode(
+            &request,
+            CommandExecutionMode::Delegating(&mut |command| {
+                commands.push(command.to_vec());
+                Ok(())
+            }),
+        );
+
+        assert_eq!(
+            result.result,
+            Err("agent 'never-configured' is not defined in config".to_string())
+        );
+        assert!(commands.is_empty());
+    }
+
     #[test]
     fn execute_stop_workspace_with_result_for_mode_includes_workspace_context() {
         let workspace = fixture_workspace("feature/auth.v2", false).with_project_context(
@@ -854,6 +912,12 @@ mod tests {
             "feature/auth.v2",
             "grove-ws-project-one-feature-auth-v2-git",
         ));
+        assert!(workspace_session_name_matches(
+            None,
+            Some("project.one"),
+            "feature/auth.v2",
+            "grove-ws-project-one-feature-auth-v2-cmd-dev-server",
+        ));
         assert!(!workspace_session_name_matches(
             None,
             Some("project.one"),
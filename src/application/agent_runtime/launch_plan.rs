@@ -1,10 +1,14 @@
 use std::path::Path;
 
+use crate::application::ports::port_env_vars;
 use crate::domain::{AgentType, PermissionMode, Workspace};
-use crate::infrastructure::config::ThemeName;
+use crate::infrastructure::config::{SandboxConfig, ThemeName};
 
 use super::execution::kill_tmux_session_command;
+use super::sandbox::sandboxed_command;
 use super::sessions::{session_name_for_task, session_name_for_workspace_in_project};
+use super::status_hooks::command_with_status_hooks;
+use super::transcript::{pipe_pane_command, transcript_path_in};
 use super::{
     GROVE_LAUNCHER_SCRIPT_PATH, LaunchPlan, LaunchRequest, LauncherScript, ShellLaunchRequest,
     tmux_theme_commands,
@@ -21,20 +25,29 @@ pub fn launch_request_for_workspace(
 ) -> LaunchRequest {
     let (capture_cols, capture_rows) =
         capture_size.map_or((None, None), |(cols, rows)| (Some(cols), Some(rows)));
+    // Project env is exported last, so it can override the allocated ports.
+    let agent_env = workspace_port_env(workspace)
+        .into_iter()
+        .chain(agent_env)
+        .collect();
     LaunchRequest {
         session_name: None,
         task_slug: workspace.task_slug.clone(),
         project_name: workspace.project_name.clone(),
         workspace_name: workspace.name.clone(),
         workspace_path: workspace.path.clone(),
-        agent: workspace.agent,
+        agent: workspace.agent.clone(),
         theme_name,
         prompt,
+        resume_command: None,
         workspace_init_command,
         permission_mode,
         agent_env,
+        sandbox: None,
         capture_cols,
         capture_rows,
+        transcript_dir: None,
+        status_hooks: true,
     }
 }
 
@@ -55,9 +68,15 @@ pub fn shell_launch_request_for_workspace(
         workspace_init_command,
         capture_cols,
         capture_rows,
+        transcript_dir: None,
+        env: workspace_port_env(workspace),
     }
 }
 
+fn workspace_port_env(workspace: &Workspace) -> Vec<(String, String)> {
+    workspace.ports.map(port_env_vars).unwrap_or_default()
+}
+
 pub fn tmux_launch_error_indicates_duplicate_session(error: &str) -> bool {
     error.to_ascii_lowercase().contains("duplicate session")
 }
@@ -83,7 +102,26 @@ pub fn build_launch_plan(request: &LaunchRequest) -> LaunchPlan {
                 )
             })
     });
-    let agent_cmd = build_agent_command(request.agent, request.permission_mode);
+    let agent_cmd = match request.resume_command.as_deref() {
+        Some(resume_command) => super::agents::resume_command_with_permission_mode(
+            &request.agent,
+            resume_command,
+            request.permission_mode,
+        ),
+        None => build_agent_command(&request.agent, request.permission_mode),
+    };
+    let agent_cmd = command_with_status_hooks(
+        &request.agent,
+        agent_cmd.as_str(),
+        &request.workspace_path,
+        request.status_hooks,
+    );
+    let agent_cmd = sandboxed_agent_command(
+        agent_cmd,
+        request.sandbox.as_ref(),
+        &request.workspace_path,
+        &request.agent_env,
+    );
     let launch_agent_cmd = launch_command_with_workspace_init(
         &request.workspace_path,
         agent_cmd,
@@ -102,7 +140,18 @@ pub fn build_launch_plan(request: &LaunchRequest) -> LaunchPlan {
 
 pub fn build_task_launch_plan(request: &super::TaskLaunchRequest) -> LaunchPlan {
     let session_name = session_name_for_task(request.task_slug.as_str());
-    let agent_cmd = build_agent_command(request.agent, request.permission_mode);
+    let agent_cmd = command_with_status_hooks(
+        &request.agent,
+        build_agent_command(&request.agent, request.permission_mode).as_str(),
+        &request.task_root,
+        request.status_hooks,
+    );
+    let agent_cmd = sandboxed_agent_command(
+        agent_cmd,
+        request.sandbox.as_ref(),
+        &request.task_root,
+        &request.agent_env,
+    );
     let launch_agent_cmd = launch_command_with_workspace_init(
         &request.task_root,
         agent_cmd,
@@ -114,14 +163,18 @@ pub fn build_task_launch_plan(request: &super::TaskLaunchRequest) -> LaunchPlan
         project_name: None,
         workspace_name: request.task_slug.clone(),
         workspace_path: request.task_root.clone(),
-        agent: request.agent,
-        theme_name: request.theme_name,
+        agent: request.agent.clone(),
+        theme_name: request.theme_name.clone(),
         prompt: request.prompt.clone(),
+        resume_command: None,
         workspace_init_command: request.workspace_init_command.clone(),
         permission_mode: request.permission_mode,
         agent_env: request.agent_env.clone(),
+        sandbox: request.sandbox.clone(),
         capture_cols: request.capture_cols,
         capture_rows: request.capture_rows,
+        transcript_dir: request.transcript_dir.clone(),
+        status_hooks: request.status_hooks,
     };
     let mut plan = tmux_launch_plan(&shared, session_name, launch_agent_cmd);
     if let Some(resize_cmd) = launch_resize_window_command(
@@ -147,13 +200,17 @@ pub fn build_shell_launch_plan(request: &ShellLaunchRequest) -> LaunchPlan {
         workspace_name: request.session_name.clone(),
         workspace_path: request.workspace_path.clone(),
         agent: AgentType::Codex,
-        theme_name: request.theme_name,
+        theme_name: request.theme_name.clone(),
         prompt: None,
+        resume_command: None,
         workspace_init_command: request.workspace_init_command.clone(),
         permission_mode: PermissionMode::Default,
-        agent_env: Vec::new(),
+        agent_env: request.env.clone(),
+        sandbox: None,
         capture_cols: request.capture_cols,
         capture_rows: request.capture_rows,
+        transcript_dir: request.transcript_dir.clone(),
+        status_hooks: false,
     };
     let mut plan = tmux_launch_plan(
         &shared,
@@ -207,9 +264,15 @@ fn tmux_launch_plan(
             "off".to_string(),
         ],
     ];
+    if let Some(transcript_dir) = request.transcript_dir.as_deref() {
+        pre_launch_cmds.push(pipe_pane_command(
+            session_name.as_str(),
+            transcript_path_in(transcript_dir, session_name.as_str()).as_path(),
+        ));
+    }
     pre_launch_cmds.extend(tmux_theme_commands(
         session_name.as_str(),
-        request.theme_name,
+        &request.theme_name,
     ));
     if let Some(agent_env_cmd) = build_agent_env_command(&request.agent_env) {
         pre_launch_cmds.push(vec![
@@ -303,7 +366,7 @@ pub(super) fn build_agent_env_command(agent_env: &[(String, String)]) -> Option<
     Some(format!("export {exports}"))
 }
 
-fn shell_quote(value: &str) -> String {
+pub(super) fn shell_quote(value: &str) -> String {
     format!("'{}'", value.replace('\'', "'\"'\"'"))
 }
 
@@ -350,7 +413,7 @@ pub fn stop_plan(session_name: &str) -> Vec<Vec<String>> {
     ]
 }
 
-pub(crate) fn build_agent_command(agent: AgentType, permission_mode: PermissionMode) -> String {
+pub(crate) fn build_agent_command(agent: &AgentType, permission_mode: PermissionMode) -> String {
     if let Some(command_override) = env_agent_command_override(agent) {
         return command_override;
     }
@@ -358,23 +421,34 @@ pub(crate) fn build_agent_command(agent: AgentType, permission_mode: PermissionM
     default_agent_command(agent, permission_mode)
 }
 
-pub(super) fn default_agent_command(agent: AgentType, permission_mode: PermissionMode) -> String {
-    match (agent, permission_mode) {
-        (AgentType::Claude, PermissionMode::Unsafe) => {
-            "claude --dangerously-skip-permissions".to_string()
-        }
-        (AgentType::Claude, PermissionMode::Auto) => "claude --enable-auto-mode".to_string(),
-        (AgentType::Claude, PermissionMode::Default) => "claude".to_string(),
-        (AgentType::Codex, PermissionMode::Unsafe) => {
-            "codex --dangerously-bypass-approvals-and-sandbox".to_string()
-        }
-        (AgentType::Codex, _) => "codex".to_string(),
+/// Fails for a config-defined agent the config no longer defines, unless its
+/// command is overridden from the environment.
+pub(super) fn ensure_agent_command(agent: &AgentType) -> Result<(), String> {
+    if env_agent_command_override(agent).is_some() {
+        return Ok(());
+    }
+    super::agents::ensure_defined(agent)
+}
+
+pub(super) fn sandboxed_agent_command(
+    agent_cmd: String,
+    sandbox: Option<&SandboxConfig>,
+    root: &Path,
+    agent_env: &[(String, String)],
+) -> String {
+    match sandbox {
+        Some(sandbox) => sandboxed_command(sandbox, root, agent_env, &agent_cmd),
+        None => agent_cmd,
     }
 }
 
-fn env_agent_command_override(agent: AgentType) -> Option<String> {
+pub(super) fn default_agent_command(agent: &AgentType, permission_mode: PermissionMode) -> String {
+    super::agents::default_command(agent, permission_mode)
+}
+
+fn env_agent_command_override(agent: &AgentType) -> Option<String> {
     let variable = agent.command_override_env_var();
-    let override_value = std::env::var(variable).ok()?;
+    let override_value = std::env::var(variable.as_str()).ok()?;
     trimmed_nonempty(&override_value)
 }
 
@@ -431,15 +505,18 @@ fn build_launcher_script(agent_cmd: &str, prompt: &str, launcher_path: &Path) ->
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
-    use crate::application::agent_runtime::{LaunchRequest, TaskLaunchRequest};
+    use crate::application::agent_runtime::{LaunchRequest, ShellLaunchRequest, TaskLaunchRequest};
     use crate::domain::{AgentType, PermissionMode};
+    use crate::infrastructure::config::{SandboxBackend, SandboxConfig, SandboxNetwork};
 
     use super::super::capture::tmux_capture_error_indicates_missing_session;
+    use super::super::status_hooks::command_with_status_hooks;
     use super::{
-        build_launch_plan, build_task_launch_plan, default_agent_command, stop_plan,
-        tmux_launch_error_indicates_duplicate_session, trimmed_nonempty,
+        build_launch_plan, build_shell_launch_plan, build_task_launch_plan, default_agent_command,
+        pipe_pane_command, stop_plan, tmux_launch_error_indicates_duplicate_session,
+        transcript_path_in, trimmed_nonempty,
     };
 
     #[test]
@@ -453,8 +530,11 @@ mod tests {
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_task_launch_plan(&request);
@@ -479,12 +559,48 @@ mod tests {
                 "send-keys".to_string(),
                 "-t".to_string(),
                 "grove-task-flohome-launch".to_string(),
-                "codex".to_string(),
+                command_with_status_hooks(
+                    &AgentType::Codex,
+                    "codex",
+                    Path::new("/tmp/.grove/tasks/flohome-launch"),
+                    true,
+                ),
                 "Enter".to_string(),
             ]
         );
     }
 
+    #[test]
+    fn build_task_launch_plan_reports_status_through_hooks() {
+        let request = TaskLaunchRequest {
+            task_slug: "hooked".to_string(),
+            task_root: PathBuf::from("/tmp/.grove/tasks/hooked"),
+            agent: AgentType::Codex,
+            theme_name: crate::infrastructure::config::ThemeName::default(),
+            prompt: None,
+            workspace_init_command: None,
+            permission_mode: PermissionMode::Default,
+            agent_env: Vec::new(),
+            sandbox: None,
+            capture_cols: None,
+            capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
+        };
+
+        let plan = build_task_launch_plan(&request);
+
+        let agent_command = plan.launch_cmd[4].as_str();
+        assert!(agent_command.starts_with("codex -c 'notify="));
+        assert!(agent_command.contains("/tmp/.grove/tasks/hooked/.grove/agent-event"));
+
+        let plan = build_task_launch_plan(&TaskLaunchRequest {
+            status_hooks: false,
+            ..request
+        });
+        assert_eq!(plan.launch_cmd[4], "codex");
+    }
+
     #[test]
     fn launch_plan_cleanup_kills_stale_session_before_creating_new_one() {
         let request = TaskLaunchRequest {
@@ -496,8 +612,11 @@ mod tests {
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_task_launch_plan(&request);
@@ -524,11 +643,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -555,11 +678,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -577,6 +704,35 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn shell_launch_plan_with_transcript_dir_pipes_pane_output_before_launch() {
+        let request = ShellLaunchRequest {
+            session_name: "grove-wt-flaky-grove-shell".to_string(),
+            workspace_path: PathBuf::from("/tasks/flaky/grove"),
+            command: String::new(),
+            theme_name: crate::infrastructure::config::ThemeName::default(),
+            workspace_init_command: None,
+            capture_cols: None,
+            capture_rows: None,
+            transcript_dir: Some(PathBuf::from("/tasks/flaky/.grove/transcripts")),
+            env: Vec::new(),
+        };
+
+        let plan = build_shell_launch_plan(&request);
+
+        assert_eq!(
+            plan.pre_launch_cmds[3],
+            pipe_pane_command(
+                "grove-wt-flaky-grove-shell",
+                transcript_path_in(
+                    PathBuf::from("/tasks/flaky/.grove/transcripts").as_path(),
+                    "grove-wt-flaky-grove-shell",
+                )
+                .as_path(),
+            )
+        );
+    }
+
     #[test]
     fn tmux_missing_session_error_detection_matches_known_patterns() {
         assert!(tmux_capture_error_indicates_missing_session(
@@ -606,27 +762,27 @@ mod tests {
     #[test]
     fn default_agent_command_maps_permission_modes_to_flags() {
         assert_eq!(
-            default_agent_command(AgentType::Claude, PermissionMode::Default),
+            default_agent_command(&AgentType::Claude, PermissionMode::Default),
             "claude"
         );
         assert_eq!(
-            default_agent_command(AgentType::Claude, PermissionMode::Auto),
+            default_agent_command(&AgentType::Claude, PermissionMode::Auto),
             "claude --enable-auto-mode"
         );
         assert_eq!(
-            default_agent_command(AgentType::Claude, PermissionMode::Unsafe),
+            default_agent_command(&AgentType::Claude, PermissionMode::Unsafe),
             "claude --dangerously-skip-permissions"
         );
         assert_eq!(
-            default_agent_command(AgentType::Codex, PermissionMode::Default),
+            default_agent_command(&AgentType::Codex, PermissionMode::Default),
             "codex"
         );
         assert_eq!(
-            default_agent_command(AgentType::Codex, PermissionMode::Unsafe),
+            default_agent_command(&AgentType::Codex, PermissionMode::Unsafe),
             "codex --dangerously-bypass-approvals-and-sandbox"
         );
         assert_eq!(
-            default_agent_command(AgentType::Codex, PermissionMode::Auto),
+            default_agent_command(&AgentType::Codex, PermissionMode::Auto),
             "codex",
             "auto mode falls back to default for non-Claude agents"
         );
@@ -657,11 +813,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Unsafe,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -681,6 +841,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn launch_plan_with_resume_command_resumes_instead_of_starting_fresh() {
+        let request = LaunchRequest {
+            session_name: None,
+            task_slug: None,
+            project_name: None,
+            workspace_name: "auth-flow".to_string(),
+            workspace_path: PathBuf::from("/repos/grove-auth-flow"),
+            agent: AgentType::Claude,
+            theme_name: crate::infrastructure::config::ThemeName::default(),
+            prompt: None,
+            resume_command: Some("claude --resume abc-123".to_string()),
+            workspace_init_command: None,
+            permission_mode: PermissionMode::Unsafe,
+            agent_env: Vec::new(),
+            sandbox: None,
+            capture_cols: None,
+            capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
+        };
+
+        let plan = build_launch_plan(&request);
+
+        assert_eq!(
+            plan.launch_cmd[4],
+            "claude --dangerously-skip-permissions --resume abc-123"
+        );
+    }
+
     #[test]
     fn launch_plan_with_workspace_init_wraps_agent_start_command() {
         let request = LaunchRequest {
@@ -692,11 +882,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: Some("direnv allow".to_string()),
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -734,11 +928,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: Some("echo init".to_string()),
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -760,11 +958,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::CatppuccinMocha,
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Unsafe,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: Some(132),
             capture_rows: Some(44),
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -795,11 +997,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::CatppuccinMocha,
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -839,6 +1045,7 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: vec![
@@ -851,8 +1058,11 @@ mod tests {
                     "https://api.example.com/v1".to_string(),
                 ),
             ],
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -882,11 +1092,15 @@ mod tests {
             agent: AgentType::Codex,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: Some("fix migration".to_string()),
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -937,11 +1151,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: None,
             permission_mode: PermissionMode::Default,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -963,6 +1181,55 @@ mod tests {
         assert!(hook_cmd[4].contains("rm -f"));
     }
 
+    #[test]
+    fn sandboxed_launch_plan_wraps_agent_and_keeps_exit_code_hook() {
+        let request = LaunchRequest {
+            session_name: None,
+            task_slug: None,
+            project_name: None,
+            workspace_name: "auth-flow".to_string(),
+            workspace_path: PathBuf::from("/repos/grove-auth-flow"),
+            agent: AgentType::Claude,
+            theme_name: crate::infrastructure::config::ThemeName::default(),
+            prompt: Some("fix the tests".to_string()),
+            resume_command: None,
+            workspace_init_command: None,
+            permission_mode: PermissionMode::Unsafe,
+            agent_env: Vec::new(),
+            sandbox: Some(SandboxConfig {
+                backend: SandboxBackend::Bubblewrap,
+                network: SandboxNetwork::None,
+                mounts: Vec::new(),
+                read_only_mounts: Vec::new(),
+            }),
+            capture_cols: None,
+            capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
+        };
+
+        let plan = build_launch_plan(&request);
+
+        assert!(plan.pre_launch_cmds.iter().any(|cmd| {
+            cmd.get(4)
+                .is_some_and(|keys| keys.contains("grove_prompt_hook"))
+        }));
+        let launcher = plan
+            .launcher_script
+            .expect("prompt launch should write a launcher script");
+        let agent_line = launcher
+            .contents
+            .lines()
+            .find(|line| line.starts_with("bwrap "))
+            .expect("launcher should start the agent inside bwrap");
+        assert!(agent_line.contains("--bind '/repos/grove-auth-flow' '/repos/grove-auth-flow'"));
+        assert!(!agent_line.contains("--share-net"));
+        assert!(agent_line.ends_with(&format!(
+            "-- {} \"$(cat <<'GROVE_PROMPT_EOF'",
+            default_agent_command(&AgentType::Claude, PermissionMode::Unsafe)
+        )));
+    }
+
     #[test]
     fn launch_plan_with_workspace_init_runs_before_agent() {
         let request = LaunchRequest {
@@ -974,11 +1241,15 @@ mod tests {
             agent: AgentType::Claude,
             theme_name: crate::infrastructure::config::ThemeName::default(),
             prompt: None,
+            resume_command: None,
             workspace_init_command: Some("direnv allow".to_string()),
             permission_mode: PermissionMode::Unsafe,
             agent_env: Vec::new(),
+            sandbox: None,
             capture_cols: None,
             capture_rows: None,
+            transcript_dir: None,
+            status_hooks: true,
         };
 
         let plan = build_launch_plan(&request);
@@ -2,21 +2,29 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::domain::{AgentType, PermissionMode, Workspace};
-use crate::infrastructure::config::ThemeName;
+use crate::infrastructure::config::{SandboxConfig, ThemeName};
 
 mod agents;
 pub mod capture;
+pub mod command_tabs;
 pub mod execution;
 pub mod launch_plan;
+pub mod pane_search;
 pub mod polling;
 pub mod reconciliation;
 pub mod restart;
+mod sandbox;
 mod sessions;
 pub mod status;
+mod status_hooks;
+pub mod status_rules;
 mod tmux_theme;
+pub mod transcript;
+pub mod usage;
 
+pub use agents::build_agent_registry;
 pub(crate) use capture::evaluate_capture_change;
-pub use capture::tmux_capture_error_indicates_missing_session;
+pub use capture::{capture_session_output, tmux_capture_error_indicates_missing_session};
 pub use execution::{
     CommandExecutionMode, CommandExecutor, DelegatingCommandExecutor, ProcessCommandExecutor,
     execute_command_with, execute_commands_for_mode, execute_commands_with,
@@ -40,8 +48,9 @@ pub use polling::{
 };
 pub use reconciliation::reconcile_with_sessions;
 pub use restart::{
-    execute_restart_workspace_in_pane_with_result, extract_agent_resume_command,
-    infer_workspace_permission_mode, restart_workspace_in_pane_with_io,
+    execute_restart_workspace_in_pane_with_result, exit_agent_for_resume_command,
+    extract_agent_resume_command, infer_workspace_permission_mode,
+    restart_workspace_in_pane_with_io,
 };
 pub use sessions::{
     git_preview_session_if_ready, git_session_name_for_workspace, live_preview_agent_session,
@@ -52,6 +61,8 @@ pub use sessions::{
     workspace_session_for_preview_tab,
 };
 pub(crate) use status::{detect_status_with_session_override, latest_assistant_attention_marker};
+pub(crate) use status_hooks::{AgentHookEvent, read_status_event, uninstall_status_hooks};
+pub use status_rules::{StatusRules, compile_status_rules};
 pub use tmux_theme::{grove_managed_tmux_sessions, tmux_theme_commands};
 
 pub const TMUX_SESSION_PREFIX: &str = "grove-ws-";
@@ -98,11 +109,18 @@ pub struct LaunchRequest {
     pub agent: AgentType,
     pub theme_name: ThemeName,
     pub prompt: Option<String>,
+    /// Resumes a saved agent conversation instead of starting a fresh one.
+    pub resume_command: Option<String>,
     pub workspace_init_command: Option<String>,
     pub permission_mode: PermissionMode,
     pub agent_env: Vec<(String, String)>,
+    pub sandbox: Option<SandboxConfig>,
     pub capture_cols: Option<u16>,
     pub capture_rows: Option<u16>,
+    /// Records pane output into a per-session transcript in this directory.
+    pub transcript_dir: Option<PathBuf>,
+    /// Installs the agent hooks that report status, see `[status_hooks]`.
+    pub status_hooks: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -115,8 +133,13 @@ pub struct TaskLaunchRequest {
     pub workspace_init_command: Option<String>,
     pub permission_mode: PermissionMode,
     pub agent_env: Vec<(String, String)>,
+    pub sandbox: Option<SandboxConfig>,
     pub capture_cols: Option<u16>,
     pub capture_rows: Option<u16>,
+    /// Records pane output into a per-session transcript in this directory.
+    pub transcript_dir: Option<PathBuf>,
+    /// Installs the agent hooks that report status, see `[status_hooks]`.
+    pub status_hooks: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -154,6 +177,10 @@ pub struct ShellLaunchRequest {
     pub workspace_init_command: Option<String>,
     pub capture_cols: Option<u16>,
     pub capture_rows: Option<u16>,
+    /// Records pane output into a per-session transcript in this directory.
+    pub transcript_dir: Option<PathBuf>,
+    /// Exported in the shell before `command` runs.
+    pub env: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -193,6 +220,6 @@ pub(crate) struct CaptureChange {
 }
 
 pub(super) enum RestartExitInput {
-    Literal(&'static str),
-    Named(&'static str),
+    Literal(String),
+    Named(String),
 }
@@ -0,0 +1,214 @@
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use super::evaluate_capture_change;
+use crate::application::preview::split_output_lines;
+
+/// Hits kept per pane, so one noisy session cannot crowd out the others.
+const MAX_HITS_PER_PANE: usize = 20;
+const EXACT_CASE_BONUS: u32 = 50;
+const WHOLE_WORD_BONUS: u32 = 25;
+/// Upper bound of the recency bonus given to the newest line of a pane.
+const RECENCY_BONUS: usize = 20;
+
+/// Captured output of one live tmux session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneSearchSource {
+    pub session_name: String,
+    pub workspace_path: PathBuf,
+    /// Task (or workspace) name that hits are grouped under.
+    pub group: String,
+    /// Tab title shown next to each hit.
+    pub label: String,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneSearchHit {
+    /// Index into the searched sources.
+    pub source: usize,
+    pub line: usize,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneSearchGroup {
+    pub group: String,
+    pub hits: Vec<PaneSearchHit>,
+}
+
+impl PaneSearchGroup {
+    fn best_score(&self) -> u32 {
+        self.hits.iter().map(|hit| hit.score).max().unwrap_or(0)
+    }
+}
+
+/// Plain lines of a capture, split the same way the preview splits them.
+pub fn pane_search_lines(raw_output: &str) -> Vec<String> {
+    split_output_lines(&evaluate_capture_change(None, raw_output).cleaned_output)
+}
+
+/// Case-insensitive matches across panes, grouped by task with the best group first.
+///
+/// Exact-case and whole-word matches rank higher, as do newer lines of a pane.
+pub fn search_panes(sources: &[PaneSearchSource], query: &str) -> Vec<PaneSearchGroup> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let lowered_query = query.to_lowercase();
+
+    let mut groups: Vec<PaneSearchGroup> = Vec::new();
+    for (source_index, source) in sources.iter().enumerate() {
+        let mut hits = source
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(line_index, line)| {
+                let score = match_score(line, query, lowered_query.as_str())?
+                    + recency_bonus(line_index, source.lines.len());
+                Some(PaneSearchHit {
+                    source: source_index,
+                    line: line_index,
+                    score,
+                })
+            })
+            .collect::<Vec<PaneSearchHit>>();
+        if hits.is_empty() {
+            continue;
+        }
+        hits.sort_by_key(|hit| (Reverse(hit.score), Reverse(hit.line)));
+        hits.truncate(MAX_HITS_PER_PANE);
+        match groups.iter_mut().find(|group| group.group == source.group) {
+            Some(group) => group.hits.extend(hits),
+            None => groups.push(PaneSearchGroup {
+                group: source.group.clone(),
+                hits,
+            }),
+        }
+    }
+
+    for group in &mut groups {
+        group
+            .hits
+            .sort_by_key(|hit| (Reverse(hit.score), hit.source, Reverse(hit.line)));
+    }
+    groups.sort_by(|left, right| {
+        right
+            .best_score()
+            .cmp(&left.best_score())
+            .then_with(|| left.group.cmp(&right.group))
+    });
+    groups
+}
+
+fn match_score(line: &str, query: &str, lowered_query: &str) -> Option<u32> {
+    let lowered_line = line.to_lowercase();
+    let position = lowered_line.find(lowered_query)?;
+    let mut score = 100;
+    if line.contains(query) {
+        score += EXACT_CASE_BONUS;
+    }
+    let before = lowered_line[..position].chars().next_back();
+    let after = lowered_line[position + lowered_query.len()..]
+        .chars()
+        .next();
+    if !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric) {
+        score += WHOLE_WORD_BONUS;
+    }
+    Some(score)
+}
+
+fn recency_bonus(line_index: usize, line_count: usize) -> u32 {
+    let bonus = line_index.saturating_mul(RECENCY_BONUS) / line_count.max(1);
+    u32::try_from(bonus).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{PaneSearchSource, pane_search_lines, search_panes};
+
+    fn source(session_name: &str, group: &str, lines: &[&str]) -> PaneSearchSource {
+        PaneSearchSource {
+            session_name: session_name.to_string(),
+            workspace_path: PathBuf::from(format!("/repos/{group}")),
+            group: group.to_string(),
+            label: "Claude".to_string(),
+            lines: lines.iter().map(|line| (*line).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn search_panes_ranks_exact_whole_word_and_recent_hits_first() {
+        let sources = vec![source(
+            "grove-wt-a",
+            "feature-a",
+            &[
+                "panicked at src/main.rs",
+                "thread main PANICKED",
+                "unpanicked state",
+                "panicked again",
+            ],
+        )];
+
+        let groups = search_panes(&sources, "panicked");
+
+        assert_eq!(groups.len(), 1);
+        let lines = groups[0]
+            .hits
+            .iter()
+            .map(|hit| hit.line)
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, vec![3, 0, 2, 1]);
+    }
+
+    #[test]
+    fn search_panes_groups_sessions_by_task_with_best_group_first() {
+        let sources = vec![
+            source(
+                "grove-wt-a-agent",
+                "feature-a",
+                &["build ok", "ERROR: fail"],
+            ),
+            source("grove-wt-b-agent", "feature-b", &["error: exact"]),
+            source("grove-wt-a-shell", "feature-a", &["no errors here"]),
+            source("grove-wt-c-agent", "feature-c", &["nothing"]),
+        ];
+
+        let groups = search_panes(&sources, "error");
+
+        let names = groups
+            .iter()
+            .map(|group| group.group.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["feature-b", "feature-a"]);
+        let feature_a_sources = groups[1]
+            .hits
+            .iter()
+            .map(|hit| hit.source)
+            .collect::<Vec<usize>>();
+        assert_eq!(feature_a_sources, vec![2, 0]);
+        assert!(search_panes(&sources, "  ").is_empty());
+    }
+
+    #[test]
+    fn search_panes_caps_hits_per_pane() {
+        let lines = vec!["retry"; 50];
+        let sources = vec![source("grove-wt-a", "feature-a", &lines)];
+
+        let groups = search_panes(&sources, "retry");
+
+        assert_eq!(groups[0].hits.len(), 20);
+        assert_eq!(groups[0].hits[0].line, 49);
+    }
+
+    #[test]
+    fn pane_search_lines_strip_escape_sequences() {
+        assert_eq!(
+            pane_search_lines("\u{1b}[31mred\u{1b}[0m line\nplain\n"),
+            vec!["red line".to_string(), "plain".to_string()]
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::domain::Workspace;
 
 use super::sessions::session_name_for_workspace_in_project;
 use super::status::detect_status;
+use super::status_rules::StatusRules;
 use super::{ReconciliationResult, SessionActivity};
 
 pub fn reconcile_with_sessions(
@@ -12,6 +13,8 @@ pub fn reconcile_with_sessions(
     previously_running_workspace_names: &HashSet<String>,
 ) -> ReconciliationResult {
     let mut matched_sessions = HashSet::with_capacity(running_sessions.len());
+    // No rule matches an empty pane, so config rules would change nothing.
+    let rules = StatusRules::default();
 
     for workspace in &mut workspaces {
         let session_name = session_name_for_workspace_in_project(
@@ -22,21 +25,25 @@ pub fn reconcile_with_sessions(
         if has_live_session {
             matched_sessions.insert(session_name.clone());
             workspace.status = detect_status(
+                &rules,
                 "",
                 SessionActivity::Active,
                 workspace.is_main,
                 true,
                 workspace.supported_agent,
+                &workspace.agent,
                 &session_name,
             );
             workspace.is_orphaned = false;
         } else {
             workspace.status = detect_status(
+                &rules,
                 "",
                 SessionActivity::Idle,
                 workspace.is_main,
                 false,
                 workspace.supported_agent,
+                &workspace.agent,
                 &session_name,
             );
             workspace.is_orphaned = if workspace.is_main {
@@ -1,18 +1,21 @@
 use std::path::Path;
 
 use crate::domain::{AgentType, PermissionMode, Workspace};
+use crate::infrastructure::config::SandboxConfig;
 
 use super::agents;
+use super::capture::capture_session_output;
 use super::execution::execute_command_with;
-use super::launch_plan::build_agent_env_command;
+use super::launch_plan::{build_agent_env_command, ensure_agent_command, sandboxed_agent_command};
 use super::sessions::session_name_for_workspace_ref;
+use super::status_hooks::{command_with_status_hooks, install_status_hooks};
 use super::{
     RESTART_RESUME_CAPTURE_ATTEMPTS, RESTART_RESUME_ERROR_MAX_CHARS,
     RESTART_RESUME_ERROR_TAIL_LINES, RESTART_RESUME_RETRY_DELAY, RESTART_RESUME_SCROLLBACK_LINES,
     RestartExitInput, SessionExecutionResult,
 };
 
-fn restart_exit_input(agent: AgentType) -> Option<RestartExitInput> {
+fn restart_exit_input(agent: &AgentType) -> Option<RestartExitInput> {
     agents::restart_exit_input(agent)
 }
 
@@ -25,7 +28,7 @@ fn restart_exit_plan(session_name: &str, exit_input: RestartExitInput) -> Vec<Ve
                 "-l".to_string(),
                 "-t".to_string(),
                 session_name.to_string(),
-                text.to_string(),
+                text,
             ],
             vec![
                 "tmux".to_string(),
@@ -40,13 +43,13 @@ fn restart_exit_plan(session_name: &str, exit_input: RestartExitInput) -> Vec<Ve
             "send-keys".to_string(),
             "-t".to_string(),
             session_name.to_string(),
-            key.to_string(),
+            key,
         ]],
     }
 }
 
 fn resume_command_with_permission_mode(
-    agent: AgentType,
+    agent: &AgentType,
     command: &str,
     permission_mode: PermissionMode,
 ) -> String {
@@ -55,11 +58,20 @@ fn resume_command_with_permission_mode(
 
 fn restart_resume_command(
     session_name: &str,
-    agent: AgentType,
+    workspace: &Workspace,
     command: &str,
     permission_mode: PermissionMode,
+    agent_env: &[(String, String)],
+    sandbox: Option<&SandboxConfig>,
+    status_hooks: bool,
 ) -> Vec<String> {
-    let command = resume_command_with_permission_mode(agent, command, permission_mode);
+    let command = command_with_status_hooks(
+        &workspace.agent,
+        resume_command_with_permission_mode(&workspace.agent, command, permission_mode).as_str(),
+        &workspace.path,
+        status_hooks,
+    );
+    let command = sandboxed_agent_command(command, sandbox, &workspace.path, agent_env);
     vec![
         "tmux".to_string(),
         "send-keys".to_string(),
@@ -70,12 +82,12 @@ fn restart_resume_command(
     ]
 }
 
-pub fn extract_agent_resume_command(agent: AgentType, output: &str) -> Option<String> {
+pub fn extract_agent_resume_command(agent: &AgentType, output: &str) -> Option<String> {
     agents::extract_resume_command(agent, output)
 }
 
 pub fn infer_workspace_permission_mode(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
 ) -> Option<PermissionMode> {
     let home_dir = dirs::home_dir()?;
@@ -87,7 +99,7 @@ pub fn infer_workspace_permission_mode(
 }
 
 fn wait_for_resume_command(
-    agent: AgentType,
+    agent: &AgentType,
     session_name: &str,
     capture_output: &mut impl FnMut(&str, usize, bool) -> std::io::Result<String>,
 ) -> Result<String, String> {
@@ -143,67 +155,74 @@ pub fn restart_workspace_in_pane_with_io(
     workspace: &Workspace,
     permission_mode: PermissionMode,
     agent_env: &[(String, String)],
+    sandbox: Option<&SandboxConfig>,
+    status_hooks: bool,
     mut execute: impl FnMut(&[String]) -> std::io::Result<()>,
     mut capture_output: impl FnMut(&str, usize, bool) -> std::io::Result<String>,
 ) -> Result<(), String> {
-    let Some(exit_input) = restart_exit_input(workspace.agent) else {
+    ensure_agent_command(&workspace.agent)?;
+    if restart_exit_input(&workspace.agent).is_none() {
         return Err(format!(
             "in-pane restart unsupported for {}",
             workspace.agent.label()
         ));
-    };
-    let session_name = session_name_for_workspace_ref(workspace);
-
-    for command in restart_exit_plan(&session_name, exit_input) {
-        execute_command_with(command.as_slice(), |command| execute(command)).map_err(|error| {
-            format!("restart exit command failed for '{session_name}': {error}")
-        })?;
     }
+    let session_name = session_name_for_workspace_ref(workspace);
 
-    let resume_command =
-        wait_for_resume_command(workspace.agent, &session_name, &mut capture_output)?;
+    let resume_command = exit_agent_for_resume_command_with_io(
+        &workspace.agent,
+        &session_name,
+        &mut execute,
+        &mut capture_output,
+    )?;
+    // Without hooks, status falls back to scraping the pane.
+    let _ = install_status_hooks(&workspace.agent, &workspace.path, status_hooks);
     if let Some(command) = restart_agent_env_command(&session_name, agent_env) {
         execute_command_with(command.as_slice(), |command| execute(command))
             .map_err(|error| format!("restart env apply failed for '{session_name}': {error}"))?;
     }
     let command = restart_resume_command(
         &session_name,
-        workspace.agent,
+        workspace,
         resume_command.as_str(),
         permission_mode,
+        agent_env,
+        sandbox,
+        status_hooks,
     );
     execute_command_with(command.as_slice(), |command| execute(command))
         .map_err(|error| format!("restart resume command failed for '{session_name}': {error}"))
 }
 
-fn capture_output_with_process(
-    target_session: &str,
-    scrollback_lines: usize,
-    include_escape_sequences: bool,
-) -> std::io::Result<String> {
-    let mut args = vec![
-        "capture-pane".to_string(),
-        "-p".to_string(),
-        "-N".to_string(),
-    ];
-    if include_escape_sequences {
-        args.push("-e".to_string());
-    }
-    args.push("-t".to_string());
-    args.push(target_session.to_string());
-    args.push("-S".to_string());
-    args.push(format!("-{scrollback_lines}"));
-
-    let output = std::process::Command::new("tmux").args(args).output()?;
-    if !output.status.success() {
-        let stderr = crate::infrastructure::process::stderr_or_status(&output);
-        return Err(std::io::Error::other(format!(
-            "tmux capture-pane failed for '{target_session}': {stderr}"
-        )));
+/// Exits the agent running in `session_name` and returns the resume command it prints.
+pub fn exit_agent_for_resume_command_with_io(
+    agent: &AgentType,
+    session_name: &str,
+    mut execute: impl FnMut(&[String]) -> std::io::Result<()>,
+    mut capture_output: impl FnMut(&str, usize, bool) -> std::io::Result<String>,
+) -> Result<String, String> {
+    let Some(exit_input) = restart_exit_input(agent) else {
+        return Err(format!("resume capture unsupported for {}", agent.label()));
+    };
+    for command in restart_exit_plan(session_name, exit_input) {
+        execute_command_with(command.as_slice(), |command| execute(command)).map_err(|error| {
+            format!("restart exit command failed for '{session_name}': {error}")
+        })?;
     }
 
-    String::from_utf8(output.stdout)
-        .map_err(|error| std::io::Error::other(format!("tmux output utf8 decode failed: {error}")))
+    wait_for_resume_command(agent, session_name, &mut capture_output)
+}
+
+pub fn exit_agent_for_resume_command(
+    agent: &AgentType,
+    session_name: &str,
+) -> Result<String, String> {
+    exit_agent_for_resume_command_with_io(
+        agent,
+        session_name,
+        crate::infrastructure::process::execute_command,
+        capture_session_output,
+    )
 }
 
 fn restart_agent_env_command(
@@ -225,6 +244,8 @@ pub fn execute_restart_workspace_in_pane_with_result(
     workspace: &Workspace,
     permission_mode: PermissionMode,
     agent_env: Vec<(String, String)>,
+    sandbox: Option<SandboxConfig>,
+    status_hooks: bool,
 ) -> SessionExecutionResult {
     let workspace_name = workspace.name.clone();
     let workspace_path = workspace.path.clone();
@@ -233,8 +254,10 @@ pub fn execute_restart_workspace_in_pane_with_result(
         workspace,
         permission_mode,
         &agent_env,
+        sandbox.as_ref(),
+        status_hooks,
         crate::infrastructure::process::execute_command,
-        capture_output_with_process,
+        capture_session_output,
     );
     SessionExecutionResult {
         workspace_name,
@@ -251,12 +274,14 @@ mod tests {
     use std::time::Duration;
 
     use crate::domain::{AgentType, PermissionMode, WorkspaceStatus};
+    use crate::infrastructure::config::{SandboxBackend, SandboxConfig, SandboxNetwork};
     use crate::test_support::unique_test_dir;
 
     use super::super::status::{
         codex_session_permission_mode, infer_claude_permission_mode_in_home,
         infer_codex_permission_mode_in_home,
     };
+    use super::super::status_hooks::command_with_status_hooks;
     use super::{extract_agent_resume_command, restart_workspace_in_pane_with_io};
 
     fn fixture_workspace(name: &str, is_main: bool) -> crate::domain::Workspace {
@@ -286,7 +311,7 @@ mod tests {
         old hint: claude --resume old-session\n\
         another line\n\
         run this next: claude --resume abc123xyz.\n";
-        let resume = extract_agent_resume_command(AgentType::Claude, output);
+        let resume = extract_agent_resume_command(&AgentType::Claude, output);
         assert_eq!(resume.as_deref(), Some("claude --resume abc123xyz"));
     }
 
@@ -297,7 +322,7 @@ mod tests {
         \n\
         Resume this session with:\n\
         claude --resume e610b734-e6b8-4b1f-b42f-f3ddeb817467\n";
-        let resume = extract_agent_resume_command(AgentType::Claude, output);
+        let resume = extract_agent_resume_command(&AgentType::Claude, output);
         assert_eq!(
             resume.as_deref(),
             Some("claude --resume e610b734-e6b8-4b1f-b42f-f3ddeb817467")
@@ -309,7 +334,7 @@ mod tests {
         let output = "\
         Resume this session with:\n\
         claude --dangerously-skip-permissions --resume e610b734-e6b8-4b1f-b42f-f3ddeb817467\n";
-        let resume = extract_agent_resume_command(AgentType::Claude, output);
+        let resume = extract_agent_resume_command(&AgentType::Claude, output);
         assert_eq!(
             resume.as_deref(),
             Some("claude --resume e610b734-e6b8-4b1f-b42f-f3ddeb817467")
@@ -319,7 +344,7 @@ mod tests {
     #[test]
     fn extract_agent_resume_command_parses_claude_short_resume_flag() {
         let output = "Run this next: claude -r e610b734-e6b8-4b1f-b42f-f3ddeb817467";
-        let resume = extract_agent_resume_command(AgentType::Claude, output);
+        let resume = extract_agent_resume_command(&AgentType::Claude, output);
         assert_eq!(
             resume.as_deref(),
             Some("claude --resume e610b734-e6b8-4b1f-b42f-f3ddeb817467")
@@ -330,7 +355,7 @@ mod tests {
     fn extract_agent_resume_command_parses_real_codex_exit_output() {
         let output = "\
         To continue this session, run codex resume 019c83c1-26c3-7fb0-bd4d-51bb9d6e7701\n";
-        let resume = extract_agent_resume_command(AgentType::Codex, output);
+        let resume = extract_agent_resume_command(&AgentType::Codex, output);
         assert_eq!(
             resume.as_deref(),
             Some("codex resume 019c83c1-26c3-7fb0-bd4d-51bb9d6e7701")
@@ -341,7 +366,7 @@ mod tests {
     fn extract_agent_resume_command_normalizes_codex_dash_resume_to_subcommand() {
         let output =
             "To continue this session, run codex --resume 019c83c1-26c3-7fb0-bd4d-51bb9d6e7701\n";
-        let resume = extract_agent_resume_command(AgentType::Codex, output);
+        let resume = extract_agent_resume_command(&AgentType::Codex, output);
         assert_eq!(
             resume.as_deref(),
             Some("codex resume 019c83c1-26c3-7fb0-bd4d-51bb9d6e7701")
@@ -355,7 +380,7 @@ mod tests {
         codex resume is\n\
         Token usage: total=1011482\n\
         To continue this session, run codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba\n";
-        let resume = extract_agent_resume_command(AgentType::Codex, output);
+        let resume = extract_agent_resume_command(&AgentType::Codex, output);
         assert_eq!(
             resume.as_deref(),
             Some("codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba")
@@ -368,7 +393,7 @@ mod tests {
         codex resume <id>\n\
         Token usage: total=587331\n\
         To continue this session, run codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba\n";
-        let resume = extract_agent_resume_command(AgentType::Codex, output);
+        let resume = extract_agent_resume_command(&AgentType::Codex, output);
         assert_eq!(
             resume.as_deref(),
             Some("codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba")
@@ -381,7 +406,7 @@ mod tests {
         codex resume run-1234\".to_string(),\n\
         Token usage: total=587331\n\
         To continue this session, run codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba\n";
-        let resume = extract_agent_resume_command(AgentType::Codex, output);
+        let resume = extract_agent_resume_command(&AgentType::Codex, output);
         assert_eq!(
             resume.as_deref(),
             Some("codex resume 019c92cf-2410-7ec3-a8bd-b203b83a6fba")
@@ -545,6 +570,8 @@ mod tests {
             &workspace,
             PermissionMode::Default,
             &[],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -600,6 +627,8 @@ mod tests {
             &workspace,
             PermissionMode::Default,
             &[],
+            None,
+            true,
             |_command| Ok(()),
             |_session_name, _scrollback_lines, _include_escape_sequences| {
                 if captures.is_empty() {
@@ -622,6 +651,8 @@ mod tests {
             &workspace,
             PermissionMode::Default,
             &[],
+            None,
+            true,
             |_command| Ok(()),
             |_session_name, _scrollback_lines, _include_escape_sequences| {
                 call_count = call_count.saturating_add(1);
@@ -653,6 +684,8 @@ mod tests {
             &workspace,
             PermissionMode::Default,
             &[],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -681,7 +714,12 @@ mod tests {
                     "send-keys".to_string(),
                     "-t".to_string(),
                     "grove-ws-feature-a".to_string(),
-                    "codex resume run-1234".to_string(),
+                    command_with_status_hooks(
+                        &AgentType::Codex,
+                        "codex resume run-1234",
+                        &workspace.path,
+                        true,
+                    ),
                     "Enter".to_string(),
                 ],
             ]
@@ -702,6 +740,8 @@ mod tests {
                 ("FOO".to_string(), "bar".to_string()),
                 ("BAR".to_string(), "baz".to_string()),
             ],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -733,12 +773,64 @@ mod tests {
                 "send-keys".to_string(),
                 "-t".to_string(),
                 "grove-ws-feature-a".to_string(),
-                "codex resume run-1234".to_string(),
+                command_with_status_hooks(
+                    &AgentType::Codex,
+                    "codex resume run-1234",
+                    &workspace.path,
+                    true,
+                ),
                 "Enter".to_string(),
             ]
         );
     }
 
+    #[test]
+    fn restart_workspace_in_pane_with_io_resumes_inside_sandbox() {
+        let mut workspace = fixture_workspace("feature-a", false);
+        workspace.agent = AgentType::Codex;
+        let mut commands = Vec::new();
+        let mut captures = vec!["run codex resume run-1234".to_string()];
+        let sandbox = SandboxConfig {
+            backend: SandboxBackend::Podman {
+                image: "agent:latest".to_string(),
+            },
+            network: SandboxNetwork::Host,
+            mounts: Vec::new(),
+            read_only_mounts: Vec::new(),
+        };
+
+        let result = restart_workspace_in_pane_with_io(
+            &workspace,
+            PermissionMode::Default,
+            &[],
+            Some(&sandbox),
+            true,
+            |command| {
+                commands.push(command.to_vec());
+                Ok(())
+            },
+            |_session_name, _scrollback_lines, _include_escape_sequences| {
+                if captures.is_empty() {
+                    return Ok(String::new());
+                }
+                Ok(captures.remove(0))
+            },
+        );
+
+        assert!(result.is_ok());
+        let resume = &commands[commands.len() - 1][4];
+        assert!(resume.starts_with("podman run --rm -it --userns=keep-id --network host"));
+        assert!(resume.ends_with(&format!(
+            "'agent:latest' {}",
+            command_with_status_hooks(
+                &AgentType::Codex,
+                "codex resume run-1234",
+                &workspace.path,
+                true
+            )
+        )));
+    }
+
     #[test]
     fn restart_workspace_in_pane_with_io_adds_permission_mode_for_codex_resume() {
         let mut workspace = fixture_workspace("feature-a", false);
@@ -750,6 +842,8 @@ mod tests {
             &workspace,
             PermissionMode::Unsafe,
             &[],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -770,7 +864,12 @@ mod tests {
                 "send-keys".to_string(),
                 "-t".to_string(),
                 "grove-ws-feature-a".to_string(),
-                "codex --dangerously-bypass-approvals-and-sandbox resume run-1234".to_string(),
+                command_with_status_hooks(
+                    &AgentType::Codex,
+                    "codex --dangerously-bypass-approvals-and-sandbox resume run-1234",
+                    &workspace.path,
+                    true,
+                ),
                 "Enter".to_string(),
             ]
         );
@@ -786,6 +885,8 @@ mod tests {
             &workspace,
             PermissionMode::Unsafe,
             &[],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -823,6 +924,8 @@ mod tests {
             &workspace,
             PermissionMode::Unsafe,
             &[],
+            None,
+            true,
             |command| {
                 commands.push(command.to_vec());
                 Ok(())
@@ -843,7 +946,12 @@ mod tests {
                 "send-keys".to_string(),
                 "-t".to_string(),
                 "grove-ws-feature-a".to_string(),
-                "codex --dangerously-bypass-approvals-and-sandbox resume run-1234".to_string(),
+                command_with_status_hooks(
+                    &AgentType::Codex,
+                    "codex --dangerously-bypass-approvals-and-sandbox resume run-1234",
+                    &workspace.path,
+                    true,
+                ),
                 "Enter".to_string(),
             ]
         );
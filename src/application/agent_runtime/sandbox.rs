@@ -0,0 +1,365 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::config::{SandboxBackend, SandboxConfig, SandboxNetwork};
+
+use super::launch_plan::shell_quote;
+
+/// Shared git paths a commit from a linked worktree writes to.
+const COMMON_GIT_WRITABLE: [&str; 4] = ["objects", "refs", "logs", "packed-refs"];
+/// Git paths that make git run code on the host, never writable.
+const GIT_PROTECTED: [&str; 2] = ["hooks", "config"];
+
+/// A host path mounted at the same location inside the sandbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Mount {
+    path: PathBuf,
+    writable: bool,
+}
+
+/// Prefixes `command` so it runs inside `sandbox`, with `root` and the parts
+/// of git directories its worktrees commit to as the only writable project
+/// paths. The wrapper exits with the agent's status, so the exit-code hook
+/// and resume capture keep working on the host shell.
+pub(super) fn sandboxed_command(
+    sandbox: &SandboxConfig,
+    root: &Path,
+    agent_env: &[(String, String)],
+    command: &str,
+) -> String {
+    let project = project_mounts(root);
+    let (program, user, image) = match &sandbox.backend {
+        SandboxBackend::Bubblewrap => {
+            let mut args = bubblewrap_args(sandbox, root, &project);
+            args.push(command.to_string());
+            return args.join(" ");
+        }
+        SandboxBackend::Podman { image } => ("podman", "--userns=keep-id", image),
+        SandboxBackend::Docker { image } => ("docker", "--user \"$(id -u):$(id -g)\"", image),
+    };
+    let mut args = container_args(program, user, sandbox, root, &project, agent_env);
+    args.push(shell_quote(image));
+    args.push(command.to_string());
+    args.join(" ")
+}
+
+fn bubblewrap_args(sandbox: &SandboxConfig, root: &Path, project: &[Mount]) -> Vec<String> {
+    let mut args = [
+        "bwrap",
+        "--die-with-parent",
+        "--ro-bind / /",
+        "--dev /dev",
+        "--proc /proc",
+        "--tmpfs /tmp",
+        "--tmpfs \"$HOME\"",
+    ]
+    .map(str::to_string)
+    .to_vec();
+    for path in sandbox.read_only_mounts.iter().map(|path| mount_path(path)) {
+        args.push(format!("--ro-bind {path} {path}"));
+    }
+    // Later binds win, so the order of `project` decides what stays read-only.
+    for mount in project {
+        let path = quoted_path(&mount.path);
+        let flag = if mount.writable {
+            "--bind"
+        } else {
+            "--ro-bind"
+        };
+        args.push(format!("{flag} {path} {path}"));
+    }
+    for path in sandbox.mounts.iter().map(|path| mount_path(path)) {
+        args.push(format!("--bind {path} {path}"));
+    }
+    args.push("--unshare-all".to_string());
+    if sandbox.network == SandboxNetwork::Host {
+        args.push("--share-net".to_string());
+    }
+    args.push(format!("--chdir {}", quoted_path(root)));
+    args.push("--".to_string());
+    args
+}
+
+fn container_args(
+    program: &str,
+    user: &str,
+    sandbox: &SandboxConfig,
+    root: &Path,
+    project: &[Mount],
+    agent_env: &[(String, String)],
+) -> Vec<String> {
+    let network = match sandbox.network {
+        SandboxNetwork::Host => "host",
+        SandboxNetwork::None => "none",
+    };
+    let mut args = vec![
+        program.to_string(),
+        "run --rm -it".to_string(),
+        user.to_string(),
+        format!("--network {network}"),
+        format!("--workdir {}", quoted_path(root)),
+        "--env HOME --env TERM".to_string(),
+    ];
+    for (key, _) in agent_env {
+        args.push(format!("--env {key}"));
+    }
+    // Nested volumes are mounted below their parents, so the deeper,
+    // read-only git paths win here too.
+    for mount in project {
+        let path = quoted_path(&mount.path);
+        let suffix = if mount.writable { "" } else { ":ro" };
+        args.push(format!("--volume {path}:{path}{suffix}"));
+    }
+    for path in sandbox.mounts.iter().map(|path| mount_path(path)) {
+        args.push(format!("--volume {path}:{path}"));
+    }
+    for path in sandbox.read_only_mounts.iter().map(|path| mount_path(path)) {
+        args.push(format!("--volume {path}:{path}:ro"));
+    }
+    args
+}
+
+/// `root` plus the git paths commits from `root` and its direct children
+/// write to, so a worktree, or a task root holding several, can commit. A
+/// shared git directory is mounted read-only with only its object, ref and
+/// per-worktree paths writable, and every `hooks` and `config` is mounted
+/// read-only last, so the agent cannot plant code that git runs on the host.
+fn project_mounts(root: &Path) -> Vec<Mount> {
+    let mut mounts = Vec::new();
+    let mut push = |path: PathBuf, writable: bool| {
+        if !mounts.iter().any(|mount: &Mount| mount.path == path) {
+            mounts.push(Mount { path, writable });
+        }
+    };
+    push(root.to_path_buf(), true);
+
+    let children = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path());
+    let mut common_dirs = Vec::new();
+    for worktree in std::iter::once(root.to_path_buf()).chain(children) {
+        let dot_git = worktree.join(".git");
+        if dot_git.is_dir() {
+            common_dirs.push(dot_git);
+            continue;
+        }
+        let Some(git_dir) = linked_worktree_git_dir(&worktree) else {
+            continue;
+        };
+        let Some(common_dir) = git_dir.parent().and_then(Path::parent) else {
+            continue;
+        };
+        if !common_dirs.iter().any(|dir| dir == common_dir) {
+            push(common_dir.to_path_buf(), false);
+            for name in COMMON_GIT_WRITABLE {
+                let path = common_dir.join(name);
+                if path.exists() {
+                    push(path, true);
+                }
+            }
+            common_dirs.push(common_dir.to_path_buf());
+        }
+        if git_dir.exists() {
+            push(git_dir, true);
+        }
+    }
+    for common_dir in common_dirs {
+        for name in GIT_PROTECTED {
+            let path = common_dir.join(name);
+            if path.exists() {
+                mounts.retain(|mount| mount.path != path);
+                mounts.push(Mount {
+                    path,
+                    writable: false,
+                });
+            }
+        }
+    }
+    mounts
+}
+
+/// A linked worktree's `.git` file points at `<common>/worktrees/<name>`.
+fn linked_worktree_git_dir(worktree: &Path) -> Option<PathBuf> {
+    let contents = fs::read_to_string(worktree.join(".git")).ok()?;
+    let git_dir = PathBuf::from(contents.trim().strip_prefix("gitdir:")?.trim());
+    Some(if git_dir.is_absolute() {
+        git_dir
+    } else {
+        worktree.join(git_dir)
+    })
+}
+
+fn quoted_path(path: &Path) -> String {
+    shell_quote(path.to_string_lossy().as_ref())
+}
+
+/// Leaves `~/` for the launching shell to expand.
+fn mount_path(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("\"$HOME\"/{}", shell_quote(rest)),
+        None => shell_quote(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::infrastructure::config::{SandboxBackend, SandboxConfig, SandboxNetwork};
+    use crate::test_support::unique_test_dir;
+
+    use super::super::launch_plan::shell_quote;
+    use super::{Mount, project_mounts, sandboxed_command};
+
+    fn sandbox(backend: SandboxBackend, network: SandboxNetwork) -> SandboxConfig {
+        SandboxConfig {
+            backend,
+            network,
+            mounts: vec!["~/.claude".to_string()],
+            read_only_mounts: vec!["/etc/ssl".to_string()],
+        }
+    }
+
+    #[test]
+    fn bubblewrap_binds_worktree_and_hides_home() {
+        let command = sandboxed_command(
+            &sandbox(SandboxBackend::Bubblewrap, SandboxNetwork::Host),
+            &PathBuf::from("/repos/grove-auth"),
+            &[],
+            "claude --dangerously-skip-permissions",
+        );
+
+        assert_eq!(
+            command,
+            "bwrap --die-with-parent --ro-bind / / --dev /dev --proc /proc --tmpfs /tmp \
+             --tmpfs \"$HOME\" --ro-bind '/etc/ssl' '/etc/ssl' \
+             --bind '/repos/grove-auth' '/repos/grove-auth' \
+             --bind \"$HOME\"/'.claude' \"$HOME\"/'.claude' --unshare-all --share-net \
+             --chdir '/repos/grove-auth' -- claude --dangerously-skip-permissions"
+        );
+    }
+
+    #[test]
+    fn container_sandbox_forwards_agent_env_and_blocks_network() {
+        let command = sandboxed_command(
+            &sandbox(
+                SandboxBackend::Docker {
+                    image: "ghcr.io/acme/agent:latest".to_string(),
+                },
+                SandboxNetwork::None,
+            ),
+            &PathBuf::from("/repos/grove-auth"),
+            &[("CODEX_HOME".to_string(), "/tmp/codex".to_string())],
+            "codex",
+        );
+
+        assert_eq!(
+            command,
+            "docker run --rm -it --user \"$(id -u):$(id -g)\" --network none \
+             --workdir '/repos/grove-auth' --env HOME --env TERM --env CODEX_HOME \
+             --volume '/repos/grove-auth':'/repos/grove-auth' \
+             --volume \"$HOME\"/'.claude':\"$HOME\"/'.claude' \
+             --volume '/etc/ssl':'/etc/ssl':ro 'ghcr.io/acme/agent:latest' codex"
+        );
+    }
+
+    #[test]
+    fn linked_worktree_git_hooks_and_config_stay_read_only() {
+        let root = unique_test_dir("sandbox-writable");
+        let common = root.join("repo/.git");
+        let git_dir = common.join("worktrees/flohome-launch");
+        for dir in [
+            "hooks",
+            "objects",
+            "refs",
+            "logs",
+            "worktrees/flohome-launch",
+        ] {
+            fs::create_dir_all(common.join(dir)).expect("git dir should exist");
+        }
+        fs::write(common.join("config"), "[core]\n").expect("config should write");
+        let task_root = root.join("task");
+        let worktree = task_root.join("flohome");
+        fs::create_dir_all(&worktree).expect("worktree dir should exist");
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .expect("git file should write");
+
+        let mounts = project_mounts(&task_root);
+        let writable = mounts
+            .iter()
+            .filter(|mount| mount.writable)
+            .map(|mount| mount.path.clone())
+            .collect::<Vec<PathBuf>>();
+        assert_eq!(
+            writable,
+            vec![
+                task_root.clone(),
+                common.join("objects"),
+                common.join("refs"),
+                common.join("logs"),
+                git_dir.clone(),
+            ]
+        );
+        let last_two = &mounts[mounts.len() - 2..];
+        assert_eq!(
+            last_two,
+            &[
+                Mount {
+                    path: common.join("hooks"),
+                    writable: false,
+                },
+                Mount {
+                    path: common.join("config"),
+                    writable: false,
+                },
+            ]
+        );
+
+        let command = sandboxed_command(
+            &sandbox(SandboxBackend::Bubblewrap, SandboxNetwork::None),
+            &task_root,
+            &[],
+            "claude",
+        );
+        let hooks = shell_quote(common.join("hooks").to_string_lossy().as_ref());
+        assert!(command.contains(format!("--ro-bind {hooks} {hooks}").as_str()));
+        assert!(!command.contains(format!("--bind {hooks} ").as_str()));
+        let common_path = shell_quote(common.to_string_lossy().as_ref());
+        assert!(!command.contains(format!("--bind {common_path} ").as_str()));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn main_checkout_git_hooks_and_config_stay_read_only() {
+        let root = unique_test_dir("sandbox-main-checkout");
+        fs::create_dir_all(root.join(".git/hooks")).expect("hooks dir should exist");
+        fs::write(root.join(".git/config"), "[core]\n").expect("config should write");
+
+        assert_eq!(
+            project_mounts(&root),
+            vec![
+                Mount {
+                    path: root.clone(),
+                    writable: true,
+                },
+                Mount {
+                    path: root.join(".git/hooks"),
+                    writable: false,
+                },
+                Mount {
+                    path: root.join(".git/config"),
+                    writable: false,
+                },
+            ]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
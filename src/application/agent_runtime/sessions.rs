@@ -199,7 +199,7 @@ mod tests {
     use std::collections::HashSet;
     use std::path::PathBuf;
 
-    use crate::domain::{AgentType, PermissionMode, WorkspaceStatus};
+    use crate::domain::{AgentType, PermissionMode, PortRange, WorkspaceStatus};
 
     use super::super::LivePreviewTarget;
     use super::super::launch_plan::{
@@ -379,6 +379,52 @@ mod tests {
         assert_eq!(request.capture_rows, Some(40));
     }
 
+    #[test]
+    fn launch_requests_export_allocated_ports_before_project_env() {
+        let mut workspace = fixture_workspace("feature", false);
+        workspace.ports = Some(PortRange {
+            base: 20_010,
+            count: 10,
+        });
+        let agent_request = launch_request_for_workspace(
+            &workspace,
+            None,
+            crate::infrastructure::config::ThemeName::default(),
+            None,
+            PermissionMode::Default,
+            vec![("PORT".to_string(), "3000".to_string())],
+            None,
+        );
+        let shell_request = shell_launch_request_for_workspace(
+            &workspace,
+            "grove-ws-feature-shell".to_string(),
+            String::new(),
+            crate::infrastructure::config::ThemeName::default(),
+            None,
+            None,
+            None,
+        );
+
+        let ports = vec![
+            ("PORT".to_string(), "20010".to_string()),
+            ("GROVE_PORT_BASE".to_string(), "20010".to_string()),
+            ("GROVE_PORT_COUNT".to_string(), "10".to_string()),
+        ];
+        assert_eq!(agent_request.agent_env[..3], ports[..]);
+        assert_eq!(
+            agent_request.agent_env[3],
+            ("PORT".to_string(), "3000".to_string())
+        );
+        assert_eq!(shell_request.env, ports);
+        assert!(
+            build_shell_launch_plan(&shell_request)
+                .pre_launch_cmds
+                .iter()
+                .any(|command| command.iter().any(|arg| arg
+                    == "export PORT='20010' GROVE_PORT_BASE='20010' GROVE_PORT_COUNT='10'"))
+        );
+    }
+
     #[test]
     fn build_shell_launch_plan_skips_send_keys_when_command_is_empty() {
         let request = shell_launch_request_for_workspace(
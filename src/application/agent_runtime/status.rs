@@ -4,8 +4,13 @@ use std::time::Duration;
 #[cfg(test)]
 use crate::domain::PermissionMode;
 use crate::domain::{AgentType, WorkspaceStatus};
+use crate::infrastructure::config::NotificationReason;
 
 use super::agents;
+use super::status_hooks::hook_status_observation;
+use super::status_rules::{
+    StatusRuleInput, StatusRuleMatch, StatusRules, first_matching_rule, has_session_record_rules,
+};
 use super::{SESSION_ACTIVITY_THRESHOLD, SessionActivity, WAITING_PATTERNS, WAITING_TAIL_LINES};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,11 +19,14 @@ pub(crate) struct WorkspaceStatusObservation {
     pub(crate) status: WorkspaceStatus,
     pub(crate) recent_activity: bool,
     pub(crate) waiting_excerpt: Option<String>,
+    /// Attention set by the status rule that reported waiting, if any.
+    pub(crate) waiting_attention: Option<NotificationReason>,
 }
 
 #[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct BackgroundStatusObservationContext<'a> {
-    pub(crate) agent: AgentType,
+    pub(crate) rules: &'a StatusRules,
+    pub(crate) agent: &'a AgentType,
     pub(crate) workspace_path: &'a Path,
     pub(crate) home_dir: Option<&'a Path>,
     pub(crate) activity_threshold: Duration,
@@ -27,17 +35,19 @@ pub(crate) struct BackgroundStatusObservationContext<'a> {
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn detect_status_with_session_override(
+    rules: &StatusRules,
     output: &str,
     session_activity: SessionActivity,
     is_main: bool,
     has_live_session: bool,
     supported_agent: bool,
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     session_name: &str,
 ) -> WorkspaceStatus {
     let home_dir = dirs::home_dir();
     detect_status_with_session_override_in_home(StatusOverrideContext {
+        rules,
         output,
         session_activity,
         is_main,
@@ -51,47 +61,123 @@ pub(crate) fn detect_status_with_session_override(
     })
 }
 
+#[cfg(test)]
 pub(crate) fn detect_waiting_prompt(output: &str) -> Option<String> {
+    waiting_pattern_line(output)
+        .or_else(|| shortcuts_hint_line(output))
+        .or_else(|| try_prompt_line(output))
+}
+
+/// The waiting prompt and its attention from the first status rule that
+/// fires on `output`, if that rule reports waiting.
+pub(crate) fn detect_waiting_rule(
+    rules: &StatusRules,
+    agent: &AgentType,
+    output: &str,
+) -> Option<StatusRuleMatch> {
+    first_matching_rule(
+        rules,
+        &StatusRuleInput {
+            agent,
+            pane: Some(output),
+            record: None,
+        },
+    )
+    .filter(|rule_match| rule_match.status == WorkspaceStatus::Waiting)
+}
+
+fn waiting_tail_lines(output: &str) -> Vec<&str> {
     let lines: Vec<&str> = output.lines().collect();
     let start = lines.len().saturating_sub(WAITING_TAIL_LINES);
-    let tail_lines = &lines[start..];
+    lines[start..].to_vec()
+}
 
-    for line in tail_lines {
-        let lower = line.to_ascii_lowercase();
-        if WAITING_PATTERNS
-            .iter()
-            .any(|pattern| lower.contains(pattern))
-        {
-            return Some(line.trim().to_string());
-        }
-    }
+pub(super) fn waiting_pattern_line(output: &str) -> Option<String> {
+    waiting_tail_lines(output)
+        .into_iter()
+        .find(|line| {
+            let lower = line.to_ascii_lowercase();
+            WAITING_PATTERNS
+                .iter()
+                .any(|pattern| lower.contains(pattern))
+        })
+        .map(|line| line.trim().to_string())
+}
 
-    for line in tail_lines.iter().rev() {
-        if line.to_ascii_lowercase().contains("for shortcuts") {
-            return Some(line.trim().to_string());
-        }
+pub(super) fn shortcuts_hint_line(output: &str) -> Option<String> {
+    waiting_tail_lines(output)
+        .into_iter()
+        .rev()
+        .find(|line| line.to_ascii_lowercase().contains("for shortcuts"))
+        .map(|line| line.trim().to_string())
+}
+
+pub(super) fn try_prompt_line(output: &str) -> Option<String> {
+    let tail_lines = waiting_tail_lines(output);
+    let last_non_empty = tail_lines
+        .iter()
+        .rev()
+        .find(|line| !line.trim().is_empty())?;
+    let trimmed = last_non_empty.trim_start();
+    let prefix = trimmed.chars().next()?;
+    if !matches!(prefix, '›' | '❯' | '»') {
+        return None;
     }
+    let without_prefix = trimmed.trim_start_matches(['›', '❯', '»']).trim_start();
+    without_prefix
+        .to_ascii_lowercase()
+        .starts_with("try ")
+        .then(|| trimmed.to_string())
+}
 
-    if let Some(last_non_empty) = tail_lines.iter().rev().find(|line| !line.trim().is_empty()) {
-        let trimmed = last_non_empty.trim_start();
-        let prefix = trimmed.chars().next()?;
-        if matches!(prefix, '›' | '❯' | '»') {
-            let without_prefix = trimmed.trim_start_matches(['›', '❯', '»']).trim_start();
-            if without_prefix.to_ascii_lowercase().starts_with("try ") {
-                return Some(trimmed.to_string());
-            }
-        }
+/// The unclosed reasoning tag or text that marks `output` as thinking.
+pub(super) fn thinking_marker(output: &str) -> Option<&'static str> {
+    if has_unclosed_tag_ci(output, b"<thinking>", b"</thinking>") {
+        return Some("<thinking>");
+    }
+    if has_unclosed_tag_ci(output, b"<internal_monologue>", b"</internal_monologue>") {
+        return Some("<internal_monologue>");
     }
+    ["thinking...", "reasoning about"]
+        .into_iter()
+        .find(|marker| contains_ascii_ci(output.as_bytes(), marker.as_bytes()))
+}
 
-    None
+/// What the agent's latest hook event says it waits for, e.g. the tool a
+/// permission request is about.
+pub(crate) fn hook_waiting_excerpt(workspace_path: &Path) -> Option<String> {
+    hook_status_observation(workspace_path, SESSION_ACTIVITY_THRESHOLD)?.waiting_excerpt
+}
+
+/// Whether a waiting prompt asks to approve a tool call rather than for input.
+pub(crate) fn is_permission_wall_prompt(prompt: &str) -> bool {
+    let lower = prompt.to_ascii_lowercase();
+    ["approve", "allow", "confirm", "do you want", "permission"]
+        .iter()
+        .any(|pattern| lower.contains(pattern))
 }
 
+/// Whether a waiting agent sits at a permission wall: the attention its
+/// status rule set, otherwise a guess from the prompt text.
+pub(crate) fn waiting_is_permission_wall(
+    prompt: Option<&str>,
+    attention: Option<NotificationReason>,
+) -> bool {
+    match attention {
+        Some(reason) => reason == NotificationReason::PermissionWall,
+        None => prompt.is_some_and(is_permission_wall_prompt),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn detect_status(
+    rules: &StatusRules,
     output: &str,
     session_activity: SessionActivity,
     is_main: bool,
     has_live_session: bool,
     supported_agent: bool,
+    agent: &AgentType,
     session_name: &str,
 ) -> WorkspaceStatus {
     if is_main && !has_live_session {
@@ -114,16 +200,15 @@ pub(crate) fn detect_status(
         };
     }
 
-    if detect_waiting_prompt(output).is_some() {
-        return WorkspaceStatus::Waiting;
-    }
-
-    if has_unclosed_tag_ci(output, b"<thinking>", b"</thinking>")
-        || has_unclosed_tag_ci(output, b"<internal_monologue>", b"</internal_monologue>")
-        || contains_ascii_ci(output.as_bytes(), b"thinking...")
-        || contains_ascii_ci(output.as_bytes(), b"reasoning about")
-    {
-        return WorkspaceStatus::Thinking;
+    if let Some(rule_match) = first_matching_rule(
+        rules,
+        &StatusRuleInput {
+            agent,
+            pane: Some(output),
+            record: None,
+        },
+    ) {
+        return rule_match.status;
     }
 
     match session_activity {
@@ -133,12 +218,13 @@ pub(crate) fn detect_status(
 }
 
 pub(crate) struct StatusOverrideContext<'a> {
+    pub(crate) rules: &'a StatusRules,
     pub(crate) output: &'a str,
     pub(crate) session_activity: SessionActivity,
     pub(crate) is_main: bool,
     pub(crate) has_live_session: bool,
     pub(crate) supported_agent: bool,
-    pub(crate) agent: AgentType,
+    pub(crate) agent: &'a AgentType,
     pub(crate) workspace_path: &'a Path,
     pub(crate) home_dir: Option<&'a Path>,
     pub(crate) activity_threshold: Duration,
@@ -149,13 +235,29 @@ pub(crate) fn detect_status_with_session_override_in_home(
     context: StatusOverrideContext<'_>,
 ) -> WorkspaceStatus {
     let detected = detect_status(
+        context.rules,
         context.output,
         context.session_activity,
         context.is_main,
         context.has_live_session,
         context.supported_agent,
+        context.agent,
         context.session_name,
     );
+    // Exit codes and sessionless states stay ahead of hook events.
+    if context.has_live_session
+        && matches!(
+            detected,
+            WorkspaceStatus::Active
+                | WorkspaceStatus::Thinking
+                | WorkspaceStatus::Waiting
+                | WorkspaceStatus::Idle
+        )
+        && let Some(observation) =
+            hook_status_observation(context.workspace_path, context.activity_threshold)
+    {
+        return observation.status;
+    }
     if !matches!(detected, WorkspaceStatus::Active | WorkspaceStatus::Waiting) {
         return detected;
     }
@@ -166,6 +268,14 @@ pub(crate) fn detect_status_with_session_override_in_home(
     if !context.workspace_path.exists() {
         return detected;
     }
+    if let Some(rule_match) = session_record_rule_match(
+        context.rules,
+        context.agent,
+        context.workspace_path,
+        home_dir,
+    ) {
+        return rule_match.status;
+    }
 
     detect_agent_session_status_in_home(
         context.agent,
@@ -177,7 +287,7 @@ pub(crate) fn detect_status_with_session_override_in_home(
 }
 
 pub(crate) fn detect_agent_session_status_in_home(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
     home_dir: &Path,
     activity_threshold: Duration,
@@ -185,8 +295,30 @@ pub(crate) fn detect_agent_session_status_in_home(
     agents::detect_session_status_in_home(agent, workspace_path, home_dir, activity_threshold)
 }
 
+/// The first jsonl status rule matching the latest record of the agent's
+/// session log. Skips reading the log when there is no such rule.
+fn session_record_rule_match(
+    rules: &StatusRules,
+    agent: &AgentType,
+    workspace_path: &Path,
+    home_dir: &Path,
+) -> Option<StatusRuleMatch> {
+    if !has_session_record_rules(rules, agent) {
+        return None;
+    }
+    let record = agents::latest_session_record_in_home(agent, workspace_path, home_dir)?;
+    first_matching_rule(
+        rules,
+        &StatusRuleInput {
+            agent,
+            pane: None,
+            record: Some(&record),
+        },
+    )
+}
+
 pub(crate) fn latest_assistant_attention_marker(
-    agent: AgentType,
+    agent: &AgentType,
     workspace_path: &Path,
 ) -> Option<String> {
     let home_dir = dirs::home_dir()?;
@@ -214,10 +346,32 @@ pub(crate) fn workspace_status_observation_in_home(
             },
             recent_activity: false,
             waiting_excerpt: None,
+            waiting_attention: None,
         });
     }
 
+    if let Some(observation) =
+        hook_status_observation(context.workspace_path, context.activity_threshold)
+    {
+        return Some(observation);
+    }
+
     let home_dir = context.home_dir?;
+    if let Some(rule_match) = session_record_rule_match(
+        context.rules,
+        context.agent,
+        context.workspace_path,
+        home_dir,
+    ) {
+        return Some(WorkspaceStatusObservation {
+            status: rule_match.status,
+            recent_activity: false,
+            waiting_excerpt: (rule_match.status == WorkspaceStatus::Waiting)
+                .then_some(rule_match.matched),
+            waiting_attention: rule_match.attention,
+        });
+    }
+
     agents::status_observation_in_home(
         context.agent,
         context.workspace_path,
@@ -231,7 +385,7 @@ pub(super) fn infer_claude_permission_mode_in_home(
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<PermissionMode> {
-    agents::infer_permission_mode_in_home(AgentType::Claude, workspace_path, home_dir)
+    agents::infer_permission_mode_in_home(&AgentType::Claude, workspace_path, home_dir)
 }
 
 #[cfg(test)]
@@ -239,7 +393,7 @@ pub(super) fn infer_codex_permission_mode_in_home(
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<PermissionMode> {
-    agents::infer_permission_mode_in_home(AgentType::Codex, workspace_path, home_dir)
+    agents::infer_permission_mode_in_home(&AgentType::Codex, workspace_path, home_dir)
 }
 
 #[cfg(test)]
@@ -252,7 +406,7 @@ pub(super) fn latest_claude_assistant_attention_marker_in_home(
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<String> {
-    agents::latest_attention_marker_in_home(AgentType::Claude, workspace_path, home_dir)
+    agents::latest_attention_marker_in_home(&AgentType::Claude, workspace_path, home_dir)
 }
 
 #[cfg(test)]
@@ -260,7 +414,7 @@ pub(super) fn latest_codex_assistant_attention_marker_in_home(
     workspace_path: &Path,
     home_dir: &Path,
 ) -> Option<String> {
-    agents::latest_attention_marker_in_home(AgentType::Codex, workspace_path, home_dir)
+    agents::latest_attention_marker_in_home(&AgentType::Codex, workspace_path, home_dir)
 }
 
 pub(crate) fn exit_code_file_path(session_name: &str) -> std::path::PathBuf {
@@ -315,6 +469,7 @@ mod tests {
 
     use super::super::SessionActivity;
     use super::super::agents::claude_project_dir_name;
+    use super::super::status_rules::StatusRules;
     use super::{
         BackgroundStatusObservationContext, StatusOverrideContext, WorkspaceStatusObservation,
         detect_agent_session_status_in_home, detect_status,
@@ -354,11 +509,13 @@ mod tests {
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 output,
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Waiting
@@ -397,7 +554,7 @@ mod tests {
         .expect("session file should be written");
 
         let status = detect_agent_session_status_in_home(
-            AgentType::Claude,
+            &AgentType::Claude,
             &workspace_path,
             &home,
             Duration::from_secs(0),
@@ -428,7 +585,7 @@ mod tests {
             .expect("session file should be written");
 
         let status = detect_agent_session_status_in_home(
-            AgentType::Codex,
+            &AgentType::Codex,
             &workspace_path,
             &home,
             Duration::from_secs(0),
@@ -505,12 +662,13 @@ mod tests {
             .expect("session file should be written");
 
         let status = detect_status_with_session_override_in_home(StatusOverrideContext {
+            rules: &StatusRules::default(),
             output: "plain output",
             session_activity: SessionActivity::Active,
             is_main: false,
             has_live_session: true,
             supported_agent: true,
-            agent: AgentType::Claude,
+            agent: &AgentType::Claude,
             workspace_path: &workspace_path,
             home_dir: Some(&home),
             activity_threshold: Duration::from_secs(0),
@@ -541,7 +699,8 @@ mod tests {
 
         let observation =
             workspace_status_observation_in_home(BackgroundStatusObservationContext {
-                agent: AgentType::Claude,
+                rules: &StatusRules::default(),
+                agent: &AgentType::Claude,
                 workspace_path: &workspace_path,
                 home_dir: Some(&home),
                 activity_threshold: Duration::from_secs(0),
@@ -553,6 +712,7 @@ mod tests {
                 status: WorkspaceStatus::Waiting,
                 recent_activity: false,
                 waiting_excerpt: None,
+                waiting_attention: None,
             })
         );
 
@@ -563,73 +723,119 @@ mod tests {
     fn status_resolution_core_priority_order() {
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "thinking...",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Thinking
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "allow edit? [y/n]",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Waiting
         );
         assert_eq!(
-            detect_status("", SessionActivity::Active, false, true, true, "no-session"),
+            detect_status(
+                &StatusRules::default(),
+                "",
+                SessionActivity::Active,
+                false,
+                true,
+                true,
+                &AgentType::Claude,
+                "no-session"
+            ),
             WorkspaceStatus::Active
         );
         assert_eq!(
-            detect_status("", SessionActivity::Idle, false, false, true, "no-session"),
+            detect_status(
+                &StatusRules::default(),
+                "",
+                SessionActivity::Idle,
+                false,
+                false,
+                true,
+                &AgentType::Claude,
+                "no-session"
+            ),
             WorkspaceStatus::Idle
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "",
                 SessionActivity::Active,
                 false,
                 true,
                 false,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Unsupported
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "warning: failed to login mcp\nline\nline\n> Implement {feature}\n? for shortcuts\n",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Waiting
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "Do you want to continue?",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Waiting
         );
         assert_eq!(
-            detect_status("", SessionActivity::Active, true, true, true, "no-session"),
+            detect_status(
+                &StatusRules::default(),
+                "",
+                SessionActivity::Active,
+                true,
+                true,
+                true,
+                &AgentType::Claude,
+                "no-session"
+            ),
             WorkspaceStatus::Active
         );
         assert_eq!(
-            detect_status("", SessionActivity::Idle, true, false, true, "no-session"),
+            detect_status(
+                &StatusRules::default(),
+                "",
+                SessionActivity::Idle,
+                true,
+                false,
+                true,
+                &AgentType::Claude,
+                "no-session"
+            ),
             WorkspaceStatus::Main
         );
     }
@@ -640,7 +846,16 @@ mod tests {
         let path = exit_code_file_path(session);
         fs::write(&path, "0\n").expect("exit code file should be written");
 
-        let status = detect_status("", SessionActivity::Active, false, true, true, session);
+        let status = detect_status(
+            &StatusRules::default(),
+            "",
+            SessionActivity::Active,
+            false,
+            true,
+            true,
+            &AgentType::Claude,
+            session,
+        );
         assert_eq!(status, WorkspaceStatus::Done);
 
         let _ = fs::remove_file(&path);
@@ -652,7 +867,16 @@ mod tests {
         let path = exit_code_file_path(session);
         fs::write(&path, "1\n").expect("exit code file should be written");
 
-        let status = detect_status("", SessionActivity::Active, false, true, true, session);
+        let status = detect_status(
+            &StatusRules::default(),
+            "",
+            SessionActivity::Active,
+            false,
+            true,
+            true,
+            &AgentType::Claude,
+            session,
+        );
         assert_eq!(status, WorkspaceStatus::Error);
 
         let _ = fs::remove_file(&path);
@@ -664,7 +888,16 @@ mod tests {
         let path = exit_code_file_path(session);
         fs::write(&path, "130\n").expect("exit code file should be written");
 
-        let status = detect_status("", SessionActivity::Active, false, true, true, session);
+        let status = detect_status(
+            &StatusRules::default(),
+            "",
+            SessionActivity::Active,
+            false,
+            true,
+            true,
+            &AgentType::Claude,
+            session,
+        );
         assert_eq!(status, WorkspaceStatus::Error);
 
         let _ = fs::remove_file(&path);
@@ -673,11 +906,13 @@ mod tests {
     #[test]
     fn missing_exit_code_file_falls_through_to_heuristics() {
         let status = detect_status(
+            &StatusRules::default(),
             "allow edit? [y/n]",
             SessionActivity::Active,
             false,
             true,
             true,
+            &AgentType::Claude,
             "grove-test-no-such-session",
         );
         assert_eq!(status, WorkspaceStatus::Waiting);
@@ -690,11 +925,13 @@ mod tests {
         fs::write(&path, "not-a-number\n").expect("exit code file should be written");
 
         let status = detect_status(
+            &StatusRules::default(),
             "thinking...",
             SessionActivity::Active,
             false,
             true,
             true,
+            &AgentType::Claude,
             session,
         );
         assert_eq!(status, WorkspaceStatus::Thinking);
@@ -709,11 +946,13 @@ mod tests {
         fs::write(&path, "0\n").expect("exit code file should be written");
 
         let status = detect_status(
+            &StatusRules::default(),
             "allow edit? [y/n]",
             SessionActivity::Active,
             false,
             true,
             true,
+            &AgentType::Claude,
             session,
         );
         assert_eq!(status, WorkspaceStatus::Done);
@@ -725,33 +964,39 @@ mod tests {
     fn text_based_done_error_patterns_no_longer_trigger_without_exit_file() {
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "panic: bad",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Active
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "task completed successfully",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Active
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "error: permission denied\n",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Active
@@ -789,33 +1034,39 @@ mod tests {
     fn status_resolution_requires_unclosed_thinking_tags() {
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "<thinking>\nworking\n</thinking>",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Active
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "<thinking>\nworking\n",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Thinking
         );
         assert_eq!(
             detect_status(
+                &StatusRules::default(),
                 "<internal_monologue>\nworking\n",
                 SessionActivity::Active,
                 false,
                 true,
                 true,
+                &AgentType::Claude,
                 "no-session"
             ),
             WorkspaceStatus::Thinking
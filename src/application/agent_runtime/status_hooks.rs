@@ -0,0 +1,372 @@
+//! Status reported by agent hooks instead of scraped from the pane.
+//!
+//! Grove installs per-worktree hook configuration that replaces
+//! `.grove/agent-event` with the latest event: a `<kind> <unix-secs>` line
+//! followed by the agent's JSON payload. Status falls back to screen and
+//! session-log heuristics while the file does not exist.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::application::session_cleanup::now_unix_secs;
+use crate::domain::{AgentType, WorkspaceStatus};
+use crate::infrastructure::config::NotificationReason;
+
+use super::agents;
+use super::launch_plan::shell_quote;
+use super::status::WorkspaceStatusObservation;
+
+pub(crate) const STATUS_EVENT_FILE: &str = ".grove/agent-event";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AgentHookEventKind {
+    /// A prompt was submitted or a tool is running.
+    Working,
+    /// The agent finished its turn and waits for input.
+    TurnComplete,
+    /// The agent needs attention, e.g. a permission request.
+    Notification,
+}
+
+impl AgentHookEventKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Working => "working",
+            Self::TurnComplete => "turn_complete",
+            Self::Notification => "notification",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "working" => Some(Self::Working),
+            "turn_complete" => Some(Self::TurnComplete),
+            "notification" => Some(Self::Notification),
+            _ => None,
+        }
+    }
+}
+
+/// Where the hook command finds the agent's JSON payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HookPayloadSource {
+    Stdin,
+    /// `$1`, for commands run as `sh -c <command> <name> <payload>`.
+    FirstArgument,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AgentHookEvent {
+    pub(crate) kind: AgentHookEventKind,
+    pub(crate) at_unix_secs: u64,
+    pub(crate) message: Option<String>,
+    /// Why a notification needs the user, when the payload says.
+    pub(crate) attention: Option<NotificationReason>,
+}
+
+pub(crate) fn status_event_path(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(STATUS_EVENT_FILE)
+}
+
+/// Shell command an agent hook runs to record `kind`, replacing the event
+/// file atomically. Always exits 0 so a failed write never blocks the agent.
+pub(crate) fn hook_event_command(
+    event_file: &Path,
+    kind: AgentHookEventKind,
+    payload: HookPayloadSource,
+) -> String {
+    let payload = match payload {
+        HookPayloadSource::Stdin => "cat",
+        HookPayloadSource::FirstArgument => "printf '%s\\n' \"$1\"",
+    };
+    format!(
+        "f={}; {{ printf '%s %s\\n' {} \"$(date +%s)\"; {payload}; }} > \"$f.tmp\" \
+         && mv \"$f.tmp\" \"$f\"; exit 0",
+        shell_quote(event_file.to_string_lossy().as_ref()),
+        kind.as_str(),
+    )
+}
+
+/// Writes the agent's hook configuration into `workspace_path` and clears
+/// events left by an earlier session. While `[status_hooks]` is disabled it
+/// leaves agent settings alone and removes hooks an earlier launch installed.
+pub(crate) fn install_status_hooks(
+    agent: &AgentType,
+    workspace_path: &Path,
+    enabled: bool,
+) -> Result<(), String> {
+    if !workspace_path.is_dir() {
+        return Ok(());
+    }
+    if !enabled {
+        return uninstall_status_hooks(workspace_path);
+    }
+    let event_file = status_event_path(workspace_path);
+    if let Some(parent) = event_file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("create {} failed: {error}", parent.display()))?;
+    }
+    remove_status_event(event_file.as_path())?;
+
+    agents::install_status_hooks(agent, workspace_path, event_file.as_path())
+}
+
+/// Removes every agent's hook configuration and the last event from
+/// `workspace_path`, once its agent stops or before it is deleted.
+pub(crate) fn uninstall_status_hooks(workspace_path: &Path) -> Result<(), String> {
+    if !workspace_path.is_dir() {
+        return Ok(());
+    }
+    remove_status_event(status_event_path(workspace_path).as_path())?;
+    // Config-defined agents install no hooks.
+    AgentType::ALL
+        .iter()
+        .try_for_each(|agent| agents::uninstall_status_hooks(agent, workspace_path))
+}
+
+fn remove_status_event(event_file: &Path) -> Result<(), String> {
+    match fs::remove_file(event_file) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("remove {} failed: {error}", event_file.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `command` with the flags some agents need to report events, e.g. Codex's
+/// `notify`, which has no per-worktree config file.
+pub(crate) fn command_with_status_hooks(
+    agent: &AgentType,
+    command: &str,
+    workspace_path: &Path,
+    enabled: bool,
+) -> String {
+    if !enabled {
+        return command.to_string();
+    }
+    agents::command_with_status_hooks(agent, command, status_event_path(workspace_path).as_path())
+}
+
+pub(crate) fn read_status_event(workspace_path: &Path) -> Option<AgentHookEvent> {
+    let content = fs::read_to_string(status_event_path(workspace_path)).ok()?;
+    parse_status_event(content.as_str())
+}
+
+fn parse_status_event(content: &str) -> Option<AgentHookEvent> {
+    let (header, payload) = content.split_once('\n').unwrap_or((content, ""));
+    let (kind, at_unix_secs) = header.trim().split_once(' ')?;
+    let payload = serde_json::from_str::<Value>(payload.trim()).ok();
+    // Claude sends `message`, Codex `last-assistant-message`.
+    let message = payload.as_ref().and_then(|payload| {
+        ["message", "last-assistant-message"]
+            .iter()
+            .find_map(|key| payload.get(*key).and_then(Value::as_str))
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .map(str::to_string)
+    });
+
+    let kind = AgentHookEventKind::parse(kind)?;
+    let attention = if kind == AgentHookEventKind::Notification {
+        notification_attention(payload.as_ref(), message.as_deref())
+    } else {
+        None
+    };
+
+    Some(AgentHookEvent {
+        kind,
+        at_unix_secs: at_unix_secs.trim().parse().ok()?,
+        message,
+        attention,
+    })
+}
+
+/// Claude tags permission prompts with `notification_type`, older versions
+/// only say so in the message.
+fn notification_attention(
+    payload: Option<&Value>,
+    message: Option<&str>,
+) -> Option<NotificationReason> {
+    let notification_type = payload
+        .and_then(|payload| payload.get("notification_type"))
+        .and_then(Value::as_str);
+    let permission = notification_type == Some("permission_prompt")
+        || (notification_type.is_none()
+            && message.is_some_and(|message| message.to_ascii_lowercase().contains("permission")));
+    permission.then_some(NotificationReason::PermissionWall)
+}
+
+/// Status from the latest hook event, or `None` to fall back to heuristics.
+pub(crate) fn hook_status_observation(
+    workspace_path: &Path,
+    activity_threshold: Duration,
+) -> Option<WorkspaceStatusObservation> {
+    let event = read_status_event(workspace_path)?;
+    status_observation_from_event(&event, activity_threshold, now_unix_secs())
+}
+
+fn status_observation_from_event(
+    event: &AgentHookEvent,
+    activity_threshold: Duration,
+    now_unix_secs: u64,
+) -> Option<WorkspaceStatusObservation> {
+    let recent = now_unix_secs.saturating_sub(event.at_unix_secs) <= activity_threshold.as_secs();
+    match event.kind {
+        // No hook fires when the user interrupts a turn, so an old working
+        // event defers to the heuristics rather than pinning the status.
+        AgentHookEventKind::Working if !recent => None,
+        AgentHookEventKind::Working => Some(WorkspaceStatusObservation {
+            status: WorkspaceStatus::Active,
+            recent_activity: true,
+            waiting_excerpt: None,
+            waiting_attention: None,
+        }),
+        AgentHookEventKind::TurnComplete | AgentHookEventKind::Notification => {
+            Some(WorkspaceStatusObservation {
+                status: WorkspaceStatus::Waiting,
+                recent_activity: false,
+                waiting_excerpt: event.message.clone(),
+                waiting_attention: event.attention,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use std::time::Duration;
+
+    use crate::domain::WorkspaceStatus;
+    use crate::infrastructure::config::NotificationReason;
+    use crate::test_support::unique_test_dir;
+
+    use super::{
+        AgentHookEvent, AgentHookEventKind, HookPayloadSource, hook_event_command,
+        parse_status_event, read_status_event, status_event_path, status_observation_from_event,
+    };
+
+    #[test]
+    fn hook_event_command_records_kind_and_payload() {
+        let root = unique_test_dir("status-hook-command");
+        fs::create_dir_all(root.join(".grove")).expect("grove dir should exist");
+        let event_file = status_event_path(&root);
+
+        let command = hook_event_command(
+            event_file.as_path(),
+            AgentHookEventKind::Notification,
+            HookPayloadSource::Stdin,
+        );
+        let mut child = Command::new("sh")
+            .args(["-c", command.as_str()])
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .expect("sh should spawn");
+        std::io::Write::write_all(
+            child.stdin.as_mut().expect("stdin should be piped"),
+            br#"{"hook_event_name":"Notification","message":"Claude needs your permission to use Bash"}"#,
+        )
+        .expect("payload should write");
+        drop(child.stdin.take());
+        assert!(child.wait().expect("sh should exit").success());
+
+        let event = read_status_event(&root).expect("event should parse");
+        assert_eq!(event.kind, AgentHookEventKind::Notification);
+        assert_eq!(
+            event.message.as_deref(),
+            Some("Claude needs your permission to use Bash")
+        );
+
+        let command = hook_event_command(
+            event_file.as_path(),
+            AgentHookEventKind::TurnComplete,
+            HookPayloadSource::FirstArgument,
+        );
+        let status = Command::new("sh")
+            .args([
+                "-c",
+                command.as_str(),
+                "grove-status",
+                r#"{"type":"agent-turn-complete","last-assistant-message":"Tests pass."}"#,
+            ])
+            .status()
+            .expect("sh should run");
+        assert!(status.success());
+        let event = read_status_event(&root).expect("event should parse");
+        assert_eq!(event.kind, AgentHookEventKind::TurnComplete);
+        assert_eq!(event.message.as_deref(), Some("Tests pass."));
+        assert!(!Path::new(&format!("{}.tmp", event_file.display())).exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn hook_events_map_to_status_and_stale_working_events_fall_back() {
+        let threshold = Duration::from_secs(30);
+        let working = parse_status_event("working 1000\n{}").expect("event should parse");
+        assert_eq!(
+            status_observation_from_event(&working, threshold, 1010)
+                .map(|observation| observation.status),
+            Some(WorkspaceStatus::Active)
+        );
+        assert_eq!(
+            status_observation_from_event(&working, threshold, 1100),
+            None
+        );
+
+        let waiting = AgentHookEvent {
+            kind: AgentHookEventKind::TurnComplete,
+            at_unix_secs: 1000,
+            message: None,
+            attention: None,
+        };
+        let observation = status_observation_from_event(&waiting, threshold, 5000)
+            .expect("turn complete should stay authoritative");
+        assert_eq!(observation.status, WorkspaceStatus::Waiting);
+        assert!(!observation.recent_activity);
+
+        assert_eq!(observation.waiting_attention, None);
+
+        assert_eq!(parse_status_event("garbage"), None);
+        assert_eq!(parse_status_event("unknown 1000\n"), None);
+    }
+
+    #[test]
+    fn permission_notifications_wait_on_a_permission_wall() {
+        let threshold = Duration::from_secs(30);
+        let attention = |content: &str| {
+            let event = parse_status_event(content).expect("event should parse");
+            status_observation_from_event(&event, threshold, 1000)
+                .expect("notification should map to a status")
+                .waiting_attention
+        };
+
+        assert_eq!(
+            attention(
+                "notification 1000\n{\"notification_type\":\"permission_prompt\",\"message\":\"Claude needs your permission to use Bash\"}"
+            ),
+            Some(NotificationReason::PermissionWall)
+        );
+        assert_eq!(
+            attention(
+                "notification 1000\n{\"message\":\"Claude needs your permission to use Bash\"}"
+            ),
+            Some(NotificationReason::PermissionWall)
+        );
+        assert_eq!(
+            attention(
+                "notification 1000\n{\"notification_type\":\"idle_prompt\",\"message\":\"Claude is waiting for your input\"}"
+            ),
+            None
+        );
+        assert_eq!(
+            attention("turn_complete 1000\n{\"message\":\"Asked for permission docs.\"}"),
+            None
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Ordered status-detection rules.
+//!
+//! The compiled-in pane heuristics are exposed as named built-in rules with
+//! priority 0, so config rules can run before or after them. The first rule
+//! that matches decides the status; without a match status falls back to
+//! session activity.
+
+use std::cmp::Reverse;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::domain::{AgentRegistry, AgentType, WorkspaceStatus};
+use crate::infrastructure::config::{NotificationReason, StatusRuleConfig, StatusRuleFieldConfig};
+
+use super::status::{shortcuts_hint_line, thinking_marker, try_prompt_line, waiting_pattern_line};
+
+const BUILTIN_RULE_PREFIX: &str = "builtin:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuiltinCheck {
+    WaitingPatterns,
+    ShortcutsHint,
+    TryPrompt,
+    Thinking,
+}
+
+impl BuiltinCheck {
+    const ALL: [Self; 4] = [
+        Self::WaitingPatterns,
+        Self::ShortcutsHint,
+        Self::TryPrompt,
+        Self::Thinking,
+    ];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::WaitingPatterns => "builtin:waiting-patterns",
+            Self::ShortcutsHint => "builtin:shortcuts-hint",
+            Self::TryPrompt => "builtin:try-prompt",
+            Self::Thinking => "builtin:thinking",
+        }
+    }
+
+    const fn status(self) -> WorkspaceStatus {
+        match self {
+            Self::WaitingPatterns | Self::ShortcutsHint | Self::TryPrompt => {
+                WorkspaceStatus::Waiting
+            }
+            Self::Thinking => WorkspaceStatus::Thinking,
+        }
+    }
+
+    fn evaluate(self, output: &str) -> Option<String> {
+        match self {
+            Self::WaitingPatterns => waiting_pattern_line(output),
+            Self::ShortcutsHint => shortcuts_hint_line(output),
+            Self::TryPrompt => try_prompt_line(output),
+            Self::Thinking => thinking_marker(output).map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum FieldTest {
+    Present,
+    Equals(String),
+    Matches(Regex),
+}
+
+#[derive(Debug)]
+struct FieldPredicate {
+    field: String,
+    test: FieldTest,
+}
+
+impl FieldPredicate {
+    fn evaluate(&self, record: &Value) -> Option<String> {
+        let value = self
+            .field
+            .split('.')
+            .try_fold(record, |value, key| value.get(key))?;
+        let text = match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        let matched = match &self.test {
+            FieldTest::Present => true,
+            FieldTest::Equals(expected) => text == *expected,
+            FieldTest::Matches(regex) => regex.is_match(text.as_str()),
+        };
+        matched.then(|| format!("{}={text}", self.field))
+    }
+}
+
+#[derive(Debug)]
+enum RuleMatcher {
+    Builtin(BuiltinCheck),
+    Pane { regex: Regex, tail_lines: usize },
+    Jsonl(Vec<FieldPredicate>),
+}
+
+#[derive(Debug)]
+pub(crate) struct StatusRule {
+    pub(crate) name: String,
+    pub(crate) agent: Option<AgentType>,
+    pub(crate) priority: i32,
+    pub(crate) status: WorkspaceStatus,
+    pub(crate) attention: Option<NotificationReason>,
+    matcher: RuleMatcher,
+}
+
+impl StatusRule {
+    fn builtin(check: BuiltinCheck) -> Self {
+        Self {
+            name: check.name().to_string(),
+            agent: None,
+            priority: 0,
+            status: check.status(),
+            attention: None,
+            matcher: RuleMatcher::Builtin(check),
+        }
+    }
+
+    fn from_config(config: &StatusRuleConfig, agents: &AgentRegistry) -> Result<Self, String> {
+        let name = config.name.trim();
+        if name.is_empty() {
+            return Err("status rule is missing a name".to_string());
+        }
+        if name.starts_with(BUILTIN_RULE_PREFIX) {
+            return Err(format!(
+                "status rule '{name}' uses the reserved '{BUILTIN_RULE_PREFIX}' prefix"
+            ));
+        }
+        let agent = config
+            .agent
+            .as_deref()
+            .map(str::trim)
+            .map(|id| {
+                agents
+                    .from_marker(id)
+                    .ok_or_else(|| format!("status rule '{name}' names unknown agent '{id}'"))
+            })
+            .transpose()?;
+        let status = config.status.workspace_status();
+        if let Some(attention) = config.attention {
+            if status != WorkspaceStatus::Waiting {
+                return Err(format!(
+                    "status rule '{name}' sets attention but its status is not waiting"
+                ));
+            }
+            if !matches!(
+                attention,
+                NotificationReason::BlockedOnQuestion | NotificationReason::PermissionWall
+            ) {
+                return Err(format!(
+                    "status rule '{name}' attention must be blocked-on-question or permission-wall"
+                ));
+            }
+        }
+
+        let matcher = match (config.pane_regex.as_deref(), config.jsonl.is_empty()) {
+            (Some(pattern), true) => {
+                if config.tail_lines == 0 {
+                    return Err(format!("status rule '{name}' tail_lines must be positive"));
+                }
+                RuleMatcher::Pane {
+                    regex: Regex::new(pattern).map_err(|error| {
+                        format!("status rule '{name}' pane_regex is invalid: {error}")
+                    })?,
+                    tail_lines: config.tail_lines,
+                }
+            }
+            (None, false) => RuleMatcher::Jsonl(
+                config
+                    .jsonl
+                    .iter()
+                    .map(|predicate| field_predicate(name, predicate))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => {
+                return Err(format!(
+                    "status rule '{name}' needs exactly one of pane_regex or jsonl"
+                ));
+            }
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            agent,
+            priority: config.priority,
+            status,
+            attention: config.attention,
+            matcher,
+        })
+    }
+
+    /// `built-in`, `pane` or `jsonl`.
+    pub(crate) const fn source(&self) -> &'static str {
+        match self.matcher {
+            RuleMatcher::Builtin(_) => "built-in",
+            RuleMatcher::Pane { .. } => "pane",
+            RuleMatcher::Jsonl(_) => "jsonl",
+        }
+    }
+
+    pub(crate) fn applies_to(&self, agent: &AgentType) -> bool {
+        self.agent
+            .as_ref()
+            .is_none_or(|rule_agent| rule_agent == agent)
+    }
+
+    /// The matched pane line or record fields, `None` when the rule does not
+    /// match or its input is missing.
+    pub(crate) fn evaluate(&self, input: &StatusRuleInput<'_>) -> Option<String> {
+        if !self.applies_to(input.agent) {
+            return None;
+        }
+        self.evaluate_for_any_agent(input.pane, input.record)
+    }
+
+    /// Like `evaluate`, ignoring which agent the rule is for.
+    pub(crate) fn evaluate_for_any_agent(
+        &self,
+        pane: Option<&str>,
+        record: Option<&Value>,
+    ) -> Option<String> {
+        match &self.matcher {
+            RuleMatcher::Builtin(check) => check.evaluate(pane?),
+            RuleMatcher::Pane { regex, tail_lines } => {
+                let lines = pane?.lines().collect::<Vec<_>>();
+                let start = lines.len().saturating_sub(*tail_lines);
+                lines[start..]
+                    .iter()
+                    .rev()
+                    .find(|line| regex.is_match(line))
+                    .map(|line| line.trim().to_string())
+            }
+            RuleMatcher::Jsonl(predicates) => {
+                let record = record?;
+                predicates
+                    .iter()
+                    .map(|predicate| predicate.evaluate(record))
+                    .collect::<Option<Vec<_>>>()
+                    .map(|fields| fields.join(", "))
+            }
+        }
+    }
+}
+
+fn field_predicate(
+    name: &str,
+    predicate: &StatusRuleFieldConfig,
+) -> Result<FieldPredicate, String> {
+    let field = predicate.field.trim();
+    if field.is_empty() || field.split('.').any(str::is_empty) {
+        return Err(format!(
+            "status rule '{name}' has an invalid jsonl field '{field}'"
+        ));
+    }
+    let test = match (predicate.equals.as_ref(), predicate.matches.as_deref()) {
+        (None, None) => FieldTest::Present,
+        (Some(expected), None) => FieldTest::Equals(expected.clone()),
+        (None, Some(pattern)) => FieldTest::Matches(Regex::new(pattern).map_err(|error| {
+            format!("status rule '{name}' jsonl pattern for '{field}' is invalid: {error}")
+        })?),
+        (Some(_), Some(_)) => {
+            return Err(format!(
+                "status rule '{name}' jsonl field '{field}' sets both equals and matches"
+            ));
+        }
+    };
+    Ok(FieldPredicate {
+        field: field.to_string(),
+        test,
+    })
+}
+
+/// What rules are matched against: the pane capture and the latest
+/// session-log record, either of which may be unavailable.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StatusRuleInput<'a> {
+    pub(crate) agent: &'a AgentType,
+    pub(crate) pane: Option<&'a str>,
+    pub(crate) record: Option<&'a Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StatusRuleMatch {
+    pub(crate) rule: String,
+    pub(crate) status: WorkspaceStatus,
+    pub(crate) attention: Option<NotificationReason>,
+    pub(crate) matched: String,
+}
+
+/// Config rules and the built-ins in evaluation order.
+#[derive(Debug)]
+pub struct StatusRules(Vec<StatusRule>);
+
+impl Default for StatusRules {
+    /// Only the built-ins.
+    fn default() -> Self {
+        Self(ordered_rules(Vec::new()))
+    }
+}
+
+impl StatusRules {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &StatusRule> {
+        self.0.iter()
+    }
+}
+
+/// Config rules followed by the built-ins, highest priority first. Config
+/// rules win ties with built-ins and keep their config order otherwise.
+fn ordered_rules(mut rules: Vec<StatusRule>) -> Vec<StatusRule> {
+    rules.extend(BuiltinCheck::ALL.into_iter().map(StatusRule::builtin));
+    rules.sort_by_key(|rule| Reverse(rule.priority));
+    rules
+}
+
+/// Orders config status rules ahead of or behind the built-ins by priority.
+pub fn compile_status_rules(
+    configs: &[StatusRuleConfig],
+    agents: &AgentRegistry,
+) -> Result<StatusRules, String> {
+    let mut rules = Vec::with_capacity(configs.len());
+    for config in configs {
+        let rule = StatusRule::from_config(config, agents)?;
+        if rules
+            .iter()
+            .any(|existing: &StatusRule| existing.name == rule.name)
+        {
+            return Err(format!("status rule '{}' is defined twice", rule.name));
+        }
+        rules.push(rule);
+    }
+    Ok(StatusRules(ordered_rules(rules)))
+}
+
+pub(crate) fn first_matching_rule(
+    rules: &StatusRules,
+    input: &StatusRuleInput<'_>,
+) -> Option<StatusRuleMatch> {
+    rules.iter().find_map(|rule| {
+        rule.evaluate(input).map(|matched| StatusRuleMatch {
+            rule: rule.name.clone(),
+            status: rule.status,
+            attention: rule.attention,
+            matched,
+        })
+    })
+}
+
+/// Whether any rule for `agent` reads session logs, so callers can skip
+/// reading them otherwise.
+pub(crate) fn has_session_record_rules(rules: &StatusRules, agent: &AgentType) -> bool {
+    rules
+        .iter()
+        .any(|rule| matches!(rule.matcher, RuleMatcher::Jsonl(_)) && rule.applies_to(agent))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::domain::{AgentRegistry, AgentType, WorkspaceStatus};
+    use crate::infrastructure::config::{
+        NotificationReason, StatusRuleConfig, StatusRuleFieldConfig, StatusRuleStatus,
+    };
+
+    use super::{StatusRuleInput, compile_status_rules, first_matching_rule};
+
+    fn pane_rule(name: &str, priority: i32, pattern: &str) -> StatusRuleConfig {
+        StatusRuleConfig {
+            name: name.to_string(),
+            agent: None,
+            priority,
+            status: StatusRuleStatus::Waiting,
+            attention: Some(NotificationReason::PermissionWall),
+            pane_regex: Some(pattern.to_string()),
+            tail_lines: 8,
+            jsonl: Vec::new(),
+        }
+    }
+
+    fn pane_input(pane: &str) -> StatusRuleInput<'_> {
+        StatusRuleInput {
+            agent: &AgentType::Codex,
+            pane: Some(pane),
+            record: None,
+        }
+    }
+
+    #[test]
+    fn rules_run_by_priority_around_the_builtins() {
+        let output = "Run tests?\n(Y)es/(N)o [y/n]\n";
+
+        let rules =
+            compile_status_rules(&[], &AgentRegistry::default()).expect("no rules should compile");
+        let fired = first_matching_rule(&rules, &pane_input(output)).expect("built-in fires");
+        assert_eq!(fired.rule, "builtin:waiting-patterns");
+        assert_eq!(fired.attention, None);
+
+        let rules = compile_status_rules(
+            &[pane_rule("yes-no", 0, r"\(Y\)es/\(N\)o")],
+            &AgentRegistry::default(),
+        )
+        .expect("rule should compile");
+        let fired = first_matching_rule(&rules, &pane_input(output)).expect("rule fires");
+        assert_eq!(fired.rule, "yes-no");
+        assert_eq!(fired.status, WorkspaceStatus::Waiting);
+        assert_eq!(fired.attention, Some(NotificationReason::PermissionWall));
+        assert_eq!(fired.matched, "(Y)es/(N)o [y/n]");
+
+        let rules = compile_status_rules(
+            &[pane_rule("yes-no", -1, r"\(Y\)es/\(N\)o")],
+            &AgentRegistry::default(),
+        )
+        .expect("rule should compile");
+        let fired = first_matching_rule(&rules, &pane_input(output)).expect("built-in fires");
+        assert_eq!(fired.rule, "builtin:waiting-patterns");
+
+        let mut claude_only = pane_rule("claude-only", 50, "Run tests");
+        claude_only.agent = Some("claude".to_string());
+        let rules = compile_status_rules(&[claude_only], &AgentRegistry::default())
+            .expect("rule should compile");
+        let fired = first_matching_rule(&rules, &pane_input(output)).expect("built-in fires");
+        assert_eq!(fired.rule, "builtin:waiting-patterns");
+    }
+
+    #[test]
+    fn pane_rules_only_see_their_tail_lines() {
+        let mut rule = pane_rule("spinner", 10, "^Working");
+        rule.status = StatusRuleStatus::Active;
+        rule.attention = None;
+        rule.tail_lines = 2;
+        let rules =
+            compile_status_rules(&[rule], &AgentRegistry::default()).expect("rule should compile");
+
+        assert_eq!(
+            first_matching_rule(&rules, &pane_input("Working (3s)\na\nb\n")),
+            None
+        );
+        let fired =
+            first_matching_rule(&rules, &pane_input("a\nWorking (3s)\nb\n")).expect("rule fires");
+        assert_eq!(fired.status, WorkspaceStatus::Active);
+    }
+
+    #[test]
+    fn jsonl_rules_require_every_field_predicate() {
+        let rule = StatusRuleConfig {
+            name: "approval".to_string(),
+            agent: None,
+            priority: 10,
+            status: StatusRuleStatus::Waiting,
+            attention: Some(NotificationReason::PermissionWall),
+            pane_regex: None,
+            tail_lines: 8,
+            jsonl: vec![
+                StatusRuleFieldConfig {
+                    field: "payload.type".to_string(),
+                    equals: Some("exec_approval_request".to_string()),
+                    matches: None,
+                },
+                StatusRuleFieldConfig {
+                    field: "payload.command".to_string(),
+                    equals: None,
+                    matches: Some("^cargo ".to_string()),
+                },
+            ],
+        };
+        let rules =
+            compile_status_rules(&[rule], &AgentRegistry::default()).expect("rule should compile");
+        let record = json!({"payload": {"type": "exec_approval_request", "command": "cargo test"}});
+        let input = StatusRuleInput {
+            agent: &AgentType::Codex,
+            pane: None,
+            record: Some(&record),
+        };
+        let fired = first_matching_rule(&rules, &input).expect("rule fires");
+        assert_eq!(
+            fired.matched,
+            "payload.type=exec_approval_request, payload.command=cargo test"
+        );
+
+        let record = json!({"payload": {"type": "exec_approval_request", "command": "rm -rf"}});
+        let input = StatusRuleInput {
+            record: Some(&record),
+            ..input
+        };
+        assert_eq!(first_matching_rule(&rules, &input), None);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let mut rule = pane_rule("bad", 10, "(");
+        assert!(compile_status_rules(&[rule.clone()], &AgentRegistry::default()).is_err());
+
+        rule.pane_regex = Some("ok".to_string());
+        rule.status = StatusRuleStatus::Active;
+        assert!(compile_status_rules(&[rule.clone()], &AgentRegistry::default()).is_err());
+
+        rule.status = StatusRuleStatus::Waiting;
+        rule.attention = Some(NotificationReason::Finished);
+        assert!(compile_status_rules(&[rule.clone()], &AgentRegistry::default()).is_err());
+
+        rule.attention = None;
+        rule.agent = Some("nope".to_string());
+        assert!(compile_status_rules(&[rule.clone()], &AgentRegistry::default()).is_err());
+
+        rule.agent = None;
+        rule.name = "builtin:mine".to_string();
+        assert!(compile_status_rules(&[rule.clone()], &AgentRegistry::default()).is_err());
+
+        rule.name = "dup".to_string();
+        assert!(
+            compile_status_rules(&[rule.clone(), rule.clone()], &AgentRegistry::default()).is_err()
+        );
+
+        rule.pane_regex = None;
+        assert!(compile_status_rules(&[rule], &AgentRegistry::default()).is_err());
+    }
+}
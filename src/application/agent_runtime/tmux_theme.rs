@@ -4,7 +4,7 @@ use ftui::Color;
 
 use crate::infrastructure::config::ThemeName;
 
-pub fn tmux_theme_commands(session_name: &str, theme_name: ThemeName) -> Vec<Vec<String>> {
+pub fn tmux_theme_commands(session_name: &str, theme_name: &ThemeName) -> Vec<Vec<String>> {
     let theme = crate::ui::tui::ui_theme_for(theme_name);
 
     vec![
@@ -111,7 +111,7 @@ mod tests {
 
     #[test]
     fn tmux_theme_commands_do_not_override_window_default_colors() {
-        let commands = tmux_theme_commands("grove-ws-main", ThemeName::CatppuccinMocha);
+        let commands = tmux_theme_commands("grove-ws-main", &ThemeName::CatppuccinMocha);
 
         assert!(!commands.iter().any(|command| {
             command.len() == 6
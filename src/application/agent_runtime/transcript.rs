@@ -0,0 +1,570 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use flate2::read::MultiGzDecoder;
+
+use super::evaluate_capture_change;
+use super::launch_plan::shell_quote;
+
+/// Per-session transcripts, relative to the task root.
+pub const TRANSCRIPTS_DIR: &str = ".grove/transcripts";
+const TRANSCRIPT_SUFFIX: &str = ".log";
+/// Rotated output, one gzip member per rotation, next to the live `.log` file.
+const ARCHIVE_SUFFIX: &str = ".log.gz";
+/// Size at which the live transcript is compressed into its archive.
+const TRANSCRIPT_ROTATE_BYTES: u64 = 4 * 1024 * 1024;
+/// How often the rotation watcher checks the live transcript size.
+const TRANSCRIPT_ROTATE_CHECK_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Text,
+    Ansi,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(Self::Text),
+            "ansi" => Some(Self::Ansi),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptFile {
+    pub session_name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub modified_unix_secs: Option<i64>,
+}
+
+pub fn transcripts_dir(task_root: &Path) -> PathBuf {
+    task_root.join(TRANSCRIPTS_DIR)
+}
+
+pub fn transcript_path(task_root: &Path, session_name: &str) -> PathBuf {
+    transcript_path_in(transcripts_dir(task_root).as_path(), session_name)
+}
+
+pub(super) fn transcript_path_in(transcripts_dir: &Path, session_name: &str) -> PathBuf {
+    transcripts_dir.join(format!("{session_name}{TRANSCRIPT_SUFFIX}"))
+}
+
+fn archive_path(transcript_path: &Path) -> PathBuf {
+    let mut path = transcript_path.as_os_str().to_os_string();
+    path.push(".gz");
+    PathBuf::from(path)
+}
+
+/// Streams the session's pane output as plain text into `transcript_path`.
+///
+/// A background watcher moves the file into its gzip archive once it passes
+/// `TRANSCRIPT_ROTATE_BYTES`, copying and then truncating it like logrotate's
+/// `copytruncate`, so the live file stays small and readable while recording.
+/// The watcher exits once the `cat` that replaced the shell does.
+pub(super) fn pipe_pane_command(session_name: &str, transcript_path: &Path) -> Vec<String> {
+    let directory = transcript_path
+        .parent()
+        .map(|parent| shell_quote(parent.to_string_lossy().as_ref()))
+        .unwrap_or_else(|| "'.'".to_string());
+    let log = shell_quote(transcript_path.to_string_lossy().as_ref());
+    let archive = shell_quote(archive_path(transcript_path).to_string_lossy().as_ref());
+    vec![
+        "tmux".to_string(),
+        "pipe-pane".to_string(),
+        "-o".to_string(),
+        "-t".to_string(),
+        session_name.to_string(),
+        format!(
+            "mkdir -p {directory} && touch {log} && {{ while sleep {TRANSCRIPT_ROTATE_CHECK_SECS} && kill -0 $$ 2>/dev/null; do \
+             if [ $(($(wc -c < {log}))) -ge {TRANSCRIPT_ROTATE_BYTES} ]; then gzip -c {log} >> {archive} && : > {log}; fi; \
+             done & }} && exec cat >> {log}"
+        ),
+    ]
+}
+
+pub fn list_transcripts(task_root: &Path) -> Vec<TranscriptFile> {
+    let Ok(entries) = fs::read_dir(transcripts_dir(task_root)) else {
+        return Vec::new();
+    };
+    let mut transcripts = BTreeMap::<String, TranscriptFile>::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(session_name) = file_name
+            .strip_suffix(TRANSCRIPT_SUFFIX)
+            .or_else(|| file_name.strip_suffix(ARCHIVE_SUFFIX))
+        else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let modified_unix_secs = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .and_then(|age| i64::try_from(age.as_secs()).ok());
+        let transcript = transcripts
+            .entry(session_name.to_string())
+            .or_insert_with(|| TranscriptFile {
+                session_name: session_name.to_string(),
+                path: transcript_path_in(transcripts_dir(task_root).as_path(), session_name),
+                size_bytes: 0,
+                modified_unix_secs: None,
+            });
+        transcript.size_bytes = transcript.size_bytes.saturating_add(metadata.len());
+        transcript.modified_unix_secs = transcript.modified_unix_secs.max(modified_unix_secs);
+    }
+    transcripts.into_values().collect()
+}
+
+/// Reads a transcript: its rotated archive, then the live plain-text file.
+pub fn read_transcript(path: &Path) -> Result<String, String> {
+    let archive = archive_path(path);
+    let mut bytes = if archive.exists() {
+        read_archive(archive.as_path())?
+    } else {
+        Vec::new()
+    };
+    match fs::read(path) {
+        Ok(live) => bytes.extend(live),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound && archive.exists() => {}
+        Err(error) => {
+            return Err(format!(
+                "transcript read failed for {}: {error}",
+                path.display()
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Decodes rotated output, keeping what was readable before a truncated tail.
+///
+/// A rotation interrupted by a killed tmux server can leave a partial member behind.
+fn read_archive(path: &Path) -> Result<Vec<u8>, String> {
+    let file = fs::File::open(path)
+        .map_err(|error| format!("transcript read failed for {}: {error}", path.display()))?;
+    let mut decoder = MultiGzDecoder::new(file);
+    let mut bytes = Vec::new();
+    let mut chunk = [0u8; 16 * 1024];
+    loop {
+        match decoder.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => bytes.extend_from_slice(&chunk[..read]),
+            Err(error) if bytes.is_empty() => {
+                return Err(format!(
+                    "transcript decode failed for {}: {error}",
+                    path.display()
+                ));
+            }
+            Err(_) => break,
+        }
+    }
+    Ok(bytes)
+}
+
+/// Splits raw pane output into display lines, keeping only SGR styling when `keep_styles` is set.
+///
+/// Cursor movement, erase and other CSI sequences, OSC strings and charset
+/// switches are always dropped.
+pub fn transcript_lines(raw: &str, keep_styles: bool) -> Vec<String> {
+    let render_output = evaluate_capture_change(None, raw).render_output;
+    let mut lines = render_output
+        .split('\n')
+        .map(|line| {
+            let visible = line
+                .rsplit('\r')
+                .find(|segment| !strip_escape_sequences(segment).trim().is_empty())
+                .unwrap_or("");
+            let line = if keep_styles {
+                visible.to_string()
+            } else {
+                strip_escape_sequences(visible)
+            };
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<String>>();
+    while lines
+        .last()
+        .is_some_and(|line| strip_escape_sequences(line).is_empty())
+    {
+        lines.pop();
+    }
+    lines
+}
+
+/// Indexes of lines containing `query`, ignoring case. An empty query matches nothing.
+pub fn search_transcript_lines(lines: &[String], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(query.as_str()))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+pub fn render_transcript(raw: &str, format: TranscriptFormat) -> String {
+    match format {
+        TranscriptFormat::Text => {
+            let mut text = transcript_lines(raw, false).join("\n");
+            text.push('\n');
+            text
+        }
+        TranscriptFormat::Ansi => {
+            let mut text = transcript_lines(raw, true).join("\x1b[0m\n");
+            text.push_str("\x1b[0m\n");
+            text
+        }
+        TranscriptFormat::Html => transcript_lines(raw, true)
+            .iter()
+            .map(|line| sgr_line_to_html(line))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
+
+/// Wraps rendered HTML transcript sections in a standalone page.
+pub fn html_document(title: &str, sections: &[(String, String)]) -> String {
+    let mut document = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body{{background:#1c1917;color:#e7e5e4;font-family:monospace}}\
+         pre{{white-space:pre-wrap}}</style>\n</head>\n<body>\n",
+        escape_html(title)
+    );
+    for (heading, body) in sections {
+        document.push_str(format!("<h2>{}</h2>\n<pre>", escape_html(heading)).as_str());
+        document.push_str(body);
+        document.push_str("</pre>\n");
+    }
+    document.push_str("</body>\n</html>\n");
+    document
+}
+
+/// Removes every escape sequence: CSI (including SGR), OSC, DCS-style strings and two-byte escapes.
+fn strip_escape_sequences(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character != '\x1b' {
+            output.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('[') => {
+                for character in characters.by_ref() {
+                    if ('@'..='~').contains(&character) {
+                        break;
+                    }
+                }
+            }
+            Some(']' | 'P' | 'X' | '^' | '_') => {
+                while let Some(character) = characters.next() {
+                    if character == '\x07' {
+                        break;
+                    }
+                    if character == '\x1b' && characters.peek() == Some(&'\\') {
+                        characters.next();
+                        break;
+                    }
+                }
+            }
+            Some('(' | ')' | '*' | '+' | '-' | '.' | '/' | '#') => {
+                characters.next();
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct HtmlStyle {
+    foreground: Option<String>,
+    background: Option<String>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl HtmlStyle {
+    fn css(&self) -> String {
+        let mut rules = Vec::new();
+        if let Some(color) = &self.foreground {
+            rules.push(format!("color:{color}"));
+        }
+        if let Some(color) = &self.background {
+            rules.push(format!("background:{color}"));
+        }
+        if self.bold {
+            rules.push("font-weight:bold".to_string());
+        }
+        if self.dim {
+            rules.push("opacity:0.7".to_string());
+        }
+        if self.italic {
+            rules.push("font-style:italic".to_string());
+        }
+        if self.underline {
+            rules.push("text-decoration:underline".to_string());
+        }
+        rules.join(";")
+    }
+
+    fn apply(&mut self, params: &str) {
+        let values = params
+            .split(';')
+            .map(|value| value.parse::<u16>().unwrap_or(0))
+            .collect::<Vec<u16>>();
+        let mut index = 0usize;
+        while index < values.len() {
+            match values[index] {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                code @ 30..=37 => self.foreground = Some(ansi_color(code - 30)),
+                code @ 90..=97 => self.foreground = Some(ansi_color(code - 90 + 8)),
+                39 => self.foreground = None,
+                code @ 40..=47 => self.background = Some(ansi_color(code - 40)),
+                code @ 100..=107 => self.background = Some(ansi_color(code - 100 + 8)),
+                49 => self.background = None,
+                code @ (38 | 48) => {
+                    let (color, consumed) = extended_color(&values[index.saturating_add(1)..]);
+                    if code == 38 {
+                        self.foreground = color;
+                    } else {
+                        self.background = color;
+                    }
+                    index = index.saturating_add(consumed);
+                }
+                _ => {}
+            }
+            index = index.saturating_add(1);
+        }
+    }
+}
+
+fn sgr_line_to_html(line: &str) -> String {
+    let mut html = String::with_capacity(line.len());
+    let mut style = HtmlStyle::default();
+    let mut span_open = false;
+    let mut rest = line;
+    loop {
+        let (text, sequence) = match rest.find("\x1b[") {
+            Some(start) => (&rest[..start], Some(&rest[start.saturating_add(2)..])),
+            None => (rest, None),
+        };
+        html.push_str(escape_html(text).as_str());
+        let Some(sequence) = sequence else {
+            break;
+        };
+        let Some(end) = sequence.find('m') else {
+            break;
+        };
+        style.apply(&sequence[..end]);
+        rest = &sequence[end.saturating_add(1)..];
+        if span_open {
+            html.push_str("</span>");
+            span_open = false;
+        }
+        if style != HtmlStyle::default() {
+            html.push_str(format!("<span style=\"{}\">", style.css()).as_str());
+            span_open = true;
+        }
+    }
+    if span_open {
+        html.push_str("</span>");
+    }
+    html
+}
+
+fn extended_color(values: &[u16]) -> (Option<String>, usize) {
+    match values {
+        [5, index, ..] => (Some(indexed_color(*index)), 2),
+        [2, red, green, blue, ..] => (Some(format!("#{:02x}{:02x}{:02x}", red, green, blue)), 4),
+        _ => (None, values.len()),
+    }
+}
+
+fn ansi_color(index: u16) -> String {
+    const PALETTE: [&str; 16] = [
+        "#000000", "#cd3131", "#0dbc79", "#e5e510", "#2472c8", "#bc3fbc", "#11a8cd", "#e5e5e5",
+        "#666666", "#f14c4c", "#23d18b", "#f5f543", "#3b8eea", "#d670d6", "#29b8db", "#ffffff",
+    ];
+    PALETTE
+        .get(usize::from(index))
+        .copied()
+        .unwrap_or("#e5e5e5")
+        .to_string()
+}
+
+fn indexed_color(index: u16) -> String {
+    match index {
+        0..=15 => ansi_color(index),
+        16..=231 => {
+            let cube = index - 16;
+            let level = |value: u16| if value == 0 { 0 } else { value * 40 + 55 };
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(cube / 36),
+                level((cube / 6) % 6),
+                level(cube % 6)
+            )
+        }
+        _ => {
+            let gray = (index.min(255) - 232) * 10 + 8;
+            format!("#{gray:02x}{gray:02x}{gray:02x}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::{
+        TranscriptFormat, list_transcripts, pipe_pane_command, read_transcript, render_transcript,
+        search_transcript_lines, transcript_lines, transcript_path,
+    };
+    use crate::test_support::unique_test_dir;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).expect("gzip should encode");
+        encoder.finish().expect("gzip should finish")
+    }
+
+    #[test]
+    fn pipe_pane_command_records_plain_text_and_rotates_into_the_archive() {
+        let path = transcript_path(Path::new("/tmp/tasks/flaky"), "grove-wt-flaky-grove");
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/tasks/flaky/.grove/transcripts/grove-wt-flaky-grove.log")
+        );
+        let command = pipe_pane_command("grove-wt-flaky-grove", path.as_path());
+        assert_eq!(
+            command[..5],
+            ["tmux", "pipe-pane", "-o", "-t", "grove-wt-flaky-grove"]
+        );
+        let script = command[5].as_str();
+        let log = "'/tmp/tasks/flaky/.grove/transcripts/grove-wt-flaky-grove.log'";
+        assert!(script.starts_with(
+            format!("mkdir -p '/tmp/tasks/flaky/.grove/transcripts' && touch {log} && ").as_str()
+        ));
+        assert!(script.contains(format!("-ge {}", 4 * 1024 * 1024).as_str()));
+        let archive = "'/tmp/tasks/flaky/.grove/transcripts/grove-wt-flaky-grove.log.gz'";
+        assert!(script.contains(format!("gzip -c {log} >> {archive} && : > {log};").as_str()));
+        assert!(script.ends_with(format!("exec cat >> {log}").as_str()));
+        let syntax = Command::new("sh")
+            .args(["-n", "-c", script])
+            .status()
+            .expect("sh should run");
+        assert!(syntax.success(), "{script}");
+    }
+
+    #[test]
+    fn read_transcript_joins_the_archive_with_the_live_file() {
+        let root = unique_test_dir("grove-transcript-read");
+        let path = transcript_path(root.as_path(), "grove-wt-a");
+        fs::create_dir_all(path.parent().expect("transcript should have a parent"))
+            .expect("transcripts dir should exist");
+        let mut archive = gzip(b"first run\n");
+        archive.extend(gzip(b"second run\n"));
+        let partial = gzip(b"unfinished");
+        archive.extend_from_slice(&partial[..partial.len() / 2]);
+        let mut archive_path = path.clone().into_os_string();
+        archive_path.push(".gz");
+        fs::write(&archive_path, &archive).expect("archive should write");
+        fs::write(&path, "third run\n").expect("transcript should write");
+
+        let raw = read_transcript(path.as_path()).expect("transcript should decode");
+        assert!(raw.starts_with("first run\nsecond run\n"));
+        assert!(raw.ends_with("third run\n"));
+
+        let listed = list_transcripts(root.as_path());
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].session_name, "grove-wt-a");
+        assert_eq!(listed[0].path, path);
+        assert_eq!(listed[0].size_bytes, archive.len() as u64 + 10);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn transcript_lines_drop_control_sequences_and_carriage_return_redraws() {
+        let raw = "\x1b[?2004h$ cargo test\r\n\x1b[32mok\x1b[0m 3 passed\r\nprogress 10%\rprogress 100%\r\n\x1b]0;title\x07done\r\n\r\n";
+        assert_eq!(
+            transcript_lines(raw, false),
+            vec!["$ cargo test", "ok 3 passed", "progress 100%", "done"]
+        );
+        assert_eq!(
+            transcript_lines(raw, true)[1],
+            "\x1b[32mok\x1b[0m 3 passed".to_string()
+        );
+        let lines = transcript_lines(raw, false);
+        assert_eq!(search_transcript_lines(&lines, "PROGRESS"), vec![2]);
+        assert!(search_transcript_lines(&lines, " ").is_empty());
+    }
+
+    #[test]
+    fn html_render_escapes_text_and_maps_sgr_colors() {
+        let raw = "\x1b[1;31m<error>\x1b[0m & \x1b[38;5;46mok\x1b[39m\n";
+        assert_eq!(
+            render_transcript(raw, TranscriptFormat::Html),
+            "<span style=\"color:#cd3131;font-weight:bold\">&lt;error&gt;</span> &amp; <span style=\"color:#00ff00\">ok</span>"
+        );
+        assert_eq!(
+            render_transcript(raw, TranscriptFormat::Text),
+            "<error> & ok\n"
+        );
+    }
+
+    #[test]
+    fn transcript_lines_drop_cursor_movement_and_erase_sequences() {
+        let raw = "\x1b[?25l\x1b[2K\x1b[1Gbuilding\x1b[0K\r\n\x1b[1A\x1b[10;4H\x1b(Bdone \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\\x1b[?25h\r\n";
+        assert_eq!(transcript_lines(raw, false), vec!["building", "done link"]);
+        assert_eq!(
+            render_transcript("\x1b[3C\x1b[31mred\x1b[0m\x1b[K\n", TranscriptFormat::Ansi),
+            "\x1b[31mred\x1b[0m\x1b[0m\n"
+        );
+    }
+}
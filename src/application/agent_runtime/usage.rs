@@ -0,0 +1,510 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::domain::AgentType;
+use crate::infrastructure::config::{ModelPriceConfig, UsageConfig};
+
+use super::agents;
+
+/// Model label used when a session log does not name the model.
+pub const UNKNOWN_MODEL: &str = "unknown";
+
+/// Built-in estimates in USD per million tokens: input, output, cache read, cache write.
+const DEFAULT_MODEL_PRICES: [(&str, f64, f64, f64, f64); 9] = [
+    ("claude-opus-4-5", 5.0, 25.0, 0.5, 6.25),
+    ("claude-opus-4", 15.0, 75.0, 1.5, 18.75),
+    ("claude-sonnet-4", 3.0, 15.0, 0.3, 3.75),
+    ("claude-3-7-sonnet", 3.0, 15.0, 0.3, 3.75),
+    ("claude-3-5-sonnet", 3.0, 15.0, 0.3, 3.75),
+    ("claude-haiku-4-5", 1.0, 5.0, 0.1, 1.25),
+    ("claude-3-5-haiku", 0.8, 4.0, 0.08, 1.0),
+    ("gpt-5", 1.25, 10.0, 0.125, 0.0),
+    ("gpt-4.1", 2.0, 8.0, 0.5, 0.0),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+}
+
+impl TokenUsage {
+    pub const fn total(self) -> u64 {
+        self.input_tokens
+            .saturating_add(self.output_tokens)
+            .saturating_add(self.cache_read_tokens)
+            .saturating_add(self.cache_write_tokens)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn add(&mut self, other: Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cache_read_tokens = self
+            .cache_read_tokens
+            .saturating_add(other.cache_read_tokens);
+        self.cache_write_tokens = self
+            .cache_write_tokens
+            .saturating_add(other.cache_write_tokens);
+    }
+
+    pub(super) const fn saturating_sub(self, other: Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(other.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(other.output_tokens),
+            cache_read_tokens: self
+                .cache_read_tokens
+                .saturating_sub(other.cache_read_tokens),
+            cache_write_tokens: self
+                .cache_write_tokens
+                .saturating_sub(other.cache_write_tokens),
+        }
+    }
+}
+
+/// Tokens billed for one model response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageSample {
+    /// Identifies the response when it can be logged more than once, in one file or across resumed sessions.
+    pub response_id: Option<String>,
+    pub model: String,
+    pub ts_ms: Option<u64>,
+    pub tokens: TokenUsage,
+}
+
+/// Parser context carried from one session log line to the next.
+#[derive(Debug, Clone, Default)]
+pub(super) struct UsageLineState {
+    /// Codex reports running session totals instead of per-response usage.
+    pub(super) cumulative: TokenUsage,
+    pub(super) model: Option<String>,
+    /// Claude repeats a response's usage on each of its content block lines.
+    pub(super) seen_responses: HashSet<String>,
+}
+
+/// Token totals per model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub by_model: BTreeMap<String, TokenUsage>,
+}
+
+impl UsageTotals {
+    pub fn record(&mut self, model: &str, tokens: TokenUsage) {
+        self.by_model
+            .entry(model.to_string())
+            .or_default()
+            .add(tokens);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (model, tokens) in &other.by_model {
+            self.record(model, *tokens);
+        }
+    }
+
+    pub fn tokens(&self) -> TokenUsage {
+        let mut tokens = TokenUsage::default();
+        for model_tokens in self.by_model.values() {
+            tokens.add(*model_tokens);
+        }
+        tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens().is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageCost {
+    pub usd: f64,
+    /// Models without a price, their tokens are left out of `usd`.
+    pub unpriced_models: Vec<String>,
+}
+
+impl UsageCost {
+    /// Dollar estimate, marked with `+` when some models had no price.
+    pub fn label(&self) -> String {
+        let suffix = if self.unpriced_models.is_empty() {
+            ""
+        } else {
+            "+"
+        };
+        format!("${:.2}{suffix}", self.usd)
+    }
+}
+
+/// Token count shortened for narrow columns, e.g. `950`, `12.3k`, `4.1M`.
+pub fn compact_token_count(count: u64) -> String {
+    if count < 1_000 {
+        return count.to_string();
+    }
+    if count < 1_000_000 {
+        return format!("{:.1}k", count as f64 / 1_000.0);
+    }
+    format!("{:.1}M", count as f64 / 1_000_000.0)
+}
+
+/// Prices matched by the longest model name prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsagePriceTable {
+    prices: Vec<(String, ModelPriceConfig)>,
+}
+
+impl Default for UsagePriceTable {
+    fn default() -> Self {
+        Self::from_config(&UsageConfig::default())
+    }
+}
+
+impl UsagePriceTable {
+    pub fn from_config(config: &UsageConfig) -> Self {
+        let mut prices = DEFAULT_MODEL_PRICES
+            .iter()
+            .filter(|(prefix, ..)| !config.prices.contains_key(*prefix))
+            .map(|(prefix, input, output, cache_read, cache_write)| {
+                (
+                    (*prefix).to_string(),
+                    ModelPriceConfig {
+                        input: *input,
+                        output: *output,
+                        cache_read: *cache_read,
+                        cache_write: *cache_write,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        prices.extend(
+            config
+                .prices
+                .iter()
+                .map(|(prefix, price)| (prefix.clone(), *price)),
+        );
+        Self { prices }
+    }
+
+    pub fn price_for(&self, model: &str) -> Option<ModelPriceConfig> {
+        self.prices
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| *price)
+    }
+
+    pub fn estimate(&self, totals: &UsageTotals) -> UsageCost {
+        let mut cost = UsageCost::default();
+        for (model, tokens) in &totals.by_model {
+            let Some(price) = self.price_for(model) else {
+                if !tokens.is_empty() {
+                    cost.unpriced_models.push(model.clone());
+                }
+                continue;
+            };
+            let per_million = |count: u64, usd: f64| count as f64 * usd / 1_000_000.0;
+            cost.usd += per_million(tokens.input_tokens, price.input)
+                + per_million(tokens.output_tokens, price.output)
+                + per_million(tokens.cache_read_tokens, price.cache_read)
+                + per_million(tokens.cache_write_tokens, price.cache_write);
+        }
+        cost
+    }
+}
+
+#[derive(Debug, Default)]
+struct UsageFileCursor {
+    offset: u64,
+    line_state: UsageLineState,
+    totals: UsageTotals,
+}
+
+/// Keeps a read offset per session log so each refresh only parses appended lines.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    files: HashMap<PathBuf, UsageFileCursor>,
+    seen_response_ids: HashSet<String>,
+}
+
+impl UsageTracker {
+    /// Totals for every session log of `workspace_path`, including lines appended since the last call.
+    pub fn refresh_worktree(
+        &mut self,
+        agent: &AgentType,
+        workspace_path: &Path,
+        home_dir: &Path,
+    ) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for path in agents::usage_session_files_in_home(agent, workspace_path, home_dir) {
+            let cursor = self.files.entry(path.clone()).or_default();
+            read_appended_usage(agent, path.as_path(), cursor, &mut self.seen_response_ids);
+            totals.merge(&cursor.totals);
+        }
+        totals
+    }
+}
+
+/// Whether `sample` is the first report of its response.
+fn first_sighting(sample: &UsageSample, seen_response_ids: &mut HashSet<String>) -> bool {
+    sample
+        .response_id
+        .as_ref()
+        .is_none_or(|response_id| seen_response_ids.insert(response_id.clone()))
+}
+
+fn read_appended_usage(
+    agent: &AgentType,
+    path: &Path,
+    cursor: &mut UsageFileCursor,
+    seen_response_ids: &mut HashSet<String>,
+) {
+    let Ok(mut file) = File::open(path) else {
+        return;
+    };
+    let Ok(len) = file.metadata().map(|metadata| metadata.len()) else {
+        return;
+    };
+    if len < cursor.offset {
+        *cursor = UsageFileCursor::default();
+    }
+    if len == cursor.offset || file.seek(SeekFrom::Start(cursor.offset)).is_err() {
+        return;
+    }
+    let mut bytes = Vec::new();
+    if file
+        .take(len.saturating_sub(cursor.offset))
+        .read_to_end(&mut bytes)
+        .is_err()
+    {
+        return;
+    }
+    // A trailing partial line is picked up once the agent finishes writing it.
+    let Some(last_newline) = bytes.iter().rposition(|byte| *byte == b'\n') else {
+        return;
+    };
+    for line in String::from_utf8_lossy(&bytes[..=last_newline]).lines() {
+        if let Some(sample) = agents::parse_usage_line(agent, line, &mut cursor.line_state)
+            && first_sighting(&sample, seen_response_ids)
+        {
+            cursor.totals.record(sample.model.as_str(), sample.tokens);
+        }
+    }
+    cursor.offset = cursor
+        .offset
+        .saturating_add(u64::try_from(last_newline).unwrap_or(u64::MAX))
+        .saturating_add(1);
+}
+
+/// Every usage sample in the session logs of `workspace_path`, for time-windowed reports.
+pub fn usage_samples_in_home(
+    agent: &AgentType,
+    workspace_path: &Path,
+    home_dir: &Path,
+) -> Vec<UsageSample> {
+    let mut samples = Vec::new();
+    let mut seen_response_ids = HashSet::new();
+    for path in agents::usage_session_files_in_home(agent, workspace_path, home_dir) {
+        let Ok(raw) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut line_state = UsageLineState::default();
+        samples.extend(
+            raw.lines()
+                .filter_map(|line| agents::parse_usage_line(agent, line, &mut line_state))
+                .filter(|sample| first_sighting(sample, &mut seen_response_ids)),
+        );
+    }
+    samples
+}
+
+/// Milliseconds since the epoch for an RFC 3339 UTC timestamp such as `2025-06-01T12:30:00.250Z`.
+pub(super) fn parse_timestamp_ms(value: &str) -> Option<u64> {
+    let value = value.trim().strip_suffix('Z').unwrap_or(value.trim());
+    let (date, time) = value.split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let year = date_parts.next()?.ok()?;
+    let month = date_parts.next()?.ok()?;
+    let day = date_parts.next()?.ok()?;
+    let (clock, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut clock_parts = clock.splitn(3, ':').map(str::parse::<i64>);
+    let hours = clock_parts.next()?.ok()?;
+    let minutes = clock_parts.next()?.ok()?;
+    let seconds = clock_parts.next()?.ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let millis = fraction
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .chain(std::iter::repeat('0'))
+        .take(3)
+        .collect::<String>()
+        .parse::<i64>()
+        .ok()?;
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar.
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let secs = days * 86_400 + hours * 3_600 + minutes * 60 + seconds;
+    u64::try_from(secs * 1_000 + millis).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use crate::domain::AgentType;
+    use crate::infrastructure::config::{ModelPriceConfig, UsageConfig};
+    use crate::test_support::unique_test_dir;
+
+    use super::super::agents::claude_project_dir_name;
+    use super::{
+        TokenUsage, UsagePriceTable, UsageTotals, UsageTracker, compact_token_count,
+        parse_timestamp_ms, usage_samples_in_home,
+    };
+
+    fn claude_usage_line(message_id: &str, output_tokens: u64) -> String {
+        format!(
+            "{{\"type\":\"assistant\",\"requestId\":\"req-{message_id}\",\"timestamp\":\"2025-06-01T12:00:00.000Z\",\"message\":{{\"id\":\"{message_id}\",\"model\":\"claude-sonnet-4-5\",\"usage\":{{\"input_tokens\":10,\"output_tokens\":{output_tokens},\"cache_read_input_tokens\":100,\"cache_creation_input_tokens\":20}}}}}}\n"
+        )
+    }
+
+    #[test]
+    fn tracker_reads_only_appended_claude_lines_and_dedupes_messages() {
+        let root = unique_test_dir("usage-claude-incremental");
+        let home = root.join("home");
+        let workspace_path = root.join("ws").join("feature-usage");
+        fs::create_dir_all(&workspace_path).expect("workspace directory should exist");
+        let project_dir = home
+            .join(".claude")
+            .join("projects")
+            .join(claude_project_dir_name(&workspace_path));
+        fs::create_dir_all(&project_dir).expect("project directory should exist");
+        let session_file = project_dir.join("session-1.jsonl");
+        fs::write(
+            &session_file,
+            format!(
+                "{}{}{{\"type\":\"user\"}}\n",
+                claude_usage_line("msg-1", 5),
+                claude_usage_line("msg-1", 5)
+            ),
+        )
+        .expect("session file should be written");
+
+        let mut tracker = UsageTracker::default();
+        let totals = tracker.refresh_worktree(&AgentType::Claude, &workspace_path, &home);
+        assert_eq!(
+            totals.tokens(),
+            TokenUsage {
+                input_tokens: 10,
+                output_tokens: 5,
+                cache_read_tokens: 100,
+                cache_write_tokens: 20,
+            }
+        );
+
+        let mut appended = fs::read_to_string(&session_file).expect("session file should read");
+        appended.push_str(claude_usage_line("msg-2", 7).as_str());
+        appended.push_str("{\"type\":\"assistant\",\"message\":{\"id\":\"partial");
+        fs::write(&session_file, appended).expect("session file should be written");
+
+        let totals = tracker.refresh_worktree(&AgentType::Claude, &workspace_path, &home);
+        assert_eq!(totals.tokens().output_tokens, 12);
+        assert_eq!(totals.tokens().input_tokens, 20);
+        assert_eq!(
+            totals.by_model.keys().cloned().collect::<Vec<_>>(),
+            vec!["claude-sonnet-4-5".to_string()]
+        );
+
+        // A resumed session starts a new log that repeats earlier responses.
+        fs::write(
+            project_dir.join("session-2.jsonl"),
+            format!(
+                "{}{}",
+                claude_usage_line("msg-1", 5),
+                claude_usage_line("msg-3", 1)
+            ),
+        )
+        .expect("resumed session file should be written");
+        let totals = tracker.refresh_worktree(&AgentType::Claude, &workspace_path, &home);
+        assert_eq!(totals.tokens().output_tokens, 13);
+        assert_eq!(
+            usage_samples_in_home(&AgentType::Claude, &workspace_path, &home)
+                .iter()
+                .map(|sample| sample.tokens.output_tokens)
+                .sum::<u64>(),
+            13
+        );
+    }
+
+    #[test]
+    fn price_table_prefers_longest_prefix_and_config_overrides() {
+        let table = UsagePriceTable::from_config(&UsageConfig {
+            prices: BTreeMap::from([(
+                "claude-sonnet-4".to_string(),
+                ModelPriceConfig {
+                    input: 1.0,
+                    output: 2.0,
+                    cache_read: 0.0,
+                    cache_write: 0.0,
+                },
+            )]),
+        });
+        let mut totals = UsageTotals::default();
+        totals.record(
+            "claude-sonnet-4-5",
+            TokenUsage {
+                input_tokens: 1_000_000,
+                output_tokens: 500_000,
+                cache_read_tokens: 1_000_000,
+                cache_write_tokens: 0,
+            },
+        );
+        totals.record(
+            "mystery-model",
+            TokenUsage {
+                input_tokens: 10,
+                ..TokenUsage::default()
+            },
+        );
+
+        let cost = table.estimate(&totals);
+        assert!((cost.usd - 2.0).abs() < 1e-9, "{cost:?}");
+        assert_eq!(cost.unpriced_models, vec!["mystery-model".to_string()]);
+        assert_eq!(cost.label(), "$2.00+");
+        assert_eq!(compact_token_count(950), "950");
+        assert_eq!(compact_token_count(12_345), "12.3k");
+        assert_eq!(compact_token_count(4_100_000), "4.1M");
+        assert_eq!(
+            UsagePriceTable::default()
+                .price_for("claude-opus-4-5-20251101")
+                .map(|price| price.input),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_ms_reads_utc_timestamps() {
+        assert_eq!(parse_timestamp_ms("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_timestamp_ms("2025-06-01T12:30:00.250Z"),
+            Some(1_748_781_000_250)
+        );
+        assert_eq!(
+            parse_timestamp_ms("2024-02-29T23:59:59.5Z"),
+            Some(1_709_251_199_500)
+        );
+        assert_eq!(parse_timestamp_ms("yesterday"), None);
+    }
+}
@@ -3,15 +3,25 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::application::agent_runtime::build_agent_registry;
 use crate::application::session_cleanup::SessionRecord;
 use crate::application::session_cleanup::{
     SessionCleanupReason, cleanup_reason_for_tasks, list_tmux_sessions, now_unix_secs,
 };
-use crate::domain::Task;
+use crate::domain::{AgentRegistry, Task};
 use crate::infrastructure::config::ProjectConfig;
 use crate::infrastructure::paths::{refer_to_same_location, tasks_root};
 use crate::infrastructure::task_manifest::decode_task_manifest;
 
+#[path = "doctor/fix.rs"]
+mod fix;
+
+use fix::plan_doctor_fixes;
+pub use fix::{
+    DoctorFix, DoctorFixChange, DoctorFixOutcome, DoctorFixPlan, DoctorSkippedFix,
+    apply_doctor_fixes, kill_doctor_session,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DoctorSeverity {
@@ -112,6 +122,8 @@ impl DoctorReport {
 
 pub fn diagnose() -> Result<DoctorReport, String> {
     let loaded_config = crate::infrastructure::config::load()?;
+    let agents = build_agent_registry(&loaded_config.config.agents)
+        .map_err(|error| format!("agents invalid: {error}"))?;
     let tasks_root = tasks_root().ok_or_else(|| "task root unavailable".to_string())?;
     let tmux_state = match list_tmux_sessions() {
         Ok(sessions) => DoctorTmuxState::Available(sessions),
@@ -120,20 +132,50 @@ pub fn diagnose() -> Result<DoctorReport, String> {
 
     Ok(diagnose_from_inputs(
         Some(tasks_root.as_path()),
+        &agents,
         loaded_config.config.projects.as_slice(),
         loaded_config.config.hidden_base_project_paths.as_slice(),
         tmux_state,
     ))
 }
 
+/// Diagnoses and plans `doctor --fix` repairs from the same tmux listing.
+pub(crate) fn diagnose_with_fix_plan() -> Result<(DoctorReport, DoctorFixPlan), String> {
+    let loaded_config = crate::infrastructure::config::load()?;
+    let agents = build_agent_registry(&loaded_config.config.agents)
+        .map_err(|error| format!("agents invalid: {error}"))?;
+    let tasks_root = tasks_root().ok_or_else(|| "task root unavailable".to_string())?;
+    let (sessions, tmux_state) = match list_tmux_sessions() {
+        Ok(sessions) => (sessions.clone(), DoctorTmuxState::Available(sessions)),
+        Err(error) => (Vec::new(), DoctorTmuxState::Unavailable(error)),
+    };
+
+    let report = diagnose_from_inputs(
+        Some(tasks_root.as_path()),
+        &agents,
+        loaded_config.config.projects.as_slice(),
+        loaded_config.config.hidden_base_project_paths.as_slice(),
+        tmux_state,
+    );
+    let plan = plan_doctor_fixes(
+        &report,
+        &agents,
+        loaded_config.config.projects.as_slice(),
+        sessions.as_slice(),
+    );
+    Ok((report, plan))
+}
+
 pub(crate) fn diagnose_from_inputs(
     tasks_root: Option<&Path>,
+    agents: &AgentRegistry,
     projects: &[ProjectConfig],
     hidden_base_project_paths: &[PathBuf],
     tmux_state: DoctorTmuxState,
 ) -> DoctorReport {
     diagnose_from_inputs_at(
         tasks_root,
+        agents,
         projects,
         hidden_base_project_paths,
         tmux_state,
@@ -143,13 +185,14 @@ pub(crate) fn diagnose_from_inputs(
 
 pub(crate) fn diagnose_from_inputs_at(
     tasks_root: Option<&Path>,
+    agents: &AgentRegistry,
     projects: &[ProjectConfig],
     hidden_base_project_paths: &[PathBuf],
     tmux_state: DoctorTmuxState,
     now_unix_secs: u64,
 ) -> DoctorReport {
     let mut findings = Vec::new();
-    let tasks = load_doctor_tasks(tasks_root, &mut findings);
+    let tasks = load_doctor_tasks(tasks_root, agents, &mut findings);
 
     collect_duplicate_slug_findings(tasks.as_slice(), &mut findings);
     collect_worktree_findings(tasks.as_slice(), &mut findings);
@@ -440,6 +483,7 @@ struct LoadedDoctorTask {
 
 fn load_doctor_tasks(
     tasks_root: Option<&Path>,
+    agents: &AgentRegistry,
     findings: &mut Vec<DoctorFinding>,
 ) -> Vec<LoadedDoctorTask> {
     let Some(tasks_root) = tasks_root else {
@@ -488,7 +532,7 @@ fn load_doctor_tasks(
             }
         };
 
-        match decode_task_manifest(raw.as_str()) {
+        match decode_task_manifest(raw.as_str(), agents) {
             Ok(task) => tasks.push(LoadedDoctorTask {
                 manifest_path,
                 task,
@@ -743,7 +787,7 @@ mod tests {
         DoctorSubject, DoctorTmuxState, diagnose_from_inputs, diagnose_from_inputs_at,
     };
     use crate::application::session_cleanup::SessionRecord;
-    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::domain::{AgentRegistry, AgentType, Task, WorkspaceStatus, Worktree};
     use crate::infrastructure::config::{ProjectConfig, ProjectDefaults};
     use crate::infrastructure::task_manifest::encode_task_manifest;
     use std::fs;
@@ -941,6 +985,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![]),
@@ -988,6 +1033,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![]),
@@ -1019,6 +1065,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![]),
@@ -1051,6 +1098,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![]),
@@ -1073,6 +1121,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[ProjectConfig {
                 name: "api".to_string(),
                 path: repo_root.clone(),
@@ -1098,6 +1147,7 @@ mod tests {
 
         let report = diagnose_from_inputs(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[ProjectConfig {
                 name: "api".to_string(),
                 path: repo_root.clone(),
@@ -1132,6 +1182,7 @@ mod tests {
 
         let report = diagnose_from_inputs_at(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![SessionRecord {
@@ -1170,6 +1221,7 @@ mod tests {
 
         let report = diagnose_from_inputs_at(
             Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![SessionRecord {
@@ -1192,6 +1244,7 @@ mod tests {
     fn diagnose_reports_legacy_grove_session_missing_metadata() {
         let report = diagnose_from_inputs_at(
             None,
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Available(vec![SessionRecord {
@@ -1212,6 +1265,7 @@ mod tests {
     fn diagnose_warns_when_tmux_checks_are_unavailable() {
         let report = diagnose_from_inputs(
             None,
+            &AgentRegistry::default(),
             &[],
             &[],
             DoctorTmuxState::Unavailable("tmux missing".to_string()),
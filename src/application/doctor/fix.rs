@@ -0,0 +1,513 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::{DoctorFinding, DoctorFindingKind, DoctorReport};
+use crate::application::session_cleanup::{SessionRecord, session_missing_error};
+use crate::application::task_lifecycle::{
+    materialize_base_task_manifest_for_project_in_root, task_lifecycle_error_message,
+};
+use crate::domain::AgentRegistry;
+use crate::infrastructure::config::ProjectConfig;
+use crate::infrastructure::paths::refer_to_same_location;
+use crate::infrastructure::process::execute_command;
+use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
+
+const BASE_MARKER_FILE: &str = ".grove/base";
+const TASK_MANIFEST_FILE: &str = ".grove/task.toml";
+
+/// A repair `doctor --fix` applies on its own; everything else stays manual.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorFix {
+    pub finding: DoctorFindingKind,
+    pub summary: String,
+    pub change: DoctorFixChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DoctorFixChange {
+    /// `before` is `None` when the file does not exist yet.
+    WriteFile {
+        path: PathBuf,
+        before: Option<String>,
+        after: String,
+    },
+    MaterializeBaseTask {
+        project: Box<ProjectConfig>,
+    },
+    KillSession {
+        session_name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorSkippedFix {
+    pub finding: DoctorFindingKind,
+    pub target: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct DoctorFixPlan {
+    pub fixes: Vec<DoctorFix>,
+    pub skipped: Vec<DoctorSkippedFix>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorFixOutcome {
+    pub summary: String,
+    pub diff: Vec<String>,
+    pub backup_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+impl DoctorFix {
+    /// What applying the fix will change, shown before asking to apply it.
+    pub fn preview(&self) -> Vec<String> {
+        match &self.change {
+            DoctorFixChange::WriteFile {
+                path,
+                before,
+                after,
+            } => file_diff(path, before.as_deref(), after),
+            DoctorFixChange::MaterializeBaseTask { project } => vec![format!(
+                "+ base task manifest for {} ({})",
+                project.name,
+                project.path.display()
+            )],
+            DoctorFixChange::KillSession { session_name } => {
+                vec![format!("- tmux session {session_name}")]
+            }
+        }
+    }
+}
+
+pub(crate) fn plan_doctor_fixes(
+    report: &DoctorReport,
+    agents: &AgentRegistry,
+    projects: &[ProjectConfig],
+    sessions: &[SessionRecord],
+) -> DoctorFixPlan {
+    let mut plan = DoctorFixPlan::default();
+    let mut missing_worktrees: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+
+    for finding in &report.findings {
+        let target = finding.subject.targets().join(", ");
+        let skip = |reason: &str| DoctorSkippedFix {
+            finding: finding.kind,
+            target: target.clone(),
+            reason: reason.to_string(),
+        };
+        match finding.kind {
+            DoctorFindingKind::MissingWorktreePath => {
+                match (
+                    finding.subject.manifest_path.as_deref(),
+                    finding.subject.worktree_path.as_deref(),
+                ) {
+                    (Some(manifest_path), Some(worktree_path)) => missing_worktrees
+                        .entry(PathBuf::from(manifest_path))
+                        .or_default()
+                        .push(PathBuf::from(worktree_path)),
+                    _ => plan
+                        .skipped
+                        .push(skip("finding has no manifest or worktree path")),
+                }
+            }
+            DoctorFindingKind::MissingBaseMarker => match plan_base_marker_fix(finding, agents) {
+                Ok(fix) => plan.fixes.push(fix),
+                Err(reason) => plan.skipped.push(skip(reason.as_str())),
+            },
+            DoctorFindingKind::ConfiguredRepoMissingBaseTaskManifest => {
+                let project =
+                    finding
+                        .subject
+                        .repository_path
+                        .as_deref()
+                        .and_then(|repository_path| {
+                            projects.iter().find(|project| {
+                                refer_to_same_location(
+                                    project.path.as_path(),
+                                    Path::new(repository_path),
+                                )
+                            })
+                        });
+                match project {
+                    Some(project) => plan.fixes.push(DoctorFix {
+                        finding: finding.kind,
+                        summary: format!("materialize base task for {}", project.name),
+                        change: DoctorFixChange::MaterializeBaseTask {
+                            project: Box::new(project.clone()),
+                        },
+                    }),
+                    None => plan
+                        .skipped
+                        .push(skip("repository is no longer configured")),
+                }
+            }
+            DoctorFindingKind::OrphanedGroveSession | DoctorFindingKind::StaleAuxiliarySession => {
+                let Some(session_name) = finding.subject.session_name.clone() else {
+                    plan.skipped.push(skip("finding has no session name"));
+                    continue;
+                };
+                let attached = sessions
+                    .iter()
+                    .any(|session| session.name == session_name && session.attached_clients > 0);
+                if attached {
+                    plan.skipped
+                        .push(skip("a client is attached to the session"));
+                    continue;
+                }
+                plan.fixes.push(DoctorFix {
+                    finding: finding.kind,
+                    summary: format!("kill tmux session {session_name}"),
+                    change: DoctorFixChange::KillSession { session_name },
+                });
+            }
+            DoctorFindingKind::InvalidTaskManifest
+            | DoctorFindingKind::DuplicateTaskSlug
+            | DoctorFindingKind::LegacyGroveSessionMissingMetadata
+            | DoctorFindingKind::ManifestRepositoryMismatch => {
+                plan.skipped.push(skip("needs a manual decision"));
+            }
+            DoctorFindingKind::SessionCheckSkipped => {}
+        }
+    }
+
+    for (manifest_path, worktree_paths) in missing_worktrees {
+        match plan_prune_fix(manifest_path.as_path(), worktree_paths.as_slice(), agents) {
+            Ok(fix) => plan.fixes.push(fix),
+            Err(reason) => plan.skipped.push(DoctorSkippedFix {
+                finding: DoctorFindingKind::MissingWorktreePath,
+                target: manifest_path.display().to_string(),
+                reason,
+            }),
+        }
+    }
+
+    plan
+}
+
+fn plan_prune_fix(
+    manifest_path: &Path,
+    worktree_paths: &[PathBuf],
+    agents: &AgentRegistry,
+) -> Result<DoctorFix, String> {
+    let before = fs::read_to_string(manifest_path)
+        .map_err(|error| format!("read {} failed: {error}", manifest_path.display()))?;
+    let mut task = decode_task_manifest(before.as_str(), agents)?;
+    task.worktrees
+        .retain(|worktree| !worktree_paths.contains(&worktree.path));
+    if task.worktrees.is_empty() {
+        return Err("every worktree is missing, delete the task instead".to_string());
+    }
+    let after = encode_task_manifest(&task)?;
+    let removed = worktree_paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+    Ok(DoctorFix {
+        finding: DoctorFindingKind::MissingWorktreePath,
+        summary: format!("prune missing worktree {removed} from task {}", task.slug),
+        change: DoctorFixChange::WriteFile {
+            path: manifest_path.to_path_buf(),
+            before: Some(before),
+            after,
+        },
+    })
+}
+
+fn plan_base_marker_fix(
+    finding: &DoctorFinding,
+    agents: &AgentRegistry,
+) -> Result<DoctorFix, String> {
+    let (Some(manifest_path), Some(worktree_path)) = (
+        finding.subject.manifest_path.as_deref(),
+        finding.subject.worktree_path.as_deref(),
+    ) else {
+        return Err("finding has no manifest or worktree path".to_string());
+    };
+    let raw = fs::read_to_string(manifest_path)
+        .map_err(|error| format!("read {manifest_path} failed: {error}"))?;
+    let task = decode_task_manifest(raw.as_str(), agents)?;
+    let base_branch = task
+        .worktrees
+        .iter()
+        .find(|worktree| worktree.path == Path::new(worktree_path))
+        .and_then(|worktree| worktree.base_branch.clone())
+        .filter(|branch| !branch.trim().is_empty())
+        .ok_or_else(|| "manifest does not record the base branch".to_string())?;
+
+    let marker_path = Path::new(worktree_path).join(BASE_MARKER_FILE);
+    Ok(DoctorFix {
+        finding: finding.kind,
+        summary: format!("write base marker {base_branch} for {worktree_path}"),
+        change: DoctorFixChange::WriteFile {
+            before: fs::read_to_string(&marker_path).ok(),
+            path: marker_path,
+            after: format!("{base_branch}\n"),
+        },
+    })
+}
+
+/// Applies every planned fix, copying each file to `backup_dir` before it is
+/// overwritten. A failed fix does not stop the others.
+pub fn apply_doctor_fixes(
+    plan: &DoctorFixPlan,
+    tasks_root: &Path,
+    backup_dir: &Path,
+    mut kill_session: impl FnMut(&str) -> Result<(), String>,
+) -> Vec<DoctorFixOutcome> {
+    plan.fixes
+        .iter()
+        .map(|fix| {
+            let mut outcome = DoctorFixOutcome {
+                summary: fix.summary.clone(),
+                diff: Vec::new(),
+                backup_path: None,
+                error: None,
+            };
+            let result = match &fix.change {
+                DoctorFixChange::WriteFile {
+                    path,
+                    before,
+                    after,
+                } => write_file_with_backup(path, before.as_deref(), after, backup_dir).map(
+                    |backup_path| {
+                        outcome.backup_path = backup_path;
+                        outcome.diff = file_diff(path, before.as_deref(), after);
+                    },
+                ),
+                DoctorFixChange::MaterializeBaseTask { project } => {
+                    // Diagnosis already found no base task owning this repository.
+                    match materialize_base_task_manifest_for_project_in_root(
+                        tasks_root,
+                        project,
+                        &[],
+                    ) {
+                        Ok(Some(created)) => encode_task_manifest(&created.task).map(|after| {
+                            let path = created.task_root.join(TASK_MANIFEST_FILE);
+                            outcome.diff = file_diff(path.as_path(), None, after.as_str());
+                        }),
+                        Ok(None) => Ok(()),
+                        Err(error) => Err(task_lifecycle_error_message(&error)),
+                    }
+                }
+                DoctorFixChange::KillSession { session_name } => kill_session(session_name)
+                    .map(|()| outcome.diff = vec![format!("- tmux session {session_name}")]),
+            };
+            outcome.error = result.err();
+            outcome
+        })
+        .collect()
+}
+
+pub fn kill_doctor_session(session_name: &str) -> Result<(), String> {
+    let command = vec![
+        "tmux".to_string(),
+        "kill-session".to_string(),
+        "-t".to_string(),
+        session_name.to_string(),
+    ];
+    match execute_command(command.as_slice()) {
+        Err(error) if !session_missing_error(error.to_string().as_str()) => Err(error.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn write_file_with_backup(
+    path: &Path,
+    before: Option<&str>,
+    after: &str,
+    backup_dir: &Path,
+) -> Result<Option<PathBuf>, String> {
+    let current = fs::read_to_string(path).ok();
+    if current.as_deref() != before {
+        return Err(format!(
+            "{} changed since diagnosis, rerun doctor",
+            path.display()
+        ));
+    }
+
+    let backup_path = match current {
+        Some(current) => {
+            let backup_path = backup_dir.join(path.strip_prefix("/").unwrap_or(path));
+            if let Some(parent) = backup_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|error| format!("create {} failed: {error}", parent.display()))?;
+            }
+            fs::write(&backup_path, current)
+                .map_err(|error| format!("backup to {} failed: {error}", backup_path.display()))?;
+            Some(backup_path)
+        }
+        None => None,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("create {} failed: {error}", parent.display()))?;
+    }
+    fs::write(path, after).map_err(|error| format!("write {} failed: {error}", path.display()))?;
+    Ok(backup_path)
+}
+
+/// Changed lines only, `-` for removed and `+` for added, under a file header.
+pub fn file_diff(path: &Path, before: Option<&str>, after: &str) -> Vec<String> {
+    let before_lines = before.unwrap_or_default().lines().collect::<Vec<&str>>();
+    let after_lines = after.lines().collect::<Vec<&str>>();
+
+    // Longest common subsequence table, suffix-based so the walk below runs forward.
+    let mut common = vec![vec![0usize; after_lines.len() + 1]; before_lines.len() + 1];
+    for left in (0..before_lines.len()).rev() {
+        for right in (0..after_lines.len()).rev() {
+            common[left][right] = if before_lines[left] == after_lines[right] {
+                common[left + 1][right + 1] + 1
+            } else {
+                common[left + 1][right].max(common[left][right + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![
+        match before {
+            Some(_) => format!("--- {}", path.display()),
+            None => "--- /dev/null".to_string(),
+        },
+        format!("+++ {}", path.display()),
+    ];
+    let (mut left, mut right) = (0, 0);
+    while left < before_lines.len() || right < after_lines.len() {
+        if left < before_lines.len()
+            && right < after_lines.len()
+            && before_lines[left] == after_lines[right]
+        {
+            left += 1;
+            right += 1;
+        } else if right < after_lines.len()
+            && (left == before_lines.len() || common[left][right + 1] >= common[left + 1][right])
+        {
+            diff.push(format!("+{}", after_lines[right]));
+            right += 1;
+        } else {
+            diff.push(format!("-{}", before_lines[left]));
+            left += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{DoctorFixChange, apply_doctor_fixes, file_diff, plan_doctor_fixes};
+    use crate::application::doctor::{DoctorFindingKind, DoctorTmuxState, diagnose_from_inputs};
+    use crate::application::session_cleanup::SessionRecord;
+    use crate::domain::{AgentRegistry, AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
+    use crate::test_support::unique_test_dir;
+
+    fn worktree(root: &Path, name: &str) -> Worktree {
+        Worktree::try_new(
+            name.to_string(),
+            root.join("repos").join(name),
+            root.join("tasks/flaky").join(name),
+            "flaky".to_string(),
+            AgentType::Codex,
+            WorkspaceStatus::Idle,
+        )
+        .expect("worktree should be valid")
+        .with_base_branch(Some("main".to_string()))
+    }
+
+    #[test]
+    fn fixes_prune_missing_worktrees_and_write_base_markers_with_backups() {
+        let root = unique_test_dir("grove-doctor-fix-apply");
+        let tasks_root = root.join("tasks");
+        let web = worktree(root.as_path(), "web");
+        let api = worktree(root.as_path(), "api");
+        fs::create_dir_all(web.path.join(".grove")).expect("web worktree should exist");
+        let task = Task::try_new(
+            "flaky".to_string(),
+            "flaky".to_string(),
+            tasks_root.join("flaky"),
+            "flaky".to_string(),
+            vec![web.clone(), api.clone()],
+        )
+        .expect("task should be valid");
+        let manifest_path = tasks_root.join("flaky/.grove/task.toml");
+        fs::create_dir_all(tasks_root.join("flaky/.grove")).expect("manifest dir should exist");
+        let manifest = encode_task_manifest(&task).expect("manifest should encode");
+        fs::write(&manifest_path, manifest.as_str()).expect("manifest should write");
+        let session = SessionRecord {
+            name: "grove-wt-gone-web".to_string(),
+            created_unix_secs: None,
+            attached_clients: 1,
+        };
+
+        let report = diagnose_from_inputs(
+            Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
+            &[],
+            &[],
+            DoctorTmuxState::Available(vec![session.clone()]),
+        );
+        let plan = plan_doctor_fixes(&report, &AgentRegistry::default(), &[], &[session]);
+        assert_eq!(plan.fixes.len(), 2);
+        assert!(plan.skipped.iter().any(|skipped| {
+            skipped.finding == DoctorFindingKind::OrphanedGroveSession
+                && skipped.reason == "a client is attached to the session"
+        }));
+        assert!(
+            plan.fixes
+                .iter()
+                .all(|fix| matches!(fix.change, DoctorFixChange::WriteFile { .. }))
+        );
+
+        let backup_dir = root.join("backup");
+        let outcomes =
+            apply_doctor_fixes(&plan, tasks_root.as_path(), backup_dir.as_path(), |_| {
+                Err("no sessions expected".to_string())
+            });
+        assert!(outcomes.iter().all(|outcome| outcome.error.is_none()));
+        assert_eq!(
+            fs::read_to_string(web.path.join(".grove/base")).expect("marker should exist"),
+            "main\n"
+        );
+        let pruned = decode_task_manifest(
+            fs::read_to_string(&manifest_path)
+                .expect("manifest should read")
+                .as_str(),
+            &AgentRegistry::default(),
+        )
+        .expect("manifest should decode");
+        assert_eq!(pruned.worktrees.len(), 1);
+        assert_eq!(pruned.worktrees[0].path, web.path);
+
+        let backup = outcomes
+            .iter()
+            .find_map(|outcome| outcome.backup_path.clone())
+            .expect("manifest should be backed up");
+        assert_eq!(
+            fs::read_to_string(backup).expect("backup should read"),
+            manifest
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_diff_lists_only_changed_lines() {
+        assert_eq!(
+            file_diff(Path::new("/t.toml"), Some("a\nb\nc\n"), "a\nc\nd\n"),
+            vec!["--- /t.toml", "+++ /t.toml", "-b", "+d"]
+        );
+        assert_eq!(
+            file_diff(Path::new("/base"), None, "main\n"),
+            vec!["--- /dev/null", "+++ /base", "+main"]
+        );
+    }
+}
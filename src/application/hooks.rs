@@ -0,0 +1,647 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::application::task_lifecycle::{CreateTaskRequest, TaskBranchSource, TaskLifecycleError};
+use crate::domain::{Task, Workspace};
+use crate::infrastructure::config::{HookEvent, ProjectConfig};
+use crate::infrastructure::paths::refer_to_same_location;
+use crate::infrastructure::task_manifest::workspace_status_name;
+
+const OUTPUT_LIMIT_BYTES: usize = 4096;
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// State handed to every hook as JSON on stdin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HookContext {
+    pub task_slug: Option<String>,
+    pub task_name: Option<String>,
+    pub worktree_paths: Vec<PathBuf>,
+    pub branch: Option<String>,
+    pub base_branch: Option<String>,
+    pub agent: Option<String>,
+    pub status: Option<String>,
+    /// Attention reason, set for `on-attention` only.
+    pub reason: Option<String>,
+}
+
+impl HookContext {
+    /// Context for an existing task and all of its worktrees.
+    pub fn for_task(task: &Task) -> Self {
+        let first = task.worktrees.first();
+        Self {
+            task_slug: Some(task.slug.clone()),
+            task_name: Some(task.name.clone()),
+            worktree_paths: task
+                .worktrees
+                .iter()
+                .map(|worktree| worktree.path.clone())
+                .collect(),
+            branch: Some(task.branch.clone()),
+            base_branch: first.and_then(|worktree| worktree.base_branch.clone()),
+            agent: first.map(|worktree| worktree.agent.marker().to_string()),
+            status: None,
+            reason: None,
+        }
+    }
+
+    /// Context for a single worktree, e.g. around its agent.
+    pub fn for_workspace(workspace: &Workspace, task_name: Option<String>) -> Self {
+        Self {
+            task_slug: workspace.task_slug.clone(),
+            task_name,
+            worktree_paths: vec![workspace.path.clone()],
+            branch: Some(workspace.branch.clone()),
+            base_branch: workspace.base_branch.clone(),
+            agent: Some(workspace.agent.marker().to_string()),
+            status: Some(workspace_status_name(workspace.status).to_string()),
+            reason: None,
+        }
+    }
+
+    /// Context for a task that is about to be created.
+    pub fn for_create_request(request: &CreateTaskRequest) -> Self {
+        let branch = match &request.branch_source {
+            TaskBranchSource::BaseBranch => request.task_name.clone(),
+            TaskBranchSource::PullRequest { branch_name, .. } => branch_name.clone(),
+        };
+        Self {
+            task_name: Some(request.task_name.clone()),
+            branch: Some(branch),
+            agent: Some(request.agent.marker().to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+/// A configured hook command bound to the project that declared it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookCommand {
+    pub project: String,
+    pub project_path: PathBuf,
+    pub command: String,
+    pub timeout: Duration,
+}
+
+/// Every hook for one event, ready to run off the UI thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookInvocation {
+    pub event: HookEvent,
+    pub commands: Vec<HookCommand>,
+    pub context: HookContext,
+}
+
+impl HookInvocation {
+    /// Collects `event` hooks across `projects`, `None` when none is configured.
+    pub fn for_projects<'a>(
+        event: HookEvent,
+        projects: impl IntoIterator<Item = &'a ProjectConfig>,
+        context: HookContext,
+    ) -> Option<Self> {
+        let commands = projects
+            .into_iter()
+            .flat_map(|project| {
+                project
+                    .defaults
+                    .hooks
+                    .iter()
+                    .filter(move |hook| hook.event == event && !hook.command.trim().is_empty())
+                    .map(move |hook| HookCommand {
+                        project: project.name.clone(),
+                        project_path: project.path.clone(),
+                        command: hook.command.clone(),
+                        timeout: Duration::from_secs(hook.timeout_secs.max(1)),
+                    })
+            })
+            .collect::<Vec<HookCommand>>();
+        if commands.is_empty() {
+            return None;
+        }
+        Some(Self {
+            event,
+            commands,
+            context,
+        })
+    }
+
+    /// Hooks for `event` from the projects owning `task`'s worktrees.
+    pub fn for_task(event: HookEvent, projects: &[ProjectConfig], task: &Task) -> Option<Self> {
+        let owners = projects.iter().filter(|project| {
+            task.worktrees
+                .iter()
+                .any(|worktree| refer_to_same_location(&project.path, &worktree.repository_path))
+        });
+        Self::for_projects(event, owners, HookContext::for_task(task))
+    }
+
+    /// Hooks for `event` from the project owning `workspace`.
+    pub fn for_workspace(
+        event: HookEvent,
+        projects: &[ProjectConfig],
+        workspace: &Workspace,
+        task_name: Option<String>,
+    ) -> Option<Self> {
+        let project_path = workspace.project_path.as_ref()?;
+        let owners = projects
+            .iter()
+            .filter(|project| refer_to_same_location(&project.path, project_path));
+        Self::for_projects(
+            event,
+            owners,
+            HookContext::for_workspace(workspace, task_name),
+        )
+    }
+
+    pub fn with_context(mut self, context: HookContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn run(&self) -> HookRunReport {
+        self.run_with(&CommandHookRunner)
+    }
+
+    /// Runs hooks in order. A failing `pre-*` hook vetoes and skips the rest.
+    pub fn run_with(&self, runner: &impl HookRunner) -> HookRunReport {
+        let mut report = HookRunReport::default();
+        for command in &self.commands {
+            let payload = hook_payload(self.event, command, &self.context);
+            let outcome = runner.run(self.event, command, payload.as_str());
+            let vetoed = self.event.can_veto() && !outcome.succeeded();
+            if vetoed {
+                report.veto = Some(outcome.failure_message());
+            }
+            report.outcomes.push(outcome);
+            if vetoed {
+                break;
+            }
+        }
+        report
+    }
+}
+
+/// Runs `operation` after its `pre-*` hooks, then whatever `post` derives from
+/// the result. A veto replaces the operation with `vetoed`.
+pub fn run_between_hooks<T>(
+    pre: Option<HookInvocation>,
+    operation: impl FnOnce() -> T,
+    vetoed: impl FnOnce(String) -> T,
+    post: impl FnOnce(&T) -> Option<HookInvocation>,
+) -> (T, Vec<HookOutcome>) {
+    run_between_hooks_with(&CommandHookRunner, pre, operation, vetoed, post)
+}
+
+pub fn run_between_hooks_with<T>(
+    runner: &impl HookRunner,
+    pre: Option<HookInvocation>,
+    operation: impl FnOnce() -> T,
+    vetoed: impl FnOnce(String) -> T,
+    post: impl FnOnce(&T) -> Option<HookInvocation>,
+) -> (T, Vec<HookOutcome>) {
+    let pre_report = pre.map(|hooks| hooks.run_with(runner)).unwrap_or_default();
+    let mut outcomes = pre_report.outcomes;
+    if let Some(veto) = pre_report.veto {
+        return (vetoed(veto), outcomes);
+    }
+    let value = operation();
+    if let Some(post) = post(&value) {
+        outcomes.extend(post.run_with(runner).outcomes);
+    }
+    (value, outcomes)
+}
+
+/// Runs `create` between the `pre-create` and `post-create` hooks of `repositories`.
+pub fn create_with_hooks<T>(
+    repositories: &[ProjectConfig],
+    context: HookContext,
+    create: impl FnOnce() -> Result<T, TaskLifecycleError>,
+    created_task: fn(&T) -> &Task,
+) -> (Result<T, TaskLifecycleError>, Vec<HookOutcome>) {
+    let pre_create_hooks =
+        HookInvocation::for_projects(HookEvent::PreCreate, repositories, context);
+    let post_create_hooks =
+        HookInvocation::for_projects(HookEvent::PostCreate, repositories, HookContext::default());
+    run_between_hooks(
+        pre_create_hooks,
+        create,
+        |veto| Err(TaskLifecycleError::HookVetoed(veto)),
+        |result| {
+            let task = created_task(result.as_ref().ok()?);
+            post_create_hooks.map(|hooks| hooks.with_context(HookContext::for_task(task)))
+        },
+    )
+}
+
+/// Failed hooks that could not veto, as warnings. Vetoes surface through the
+/// operation they stopped.
+pub fn hook_failure_warnings(outcomes: &[HookOutcome]) -> Vec<String> {
+    outcomes
+        .iter()
+        .filter(|outcome| !outcome.succeeded() && !outcome.event.can_veto())
+        .map(HookOutcome::failure_message)
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutcome {
+    pub event: HookEvent,
+    pub project: String,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub stdout: String,
+    pub stderr: String,
+    /// Set when the hook could not be run at all.
+    pub error: Option<String>,
+}
+
+impl HookOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none() && !self.timed_out && self.exit_code == Some(0)
+    }
+
+    pub fn failure_message(&self) -> String {
+        let cause = if let Some(error) = self.error.as_ref() {
+            error.clone()
+        } else if self.timed_out {
+            "timed out".to_string()
+        } else if let Some(code) = self.exit_code {
+            format!("exited with {code}")
+        } else {
+            "was killed".to_string()
+        };
+        let detail = [self.stderr.as_str(), self.stdout.as_str()]
+            .into_iter()
+            .flat_map(str::lines)
+            .map(str::trim)
+            .find(|line| !line.is_empty());
+        match detail {
+            Some(detail) => format!("{} hook {cause}: {detail}", self.event.config_key()),
+            None => format!("{} hook {cause}", self.event.config_key()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookRunReport {
+    pub outcomes: Vec<HookOutcome>,
+    pub veto: Option<String>,
+}
+
+pub trait HookRunner {
+    fn run(&self, event: HookEvent, command: &HookCommand, payload: &str) -> HookOutcome;
+}
+
+/// Runs hooks with `sh -c` from the project root.
+pub struct CommandHookRunner;
+
+impl HookRunner for CommandHookRunner {
+    fn run(&self, event: HookEvent, command: &HookCommand, payload: &str) -> HookOutcome {
+        let started_at = Instant::now();
+        let mut outcome = HookOutcome {
+            event,
+            project: command.project.clone(),
+            command: command.command.clone(),
+            exit_code: None,
+            timed_out: false,
+            duration_ms: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+        };
+        match run_command(event, command, payload) {
+            Ok(output) => {
+                outcome.exit_code = output.status.and_then(|status| status.code());
+                outcome.timed_out = output.status.is_none();
+                outcome.stdout = output.stdout;
+                outcome.stderr = output.stderr;
+            }
+            Err(error) => outcome.error = Some(error),
+        }
+        outcome.duration_ms = u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+        outcome
+    }
+}
+
+struct CapturedOutput {
+    /// `None` when the hook was killed after its timeout.
+    status: Option<ExitStatus>,
+    stdout: String,
+    stderr: String,
+}
+
+fn run_command(
+    event: HookEvent,
+    command: &HookCommand,
+    payload: &str,
+) -> Result<CapturedOutput, String> {
+    let mut process = Command::new("sh");
+    process
+        .arg("-c")
+        .arg(&command.command)
+        .env("GROVE_HOOK_EVENT", event.config_key())
+        .env("GROVE_HOOK_PROJECT", &command.project)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if command.project_path.is_dir() {
+        process.current_dir(&command.project_path);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        process.process_group(0);
+    }
+    let mut child = process
+        .spawn()
+        .map_err(|error| format!("spawn failed: {error}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may exit before reading it.
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let stdout = child.stdout.take().map(spawn_reader);
+    let stderr = child.stderr.take().map(spawn_reader);
+
+    let started_at = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started_at.elapsed() >= command.timeout => {
+                kill_process_group(&mut child);
+                break None;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(error) => {
+                kill_process_group(&mut child);
+                return Err(format!("wait failed: {error}"));
+            }
+        }
+    };
+
+    Ok(CapturedOutput {
+        status,
+        stdout: join_reader(stdout),
+        stderr: join_reader(stderr),
+    })
+}
+
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill")
+            .args(["-KILL", "--"])
+            .arg(format!("-{}", child.id()))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn spawn_reader(mut source: impl Read + Send + 'static) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut captured = Vec::new();
+        let mut buffer = [0_u8; 1024];
+        loop {
+            match source.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    let room = OUTPUT_LIMIT_BYTES.saturating_sub(captured.len());
+                    captured.extend_from_slice(&buffer[..read.min(room)]);
+                }
+            }
+        }
+        String::from_utf8_lossy(&captured).trim_end().to_string()
+    })
+}
+
+fn join_reader(reader: Option<JoinHandle<String>>) -> String {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
+
+fn hook_payload(event: HookEvent, command: &HookCommand, context: &HookContext) -> String {
+    let mut payload = serde_json::to_value(context).unwrap_or(Value::Null);
+    if let Value::Object(fields) = &mut payload {
+        fields.insert("event".to_string(), Value::from(event.config_key()));
+        fields.insert("project".to_string(), Value::from(command.project.clone()));
+        fields.insert(
+            "project_path".to_string(),
+            Value::from(path_text(&command.project_path)),
+        );
+    }
+    payload.to_string()
+}
+
+fn path_text(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandHookRunner, HookCommand, HookContext, HookInvocation, HookOutcome, HookRunner,
+    };
+    use crate::infrastructure::config::{HookConfig, HookEvent, ProjectConfig, ProjectDefaults};
+    use crate::test_support::unique_test_dir;
+    use serde_json::Value;
+    use std::cell::RefCell;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    fn project(name: &str, path: PathBuf, hooks: Vec<HookConfig>) -> ProjectConfig {
+        ProjectConfig {
+            name: name.to_string(),
+            path,
+            defaults: ProjectDefaults {
+                hooks,
+                ..ProjectDefaults::default()
+            },
+        }
+    }
+
+    fn hook(event: HookEvent, command: &str) -> HookConfig {
+        HookConfig {
+            event,
+            command: command.to_string(),
+            timeout_secs: 5,
+        }
+    }
+
+    fn context() -> HookContext {
+        HookContext {
+            task_slug: Some("flaky-tests".to_string()),
+            task_name: Some("flaky-tests".to_string()),
+            worktree_paths: vec![PathBuf::from("/tasks/flaky-tests/grove")],
+            branch: Some("flaky-tests".to_string()),
+            base_branch: Some("main".to_string()),
+            agent: Some("codex".to_string()),
+            status: Some("idle".to_string()),
+            reason: None,
+        }
+    }
+
+    struct RecordingRunner {
+        exit_codes: Vec<i32>,
+        payloads: RefCell<Vec<String>>,
+    }
+
+    impl HookRunner for RecordingRunner {
+        fn run(&self, event: HookEvent, command: &HookCommand, payload: &str) -> HookOutcome {
+            let mut payloads = self.payloads.borrow_mut();
+            let exit_code = self.exit_codes[payloads.len()];
+            payloads.push(payload.to_string());
+            HookOutcome {
+                event,
+                project: command.project.clone(),
+                command: command.command.clone(),
+                exit_code: Some(exit_code),
+                timed_out: false,
+                duration_ms: 1,
+                stdout: String::new(),
+                stderr: format!("refused by {}", command.command),
+                error: None,
+            }
+        }
+    }
+
+    #[test]
+    fn invocation_collects_only_matching_event_hooks_across_projects() {
+        let projects = [
+            project(
+                "grove",
+                PathBuf::from("/repos/grove"),
+                vec![
+                    hook(HookEvent::PreMerge, "make check"),
+                    hook(HookEvent::PostMerge, "make deploy"),
+                ],
+            ),
+            project(
+                "site",
+                PathBuf::from("/repos/site"),
+                vec![hook(HookEvent::PreMerge, "npm test")],
+            ),
+        ];
+
+        let invocation = HookInvocation::for_projects(HookEvent::PreMerge, &projects, context())
+            .expect("pre-merge hooks should be collected");
+        let commands = invocation
+            .commands
+            .iter()
+            .map(|command| (command.project.as_str(), command.command.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        assert_eq!(
+            commands,
+            vec![("grove", "make check"), ("site", "npm test")]
+        );
+        assert!(HookInvocation::for_projects(HookEvent::PreDelete, &projects, context()).is_none());
+    }
+
+    #[test]
+    fn failing_pre_hook_vetoes_and_skips_remaining_hooks() {
+        let projects = [project(
+            "grove",
+            PathBuf::from("/repos/grove"),
+            vec![
+                hook(HookEvent::PreDelete, "first"),
+                hook(HookEvent::PreDelete, "second"),
+                hook(HookEvent::PreDelete, "third"),
+            ],
+        )];
+        let invocation = HookInvocation::for_projects(HookEvent::PreDelete, &projects, context())
+            .expect("hooks should be collected");
+        let runner = RecordingRunner {
+            exit_codes: vec![0, 3, 0],
+            payloads: RefCell::new(Vec::new()),
+        };
+
+        let report = invocation.run_with(&runner);
+
+        assert_eq!(report.outcomes.len(), 2);
+        assert_eq!(
+            report.veto.as_deref(),
+            Some("pre-delete hook exited with 3: refused by second")
+        );
+        let payload: Value = serde_json::from_str(runner.payloads.borrow()[0].as_str())
+            .expect("payload should be json");
+        assert_eq!(payload["event"], "pre-delete");
+        assert_eq!(payload["project"], "grove");
+        assert_eq!(payload["task_slug"], "flaky-tests");
+        assert_eq!(payload["worktree_paths"][0], "/tasks/flaky-tests/grove");
+        assert_eq!(payload["status"], "idle");
+    }
+
+    #[test]
+    fn failing_post_hook_does_not_veto() {
+        let projects = [project(
+            "grove",
+            PathBuf::from("/repos/grove"),
+            vec![
+                hook(HookEvent::PostMerge, "first"),
+                hook(HookEvent::PostMerge, "second"),
+            ],
+        )];
+        let invocation = HookInvocation::for_projects(HookEvent::PostMerge, &projects, context())
+            .expect("hooks should be collected");
+        let runner = RecordingRunner {
+            exit_codes: vec![1, 0],
+            payloads: RefCell::new(Vec::new()),
+        };
+
+        let report = invocation.run_with(&runner);
+
+        assert_eq!(report.outcomes.len(), 2);
+        assert_eq!(report.veto, None);
+    }
+
+    #[test]
+    fn command_runner_passes_context_on_stdin_and_captures_output() {
+        let root = unique_test_dir("grove-hooks-stdin");
+        let command = HookCommand {
+            project: "grove".to_string(),
+            project_path: root.clone(),
+            command: "cat; echo \"$GROVE_HOOK_EVENT\" >&2; pwd >&2".to_string(),
+            timeout: Duration::from_secs(5),
+        };
+
+        let outcome = CommandHookRunner.run(HookEvent::PostCreate, &command, "{\"a\":1}");
+
+        assert!(outcome.succeeded());
+        assert_eq!(outcome.stdout, "{\"a\":1}");
+        let stderr_lines = outcome.stderr.lines().collect::<Vec<&str>>();
+        assert_eq!(stderr_lines[0], "post-create");
+        assert_eq!(
+            fs::canonicalize(stderr_lines[1]).expect("cwd should resolve"),
+            fs::canonicalize(&root).expect("root should resolve")
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn command_runner_kills_hooks_that_exceed_their_timeout() {
+        let command = HookCommand {
+            project: "grove".to_string(),
+            project_path: PathBuf::from("/nonexistent/grove"),
+            command: "sleep 30 & sleep 30".to_string(),
+            timeout: Duration::from_millis(200),
+        };
+
+        let started_at = Instant::now();
+        let outcome = CommandHookRunner.run(HookEvent::PreAgentStart, &command, "{}");
+
+        assert!(outcome.timed_out);
+        assert!(!outcome.succeeded());
+        assert_eq!(outcome.failure_message(), "pre-agent-start hook timed out");
+        assert!(started_at.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::Instant;
 
+use crate::domain::WorkspaceStatus;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InteractiveState {
     pub active: bool,
@@ -184,6 +186,22 @@ pub fn encode_paste_payload(text: &str, bracketed_paste: bool) -> String {
     text.to_string()
 }
 
+/// Whether an agent in `status` can take a broadcast prompt without forcing it.
+pub fn broadcast_accepts_status(status: WorkspaceStatus) -> bool {
+    matches!(status, WorkspaceStatus::Waiting | WorkspaceStatus::Idle)
+}
+
+/// Types `text` into `target_session` as one bracketed paste, then submits it.
+pub fn submit_prompt_commands(target_session: &str, text: &str) -> Vec<Vec<String>> {
+    [
+        InteractiveAction::SendLiteral(encode_paste_payload(text, true)),
+        InteractiveAction::SendNamed("Enter".to_string()),
+    ]
+    .iter()
+    .filter_map(|action| multiplexer_send_input_command(target_session, action))
+    .collect()
+}
+
 pub fn render_cursor_overlay(line: &str, cursor_col: usize, cursor_visible: bool) -> String {
     if !cursor_visible {
         return line.to_string();
@@ -289,9 +307,11 @@ mod tests {
     use std::time::Instant;
 
     use super::{
-        InteractiveAction, InteractiveKey, InteractiveState, encode_paste_payload, is_paste_event,
-        multiplexer_send_input_command, render_cursor_overlay, render_cursor_overlay_ansi,
+        InteractiveAction, InteractiveKey, InteractiveState, broadcast_accepts_status,
+        encode_paste_payload, is_paste_event, multiplexer_send_input_command,
+        render_cursor_overlay, render_cursor_overlay_ansi, submit_prompt_commands,
     };
+    use crate::domain::WorkspaceStatus;
 
     #[test]
     fn double_escape_is_forwarded_to_the_session() {
@@ -451,6 +471,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn broadcast_pastes_prompt_then_submits_it() {
+        assert_eq!(
+            submit_prompt_commands("grove-ws-auth", "run the tests\nthen fix"),
+            vec![
+                vec![
+                    "tmux".to_string(),
+                    "send-keys".to_string(),
+                    "-l".to_string(),
+                    "-t".to_string(),
+                    "grove-ws-auth".to_string(),
+                    "\u{1b}[200~run the tests\nthen fix\u{1b}[201~".to_string(),
+                ],
+                vec![
+                    "tmux".to_string(),
+                    "send-keys".to_string(),
+                    "-t".to_string(),
+                    "grove-ws-auth".to_string(),
+                    "Enter".to_string(),
+                ],
+            ]
+        );
+        assert!(broadcast_accepts_status(WorkspaceStatus::Waiting));
+        assert!(broadcast_accepts_status(WorkspaceStatus::Idle));
+        assert!(!broadcast_accepts_status(WorkspaceStatus::Active));
+        assert!(!broadcast_accepts_status(WorkspaceStatus::Thinking));
+    }
+
     #[test]
     fn cursor_overlay_marks_current_column() {
         assert_eq!(render_cursor_overlay("abcd", 1, true), "a|bcd");
@@ -1,8 +1,15 @@
+pub mod agent_control;
 pub mod agent_runtime;
 pub mod doctor;
 pub mod hardening;
+pub mod hooks;
 pub mod interactive;
+pub mod notifications;
+pub mod ports;
 pub mod preview;
+pub mod prompt_queue;
+pub mod pull_request_sync;
+pub mod recipes;
 pub mod scale_benchmark;
 pub mod session_cleanup;
 pub mod task_discovery;
@@ -0,0 +1,615 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::infrastructure::config::{
+    NotificationReason, NotificationSinkConfig, NotificationSinkKind, NotificationsConfig,
+};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An attention event on its way out of grove.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Notification {
+    pub reason: NotificationReason,
+    pub task: String,
+    pub workspace: String,
+    pub workspace_path: PathBuf,
+    pub summary: String,
+}
+
+impl Notification {
+    pub fn title(&self) -> String {
+        format!("grove: {}", self.task)
+    }
+
+    pub fn body(&self) -> String {
+        format!("{}, {}", self.workspace, self.summary)
+    }
+}
+
+/// Local time window, in minutes since midnight, that may wrap past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start_minute: u16,
+    end_minute: u16,
+}
+
+impl QuietHours {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let invalid = || format!("quiet_hours expects HH:MM-HH:MM, got `{raw}`");
+        let (start, end) = raw.trim().split_once('-').ok_or_else(invalid)?;
+        let start_minute = parse_clock(start).ok_or_else(invalid)?;
+        let end_minute = parse_clock(end).ok_or_else(invalid)?;
+        Ok(Self {
+            start_minute,
+            end_minute,
+        })
+    }
+
+    pub fn contains(self, minute_of_day: u16) -> bool {
+        if self.start_minute <= self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute_of_day)
+        } else {
+            minute_of_day >= self.start_minute || minute_of_day < self.end_minute
+        }
+    }
+}
+
+fn parse_clock(raw: &str) -> Option<u16> {
+    let (hours, minutes) = raw.trim().split_once(':')?;
+    let hours = hours.parse::<u16>().ok().filter(|hours| *hours < 24)?;
+    let minutes = minutes
+        .parse::<u16>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+    Some(hours * 60 + minutes)
+}
+
+/// Decides which sinks receive an attention event, applying reason filters,
+/// quiet hours and per-worktree debouncing.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NotificationDispatcher {
+    config: NotificationsConfig,
+    quiet_hours: Option<QuietHours>,
+    last_sent_ms: HashMap<(PathBuf, NotificationReason), u64>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(config: NotificationsConfig) -> Result<Self, String> {
+        for sink in &config.sinks {
+            validate_sink(sink)?;
+        }
+        let quiet_hours = config
+            .quiet_hours
+            .as_deref()
+            .map(QuietHours::parse)
+            .transpose()?;
+        Ok(Self {
+            config,
+            quiet_hours,
+            last_sent_ms: HashMap::new(),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.config.sinks.is_empty()
+    }
+
+    /// Sinks that should receive `notification` now. `local_minute_of_day` is
+    /// only consulted when quiet hours are configured.
+    pub fn route(
+        &mut self,
+        notification: &Notification,
+        now_ms: u64,
+        local_minute_of_day: impl FnOnce() -> Option<u16>,
+    ) -> Vec<NotificationSinkConfig> {
+        if !self.is_enabled() || !reason_allowed(&self.config.reasons, notification.reason) {
+            return Vec::new();
+        }
+        if let Some(quiet_hours) = self.quiet_hours
+            && local_minute_of_day().is_some_and(|minute| quiet_hours.contains(minute))
+        {
+            return Vec::new();
+        }
+
+        let key = (notification.workspace_path.clone(), notification.reason);
+        let debounce_ms = self.config.debounce_secs.saturating_mul(1000);
+        if self
+            .last_sent_ms
+            .get(&key)
+            .is_some_and(|last_sent_ms| now_ms.saturating_sub(*last_sent_ms) < debounce_ms)
+        {
+            return Vec::new();
+        }
+
+        let sinks = self
+            .config
+            .sinks
+            .iter()
+            .filter(|sink| reason_allowed(&sink.reasons, notification.reason))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !sinks.is_empty() {
+            self.last_sent_ms.insert(key, now_ms);
+        }
+        sinks
+    }
+}
+
+fn reason_allowed(reasons: &[NotificationReason], reason: NotificationReason) -> bool {
+    reasons.is_empty() || reasons.contains(&reason)
+}
+
+fn validate_sink(sink: &NotificationSinkConfig) -> Result<(), String> {
+    match sink.kind {
+        NotificationSinkKind::Command => {
+            if sink
+                .command
+                .as_deref()
+                .is_none_or(|command| command.trim().is_empty())
+            {
+                return Err("command notification sink needs `command`".to_string());
+            }
+        }
+        NotificationSinkKind::Webhook => {
+            let Some(url) = sink.url.as_deref() else {
+                return Err("webhook notification sink needs `url`".to_string());
+            };
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("webhook url must be http(s), got `{url}`"));
+            }
+        }
+        NotificationSinkKind::Desktop | NotificationSinkKind::Terminal => {}
+    }
+    Ok(())
+}
+
+pub fn deliver(sink: &NotificationSinkConfig, notification: &Notification) -> Result<(), String> {
+    match sink.kind {
+        NotificationSinkKind::Command => {
+            deliver_command(sink.command.as_deref().unwrap_or_default(), notification)
+        }
+        NotificationSinkKind::Desktop => deliver_desktop(notification),
+        NotificationSinkKind::Terminal => deliver_terminal(notification),
+        NotificationSinkKind::Webhook => {
+            deliver_webhook(sink.url.as_deref().unwrap_or_default(), notification)
+        }
+    }
+}
+
+fn notification_json(notification: &Notification) -> Result<String, String> {
+    serde_json::to_string(notification)
+        .map_err(|error| format!("notification encode failed: {error}"))
+}
+
+fn deliver_command(command: &str, notification: &Notification) -> Result<(), String> {
+    let payload = notification_json(notification)?;
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("GROVE_NOTIFY_REASON", notification.reason.config_key())
+        .env("GROVE_NOTIFY_TASK", &notification.task)
+        .env("GROVE_NOTIFY_WORKSPACE", &notification.workspace)
+        .env("GROVE_NOTIFY_WORKSPACE_PATH", &notification.workspace_path)
+        .env("GROVE_NOTIFY_SUMMARY", &notification.summary)
+        .env("GROVE_NOTIFY_TITLE", notification.title())
+        .env("GROVE_NOTIFY_BODY", notification.body())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("notification command spawn failed: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+
+    let started_at = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("notification command exited with {status}")),
+            Ok(None) if started_at.elapsed() >= DELIVERY_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("notification command timed out".to_string());
+            }
+            Ok(None) => thread::sleep(COMMAND_POLL_INTERVAL),
+            Err(error) => return Err(format!("notification command wait failed: {error}")),
+        }
+    }
+}
+
+fn deliver_desktop(notification: &Notification) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(notification.body().as_str()),
+            applescript_string(notification.title().as_str())
+        );
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(script);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command
+            .arg("--app-name=grove")
+            .arg(notification.title())
+            .arg(notification.body());
+        command
+    };
+    let output = command
+        .output()
+        .map_err(|error| format!("desktop notification failed: {error}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(format!(
+        "desktop notification failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// OSC 777 notification, wrapped for tmux passthrough when grove itself runs
+/// inside tmux.
+fn terminal_notification_sequence(notification: &Notification, inside_tmux: bool) -> String {
+    let clean = |value: String| {
+        value
+            .chars()
+            .filter(|character| !character.is_control() && *character != ';')
+            .collect::<String>()
+    };
+    let sequence = format!(
+        "\x1b]777;notify;{};{}\x07",
+        clean(notification.title()),
+        clean(notification.body())
+    );
+    if inside_tmux {
+        return format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"));
+    }
+    sequence
+}
+
+fn deliver_terminal(notification: &Notification) -> Result<(), String> {
+    let sequence = terminal_notification_sequence(notification, std::env::var_os("TMUX").is_some());
+    let mut tty = OpenOptions::new()
+        .write(true)
+        .open("/dev/tty")
+        .map_err(|error| format!("terminal notification failed: {error}"))?;
+    tty.write_all(sequence.as_bytes())
+        .and_then(|()| tty.flush())
+        .map_err(|error| format!("terminal notification failed: {error}"))
+}
+
+fn deliver_webhook(url: &str, notification: &Notification) -> Result<(), String> {
+    let payload = notification_json(notification)?;
+    let mut child = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--max-time",
+            DELIVERY_TIMEOUT.as_secs().to_string().as_str(),
+            "--header",
+            "Content-Type: application/json",
+            "--data-binary",
+            "@-",
+            url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("webhook curl spawn failed: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("webhook curl failed: {error}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(format!(
+        "webhook failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+/// Minutes since local midnight, read from `date` so the system time zone
+/// rules apply.
+pub fn local_minute_of_day() -> Option<u16> {
+    let output = Command::new("date").arg("+%H:%M").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_clock(String::from_utf8_lossy(&output.stdout).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Notification, NotificationDispatcher, QuietHours, deliver, local_minute_of_day,
+        terminal_notification_sequence,
+    };
+    use crate::infrastructure::config::{
+        NotificationReason, NotificationSinkConfig, NotificationSinkKind, NotificationsConfig,
+    };
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn notification(reason: NotificationReason) -> Notification {
+        Notification {
+            reason,
+            task: "flaky-tests".to_string(),
+            workspace: "grove".to_string(),
+            workspace_path: PathBuf::from("/tasks/flaky-tests/grove"),
+            summary: reason.config_key().to_string(),
+        }
+    }
+
+    fn sink(
+        kind: NotificationSinkKind,
+        reasons: Vec<NotificationReason>,
+    ) -> NotificationSinkConfig {
+        NotificationSinkConfig {
+            kind,
+            command: Some("true".to_string()),
+            url: Some("http://127.0.0.1:9/".to_string()),
+            reasons,
+        }
+    }
+
+    fn dispatcher(config: NotificationsConfig) -> NotificationDispatcher {
+        NotificationDispatcher::new(config).expect("config should be valid")
+    }
+
+    #[test]
+    fn quiet_hours_parse_and_wrap_past_midnight() {
+        let overnight = QuietHours::parse("22:00-07:30").expect("window should parse");
+        assert!(overnight.contains(23 * 60));
+        assert!(overnight.contains(7 * 60 + 29));
+        assert!(!overnight.contains(7 * 60 + 30));
+        assert!(!overnight.contains(12 * 60));
+
+        let lunch = QuietHours::parse("12:00-13:00").expect("window should parse");
+        assert!(lunch.contains(12 * 60 + 30));
+        assert!(!lunch.contains(13 * 60));
+
+        assert!(QuietHours::parse("25:00-07:00").is_err());
+        assert!(QuietHours::parse("22:00").is_err());
+    }
+
+    #[test]
+    fn dispatcher_rejects_sinks_missing_their_target() {
+        let mut config = NotificationsConfig::default();
+        config.sinks.push(NotificationSinkConfig {
+            kind: NotificationSinkKind::Webhook,
+            command: None,
+            url: None,
+            reasons: Vec::new(),
+        });
+        assert!(NotificationDispatcher::new(config).is_err());
+    }
+
+    #[test]
+    fn route_applies_global_and_per_sink_reason_filters() {
+        let mut dispatcher = dispatcher(NotificationsConfig {
+            sinks: vec![
+                sink(NotificationSinkKind::Desktop, Vec::new()),
+                sink(
+                    NotificationSinkKind::Webhook,
+                    vec![NotificationReason::BlockedOnQuestion],
+                ),
+            ],
+            reasons: vec![
+                NotificationReason::BlockedOnQuestion,
+                NotificationReason::Finished,
+            ],
+            ..NotificationsConfig::default()
+        });
+
+        let kinds = |sinks: Vec<NotificationSinkConfig>| {
+            sinks.into_iter().map(|sink| sink.kind).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds(dispatcher.route(
+                &notification(NotificationReason::BlockedOnQuestion),
+                0,
+                || None
+            )),
+            vec![NotificationSinkKind::Desktop, NotificationSinkKind::Webhook]
+        );
+        assert_eq!(
+            kinds(dispatcher.route(&notification(NotificationReason::Finished), 0, || None)),
+            vec![NotificationSinkKind::Desktop]
+        );
+        assert!(
+            dispatcher
+                .route(&notification(NotificationReason::Stalled), 0, || None)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn route_debounces_per_worktree_and_reason() {
+        let mut dispatcher = dispatcher(NotificationsConfig {
+            sinks: vec![sink(NotificationSinkKind::Desktop, Vec::new())],
+            debounce_secs: 60,
+            ..NotificationsConfig::default()
+        });
+        let finished = notification(NotificationReason::Finished);
+
+        assert_eq!(dispatcher.route(&finished, 1_000, || None).len(), 1);
+        assert!(dispatcher.route(&finished, 30_000, || None).is_empty());
+        assert_eq!(
+            dispatcher
+                .route(&notification(NotificationReason::Stalled), 30_000, || None)
+                .len(),
+            1
+        );
+        assert_eq!(dispatcher.route(&finished, 61_000, || None).len(), 1);
+    }
+
+    #[test]
+    fn route_stays_silent_during_quiet_hours() {
+        let mut dispatcher = dispatcher(NotificationsConfig {
+            sinks: vec![sink(NotificationSinkKind::Desktop, Vec::new())],
+            quiet_hours: Some("22:00-07:00".to_string()),
+            ..NotificationsConfig::default()
+        });
+        let finished = notification(NotificationReason::Finished);
+
+        assert!(dispatcher.route(&finished, 0, || Some(23 * 60)).is_empty());
+        assert_eq!(dispatcher.route(&finished, 0, || Some(9 * 60)).len(), 1);
+    }
+
+    #[test]
+    fn terminal_sequence_strips_separators_and_wraps_for_tmux() {
+        let mut event = notification(NotificationReason::Finished);
+        event.summary = "done; review\nplease".to_string();
+
+        assert_eq!(
+            terminal_notification_sequence(&event, false),
+            "\x1b]777;notify;grove: flaky-tests;grove, done reviewplease\x07"
+        );
+        assert_eq!(
+            terminal_notification_sequence(&event, true),
+            "\x1bPtmux;\x1b\x1b]777;notify;grove: flaky-tests;grove, done reviewplease\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn command_sink_receives_notification_env_and_json() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        let output_path = std::env::temp_dir().join(format!(
+            "grove-notify-command-{}-{nanos}.txt",
+            std::process::id()
+        ));
+        let command_sink = NotificationSinkConfig {
+            kind: NotificationSinkKind::Command,
+            command: Some(format!(
+                "{{ echo \"$GROVE_NOTIFY_REASON $GROVE_NOTIFY_TASK\"; cat; }} > '{}'",
+                output_path.display()
+            )),
+            url: None,
+            reasons: Vec::new(),
+        };
+
+        deliver(
+            &command_sink,
+            &notification(NotificationReason::PermissionWall),
+        )
+        .expect("command sink should succeed");
+
+        let output = fs::read_to_string(&output_path).expect("command output should exist");
+        let _ = fs::remove_file(&output_path);
+        let (first_line, payload) = output.split_once('\n').expect("output has two parts");
+        assert_eq!(first_line, "permission-wall flaky-tests");
+        let payload: serde_json::Value =
+            serde_json::from_str(payload).expect("payload should be json");
+        assert_eq!(payload["reason"], "permission-wall");
+        assert_eq!(payload["workspace_path"], "/tasks/flaky-tests/grove");
+    }
+
+    #[test]
+    fn local_minute_of_day_reads_the_local_clock() {
+        let minute = local_minute_of_day().expect("local time should resolve");
+        assert!(minute < 24 * 60);
+    }
+
+    #[test]
+    fn webhook_sink_posts_json_to_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let port = listener
+            .local_addr()
+            .expect("listener should have an address")
+            .port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("webhook should connect");
+            let mut request = Vec::new();
+            let mut buffer = [0_u8; 1024];
+            loop {
+                let read = stream.read(&mut buffer).expect("request should read");
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if read == 0 || text.ends_with('}') {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .expect("response should write");
+            String::from_utf8(request).expect("request should be utf8")
+        });
+
+        deliver(
+            &NotificationSinkConfig {
+                kind: NotificationSinkKind::Webhook,
+                command: None,
+                url: Some(format!("http://127.0.0.1:{port}/hooks/grove")),
+                reasons: Vec::new(),
+            },
+            &notification(NotificationReason::BlockedOnQuestion),
+        )
+        .expect("webhook should succeed");
+
+        let request = server.join().expect("server thread should finish");
+        assert!(request.starts_with("POST /hooks/grove HTTP/1.1\r\n"));
+        assert!(request.contains("Content-Type: application/json\r\n"));
+        let (_, body) = request
+            .split_once("\r\n\r\n")
+            .expect("request should have a body");
+        let body: serde_json::Value = serde_json::from_str(body).expect("body should be json");
+        assert_eq!(body["reason"], "blocked-on-question");
+        assert_eq!(body["task"], "flaky-tests");
+    }
+
+    #[test]
+    fn webhook_sink_reports_http_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let port = listener
+            .local_addr()
+            .expect("listener should have an address")
+            .port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("webhook should connect");
+            let mut buffer = [0_u8; 1024];
+            let _ = stream.read(&mut buffer);
+            let _ = stream.write_all(b"HTTP/1.1 500 Internal Server Error\r\n\r\n");
+        });
+
+        let result = deliver(
+            &NotificationSinkConfig {
+                kind: NotificationSinkKind::Webhook,
+                command: None,
+                url: Some(format!("http://127.0.0.1:{port}")),
+                reasons: Vec::new(),
+            },
+            &notification(NotificationReason::Stalled),
+        );
+        server.join().expect("server thread should finish");
+
+        let error = result.expect_err("a 500 response should fail the delivery");
+        assert!(error.starts_with("webhook failed: "), "{error}");
+        assert!(error.contains("500"), "{error}");
+    }
+}
@@ -0,0 +1,183 @@
+//! Stable per-worktree port ranges, so parallel dev servers do not collide.
+//!
+//! A worktree's range starts at a slot picked from a hash of its path, so the
+//! same worktree gets the same ports back after a restore. Ranges live in the
+//! task manifests, deleting a task or worktree frees its ports.
+
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+
+use crate::domain::{AgentRegistry, PortRange};
+use crate::infrastructure::task_manifest::decode_task_manifest;
+
+const TASK_MANIFEST_FILE: &str = ".grove/task.toml";
+const PORT_POOL_START: u16 = 20_000;
+const PORT_POOL_END: u16 = 29_999;
+
+/// Port ranges recorded in every task manifest under `tasks_root`.
+pub fn allocated_port_ranges(tasks_root: &Path, agents: &AgentRegistry) -> Vec<PortRange> {
+    let Ok(entries) = fs::read_dir(tasks_root) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path().join(TASK_MANIFEST_FILE)).ok())
+        .filter_map(|raw| decode_task_manifest(raw.as_str(), agents).ok())
+        .flat_map(|task| task.worktrees)
+        .filter_map(|worktree| worktree.ports)
+        .collect()
+}
+
+/// Picks `count` ports for `worktree_path` that overlap no `taken` range and
+/// that `port_is_free` accepts.
+pub fn allocate_port_range(
+    worktree_path: &Path,
+    count: u16,
+    taken: &[PortRange],
+    port_is_free: impl Fn(u16) -> bool,
+) -> Result<PortRange, String> {
+    let pool_size = PORT_POOL_END - PORT_POOL_START + 1;
+    if count == 0 || count > pool_size {
+        return Err(format!(
+            "port count must be between 1 and {pool_size}, got {count}"
+        ));
+    }
+    let slots = u64::from(pool_size / count);
+    let start_slot = fnv1a(worktree_path.to_string_lossy().as_bytes()) % slots;
+    (0..slots)
+        .map(|offset| (start_slot + offset) % slots)
+        .filter_map(|slot| u16::try_from(slot).ok())
+        .map(|slot| PortRange {
+            base: PORT_POOL_START + slot * count,
+            count,
+        })
+        .find(|range| {
+            !taken.iter().any(|other| range.overlaps(*other))
+                && (range.base..=range.last()).all(&port_is_free)
+        })
+        .ok_or_else(|| {
+            format!("no free range of {count} ports between {PORT_POOL_START} and {PORT_POOL_END}")
+        })
+}
+
+/// Whether nothing listens on `port` on the loopback interface.
+pub fn port_is_bindable(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Environment exported to a worktree's sessions and setup script.
+pub fn port_env_vars(ports: PortRange) -> Vec<(String, String)> {
+    vec![
+        ("PORT".to_string(), ports.base.to_string()),
+        ("GROVE_PORT_BASE".to_string(), ports.base.to_string()),
+        ("GROVE_PORT_COUNT".to_string(), ports.count.to_string()),
+    ]
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use crate::domain::{AgentRegistry, AgentType, PortRange, Task, WorkspaceStatus, Worktree};
+    use crate::infrastructure::task_manifest::encode_task_manifest;
+    use crate::test_support::unique_test_dir;
+
+    use super::{allocate_port_range, allocated_port_ranges, port_env_vars};
+
+    fn write_manifest(tasks_root: &Path, slug: &str, ports: PortRange) {
+        let task_root = tasks_root.join(slug);
+        let worktree = Worktree::try_new(
+            "app".to_string(),
+            PathBuf::from("/repos/app"),
+            task_root.join("app"),
+            slug.to_string(),
+            AgentType::Codex,
+            WorkspaceStatus::Idle,
+        )
+        .expect("worktree should be valid")
+        .with_ports(Some(ports));
+        let task = Task::try_new(
+            slug.to_string(),
+            slug.to_string(),
+            task_root.clone(),
+            slug.to_string(),
+            vec![worktree],
+        )
+        .expect("task should be valid");
+        fs::create_dir_all(task_root.join(".grove")).expect("manifest dir should exist");
+        fs::write(
+            task_root.join(".grove/task.toml"),
+            encode_task_manifest(&task).expect("manifest should encode"),
+        )
+        .expect("manifest should write");
+    }
+
+    #[test]
+    fn allocation_is_stable_and_skips_taken_or_busy_ports() {
+        let path = Path::new("/tmp/.grove/tasks/launch/app");
+        let first = allocate_port_range(path, 10, &[], |_| true).expect("range should allocate");
+        assert_eq!(first.count, 10);
+        assert_eq!(
+            allocate_port_range(path, 10, &[], |_| true).expect("range should allocate"),
+            first
+        );
+
+        let overlapping = PortRange {
+            base: first.base + 5,
+            count: 1,
+        };
+        let moved =
+            allocate_port_range(path, 10, &[overlapping], |_| true).expect("range should move");
+        assert!(!moved.overlaps(overlapping));
+
+        let busy = first.last();
+        let moved =
+            allocate_port_range(path, 10, &[], |port| port != busy).expect("range should move");
+        assert!(!(moved.base..=moved.last()).contains(&busy));
+
+        assert!(allocate_port_range(path, 0, &[], |_| true).is_err());
+        assert!(allocate_port_range(path, 10, &[], |_| false).is_err());
+    }
+
+    #[test]
+    fn manifests_hold_allocations_until_deleted() {
+        let tasks_root = unique_test_dir("ports");
+        let ports = PortRange {
+            base: 21_000,
+            count: 5,
+        };
+        write_manifest(&tasks_root, "launch", ports);
+
+        assert_eq!(
+            allocated_port_ranges(&tasks_root, &AgentRegistry::default()),
+            vec![ports]
+        );
+        fs::remove_dir_all(tasks_root.join("launch")).expect("task should delete");
+        assert!(allocated_port_ranges(&tasks_root, &AgentRegistry::default()).is_empty());
+
+        let _ = fs::remove_dir_all(tasks_root);
+    }
+
+    #[test]
+    fn port_env_exports_base_and_count() {
+        assert_eq!(
+            port_env_vars(PortRange {
+                base: 20_010,
+                count: 10,
+            }),
+            vec![
+                ("PORT".to_string(), "20010".to_string()),
+                ("GROVE_PORT_BASE".to_string(), "20010".to_string()),
+                ("GROVE_PORT_COUNT".to_string(), "10".to_string()),
+            ]
+        );
+    }
+}
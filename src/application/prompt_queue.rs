@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::agent_runtime::status::waiting_is_permission_wall;
+use crate::domain::WorkspaceStatus;
+use crate::infrastructure::config::NotificationReason;
+
+const PROMPT_QUEUE_FILE: &str = ".grove/prompt-queue.json";
+
+/// Follow-up prompts waiting to be sent to a worktree's agent, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PromptQueue {
+    #[serde(default)]
+    pub prompts: Vec<String>,
+    /// Why sending stopped; nothing is sent until the queue is resumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<String>,
+}
+
+/// What a status observation means for the queue of that worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptQueueTrigger {
+    /// The agent is back at its input prompt.
+    Send,
+    Pause(String),
+    Hold,
+}
+
+pub fn prompt_queue_path(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(PROMPT_QUEUE_FILE)
+}
+
+pub fn load_prompt_queue(workspace_path: &Path) -> Result<PromptQueue, String> {
+    let path = prompt_queue_path(workspace_path);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(PromptQueue::default());
+        }
+        Err(error) => return Err(format!("read {} failed: {error}", path.display())),
+    };
+    serde_json::from_str(raw.as_str())
+        .map_err(|error| format!("parse {} failed: {error}", path.display()))
+}
+
+/// Writes the queue, removing the file once it is empty and not paused.
+pub fn save_prompt_queue(workspace_path: &Path, queue: &PromptQueue) -> Result<(), String> {
+    let path = prompt_queue_path(workspace_path);
+    if queue.prompts.is_empty() && queue.paused.is_none() {
+        return match fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("remove {} failed: {error}", path.display()))
+            }
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("create {} failed: {error}", parent.display()))?;
+    }
+    let encoded = serde_json::to_string_pretty(queue).map_err(|error| error.to_string())?;
+    fs::write(&path, encoded).map_err(|error| format!("write {} failed: {error}", path.display()))
+}
+
+pub fn enqueue_prompt(workspace_path: &Path, prompt: &str) -> Result<PromptQueue, String> {
+    let prompt = prompt.trim_end_matches(['\n', '\r']);
+    if prompt.trim().is_empty() {
+        return Err("queued prompt is empty".to_string());
+    }
+    let mut queue = load_prompt_queue(workspace_path)?;
+    queue.prompts.push(prompt.to_string());
+    save_prompt_queue(workspace_path, &queue)?;
+    Ok(queue)
+}
+
+/// Permission walls and errors pause the queue, a plain input prompt sends the next entry.
+pub fn prompt_queue_trigger(
+    status: WorkspaceStatus,
+    waiting_prompt: Option<&str>,
+    waiting_attention: Option<NotificationReason>,
+) -> PromptQueueTrigger {
+    match status {
+        WorkspaceStatus::Waiting
+            if waiting_is_permission_wall(waiting_prompt, waiting_attention) =>
+        {
+            PromptQueueTrigger::Pause(match waiting_prompt {
+                Some(prompt) => format!("permission wall: {prompt}"),
+                None => "permission wall".to_string(),
+            })
+        }
+        WorkspaceStatus::Waiting => PromptQueueTrigger::Send,
+        WorkspaceStatus::Error => {
+            PromptQueueTrigger::Pause("agent exited with an error".to_string())
+        }
+        _ => PromptQueueTrigger::Hold,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{
+        PromptQueue, PromptQueueTrigger, enqueue_prompt, load_prompt_queue, prompt_queue_path,
+        prompt_queue_trigger, save_prompt_queue,
+    };
+    use crate::domain::WorkspaceStatus;
+    use crate::infrastructure::config::NotificationReason;
+    use crate::test_support::unique_test_dir;
+
+    #[test]
+    fn enqueue_appends_and_an_empty_queue_removes_the_file() {
+        let worktree = unique_test_dir("grove-prompt-queue-enqueue");
+
+        assert_eq!(
+            load_prompt_queue(worktree.as_path()),
+            Ok(PromptQueue::default())
+        );
+        enqueue_prompt(worktree.as_path(), "run the tests\n").expect("first prompt should queue");
+        let queue = enqueue_prompt(worktree.as_path(), "then fix lint")
+            .expect("second prompt should queue");
+        assert_eq!(queue.prompts, vec!["run the tests", "then fix lint"]);
+        assert_eq!(load_prompt_queue(worktree.as_path()), Ok(queue));
+        assert!(enqueue_prompt(worktree.as_path(), "  \n").is_err());
+
+        save_prompt_queue(worktree.as_path(), &PromptQueue::default())
+            .expect("empty queue should save");
+        assert!(!prompt_queue_path(worktree.as_path()).exists());
+        let _ = fs::remove_dir_all(worktree);
+    }
+
+    #[test]
+    fn trigger_sends_at_input_prompt_and_pauses_on_walls_and_errors() {
+        assert_eq!(
+            prompt_queue_trigger(WorkspaceStatus::Waiting, Some("? for shortcuts"), None),
+            PromptQueueTrigger::Send
+        );
+        assert_eq!(
+            prompt_queue_trigger(WorkspaceStatus::Waiting, None, None),
+            PromptQueueTrigger::Send
+        );
+        assert_eq!(
+            prompt_queue_trigger(
+                WorkspaceStatus::Waiting,
+                Some("Do you want to proceed?"),
+                None
+            ),
+            PromptQueueTrigger::Pause("permission wall: Do you want to proceed?".to_string())
+        );
+        assert_eq!(
+            prompt_queue_trigger(
+                WorkspaceStatus::Waiting,
+                Some("Do you want to proceed?"),
+                Some(NotificationReason::BlockedOnQuestion)
+            ),
+            PromptQueueTrigger::Send
+        );
+        assert_eq!(
+            prompt_queue_trigger(
+                WorkspaceStatus::Waiting,
+                Some("(Y)es/(N)o"),
+                Some(NotificationReason::PermissionWall)
+            ),
+            PromptQueueTrigger::Pause("permission wall: (Y)es/(N)o".to_string())
+        );
+        assert!(matches!(
+            prompt_queue_trigger(WorkspaceStatus::Error, None, None),
+            PromptQueueTrigger::Pause(_)
+        ));
+        assert_eq!(
+            prompt_queue_trigger(WorkspaceStatus::Thinking, None, None),
+            PromptQueueTrigger::Hold
+        );
+    }
+}
@@ -0,0 +1,563 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::domain::{
+    AgentRegistry, PullRequest, PullRequestChecks, PullRequestReview, PullRequestStatus, Task,
+};
+use crate::infrastructure::config::PullRequestForgeKind;
+use crate::infrastructure::process::stderr_trimmed;
+use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
+
+const TASK_MANIFEST_FILE: &str = ".grove/task.toml";
+const GH_PULL_REQUEST_FIELDS: &str = "number,url,state,reviewDecision,statusCheckRollup";
+
+/// Looks up the pull requests opened from a branch of a repository.
+pub trait PullRequestForge: Send + Sync {
+    fn pull_requests_for_branch(
+        &self,
+        repository_path: &Path,
+        branch: &str,
+    ) -> Result<Vec<PullRequest>, String>;
+}
+
+/// GitHub, queried through the `gh` CLI from inside the repository.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GitHubCliForge;
+
+impl PullRequestForge for GitHubCliForge {
+    fn pull_requests_for_branch(
+        &self,
+        repository_path: &Path,
+        branch: &str,
+    ) -> Result<Vec<PullRequest>, String> {
+        let output = Command::new("gh")
+            .current_dir(repository_path)
+            .args([
+                "pr",
+                "list",
+                "--head",
+                branch,
+                "--state",
+                "all",
+                "--json",
+                GH_PULL_REQUEST_FIELDS,
+            ])
+            .output()
+            .map_err(|error| format!("gh pr list failed: {error}"))?;
+        if !output.status.success() {
+            return Err(stderr_trimmed(&output));
+        }
+
+        parse_gh_pull_requests(&output.stdout)
+    }
+}
+
+/// Canned pull requests keyed by branch, for tests.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FakePullRequestForge {
+    pub(crate) pull_requests: std::collections::HashMap<String, Vec<PullRequest>>,
+    pub(crate) error: Option<String>,
+}
+
+#[cfg(test)]
+impl PullRequestForge for FakePullRequestForge {
+    fn pull_requests_for_branch(
+        &self,
+        _repository_path: &Path,
+        branch: &str,
+    ) -> Result<Vec<PullRequest>, String> {
+        if let Some(error) = self.error.as_ref() {
+            return Err(error.clone());
+        }
+        Ok(self.pull_requests.get(branch).cloned().unwrap_or_default())
+    }
+}
+
+pub fn forge_for_kind(kind: PullRequestForgeKind) -> Option<Arc<dyn PullRequestForge>> {
+    match kind {
+        PullRequestForgeKind::Github => Some(Arc::new(GitHubCliForge)),
+        PullRequestForgeKind::None => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestEventKind {
+    Merged,
+    ChecksFailed,
+}
+
+/// A transition worth surfacing, observed between two syncs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestEvent {
+    pub worktree_path: PathBuf,
+    pub kind: PullRequestEventKind,
+    pub pull_request: PullRequest,
+}
+
+/// New pull request list for a worktree whose state changed on the forge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestSyncUpdate {
+    pub task_root: PathBuf,
+    pub worktree_path: PathBuf,
+    pub pull_requests: Vec<PullRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PullRequestSyncOutcome {
+    pub checked: usize,
+    pub updates: Vec<PullRequestSyncUpdate>,
+    pub events: Vec<PullRequestEvent>,
+    pub errors: Vec<String>,
+}
+
+/// Queries the forge for every non-base worktree and diffs the result against
+/// the pull requests grove already knows about.
+pub fn sync_task_pull_requests(
+    tasks: &[Task],
+    forge: &dyn PullRequestForge,
+) -> PullRequestSyncOutcome {
+    let mut outcome = PullRequestSyncOutcome::default();
+    for task in tasks {
+        for worktree in &task.worktrees {
+            if worktree.is_main_checkout() {
+                continue;
+            }
+            outcome.checked = outcome.checked.saturating_add(1);
+            let found = match forge
+                .pull_requests_for_branch(&worktree.repository_path, worktree.branch.as_str())
+            {
+                Ok(found) => found,
+                Err(error) => {
+                    outcome
+                        .errors
+                        .push(format!("{}: {error}", worktree.path.display()));
+                    continue;
+                }
+            };
+
+            outcome.events.extend(
+                pull_request_events(&worktree.pull_requests, &found)
+                    .into_iter()
+                    .map(|(kind, pull_request)| PullRequestEvent {
+                        worktree_path: worktree.path.clone(),
+                        kind,
+                        pull_request,
+                    }),
+            );
+            let pull_requests = merge_pull_requests(&worktree.pull_requests, found);
+            if pull_requests != worktree.pull_requests {
+                outcome.updates.push(PullRequestSyncUpdate {
+                    task_root: task.root_path.clone(),
+                    worktree_path: worktree.path.clone(),
+                    pull_requests,
+                });
+            }
+        }
+    }
+    outcome
+}
+
+fn pull_request_events(
+    known: &[PullRequest],
+    found: &[PullRequest],
+) -> Vec<(PullRequestEventKind, PullRequest)> {
+    let mut events = Vec::new();
+    for pull_request in found {
+        let previous = known
+            .iter()
+            .find(|previous| previous.number == pull_request.number);
+        let kind = match pull_request.status {
+            PullRequestStatus::Merged => previous
+                .is_some_and(|previous| previous.status != PullRequestStatus::Merged)
+                .then_some(PullRequestEventKind::Merged),
+            PullRequestStatus::Open => {
+                let failing = pull_request.checks == Some(PullRequestChecks::Failing);
+                let was_failing = previous
+                    .is_some_and(|previous| previous.checks == Some(PullRequestChecks::Failing));
+                (failing && !was_failing).then_some(PullRequestEventKind::ChecksFailed)
+            }
+            PullRequestStatus::Closed => None,
+        };
+        if let Some(kind) = kind {
+            events.push((kind, pull_request.clone()));
+        }
+    }
+    events
+}
+
+/// Known pull requests are kept in place and refreshed, newly found ones are
+/// appended oldest first. Pull requests the forge no longer lists are kept.
+fn merge_pull_requests(known: &[PullRequest], mut found: Vec<PullRequest>) -> Vec<PullRequest> {
+    let mut merged = known.to_vec();
+    found.sort_by_key(|pull_request| pull_request.number);
+    for pull_request in found {
+        match merged
+            .iter_mut()
+            .find(|existing| existing.number == pull_request.number)
+        {
+            Some(existing) => *existing = pull_request,
+            None => merged.push(pull_request),
+        }
+    }
+    merged
+}
+
+/// Replaces the pull requests of updated worktrees, returning how many changed.
+pub fn apply_pull_request_updates(tasks: &mut [Task], updates: &[PullRequestSyncUpdate]) -> usize {
+    let mut applied = 0usize;
+    for update in updates {
+        let Some(worktree) = tasks
+            .iter_mut()
+            .filter(|task| task.root_path == update.task_root)
+            .flat_map(|task| task.worktrees.iter_mut())
+            .find(|worktree| worktree.path == update.worktree_path)
+        else {
+            continue;
+        };
+        worktree.pull_requests = update.pull_requests.clone();
+        applied = applied.saturating_add(1);
+    }
+    applied
+}
+
+/// Writes synced pull requests back into each affected task manifest,
+/// returning one message per manifest that could not be updated.
+pub fn persist_pull_request_updates(
+    updates: &[PullRequestSyncUpdate],
+    agents: &AgentRegistry,
+) -> Vec<String> {
+    let mut by_task_root = BTreeMap::<&Path, Vec<&PullRequestSyncUpdate>>::new();
+    for update in updates {
+        by_task_root
+            .entry(update.task_root.as_path())
+            .or_default()
+            .push(update);
+    }
+
+    by_task_root
+        .into_iter()
+        .filter_map(|(task_root, updates)| {
+            persist_task_pull_requests(task_root, &updates, agents)
+                .err()
+                .map(|error| format!("{}: {error}", task_root.display()))
+        })
+        .collect()
+}
+
+fn persist_task_pull_requests(
+    task_root: &Path,
+    updates: &[&PullRequestSyncUpdate],
+    agents: &AgentRegistry,
+) -> Result<(), String> {
+    let manifest_path = task_root.join(TASK_MANIFEST_FILE);
+    let raw = std::fs::read_to_string(&manifest_path)
+        .map_err(|error| format!("read manifest: {error}"))?;
+    let mut task =
+        decode_task_manifest(&raw, agents).map_err(|error| format!("decode manifest: {error}"))?;
+    for update in updates {
+        if let Some(worktree) = task
+            .worktrees
+            .iter_mut()
+            .find(|worktree| worktree.path == update.worktree_path)
+        {
+            worktree.pull_requests = update.pull_requests.clone();
+        }
+    }
+
+    let encoded =
+        encode_task_manifest(&task).map_err(|error| format!("encode manifest: {error}"))?;
+    std::fs::write(&manifest_path, encoded).map_err(|error| format!("write manifest: {error}"))
+}
+
+fn parse_gh_pull_requests(stdout: &[u8]) -> Result<Vec<PullRequest>, String> {
+    let payload: Value =
+        serde_json::from_slice(stdout).map_err(|error| format!("invalid gh response: {error}"))?;
+    let Some(entries) = payload.as_array() else {
+        return Err("gh pr list did not return a list".to_string());
+    };
+
+    entries.iter().map(parse_gh_pull_request).collect()
+}
+
+fn parse_gh_pull_request(entry: &Value) -> Result<PullRequest, String> {
+    let number = entry
+        .get("number")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "pull request number is missing".to_string())?;
+    let url = entry
+        .get("url")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("pull request #{number} url is missing"))?;
+    let state = entry.get("state").and_then(Value::as_str).unwrap_or("");
+    let status = match state {
+        "OPEN" => PullRequestStatus::Open,
+        "MERGED" => PullRequestStatus::Merged,
+        "CLOSED" => PullRequestStatus::Closed,
+        _ => {
+            return Err(format!(
+                "pull request #{number} has unknown state '{state}'"
+            ));
+        }
+    };
+    let review = match entry.get("reviewDecision").and_then(Value::as_str) {
+        Some("APPROVED") => Some(PullRequestReview::Approved),
+        Some("CHANGES_REQUESTED") => Some(PullRequestReview::ChangesRequested),
+        Some("REVIEW_REQUIRED") => Some(PullRequestReview::ReviewRequired),
+        _ => None,
+    };
+    let checks = entry
+        .get("statusCheckRollup")
+        .and_then(Value::as_array)
+        .and_then(|checks| rollup_checks(checks));
+
+    Ok(PullRequest {
+        number,
+        url: url.to_string(),
+        status,
+        review,
+        checks,
+    })
+}
+
+/// Folds check runs and commit statuses into one state: any failure wins,
+/// then anything still running, otherwise passing.
+fn rollup_checks(checks: &[Value]) -> Option<PullRequestChecks> {
+    if checks.is_empty() {
+        return None;
+    }
+
+    let mut pending = false;
+    for check in checks {
+        let field = |name: &str| check.get(name).and_then(Value::as_str).unwrap_or("");
+        let conclusion = field("conclusion");
+        let state = field("state");
+        if matches!(
+            conclusion,
+            "FAILURE" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" | "STARTUP_FAILURE"
+        ) || matches!(state, "FAILURE" | "ERROR")
+        {
+            return Some(PullRequestChecks::Failing);
+        }
+        let status = field("status");
+        if (!status.is_empty() && status != "COMPLETED") || matches!(state, "PENDING" | "EXPECTED")
+        {
+            pending = true;
+        }
+    }
+
+    Some(if pending {
+        PullRequestChecks::Pending
+    } else {
+        PullRequestChecks::Passing
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FakePullRequestForge, PullRequestEventKind, apply_pull_request_updates,
+        parse_gh_pull_requests, persist_pull_request_updates, sync_task_pull_requests,
+    };
+    use crate::domain::{
+        AgentRegistry, AgentType, PullRequest, PullRequestChecks, PullRequestReview,
+        PullRequestStatus, Task, WorkspaceStatus, Worktree,
+    };
+    use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
+    use crate::test_support::unique_test_dir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn pull_request(number: u64, status: PullRequestStatus) -> PullRequest {
+        PullRequest {
+            number,
+            url: format!("https://github.com/acme/grove/pull/{number}"),
+            status,
+            review: None,
+            checks: None,
+        }
+    }
+
+    fn fixture_task(root: PathBuf, known: Vec<PullRequest>) -> Task {
+        let base = Worktree::try_new(
+            "grove".to_string(),
+            PathBuf::from("/repos/grove"),
+            PathBuf::from("/repos/grove"),
+            "main".to_string(),
+            AgentType::Claude,
+            WorkspaceStatus::Main,
+        )
+        .expect("worktree should be valid");
+        let feature = Worktree::try_new(
+            "grove".to_string(),
+            PathBuf::from("/repos/grove"),
+            root.join("grove"),
+            "feature".to_string(),
+            AgentType::Claude,
+            WorkspaceStatus::Idle,
+        )
+        .expect("worktree should be valid")
+        .with_pull_requests(known);
+        Task::try_new(
+            "feature".to_string(),
+            "feature".to_string(),
+            root,
+            "feature".to_string(),
+            vec![base, feature],
+        )
+        .expect("task should be valid")
+    }
+
+    #[test]
+    fn parse_gh_pull_requests_reads_state_review_and_checks() {
+        let stdout = br#"[
+            {"number": 7, "url": "https://github.com/acme/grove/pull/7", "state": "OPEN",
+             "reviewDecision": "CHANGES_REQUESTED",
+             "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"__typename": "StatusContext", "state": "FAILURE"}
+             ]},
+            {"number": 5, "url": "https://github.com/acme/grove/pull/5", "state": "MERGED",
+             "reviewDecision": "", "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "IN_PROGRESS", "conclusion": ""}
+             ]},
+            {"number": 3, "url": "https://github.com/acme/grove/pull/3", "state": "CLOSED",
+             "reviewDecision": "APPROVED", "statusCheckRollup": []}
+        ]"#;
+
+        let parsed = parse_gh_pull_requests(stdout).expect("gh output should parse");
+
+        assert_eq!(
+            parsed
+                .iter()
+                .map(|pull_request| (
+                    pull_request.number,
+                    pull_request.status,
+                    pull_request.review,
+                    pull_request.checks
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    7,
+                    PullRequestStatus::Open,
+                    Some(PullRequestReview::ChangesRequested),
+                    Some(PullRequestChecks::Failing)
+                ),
+                (
+                    5,
+                    PullRequestStatus::Merged,
+                    None,
+                    Some(PullRequestChecks::Pending)
+                ),
+                (
+                    3,
+                    PullRequestStatus::Closed,
+                    Some(PullRequestReview::Approved),
+                    None
+                ),
+            ]
+        );
+        assert!(parse_gh_pull_requests(br#"{"number": 1}"#).is_err());
+    }
+
+    #[test]
+    fn sync_reports_merges_and_new_check_failures_for_task_worktrees() {
+        let task = fixture_task(
+            PathBuf::from("/tmp/tasks/feature"),
+            vec![pull_request(4, PullRequestStatus::Open)],
+        );
+        let mut failing = pull_request(9, PullRequestStatus::Open);
+        failing.checks = Some(PullRequestChecks::Failing);
+        let mut forge = FakePullRequestForge::default();
+        forge.pull_requests.insert(
+            "feature".to_string(),
+            vec![failing.clone(), pull_request(4, PullRequestStatus::Merged)],
+        );
+        forge.pull_requests.insert(
+            "main".to_string(),
+            vec![pull_request(1, PullRequestStatus::Open)],
+        );
+
+        let outcome = sync_task_pull_requests(std::slice::from_ref(&task), &forge);
+
+        assert_eq!(outcome.checked, 1);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(outcome.updates.len(), 1);
+        assert_eq!(
+            outcome.updates[0].pull_requests,
+            vec![pull_request(4, PullRequestStatus::Merged), failing]
+        );
+        assert_eq!(
+            outcome
+                .events
+                .iter()
+                .map(|event| (event.kind, event.pull_request.number))
+                .collect::<Vec<_>>(),
+            vec![
+                (PullRequestEventKind::ChecksFailed, 9),
+                (PullRequestEventKind::Merged, 4)
+            ]
+        );
+
+        let mut tasks = vec![task];
+        assert_eq!(apply_pull_request_updates(&mut tasks, &outcome.updates), 1);
+        let rerun = sync_task_pull_requests(&tasks, &forge);
+        assert!(rerun.updates.is_empty());
+        assert!(rerun.events.is_empty());
+    }
+
+    #[test]
+    fn sync_collects_forge_errors_per_worktree() {
+        let task = fixture_task(PathBuf::from("/tmp/tasks/feature"), Vec::new());
+        let forge = FakePullRequestForge {
+            error: Some("gh: not logged in".to_string()),
+            ..FakePullRequestForge::default()
+        };
+
+        let outcome = sync_task_pull_requests(&[task], &forge);
+
+        assert_eq!(
+            outcome.errors,
+            vec!["/tmp/tasks/feature/grove: gh: not logged in".to_string()]
+        );
+        assert!(outcome.updates.is_empty());
+    }
+
+    #[test]
+    fn persist_pull_request_updates_rewrites_task_manifest() {
+        let root = unique_test_dir("grove-pr-sync");
+        let task = fixture_task(root.clone(), Vec::new());
+        fs::create_dir_all(root.join(".grove")).expect("manifest dir should exist");
+        fs::write(
+            root.join(".grove/task.toml"),
+            encode_task_manifest(&task).expect("manifest should encode"),
+        )
+        .expect("manifest should write");
+        let mut forge = FakePullRequestForge::default();
+        forge.pull_requests.insert(
+            "feature".to_string(),
+            vec![pull_request(12, PullRequestStatus::Open)],
+        );
+
+        let outcome = sync_task_pull_requests(std::slice::from_ref(&task), &forge);
+        assert!(
+            persist_pull_request_updates(&outcome.updates, &AgentRegistry::default()).is_empty()
+        );
+
+        let raw = fs::read_to_string(root.join(".grove/task.toml")).expect("manifest should read");
+        let persisted =
+            decode_task_manifest(&raw, &AgentRegistry::default()).expect("manifest should decode");
+        assert_eq!(
+            persisted.worktrees[1].pull_requests,
+            vec![pull_request(12, PullRequestStatus::Open)]
+        );
+        assert!(persisted.worktrees[0].pull_requests.is_empty());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::application::task_lifecycle::CreateTaskRequest;
+use crate::application::workspace_lifecycle::{
+    WORKSPACE_INIT_COMMAND_FILE, WORKSPACE_LAUNCH_PROMPT_FILE, WORKSPACE_PERMISSION_MODE_FILE,
+};
+use crate::domain::{AgentRegistry, AgentType, PermissionMode, Task};
+use crate::infrastructure::config::{ProjectConfig, recipes_dir};
+
+const REPOSITORY_RECIPES_DIR: &str = ".grove/recipes";
+
+/// A reusable task template, loaded from `<recipes dir>/<name>.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Recipe {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub prompt: String,
+    #[serde(default)]
+    pub variables: Vec<RecipeVariable>,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub permission_mode: Option<PermissionMode>,
+    #[serde(default)]
+    pub base_branch: Option<String>,
+    #[serde(default)]
+    pub init_command: Option<String>,
+}
+
+/// A `{{name}}` placeholder in the recipe prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RecipeVariable {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl Recipe {
+    pub fn agent_type(&self, agents: &AgentRegistry) -> Result<Option<AgentType>, String> {
+        let Some(marker) = self.agent.as_deref() else {
+            return Ok(None);
+        };
+        agents
+            .from_marker(marker)
+            .map(Some)
+            .ok_or_else(|| format!("recipe '{}' uses unknown agent '{marker}'", self.name))
+    }
+
+    /// Declared variables filled from `provided`, falling back to defaults.
+    pub fn variable_values(
+        &self,
+        provided: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, String> {
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.variables.iter().any(|variable| &variable.name == *key))
+        {
+            return Err(format!(
+                "recipe '{}' has no variable '{unknown}'",
+                self.name
+            ));
+        }
+        self.variables
+            .iter()
+            .map(|variable| {
+                provided
+                    .get(&variable.name)
+                    .or(variable.default.as_ref())
+                    .map(|value| (variable.name.clone(), value.clone()))
+                    .ok_or_else(|| {
+                        format!(
+                            "recipe '{}' needs a value for '{}'",
+                            self.name, variable.name
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    pub fn render_prompt(&self, provided: &BTreeMap<String, String>) -> Result<String, String> {
+        let values = self.variable_values(provided)?;
+        let mut rendered = String::new();
+        let mut rest = self.prompt.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(length) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + 2 + length].trim();
+            let Some(value) = values.get(name) else {
+                return Err(format!(
+                    "recipe '{}' prompt uses undeclared variable '{name}'",
+                    self.name
+                ));
+            };
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(value);
+            rest = &rest[start + 2 + length + 2..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered.trim().to_string())
+    }
+
+    /// Records the recipe on `request` and applies its agent and base branch.
+    pub fn apply_to_request(
+        &self,
+        request: &mut CreateTaskRequest,
+        agents: &AgentRegistry,
+    ) -> Result<(), String> {
+        if let Some(agent) = self.agent_type(agents)? {
+            request.agent = agent;
+        }
+        if let Some(base_branch) = self.base_branch.as_deref().map(str::trim)
+            && !base_branch.is_empty()
+        {
+            for repository in &mut request.repositories {
+                repository.defaults.base_branch = base_branch.to_string();
+            }
+        }
+        request.recipe = Some(self.name.clone());
+        Ok(())
+    }
+}
+
+/// Recipes from the per-user directory and each repository's
+/// `.grove/recipes`, sorted by name. Repository recipes win on name clashes.
+pub fn load_recipes(
+    repositories: &[ProjectConfig],
+    agents: &AgentRegistry,
+) -> Result<Vec<Recipe>, String> {
+    let directories = recipes_dir()
+        .into_iter()
+        .chain(
+            repositories
+                .iter()
+                .map(|repository| repository.path.join(REPOSITORY_RECIPES_DIR)),
+        )
+        .collect::<Vec<PathBuf>>();
+    load_recipes_from_dirs(&directories, agents)
+}
+
+fn load_recipes_from_dirs(
+    directories: &[PathBuf],
+    agents: &AgentRegistry,
+) -> Result<Vec<Recipe>, String> {
+    let mut recipes = BTreeMap::<String, Recipe>::new();
+    for directory in directories {
+        let Ok(entries) = fs::read_dir(directory) else {
+            continue;
+        };
+        let mut paths = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+        for path in paths {
+            let recipe = load_recipe(&path, agents)?;
+            recipes.insert(recipe.name.clone(), recipe);
+        }
+    }
+    Ok(recipes.into_values().collect())
+}
+
+fn load_recipe(path: &Path, agents: &AgentRegistry) -> Result<Recipe, String> {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let raw = fs::read_to_string(path)
+        .map_err(|error| format!("read recipe {} failed: {error}", path.display()))?;
+    let mut recipe = toml::from_str::<Recipe>(&raw)
+        .map_err(|error| format!("recipe {} invalid: {error}", path.display()))?;
+    recipe.name = name;
+    recipe.path = path.to_path_buf();
+    recipe.agent_type(agents)?;
+    Ok(recipe)
+}
+
+pub fn find_recipe<'a>(recipes: &'a [Recipe], name: &str) -> Result<&'a Recipe, String> {
+    recipes
+        .iter()
+        .find(|recipe| recipe.name == name)
+        .ok_or_else(|| format!("recipe '{name}' not found"))
+}
+
+/// Writes the rendered prompt, init command and permission mode markers into
+/// the task root and every worktree, so the first agent launch picks them up.
+pub fn write_recipe_markers(task: &Task, recipe: &Recipe, prompt: &str) -> Vec<String> {
+    let mut markers = Vec::<(&str, String)>::new();
+    if !prompt.is_empty() {
+        markers.push((WORKSPACE_LAUNCH_PROMPT_FILE, prompt.to_string()));
+    }
+    if let Some(init_command) = recipe.init_command.as_deref().map(str::trim)
+        && !init_command.is_empty()
+    {
+        markers.push((WORKSPACE_INIT_COMMAND_FILE, init_command.to_string()));
+    }
+    if let Some(permission_mode) = recipe.permission_mode {
+        markers.push((
+            WORKSPACE_PERMISSION_MODE_FILE,
+            permission_mode.marker().to_string(),
+        ));
+    }
+
+    let roots = std::iter::once(task.root_path.as_path()).chain(
+        task.worktrees
+            .iter()
+            .map(|worktree| worktree.path.as_path()),
+    );
+    let mut warnings = Vec::new();
+    for root in roots {
+        for (file, value) in &markers {
+            let marker_path = root.join(file);
+            let result = marker_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&marker_path, format!("{value}\n")));
+            if let Err(error) = result {
+                warnings.push(format!(
+                    "recipe marker write failed for {}: {error}",
+                    marker_path.display()
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::application::task_lifecycle::{CreateTaskRequest, TaskBranchSource};
+    use crate::application::workspace_lifecycle::{
+        WORKSPACE_INIT_COMMAND_FILE, WORKSPACE_LAUNCH_PROMPT_FILE, WORKSPACE_PERMISSION_MODE_FILE,
+    };
+    use crate::domain::{
+        AgentRegistry, AgentType, PermissionMode, Task, WorkspaceStatus, Worktree,
+    };
+    use crate::infrastructure::config::ProjectConfig;
+    use crate::test_support::unique_test_dir;
+
+    use super::{load_recipes_from_dirs, write_recipe_markers};
+
+    const BUGFIX_RECIPE: &str = r#"
+description = "Fix a reported bug"
+prompt = "Fix issue {{ issue }} in {{area}}. Add a regression test."
+agent = "claude"
+permission_mode = "auto"
+base_branch = "develop"
+init_command = "make setup"
+
+[[variables]]
+name = "issue"
+description = "Issue number"
+
+[[variables]]
+name = "area"
+default = "the backend"
+"#;
+
+    #[test]
+    fn repository_recipes_override_user_recipes_and_render_variables() {
+        let root = unique_test_dir("recipes-load");
+        let user_dir = root.join("user");
+        let repo_dir = root.join("repo");
+        fs::create_dir_all(&user_dir).expect("user dir should exist");
+        fs::create_dir_all(&repo_dir).expect("repo dir should exist");
+        fs::write(user_dir.join("bugfix.toml"), "prompt = \"user\"\n")
+            .expect("user recipe should write");
+        fs::write(user_dir.join("docs.toml"), "prompt = \"Write docs\"\n")
+            .expect("user recipe should write");
+        fs::write(repo_dir.join("bugfix.toml"), BUGFIX_RECIPE).expect("repo recipe should write");
+
+        let recipes =
+            load_recipes_from_dirs(&[user_dir, repo_dir.clone()], &AgentRegistry::default())
+                .expect("recipes should load");
+
+        assert_eq!(
+            recipes
+                .iter()
+                .map(|recipe| recipe.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["bugfix", "docs"]
+        );
+        let bugfix = &recipes[0];
+        assert_eq!(bugfix.path, repo_dir.join("bugfix.toml"));
+        assert_eq!(bugfix.permission_mode, Some(PermissionMode::Auto));
+        let values = BTreeMap::from([("issue".to_string(), "#42".to_string())]);
+        assert_eq!(
+            bugfix.render_prompt(&values),
+            Ok("Fix issue #42 in the backend. Add a regression test.".to_string())
+        );
+        assert_eq!(
+            bugfix.render_prompt(&BTreeMap::new()),
+            Err("recipe 'bugfix' needs a value for 'issue'".to_string())
+        );
+        let unknown = BTreeMap::from([("ticket".to_string(), "1".to_string())]);
+        assert_eq!(
+            bugfix.render_prompt(&unknown),
+            Err("recipe 'bugfix' has no variable 'ticket'".to_string())
+        );
+
+        let mut request = CreateTaskRequest {
+            task_name: "fix-login".to_string(),
+            repositories: vec![ProjectConfig {
+                name: "grove".to_string(),
+                path: PathBuf::from("/repos/grove"),
+                defaults: Default::default(),
+            }],
+            agent: AgentType::Codex,
+            branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
+        };
+        bugfix
+            .apply_to_request(&mut request, &AgentRegistry::default())
+            .expect("recipe should apply");
+        assert_eq!(request.agent, AgentType::Claude);
+        assert_eq!(request.repositories[0].defaults.base_branch, "develop");
+        assert_eq!(request.recipe.as_deref(), Some("bugfix"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn load_rejects_recipe_with_unknown_agent() {
+        let root = unique_test_dir("recipes-invalid");
+        fs::write(root.join("broken.toml"), "agent = \"nope\"\n").expect("recipe should write");
+
+        let error = load_recipes_from_dirs(std::slice::from_ref(&root), &AgentRegistry::default())
+            .expect_err("unknown agent should fail");
+
+        assert_eq!(error, "recipe 'broken' uses unknown agent 'nope'");
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn write_recipe_markers_seeds_task_root_and_worktrees() {
+        let root = unique_test_dir("recipes-markers");
+        let recipe_dir = root.join("recipes");
+        fs::create_dir_all(&recipe_dir).expect("recipe dir should exist");
+        fs::write(recipe_dir.join("bugfix.toml"), BUGFIX_RECIPE).expect("recipe should write");
+        let recipes = load_recipes_from_dirs(&[recipe_dir], &AgentRegistry::default())
+            .expect("recipes should load");
+        let worktree = Worktree::try_new(
+            "grove".to_string(),
+            PathBuf::from("/repos/grove"),
+            root.join("fix-login/grove"),
+            "fix-login".to_string(),
+            AgentType::Claude,
+            WorkspaceStatus::Idle,
+        )
+        .expect("worktree should be valid");
+        let task = Task::try_new(
+            "fix-login".to_string(),
+            "fix-login".to_string(),
+            root.join("fix-login"),
+            "fix-login".to_string(),
+            vec![worktree],
+        )
+        .expect("task should be valid");
+
+        let warnings = write_recipe_markers(&task, &recipes[0], "Fix issue #42");
+
+        assert!(warnings.is_empty());
+        for path in [root.join("fix-login"), root.join("fix-login/grove")] {
+            assert_eq!(
+                fs::read_to_string(path.join(WORKSPACE_LAUNCH_PROMPT_FILE)).ok(),
+                Some("Fix issue #42\n".to_string())
+            );
+            assert_eq!(
+                fs::read_to_string(path.join(WORKSPACE_INIT_COMMAND_FILE)).ok(),
+                Some("make setup\n".to_string())
+            );
+            assert_eq!(
+                fs::read_to_string(path.join(WORKSPACE_PERMISSION_MODE_FILE)).ok(),
+                Some("auto\n".to_string())
+            );
+        }
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -9,7 +9,7 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
 use crate::application::agent_runtime::{
-    LivePreviewTarget, SessionActivity, detect_status_with_session_override,
+    LivePreviewTarget, SessionActivity, StatusRules, detect_status_with_session_override,
     reconcile_with_sessions, session_name_for_workspace_in_project,
     workspace_status_targets_for_polling_with_live_preview,
 };
@@ -114,6 +114,7 @@ pub(crate) fn run_scale_benchmark(options: ScaleBenchmarkOptions) -> io::Result<
 
 fn run_scale_benchmarks() -> io::Result<ScaleBenchmarkReport> {
     let mut cases = Vec::with_capacity(TASK_COUNTS.len());
+    let status_rules = StatusRules::default();
 
     for task_count in TASK_COUNTS {
         let fixture = SyntheticScaleFixture::create(task_count)?;
@@ -175,12 +176,13 @@ fn run_scale_benchmarks() -> io::Result<ScaleBenchmarkReport> {
                     .map(String::as_str)
                     .unwrap_or("processing...");
                 workspace.status = detect_status_with_session_override(
+                    &status_rules,
                     output,
                     SessionActivity::Active,
                     workspace.is_main,
                     true,
                     workspace.supported_agent,
-                    workspace.agent,
+                    &workspace.agent,
                     workspace.path.as_path(),
                     "",
                 );
@@ -629,7 +631,7 @@ fn flatten_benchmark_tasks(tasks: &[Task]) -> Vec<Workspace> {
                     branch: worktree.branch.clone(),
                     base_branch: worktree.base_branch.clone(),
                     last_activity_unix_secs: worktree.last_activity_unix_secs,
-                    agent: worktree.agent,
+                    agent: worktree.agent.clone(),
                     status: if worktree.is_main_checkout() {
                         WorkspaceStatus::Main
                     } else {
@@ -639,6 +641,7 @@ fn flatten_benchmark_tasks(tasks: &[Task]) -> Vec<Workspace> {
                     is_orphaned: worktree.is_orphaned,
                     supported_agent: worktree.supported_agent,
                     pull_requests: worktree.pull_requests.clone(),
+                    ports: worktree.ports,
                 })
                 .collect::<Vec<Workspace>>()
         })
@@ -661,7 +664,7 @@ fn task_from_benchmark_workspace(workspace: &Workspace) -> Task {
         repository_path,
         workspace.path.clone(),
         workspace.branch.clone(),
-        workspace.agent,
+        workspace.agent.clone(),
         workspace.status,
     )
     .expect("benchmark worktree should be valid")
@@ -3,7 +3,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::application::agent_runtime::task_session_names_for_cleanup;
 use crate::application::task_discovery::bootstrap_task_data_for_root;
-use crate::domain::Task;
+use crate::domain::{AgentRegistry, Task};
 use crate::infrastructure::paths::tasks_root;
 use crate::infrastructure::process::{execute_command, stderr_trimmed};
 
@@ -50,9 +50,12 @@ pub(crate) struct SessionRecord {
     pub(crate) attached_clients: u32,
 }
 
-pub fn plan_session_cleanup(options: SessionCleanupOptions) -> Result<SessionCleanupPlan, String> {
+pub fn plan_session_cleanup(
+    options: SessionCleanupOptions,
+    agents: &AgentRegistry,
+) -> Result<SessionCleanupPlan, String> {
     let task_root = tasks_root().ok_or_else(|| "task root unavailable".to_string())?;
-    let bootstrap = bootstrap_task_data_for_root(task_root.as_path());
+    let bootstrap = bootstrap_task_data_for_root(task_root.as_path(), agents);
     plan_session_cleanup_for_tasks(bootstrap.tasks.as_slice(), options)
 }
 
@@ -275,7 +278,7 @@ pub(crate) fn is_auxiliary_session(session_name: &str) -> bool {
     !ordinal.is_empty() && ordinal.chars().all(|character| character.is_ascii_digit())
 }
 
-fn session_missing_error(message: &str) -> bool {
+pub(crate) fn session_missing_error(message: &str) -> bool {
     message.contains("can't find session")
 }
 
@@ -3,7 +3,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::application::agent_runtime::session_name_for_task_worktree;
-use crate::domain::Task;
+use crate::domain::{AgentRegistry, Task};
+use crate::infrastructure::event_log::now_millis;
+use crate::infrastructure::state_store::StateStore;
 use crate::infrastructure::task_manifest::decode_task_manifest;
 
 const TASK_MANIFEST_FILE: &str = ".grove/task.toml";
@@ -21,15 +23,40 @@ pub struct TaskBootstrapData {
     pub discovery_state: TaskDiscoveryState,
 }
 
-pub fn bootstrap_task_data_for_root(tasks_root: &Path) -> TaskBootstrapData {
-    bootstrap_task_data_for_root_with_sessions(tasks_root, &HashSet::new())
+pub fn bootstrap_task_data_for_root(
+    tasks_root: &Path,
+    agents: &AgentRegistry,
+) -> TaskBootstrapData {
+    bootstrap_task_data_for_root_with_sessions(tasks_root, &HashSet::new(), agents)
 }
 
 pub fn bootstrap_task_data_for_root_with_sessions(
     tasks_root: &Path,
     running_sessions: &HashSet<String>,
+    agents: &AgentRegistry,
+) -> TaskBootstrapData {
+    bootstrap_task_data(tasks_root, running_sessions, None, agents)
+}
+
+/// Like [`bootstrap_task_data_for_root_with_sessions`], but caches every
+/// manifest it reads in the state store and falls back to the cached copy
+/// when a manifest on disk no longer parses.
+pub fn bootstrap_task_data_for_root_with_state_store(
+    tasks_root: &Path,
+    running_sessions: &HashSet<String>,
+    state_store: &StateStore,
+    agents: &AgentRegistry,
 ) -> TaskBootstrapData {
-    match load_tasks_from_root(tasks_root) {
+    bootstrap_task_data(tasks_root, running_sessions, Some(state_store), agents)
+}
+
+fn bootstrap_task_data(
+    tasks_root: &Path,
+    running_sessions: &HashSet<String>,
+    state_store: Option<&StateStore>,
+    agents: &AgentRegistry,
+) -> TaskBootstrapData {
+    match load_tasks_from_root(tasks_root, state_store, agents) {
         Ok(tasks) if tasks.is_empty() => TaskBootstrapData {
             tasks,
             discovery_state: TaskDiscoveryState::Empty,
@@ -45,7 +72,11 @@ pub fn bootstrap_task_data_for_root_with_sessions(
     }
 }
 
-fn load_tasks_from_root(tasks_root: &Path) -> Result<Vec<Task>, String> {
+fn load_tasks_from_root(
+    tasks_root: &Path,
+    state_store: Option<&StateStore>,
+    agents: &AgentRegistry,
+) -> Result<Vec<Task>, String> {
     if !tasks_root.exists() {
         return Ok(Vec::new());
     }
@@ -72,12 +103,20 @@ fn load_tasks_from_root(tasks_root: &Path) -> Result<Vec<Task>, String> {
                 manifest_path.display()
             )
         })?;
-        let task = decode_task_manifest(raw.as_str()).map_err(|error| {
-            format!(
-                "task manifest decode failed for {}: {error}",
-                manifest_path.display()
-            )
-        })?;
+        let task = match decode_task_manifest(raw.as_str(), agents) {
+            Ok(task) => {
+                if let Some(state_store) = state_store {
+                    let _ = state_store.cache_task_manifest(now_millis(), &task_root, &raw);
+                }
+                task
+            }
+            Err(error) => cached_task(state_store, &task_root, agents).ok_or_else(|| {
+                format!(
+                    "task manifest decode failed for {}: {error}",
+                    manifest_path.display()
+                )
+            })?,
+        };
         tasks.push(task);
     }
 
@@ -85,6 +124,15 @@ fn load_tasks_from_root(tasks_root: &Path) -> Result<Vec<Task>, String> {
     Ok(tasks)
 }
 
+fn cached_task(
+    state_store: Option<&StateStore>,
+    task_root: &Path,
+    agents: &AgentRegistry,
+) -> Option<Task> {
+    let raw = state_store?.cached_task_manifest(task_root).ok()??;
+    decode_task_manifest(raw.as_str(), agents).ok()
+}
+
 fn reconcile_tasks_with_sessions(
     tasks: Vec<Task>,
     running_sessions: &HashSet<String>,
@@ -116,9 +164,10 @@ fn task_manifest_path(task_root: &Path) -> PathBuf {
 mod tests {
     use super::{
         TaskDiscoveryState, bootstrap_task_data_for_root,
-        bootstrap_task_data_for_root_with_sessions,
+        bootstrap_task_data_for_root_with_sessions, bootstrap_task_data_for_root_with_state_store,
     };
-    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::domain::{AgentRegistry, AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::infrastructure::state_store::StateStore;
     use crate::infrastructure::task_manifest::encode_task_manifest;
     use std::collections::HashSet;
     use std::fs;
@@ -190,7 +239,7 @@ mod tests {
             fs::write(task_dir.join("task.toml"), raw).expect("task manifest should write");
         }
 
-        let bootstrap = bootstrap_task_data_for_root(&tasks_root);
+        let bootstrap = bootstrap_task_data_for_root(&tasks_root, &AgentRegistry::default());
 
         assert_eq!(bootstrap.discovery_state, TaskDiscoveryState::Ready);
         assert_eq!(bootstrap.tasks.len(), 2);
@@ -206,7 +255,7 @@ mod tests {
         fs::create_dir_all(&broken_task_dir).expect("broken task dir should exist");
         fs::write(broken_task_dir.join("task.toml"), "not = [valid").expect("broken manifest");
 
-        let bootstrap = bootstrap_task_data_for_root(&tasks_root);
+        let bootstrap = bootstrap_task_data_for_root(&tasks_root, &AgentRegistry::default());
 
         assert!(matches!(
             bootstrap.discovery_state,
@@ -215,6 +264,37 @@ mod tests {
         assert!(bootstrap.tasks.is_empty());
     }
 
+    #[test]
+    fn bootstrap_task_data_falls_back_to_cached_manifest_when_corrupted() {
+        let temp = TestDir::new("cached");
+        let tasks_root = temp.path.join("tasks");
+        let task = fixture_task("flohome-launch", "flohome");
+        let task_dir = tasks_root.join("flohome-launch").join(".grove");
+        fs::create_dir_all(&task_dir).expect("task dir should exist");
+        let raw = encode_task_manifest(&task).expect("task manifest should encode");
+        fs::write(task_dir.join("task.toml"), raw).expect("task manifest should write");
+        let state_store = StateStore::open_in_memory().expect("state store should open");
+
+        let bootstrap = bootstrap_task_data_for_root_with_state_store(
+            &tasks_root,
+            &HashSet::new(),
+            &state_store,
+            &AgentRegistry::default(),
+        );
+        assert_eq!(bootstrap.tasks, vec![task.clone()]);
+
+        fs::write(task_dir.join("task.toml"), "not = [valid").expect("broken manifest");
+        let bootstrap = bootstrap_task_data_for_root_with_state_store(
+            &tasks_root,
+            &HashSet::new(),
+            &state_store,
+            &AgentRegistry::default(),
+        );
+
+        assert_eq!(bootstrap.discovery_state, TaskDiscoveryState::Ready);
+        assert_eq!(bootstrap.tasks, vec![task]);
+    }
+
     #[test]
     fn bootstrap_task_data_reconciles_running_worktree_sessions() {
         let temp = TestDir::new("sessions");
@@ -228,6 +308,7 @@ mod tests {
         let bootstrap = bootstrap_task_data_for_root_with_sessions(
             &tasks_root,
             &HashSet::from(["grove-wt-flohome-launch-flohome".to_string()]),
+            &AgentRegistry::default(),
         );
 
         assert_eq!(bootstrap.discovery_state, TaskDiscoveryState::Ready);
@@ -2,10 +2,14 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::application::agent_runtime::kill_task_session_commands;
-use crate::domain::{AgentType, Task, Worktree};
+use crate::application::agent_runtime::{
+    kill_task_session_commands, kill_task_session_commands_for_existing_sessions,
+};
+use crate::application::ports::allocated_port_ranges;
+use crate::application::session_cleanup::list_tmux_sessions;
+use crate::domain::{AgentRegistry, AgentType, Task, Worktree};
 use crate::infrastructure::config::RepositoryConfig;
-use crate::infrastructure::paths::{refer_to_same_location, tasks_root};
+use crate::infrastructure::paths::{archive_root, refer_to_same_location, tasks_root};
 use crate::infrastructure::process::{execute_command, stderr_trimmed};
 use crate::infrastructure::task_manifest::encode_task_manifest;
 
@@ -13,10 +17,22 @@ use crate::application::workspace_lifecycle::{
     CommandGitRunner, GitCommandRunner, SetupCommandRunner, SetupScriptRunner,
 };
 
+#[path = "task_lifecycle/archive.rs"]
+mod archive;
 #[path = "task_lifecycle/create.rs"]
 mod create;
 #[path = "task_lifecycle/delete.rs"]
 mod delete;
+#[path = "task_lifecycle/pull_request.rs"]
+mod pull_request;
+
+pub use archive::{
+    ArchiveTaskResult, ArchivedTab, ArchivedWorktree, RestoreTaskResult, TaskArchive,
+};
+pub use pull_request::{
+    ParsedGitHubPullRequest, ensure_project_matches_pull_request, parse_github_pull_request_url,
+    resolve_pull_request_branch_name_with_gh,
+};
 
 const GROVE_SETUP_SCRIPT_FILE: &str = ".grove/setup.sh";
 const TASK_MANIFEST_FILE: &str = ".grove/task.toml";
@@ -35,6 +51,7 @@ pub enum TaskLifecycleError {
     TaskAlreadyHasRepository(String),
     GitCommandFailed(String),
     Io(String),
+    HookVetoed(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,6 +82,7 @@ pub fn task_lifecycle_error_message(error: &TaskLifecycleError) -> String {
             format!("git command failed: {message}")
         }
         TaskLifecycleError::Io(message) => format!("io error: {message}"),
+        TaskLifecycleError::HookVetoed(message) => message.clone(),
     }
 }
 
@@ -74,6 +92,8 @@ pub struct CreateTaskRequest {
     pub repositories: Vec<RepositoryConfig>,
     pub agent: AgentType,
     pub branch_source: TaskBranchSource,
+    /// Recipe name recorded in the task manifest.
+    pub recipe: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -129,6 +149,7 @@ impl CreateTaskRequest {
 
 pub fn create_task(
     request: &CreateTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
@@ -138,6 +159,7 @@ pub fn create_task(
     create_task_in_root(
         tasks_root.as_path(),
         request,
+        agents,
         git_runner,
         setup_script_runner,
         setup_command_runner,
@@ -147,6 +169,7 @@ pub fn create_task(
 pub fn create_task_in_root(
     tasks_root: &Path,
     request: &CreateTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
@@ -154,6 +177,7 @@ pub fn create_task_in_root(
     create::create_task_in_root(
         tasks_root,
         request,
+        agents,
         git_runner,
         setup_script_runner,
         setup_command_runner,
@@ -199,6 +223,7 @@ pub fn materialize_base_task_manifest_for_project_in_root(
 
 pub fn add_worktree_to_task(
     request: &AddWorktreeToTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
@@ -206,6 +231,7 @@ pub fn add_worktree_to_task(
     let manifest_tasks_root = tasks_root();
     add_worktree_to_task_in_manifest_root(
         request,
+        agents,
         git_runner,
         setup_script_runner,
         setup_command_runner,
@@ -216,12 +242,14 @@ pub fn add_worktree_to_task(
 pub fn add_worktree_to_task_in_root(
     tasks_root: &Path,
     request: &AddWorktreeToTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
 ) -> Result<AddWorktreeToTaskResult, TaskLifecycleError> {
     add_worktree_to_task_in_manifest_root(
         request,
+        agents,
         git_runner,
         setup_script_runner,
         setup_command_runner,
@@ -250,6 +278,44 @@ fn delete_task_with_runner_in_manifest_root(
     delete::delete_task_with_runner(request, git_runner, stop_task_sessions, manifest_tasks_root)
 }
 
+/// Snapshots the task into the archive directory, then removes its worktrees but keeps branches.
+pub fn archive_task(
+    task: &Task,
+    agents: &AgentRegistry,
+) -> Result<ArchiveTaskResult, TaskLifecycleError> {
+    let archive_root = archive_root().ok_or(TaskLifecycleError::HomeDirectoryUnavailable)?;
+    let manifest_tasks_root = tasks_root();
+    archive::archive_task_in_roots(
+        task,
+        archive_root.as_path(),
+        manifest_tasks_root.as_deref(),
+        &CommandGitRunner,
+        |task| archive::capture_task_tabs(task, agents),
+        stop_task_and_tab_sessions,
+    )
+}
+
+pub fn restore_task(
+    slug: &str,
+    agents: &AgentRegistry,
+) -> Result<RestoreTaskResult, TaskLifecycleError> {
+    let archive_root = archive_root().ok_or(TaskLifecycleError::HomeDirectoryUnavailable)?;
+    let manifest_tasks_root = tasks_root();
+    archive::restore_task_in_roots(
+        slug,
+        archive_root.as_path(),
+        manifest_tasks_root.as_deref(),
+        agents,
+        &CommandGitRunner,
+    )
+}
+
+/// Archived tasks, most recently archived first.
+pub fn list_task_archives() -> Result<Vec<TaskArchive>, TaskLifecycleError> {
+    let archive_root = archive_root().ok_or(TaskLifecycleError::HomeDirectoryUnavailable)?;
+    archive::list_task_archives_in_root(archive_root.as_path())
+}
+
 fn task_name_is_valid(name: &str) -> bool {
     name.chars()
         .all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_')
@@ -409,6 +475,7 @@ fn create_task_domain(
 
 fn add_worktree_to_task_in_manifest_root(
     request: &AddWorktreeToTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
@@ -428,14 +495,26 @@ fn add_worktree_to_task_in_manifest_root(
         ));
     }
 
+    let mut taken_ports = manifest_tasks_root
+        .or(request.task.root_path.parent())
+        .map(|tasks_root| allocated_port_ranges(tasks_root, agents))
+        .unwrap_or_default();
+    taken_ports.extend(
+        request
+            .task
+            .worktrees
+            .iter()
+            .filter_map(|worktree| worktree.ports),
+    );
     let (worktree, warnings) = create::create_task_worktree(
         request.task.root_path.as_path(),
         request.task.branch.as_str(),
         &request.repository,
-        request.agent,
+        request.agent.clone(),
         git_runner,
         setup_script_runner,
         setup_command_runner,
+        &mut taken_ports,
     )?;
     let added_worktree_path = worktree.path.clone();
     let mut worktrees = request.task.worktrees.clone();
@@ -445,7 +524,8 @@ fn add_worktree_to_task_in_manifest_root(
         request.task.branch.as_str(),
         request.task.root_path.as_path(),
         worktrees,
-    )?;
+    )?
+    .with_recipe(request.task.recipe.clone());
     let manifest_root = manifest_write_root(manifest_tasks_root, &task);
     write_task_manifest(manifest_root.as_path(), &task)?;
 
@@ -463,12 +543,27 @@ fn stop_task_sessions(task: &Task) {
     }
 }
 
+/// Also stops the agent, shell and git tab sessions opened for the task's worktrees.
+fn stop_task_and_tab_sessions(task: &Task) {
+    let existing_sessions = list_tmux_sessions()
+        .map(|sessions| {
+            sessions
+                .into_iter()
+                .map(|session| session.name)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    for command in kill_task_session_commands_for_existing_sessions(task, &existing_sessions) {
+        let _ = execute_command(command.as_slice());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        AddWorktreeToTaskRequest, CreateBaseTaskRequest, CreateTaskRequest, DeleteTaskRequest,
-        TaskBranchSource, add_worktree_to_task_in_root, create_base_task_in_root,
-        create_task_in_root, delete_task_with_runner_in_manifest_root,
+        AddWorktreeToTaskRequest, ArchivedTab, CreateBaseTaskRequest, CreateTaskRequest,
+        DeleteTaskRequest, TaskBranchSource, add_worktree_to_task_in_root, archive,
+        create_base_task_in_root, create_task_in_root, delete_task_with_runner_in_manifest_root,
         detect_repository_base_branch, materialize_base_task_manifest_for_project_in_root,
         repo_directory_name, task_manifest_path,
     };
@@ -476,12 +571,13 @@ mod tests {
         GitCommandRunner, SetupCommandContext, SetupCommandRunner, SetupScriptContext,
         SetupScriptRunner,
     };
-    use crate::domain::AgentType;
-    use crate::infrastructure::config::{ProjectDefaults, RepositoryConfig};
+    use crate::domain::{AgentRegistry, AgentType};
+    use crate::infrastructure::config::{PortsConfig, ProjectDefaults, RepositoryConfig};
     use crate::infrastructure::process::stderr_trimmed;
     use crate::infrastructure::task_manifest::decode_task_manifest;
     use std::cell::RefCell;
     use std::fs;
+    use std::io::Write;
     use std::path::{Path, PathBuf};
     use std::process::Command;
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -530,6 +626,10 @@ mod tests {
                 .push((repo_root.to_path_buf(), args.to_vec()));
             Ok(())
         }
+
+        fn stdout(&self, repo_root: &Path, args: &[String]) -> Result<Vec<u8>, String> {
+            self.run(repo_root, args).map(|()| Vec::new())
+        }
     }
 
     #[derive(Default)]
@@ -550,6 +650,18 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct RecordingSetupCommandRunner {
+        envs: RefCell<Vec<Vec<(String, String)>>>,
+    }
+
+    impl SetupCommandRunner for RecordingSetupCommandRunner {
+        fn run(&self, context: &SetupCommandContext, _command: &str) -> Result<(), String> {
+            self.envs.borrow_mut().push(context.env.clone());
+            Ok(())
+        }
+    }
+
     fn repository(path: PathBuf) -> RepositoryConfig {
         RepositoryConfig {
             name: path
@@ -590,14 +702,21 @@ mod tests {
             repositories: vec![repository(flohome.clone()), repository(fastly.clone())],
             agent: AgentType::Codex,
             branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
         };
         let git = StubGitRunner::default();
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command)
-                .expect("task should create");
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        )
+        .expect("task should create");
 
         assert_eq!(result.task_root, tasks_root.join("flohome-launch"));
         assert_eq!(result.task.worktrees.len(), 2);
@@ -657,6 +776,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_task_allocates_distinct_port_ranges_and_exports_them_to_setup() {
+        let temp = TestDir::new("create-ports");
+        let tasks_root = temp.path.join("tasks");
+        let app = temp.path.join("repos").join("app");
+        fs::create_dir_all(&app).expect("app repo should exist");
+        let mut app_repository = repository(app);
+        app_repository.defaults.ports = Some(PortsConfig { count: 4 });
+        app_repository.defaults.workspace_init_command = "npm install".to_string();
+        let git = StubGitRunner::default();
+        let setup = StubSetupRunner;
+        let setup_command = RecordingSetupCommandRunner::default();
+
+        let mut allocated = Vec::new();
+        for task_name in ["first", "second"] {
+            let request = CreateTaskRequest {
+                task_name: task_name.to_string(),
+                repositories: vec![app_repository.clone()],
+                agent: AgentType::Codex,
+                branch_source: TaskBranchSource::BaseBranch,
+                recipe: None,
+            };
+            let result = create_task_in_root(
+                tasks_root.as_path(),
+                &request,
+                &AgentRegistry::default(),
+                &git,
+                &setup,
+                &setup_command,
+            )
+            .expect("task should create");
+            let raw = fs::read_to_string(task_manifest_path(&result.task_root))
+                .expect("manifest should exist");
+            let manifest = decode_task_manifest(&raw, &AgentRegistry::default())
+                .expect("manifest should decode");
+            let ports = manifest.worktrees[0].ports.expect("ports should persist");
+            assert_eq!(ports.count, 4);
+            allocated.push(ports);
+        }
+
+        assert!(!allocated[0].overlaps(allocated[1]));
+        let envs = setup_command.envs.borrow();
+        assert_eq!(
+            envs[1][0],
+            ("PORT".to_string(), allocated[1].base.to_string())
+        );
+    }
+
     #[test]
     fn create_task_resolves_base_branch_per_repository() {
         let temp = TestDir::new("create-per-project-base");
@@ -691,14 +858,21 @@ mod tests {
             ],
             agent: AgentType::Codex,
             branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
         };
         let git = StubGitRunner::default();
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command)
-                .expect("task should create");
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        )
+        .expect("task should create");
 
         assert_eq!(
             result.task.worktrees[0].base_branch.as_deref(),
@@ -767,6 +941,7 @@ mod tests {
                 number: 123,
                 branch_name: "feature/from-pr".to_string(),
             },
+            recipe: None,
         };
 
         assert!(request.validate().is_ok());
@@ -802,14 +977,21 @@ mod tests {
                 number: 123,
                 branch_name: "feature/from-pr".to_string(),
             },
+            recipe: None,
         };
         let git = crate::application::workspace_lifecycle::CommandGitRunner;
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command)
-                .expect("task should create");
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        )
+        .expect("task should create");
 
         assert_eq!(result.task.worktrees.len(), 1);
         assert_eq!(result.task.worktrees[0].branch, "feature/from-pr");
@@ -864,14 +1046,21 @@ mod tests {
                 number: 123,
                 branch_name: "feature/from-pr".to_string(),
             },
+            recipe: None,
         };
         let git = crate::application::workspace_lifecycle::CommandGitRunner;
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command)
-                .expect("task should create");
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        )
+        .expect("task should create");
 
         assert_eq!(result.task.worktrees[0].branch, "feature/from-pr");
         assert_eq!(
@@ -918,13 +1107,20 @@ mod tests {
                 number: 123,
                 branch_name: "feature/from-pr".to_string(),
             },
+            recipe: None,
         };
         let git = crate::application::workspace_lifecycle::CommandGitRunner;
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command);
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        );
 
         let error = result.expect_err("task creation should fail");
         assert!(matches!(
@@ -986,6 +1182,7 @@ mod tests {
                 repository: repository(site_repo.clone()),
                 agent: AgentType::Codex,
             },
+            &AgentRegistry::default(),
             &git,
             &setup,
             &setup_command,
@@ -1011,7 +1208,8 @@ mod tests {
 
         let manifest = fs::read_to_string(task_manifest_path(&task_root))
             .expect("task manifest should be readable");
-        let decoded = decode_task_manifest(manifest.as_str()).expect("manifest should decode");
+        let decoded = decode_task_manifest(manifest.as_str(), &AgentRegistry::default())
+            .expect("manifest should decode");
         assert_eq!(decoded.worktrees.len(), 2);
         assert!(
             decoded
@@ -1457,6 +1655,10 @@ mod tests {
         fn run(&self, _repo_root: &Path, _args: &[String]) -> Result<(), String> {
             Err("git worktree add failed".to_string())
         }
+
+        fn stdout(&self, repo_root: &Path, args: &[String]) -> Result<Vec<u8>, String> {
+            self.run(repo_root, args).map(|()| Vec::new())
+        }
     }
 
     #[test]
@@ -1472,14 +1674,21 @@ mod tests {
             repositories: vec![repository(repo.clone())],
             agent: AgentType::Codex,
             branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
         };
         let git = crate::application::workspace_lifecycle::CommandGitRunner;
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command)
-                .expect("task should create even when branch already exists");
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        )
+        .expect("task should create even when branch already exists");
 
         assert_eq!(result.task.worktrees.len(), 1);
         assert!(result.task.worktrees[0].path.exists());
@@ -1503,13 +1712,20 @@ mod tests {
             repositories: vec![repository(repo_root)],
             agent: AgentType::Codex,
             branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
         };
         let git = FailingGitRunner;
         let setup = StubSetupRunner;
         let setup_command = StubSetupCommandRunner;
 
-        let result =
-            create_task_in_root(tasks_root.as_path(), &request, &git, &setup, &setup_command);
+        let result = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &setup,
+            &setup_command,
+        );
 
         assert!(result.is_err(), "task creation should fail when git fails");
         assert!(
@@ -1517,4 +1733,184 @@ mod tests {
             "empty task directory should be cleaned up on failure"
         );
     }
+
+    #[test]
+    fn archive_snapshots_worktrees_through_the_git_runner() {
+        let temp = TestDir::new("archive-git-runner");
+        let tasks_root = temp.path.join("tasks");
+        let archive_root = temp.path.join("archive");
+        let repo = temp.path.join("repos").join("myapp");
+        init_git_repo(&repo, "main");
+        let request = CreateTaskRequest {
+            task_name: "stubbed".to_string(),
+            repositories: vec![repository(repo)],
+            agent: AgentType::Codex,
+            branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
+        };
+        let git = StubGitRunner::default();
+        let task = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &StubSetupRunner,
+            &StubSetupCommandRunner,
+        )
+        .expect("task should create")
+        .task;
+        let worktree_path = task.worktrees[0].path.clone();
+        fs::create_dir_all(&worktree_path).expect("worktree dir should exist");
+
+        archive::archive_task_in_roots(
+            &task,
+            archive_root.as_path(),
+            Some(tasks_root.as_path()),
+            &git,
+            |_| (Vec::new(), Vec::new()),
+            |_| {},
+        )
+        .expect("archive should succeed");
+
+        let calls = git.calls();
+        for args in [
+            ["rev-parse", "HEAD"].as_slice(),
+            &["diff", "--binary", "HEAD"],
+        ] {
+            assert!(
+                calls
+                    .iter()
+                    .any(|(path, call)| path == &worktree_path && call == args),
+                "missing git {args:?} in {calls:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn archive_and_restore_round_trip_keeps_branch_changes_and_tabs() {
+        let temp = TestDir::new("archive-restore");
+        let tasks_root = temp.path.join("tasks");
+        let archive_root = temp.path.join("archive");
+        let repo = temp.path.join("repos").join("myapp");
+        init_git_repo(&repo, "main");
+        let request = CreateTaskRequest {
+            task_name: "parked".to_string(),
+            repositories: vec![repository(repo.clone())],
+            agent: AgentType::Claude,
+            branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
+        };
+        let git = crate::application::workspace_lifecycle::CommandGitRunner;
+        let task = create_task_in_root(
+            tasks_root.as_path(),
+            &request,
+            &AgentRegistry::default(),
+            &git,
+            &StubSetupRunner,
+            &StubSetupCommandRunner,
+        )
+        .expect("task should create")
+        .task;
+        let worktree_path = task.worktrees[0].path.clone();
+        fs::write(worktree_path.join("README.md"), "hello\nwork in progress\n")
+            .expect("edit should write");
+        fs::write(worktree_path.join("notes.txt"), "untracked\n").expect("new file should write");
+        let mut exclude = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(repo.join(".git/info/exclude"))
+            .expect("git exclude should open");
+        writeln!(exclude, ".env").expect("git exclude should write");
+        fs::write(worktree_path.join(".env"), "TOKEN=local\n").expect("env file should write");
+        let agent_tab = ArchivedTab {
+            kind: "agent".to_string(),
+            title: "Claude 1".to_string(),
+            agent: Some("claude".to_string()),
+            resume_command: Some("claude --resume abc-123".to_string()),
+        };
+        let captured_path = worktree_path.clone();
+        let captured_tab = agent_tab.clone();
+
+        let archived = archive::archive_task_in_roots(
+            &task,
+            archive_root.as_path(),
+            Some(tasks_root.as_path()),
+            &git,
+            move |_| {
+                (
+                    vec![(captured_path.clone(), vec![captured_tab.clone()])],
+                    Vec::new(),
+                )
+            },
+            |_| {},
+        )
+        .expect("archive should succeed");
+
+        assert!(!worktree_path.exists());
+        assert!(!task.root_path.exists());
+        assert!(archived.archive_dir.join("myapp.patch").exists());
+        assert_eq!(
+            archived.warnings,
+            vec!["myapp: ignored files were not archived: .env".to_string()]
+        );
+        assert_eq!(
+            archive::list_task_archives_in_root(archive_root.as_path())
+                .expect("archives should list")
+                .len(),
+            1
+        );
+        run_git(&repo, &["worktree", "prune"]);
+        run_git(&repo, &["branch", "-D", "parked"]);
+
+        let restored = archive::restore_task_in_roots(
+            "parked",
+            archive_root.as_path(),
+            Some(tasks_root.as_path()),
+            &AgentRegistry::default(),
+            &git,
+        )
+        .expect("restore should succeed");
+
+        assert_eq!(restored.task.slug, "parked");
+        assert_eq!(
+            git_stdout(&worktree_path, &["branch", "--show-current"]),
+            "parked"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree_path.join("README.md")).expect("readme should read"),
+            "hello\nwork in progress\n"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree_path.join("notes.txt")).expect("notes should read"),
+            "untracked\n"
+        );
+        assert!(task_manifest_path(&task.root_path).exists());
+        assert!(!archived.archive_dir.exists());
+        assert!(
+            restored
+                .warnings
+                .iter()
+                .any(|warning| warning.contains("was recreated"))
+        );
+        assert_eq!(
+            restored.archive.resumable_tabs(&AgentRegistry::default()),
+            vec![(worktree_path, agent_tab)]
+        );
+    }
+
+    #[test]
+    fn archive_tab_rows_keep_only_the_worktree_tabs_in_order() {
+        let output = "grove-wt-parked-app-agent-2\t/tasks/parked/app\tagent\tReview\tcodex\t2\n\
+grove-wt-parked-app-shell-1\t/tasks/parked/app\tshell\tShell 1\t\t1\n\
+grove-wt-other-app-agent-1\t/tasks/other/app\tagent\tClaude 1\tclaude\t0\n\
+plain-session\t\t\t\t\t\n";
+
+        let rows = archive::parse_tab_rows(output, Path::new("/tasks/parked/app"));
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].1, "grove-wt-parked-app-agent-2");
+        assert_eq!(rows[0].2.agent.as_deref(), Some("codex"));
+        assert_eq!(rows[1].0, 1);
+        assert_eq!(rows[1].2.agent, None);
+    }
 }
@@ -0,0 +1,514 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::agent_runtime::exit_agent_for_resume_command;
+use crate::application::ports::{allocate_port_range, allocated_port_ranges, port_is_bindable};
+use crate::application::workspace_lifecycle::{GROVE_GIT_EXCLUDE_ENTRIES, GitCommandRunner};
+use crate::domain::{AgentRegistry, AgentType, Task};
+use crate::infrastructure::event_log::now_millis;
+use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
+
+use super::delete::{manifest_task_root, remove_task_root, run_delete_worktree_git};
+use super::{TaskLifecycleError, manifest_write_root, write_task_manifest};
+
+const ARCHIVE_MANIFEST_FILE: &str = "archive.toml";
+const ARCHIVED_TASK_MANIFEST_FILE: &str = "task.toml";
+
+/// A tab that was open when the task was archived, in display order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedTab {
+    pub kind: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_command: Option<String>,
+}
+
+impl ArchivedTab {
+    pub fn agent_type(&self, agents: &AgentRegistry) -> Option<AgentType> {
+        let marker = self.agent.as_deref()?;
+        agents.from_marker(marker)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedWorktree {
+    pub repository_name: String,
+    pub path: PathBuf,
+    pub branch: String,
+    /// Commit the worktree was on, used to recreate the branch if it was deleted meanwhile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// Uncommitted changes, relative to the archive directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch_file: Option<String>,
+    #[serde(default)]
+    pub tabs: Vec<ArchivedTab>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskArchive {
+    pub name: String,
+    pub slug: String,
+    pub archived_at_ms: u64,
+    pub worktrees: Vec<ArchivedWorktree>,
+}
+
+impl TaskArchive {
+    /// Agent tabs that can pick their conversation back up, with their worktree path.
+    pub fn resumable_tabs(&self, agents: &AgentRegistry) -> Vec<(PathBuf, ArchivedTab)> {
+        self.worktrees
+            .iter()
+            .flat_map(|worktree| {
+                worktree
+                    .tabs
+                    .iter()
+                    .filter(|tab| tab.resume_command.is_some() && tab.agent_type(agents).is_some())
+                    .map(|tab| (worktree.path.clone(), tab.clone()))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveTaskResult {
+    pub archive_dir: PathBuf,
+    pub archive: TaskArchive,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreTaskResult {
+    pub task: Task,
+    pub archive: TaskArchive,
+    pub warnings: Vec<String>,
+}
+
+/// Tab layout per worktree path, plus warnings for agents whose resume command was not found.
+pub(super) type CapturedTabs = (Vec<(PathBuf, Vec<ArchivedTab>)>, Vec<String>);
+
+pub(super) fn archive_task_in_roots(
+    task: &Task,
+    archive_root: &Path,
+    manifest_tasks_root: Option<&Path>,
+    git_runner: &impl GitCommandRunner,
+    capture_tabs: impl Fn(&Task) -> CapturedTabs,
+    stop_sessions: impl Fn(&Task),
+) -> Result<ArchiveTaskResult, TaskLifecycleError> {
+    if task.has_base_worktree() {
+        return Err(TaskLifecycleError::TaskInvalid(
+            "base tasks cannot be archived".to_string(),
+        ));
+    }
+    let archive_dir = archive_root.join(task.slug.as_str());
+    if archive_dir.exists() {
+        return Err(TaskLifecycleError::TaskInvalid(format!(
+            "task '{}' is already archived at {}",
+            task.slug,
+            archive_dir.display()
+        )));
+    }
+
+    let (mut captured_tabs, mut warnings) = capture_tabs(task);
+    stop_sessions(task);
+    fs::create_dir_all(&archive_dir).map_err(|error| {
+        TaskLifecycleError::Io(format!("create {}: {error}", archive_dir.display()))
+    })?;
+
+    let mut worktrees = Vec::with_capacity(task.worktrees.len());
+    for worktree in &task.worktrees {
+        let tabs = captured_tabs
+            .iter()
+            .position(|(path, _)| path == &worktree.path)
+            .map(|index| captured_tabs.swap_remove(index).1)
+            .unwrap_or_default();
+        let mut archived = ArchivedWorktree {
+            repository_name: worktree.repository_name.clone(),
+            path: worktree.path.clone(),
+            branch: worktree.branch.clone(),
+            head: None,
+            patch_file: None,
+            tabs,
+        };
+        if !worktree.path.exists() {
+            warnings.push(format!(
+                "{} worktree missing, nothing to snapshot",
+                worktree.repository_name
+            ));
+            worktrees.push(archived);
+            continue;
+        }
+
+        archived.head = git_runner
+            .stdout(
+                worktree.path.as_path(),
+                &["rev-parse".to_string(), "HEAD".to_string()],
+            )
+            .map(|head| String::from_utf8_lossy(&head).trim().to_string())
+            .ok();
+        let patch = worktree_changes_patch(git_runner, worktree.path.as_path())
+            .map_err(TaskLifecycleError::GitCommandFailed)?;
+        match ignored_paths(git_runner, worktree.path.as_path()) {
+            Ok(ignored) if ignored.is_empty() => {}
+            Ok(ignored) => warnings.push(format!(
+                "{}: ignored files were not archived: {}",
+                worktree.repository_name,
+                ignored.join(", ")
+            )),
+            Err(error) => warnings.push(format!(
+                "{}: could not list ignored files: {error}",
+                worktree.repository_name
+            )),
+        }
+        if !patch.is_empty() {
+            let patch_file = format!("{}.patch", worktree.repository_name);
+            fs::write(archive_dir.join(&patch_file), patch)
+                .map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
+            archived.patch_file = Some(patch_file);
+        }
+        worktrees.push(archived);
+    }
+
+    let archive = TaskArchive {
+        name: task.name.clone(),
+        slug: task.slug.clone(),
+        archived_at_ms: now_millis(),
+        worktrees,
+    };
+    let encoded_task = encode_task_manifest(task).map_err(TaskLifecycleError::TaskManifest)?;
+    let encoded_archive = toml::to_string(&archive)
+        .map_err(|error| TaskLifecycleError::TaskManifest(error.to_string()))?;
+    fs::write(archive_dir.join(ARCHIVED_TASK_MANIFEST_FILE), encoded_task)
+        .map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
+    fs::write(archive_dir.join(ARCHIVE_MANIFEST_FILE), encoded_archive)
+        .map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
+
+    // The snapshot is on disk, so from here on failures leave the archive in place.
+    for worktree in &task.worktrees {
+        run_delete_worktree_git(
+            git_runner,
+            worktree.repository_path.as_path(),
+            worktree.path.as_path(),
+            !worktree.path.exists(),
+        )
+        .map_err(TaskLifecycleError::GitCommandFailed)?;
+    }
+    remove_task_root(task.root_path.as_path()).map_err(TaskLifecycleError::Io)?;
+    if let Some(manifest_root) = manifest_task_root(
+        manifest_tasks_root,
+        task.slug.as_str(),
+        task.root_path.as_path(),
+    ) {
+        remove_task_root(manifest_root.as_path()).map_err(TaskLifecycleError::Io)?;
+    }
+
+    Ok(ArchiveTaskResult {
+        archive_dir,
+        archive,
+        warnings,
+    })
+}
+
+/// Ports freed by the archive may have been handed out since, so restored
+/// worktrees get their ranges again, keeping the archived port counts.
+fn reallocate_worktree_ports(
+    task: &mut Task,
+    manifest_root: &Path,
+    agents: &AgentRegistry,
+    warnings: &mut Vec<String>,
+) {
+    let mut taken_ports = manifest_root
+        .parent()
+        .map(|tasks_root| allocated_port_ranges(tasks_root, agents))
+        .unwrap_or_default();
+    for worktree in &mut task.worktrees {
+        let Some(ports) = worktree.ports else {
+            continue;
+        };
+        worktree.ports = match allocate_port_range(
+            worktree.path.as_path(),
+            ports.count,
+            &taken_ports,
+            port_is_bindable,
+        ) {
+            Ok(ports) => {
+                taken_ports.push(ports);
+                Some(ports)
+            }
+            Err(error) => {
+                warnings.push(format!(
+                    "port allocation failed for {}: {error}",
+                    worktree.repository_name
+                ));
+                None
+            }
+        };
+    }
+}
+
+pub(super) fn restore_task_in_roots(
+    slug: &str,
+    archive_root: &Path,
+    manifest_tasks_root: Option<&Path>,
+    agents: &AgentRegistry,
+    git_runner: &impl GitCommandRunner,
+) -> Result<RestoreTaskResult, TaskLifecycleError> {
+    let archive_dir = archive_root.join(slug);
+    let archive = read_task_archive(archive_dir.as_path())?;
+    let raw_task = fs::read_to_string(archive_dir.join(ARCHIVED_TASK_MANIFEST_FILE))
+        .map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
+    let mut task =
+        decode_task_manifest(&raw_task, agents).map_err(TaskLifecycleError::TaskManifest)?;
+    let manifest_root = manifest_write_root(manifest_tasks_root, &task);
+    if super::task_manifest_path(manifest_root.as_path()).exists() {
+        return Err(TaskLifecycleError::TaskInvalid(format!(
+            "task '{}' already exists",
+            task.slug
+        )));
+    }
+
+    let mut warnings = Vec::new();
+    reallocate_worktree_ports(&mut task, manifest_root.as_path(), agents, &mut warnings);
+    let mut keep_archive = false;
+    for worktree in &task.worktrees {
+        let archived = archive
+            .worktrees
+            .iter()
+            .find(|archived| archived.repository_name == worktree.repository_name);
+        if let Some(parent) = worktree.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
+        }
+        let worktree_path = worktree.path.to_string_lossy().to_string();
+        let add_args = if local_branch_exists(
+            git_runner,
+            worktree.repository_path.as_path(),
+            worktree.branch.as_str(),
+        ) {
+            vec![
+                "worktree".to_string(),
+                "add".to_string(),
+                worktree_path,
+                worktree.branch.clone(),
+            ]
+        } else {
+            let Some(head) = archived.and_then(|archived| archived.head.clone()) else {
+                return Err(TaskLifecycleError::GitCommandFailed(format!(
+                    "branch '{}' no longer exists in {}",
+                    worktree.branch, worktree.repository_name
+                )));
+            };
+            warnings.push(format!(
+                "{} branch '{}' was recreated from {head}",
+                worktree.repository_name, worktree.branch
+            ));
+            vec![
+                "worktree".to_string(),
+                "add".to_string(),
+                "-b".to_string(),
+                worktree.branch.clone(),
+                worktree_path,
+                head,
+            ]
+        };
+        git_runner
+            .run(worktree.repository_path.as_path(), &add_args)
+            .map_err(TaskLifecycleError::GitCommandFailed)?;
+
+        let Some(patch_file) = archived.and_then(|archived| archived.patch_file.as_deref()) else {
+            continue;
+        };
+        let patch_path = archive_dir.join(patch_file);
+        let apply_args = vec![
+            "apply".to_string(),
+            "--binary".to_string(),
+            "--whitespace=nowarn".to_string(),
+            patch_path.to_string_lossy().to_string(),
+        ];
+        if let Err(error) = git_runner.run(worktree.path.as_path(), &apply_args) {
+            keep_archive = true;
+            warnings.push(format!(
+                "{} changes did not apply, patch kept at {}: {error}",
+                worktree.repository_name,
+                patch_path.display()
+            ));
+        }
+    }
+
+    write_task_manifest(task.root_path.as_path(), &task)?;
+    if manifest_root != task.root_path {
+        write_task_manifest(manifest_root.as_path(), &task)?;
+    }
+    if !keep_archive && let Err(error) = fs::remove_dir_all(&archive_dir) {
+        warnings.push(format!(
+            "archive cleanup for {} failed: {error}",
+            archive_dir.display()
+        ));
+    }
+
+    Ok(RestoreTaskResult {
+        task,
+        archive,
+        warnings,
+    })
+}
+
+pub(super) fn list_task_archives_in_root(
+    archive_root: &Path,
+) -> Result<Vec<TaskArchive>, TaskLifecycleError> {
+    let entries = match fs::read_dir(archive_root) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(TaskLifecycleError::Io(error.to_string())),
+    };
+    let mut archives = Vec::new();
+    for entry in entries.flatten() {
+        if entry.path().join(ARCHIVE_MANIFEST_FILE).exists() {
+            archives.push(read_task_archive(entry.path().as_path())?);
+        }
+    }
+    archives.sort_by_key(|archive| std::cmp::Reverse(archive.archived_at_ms));
+    Ok(archives)
+}
+
+fn read_task_archive(archive_dir: &Path) -> Result<TaskArchive, TaskLifecycleError> {
+    let path = archive_dir.join(ARCHIVE_MANIFEST_FILE);
+    let raw = fs::read_to_string(&path)
+        .map_err(|error| TaskLifecycleError::Io(format!("read {}: {error}", path.display())))?;
+    toml::from_str(&raw)
+        .map_err(|error| TaskLifecycleError::TaskManifest(format!("{}: {error}", path.display())))
+}
+
+/// Every uncommitted change, untracked files included, as a binary diff against HEAD.
+fn worktree_changes_patch(
+    git_runner: &impl GitCommandRunner,
+    worktree_path: &Path,
+) -> Result<Vec<u8>, String> {
+    git_runner.run(
+        worktree_path,
+        &["add".to_string(), "-A".to_string(), "-N".to_string()],
+    )?;
+    git_runner.stdout(
+        worktree_path,
+        &[
+            "diff".to_string(),
+            "--binary".to_string(),
+            "HEAD".to_string(),
+        ],
+    )
+}
+
+/// Ignored paths the patch leaves out, other than Grove's own markers.
+fn ignored_paths(
+    git_runner: &impl GitCommandRunner,
+    worktree_path: &Path,
+) -> Result<Vec<String>, String> {
+    let status = git_runner.stdout(
+        worktree_path,
+        &[
+            "status".to_string(),
+            "--ignored".to_string(),
+            "--porcelain".to_string(),
+        ],
+    )?;
+    Ok(String::from_utf8_lossy(&status)
+        .lines()
+        .filter_map(|line| line.strip_prefix("!! "))
+        .filter(|path| !GROVE_GIT_EXCLUDE_ENTRIES.contains(path))
+        .map(str::to_string)
+        .collect())
+}
+
+fn local_branch_exists(git_runner: &impl GitCommandRunner, repo_root: &Path, branch: &str) -> bool {
+    git_runner
+        .run(
+            repo_root,
+            &[
+                "show-ref".to_string(),
+                "--verify".to_string(),
+                "--quiet".to_string(),
+                format!("refs/heads/{branch}"),
+            ],
+        )
+        .is_ok()
+}
+
+/// Reads the tab layout of `task` from tmux and exits each agent to record its resume command.
+pub(super) fn capture_task_tabs(task: &Task, agents: &AgentRegistry) -> CapturedTabs {
+    let output = Command::new("tmux")
+        .args([
+            "list-sessions",
+            "-F",
+            "#{session_name}\t#{@grove_workspace_path}\t#{@grove_tab_kind}\t#{@grove_tab_title}\t#{@grove_tab_agent}\t#{@grove_tab_order}",
+        ])
+        .output();
+    let Ok(output) = output else {
+        return (Vec::new(), Vec::new());
+    };
+    if !output.status.success() {
+        return (Vec::new(), Vec::new());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut warnings = Vec::new();
+    let tabs = task
+        .worktrees
+        .iter()
+        .map(|worktree| {
+            let mut rows = parse_tab_rows(stdout.as_ref(), worktree.path.as_path());
+            rows.sort_by_key(|(order, _, _)| *order);
+            let tabs = rows
+                .into_iter()
+                .map(|(_, session_name, mut tab)| {
+                    if let Some(agent) = tab.agent_type(agents) {
+                        match exit_agent_for_resume_command(&agent, session_name.as_str()) {
+                            Ok(command) => tab.resume_command = Some(command),
+                            Err(error) => warnings.push(format!("{}: {error}", tab.title)),
+                        }
+                    }
+                    tab
+                })
+                .collect();
+            (worktree.path.clone(), tabs)
+        })
+        .collect();
+    (tabs, warnings)
+}
+
+/// Rows of `tmux list-sessions` tab metadata belonging to `workspace_path`, as (order, session, tab).
+pub(super) fn parse_tab_rows(
+    output: &str,
+    workspace_path: &Path,
+) -> Vec<(u64, String, ArchivedTab)> {
+    output
+        .lines()
+        .filter_map(|row| {
+            let mut fields = row.split('\t');
+            let session_name = fields.next()?.trim();
+            let path = fields.next()?.trim();
+            let kind = fields.next()?.trim();
+            let title = fields.next()?.trim();
+            let agent = fields.next()?.trim();
+            let order = fields.next().unwrap_or_default().trim();
+            if session_name.is_empty() || path.is_empty() || Path::new(path) != workspace_path {
+                return None;
+            }
+            if !matches!(kind, "agent" | "shell" | "git" | "diff") {
+                return None;
+            }
+            Some((
+                order.parse().unwrap_or(u64::MAX),
+                session_name.to_string(),
+                ArchivedTab {
+                    kind: kind.to_string(),
+                    title: title.to_string(),
+                    agent: (kind == "agent" && !agent.is_empty()).then(|| agent.to_string()),
+                    resume_command: None,
+                },
+            ))
+        })
+        .collect()
+}
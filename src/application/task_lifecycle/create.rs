@@ -2,19 +2,23 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
-use crate::domain::{Task, WorkspaceStatus, Worktree};
+use crate::domain::{AgentRegistry, PortRange, Task, WorkspaceStatus, Worktree};
 
 use super::{
     CreateBaseTaskRequest, CreateTaskRequest, CreateTaskResult, GROVE_SETUP_SCRIPT_FILE,
     TaskBranchSource, TaskLifecycleError, create_task_domain, repo_directory_name,
     resolve_repository_base_branch, write_task_manifest,
 };
+use crate::application::ports::{
+    allocate_port_range, allocated_port_ranges, port_env_vars, port_is_bindable,
+};
 use crate::application::workspace_lifecycle::{
     GitCommandRunner, SetupCommandContext, SetupCommandRunner, SetupScriptContext,
     SetupScriptRunner, copy_env_files, ensure_grove_git_exclude_entries,
     write_workspace_base_marker,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn create_task_worktree(
     task_root: &Path,
     task_branch: &str,
@@ -23,6 +27,7 @@ pub(super) fn create_task_worktree(
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
+    taken_ports: &mut Vec<PortRange>,
 ) -> Result<(Worktree, Vec<String>), TaskLifecycleError> {
     let base_branch = resolve_repository_base_branch(repository)?;
     let repository_dir = repo_directory_name(repository)?;
@@ -58,6 +63,8 @@ pub(super) fn create_task_worktree(
         .map_err(|error| TaskLifecycleError::Io(format!("{error:?}")))?;
 
     let mut warnings = Vec::new();
+    let ports = reserve_worktree_ports(repository, &worktree_path, taken_ports, &mut warnings);
+    let env = ports.map(port_env_vars).unwrap_or_default();
     let setup_script_path = repository.path.join(GROVE_SETUP_SCRIPT_FILE);
     if setup_script_path.exists() {
         let context = SetupScriptContext {
@@ -65,6 +72,7 @@ pub(super) fn create_task_worktree(
             main_worktree_path: repository.path.clone(),
             workspace_path: worktree_path.clone(),
             worktree_branch: task_branch.to_string(),
+            env: env.clone(),
         };
         if let Err(error) = setup_script_runner.run(&context) {
             warnings.push(format!(
@@ -80,6 +88,7 @@ pub(super) fn create_task_worktree(
             main_worktree_path: repository.path.clone(),
             workspace_path: worktree_path.clone(),
             worktree_branch: task_branch.to_string(),
+            env,
         };
         if let Err(error) = setup_command_runner.run(&context, setup_command) {
             warnings.push(format!(
@@ -98,7 +107,8 @@ pub(super) fn create_task_worktree(
         WorkspaceStatus::Idle,
     )
     .map_err(|error| TaskLifecycleError::TaskInvalid(format!("{error:?}")))?
-    .with_base_branch(Some(base_branch));
+    .with_base_branch(Some(base_branch))
+    .with_ports(ports);
 
     Ok((worktree, warnings))
 }
@@ -106,6 +116,7 @@ pub(super) fn create_task_worktree(
 pub(super) fn create_task_in_root(
     tasks_root: &Path,
     request: &CreateTaskRequest,
+    agents: &AgentRegistry,
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
@@ -115,12 +126,14 @@ pub(super) fn create_task_in_root(
     let task_root = tasks_root.join(&request.task_name);
     fs::create_dir_all(&task_root).map_err(|error| TaskLifecycleError::Io(error.to_string()))?;
 
+    let mut taken_ports = allocated_port_ranges(tasks_root, agents);
     let result = create_task_in_dir(
         &task_root,
         request,
         git_runner,
         setup_script_runner,
         setup_command_runner,
+        &mut taken_ports,
     );
     if result.is_err() {
         let _ = remove_dir_if_empty(&task_root);
@@ -134,6 +147,7 @@ fn create_task_in_dir(
     git_runner: &impl GitCommandRunner,
     setup_script_runner: &impl SetupScriptRunner,
     setup_command_runner: &impl SetupCommandRunner,
+    taken_ports: &mut Vec<PortRange>,
 ) -> Result<CreateTaskResult, TaskLifecycleError> {
     let mut warnings = Vec::new();
     let mut worktrees = Vec::new();
@@ -149,10 +163,11 @@ fn create_task_in_dir(
                     task_root,
                     request.task_name.as_str(),
                     repository,
-                    request.agent,
+                    request.agent.clone(),
                     git_runner,
                     setup_script_runner,
                     setup_command_runner,
+                    taken_ports,
                 )?;
                 warnings.append(&mut repository_warnings);
                 worktrees.push(worktree);
@@ -214,6 +229,9 @@ fn create_task_in_dir(
                 copy_env_files(repository.path.as_path(), &worktree_path)
                     .map_err(|error| TaskLifecycleError::Io(format!("{error:?}")))?;
 
+                let ports =
+                    reserve_worktree_ports(repository, &worktree_path, taken_ports, &mut warnings);
+                let env = ports.map(port_env_vars).unwrap_or_default();
                 let setup_script_path = repository.path.join(GROVE_SETUP_SCRIPT_FILE);
                 if setup_script_path.exists() {
                     let context = SetupScriptContext {
@@ -221,6 +239,7 @@ fn create_task_in_dir(
                         main_worktree_path: repository.path.clone(),
                         workspace_path: worktree_path.clone(),
                         worktree_branch: worktree_branch.clone(),
+                        env: env.clone(),
                     };
                     if let Err(error) = setup_script_runner.run(&context) {
                         warnings.push(format!(
@@ -236,6 +255,7 @@ fn create_task_in_dir(
                         main_worktree_path: repository.path.clone(),
                         workspace_path: worktree_path.clone(),
                         worktree_branch: worktree_branch.clone(),
+                        env,
                     };
                     if let Err(error) = setup_command_runner.run(&context, setup_command) {
                         warnings.push(format!(
@@ -250,11 +270,12 @@ fn create_task_in_dir(
                     repository.path.clone(),
                     worktree_path,
                     worktree_branch,
-                    request.agent,
+                    request.agent.clone(),
                     WorkspaceStatus::Idle,
                 )
                 .map_err(|error| TaskLifecycleError::TaskInvalid(format!("{error:?}")))?
-                .with_base_branch(Some(base_branch));
+                .with_base_branch(Some(base_branch))
+                .with_ports(ports);
                 worktrees.push(worktree);
             }
         }
@@ -265,7 +286,8 @@ fn create_task_in_dir(
         task_branch.as_str(),
         task_root,
         worktrees,
-    )?;
+    )?
+    .with_recipe(request.recipe.clone());
     write_task_manifest(task_root, &task)?;
 
     Ok(CreateTaskResult {
@@ -275,6 +297,30 @@ fn create_task_in_dir(
     })
 }
 
+/// Allocates the project's port range for a new worktree and records it in
+/// `taken_ports`. Failing to find free ports only warns.
+pub(super) fn reserve_worktree_ports(
+    repository: &crate::infrastructure::config::RepositoryConfig,
+    worktree_path: &Path,
+    taken_ports: &mut Vec<PortRange>,
+    warnings: &mut Vec<String>,
+) -> Option<PortRange> {
+    let config = repository.defaults.ports.as_ref()?;
+    match allocate_port_range(worktree_path, config.count, taken_ports, port_is_bindable) {
+        Ok(ports) => {
+            taken_ports.push(ports);
+            Some(ports)
+        }
+        Err(error) => {
+            warnings.push(format!(
+                "port allocation failed for {}: {error}",
+                repository.name
+            ));
+            None
+        }
+    }
+}
+
 fn remove_dir_if_empty(path: &Path) -> std::io::Result<()> {
     if path.read_dir()?.next().is_none() {
         fs::remove_dir(path)?;
@@ -302,7 +348,7 @@ pub(super) fn create_base_task_in_root(
         request.repository.path.clone(),
         request.repository.path.clone(),
         request.base_branch.clone(),
-        request.agent,
+        request.agent.clone(),
         WorkspaceStatus::Main,
     )
     .map_err(|error| TaskLifecycleError::TaskInvalid(format!("{error:?}")))?
@@ -82,7 +82,7 @@ pub(super) fn delete_task_with_runner(
     (Ok(()), warnings)
 }
 
-fn manifest_task_root(
+pub(super) fn manifest_task_root(
     manifest_tasks_root: Option<&Path>,
     task_slug: &str,
     task_root: &Path,
@@ -120,7 +120,7 @@ fn base_task_removal_root(task: &Task, manifest_tasks_root: Option<&Path>) -> Op
     }
 }
 
-fn remove_task_root(task_root: &Path) -> Result<(), String> {
+pub(super) fn remove_task_root(task_root: &Path) -> Result<(), String> {
     if !task_root.exists() {
         return Ok(());
     }
@@ -129,7 +129,7 @@ fn remove_task_root(task_root: &Path) -> Result<(), String> {
         .map_err(|error| format!("remove task root '{}': {error}", task_root.display()))
 }
 
-fn run_delete_worktree_git(
+pub(super) fn run_delete_worktree_git(
     git_runner: &impl GitCommandRunner,
     repo_root: &Path,
     worktree_path: &Path,
@@ -0,0 +1,129 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::infrastructure::process::stderr_trimmed;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedGitHubPullRequest {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+pub fn parse_github_pull_request_url(url: &str) -> Result<ParsedGitHubPullRequest, String> {
+    let trimmed = url.trim();
+    let trimmed = trimmed.trim_end_matches('/');
+    let parts = trimmed.split('/').collect::<Vec<&str>>();
+    if parts.len() < 7 {
+        return Err("GitHub pull request URL is invalid".to_string());
+    }
+
+    let owner = parts[3].trim();
+    let repo = parts[4].trim();
+    let kind = parts[5].trim();
+    let number = parts[6].trim();
+    if owner.is_empty() || repo.is_empty() || kind != "pull" {
+        return Err("GitHub pull request URL is invalid".to_string());
+    }
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| "GitHub pull request number is invalid".to_string())?;
+
+    Ok(ParsedGitHubPullRequest {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        number,
+    })
+}
+
+pub fn ensure_project_matches_pull_request(
+    project_root: &Path,
+    pull_request: &ParsedGitHubPullRequest,
+) -> Result<(), String> {
+    let output = Command::new("git")
+        .current_dir(project_root)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .map_err(|error| format!("git remote get-url origin failed: {error}"))?;
+    if !output.status.success() {
+        return Err(stderr_trimmed(&output));
+    }
+
+    let origin = String::from_utf8(output.stdout)
+        .map_err(|error| format!("origin URL was invalid UTF-8: {error}"))?;
+    let normalized = origin.trim().replace(':', "/");
+    let expected_fragment = format!("{}/{}", pull_request.owner, pull_request.repo);
+    if normalized.contains(expected_fragment.as_str()) {
+        return Ok(());
+    }
+
+    Err("selected project does not match pull request repository".to_string())
+}
+
+pub fn resolve_pull_request_branch_name_with_gh(
+    pull_request: &ParsedGitHubPullRequest,
+) -> Result<String, String> {
+    let output = Command::new("gh")
+        .args([
+            "api",
+            &format!(
+                "repos/{}/{}/pulls/{}",
+                pull_request.owner, pull_request.repo, pull_request.number
+            ),
+        ])
+        .output()
+        .map_err(|error| format!("gh api failed: {error}"))?;
+    if !output.status.success() {
+        return Err(stderr_trimmed(&output));
+    }
+
+    parse_pull_request_head_branch_name(&output.stdout)
+}
+
+fn parse_pull_request_head_branch_name(stdout: &[u8]) -> Result<String, String> {
+    let payload: Value =
+        serde_json::from_slice(stdout).map_err(|error| format!("invalid gh response: {error}"))?;
+    let Some(branch_name) = payload
+        .get("head")
+        .and_then(|head| head.get("ref"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return Err("pull request head branch is missing".to_string());
+    };
+
+    Ok(branch_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ParsedGitHubPullRequest, parse_github_pull_request_url, parse_pull_request_head_branch_name,
+    };
+
+    #[test]
+    fn parse_github_pull_request_url_reads_owner_repo_and_number() {
+        assert_eq!(
+            parse_github_pull_request_url("https://github.com/acme/grove/pull/42/"),
+            Ok(ParsedGitHubPullRequest {
+                owner: "acme".to_string(),
+                repo: "grove".to_string(),
+                number: 42,
+            })
+        );
+        assert!(parse_github_pull_request_url("https://github.com/acme/grove/issues/42").is_err());
+        assert!(parse_github_pull_request_url("https://github.com/acme/grove/pull/abc").is_err());
+    }
+
+    #[test]
+    fn parse_pull_request_head_branch_name_requires_head_ref() {
+        assert_eq!(
+            parse_pull_request_head_branch_name(br#"{"head":{"ref":"feature/x"}}"#),
+            Ok("feature/x".to_string())
+        );
+        assert!(parse_pull_request_head_branch_name(br#"{"head":{}}"#).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::application::agent_runtime::{
     kill_workspace_session_commands, kill_workspace_session_commands_for_existing_sessions,
 };
 use crate::application::session_cleanup::list_tmux_sessions;
+use crate::domain::{AgentRegistry, MergeStrategy, UpdateStrategy};
 use crate::infrastructure::process::{execute_command, stderr_trimmed};
 use std::fs;
 use std::fs::OpenOptions;
@@ -9,6 +10,8 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+#[path = "workspace_lifecycle/conflict.rs"]
+mod conflict;
 #[path = "workspace_lifecycle/delete.rs"]
 mod delete;
 #[path = "workspace_lifecycle/git_ops.rs"]
@@ -22,9 +25,15 @@ mod requests;
 #[path = "workspace_lifecycle/update.rs"]
 mod update;
 
+pub use git_ops::IntegrationOperation;
+
 const GROVE_DIR: &str = ".grove";
 const GROVE_BASE_MARKER_FILE: &str = ".grove/base";
-const GROVE_GIT_EXCLUDE_ENTRIES: [&str; 1] = [".grove/"];
+/// Per-workspace launch settings, shared by the TUI, the CLI and recipes.
+pub const WORKSPACE_LAUNCH_PROMPT_FILE: &str = ".grove/prompt";
+pub const WORKSPACE_INIT_COMMAND_FILE: &str = ".grove/init_command";
+pub const WORKSPACE_PERMISSION_MODE_FILE: &str = ".grove/skip_permissions";
+pub(crate) const GROVE_GIT_EXCLUDE_ENTRIES: [&str; 2] = [".grove/", ".claude/settings.local.json"];
 const ENV_FILES_TO_COPY: [&str; 4] = [
     ".env",
     ".env.local",
@@ -91,6 +100,8 @@ pub struct MergeWorkspaceRequest {
     pub workspace_branch: String,
     pub workspace_path: PathBuf,
     pub base_branch: String,
+    pub strategy: MergeStrategy,
+    pub keep_conflicts: bool,
     pub cleanup_workspace: bool,
     pub cleanup_local_branch: bool,
 }
@@ -104,6 +115,19 @@ pub struct UpdateWorkspaceFromBaseRequest {
     pub workspace_branch: String,
     pub workspace_path: PathBuf,
     pub base_branch: String,
+    /// Ignored when updating the base workspace, which always pulls `--ff-only`.
+    pub strategy: UpdateStrategy,
+    pub keep_conflicts: bool,
+}
+
+/// A merge, squash or rebase that stopped on conflicts and was left in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceConflict {
+    pub operation: IntegrationOperation,
+    /// Worktree holding the conflicted index, the base worktree for merge and
+    /// squash, the workspace itself for rebase and update.
+    pub worktree_path: PathBuf,
+    pub files: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -113,6 +137,9 @@ pub struct WorkspaceMarkers {
 
 pub trait GitCommandRunner {
     fn run(&self, repo_root: &Path, args: &[String]) -> Result<(), String>;
+
+    /// Like [`GitCommandRunner::run`], returning what git printed.
+    fn stdout(&self, repo_root: &Path, args: &[String]) -> Result<Vec<u8>, String>;
 }
 
 pub trait SetupScriptRunner {
@@ -138,6 +165,8 @@ pub struct SetupScriptContext {
     pub main_worktree_path: PathBuf,
     pub workspace_path: PathBuf,
     pub worktree_branch: String,
+    /// Extra variables for the worktree, e.g. its allocated ports.
+    pub env: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -145,12 +174,18 @@ pub struct SetupCommandContext {
     pub main_worktree_path: PathBuf,
     pub workspace_path: PathBuf,
     pub worktree_branch: String,
+    /// Extra variables for the worktree, e.g. its allocated ports.
+    pub env: Vec<(String, String)>,
 }
 
 pub struct CommandGitRunner;
 
 impl GitCommandRunner for CommandGitRunner {
     fn run(&self, repo_root: &Path, args: &[String]) -> Result<(), String> {
+        self.stdout(repo_root, args).map(|_| ())
+    }
+
+    fn stdout(&self, repo_root: &Path, args: &[String]) -> Result<Vec<u8>, String> {
         let output = Command::new("git")
             .current_dir(repo_root)
             .args(args)
@@ -158,7 +193,7 @@ impl GitCommandRunner for CommandGitRunner {
             .map_err(|error| error.to_string())?;
 
         if output.status.success() {
-            return Ok(());
+            return Ok(output.stdout);
         }
 
         let stderr = stderr_trimmed(&output);
@@ -182,6 +217,7 @@ impl SetupScriptRunner for CommandSetupScriptRunner {
             .env("MAIN_WORKTREE", &context.main_worktree_path)
             .env("WORKTREE_BRANCH", &context.worktree_branch)
             .env("WORKTREE_PATH", &context.workspace_path)
+            .envs(context.env.iter().map(|(key, value)| (key, value)))
             .output()
             .map_err(|error| error.to_string())?;
 
@@ -212,6 +248,7 @@ impl SetupCommandRunner for CommandSetupCommandRunner {
             .env("MAIN_WORKTREE", &context.main_worktree_path)
             .env("WORKTREE_BRANCH", &context.worktree_branch)
             .env("WORKTREE_PATH", &context.workspace_path)
+            .envs(context.env.iter().map(|(key, value)| (key, value)))
             .output()
             .map_err(|error| error.to_string())?;
 
@@ -279,24 +316,34 @@ impl SessionTerminator for RuntimeSessionTerminator {
     }
 }
 
-pub fn delete_workspace(request: DeleteWorkspaceRequest) -> (Result<(), String>, Vec<String>) {
-    delete_workspace_with_terminator(request, &NoopSessionTerminator)
+/// Deletes the worktree; `agents` decodes the task manifest it is dropped from.
+pub fn delete_workspace(
+    request: DeleteWorkspaceRequest,
+    agents: &AgentRegistry,
+) -> (Result<(), String>, Vec<String>) {
+    delete_workspace_with_terminator(request, agents, &NoopSessionTerminator)
 }
 
 pub fn delete_workspace_with_terminator(
     request: DeleteWorkspaceRequest,
+    agents: &AgentRegistry,
     session_terminator: &impl SessionTerminator,
 ) -> (Result<(), String>, Vec<String>) {
-    delete_workspace_with_session_stopper(request, |task_slug, project_name, workspace_name| {
-        session_terminator.stop_workspace_sessions(task_slug, project_name, workspace_name);
-    })
+    delete_workspace_with_session_stopper(
+        request,
+        agents,
+        |task_slug, project_name, workspace_name| {
+            session_terminator.stop_workspace_sessions(task_slug, project_name, workspace_name);
+        },
+    )
 }
 
 pub(crate) fn delete_workspace_with_session_stopper(
     request: DeleteWorkspaceRequest,
+    agents: &AgentRegistry,
     stop_sessions: impl Fn(Option<&str>, Option<&str>, &str),
 ) -> (Result<(), String>, Vec<String>) {
-    delete::delete_workspace_with_session_stopper(request, stop_sessions)
+    delete::delete_workspace_with_session_stopper(request, agents, stop_sessions)
 }
 
 pub fn merge_workspace(request: MergeWorkspaceRequest) -> (Result<(), String>, Vec<String>) {
@@ -344,6 +391,40 @@ pub(crate) fn update_workspace_from_base_with_session_stopper(
     update::update_workspace_from_base_with_session_stopper(request, stop_sessions)
 }
 
+/// Reports a merge, squash or rebase left mid-way in `worktree_path`.
+pub fn detect_workspace_conflict(worktree_path: &Path) -> Option<WorkspaceConflict> {
+    conflict::detect_workspace_conflict(worktree_path)
+}
+
+/// Stages the resolved files and finishes the operation left in progress,
+/// with the commit message and cleanup of the merge that stopped there.
+pub fn continue_workspace_conflict(
+    worktree_path: &Path,
+) -> (Result<IntegrationOperation, String>, Vec<String>) {
+    continue_workspace_conflict_with_terminator(worktree_path, &NoopSessionTerminator)
+}
+
+pub fn continue_workspace_conflict_with_terminator(
+    worktree_path: &Path,
+    session_terminator: &impl SessionTerminator,
+) -> (Result<IntegrationOperation, String>, Vec<String>) {
+    conflict::continue_workspace_conflict(
+        worktree_path,
+        |task_slug, project_name, workspace_name| {
+            session_terminator.stop_workspace_sessions(task_slug, project_name, workspace_name);
+        },
+    )
+}
+
+pub fn abort_workspace_conflict(worktree_path: &Path) -> Result<IntegrationOperation, String> {
+    conflict::abort_workspace_conflict(worktree_path)
+}
+
+/// Prompt asking an agent to resolve `conflict` without finishing it.
+pub fn conflict_resolution_prompt(conflict: &WorkspaceConflict) -> String {
+    conflict::conflict_resolution_prompt(conflict)
+}
+
 pub(crate) fn ensure_grove_git_exclude_entries(
     repo_root: &Path,
 ) -> Result<(), WorkspaceLifecycleError> {
@@ -467,13 +548,16 @@ pub fn write_workspace_base_marker(
 #[cfg(test)]
 mod tests {
     use super::{
-        DeleteWorkspaceRequest, MergeWorkspaceRequest, UpdateWorkspaceFromBaseRequest,
-        WorkspaceLifecycleError, WorkspaceMarkerError, copy_env_files, delete_workspace,
+        DeleteWorkspaceRequest, IntegrationOperation, MergeWorkspaceRequest,
+        UpdateWorkspaceFromBaseRequest, WorkspaceLifecycleError, WorkspaceMarkerError,
+        abort_workspace_conflict, conflict_resolution_prompt, continue_workspace_conflict,
+        copy_env_files, delete_workspace, detect_workspace_conflict,
         ensure_grove_git_exclude_entries, merge_workspace, merge_workspace_with_session_stopper,
         read_workspace_markers, update_workspace_from_base,
         update_workspace_from_base_with_session_stopper, workspace_lifecycle_error_message,
         write_workspace_base_marker,
     };
+    use crate::domain::{AgentRegistry, MergeStrategy, UpdateStrategy};
     use std::cell::RefCell;
     use std::fs;
     use std::path::{Path, PathBuf};
@@ -674,7 +758,7 @@ mod tests {
             kill_tmux_sessions: false,
         };
 
-        let (result, warnings) = delete_workspace(request);
+        let (result, warnings) = delete_workspace(request, &AgentRegistry::default());
         assert_eq!(result, Ok(()));
         assert!(warnings.is_empty());
     }
@@ -699,7 +783,7 @@ mod tests {
             kill_tmux_sessions: false,
         };
 
-        let (result, warnings) = delete_workspace(request);
+        let (result, warnings) = delete_workspace(request, &AgentRegistry::default());
         assert_eq!(result, Ok(()));
         assert_eq!(warnings.len(), 1);
         assert!(
@@ -743,7 +827,7 @@ mod tests {
             delete_local_branch: true,
             kill_tmux_sessions: false,
         };
-        let (result, warnings) = delete_workspace(request);
+        let (result, warnings) = delete_workspace(request, &AgentRegistry::default());
         std::env::set_current_dir(&original_cwd).expect("should restore cwd");
 
         assert_eq!(result, Ok(()));
@@ -822,14 +906,14 @@ mod tests {
             kill_tmux_sessions: false,
         };
 
-        let (result, warnings) = delete_workspace(request);
+        let (result, warnings) = delete_workspace(request, &AgentRegistry::default());
         assert_eq!(result, Ok(()));
         assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
 
         let updated_raw = fs::read_to_string(manifest_dir.join("task.toml"))
             .expect("manifest should still exist");
-        let updated_task =
-            decode_task_manifest(&updated_raw).expect("updated manifest should decode");
+        let updated_task = decode_task_manifest(&updated_raw, &AgentRegistry::default())
+            .expect("updated manifest should decode");
         assert_eq!(updated_task.worktrees.len(), 1);
         assert_eq!(updated_task.worktrees[0].repository_name, "repo-b");
     }
@@ -867,6 +951,8 @@ mod tests {
             workspace_branch: "feature-merge".to_string(),
             workspace_path: workspace_path.clone(),
             base_branch: base_branch.clone(),
+            strategy: MergeStrategy::Merge,
+            keep_conflicts: false,
             cleanup_workspace: true,
             cleanup_local_branch: true,
         };
@@ -921,6 +1007,8 @@ mod tests {
             workspace_branch: "feature-dirty".to_string(),
             workspace_path: workspace_path.clone(),
             base_branch: base_branch.clone(),
+            strategy: MergeStrategy::Merge,
+            keep_conflicts: false,
             cleanup_workspace: false,
             cleanup_local_branch: false,
         };
@@ -946,6 +1034,8 @@ mod tests {
             workspace_branch: "feature-dirty".to_string(),
             workspace_path: workspace_path.clone(),
             base_branch,
+            strategy: MergeStrategy::Merge,
+            keep_conflicts: false,
             cleanup_workspace: false,
             cleanup_local_branch: false,
         };
@@ -990,6 +1080,8 @@ mod tests {
             workspace_branch: "feature-no-stop".to_string(),
             workspace_path,
             base_branch,
+            strategy: MergeStrategy::Merge,
+            keep_conflicts: false,
             cleanup_workspace: true,
             cleanup_local_branch: false,
         };
@@ -1049,6 +1141,8 @@ mod tests {
             workspace_branch: "feature-cleanup-stop".to_string(),
             workspace_path: workspace_path.clone(),
             base_branch,
+            strategy: MergeStrategy::Merge,
+            keep_conflicts: false,
             cleanup_workspace: true,
             cleanup_local_branch: false,
         };
@@ -1109,6 +1203,8 @@ mod tests {
             workspace_branch: "feature-update-no-stop".to_string(),
             workspace_path,
             base_branch,
+            strategy: UpdateStrategy::Merge,
+            keep_conflicts: false,
         };
 
         let (result, warnings) = update_workspace_from_base_with_session_stopper(
@@ -1167,6 +1263,8 @@ mod tests {
             workspace_branch: "feature-sync".to_string(),
             workspace_path: workspace_path.clone(),
             base_branch,
+            strategy: UpdateStrategy::Merge,
+            keep_conflicts: false,
         };
 
         let (result, warnings) = update_workspace_from_base(request);
@@ -1208,6 +1306,8 @@ mod tests {
             workspace_branch: "feature-sync-dirty".to_string(),
             workspace_path,
             base_branch,
+            strategy: UpdateStrategy::Merge,
+            keep_conflicts: false,
         };
 
         let (result, warnings) = update_workspace_from_base(request);
@@ -1266,6 +1366,8 @@ mod tests {
             workspace_branch: base_branch.clone(),
             workspace_path: repo_root.clone(),
             base_branch: base_branch.clone(),
+            strategy: UpdateStrategy::Merge,
+            keep_conflicts: false,
         };
 
         let (result, warnings) = update_workspace_from_base(request);
@@ -1305,6 +1407,8 @@ mod tests {
             workspace_branch: "feature-sync".to_string(),
             workspace_path,
             base_branch: "feature-sync".to_string(),
+            strategy: UpdateStrategy::Merge,
+            keep_conflicts: false,
         };
 
         let (result, warnings) = update_workspace_from_base(request);
@@ -1315,6 +1419,464 @@ mod tests {
         );
     }
 
+    fn feature_worktree_with_commits(
+        repo_root: &Path,
+        workspace_path: &Path,
+        branch: &str,
+        commits: &[(&str, &str)],
+    ) {
+        run_git(
+            repo_root,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                branch,
+                workspace_path.to_string_lossy().as_ref(),
+                "HEAD",
+            ],
+        );
+        for (file, message) in commits {
+            fs::write(workspace_path.join(file), format!("{message}\n"))
+                .expect("feature file should be writable");
+            run_git(workspace_path, &["add", file]);
+            run_git(workspace_path, &["commit", "-m", message]);
+        }
+    }
+
+    fn commit_on_base(repo_root: &Path, file: &str, content: &str) {
+        fs::write(repo_root.join(file), content).expect("base file should be writable");
+        run_git(repo_root, &["add", file]);
+        run_git(repo_root, &["commit", "-m", "base change"]);
+    }
+
+    fn merge_request(
+        repo_root: &Path,
+        workspace_path: &Path,
+        branch: &str,
+        base_branch: &str,
+        strategy: MergeStrategy,
+    ) -> MergeWorkspaceRequest {
+        MergeWorkspaceRequest {
+            task_slug: None,
+            project_name: None,
+            project_path: Some(repo_root.to_path_buf()),
+            workspace_name: branch.to_string(),
+            workspace_branch: branch.to_string(),
+            workspace_path: workspace_path.to_path_buf(),
+            base_branch: base_branch.to_string(),
+            strategy,
+            keep_conflicts: false,
+            cleanup_workspace: false,
+            cleanup_local_branch: false,
+        }
+    }
+
+    #[test]
+    fn merge_workspace_squash_lands_single_commit_with_generated_message() {
+        let temp = TestDir::new("merge-squash");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-squash");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-squash",
+            &[("a.txt", "add a"), ("b.txt", "add b")],
+        );
+
+        let (result, warnings) = merge_workspace(merge_request(
+            &repo_root,
+            &workspace_path,
+            "feature-squash",
+            base_branch.as_str(),
+            MergeStrategy::Squash,
+        ));
+
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+        assert!(repo_root.join("a.txt").exists());
+        assert!(repo_root.join("b.txt").exists());
+        assert_eq!(
+            git_stdout(&repo_root, &["rev-list", "--count", "HEAD"]).trim(),
+            "2"
+        );
+        assert_eq!(
+            git_stdout(&repo_root, &["log", "-1", "--format=%B"]).trim(),
+            format!("Squash merge branch 'feature-squash' into {base_branch}\n\n* add a\n* add b")
+        );
+    }
+
+    #[test]
+    fn merge_workspace_rebase_keeps_base_history_linear() {
+        let temp = TestDir::new("merge-rebase");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-rebase");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-rebase",
+            &[("feature.txt", "add feature")],
+        );
+        commit_on_base(&repo_root, "base.txt", "base\n");
+
+        let (result, warnings) = merge_workspace(merge_request(
+            &repo_root,
+            &workspace_path,
+            "feature-rebase",
+            base_branch.as_str(),
+            MergeStrategy::Rebase,
+        ));
+
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+        assert!(repo_root.join("feature.txt").exists());
+        assert!(
+            git_stdout(&repo_root, &["rev-list", "--merges", "HEAD"])
+                .trim()
+                .is_empty(),
+            "rebase strategy should not create merge commits"
+        );
+        assert_eq!(
+            git_stdout(&repo_root, &["rev-parse", "HEAD"]),
+            git_stdout(&repo_root, &["rev-parse", "feature-rebase"])
+        );
+    }
+
+    #[test]
+    fn merge_workspace_conflict_aborts_and_names_conflicted_files() {
+        let temp = TestDir::new("merge-conflict");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-conflict");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-conflict",
+            &[("README.md", "from feature")],
+        );
+        commit_on_base(&repo_root, "README.md", "from base\n");
+
+        for strategy in [MergeStrategy::Merge, MergeStrategy::Squash] {
+            let (result, _warnings) = merge_workspace(merge_request(
+                &repo_root,
+                &workspace_path,
+                "feature-conflict",
+                base_branch.as_str(),
+                strategy,
+            ));
+
+            let error = result.expect_err("conflicting merge should fail");
+            assert_eq!(
+                error,
+                format!(
+                    "{} conflict in README.md; {} aborted, base worktree unchanged",
+                    strategy.label(),
+                    strategy.label()
+                )
+            );
+            assert!(
+                git_stdout(&repo_root, &["status", "--porcelain"])
+                    .trim()
+                    .is_empty(),
+                "base worktree should be clean after abort"
+            );
+        }
+    }
+
+    #[test]
+    fn update_workspace_from_base_rebase_replays_workspace_commits() {
+        let temp = TestDir::new("update-rebase");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-update-rebase");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-update-rebase",
+            &[("feature.txt", "add feature")],
+        );
+        commit_on_base(&repo_root, "base.txt", "base\n");
+
+        let (result, warnings) = update_workspace_from_base(UpdateWorkspaceFromBaseRequest {
+            task_slug: None,
+            project_name: None,
+            project_path: Some(repo_root.clone()),
+            workspace_name: "feature-update-rebase".to_string(),
+            workspace_branch: "feature-update-rebase".to_string(),
+            workspace_path: workspace_path.clone(),
+            base_branch: base_branch.clone(),
+            strategy: UpdateStrategy::Rebase,
+            keep_conflicts: false,
+        });
+
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty());
+        assert!(workspace_path.join("base.txt").exists());
+        assert!(
+            git_stdout(&workspace_path, &["rev-list", "--merges", "HEAD"])
+                .trim()
+                .is_empty(),
+            "rebase update should not create merge commits"
+        );
+    }
+
+    #[test]
+    fn update_workspace_from_base_rebase_conflict_restores_workspace_branch() {
+        let temp = TestDir::new("update-rebase-conflict");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-rebase-conflict");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-rebase-conflict",
+            &[("README.md", "from feature")],
+        );
+        commit_on_base(&repo_root, "README.md", "from base\n");
+        let head_before = git_stdout(&workspace_path, &["rev-parse", "HEAD"]);
+
+        let (result, _warnings) = update_workspace_from_base(UpdateWorkspaceFromBaseRequest {
+            task_slug: None,
+            project_name: None,
+            project_path: Some(repo_root),
+            workspace_name: "feature-rebase-conflict".to_string(),
+            workspace_branch: "feature-rebase-conflict".to_string(),
+            workspace_path: workspace_path.clone(),
+            base_branch,
+            strategy: UpdateStrategy::Rebase,
+            keep_conflicts: false,
+        });
+
+        assert_eq!(
+            result,
+            Err(
+                "rebase conflict in README.md; rebase aborted, workspace branch unchanged"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            git_stdout(&workspace_path, &["rev-parse", "HEAD"]),
+            head_before
+        );
+        assert_eq!(
+            git_stdout(&workspace_path, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(),
+            "feature-rebase-conflict"
+        );
+    }
+
+    #[test]
+    fn merge_workspace_keep_conflicts_leaves_conflict_until_continued() {
+        for strategy in [MergeStrategy::Merge, MergeStrategy::Squash] {
+            let temp = TestDir::new(format!("merge-keep-{}", strategy.label()).as_str());
+            let repo_root = temp.path.join("grove");
+            fs::create_dir_all(&repo_root).expect("repo dir should exist");
+            init_git_repo(&repo_root);
+            let base_branch = current_branch(&repo_root);
+            let workspace_path = temp.path.join("grove-feature-keep");
+            feature_worktree_with_commits(
+                &repo_root,
+                &workspace_path,
+                "feature-keep",
+                &[("README.md", "from feature")],
+            );
+            commit_on_base(&repo_root, "README.md", "from base\n");
+
+            let mut request = merge_request(
+                &repo_root,
+                &workspace_path,
+                "feature-keep",
+                base_branch.as_str(),
+                strategy,
+            );
+            request.keep_conflicts = true;
+            request.cleanup_workspace = true;
+            request.cleanup_local_branch = true;
+            let (result, warnings) = merge_workspace(request);
+            assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+
+            let error = result.expect_err("conflicting merge should fail");
+            assert!(
+                error.starts_with(
+                    format!(
+                        "{} conflict in README.md; left in place at ",
+                        strategy.label()
+                    )
+                    .as_str()
+                ),
+                "unexpected error: {error}"
+            );
+            let conflict =
+                detect_workspace_conflict(&repo_root).expect("conflict should be left in place");
+            assert_eq!(conflict.operation.name(), strategy.label());
+            assert_eq!(conflict.files, vec!["README.md".to_string()]);
+            assert_eq!(detect_workspace_conflict(&workspace_path), None);
+
+            assert_eq!(
+                continue_workspace_conflict(&repo_root).0,
+                Err("conflict markers remain in README.md".to_string())
+            );
+            assert!(workspace_path.exists());
+            fs::write(repo_root.join("README.md"), "resolved\n").expect("resolution should write");
+            assert_eq!(
+                continue_workspace_conflict(&repo_root),
+                (Ok(conflict.operation), Vec::new())
+            );
+            assert_eq!(detect_workspace_conflict(&repo_root), None);
+            assert_eq!(
+                git_stdout(&repo_root, &["show", "HEAD:README.md"]),
+                "resolved\n"
+            );
+            if strategy == MergeStrategy::Squash {
+                assert_eq!(
+                    git_stdout(&repo_root, &["log", "-1", "--format=%B"]).trim(),
+                    format!(
+                        "Squash merge branch 'feature-keep' into {base_branch}\n\n* from feature"
+                    )
+                );
+            }
+            assert!(!workspace_path.exists());
+            assert!(
+                git_stdout(&repo_root, &["branch", "--list", "feature-keep"])
+                    .trim()
+                    .is_empty()
+            );
+            assert!(
+                git_stdout(&repo_root, &["status", "--porcelain"])
+                    .trim()
+                    .is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn merge_workspace_rebase_keep_conflicts_fast_forwards_base_when_continued() {
+        let temp = TestDir::new("merge-keep-rebase");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-keep-rebase");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-keep-rebase",
+            &[("README.md", "from feature")],
+        );
+        commit_on_base(&repo_root, "README.md", "from base\n");
+        let base_head_before = git_stdout(&repo_root, &["rev-parse", "HEAD"]);
+
+        let mut request = merge_request(
+            &repo_root,
+            &workspace_path,
+            "feature-keep-rebase",
+            base_branch.as_str(),
+            MergeStrategy::Rebase,
+        );
+        request.keep_conflicts = true;
+        request.cleanup_workspace = true;
+        request.cleanup_local_branch = true;
+        let (result, warnings) = merge_workspace(request);
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+        assert!(result.is_err());
+        assert_eq!(detect_workspace_conflict(&repo_root), None);
+        let conflict =
+            detect_workspace_conflict(&workspace_path).expect("rebase should be left in place");
+        assert_eq!(conflict.operation, IntegrationOperation::Rebase);
+
+        fs::write(workspace_path.join("README.md"), "resolved\n").expect("resolution should write");
+        assert_eq!(
+            continue_workspace_conflict(&workspace_path),
+            (Ok(IntegrationOperation::Rebase), Vec::new())
+        );
+
+        assert_ne!(
+            git_stdout(&repo_root, &["rev-parse", "HEAD"]),
+            base_head_before
+        );
+        assert_eq!(
+            git_stdout(&repo_root, &["show", "HEAD:README.md"]),
+            "resolved\n"
+        );
+        assert_eq!(
+            git_stdout(&repo_root, &["log", "-1", "--format=%s"]).trim(),
+            "from feature"
+        );
+        assert!(!workspace_path.exists());
+        assert!(
+            git_stdout(&repo_root, &["branch", "--list", "feature-keep-rebase"])
+                .trim()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn update_workspace_from_base_keep_conflicts_can_be_aborted() {
+        let temp = TestDir::new("update-keep-abort");
+        let repo_root = temp.path.join("grove");
+        fs::create_dir_all(&repo_root).expect("repo dir should exist");
+        init_git_repo(&repo_root);
+        let base_branch = current_branch(&repo_root);
+        let workspace_path = temp.path.join("grove-feature-keep-abort");
+        feature_worktree_with_commits(
+            &repo_root,
+            &workspace_path,
+            "feature-keep-abort",
+            &[("README.md", "from feature")],
+        );
+        commit_on_base(&repo_root, "README.md", "from base\n");
+        let head_before = git_stdout(&workspace_path, &["rev-parse", "HEAD"]);
+
+        let (result, _warnings) = update_workspace_from_base(UpdateWorkspaceFromBaseRequest {
+            task_slug: None,
+            project_name: None,
+            project_path: Some(repo_root),
+            workspace_name: "feature-keep-abort".to_string(),
+            workspace_branch: "feature-keep-abort".to_string(),
+            workspace_path: workspace_path.clone(),
+            base_branch,
+            strategy: UpdateStrategy::Rebase,
+            keep_conflicts: true,
+        });
+
+        assert!(result.is_err());
+        let conflict =
+            detect_workspace_conflict(&workspace_path).expect("rebase should be left in place");
+        assert_eq!(conflict.operation, IntegrationOperation::Rebase);
+        assert_eq!(conflict.files, vec!["README.md".to_string()]);
+        let prompt = conflict_resolution_prompt(&conflict);
+        assert!(prompt.contains("A git rebase stopped on conflicts in "));
+        assert!(prompt.contains("\n- README.md\n"));
+
+        assert_eq!(
+            abort_workspace_conflict(&workspace_path),
+            Ok(IntegrationOperation::Rebase)
+        );
+        assert_eq!(detect_workspace_conflict(&workspace_path), None);
+        assert_eq!(
+            git_stdout(&workspace_path, &["rev-parse", "HEAD"]),
+            head_before
+        );
+        assert_eq!(
+            abort_workspace_conflict(&workspace_path),
+            Err(format!(
+                "no merge, squash or rebase in progress in {}",
+                workspace_path.display()
+            ))
+        );
+    }
+
     fn init_git_repo(repo_root: &Path) {
         run_git(repo_root, &["init"]);
         run_git(
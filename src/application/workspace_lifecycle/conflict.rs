@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceConflict;
+use super::git_ops::{
+    IntegrationOperation, conflicted_files, has_staged_changes, run_git_command,
+    run_git_command_stdout,
+};
+use super::merge::{BaseFastForward, MergeCleanup};
+
+/// Per-worktree git path holding the [`PendingMerge`] of a conflicted merge.
+const PENDING_MERGE_GIT_PATH: &str = "grove-pending-merge.json";
+
+/// What a merge that stopped on conflicts does once they are resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PendingMerge {
+    /// HEAD the conflict started from; a record from an older conflict is
+    /// ignored.
+    head: String,
+    commit_message: Option<String>,
+    /// Set for a rebase merge, whose conflict is in the workspace worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fast_forward: Option<BaseFastForward>,
+    cleanup: MergeCleanup,
+}
+
+pub(super) fn detect_workspace_conflict(worktree_path: &Path) -> Option<WorkspaceConflict> {
+    let git_dir = worktree_git_dir(worktree_path)?;
+    let operation =
+        if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+            IntegrationOperation::Rebase
+        } else if git_dir.join("MERGE_HEAD").exists() {
+            IntegrationOperation::Merge
+        } else if git_dir.join("SQUASH_MSG").exists() {
+            IntegrationOperation::Squash
+        } else {
+            return None;
+        };
+
+    let files = conflicted_files(worktree_path);
+    // SQUASH_MSG outlives an aborted squash, so only a dirty index counts.
+    if operation == IntegrationOperation::Squash
+        && files.is_empty()
+        && !has_staged_changes(worktree_path).unwrap_or(false)
+    {
+        return None;
+    }
+
+    Some(WorkspaceConflict {
+        operation,
+        worktree_path: worktree_path.to_path_buf(),
+        files,
+    })
+}
+
+pub(super) fn continue_workspace_conflict(
+    worktree_path: &Path,
+    stop_sessions: impl Fn(Option<&str>, Option<&str>, &str),
+) -> (Result<IntegrationOperation, String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let pending = load_pending_merge(worktree_path);
+    let commit_message = pending
+        .as_ref()
+        .and_then(|pending| pending.commit_message.as_deref());
+    let operation = match finish_workspace_conflict(worktree_path, commit_message) {
+        Ok(operation) => operation,
+        Err(error) => {
+            // A rebase that stops on its next commit has moved HEAD.
+            if let Some(pending) = pending
+                && detect_workspace_conflict(worktree_path).is_some()
+                && let Err(save_error) = save_pending_merge(
+                    worktree_path,
+                    pending.commit_message,
+                    pending.fast_forward,
+                    pending.cleanup,
+                )
+            {
+                warnings.push(format!("conflict state: {save_error}"));
+            }
+            return (Err(error), warnings);
+        }
+    };
+
+    remove_pending_merge(worktree_path);
+    let Some(pending) = pending else {
+        return (Ok(operation), warnings);
+    };
+    let cleanup_root = match pending.fast_forward.as_ref() {
+        Some(fast_forward) => {
+            if let Err(error) = fast_forward.run() {
+                return (Err(error), warnings);
+            }
+            fast_forward.repo_root()
+        }
+        None => worktree_path,
+    };
+    pending
+        .cleanup
+        .run(cleanup_root, stop_sessions, &mut warnings);
+    (Ok(operation), warnings)
+}
+
+fn finish_workspace_conflict(
+    worktree_path: &Path,
+    commit_message: Option<&str>,
+) -> Result<IntegrationOperation, String> {
+    let conflict =
+        detect_workspace_conflict(worktree_path).ok_or_else(|| no_conflict_error(worktree_path))?;
+    let name = conflict.operation.name();
+
+    let unresolved = files_with_conflict_markers(worktree_path, conflict.files.as_slice());
+    if !unresolved.is_empty() {
+        return Err(format!(
+            "conflict markers remain in {}",
+            unresolved.join(", ")
+        ));
+    }
+
+    if !conflict.files.is_empty() {
+        let mut args = vec!["add".to_string(), "-A".to_string(), "--".to_string()];
+        args.extend(conflict.files.iter().cloned());
+        run_git_command(worktree_path, &args)
+            .map_err(|error| format!("git add failed: {error}"))?;
+    }
+
+    let args = match (conflict.operation, commit_message) {
+        (IntegrationOperation::Squash, Some(message)) => {
+            vec!["commit".to_string(), "-m".to_string(), message.to_string()]
+        }
+        (IntegrationOperation::Merge | IntegrationOperation::Squash, _) => {
+            vec!["commit".to_string(), "--no-edit".to_string()]
+        }
+        (IntegrationOperation::Rebase, _) => vec![
+            "-c".to_string(),
+            "core.editor=true".to_string(),
+            "rebase".to_string(),
+            "--continue".to_string(),
+        ],
+    };
+    run_git_command(worktree_path, &args).map_err(|error| {
+        let remaining = conflicted_files(worktree_path);
+        if remaining.is_empty() {
+            format!(
+                "`{}` failed: {error}",
+                conflict.operation.continue_command()
+            )
+        } else {
+            format!(
+                "{name} stopped on new conflicts in {}",
+                remaining.join(", ")
+            )
+        }
+    })?;
+
+    Ok(conflict.operation)
+}
+
+pub(super) fn abort_workspace_conflict(
+    worktree_path: &Path,
+) -> Result<IntegrationOperation, String> {
+    let conflict =
+        detect_workspace_conflict(worktree_path).ok_or_else(|| no_conflict_error(worktree_path))?;
+    let [command, flag] = conflict.operation.abort_args();
+    run_git_command(worktree_path, &[command.to_string(), flag.to_string()])
+        .map_err(|error| format!("git {command} {flag} failed: {error}"))?;
+    remove_pending_merge(worktree_path);
+    Ok(conflict.operation)
+}
+
+pub(super) fn save_pending_merge(
+    worktree_path: &Path,
+    commit_message: Option<String>,
+    fast_forward: Option<BaseFastForward>,
+    cleanup: MergeCleanup,
+) -> Result<(), String> {
+    let head = worktree_head(worktree_path)?;
+    let path = pending_merge_path(worktree_path)?;
+    let pending = PendingMerge {
+        head,
+        commit_message,
+        fast_forward,
+        cleanup,
+    };
+    let content = serde_json::to_string_pretty(&pending).map_err(|error| error.to_string())?;
+    fs::write(&path, content).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+fn load_pending_merge(worktree_path: &Path) -> Option<PendingMerge> {
+    let content = fs::read_to_string(pending_merge_path(worktree_path).ok()?).ok()?;
+    let pending = serde_json::from_str::<PendingMerge>(content.as_str()).ok()?;
+    let head = worktree_head(worktree_path).ok()?;
+    (pending.head == head).then_some(pending)
+}
+
+fn remove_pending_merge(worktree_path: &Path) {
+    if let Ok(path) = pending_merge_path(worktree_path) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn pending_merge_path(worktree_path: &Path) -> Result<PathBuf, String> {
+    let stdout = run_git_command_stdout(
+        worktree_path,
+        &[
+            "rev-parse".to_string(),
+            "--git-path".to_string(),
+            PENDING_MERGE_GIT_PATH.to_string(),
+        ],
+    )?;
+    Ok(worktree_path.join(stdout.trim()))
+}
+
+fn worktree_head(worktree_path: &Path) -> Result<String, String> {
+    run_git_command_stdout(
+        worktree_path,
+        &["rev-parse".to_string(), "HEAD".to_string()],
+    )
+    .map(|stdout| stdout.trim().to_string())
+}
+
+pub(super) fn conflict_resolution_prompt(conflict: &WorkspaceConflict) -> String {
+    let name = conflict.operation.name();
+    let mut prompt = format!(
+        "A git {name} stopped on conflicts in {}.\n",
+        conflict.worktree_path.display()
+    );
+    if conflict.files.is_empty() {
+        prompt.push_str("\nRun `git status` there to see what is left to resolve.\n");
+    } else {
+        prompt.push_str("\nConflicted files:\n");
+        for file in &conflict.files {
+            prompt.push_str(format!("- {file}\n").as_str());
+        }
+    }
+    prompt.push_str(
+        "\nResolve every conflict by editing the files so they keep the intent of both sides, \
+         and remove all conflict markers. Do not commit, continue or abort the ",
+    );
+    prompt.push_str(name);
+    prompt.push_str(
+        "; it is finished from grove once you are done. Reply with a short summary of how each \
+         file was resolved.",
+    );
+    prompt
+}
+
+fn worktree_git_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let stdout = run_git_command_stdout(
+        worktree_path,
+        &["rev-parse".to_string(), "--git-dir".to_string()],
+    )
+    .ok()?;
+    let git_dir = stdout.trim();
+    if git_dir.is_empty() {
+        return None;
+    }
+    Some(worktree_path.join(git_dir))
+}
+
+fn files_with_conflict_markers(worktree_path: &Path, files: &[String]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|file| {
+            // Deleted-side conflicts have nothing on disk to inspect.
+            fs::read_to_string(worktree_path.join(file)).is_ok_and(|content| {
+                content
+                    .lines()
+                    .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+fn no_conflict_error(worktree_path: &Path) -> String {
+    format!(
+        "no merge, squash or rebase in progress in {}",
+        worktree_path.display()
+    )
+}
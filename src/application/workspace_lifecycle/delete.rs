@@ -1,7 +1,9 @@
 use super::DeleteWorkspaceRequest;
+use crate::domain::AgentRegistry;
 
 pub(super) fn delete_workspace_with_session_stopper(
     request: DeleteWorkspaceRequest,
+    agents: &AgentRegistry,
     stop_sessions: impl Fn(Option<&str>, Option<&str>, &str),
 ) -> (Result<(), String>, Vec<String>) {
     let mut warnings = Vec::new();
@@ -20,6 +22,13 @@ pub(super) fn delete_workspace_with_session_stopper(
         }
     };
 
+    // Keeps hooks out of the user's settings if the worktree outlives the delete.
+    if let Err(error) =
+        crate::application::agent_runtime::uninstall_status_hooks(&request.workspace_path)
+    {
+        warnings.push(format!("status hooks: {error}"));
+    }
+
     if let Err(error) = super::git_ops::run_delete_worktree_git(
         &repo_root,
         &request.workspace_path,
@@ -36,7 +45,7 @@ pub(super) fn delete_workspace_with_session_stopper(
 
     if let Some(task_root) = &request.task_root
         && let Err(error) =
-            remove_worktree_from_manifest(task_root.as_path(), &request.workspace_path)
+            remove_worktree_from_manifest(task_root.as_path(), &request.workspace_path, agents)
     {
         warnings.push(format!("task manifest update: {error}"));
     }
@@ -47,6 +56,7 @@ pub(super) fn delete_workspace_with_session_stopper(
 fn remove_worktree_from_manifest(
     task_root: &std::path::Path,
     deleted_workspace_path: &std::path::Path,
+    agents: &AgentRegistry,
 ) -> Result<(), String> {
     use crate::infrastructure::task_manifest::{decode_task_manifest, encode_task_manifest};
 
@@ -54,7 +64,7 @@ fn remove_worktree_from_manifest(
     let raw = std::fs::read_to_string(&manifest_path)
         .map_err(|error| format!("read manifest: {error}"))?;
     let mut task =
-        decode_task_manifest(&raw).map_err(|error| format!("decode manifest: {error}"))?;
+        decode_task_manifest(&raw, agents).map_err(|error| format!("decode manifest: {error}"))?;
 
     let original_count = task.worktrees.len();
     task.worktrees
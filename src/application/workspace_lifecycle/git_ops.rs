@@ -99,3 +99,160 @@ pub(super) fn ensure_git_worktree_clean(worktree_path: &Path) -> Result<(), Stri
 
     Err("commit, stash, or discard changes first".to_string())
 }
+
+pub(super) fn run_git_command_stdout(repo_root: &Path, args: &[String]) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(args)
+        .output()
+        .map_err(|error| format!("git {}: {error}", args.join(" ")))?;
+    if !output.status.success() {
+        let stderr = stderr_trimmed(&output);
+        if stderr.is_empty() {
+            return Err(format!(
+                "git {}: exit status {}",
+                args.join(" "),
+                output.status
+            ));
+        }
+        return Err(format!("git {}: {stderr}", args.join(" ")));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub(super) fn has_staged_changes(repo_root: &Path) -> Result<bool, String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["diff", "--cached", "--quiet"])
+        .output()
+        .map_err(|error| format!("git diff --cached --quiet: {error}"))?;
+    match output.status.code() {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        _ => Err(format!(
+            "git diff --cached --quiet: {}",
+            stderr_trimmed(&output)
+        )),
+    }
+}
+
+pub(super) fn conflicted_files(worktree_path: &Path) -> Vec<String> {
+    run_git_command_stdout(
+        worktree_path,
+        &[
+            "diff".to_string(),
+            "--name-only".to_string(),
+            "--diff-filter=U".to_string(),
+        ],
+    )
+    .map(|stdout| {
+        stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+pub(super) fn git_path_exists(worktree_path: &Path, name: &str) -> bool {
+    run_git_command_stdout(
+        worktree_path,
+        &[
+            "rev-parse".to_string(),
+            "--git-path".to_string(),
+            name.to_string(),
+        ],
+    )
+    .is_ok_and(|stdout| worktree_path.join(stdout.trim()).exists())
+}
+
+/// A git operation that can stop halfway and leave a worktree mid-merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationOperation {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl IntegrationOperation {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+        }
+    }
+
+    pub(super) const fn abort_args(self) -> [&'static str; 2] {
+        match self {
+            Self::Merge => ["merge", "--abort"],
+            Self::Squash => ["reset", "--merge"],
+            Self::Rebase => ["rebase", "--abort"],
+        }
+    }
+
+    pub(super) const fn continue_command(self) -> &'static str {
+        match self {
+            Self::Merge | Self::Squash => "git commit",
+            Self::Rebase => "git rebase --continue",
+        }
+    }
+
+    pub(super) fn in_progress(self, worktree_path: &Path) -> bool {
+        match self {
+            Self::Merge => git_path_exists(worktree_path, "MERGE_HEAD"),
+            Self::Squash => false,
+            Self::Rebase => {
+                git_path_exists(worktree_path, "rebase-merge")
+                    || git_path_exists(worktree_path, "rebase-apply")
+            }
+        }
+    }
+}
+
+/// Rolls back a failed merge, squash or rebase so the worktree is usable
+/// again, and describes what happened. When the rollback itself fails the
+/// message says how to finish or abort by hand. With `keep_conflicts` a
+/// conflicted operation is left in place for resolution instead.
+pub(super) fn recover_failed_integration(
+    worktree_path: &Path,
+    operation: IntegrationOperation,
+    location: &str,
+    error: &str,
+    keep_conflicts: bool,
+) -> String {
+    let name = operation.name();
+    let conflicts = conflicted_files(worktree_path);
+    if conflicts.is_empty() && !operation.in_progress(worktree_path) {
+        return format!("git {name} failed: {error}");
+    }
+    if keep_conflicts && !conflicts.is_empty() {
+        return format!(
+            "{name} conflict in {}; left in place at {}, resolve then continue or abort",
+            conflicts.join(", "),
+            worktree_path.display(),
+        );
+    }
+
+    let [abort_command, abort_flag] = operation.abort_args();
+    let abort_result = run_git_command(
+        worktree_path,
+        &[abort_command.to_string(), abort_flag.to_string()],
+    );
+    let summary = if conflicts.is_empty() {
+        format!("git {name} failed: {error}")
+    } else {
+        format!("{name} conflict in {}", conflicts.join(", "))
+    };
+    match abort_result {
+        Ok(()) => format!("{summary}; {name} aborted, {location} unchanged"),
+        Err(abort_error) => format!(
+            "{summary}; abort failed ({abort_error}), {location} at {} is mid-{name}, resolve then run `{}` or run `git {abort_command} {abort_flag}`",
+            worktree_path.display(),
+            operation.continue_command(),
+        ),
+    }
+}
@@ -1,4 +1,11 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::MergeStrategy;
+
 use super::MergeWorkspaceRequest;
+use super::git_ops::{IntegrationOperation, recover_failed_integration};
 
 pub(super) fn merge_workspace_with_session_stopper(
     request: MergeWorkspaceRequest,
@@ -38,40 +45,287 @@ pub(super) fn merge_workspace_with_session_stopper(
         return (Err(format!("git switch failed: {error}")), warnings);
     }
 
-    if let Err(error) = super::git_ops::run_git_command(
-        &repo_root,
+    if let Err(error) = match request.strategy {
+        MergeStrategy::Merge => merge_no_ff(&repo_root, &request),
+        MergeStrategy::Squash => squash_merge(&repo_root, &request, &mut warnings),
+        MergeStrategy::Rebase => rebase_and_fast_forward(&repo_root, &request),
+    } {
+        if request.keep_conflicts
+            && super::conflict::detect_workspace_conflict(conflict_worktree(&repo_root, &request))
+                .is_some()
+            && let Err(save_error) = save_pending_merge_for_request(&repo_root, &request)
+        {
+            warnings.push(format!("conflict state: {save_error}"));
+        }
+        return (Err(error), warnings);
+    }
+
+    MergeCleanup::from_request(&request).run(&repo_root, stop_sessions, &mut warnings);
+    (Ok(()), warnings)
+}
+
+/// Workspace cleanup a merge runs once it lands.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct MergeCleanup {
+    task_slug: Option<String>,
+    project_name: Option<String>,
+    workspace_name: String,
+    workspace_branch: String,
+    workspace_path: PathBuf,
+    cleanup_workspace: bool,
+    cleanup_local_branch: bool,
+}
+
+impl MergeCleanup {
+    pub(super) fn from_request(request: &MergeWorkspaceRequest) -> Self {
+        Self {
+            task_slug: request.task_slug.clone(),
+            project_name: request.project_name.clone(),
+            workspace_name: request.workspace_name.clone(),
+            workspace_branch: request.workspace_branch.clone(),
+            workspace_path: request.workspace_path.clone(),
+            cleanup_workspace: request.cleanup_workspace,
+            cleanup_local_branch: request.cleanup_local_branch,
+        }
+    }
+
+    pub(super) fn run(
+        &self,
+        repo_root: &Path,
+        stop_sessions: impl Fn(Option<&str>, Option<&str>, &str),
+        warnings: &mut Vec<String>,
+    ) {
+        if self.cleanup_workspace {
+            stop_sessions(
+                self.task_slug.as_deref(),
+                self.project_name.as_deref(),
+                self.workspace_name.as_str(),
+            );
+            if let Err(error) =
+                super::git_ops::run_delete_worktree_git(repo_root, &self.workspace_path, false)
+            {
+                warnings.push(format!("workspace cleanup: {error}"));
+            }
+        }
+
+        if self.cleanup_local_branch
+            && let Err(error) =
+                super::git_ops::run_delete_local_branch_git(repo_root, &self.workspace_branch)
+        {
+            warnings.push(format!("local branch cleanup: {error}"));
+        }
+    }
+}
+
+/// Fast-forward of base that finishes a rebase merge once the workspace
+/// branch sits on top of base.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct BaseFastForward {
+    repo_root: PathBuf,
+    base_branch: String,
+    workspace_branch: String,
+}
+
+impl BaseFastForward {
+    fn from_request(repo_root: &Path, request: &MergeWorkspaceRequest) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            base_branch: request.base_branch.clone(),
+            workspace_branch: request.workspace_branch.clone(),
+        }
+    }
+
+    pub(super) fn repo_root(&self) -> &Path {
+        self.repo_root.as_path()
+    }
+
+    pub(super) fn run(&self) -> Result<(), String> {
+        super::git_ops::run_git_command(
+            &self.repo_root,
+            &["switch".to_string(), self.base_branch.clone()],
+        )
+        .map_err(|error| format!("git switch failed: {error}"))?;
+        super::git_ops::run_git_command(
+            &self.repo_root,
+            &[
+                "merge".to_string(),
+                "--ff-only".to_string(),
+                self.workspace_branch.clone(),
+            ],
+        )
+        .map_err(|error| format!("git merge --ff-only failed: {error}"))
+    }
+}
+
+fn merge_no_ff(repo_root: &Path, request: &MergeWorkspaceRequest) -> Result<(), String> {
+    super::git_ops::run_git_command(
+        repo_root,
         &[
             "merge".to_string(),
             "--no-ff".to_string(),
             request.workspace_branch.clone(),
         ],
-    ) {
-        let _ = super::git_ops::run_git_command(
-            &repo_root,
-            &["merge".to_string(), "--abort".to_string()],
-        );
-        return (Err(format!("git merge failed: {error}")), warnings);
+    )
+    .map_err(|error| {
+        recover_failed_integration(
+            repo_root,
+            IntegrationOperation::Merge,
+            "base worktree",
+            error.as_str(),
+            request.keep_conflicts,
+        )
+    })
+}
+
+fn squash_merge(
+    repo_root: &Path,
+    request: &MergeWorkspaceRequest,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    let message = squash_message_for_request(repo_root, request)?;
+    super::git_ops::run_git_command(
+        repo_root,
+        &[
+            "merge".to_string(),
+            "--squash".to_string(),
+            request.workspace_branch.clone(),
+        ],
+    )
+    .map_err(|error| {
+        recover_failed_integration(
+            repo_root,
+            IntegrationOperation::Squash,
+            "base worktree",
+            error.as_str(),
+            request.keep_conflicts,
+        )
+    })?;
+
+    if !super::git_ops::has_staged_changes(repo_root)? {
+        warnings.push(format!(
+            "nothing to squash, '{}' has no changes beyond '{}'",
+            request.workspace_branch, request.base_branch
+        ));
+        return Ok(());
     }
 
-    if request.cleanup_workspace {
-        stop_sessions(
-            request.task_slug.as_deref(),
-            request.project_name.as_deref(),
-            request.workspace_name.as_str(),
+    if let Err(error) = super::git_ops::run_git_command(
+        repo_root,
+        &["commit".to_string(), "-m".to_string(), message],
+    ) {
+        let reset = super::git_ops::run_git_command(
+            repo_root,
+            &["reset".to_string(), "--merge".to_string()],
         );
-        if let Err(error) =
-            super::git_ops::run_delete_worktree_git(&repo_root, &request.workspace_path, false)
-        {
-            warnings.push(format!("workspace cleanup: {error}"));
-        }
+        return Err(match reset {
+            Ok(()) => {
+                format!("git commit failed: {error}; squash aborted, base worktree unchanged")
+            }
+            Err(reset_error) => format!(
+                "git commit failed: {error}; reset failed ({reset_error}), squashed changes are still staged in {}",
+                repo_root.display()
+            ),
+        });
     }
 
-    if request.cleanup_local_branch
-        && let Err(error) =
-            super::git_ops::run_delete_local_branch_git(&repo_root, &request.workspace_branch)
-    {
-        warnings.push(format!("local branch cleanup: {error}"));
+    Ok(())
+}
+
+/// Rebases the workspace branch onto base in its own worktree, then
+/// fast-forwards base so history stays linear.
+fn rebase_and_fast_forward(
+    repo_root: &Path,
+    request: &MergeWorkspaceRequest,
+) -> Result<(), String> {
+    super::git_ops::run_git_command(
+        &request.workspace_path,
+        &["rebase".to_string(), request.base_branch.clone()],
+    )
+    .map_err(|error| {
+        recover_failed_integration(
+            &request.workspace_path,
+            IntegrationOperation::Rebase,
+            "workspace branch",
+            error.as_str(),
+            request.keep_conflicts,
+        )
+    })?;
+
+    BaseFastForward::from_request(repo_root, request).run()
+}
+
+/// Worktree a conflicted merge stops in: base for merge and squash, the
+/// workspace for a rebase.
+fn conflict_worktree<'a>(repo_root: &'a Path, request: &'a MergeWorkspaceRequest) -> &'a Path {
+    match request.strategy {
+        MergeStrategy::Rebase => request.workspace_path.as_path(),
+        MergeStrategy::Merge | MergeStrategy::Squash => repo_root,
     }
+}
 
-    (Ok(()), warnings)
+/// Remembers what a merge left on conflicts still has to do, so continuing
+/// it commits the squash message or fast-forwards base, then runs the cleanup.
+fn save_pending_merge_for_request(
+    repo_root: &Path,
+    request: &MergeWorkspaceRequest,
+) -> Result<(), String> {
+    let (commit_message, fast_forward) = match request.strategy {
+        MergeStrategy::Merge => (None, None),
+        MergeStrategy::Squash => (Some(squash_message_for_request(repo_root, request)?), None),
+        MergeStrategy::Rebase => (
+            None,
+            Some(BaseFastForward::from_request(repo_root, request)),
+        ),
+    };
+    super::conflict::save_pending_merge(
+        conflict_worktree(repo_root, request),
+        commit_message,
+        fast_forward,
+        MergeCleanup::from_request(request),
+    )
+}
+
+fn squash_message_for_request(
+    repo_root: &Path,
+    request: &MergeWorkspaceRequest,
+) -> Result<String, String> {
+    let subjects = super::git_ops::run_git_command_stdout(
+        repo_root,
+        &[
+            "log".to_string(),
+            "--reverse".to_string(),
+            "--format=%s".to_string(),
+            format!("{}..{}", request.base_branch, request.workspace_branch),
+        ],
+    )
+    .map_err(|error| format!("git log failed: {error}"))?
+    .lines()
+    .map(str::to_string)
+    .collect::<Vec<String>>();
+    Ok(squash_commit_message(
+        request.workspace_branch.as_str(),
+        request.base_branch.as_str(),
+        subjects.as_slice(),
+    ))
+}
+
+pub(super) fn squash_commit_message(
+    workspace_branch: &str,
+    base_branch: &str,
+    subjects: &[String],
+) -> String {
+    let mut message = format!("Squash merge branch '{workspace_branch}' into {base_branch}");
+    let subjects = subjects
+        .iter()
+        .map(|subject| subject.trim())
+        .filter(|subject| !subject.is_empty())
+        .collect::<Vec<&str>>();
+    if !subjects.is_empty() {
+        message.push('\n');
+        for subject in subjects {
+            message.push_str("\n* ");
+            message.push_str(subject);
+        }
+    }
+    message
 }
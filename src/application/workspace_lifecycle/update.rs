@@ -1,6 +1,8 @@
+use crate::domain::UpdateStrategy;
 use crate::infrastructure::paths::refer_to_same_location;
 
 use super::UpdateWorkspaceFromBaseRequest;
+use super::git_ops::{IntegrationOperation, recover_failed_integration};
 
 pub(super) fn update_workspace_from_base_with_session_stopper(
     request: UpdateWorkspaceFromBaseRequest,
@@ -76,19 +78,31 @@ pub(super) fn update_workspace_from_base_with_session_stopper(
         return (Ok(()), warnings);
     }
 
-    if let Err(error) = super::git_ops::run_git_command(
-        &request.workspace_path,
-        &[
-            "merge".to_string(),
-            "--no-ff".to_string(),
-            request.base_branch.clone(),
-        ],
-    ) {
-        let _ = super::git_ops::run_git_command(
-            &request.workspace_path,
-            &["merge".to_string(), "--abort".to_string()],
+    let (args, operation) = match request.strategy {
+        UpdateStrategy::Merge => (
+            vec![
+                "merge".to_string(),
+                "--no-ff".to_string(),
+                request.base_branch.clone(),
+            ],
+            IntegrationOperation::Merge,
+        ),
+        UpdateStrategy::Rebase => (
+            vec!["rebase".to_string(), request.base_branch.clone()],
+            IntegrationOperation::Rebase,
+        ),
+    };
+    if let Err(error) = super::git_ops::run_git_command(&request.workspace_path, &args) {
+        return (
+            Err(recover_failed_integration(
+                &request.workspace_path,
+                operation,
+                "workspace branch",
+                error.as_str(),
+                request.keep_conflicts,
+            )),
+            warnings,
         );
-        return (Err(format!("git merge failed: {error}")), warnings);
     }
 
     (Ok(()), warnings)
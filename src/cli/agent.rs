@@ -0,0 +1,786 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::application::agent_control::{
+    AgentStatusReport, AgentTarget, AgentTargetError, StartAgentOptions, agent_session_exists,
+    agent_status, agent_target_error_message, capture_agent, resolve_agent_targets,
+    resolve_single_agent_target, restart_agent, send_to_agent, start_agent, stop_agent,
+};
+use crate::application::agent_runtime::{
+    SessionExecutionResult, StatusRules, session_name_for_workspace_ref,
+};
+use crate::application::hooks::{
+    HookInvocation, HookOutcome, hook_failure_warnings, run_between_hooks,
+};
+use crate::application::prompt_queue::{
+    PromptQueue, enqueue_prompt, load_prompt_queue, save_prompt_queue,
+};
+use crate::application::task_lifecycle::task_lifecycle_error_message;
+use crate::domain::{PermissionMode, Task};
+use crate::infrastructure::config::HookEvent;
+use crate::infrastructure::task_manifest::workspace_status_name;
+
+use super::history::record_task_event;
+use super::{EXIT_FAILURE, EXIT_NOT_FOUND, invalid_input, load_tasks, print_json, required_value};
+
+const DEFAULT_CAPTURE_LINES: usize = 200;
+const EXIT_AMBIGUOUS_TARGET: i32 = 4;
+const EXIT_AGENT_NOT_RUNNING: i32 = 5;
+const EXIT_AGENT_ALREADY_RUNNING: i32 = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AgentCliArgs {
+    pub(crate) command: AgentCommand,
+    pub(crate) target: String,
+    pub(crate) json_output: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AgentCommand {
+    Start {
+        prompt: Option<String>,
+        prompt_file: Option<PathBuf>,
+        permission_mode: Option<PermissionMode>,
+    },
+    Stop,
+    Restart,
+    Send {
+        input: AgentSendInput,
+        submit: bool,
+    },
+    Capture {
+        lines: usize,
+        ansi: bool,
+    },
+    Status,
+    Queue(AgentQueueAction),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AgentQueueAction {
+    Add(AgentSendInput),
+    List,
+    Clear,
+    Resume,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AgentSendInput {
+    Text(String),
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AgentCliFailure {
+    Target(AgentTargetError),
+    NotRunning(String),
+    AlreadyRunning(String),
+    HookVetoed(String),
+    Other(String),
+}
+
+impl AgentCliFailure {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Target(AgentTargetError::UnknownTask(_)) => "unknown_task",
+            Self::Target(AgentTargetError::UnknownRepository { .. }) => "unknown_repository",
+            Self::Target(AgentTargetError::AmbiguousRepository { .. }) => "ambiguous_target",
+            Self::NotRunning(_) => "not_running",
+            Self::AlreadyRunning(_) => "already_running",
+            Self::HookVetoed(_) => "hook_vetoed",
+            Self::Other(_) => "failed",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Target(error) => agent_target_error_message(error),
+            Self::NotRunning(target) => format!("agent for '{target}' is not running"),
+            Self::AlreadyRunning(target) => format!("agent for '{target}' is already running"),
+            Self::HookVetoed(message) | Self::Other(message) => message.clone(),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::Target(AgentTargetError::AmbiguousRepository { .. }) => EXIT_AMBIGUOUS_TARGET,
+            Self::Target(_) => EXIT_NOT_FOUND,
+            Self::NotRunning(_) => EXIT_AGENT_NOT_RUNNING,
+            Self::AlreadyRunning(_) => EXIT_AGENT_ALREADY_RUNNING,
+            Self::HookVetoed(_) | Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+impl From<AgentTargetError> for AgentCliFailure {
+    fn from(error: AgentTargetError) -> Self {
+        Self::Target(error)
+    }
+}
+
+pub(crate) fn parse_agent_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<AgentCliArgs> {
+    let Some(action) = args.next() else {
+        return Err(invalid_input(
+            "agent requires an action (`start`, `stop`, `restart`, `send`, `capture`, `status`, `queue`)",
+        ));
+    };
+
+    let mut json_output = false;
+    let mut positionals = Vec::new();
+    let mut prompt = None;
+    let mut prompt_file = None;
+    let mut permission_mode = None;
+    let mut send_file = None;
+    let mut submit = true;
+    let mut lines = None;
+    let mut ansi = false;
+    let mut queue_action = None;
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--prompt" => prompt = Some(required_value(args, "--prompt", "prompt text")?),
+            "--prompt-file" => {
+                prompt_file = Some(PathBuf::from(required_value(
+                    args,
+                    "--prompt-file",
+                    "a file path",
+                )?));
+            }
+            "--permission-mode" => {
+                let raw = required_value(args, "--permission-mode", "a mode")?;
+                permission_mode = Some(parse_permission_mode(raw.as_str())?);
+            }
+            "--file" => {
+                send_file = Some(PathBuf::from(required_value(
+                    args,
+                    "--file",
+                    "a file path",
+                )?));
+            }
+            "--no-enter" => submit = false,
+            "--lines" => {
+                let raw = required_value(args, "--lines", "a line count")?;
+                let parsed = raw.parse::<usize>().map_err(|error| {
+                    invalid_input(format!("invalid --lines value `{raw}`: {error}"))
+                })?;
+                if parsed == 0 {
+                    return Err(invalid_input("--lines must be greater than zero"));
+                }
+                lines = Some(parsed);
+            }
+            "--ansi" => ansi = true,
+            "--list" | "--clear" | "--resume" => {
+                if queue_action.is_some() {
+                    return Err(invalid_input(
+                        "--list, --clear and --resume cannot be combined",
+                    ));
+                }
+                queue_action = Some(match argument.as_str() {
+                    "--list" => AgentQueueAction::List,
+                    "--clear" => AgentQueueAction::Clear,
+                    _ => AgentQueueAction::Resume,
+                });
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!("unknown agent flag `{value}`")));
+            }
+            _ => positionals.push(argument),
+        }
+    }
+
+    if positionals.is_empty() {
+        return Err(invalid_input(format!(
+            "agent {action} requires a target `<task>[/<repo>]`"
+        )));
+    }
+    let target = positionals.remove(0);
+    let start_flags = prompt.is_some() || prompt_file.is_some() || permission_mode.is_some();
+    let send_flags = send_file.is_some() || !submit;
+    let capture_flags = lines.is_some() || ansi;
+    let queue_flags = queue_action.is_some();
+
+    let command = match action.as_str() {
+        "start" => {
+            if send_flags || capture_flags || queue_flags || !positionals.is_empty() {
+                return Err(invalid_input(
+                    "agent start only accepts --prompt, --prompt-file, --permission-mode and --json",
+                ));
+            }
+            if prompt.is_some() && prompt_file.is_some() {
+                return Err(invalid_input(
+                    "--prompt cannot be combined with --prompt-file",
+                ));
+            }
+            AgentCommand::Start {
+                prompt,
+                prompt_file,
+                permission_mode,
+            }
+        }
+        "send" => {
+            if start_flags || capture_flags || queue_flags {
+                return Err(invalid_input(
+                    "agent send only accepts text, --file, --no-enter and --json",
+                ));
+            }
+            let Some(input) = parse_send_input(send_file, positionals)? else {
+                return Err(invalid_input("agent send requires text, --file or `-`"));
+            };
+            AgentCommand::Send { input, submit }
+        }
+        "queue" => {
+            if start_flags || capture_flags || !submit {
+                return Err(invalid_input(
+                    "agent queue only accepts text, --file, --list, --clear, --resume and --json",
+                ));
+            }
+            let input = parse_send_input(send_file, positionals)?;
+            let action = match (input, queue_action) {
+                (Some(input), None) => AgentQueueAction::Add(input),
+                (None, action) => action.unwrap_or(AgentQueueAction::List),
+                (Some(_), Some(_)) => {
+                    return Err(invalid_input(
+                        "queued text cannot be combined with --list, --clear or --resume",
+                    ));
+                }
+            };
+            AgentCommand::Queue(action)
+        }
+        "capture" => {
+            if start_flags || send_flags || queue_flags || !positionals.is_empty() {
+                return Err(invalid_input(
+                    "agent capture only accepts --lines, --ansi and --json",
+                ));
+            }
+            AgentCommand::Capture {
+                lines: lines.unwrap_or(DEFAULT_CAPTURE_LINES),
+                ansi,
+            }
+        }
+        "stop" | "restart" | "status" => {
+            if start_flags || send_flags || capture_flags || queue_flags || !positionals.is_empty()
+            {
+                return Err(invalid_input(format!("agent {action} only accepts --json")));
+            }
+            match action.as_str() {
+                "stop" => AgentCommand::Stop,
+                "restart" => AgentCommand::Restart,
+                _ => AgentCommand::Status,
+            }
+        }
+        other => {
+            return Err(invalid_input(format!("unsupported agent action `{other}`")));
+        }
+    };
+
+    Ok(AgentCliArgs {
+        command,
+        target,
+        json_output,
+    })
+}
+
+fn parse_send_input(
+    send_file: Option<PathBuf>,
+    positionals: Vec<String>,
+) -> std::io::Result<Option<AgentSendInput>> {
+    match (send_file, positionals.as_slice()) {
+        (Some(path), []) => Ok(Some(AgentSendInput::File(path))),
+        (None, [dash]) if dash == "-" => Ok(Some(AgentSendInput::Stdin)),
+        (None, [_, ..]) => Ok(Some(AgentSendInput::Text(positionals.join(" ")))),
+        (None, []) => Ok(None),
+        (Some(_), [_, ..]) => Err(invalid_input("--file cannot be combined with text")),
+    }
+}
+
+fn parse_permission_mode(raw: &str) -> std::io::Result<PermissionMode> {
+    match raw {
+        "default" => Ok(PermissionMode::Default),
+        "auto" => Ok(PermissionMode::Auto),
+        "unsafe" => Ok(PermissionMode::Unsafe),
+        other => Err(invalid_input(format!(
+            "invalid --permission-mode `{other}`, expected default, auto or unsafe"
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AgentActionOutput {
+    target: String,
+    session_name: String,
+    action: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AgentCaptureOutput {
+    target: String,
+    session_name: String,
+    lines: usize,
+    output: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AgentQueueOutput {
+    target: String,
+    prompts: Vec<String>,
+    paused: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AgentStatusView {
+    target: String,
+    session_name: String,
+    running: bool,
+    status: &'static str,
+    waiting_prompt: Option<String>,
+}
+
+impl AgentStatusView {
+    fn from_report(target: &AgentTarget, report: AgentStatusReport) -> Self {
+        Self {
+            target: target.label.clone(),
+            session_name: report.session_name,
+            running: report.running,
+            status: workspace_status_name(report.status),
+            waiting_prompt: report.waiting_prompt,
+        }
+    }
+}
+
+pub(crate) fn run_agent(cli: &AgentCliArgs) -> std::io::Result<()> {
+    let failure = match execute_agent_command(cli) {
+        Ok(()) => return Ok(()),
+        Err(failure) => failure,
+    };
+    super::exit_with_failure(
+        cli.json_output,
+        "agent",
+        failure.code(),
+        failure.message().as_str(),
+        failure.exit_code(),
+    )
+}
+
+fn execute_agent_command(cli: &AgentCliArgs) -> Result<(), AgentCliFailure> {
+    let loaded = super::load_cli_config().map_err(AgentCliFailure::Other)?;
+    let tasks = load_tasks(&loaded.agents)
+        .map_err(|error| AgentCliFailure::Other(task_lifecycle_error_message(&error)))?;
+    if cli.command == AgentCommand::Status {
+        return print_status(cli, tasks.as_slice(), &loaded.status_rules);
+    }
+
+    let target = resolve_single_agent_target(tasks.as_slice(), cli.target.as_str())?;
+    if let AgentCommand::Queue(action) = &cli.command {
+        return run_queue_action(cli.json_output, &target, action);
+    }
+    let session_name = session_name_for_workspace_ref(&target.workspace);
+    let running = agent_session_exists(&session_name).map_err(AgentCliFailure::Other)?;
+    let is_start = matches!(cli.command, AgentCommand::Start { .. });
+    if is_start && running {
+        return Err(AgentCliFailure::AlreadyRunning(target.label));
+    }
+    if !is_start && !running {
+        return Err(AgentCliFailure::NotRunning(target.label));
+    }
+
+    let task_name = tasks
+        .iter()
+        .find(|task| target.workspace.task_slug.as_ref() == Some(&task.slug))
+        .map(|task| task.name.clone());
+    let hooks = |event| {
+        HookInvocation::for_workspace(
+            event,
+            &loaded.config.projects,
+            &target.workspace,
+            task_name.clone(),
+        )
+    };
+
+    match &cli.command {
+        AgentCommand::Start {
+            prompt,
+            prompt_file,
+            permission_mode,
+        } => {
+            let prompt = match prompt_file {
+                Some(path) => Some(read_input_file(path)?),
+                None => prompt.clone(),
+            };
+            let (result, outcomes) = run_between_hooks(
+                hooks(HookEvent::PreAgentStart),
+                || {
+                    Ok(start_agent(
+                        &loaded.config,
+                        &target.workspace,
+                        StartAgentOptions {
+                            prompt,
+                            permission_mode: *permission_mode,
+                            task_root: tasks
+                                .iter()
+                                .find(|task| {
+                                    target.workspace.task_slug.as_ref() == Some(&task.slug)
+                                })
+                                .map(|task| task.root_path.clone()),
+                            theme_name: super::resolve_cli_theme(&loaded.config),
+                        },
+                    ))
+                },
+                |veto| Err(AgentCliFailure::HookVetoed(veto)),
+                |_| None,
+            );
+            finish_session_action(cli.json_output, &target, result?, "started", &outcomes)
+        }
+        AgentCommand::Stop => {
+            let result = stop_agent(&target.workspace);
+            let outcomes = match hooks(HookEvent::PostAgentStop) {
+                Some(post_stop_hooks) if result.result.is_ok() => post_stop_hooks.run().outcomes,
+                _ => Vec::new(),
+            };
+            finish_session_action(cli.json_output, &target, result, "stopped", &outcomes)
+        }
+        AgentCommand::Restart => {
+            let (result, outcomes) = run_between_hooks(
+                hooks(HookEvent::PreAgentStart),
+                || Ok(restart_agent(&loaded.config, &target.workspace)),
+                |veto| Err(AgentCliFailure::HookVetoed(veto)),
+                |_| None,
+            );
+            finish_session_action(cli.json_output, &target, result?, "restarted", &outcomes)
+        }
+        AgentCommand::Send { input, submit } => {
+            let text = read_send_input(input)?;
+            send_to_agent(&session_name, text.as_str(), *submit).map_err(AgentCliFailure::Other)?;
+            print_action(
+                cli.json_output,
+                &AgentActionOutput {
+                    target: target.label,
+                    session_name,
+                    action: "sent",
+                },
+            )
+        }
+        AgentCommand::Capture { lines, ansi } => {
+            let output =
+                capture_agent(&session_name, *lines, *ansi).map_err(AgentCliFailure::Other)?;
+            if cli.json_output {
+                return print_json(&AgentCaptureOutput {
+                    target: target.label,
+                    session_name,
+                    lines: *lines,
+                    output,
+                })
+                .map_err(AgentCliFailure::Other);
+            }
+            print!("{output}");
+            if !output.ends_with('\n') {
+                println!();
+            }
+            Ok(())
+        }
+        AgentCommand::Status => print_status(cli, tasks.as_slice(), &loaded.status_rules),
+        AgentCommand::Queue(action) => run_queue_action(cli.json_output, &target, action),
+    }
+}
+
+/// The queue lives in the worktree, so it can be edited while the agent is
+/// stopped; a running Grove sends entries as the agent returns to its input.
+fn run_queue_action(
+    json_output: bool,
+    target: &AgentTarget,
+    action: &AgentQueueAction,
+) -> Result<(), AgentCliFailure> {
+    let worktree = target.workspace.path.as_path();
+    let queue = match action {
+        AgentQueueAction::Add(input) => {
+            let text = read_send_input(input)?;
+            let queue = enqueue_prompt(worktree, text.as_str()).map_err(AgentCliFailure::Other)?;
+            if let Some(task_slug) = target.workspace.task_slug.as_deref() {
+                record_task_event(task_slug, "prompt_queued", Some(worktree));
+            }
+            queue
+        }
+        AgentQueueAction::List => load_prompt_queue(worktree).map_err(AgentCliFailure::Other)?,
+        AgentQueueAction::Clear | AgentQueueAction::Resume => {
+            let mut queue = load_prompt_queue(worktree).map_err(AgentCliFailure::Other)?;
+            if *action == AgentQueueAction::Clear {
+                queue = PromptQueue::default();
+            } else {
+                queue.paused = None;
+            }
+            save_prompt_queue(worktree, &queue).map_err(AgentCliFailure::Other)?;
+            queue
+        }
+    };
+
+    let output = AgentQueueOutput {
+        target: target.label.clone(),
+        prompts: queue.prompts,
+        paused: queue.paused,
+    };
+    if json_output {
+        return print_json(&output).map_err(AgentCliFailure::Other);
+    }
+    match output.paused.as_deref() {
+        Some(reason) => println!(
+            "{} {} queued, paused: {reason}",
+            output.target,
+            output.prompts.len()
+        ),
+        None => println!("{} {} queued", output.target, output.prompts.len()),
+    }
+    for (index, prompt) in output.prompts.iter().enumerate() {
+        println!(
+            "  {}. {}",
+            index + 1,
+            prompt.lines().next().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Status accepts a bare task and reports every worktree in it.
+fn print_status(
+    cli: &AgentCliArgs,
+    tasks: &[Task],
+    rules: &StatusRules,
+) -> Result<(), AgentCliFailure> {
+    let targets = resolve_agent_targets(tasks, cli.target.as_str())?;
+    let views = targets
+        .iter()
+        .map(|target| {
+            agent_status(&target.workspace, rules)
+                .map(|report| AgentStatusView::from_report(target, report))
+                .map_err(AgentCliFailure::Other)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if cli.json_output {
+        return print_json(&views).map_err(AgentCliFailure::Other);
+    }
+    for view in &views {
+        match view.waiting_prompt.as_deref() {
+            Some(prompt) => println!("{} {} ({prompt})", view.target, view.status),
+            None => println!("{} {}", view.target, view.status),
+        }
+    }
+    Ok(())
+}
+
+fn finish_session_action(
+    json_output: bool,
+    target: &AgentTarget,
+    result: SessionExecutionResult,
+    action: &'static str,
+    hook_outcomes: &[HookOutcome],
+) -> Result<(), AgentCliFailure> {
+    result.result.map_err(AgentCliFailure::Other)?;
+    for warning in hook_failure_warnings(hook_outcomes) {
+        eprintln!("warning: {warning}");
+    }
+    if let Some(task_slug) = target.workspace.task_slug.as_deref() {
+        record_task_event(
+            task_slug,
+            format!("agent_{action}").as_str(),
+            Some(&target.workspace.path),
+        );
+    }
+    print_action(
+        json_output,
+        &AgentActionOutput {
+            target: target.label.clone(),
+            session_name: result.session_name,
+            action,
+        },
+    )
+}
+
+fn read_send_input(input: &AgentSendInput) -> Result<String, AgentCliFailure> {
+    match input {
+        AgentSendInput::Text(text) => Ok(text.clone()),
+        AgentSendInput::File(path) => read_input_file(path),
+        AgentSendInput::Stdin => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| AgentCliFailure::Other(format!("read stdin failed: {error}")))?;
+            nonempty_input(text, "stdin")
+        }
+    }
+}
+
+fn read_input_file(path: &Path) -> Result<String, AgentCliFailure> {
+    let text = fs::read_to_string(path).map_err(|error| {
+        AgentCliFailure::Other(format!("read {} failed: {error}", path.display()))
+    })?;
+    nonempty_input(text, path.display().to_string().as_str())
+}
+
+fn nonempty_input(text: String, source: &str) -> Result<String, AgentCliFailure> {
+    let trimmed = text.trim_end_matches(['\n', '\r']);
+    if trimmed.trim().is_empty() {
+        return Err(AgentCliFailure::Other(format!("{source} is empty")));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn print_action(json_output: bool, output: &AgentActionOutput) -> Result<(), AgentCliFailure> {
+    if json_output {
+        return print_json(output).map_err(AgentCliFailure::Other);
+    }
+    println!(
+        "{} {} ({})",
+        output.action, output.target, output.session_name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AgentCliArgs, AgentCliFailure, AgentCommand, AgentQueueAction, AgentSendInput,
+        DEFAULT_CAPTURE_LINES, EXIT_AMBIGUOUS_TARGET, parse_agent_args,
+    };
+    use crate::application::agent_control::AgentTargetError;
+    use crate::domain::PermissionMode;
+    use std::path::PathBuf;
+
+    fn parse(args: &[&str]) -> std::io::Result<AgentCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_agent_args(&mut args)
+    }
+
+    #[test]
+    fn parse_agent_start_reads_prompt_and_permission_mode() {
+        let parsed = parse(&[
+            "start",
+            "flaky-tests/web",
+            "--prompt",
+            "fix the flake",
+            "--permission-mode",
+            "auto",
+            "--json",
+        ])
+        .expect("start should parse");
+        assert_eq!(
+            parsed,
+            AgentCliArgs {
+                command: AgentCommand::Start {
+                    prompt: Some("fix the flake".to_string()),
+                    prompt_file: None,
+                    permission_mode: Some(PermissionMode::Auto),
+                },
+                target: "flaky-tests/web".to_string(),
+                json_output: true,
+            }
+        );
+        assert!(parse(&["start", "t", "--prompt", "a", "--prompt-file", "b"]).is_err());
+        assert!(parse(&["start", "t", "--permission-mode", "yolo"]).is_err());
+    }
+
+    #[test]
+    fn parse_agent_send_reads_text_file_and_stdin() {
+        let parsed = parse(&["send", "t/web", "run", "the", "tests", "--no-enter"])
+            .expect("send should parse");
+        assert_eq!(
+            parsed.command,
+            AgentCommand::Send {
+                input: AgentSendInput::Text("run the tests".to_string()),
+                submit: false,
+            }
+        );
+        assert_eq!(
+            parse(&["send", "t", "--file", "/tmp/prompt.md"])
+                .expect("send file should parse")
+                .command,
+            AgentCommand::Send {
+                input: AgentSendInput::File(PathBuf::from("/tmp/prompt.md")),
+                submit: true,
+            }
+        );
+        assert_eq!(
+            parse(&["send", "t", "-"])
+                .expect("send stdin should parse")
+                .command,
+            AgentCommand::Send {
+                input: AgentSendInput::Stdin,
+                submit: true,
+            }
+        );
+        assert!(parse(&["send", "t"]).is_err());
+        assert!(parse(&["send", "t", "hi", "--file", "/tmp/prompt.md"]).is_err());
+    }
+
+    #[test]
+    fn parse_agent_queue_adds_text_or_manages_the_queue() {
+        assert_eq!(
+            parse(&["queue", "t/web", "now", "fix", "lint"])
+                .expect("queue text should parse")
+                .command,
+            AgentCommand::Queue(AgentQueueAction::Add(AgentSendInput::Text(
+                "now fix lint".to_string()
+            )))
+        );
+        assert_eq!(
+            parse(&["queue", "t"])
+                .expect("bare queue should parse")
+                .command,
+            AgentCommand::Queue(AgentQueueAction::List)
+        );
+        assert_eq!(
+            parse(&["queue", "t", "--resume", "--json"])
+                .expect("queue resume should parse")
+                .command,
+            AgentCommand::Queue(AgentQueueAction::Resume)
+        );
+        assert!(parse(&["queue", "t", "--list", "--clear"]).is_err());
+        assert!(parse(&["queue", "t", "hi", "--clear"]).is_err());
+        assert!(parse(&["queue", "t", "hi", "--no-enter"]).is_err());
+        assert!(parse(&["send", "t", "hi", "--list"]).is_err());
+    }
+
+    #[test]
+    fn parse_agent_capture_defaults_and_rejects_foreign_flags() {
+        assert_eq!(
+            parse(&["capture", "t"])
+                .expect("capture should parse")
+                .command,
+            AgentCommand::Capture {
+                lines: DEFAULT_CAPTURE_LINES,
+                ansi: false,
+            }
+        );
+        assert_eq!(
+            parse(&["capture", "t", "--lines", "50", "--ansi"])
+                .expect("capture options should parse")
+                .command,
+            AgentCommand::Capture {
+                lines: 50,
+                ansi: true,
+            }
+        );
+        assert!(parse(&["capture", "t", "--lines", "0"]).is_err());
+        assert!(parse(&["status", "t", "--ansi"]).is_err());
+        assert!(parse(&["stop"]).is_err());
+        assert!(parse(&["launch", "t"]).is_err());
+    }
+
+    #[test]
+    fn ambiguous_targets_get_their_own_exit_code() {
+        let failure = AgentCliFailure::Target(AgentTargetError::AmbiguousRepository {
+            task: "t".to_string(),
+            repositories: vec!["web".to_string(), "api".to_string()],
+        });
+        assert_eq!(failure.code(), "ambiguous_target");
+        assert_eq!(failure.exit_code(), EXIT_AMBIGUOUS_TARGET);
+        assert_eq!(
+            AgentCliFailure::Target(AgentTargetError::UnknownTask("t".to_string())).exit_code(),
+            super::EXIT_NOT_FOUND
+        );
+    }
+}
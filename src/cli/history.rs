@@ -0,0 +1,335 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::infrastructure::event_log::now_millis;
+use crate::infrastructure::state_store::{AttentionRecord, StateStore, TaskEventRecord};
+use crate::infrastructure::task_manifest::workspace_status_name;
+
+use super::{EXIT_FAILURE, format_age, invalid_input, required_value};
+
+const DEFAULT_HISTORY_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HistoryCliArgs {
+    pub(crate) task: Option<String>,
+    pub(crate) since_secs: u64,
+    pub(crate) json_output: bool,
+}
+
+pub(crate) fn parse_history_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<HistoryCliArgs> {
+    let mut task = None;
+    let mut since_secs = DEFAULT_HISTORY_WINDOW_SECS;
+    let mut json_output = false;
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--since" => {
+                let raw = required_value(args, "--since", "a duration like 90m, 24h or 7d")?;
+                since_secs = parse_window(raw.as_str()).ok_or_else(|| {
+                    invalid_input(format!(
+                        "--since expects a duration like 90m, 24h or 7d, got `{raw}`"
+                    ))
+                })?;
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!("unknown history flag `{value}`")));
+            }
+            _ if task.is_some() => {
+                return Err(invalid_input("history accepts a single task"));
+            }
+            _ => task = Some(argument),
+        }
+    }
+
+    Ok(HistoryCliArgs {
+        task,
+        since_secs,
+        json_output,
+    })
+}
+
+pub(super) fn parse_window(raw: &str) -> Option<u64> {
+    let split = raw.find(|character: char| !character.is_ascii_digit())?;
+    let (value, unit) = raw.split_at(split);
+    let value = value.parse::<u64>().ok().filter(|value| *value > 0)?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    value.checked_mul(unit_secs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct HistoryOutput {
+    since_ms: u64,
+    until_ms: u64,
+    events: Vec<TaskEventView>,
+    status_time: Vec<StatusTimeView>,
+    attention: Vec<AttentionView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskEventView {
+    ts_ms: u64,
+    task: String,
+    event: String,
+    worktree_path: Option<String>,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StatusTimeView {
+    task: Option<String>,
+    workspace_path: String,
+    durations: Vec<StatusDurationView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StatusDurationView {
+    status: &'static str,
+    duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AttentionView {
+    task: Option<String>,
+    workspace_path: String,
+    reason: String,
+    summary: String,
+    first_seen_ms: u64,
+    acknowledged_ms: Option<u64>,
+    cleared_ms: Option<u64>,
+}
+
+impl TaskEventView {
+    fn from_record(record: TaskEventRecord) -> Self {
+        Self {
+            ts_ms: record.ts_ms,
+            task: record.task_slug,
+            event: record.event,
+            worktree_path: record
+                .worktree_path
+                .map(|path| path.to_string_lossy().into_owned()),
+            detail: record.detail,
+        }
+    }
+}
+
+impl AttentionView {
+    fn from_record(record: AttentionRecord) -> Self {
+        Self {
+            task: record.task_slug,
+            workspace_path: record.workspace_path.to_string_lossy().into_owned(),
+            reason: record.reason,
+            summary: record.summary,
+            first_seen_ms: record.first_seen_ms,
+            acknowledged_ms: record.acknowledged_ms,
+            cleared_ms: record.cleared_ms,
+        }
+    }
+}
+
+pub(crate) fn run_history(cli: &HistoryCliArgs) -> std::io::Result<()> {
+    let output = match load_history(cli) {
+        Ok(output) => output,
+        Err(message) => super::exit_with_failure(
+            cli.json_output,
+            "history",
+            "failed",
+            message.as_str(),
+            EXIT_FAILURE,
+        ),
+    };
+    if cli.json_output {
+        let encoded = serde_json::to_string_pretty(&output).map_err(std::io::Error::other)?;
+        println!("{encoded}");
+        return Ok(());
+    }
+    print_history(&output, cli.since_secs);
+    Ok(())
+}
+
+fn load_history(cli: &HistoryCliArgs) -> Result<HistoryOutput, String> {
+    let state_store = StateStore::open_default()?;
+    let until_ms = now_millis();
+    let since_ms = until_ms.saturating_sub(cli.since_secs.saturating_mul(1000));
+    let task = cli.task.as_deref();
+
+    let events = state_store
+        .task_events(task, since_ms)?
+        .into_iter()
+        .map(TaskEventView::from_record)
+        .collect();
+    let mut status_time = Vec::new();
+    for tracked in state_store.tracked_workspaces(task)? {
+        let durations =
+            state_store.status_durations(&tracked.workspace_path, since_ms, until_ms)?;
+        if durations.is_empty() {
+            continue;
+        }
+        status_time.push(StatusTimeView {
+            task: tracked.task_slug,
+            workspace_path: tracked.workspace_path.to_string_lossy().into_owned(),
+            durations: durations
+                .into_iter()
+                .map(|(status, duration_ms)| StatusDurationView {
+                    status: workspace_status_name(status),
+                    duration_ms,
+                })
+                .collect(),
+        });
+    }
+    let attention = state_store
+        .attention_history(task, since_ms)?
+        .into_iter()
+        .map(AttentionView::from_record)
+        .collect();
+
+    Ok(HistoryOutput {
+        since_ms,
+        until_ms,
+        events,
+        status_time,
+        attention,
+    })
+}
+
+fn ago(output: &HistoryOutput, ts_ms: u64) -> String {
+    format!(
+        "{} ago",
+        format_age(Some(output.until_ms.saturating_sub(ts_ms) / 1000))
+    )
+}
+
+fn print_history(output: &HistoryOutput, since_secs: u64) {
+    println!("history for the last {}", format_age(Some(since_secs)));
+
+    println!("events");
+    if output.events.is_empty() {
+        println!("  none");
+    }
+    for event in &output.events {
+        let mut line = format!(
+            "  {} {} {}",
+            ago(output, event.ts_ms),
+            event.task,
+            event.event
+        );
+        if let Some(path) = event.worktree_path.as_deref() {
+            line.push_str(format!(" {path}").as_str());
+        }
+        if let Some(detail) = event.detail.as_deref() {
+            line.push_str(format!(" ({detail})").as_str());
+        }
+        println!("{line}");
+    }
+
+    println!("status time");
+    if output.status_time.is_empty() {
+        println!("  none");
+    }
+    for entry in &output.status_time {
+        let durations = entry
+            .durations
+            .iter()
+            .map(|duration| {
+                format!(
+                    "{} {}",
+                    duration.status,
+                    format_age(Some(duration.duration_ms / 1000))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("  {}: {durations}", entry.workspace_path);
+    }
+
+    println!("attention");
+    if output.attention.is_empty() {
+        println!("  none");
+    }
+    for item in &output.attention {
+        let state = match (item.acknowledged_ms, item.cleared_ms) {
+            (Some(acknowledged_ms), _) => format!(
+                "acknowledged after {}",
+                format_age(Some(
+                    acknowledged_ms.saturating_sub(item.first_seen_ms) / 1000
+                ))
+            ),
+            (None, Some(cleared_ms)) => format!(
+                "cleared after {}",
+                format_age(Some(cleared_ms.saturating_sub(item.first_seen_ms) / 1000))
+            ),
+            (None, None) => "open".to_string(),
+        };
+        println!(
+            "  {} {}: {}, {state}",
+            ago(output, item.first_seen_ms),
+            item.workspace_path,
+            item.summary
+        );
+    }
+}
+
+/// Headless commands record lifecycle history best-effort, a missing or
+/// unreadable state store never fails the command itself.
+pub(super) fn record_task_event(task_slug: &str, event: &str, worktree_path: Option<&Path>) {
+    if let Ok(state_store) = StateStore::open_default() {
+        let _ = state_store.record_task_event(now_millis(), task_slug, event, worktree_path, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_HISTORY_WINDOW_SECS, HistoryCliArgs, parse_history_args, parse_window};
+
+    fn parse(args: &[&str]) -> std::io::Result<HistoryCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_history_args(&mut args)
+    }
+
+    #[test]
+    fn parse_history_defaults_to_last_day_for_all_tasks() {
+        assert_eq!(
+            parse(&[]).expect("history should parse"),
+            HistoryCliArgs {
+                task: None,
+                since_secs: DEFAULT_HISTORY_WINDOW_SECS,
+                json_output: false,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_history_reads_task_window_and_json() {
+        assert_eq!(
+            parse(&["feature-a", "--since", "7d", "--json"]).expect("history should parse"),
+            HistoryCliArgs {
+                task: Some("feature-a".to_string()),
+                since_secs: 7 * 24 * 60 * 60,
+                json_output: true,
+            }
+        );
+        assert!(parse(&["a", "b"]).is_err());
+        assert!(parse(&["--since", "soon"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn parse_window_accepts_seconds_minutes_hours_and_days() {
+        assert_eq!(parse_window("30s"), Some(30));
+        assert_eq!(parse_window("90m"), Some(90 * 60));
+        assert_eq!(parse_window("24h"), Some(24 * 60 * 60));
+        assert_eq!(parse_window("2d"), Some(2 * 24 * 60 * 60));
+        assert_eq!(parse_window("0h"), None);
+        assert_eq!(parse_window("12"), None);
+        assert_eq!(parse_window("h"), None);
+    }
+}
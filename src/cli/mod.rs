@@ -1,14 +1,166 @@
+mod agent;
+mod history;
+mod status_rules;
+mod task;
+mod transcript;
+mod usage;
+
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::application::doctor::DoctorReport;
+use serde::Serialize;
+
+use crate::application::agent_runtime::StatusRules;
+use crate::application::doctor::{
+    DoctorFix, DoctorFixOutcome, DoctorFixPlan, DoctorReport, DoctorSkippedFix, apply_doctor_fixes,
+    kill_doctor_session,
+};
 use crate::application::session_cleanup::{
     SessionCleanupEntry, SessionCleanupOptions, SessionCleanupReason, apply_session_cleanup,
-    plan_session_cleanup,
+    list_tmux_sessions, plan_session_cleanup,
+};
+use crate::application::task_discovery::{
+    TaskDiscoveryState, bootstrap_task_data_for_root_with_sessions,
+    bootstrap_task_data_for_root_with_state_store,
 };
+use crate::application::task_lifecycle::TaskLifecycleError;
+use crate::domain::{AgentRegistry, Task};
+use crate::infrastructure::config::{GroveConfig, ThemeName};
 use crate::infrastructure::event_log::now_millis;
+use crate::infrastructure::paths::tasks_root;
+use crate::infrastructure::state_store::StateStore;
 
 const DEBUG_RECORD_DIR: &str = ".grove";
+const EXIT_FAILURE: i32 = 1;
+const EXIT_NOT_FOUND: i32 = 3;
+
+#[derive(Debug, Serialize)]
+struct CliErrorOutput<'a> {
+    error: CliErrorView<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct CliErrorView<'a> {
+    code: &'a str,
+    message: &'a str,
+    exit_code: i32,
+}
+
+fn invalid_input(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message.into())
+}
+
+fn required_value(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+    what: &str,
+) -> std::io::Result<String> {
+    args.next()
+        .ok_or_else(|| invalid_input(format!("{flag} requires {what}")))
+}
+
+/// Loads task manifests with session-derived status, as the TUI sees them.
+pub(super) fn load_tasks(agents: &AgentRegistry) -> Result<Vec<Task>, TaskLifecycleError> {
+    let tasks_root = tasks_root().ok_or(TaskLifecycleError::HomeDirectoryUnavailable)?;
+    let running_sessions = list_tmux_sessions()
+        .map(|sessions| {
+            sessions
+                .into_iter()
+                .map(|session| session.name)
+                .collect::<HashSet<_>>()
+        })
+        .unwrap_or_default();
+    let data = match StateStore::open_default() {
+        Ok(state_store) => bootstrap_task_data_for_root_with_state_store(
+            &tasks_root,
+            &running_sessions,
+            &state_store,
+            agents,
+        ),
+        Err(_) => {
+            bootstrap_task_data_for_root_with_sessions(&tasks_root, &running_sessions, agents)
+        }
+    };
+    if let TaskDiscoveryState::Error(error) = data.discovery_state {
+        return Err(TaskLifecycleError::TaskManifest(error));
+    }
+    Ok(data.tasks)
+}
+
+/// Prints `value` as pretty JSON for `--json` output.
+pub(super) fn print_json(value: &impl Serialize) -> Result<(), String> {
+    let encoded = serde_json::to_string_pretty(value).map_err(|error| error.to_string())?;
+    println!("{encoded}");
+    Ok(())
+}
+
+/// Config for headless commands with the agents it defines, so task
+/// manifests that reference them decode, and the status rules that use them.
+struct CliConfig {
+    path: PathBuf,
+    config: GroveConfig,
+    agents: AgentRegistry,
+    status_rules: StatusRules,
+}
+
+/// Loads config for headless commands with its custom agents and status
+/// rules.
+fn load_cli_config() -> Result<CliConfig, String> {
+    let loaded = crate::infrastructure::config::load()?;
+    let agents = crate::application::agent_runtime::build_agent_registry(&loaded.config.agents)
+        .map_err(|error| format!("agents invalid: {error}"))?;
+    let status_rules = crate::application::agent_runtime::compile_status_rules(
+        &loaded.config.status_rules,
+        &agents,
+    )
+    .map_err(|error| format!("status rules invalid: {error}"))?;
+    Ok(CliConfig {
+        path: loaded.path,
+        config: loaded.config,
+        agents,
+        status_rules,
+    })
+}
+
+/// Resolves the configured theme, warning and falling back to the default
+/// when custom themes fail to load or the theme is unknown.
+fn resolve_cli_theme(config: &GroveConfig) -> ThemeName {
+    let (themes, errors) = crate::infrastructure::themes::load_theme_registry();
+    for error in errors {
+        eprintln!("warning: themes invalid: {error}");
+    }
+    themes.resolve(&config.theme).unwrap_or_else(|error| {
+        eprintln!("warning: {error}, using the default theme");
+        ThemeName::default()
+    })
+}
+
+/// Reports a scripting-command failure and exits with its mapped code.
+fn exit_with_failure(
+    json_output: bool,
+    scope: &str,
+    code: &str,
+    message: &str,
+    exit_code: i32,
+) -> ! {
+    if json_output {
+        let output = CliErrorOutput {
+            error: CliErrorView {
+                code,
+                message,
+                exit_code,
+            },
+        };
+        match serde_json::to_string_pretty(&output) {
+            Ok(encoded) => println!("{encoded}"),
+            Err(error) => eprintln!("{scope} {code}: {message} ({error})"),
+        }
+    } else {
+        eprintln!("{scope} {code}: {message}");
+    }
+    std::process::exit(exit_code);
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub(crate) struct CliArgs {
@@ -26,10 +178,18 @@ pub(crate) struct CliArgs {
     pub(crate) benchmark_warn_regression_pct: Option<u64>,
     pub(crate) doctor: bool,
     pub(crate) doctor_json_output: bool,
+    pub(crate) doctor_fix: bool,
+    pub(crate) doctor_fix_yes: bool,
     pub(crate) cleanup_sessions: bool,
     pub(crate) cleanup_sessions_apply: bool,
     pub(crate) cleanup_sessions_include_stale: bool,
     pub(crate) cleanup_sessions_include_attached: bool,
+    pub(crate) task: Option<task::TaskCliArgs>,
+    pub(crate) agent: Option<agent::AgentCliArgs>,
+    pub(crate) history: Option<history::HistoryCliArgs>,
+    pub(crate) usage: Option<usage::UsageCliArgs>,
+    pub(crate) transcript: Option<transcript::TranscriptCliArgs>,
+    pub(crate) status_rules: Option<status_rules::StatusRulesCliArgs>,
 }
 
 pub(crate) fn parse_cli_args(args: impl IntoIterator<Item = String>) -> std::io::Result<CliArgs> {
@@ -96,6 +256,24 @@ pub(crate) fn parse_cli_args(args: impl IntoIterator<Item = String>) -> std::io:
                 }
                 cli.cleanup_sessions = true;
             }
+            "task" => {
+                cli.task = Some(task::parse_task_args(&mut args)?);
+            }
+            "agent" => {
+                cli.agent = Some(agent::parse_agent_args(&mut args)?);
+            }
+            "history" => {
+                cli.history = Some(history::parse_history_args(&mut args)?);
+            }
+            "usage" => {
+                cli.usage = Some(usage::parse_usage_args(&mut args)?);
+            }
+            "transcript" => {
+                cli.transcript = Some(transcript::parse_transcript_args(&mut args)?);
+            }
+            "status-rules" => {
+                cli.status_rules = Some(status_rules::parse_status_rules_args(&mut args)?);
+            }
             "--snapshot" => {
                 let Some(path) = args.next() else {
                     return Err(std::io::Error::new(
@@ -162,6 +340,12 @@ pub(crate) fn parse_cli_args(args: impl IntoIterator<Item = String>) -> std::io:
             "--apply" => {
                 cli.cleanup_sessions_apply = true;
             }
+            "--fix" => {
+                cli.doctor_fix = true;
+            }
+            "--yes" => {
+                cli.doctor_fix_yes = true;
+            }
             "--include-stale" => {
                 cli.cleanup_sessions_include_stale = true;
             }
@@ -219,31 +403,53 @@ pub(crate) fn parse_cli_args(args: impl IntoIterator<Item = String>) -> std::io:
         ));
     }
 
-    if cli.cleanup_sessions
-        && (cli.replay_trace_path.is_some()
+    // Listed in the order the conflicting mode is named in the error.
+    let command_modes = [
+        ("cleanup sessions", cli.cleanup_sessions),
+        ("status-rules", cli.status_rules.is_some()),
+        ("transcript", cli.transcript.is_some()),
+        ("usage", cli.usage.is_some()),
+        ("history", cli.history.is_some()),
+        ("agent", cli.agent.is_some()),
+        ("task", cli.task.is_some()),
+        ("doctor", cli.doctor),
+    ];
+    let mut active_modes = command_modes
+        .iter()
+        .filter(|(_, active)| *active)
+        .map(|(name, _)| *name);
+    if let Some(mode) = active_modes.next()
+        && (active_modes.next().is_some()
+            || cli.replay_trace_path.is_some()
             || cli.benchmark_scale
-            || cli.doctor
             || cli.debug_record
             || cli.event_log_path.is_some()
             || cli.print_hello)
     {
         return Err(std::io::Error::new(
             std::io::ErrorKind::InvalidInput,
-            "cleanup sessions cannot be combined with other command modes",
+            format!("{mode} cannot be combined with other command modes"),
         ));
     }
 
-    if cli.doctor
-        && (cli.replay_trace_path.is_some()
-            || cli.benchmark_scale
-            || cli.cleanup_sessions
-            || cli.debug_record
-            || cli.event_log_path.is_some()
-            || cli.print_hello)
-    {
+    if !cli.doctor && cli.doctor_fix {
         return Err(std::io::Error::new(
             std::io::ErrorKind::InvalidInput,
-            "doctor cannot be combined with other command modes",
+            "--fix requires `doctor`",
+        ));
+    }
+
+    if !cli.doctor_fix && cli.doctor_fix_yes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "--yes requires `doctor --fix`",
+        ));
+    }
+
+    if cli.doctor_fix && cli.doctor_json_output && !cli.doctor_fix_yes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "`doctor --fix --json` cannot prompt, add --yes",
         ));
     }
 
@@ -336,7 +542,8 @@ fn run_cleanup_sessions(cli: &CliArgs) -> std::io::Result<()> {
         include_stale: cli.cleanup_sessions_include_stale,
         include_attached: cli.cleanup_sessions_include_attached,
     };
-    let plan = plan_session_cleanup(options).map_err(std::io::Error::other)?;
+    let cli_config = load_cli_config().map_err(std::io::Error::other)?;
+    let plan = plan_session_cleanup(options, &cli_config.agents).map_err(std::io::Error::other)?;
     if plan.candidates.is_empty() {
         println!("cleanup sessions: no candidates");
     } else {
@@ -471,7 +678,151 @@ fn print_doctor_report(report: &DoctorReport) -> std::io::Result<()> {
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+struct DoctorFixOutput<'a> {
+    before: &'a DoctorReport,
+    fixes: &'a [DoctorFix],
+    skipped: &'a [DoctorSkippedFix],
+    outcomes: &'a [DoctorFixOutcome],
+    backup_dir: Option<&'a Path>,
+    after: &'a DoctorReport,
+}
+
+fn print_doctor_fix_plan(plan: &DoctorFixPlan) {
+    if !plan.fixes.is_empty() {
+        println!();
+        println!("fixes");
+        for (index, fix) in plan.fixes.iter().enumerate() {
+            println!("{}. {}", index + 1, fix.summary);
+            for line in fix.preview() {
+                println!("   {line}");
+            }
+        }
+    }
+    if !plan.skipped.is_empty() {
+        println!();
+        println!("left for manual repair");
+        for skipped in &plan.skipped {
+            println!(
+                "- {} [{}]: {}",
+                skipped.finding.label(),
+                skipped.target,
+                skipped.reason
+            );
+        }
+    }
+}
+
+fn confirm_doctor_fixes(count: usize) -> std::io::Result<bool> {
+    use std::io::Write;
+
+    print!("\napply {count} fix(es)? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn print_doctor_fix_outcomes(outcomes: &[DoctorFixOutcome]) {
+    println!();
+    for outcome in outcomes {
+        match outcome.error.as_deref() {
+            Some(error) => println!("failed: {}: {error}", outcome.summary),
+            None => println!("fixed: {}", outcome.summary),
+        }
+        for line in &outcome.diff {
+            println!("   {line}");
+        }
+        if let Some(backup_path) = outcome.backup_path.as_ref() {
+            println!("   backup: {}", backup_path.display());
+        }
+    }
+}
+
+fn run_doctor_fix(cli: &CliArgs) -> std::io::Result<()> {
+    let (before, plan) =
+        crate::application::doctor::diagnose_with_fix_plan().map_err(std::io::Error::other)?;
+    if !cli.doctor_json_output {
+        println!("{}", format_doctor_summary(&before));
+        print_doctor_fix_plan(&plan);
+    }
+
+    let apply =
+        !plan.fixes.is_empty() && (cli.doctor_fix_yes || confirm_doctor_fixes(plan.fixes.len())?);
+    let mut outcomes = Vec::new();
+    let mut backup_dir = None;
+    if apply {
+        let tasks_root = crate::infrastructure::paths::tasks_root()
+            .ok_or_else(|| std::io::Error::other("task root unavailable"))?;
+        let run_dir = crate::infrastructure::paths::doctor_backup_root()
+            .ok_or_else(|| std::io::Error::other("home directory unavailable"))?
+            .join(now_millis().to_string());
+        outcomes = apply_doctor_fixes(
+            &plan,
+            tasks_root.as_path(),
+            run_dir.as_path(),
+            kill_doctor_session,
+        );
+        if outcomes.iter().any(|outcome| outcome.backup_path.is_some()) {
+            backup_dir = Some(run_dir);
+        }
+    }
+
+    let after = if apply {
+        crate::application::doctor::diagnose().map_err(std::io::Error::other)?
+    } else {
+        before.clone()
+    };
+    if cli.doctor_json_output {
+        let output = DoctorFixOutput {
+            before: &before,
+            fixes: plan.fixes.as_slice(),
+            skipped: plan.skipped.as_slice(),
+            outcomes: outcomes.as_slice(),
+            backup_dir: backup_dir.as_deref(),
+            after: &after,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).map_err(std::io::Error::other)?
+        );
+    } else if apply {
+        print_doctor_fix_outcomes(outcomes.as_slice());
+        if let Some(backup_dir) = backup_dir.as_ref() {
+            println!("backups: {}", backup_dir.display());
+        }
+        println!();
+        println!(
+            "before: {}\nafter: {}",
+            format_doctor_summary(&before),
+            format_doctor_summary(&after)
+        );
+    } else if plan.fixes.is_empty() {
+        println!("nothing to fix automatically");
+    } else {
+        println!("no changes made");
+    }
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| outcome.error.is_some())
+        .count();
+    if failed > 0 {
+        return Err(std::io::Error::other(format!(
+            "doctor --fix failed for {failed} fix(es)"
+        )));
+    }
+    if doctor_exit_code(&after) == 0 {
+        return Ok(());
+    }
+
+    Err(std::io::Error::other("doctor found actionable issues"))
+}
+
 fn run_doctor(cli: &CliArgs) -> std::io::Result<()> {
+    if cli.doctor_fix {
+        return run_doctor_fix(cli);
+    }
     let report = crate::application::doctor::diagnose().map_err(std::io::Error::other)?;
     if cli.doctor_json_output {
         println!(
@@ -501,6 +852,30 @@ fn doctor_exit_code(report: &DoctorReport) -> i32 {
 pub fn run(args: impl IntoIterator<Item = String>) -> std::io::Result<()> {
     let cli = parse_cli_args(args)?;
 
+    if let Some(task) = cli.task.as_ref() {
+        return task::run_task(task);
+    }
+
+    if let Some(agent) = cli.agent.as_ref() {
+        return agent::run_agent(agent);
+    }
+
+    if let Some(history) = cli.history.as_ref() {
+        return history::run_history(history);
+    }
+
+    if let Some(usage) = cli.usage.as_ref() {
+        return usage::run_usage(usage);
+    }
+
+    if let Some(transcript) = cli.transcript.as_ref() {
+        return transcript::run_transcript(transcript);
+    }
+
+    if let Some(status_rules) = cli.status_rules.as_ref() {
+        return status_rules::run_status_rules(status_rules);
+    }
+
     if cli.doctor {
         return run_doctor(&cli);
     }
@@ -608,10 +983,18 @@ mod tests {
                 benchmark_warn_regression_pct: None,
                 doctor: false,
                 doctor_json_output: false,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: false,
                 cleanup_sessions_apply: false,
                 cleanup_sessions_include_stale: false,
                 cleanup_sessions_include_attached: false,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -644,10 +1027,18 @@ mod tests {
                 benchmark_warn_regression_pct: None,
                 doctor: false,
                 doctor_json_output: false,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: false,
                 cleanup_sessions_apply: false,
                 cleanup_sessions_include_stale: false,
                 cleanup_sessions_include_attached: false,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -682,10 +1073,18 @@ mod tests {
                 benchmark_warn_regression_pct: None,
                 doctor: false,
                 doctor_json_output: false,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: false,
                 cleanup_sessions_apply: false,
                 cleanup_sessions_include_stale: false,
                 cleanup_sessions_include_attached: false,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -728,10 +1127,18 @@ mod tests {
                 benchmark_warn_regression_pct: Some(25),
                 doctor: false,
                 doctor_json_output: false,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: false,
                 cleanup_sessions_apply: false,
                 cleanup_sessions_include_stale: false,
                 cleanup_sessions_include_attached: false,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -782,10 +1189,18 @@ mod tests {
                 benchmark_warn_regression_pct: None,
                 doctor: false,
                 doctor_json_output: false,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: true,
                 cleanup_sessions_apply: true,
                 cleanup_sessions_include_stale: true,
                 cleanup_sessions_include_attached: true,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -819,10 +1234,18 @@ mod tests {
                 benchmark_warn_regression_pct: None,
                 doctor: true,
                 doctor_json_output: true,
+                doctor_fix: false,
+                doctor_fix_yes: false,
                 cleanup_sessions: false,
                 cleanup_sessions_apply: false,
                 cleanup_sessions_include_stale: false,
                 cleanup_sessions_include_attached: false,
+                task: None,
+                agent: None,
+                history: None,
+                usage: None,
+                transcript: None,
+                status_rules: None,
             }
         );
     }
@@ -838,6 +1261,159 @@ mod tests {
         assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
     }
 
+    #[test]
+    fn cli_parser_reads_doctor_fix_flags() {
+        let parsed = parse_cli_args(vec![
+            "doctor".to_string(),
+            "--fix".to_string(),
+            "--yes".to_string(),
+            "--json".to_string(),
+        ])
+        .expect("doctor fix arguments should parse");
+        assert!(parsed.doctor && parsed.doctor_fix && parsed.doctor_fix_yes);
+        assert!(parsed.doctor_json_output);
+
+        for args in [
+            vec!["--fix"],
+            vec!["doctor", "--yes"],
+            vec!["doctor", "--fix", "--json"],
+        ] {
+            let error = parse_cli_args(args.into_iter().map(str::to_string))
+                .expect_err("invalid doctor fix flags should fail");
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn cli_parser_reads_task_subcommand() {
+        let parsed = parse_cli_args(vec![
+            "task".to_string(),
+            "list".to_string(),
+            "--json".to_string(),
+        ])
+        .expect("task arguments should parse");
+
+        assert_eq!(
+            parsed.task,
+            Some(super::task::TaskCliArgs {
+                command: super::task::TaskCommand::List,
+                json_output: true,
+            })
+        );
+        assert!(!parsed.doctor_json_output);
+    }
+
+    #[test]
+    fn cli_parser_rejects_task_combined_with_other_modes() {
+        let error = parse_cli_args(vec![
+            "--print-hello".to_string(),
+            "task".to_string(),
+            "list".to_string(),
+        ])
+        .expect_err("task should not combine with other modes");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn cli_parser_reads_history_subcommand() {
+        let parsed = parse_cli_args(vec![
+            "history".to_string(),
+            "flaky-tests".to_string(),
+            "--since".to_string(),
+            "2h".to_string(),
+        ])
+        .expect("history arguments should parse");
+
+        assert_eq!(
+            parsed.history,
+            Some(super::history::HistoryCliArgs {
+                task: Some("flaky-tests".to_string()),
+                since_secs: 2 * 60 * 60,
+                json_output: false,
+            })
+        );
+
+        let error = parse_cli_args(vec!["doctor".to_string(), "history".to_string()])
+            .expect_err("history should not combine with other modes");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn cli_parser_reads_usage_subcommand() {
+        let parsed = parse_cli_args(vec![
+            "usage".to_string(),
+            "--since".to_string(),
+            "7d".to_string(),
+            "--json".to_string(),
+        ])
+        .expect("usage arguments should parse");
+
+        assert_eq!(
+            parsed.usage,
+            Some(super::usage::UsageCliArgs {
+                task: None,
+                since_secs: Some(7 * 24 * 60 * 60),
+                json_output: true,
+            })
+        );
+
+        let error = parse_cli_args(vec!["doctor".to_string(), "usage".to_string()])
+            .expect_err("usage should not combine with other modes");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn cli_parser_reads_transcript_subcommand() {
+        let parsed = parse_cli_args(vec![
+            "transcript".to_string(),
+            "feature-a".to_string(),
+            "--format".to_string(),
+            "ansi".to_string(),
+        ])
+        .expect("transcript arguments should parse");
+
+        assert_eq!(
+            parsed.transcript,
+            Some(super::transcript::TranscriptCliArgs {
+                task: "feature-a".to_string(),
+                session: None,
+                format: crate::application::agent_runtime::transcript::TranscriptFormat::Ansi,
+                output_path: None,
+                list: false,
+                json_output: false,
+            })
+        );
+
+        let error = parse_cli_args(vec![
+            "doctor".to_string(),
+            "transcript".to_string(),
+            "feature-a".to_string(),
+        ])
+        .expect_err("transcript should not combine with other modes");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn cli_parser_reads_agent_subcommand() {
+        let parsed = parse_cli_args(vec![
+            "agent".to_string(),
+            "status".to_string(),
+            "flaky-tests".to_string(),
+            "--json".to_string(),
+        ])
+        .expect("agent arguments should parse");
+
+        assert_eq!(
+            parsed.agent,
+            Some(super::agent::AgentCliArgs {
+                command: super::agent::AgentCommand::Status,
+                target: "flaky-tests".to_string(),
+                json_output: true,
+            })
+        );
+        assert_eq!(parsed.task, None);
+    }
+
     #[test]
     fn doctor_exit_code_is_zero_for_clean_report() {
         let report = DoctorReport::from_findings(Vec::new());
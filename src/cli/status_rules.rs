@@ -0,0 +1,335 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::application::agent_runtime::evaluate_capture_change;
+use crate::application::agent_runtime::status_rules::StatusRule;
+use crate::domain::AgentType;
+use crate::infrastructure::task_manifest::workspace_status_name;
+
+use super::{EXIT_FAILURE, EXIT_NOT_FOUND, invalid_input, required_value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StatusRulesCliArgs {
+    pub(crate) capture_path: PathBuf,
+    /// Only rules for this agent id apply, every rule when unset.
+    pub(crate) agent: Option<String>,
+    /// Session log whose last record feeds `jsonl` rules.
+    pub(crate) jsonl_path: Option<PathBuf>,
+    pub(crate) json_output: bool,
+}
+
+pub(crate) fn parse_status_rules_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<StatusRulesCliArgs> {
+    match args.next().as_deref() {
+        Some("test") => {}
+        Some(other) => {
+            return Err(invalid_input(format!(
+                "unsupported status-rules command `{other}`, expected `test`"
+            )));
+        }
+        None => return Err(invalid_input("status-rules requires a command (`test`)")),
+    }
+
+    let mut capture_path = None;
+    let mut agent = None;
+    let mut jsonl_path = None;
+    let mut json_output = false;
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--agent" => agent = Some(required_value(args, "--agent", "an agent id")?),
+            "--jsonl" => {
+                jsonl_path = Some(PathBuf::from(required_value(
+                    args,
+                    "--jsonl",
+                    "a session log path",
+                )?));
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!(
+                    "unknown status-rules flag `{value}`"
+                )));
+            }
+            _ if capture_path.is_some() => {
+                return Err(invalid_input(
+                    "status-rules test accepts a single capture file",
+                ));
+            }
+            _ => capture_path = Some(PathBuf::from(argument)),
+        }
+    }
+
+    let Some(capture_path) = capture_path else {
+        return Err(invalid_input("status-rules test requires a capture file"));
+    };
+    Ok(StatusRulesCliArgs {
+        capture_path,
+        agent,
+        jsonl_path,
+        json_output,
+    })
+}
+
+enum StatusRulesCliFailure {
+    UnknownAgent(String),
+    Other(String),
+}
+
+impl StatusRulesCliFailure {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownAgent(_) => "unknown_agent",
+            Self::Other(_) => "failed",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::UnknownAgent(agent) => format!("unknown agent '{agent}'"),
+            Self::Other(message) => message.clone(),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::UnknownAgent(_) => EXIT_NOT_FOUND,
+            Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StatusRulesTestOutput {
+    capture: String,
+    agent: Option<String>,
+    /// Name of the first matching rule.
+    fired: Option<String>,
+    rules: Vec<StatusRuleView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StatusRuleView {
+    name: String,
+    source: &'static str,
+    priority: i32,
+    agent: Option<String>,
+    status: &'static str,
+    attention: Option<&'static str>,
+    /// The pane line or record fields the rule matched.
+    matched: Option<String>,
+    /// Why the rule was not checked, e.g. it is for another agent.
+    skipped: Option<String>,
+}
+
+impl StatusRuleView {
+    fn evaluate(
+        rule: &StatusRule,
+        agent: Option<&AgentType>,
+        pane: &str,
+        record: Option<&Value>,
+    ) -> Self {
+        let skipped = match (agent, rule.agent.as_ref()) {
+            (Some(agent), Some(rule_agent)) if !rule.applies_to(agent) => {
+                Some(format!("for agent {}", rule_agent.marker()))
+            }
+            _ if rule.source() == "jsonl" && record.is_none() => Some("needs --jsonl".to_string()),
+            _ => None,
+        };
+        let matched = skipped
+            .is_none()
+            .then(|| rule.evaluate_for_any_agent(Some(pane), record))
+            .flatten();
+        Self {
+            name: rule.name.clone(),
+            source: rule.source(),
+            priority: rule.priority,
+            agent: rule.agent.as_ref().map(|agent| agent.marker().to_string()),
+            status: workspace_status_name(rule.status),
+            attention: rule.attention.map(|attention| attention.config_key()),
+            matched,
+            skipped,
+        }
+    }
+}
+
+pub(crate) fn run_status_rules(cli: &StatusRulesCliArgs) -> std::io::Result<()> {
+    match execute_status_rules_test(cli) {
+        Ok(output) => {
+            print_status_rules_test(cli.json_output, &output);
+            Ok(())
+        }
+        Err(failure) => super::exit_with_failure(
+            cli.json_output,
+            "status-rules",
+            failure.code(),
+            failure.message().as_str(),
+            failure.exit_code(),
+        ),
+    }
+}
+
+fn execute_status_rules_test(
+    cli: &StatusRulesCliArgs,
+) -> Result<StatusRulesTestOutput, StatusRulesCliFailure> {
+    let loaded = super::load_cli_config().map_err(StatusRulesCliFailure::Other)?;
+    let agent = cli
+        .agent
+        .as_deref()
+        .map(|id| {
+            loaded
+                .agents
+                .from_marker(id.trim())
+                .ok_or_else(|| StatusRulesCliFailure::UnknownAgent(id.to_string()))
+        })
+        .transpose()?;
+    let raw = fs::read_to_string(&cli.capture_path).map_err(|error| {
+        StatusRulesCliFailure::Other(format!(
+            "read {} failed: {error}",
+            cli.capture_path.display()
+        ))
+    })?;
+    // Strip escapes the way status polling does, so `capture-pane -e` dumps work.
+    let pane = evaluate_capture_change(None, raw.as_str()).cleaned_output;
+    let record = cli
+        .jsonl_path
+        .as_ref()
+        .map(|path| last_json_record(path))
+        .transpose()?;
+
+    let rules = loaded
+        .status_rules
+        .iter()
+        .map(|rule| StatusRuleView::evaluate(rule, agent.as_ref(), pane.as_str(), record.as_ref()))
+        .collect::<Vec<_>>();
+    Ok(StatusRulesTestOutput {
+        capture: cli.capture_path.to_string_lossy().into_owned(),
+        agent: agent.as_ref().map(|agent| agent.marker().to_string()),
+        fired: rules
+            .iter()
+            .find(|rule| rule.matched.is_some())
+            .map(|rule| rule.name.clone()),
+        rules,
+    })
+}
+
+fn last_json_record(path: &Path) -> Result<Value, StatusRulesCliFailure> {
+    let content = fs::read_to_string(path).map_err(|error| {
+        StatusRulesCliFailure::Other(format!("read {} failed: {error}", path.display()))
+    })?;
+    content
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .ok_or_else(|| {
+            StatusRulesCliFailure::Other(format!("{} has no JSON records", path.display()))
+        })
+}
+
+fn print_status_rules_test(json_output: bool, output: &StatusRulesTestOutput) {
+    if json_output {
+        if let Ok(encoded) = serde_json::to_string_pretty(output) {
+            println!("{encoded}");
+        }
+        return;
+    }
+
+    println!(
+        "status rules for {} ({})",
+        output.capture,
+        output
+            .agent
+            .as_deref()
+            .map_or_else(|| "any agent".to_string(), |agent| format!("agent {agent}"))
+    );
+    let name_width = output
+        .rules
+        .iter()
+        .map(|rule| rule.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for rule in &output.rules {
+        let marker = if output.fired.as_deref() == Some(rule.name.as_str()) {
+            "=>"
+        } else {
+            "  "
+        };
+        let target = match rule.attention {
+            Some(attention) => format!("{} ({attention})", rule.status),
+            None => rule.status.to_string(),
+        };
+        let result = match (&rule.skipped, &rule.matched) {
+            (Some(reason), _) => format!("skipped, {reason}"),
+            (None, Some(matched)) => format!("matched: {matched}"),
+            (None, None) => "no match".to_string(),
+        };
+        println!(
+            "{marker} {:>4}  {:<name_width$}  {:<8}  {target}  {result}",
+            rule.priority, rule.name, rule.source
+        );
+    }
+    match output
+        .fired
+        .as_deref()
+        .and_then(|fired| output.rules.iter().find(|rule| rule.name == fired))
+    {
+        Some(rule) => println!("fires: {} -> {}", rule.name, rule.status),
+        None => println!("no rule fires, status follows session activity"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{StatusRulesCliArgs, parse_status_rules_args};
+
+    fn parse(args: &[&str]) -> std::io::Result<StatusRulesCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_status_rules_args(&mut args)
+    }
+
+    #[test]
+    fn parse_status_rules_test_reads_capture_agent_and_jsonl() {
+        assert_eq!(
+            parse(&["test", "pane.txt"]).expect("status-rules should parse"),
+            StatusRulesCliArgs {
+                capture_path: PathBuf::from("pane.txt"),
+                agent: None,
+                jsonl_path: None,
+                json_output: false,
+            }
+        );
+        assert_eq!(
+            parse(&[
+                "test",
+                "pane.txt",
+                "--agent",
+                "codex",
+                "--jsonl",
+                "rollout.jsonl",
+                "--json",
+            ])
+            .expect("status-rules should parse"),
+            StatusRulesCliArgs {
+                capture_path: PathBuf::from("pane.txt"),
+                agent: Some("codex".to_string()),
+                jsonl_path: Some(PathBuf::from("rollout.jsonl")),
+                json_output: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_status_rules_rejects_bad_input() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["list"]).is_err());
+        assert!(parse(&["test"]).is_err());
+        assert!(parse(&["test", "a", "b"]).is_err());
+        assert!(parse(&["test", "a", "--agent"]).is_err());
+        assert!(parse(&["test", "a", "--verbose"]).is_err());
+    }
+}
@@ -0,0 +1,1306 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::application::agent_runtime::session_name_for_task_worktree;
+use crate::application::hooks::{
+    CommandHookRunner, HookContext, HookInvocation, HookRunner, create_with_hooks,
+    hook_failure_warnings, run_between_hooks_with,
+};
+use crate::application::recipes::{Recipe, find_recipe, load_recipes, write_recipe_markers};
+use crate::application::task_lifecycle::{
+    AddWorktreeToTaskRequest, CreateBaseTaskRequest, CreateTaskRequest, CreateTaskResult,
+    DeleteTaskRequest, TaskArchive, TaskBranchSource, TaskLifecycleError, add_worktree_to_task,
+    archive_task, create_base_task, create_task, delete_task, ensure_project_matches_pull_request,
+    list_task_archives, parse_github_pull_request_url, resolve_pull_request_branch_name_with_gh,
+    restore_task, task_lifecycle_error_message,
+};
+use crate::application::workspace_lifecycle::{
+    CommandGitRunner, CommandSetupCommandRunner, CommandSetupScriptRunner,
+    WORKSPACE_LAUNCH_PROMPT_FILE,
+};
+use crate::domain::{AgentRegistry, AgentType, Task};
+use crate::infrastructure::config::{GroveConfig, HookEvent, ProjectConfig};
+use crate::infrastructure::event_log::now_millis;
+use crate::infrastructure::paths::refer_to_same_location;
+use crate::infrastructure::task_manifest::workspace_status_name;
+
+use super::history::record_task_event;
+use super::{
+    EXIT_FAILURE, EXIT_NOT_FOUND, format_age, invalid_input, load_tasks, print_json, required_value,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TaskCliArgs {
+    pub(crate) command: TaskCommand,
+    pub(crate) json_output: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TaskCommand {
+    Create {
+        name: Option<String>,
+        projects: Vec<String>,
+        pull_request_url: Option<String>,
+        agent: Option<String>,
+        prompt_file: Option<PathBuf>,
+        base: bool,
+        recipe: Option<String>,
+        variables: BTreeMap<String, String>,
+    },
+    List,
+    AddWorktree {
+        task: String,
+        project: String,
+        agent: Option<String>,
+    },
+    Delete {
+        task: String,
+        keep_branch: bool,
+    },
+    Archive {
+        task: String,
+    },
+    Restore {
+        task: String,
+    },
+    Archives,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TaskCliFailure {
+    Lifecycle(TaskLifecycleError),
+    NotFound(String),
+    Other(String),
+}
+
+impl TaskCliFailure {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Lifecycle(error) => task_lifecycle_error_code(error),
+            Self::NotFound(_) => "not_found",
+            Self::Other(_) => "failed",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Lifecycle(error) => task_lifecycle_error_message(error),
+            Self::NotFound(message) | Self::Other(message) => message.clone(),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::Lifecycle(error) => task_lifecycle_exit_code(error),
+            Self::NotFound(_) => EXIT_NOT_FOUND,
+            Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+impl From<TaskLifecycleError> for TaskCliFailure {
+    fn from(error: TaskLifecycleError) -> Self {
+        Self::Lifecycle(error)
+    }
+}
+
+fn task_lifecycle_error_code(error: &TaskLifecycleError) -> &'static str {
+    match error {
+        TaskLifecycleError::EmptyTaskName => "empty_task_name",
+        TaskLifecycleError::InvalidTaskName => "invalid_task_name",
+        TaskLifecycleError::EmptyRepositories => "empty_repositories",
+        TaskLifecycleError::HomeDirectoryUnavailable => "home_directory_unavailable",
+        TaskLifecycleError::RepositoryNameUnavailable => "repository_name_unavailable",
+        TaskLifecycleError::BaseBranchDetectionFailed(_) => "base_branch_detection_failed",
+        TaskLifecycleError::TaskInvalid(_) => "task_invalid",
+        TaskLifecycleError::TaskManifest(_) => "task_manifest",
+        TaskLifecycleError::BaseTaskCannotAddWorktrees => "base_task_cannot_add_worktrees",
+        TaskLifecycleError::TaskAlreadyHasRepository(_) => "task_already_has_repository",
+        TaskLifecycleError::GitCommandFailed(_) => "git_command_failed",
+        TaskLifecycleError::Io(_) => "io",
+        TaskLifecycleError::HookVetoed(_) => "hook_vetoed",
+    }
+}
+
+/// Each lifecycle error gets its own exit code so scripts can branch on it.
+fn task_lifecycle_exit_code(error: &TaskLifecycleError) -> i32 {
+    match error {
+        TaskLifecycleError::EmptyTaskName => 10,
+        TaskLifecycleError::InvalidTaskName => 11,
+        TaskLifecycleError::EmptyRepositories => 12,
+        TaskLifecycleError::HomeDirectoryUnavailable => 13,
+        TaskLifecycleError::RepositoryNameUnavailable => 14,
+        TaskLifecycleError::BaseBranchDetectionFailed(_) => 15,
+        TaskLifecycleError::TaskInvalid(_) => 16,
+        TaskLifecycleError::TaskManifest(_) => 17,
+        TaskLifecycleError::BaseTaskCannotAddWorktrees => 18,
+        TaskLifecycleError::TaskAlreadyHasRepository(_) => 19,
+        TaskLifecycleError::GitCommandFailed(_) => 20,
+        TaskLifecycleError::Io(_) => 21,
+        TaskLifecycleError::HookVetoed(_) => 22,
+    }
+}
+
+pub(crate) fn parse_task_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<TaskCliArgs> {
+    let Some(action) = args.next() else {
+        return Err(invalid_input(
+            "task requires an action (`create`, `list`, `add-worktree`, `delete`, `archive`, `restore`, `archives`)",
+        ));
+    };
+
+    let mut json_output = false;
+    let mut positionals = Vec::new();
+    let mut projects = Vec::new();
+    let mut pull_request_url = None;
+    let mut agent = None;
+    let mut prompt_file = None;
+    let mut base = false;
+    let mut keep_branch = false;
+    let mut recipe = None;
+    let mut variables = BTreeMap::new();
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--project" => projects.push(required_value(args, "--project", "a project")?),
+            "--pr" => {
+                pull_request_url = Some(required_value(args, "--pr", "a pull request URL")?);
+            }
+            "--agent" => agent = Some(required_value(args, "--agent", "an agent id")?),
+            "--prompt-file" => {
+                prompt_file = Some(PathBuf::from(required_value(
+                    args,
+                    "--prompt-file",
+                    "a file path",
+                )?));
+            }
+            "--base" => base = true,
+            "--keep-branch" => keep_branch = true,
+            "--recipe" => recipe = Some(required_value(args, "--recipe", "a recipe name")?),
+            "--var" => {
+                let assignment = required_value(args, "--var", "a key=value pair")?;
+                let Some((key, value)) = assignment.split_once('=') else {
+                    return Err(invalid_input(format!(
+                        "--var expects key=value, got `{assignment}`"
+                    )));
+                };
+                variables.insert(key.trim().to_string(), value.to_string());
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!("unknown task flag `{value}`")));
+            }
+            _ => positionals.push(argument),
+        }
+    }
+
+    let command = match action.as_str() {
+        "create" => {
+            if keep_branch {
+                return Err(invalid_input("--keep-branch requires `task delete`"));
+            }
+            if positionals.len() > 1 {
+                return Err(invalid_input("task create accepts a single task name"));
+            }
+            if projects.is_empty() {
+                return Err(invalid_input("task create requires --project"));
+            }
+            if (pull_request_url.is_some() || base) && projects.len() > 1 {
+                return Err(invalid_input(
+                    "--pr and --base require exactly one --project",
+                ));
+            }
+            if pull_request_url.is_some() && base {
+                return Err(invalid_input("--pr cannot be combined with --base"));
+            }
+            let name = positionals.pop();
+            if name.is_none() && pull_request_url.is_none() && !base {
+                return Err(invalid_input("task create requires a task name"));
+            }
+            if name.is_some() && base {
+                return Err(invalid_input("--base names the task after the project"));
+            }
+            if recipe.is_some() && (base || prompt_file.is_some()) {
+                return Err(invalid_input(
+                    "--recipe cannot be combined with --base or --prompt-file",
+                ));
+            }
+            if recipe.is_none() && !variables.is_empty() {
+                return Err(invalid_input("--var requires --recipe"));
+            }
+            TaskCommand::Create {
+                name,
+                projects,
+                pull_request_url,
+                agent,
+                prompt_file,
+                base,
+                recipe,
+                variables,
+            }
+        }
+        "list" => {
+            if !positionals.is_empty()
+                || !projects.is_empty()
+                || pull_request_url.is_some()
+                || agent.is_some()
+                || prompt_file.is_some()
+                || base
+                || keep_branch
+                || recipe.is_some()
+                || !variables.is_empty()
+            {
+                return Err(invalid_input("task list only accepts --json"));
+            }
+            TaskCommand::List
+        }
+        "add-worktree" => {
+            if pull_request_url.is_some()
+                || prompt_file.is_some()
+                || base
+                || keep_branch
+                || recipe.is_some()
+                || !variables.is_empty()
+            {
+                return Err(invalid_input(
+                    "task add-worktree only accepts --project, --agent and --json",
+                ));
+            }
+            let [task] = <[String; 1]>::try_from(positionals)
+                .map_err(|_| invalid_input("task add-worktree requires a single task"))?;
+            let [project] = <[String; 1]>::try_from(projects)
+                .map_err(|_| invalid_input("task add-worktree requires a single --project"))?;
+            TaskCommand::AddWorktree {
+                task,
+                project,
+                agent,
+            }
+        }
+        "delete" => {
+            if !projects.is_empty()
+                || pull_request_url.is_some()
+                || agent.is_some()
+                || prompt_file.is_some()
+                || base
+                || recipe.is_some()
+                || !variables.is_empty()
+            {
+                return Err(invalid_input(
+                    "task delete only accepts --keep-branch and --json",
+                ));
+            }
+            let [task] = <[String; 1]>::try_from(positionals)
+                .map_err(|_| invalid_input("task delete requires a single task"))?;
+            TaskCommand::Delete { task, keep_branch }
+        }
+        "archive" | "restore" | "archives" => {
+            if !projects.is_empty()
+                || pull_request_url.is_some()
+                || agent.is_some()
+                || prompt_file.is_some()
+                || base
+                || keep_branch
+                || recipe.is_some()
+                || !variables.is_empty()
+            {
+                return Err(invalid_input(format!("task {action} only accepts --json")));
+            }
+            if action == "archives" {
+                if !positionals.is_empty() {
+                    return Err(invalid_input("task archives does not take a task"));
+                }
+                TaskCommand::Archives
+            } else {
+                let [task] = <[String; 1]>::try_from(positionals)
+                    .map_err(|_| invalid_input(format!("task {action} requires a single task")))?;
+                if action == "archive" {
+                    TaskCommand::Archive { task }
+                } else {
+                    TaskCommand::Restore { task }
+                }
+            }
+        }
+        other => {
+            return Err(invalid_input(format!("unsupported task action `{other}`")));
+        }
+    };
+
+    Ok(TaskCliArgs {
+        command,
+        json_output,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskView {
+    name: String,
+    slug: String,
+    root_path: String,
+    branch: String,
+    worktrees: Vec<WorktreeView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recipe: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct WorktreeView {
+    repository_name: String,
+    repository_path: String,
+    path: String,
+    branch: String,
+    base_branch: Option<String>,
+    agent: String,
+    status: String,
+    session_name: String,
+    pull_requests: Vec<PullRequestView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct PullRequestView {
+    number: u64,
+    url: String,
+}
+
+impl TaskView {
+    fn from_task(task: &Task) -> Self {
+        Self {
+            name: task.name.clone(),
+            slug: task.slug.clone(),
+            root_path: task.root_path.to_string_lossy().into_owned(),
+            branch: task.branch.clone(),
+            worktrees: task
+                .worktrees
+                .iter()
+                .map(|worktree| WorktreeView {
+                    repository_name: worktree.repository_name.clone(),
+                    repository_path: worktree.repository_path.to_string_lossy().into_owned(),
+                    path: worktree.path.to_string_lossy().into_owned(),
+                    branch: worktree.branch.clone(),
+                    base_branch: worktree.base_branch.clone(),
+                    agent: worktree.agent.marker().to_string(),
+                    status: workspace_status_name(worktree.status).to_string(),
+                    session_name: session_name_for_task_worktree(
+                        task.slug.as_str(),
+                        worktree.repository_name.as_str(),
+                    ),
+                    pull_requests: worktree
+                        .pull_requests
+                        .iter()
+                        .map(|pull_request| PullRequestView {
+                            number: pull_request.number,
+                            url: pull_request.url.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            recipe: task.recipe.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskMutationOutput {
+    task: TaskView,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    added_worktree_path: Option<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskDeleteOutput {
+    deleted: String,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskArchiveOutput {
+    archived: String,
+    archive_dir: String,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TaskRestoreOutput {
+    task: TaskView,
+    resume: Vec<ResumeView>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ResumeView {
+    path: String,
+    title: String,
+    agent: String,
+    command: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ArchiveView {
+    name: String,
+    slug: String,
+    archived_at_ms: u64,
+    repositories: Vec<String>,
+    resumable_agents: usize,
+}
+
+impl ArchiveView {
+    fn from_archive(archive: &TaskArchive, agents: &AgentRegistry) -> Self {
+        Self {
+            name: archive.name.clone(),
+            slug: archive.slug.clone(),
+            archived_at_ms: archive.archived_at_ms,
+            repositories: archive
+                .worktrees
+                .iter()
+                .map(|worktree| worktree.repository_name.clone())
+                .collect(),
+            resumable_agents: archive.resumable_tabs(agents).len(),
+        }
+    }
+}
+
+pub(crate) fn run_task(cli: &TaskCliArgs) -> std::io::Result<()> {
+    let failure = match execute_task_command(cli) {
+        Ok(()) => return Ok(()),
+        Err(failure) => failure,
+    };
+    super::exit_with_failure(
+        cli.json_output,
+        "task",
+        failure.code(),
+        failure.message().as_str(),
+        failure.exit_code(),
+    )
+}
+
+fn execute_task_command(cli: &TaskCliArgs) -> Result<(), TaskCliFailure> {
+    let loaded = super::load_cli_config().map_err(TaskCliFailure::Other)?;
+
+    match &cli.command {
+        TaskCommand::Create {
+            name,
+            projects,
+            pull_request_url,
+            agent: agent_id,
+            prompt_file,
+            base,
+            recipe,
+            variables,
+        } => {
+            let agent = resolve_agent(&loaded.agents, agent_id.as_deref())?;
+            let projects = projects
+                .iter()
+                .map(|project| resolve_project(&loaded.config, project))
+                .collect::<Result<Vec<_>, _>>()?;
+            let recipe = recipe
+                .as_deref()
+                .map(|name| load_recipe(&projects, &loaded.agents, name))
+                .transpose()?;
+            let mut prompt = prompt_file.as_deref().map(read_prompt_file).transpose()?;
+            if let Some(recipe) = recipe.as_ref() {
+                prompt = Some(
+                    recipe
+                        .render_prompt(variables)
+                        .map_err(TaskCliFailure::Other)?,
+                );
+            }
+            let result = if *base {
+                create_base_task_for_project(&loaded.config, &loaded.path, &projects[0], agent)?
+            } else {
+                let mut request = create_task_request(
+                    name.as_deref(),
+                    projects,
+                    pull_request_url.as_deref(),
+                    agent.clone(),
+                )?;
+                if let Some(recipe) = recipe.as_ref() {
+                    recipe
+                        .apply_to_request(&mut request, &loaded.agents)
+                        .map_err(TaskCliFailure::Other)?;
+                    if agent_id.is_some() {
+                        request.agent = agent;
+                    }
+                }
+                request.validate()?;
+                let (result, outcomes) = create_with_hooks(
+                    &request.repositories,
+                    HookContext::for_create_request(&request),
+                    || {
+                        create_task(
+                            &request,
+                            &loaded.agents,
+                            &CommandGitRunner,
+                            &CommandSetupScriptRunner,
+                            &CommandSetupCommandRunner,
+                        )
+                    },
+                    |result| &result.task,
+                );
+                let mut result = result?;
+                result.warnings.extend(hook_failure_warnings(&outcomes));
+                result
+            };
+            record_task_event(&result.task.slug, "created", None);
+            let mut warnings = result.warnings;
+            match (recipe.as_ref(), prompt.as_deref()) {
+                (Some(recipe), prompt) => warnings.extend(write_recipe_markers(
+                    &result.task,
+                    recipe,
+                    prompt.unwrap_or_default(),
+                )),
+                (None, Some(prompt)) => warnings.extend(write_launch_prompts(&result.task, prompt)),
+                (None, None) => {}
+            }
+            print_mutation(
+                cli.json_output,
+                &TaskMutationOutput {
+                    task: TaskView::from_task(&result.task),
+                    added_worktree_path: None,
+                    warnings,
+                },
+                "created",
+            )
+        }
+        TaskCommand::List => {
+            let tasks = load_tasks(&loaded.agents).map_err(TaskCliFailure::from)?;
+            if cli.json_output {
+                let views = tasks.iter().map(TaskView::from_task).collect::<Vec<_>>();
+                print_json(&views).map_err(TaskCliFailure::Other)
+            } else {
+                print_task_list(tasks.as_slice());
+                Ok(())
+            }
+        }
+        TaskCommand::AddWorktree {
+            task,
+            project,
+            agent,
+        } => {
+            let task = resolve_task(
+                load_tasks(&loaded.agents).map_err(TaskCliFailure::from)?,
+                task,
+            )?;
+            let request = AddWorktreeToTaskRequest {
+                task,
+                repository: resolve_project(&loaded.config, project)?,
+                agent: resolve_agent(&loaded.agents, agent.as_deref())?,
+            };
+            let (result, outcomes) = create_with_hooks(
+                std::slice::from_ref(&request.repository),
+                HookContext::for_task(&request.task),
+                || {
+                    add_worktree_to_task(
+                        &request,
+                        &loaded.agents,
+                        &CommandGitRunner,
+                        &CommandSetupScriptRunner,
+                        &CommandSetupCommandRunner,
+                    )
+                },
+                |result| &result.task,
+            );
+            let mut result = result?;
+            result.warnings.extend(hook_failure_warnings(&outcomes));
+            record_task_event(
+                &result.task.slug,
+                "worktree_added",
+                Some(&result.added_worktree_path),
+            );
+            print_mutation(
+                cli.json_output,
+                &TaskMutationOutput {
+                    task: TaskView::from_task(&result.task),
+                    added_worktree_path: Some(
+                        result.added_worktree_path.to_string_lossy().into_owned(),
+                    ),
+                    warnings: result.warnings,
+                },
+                "updated",
+            )
+        }
+        TaskCommand::Delete { task, keep_branch } => {
+            let task = resolve_task(
+                load_tasks(&loaded.agents).map_err(TaskCliFailure::from)?,
+                task,
+            )?;
+            let slug = task.slug.clone();
+            let warnings = delete_task_with_hooks(
+                &loaded.config.projects,
+                DeleteTaskRequest {
+                    task,
+                    delete_local_branch: !keep_branch,
+                    kill_tmux_sessions: true,
+                },
+                &CommandHookRunner,
+                delete_task,
+            )?;
+            record_task_event(&slug, "deleted", None);
+            let output = TaskDeleteOutput {
+                deleted: slug,
+                warnings,
+            };
+            if cli.json_output {
+                return print_json(&output).map_err(TaskCliFailure::Other);
+            }
+            println!("deleted {}", output.deleted);
+            print_warnings(output.warnings.as_slice());
+            Ok(())
+        }
+        TaskCommand::Archive { task } => {
+            let task = resolve_task(
+                load_tasks(&loaded.agents).map_err(TaskCliFailure::from)?,
+                task,
+            )?;
+            let result = archive_task(&task, &loaded.agents)?;
+            record_task_event(&task.slug, "archived", None);
+            let output = TaskArchiveOutput {
+                archived: task.slug,
+                archive_dir: result.archive_dir.to_string_lossy().into_owned(),
+                warnings: result.warnings,
+            };
+            if cli.json_output {
+                return print_json(&output).map_err(TaskCliFailure::Other);
+            }
+            println!("archived {} ({})", output.archived, output.archive_dir);
+            print_warnings(output.warnings.as_slice());
+            Ok(())
+        }
+        TaskCommand::Restore { task } => {
+            let archives = list_task_archives()?;
+            let Some(archive) = archives
+                .iter()
+                .find(|archive| archive.slug == *task)
+                .or_else(|| archives.iter().find(|archive| archive.name == *task))
+            else {
+                return Err(TaskCliFailure::NotFound(format!(
+                    "no archived task '{task}'"
+                )));
+            };
+            let result = restore_task(archive.slug.as_str(), &loaded.agents)?;
+            record_task_event(&result.task.slug, "restored", None);
+            let output = TaskRestoreOutput {
+                task: TaskView::from_task(&result.task),
+                resume: result
+                    .archive
+                    .resumable_tabs(&loaded.agents)
+                    .into_iter()
+                    .map(|(path, tab)| ResumeView {
+                        path: path.to_string_lossy().into_owned(),
+                        title: tab.title,
+                        agent: tab.agent.unwrap_or_default(),
+                        command: tab.resume_command.unwrap_or_default(),
+                    })
+                    .collect(),
+                warnings: result.warnings,
+            };
+            if cli.json_output {
+                return print_json(&output).map_err(TaskCliFailure::Other);
+            }
+            println!("restored {} ({})", output.task.slug, output.task.root_path);
+            for resume in &output.resume {
+                println!(
+                    "resume {}: cd {} && {}",
+                    resume.title, resume.path, resume.command
+                );
+            }
+            print_warnings(output.warnings.as_slice());
+            Ok(())
+        }
+        TaskCommand::Archives => {
+            let archives = list_task_archives()?;
+            let views = archives
+                .iter()
+                .map(|archive| ArchiveView::from_archive(archive, &loaded.agents))
+                .collect::<Vec<_>>();
+            if cli.json_output {
+                return print_json(&views).map_err(TaskCliFailure::Other);
+            }
+            print_archive_list(views.as_slice());
+            Ok(())
+        }
+    }
+}
+
+/// Deletes the task unless one of its projects' `pre-delete` hooks vetoes.
+fn delete_task_with_hooks(
+    projects: &[ProjectConfig],
+    request: DeleteTaskRequest,
+    hook_runner: &impl HookRunner,
+    delete: impl FnOnce(DeleteTaskRequest) -> (Result<(), String>, Vec<String>),
+) -> Result<Vec<String>, TaskCliFailure> {
+    let pre_delete_hooks = HookInvocation::for_task(HookEvent::PreDelete, projects, &request.task);
+    let (result, outcomes) = run_between_hooks_with(
+        hook_runner,
+        pre_delete_hooks,
+        || Ok(delete(request)),
+        |veto| {
+            Err(TaskCliFailure::Lifecycle(TaskLifecycleError::HookVetoed(
+                veto,
+            )))
+        },
+        |_| None,
+    );
+    let (result, mut warnings) = result?;
+    result.map_err(TaskCliFailure::Other)?;
+    warnings.extend(hook_failure_warnings(&outcomes));
+    Ok(warnings)
+}
+
+fn create_task_request(
+    name: Option<&str>,
+    repositories: Vec<ProjectConfig>,
+    pull_request_url: Option<&str>,
+    agent: AgentType,
+) -> Result<CreateTaskRequest, TaskCliFailure> {
+    let Some(pull_request_url) = pull_request_url else {
+        return Ok(CreateTaskRequest {
+            task_name: name.unwrap_or_default().trim().to_string(),
+            repositories,
+            agent,
+            branch_source: TaskBranchSource::BaseBranch,
+            recipe: None,
+        });
+    };
+
+    let parsed = parse_github_pull_request_url(pull_request_url).map_err(TaskCliFailure::Other)?;
+    ensure_project_matches_pull_request(&repositories[0].path, &parsed)
+        .map_err(TaskCliFailure::Other)?;
+    let branch_name =
+        resolve_pull_request_branch_name_with_gh(&parsed).map_err(TaskCliFailure::Other)?;
+    Ok(CreateTaskRequest {
+        task_name: name
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|| format!("pr-{}", parsed.number)),
+        repositories,
+        agent,
+        branch_source: TaskBranchSource::PullRequest {
+            number: parsed.number,
+            branch_name,
+        },
+        recipe: None,
+    })
+}
+
+fn create_base_task_for_project(
+    config: &GroveConfig,
+    config_path: &Path,
+    project: &ProjectConfig,
+    agent: AgentType,
+) -> Result<CreateTaskResult, TaskCliFailure> {
+    let configured = project.defaults.base_branch.trim();
+    let base_branch = if configured.is_empty() {
+        "main".to_string()
+    } else {
+        configured.to_string()
+    };
+    let context = HookContext {
+        task_name: Some(project.name.clone()),
+        branch: Some(base_branch.clone()),
+        agent: Some(agent.marker().to_string()),
+        ..HookContext::default()
+    };
+    let request = CreateBaseTaskRequest {
+        repository: project.clone(),
+        agent,
+        base_branch,
+    };
+    let (result, outcomes) = create_with_hooks(
+        std::slice::from_ref(project),
+        context,
+        || create_base_task(&request),
+        |result| &result.task,
+    );
+    let mut result = result?;
+    result.warnings.extend(hook_failure_warnings(&outcomes));
+
+    let mut hidden_base_project_paths = config.hidden_base_project_paths.clone();
+    hidden_base_project_paths.retain(|path| !refer_to_same_location(path, &project.path));
+    if hidden_base_project_paths.len() != config.hidden_base_project_paths.len() {
+        let projects_path = crate::infrastructure::config::projects_path_for(config_path);
+        crate::infrastructure::config::save_projects_to_path(
+            &projects_path,
+            &config.projects,
+            &config.task_order,
+            &config.attention_acks,
+            &hidden_base_project_paths,
+        )
+        .map_err(|error| {
+            TaskCliFailure::Other(format!(
+                "task created, but hidden-base state save failed: {error}"
+            ))
+        })?;
+    }
+
+    Ok(result)
+}
+
+fn resolve_agent(agents: &AgentRegistry, agent: Option<&str>) -> Result<AgentType, TaskCliFailure> {
+    let Some(agent) = agent else {
+        return Ok(AgentType::Codex);
+    };
+    agents
+        .from_marker(agent.trim())
+        .ok_or_else(|| TaskCliFailure::NotFound(format!("unknown agent '{agent}'")))
+}
+
+fn resolve_project(config: &GroveConfig, selector: &str) -> Result<ProjectConfig, TaskCliFailure> {
+    let selector_path = Path::new(selector);
+    config
+        .projects
+        .iter()
+        .find(|project| project.name == selector)
+        .or_else(|| {
+            config
+                .projects
+                .iter()
+                .find(|project| refer_to_same_location(&project.path, selector_path))
+        })
+        .cloned()
+        .ok_or_else(|| TaskCliFailure::NotFound(format!("unknown project '{selector}'")))
+}
+
+fn resolve_task(tasks: Vec<Task>, selector: &str) -> Result<Task, TaskCliFailure> {
+    let mut tasks = tasks;
+    let index = tasks
+        .iter()
+        .position(|task| task.slug == selector)
+        .or_else(|| tasks.iter().position(|task| task.name == selector))
+        .ok_or_else(|| TaskCliFailure::NotFound(format!("unknown task '{selector}'")))?;
+    Ok(tasks.swap_remove(index))
+}
+
+/// Recipes visible to the chosen projects, per-user ones included.
+fn load_recipe(
+    projects: &[ProjectConfig],
+    agents: &AgentRegistry,
+    name: &str,
+) -> Result<Recipe, TaskCliFailure> {
+    let recipes = load_recipes(projects, agents).map_err(TaskCliFailure::Other)?;
+    find_recipe(&recipes, name)
+        .cloned()
+        .map_err(TaskCliFailure::Other)
+}
+
+fn read_prompt_file(path: &Path) -> Result<String, TaskCliFailure> {
+    let raw = fs::read_to_string(path).map_err(|error| {
+        TaskCliFailure::Lifecycle(TaskLifecycleError::Io(format!(
+            "read prompt file {} failed: {error}",
+            path.display()
+        )))
+    })?;
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(TaskCliFailure::Other(format!(
+            "prompt file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Stores the launch prompt where the TUI and `grove agent start` pick it up.
+fn write_launch_prompts(task: &Task, prompt: &str) -> Vec<String> {
+    std::iter::once(task.root_path.as_path())
+        .chain(
+            task.worktrees
+                .iter()
+                .map(|worktree| worktree.path.as_path()),
+        )
+        .filter_map(|root| {
+            let prompt_path = root.join(WORKSPACE_LAUNCH_PROMPT_FILE);
+            let result = prompt_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&prompt_path, format!("{prompt}\n")));
+            result
+                .err()
+                .map(|error| format!("prompt write failed for {}: {error}", prompt_path.display()))
+        })
+        .collect()
+}
+
+fn print_mutation(
+    json_output: bool,
+    output: &TaskMutationOutput,
+    verb: &str,
+) -> Result<(), TaskCliFailure> {
+    if json_output {
+        return print_json(output).map_err(TaskCliFailure::Other);
+    }
+    println!("{verb} {} ({})", output.task.slug, output.task.root_path);
+    if let Some(path) = output.added_worktree_path.as_deref() {
+        println!("added worktree {path}");
+    }
+    print_warnings(output.warnings.as_slice());
+    Ok(())
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {warning}");
+    }
+}
+
+fn print_archive_list(archives: &[ArchiveView]) {
+    if archives.is_empty() {
+        println!("no archived tasks");
+        return;
+    }
+    let now_secs = now_millis() / 1000;
+    for archive in archives {
+        println!(
+            "{} archived {} ago ({})",
+            archive.slug,
+            format_age(Some(now_secs.saturating_sub(archive.archived_at_ms / 1000))),
+            archive.repositories.join(", ")
+        );
+    }
+}
+
+fn print_task_list(tasks: &[Task]) {
+    if tasks.is_empty() {
+        println!("no tasks");
+        return;
+    }
+    for task in tasks {
+        println!(
+            "{} [{}] {}",
+            task.slug,
+            task.branch,
+            task.root_path.display()
+        );
+        for worktree in &task.worktrees {
+            println!(
+                "  {} {} {} {}",
+                worktree.repository_name,
+                workspace_status_name(worktree.status),
+                worktree.agent.marker(),
+                worktree.path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        TaskCliArgs, TaskCliFailure, TaskCommand, TaskView, delete_task_with_hooks,
+        parse_task_args, task_lifecycle_exit_code,
+    };
+    use crate::application::hooks::CommandHookRunner;
+    use crate::application::task_lifecycle::{DeleteTaskRequest, TaskLifecycleError};
+    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::infrastructure::config::{HookConfig, HookEvent, ProjectConfig, ProjectDefaults};
+    use std::cell::Cell;
+    use std::collections::{BTreeMap, HashSet};
+    use std::path::PathBuf;
+
+    fn parse(args: &[&str]) -> std::io::Result<TaskCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_task_args(&mut args)
+    }
+
+    #[test]
+    fn parse_task_create_reads_all_options() {
+        let parsed = parse(&[
+            "create",
+            "flaky-tests",
+            "--project",
+            "grove",
+            "--project",
+            "/repos/site",
+            "--agent",
+            "claude",
+            "--prompt-file",
+            "/tmp/prompt.md",
+            "--json",
+        ])
+        .expect("create should parse");
+
+        assert_eq!(
+            parsed,
+            TaskCliArgs {
+                command: TaskCommand::Create {
+                    name: Some("flaky-tests".to_string()),
+                    projects: vec!["grove".to_string(), "/repos/site".to_string()],
+                    pull_request_url: None,
+                    agent: Some("claude".to_string()),
+                    prompt_file: Some(PathBuf::from("/tmp/prompt.md")),
+                    base: false,
+                    recipe: None,
+                    variables: BTreeMap::new(),
+                },
+                json_output: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_task_create_allows_pull_request_without_name() {
+        let parsed = parse(&[
+            "create",
+            "--project",
+            "grove",
+            "--pr",
+            "https://github.com/acme/grove/pull/7",
+        ])
+        .expect("pr create should parse");
+
+        assert!(matches!(
+            parsed.command,
+            TaskCommand::Create {
+                name: None,
+                pull_request_url: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_task_create_reads_recipe_variables() {
+        let parsed = parse(&[
+            "create",
+            "fix-login",
+            "--project",
+            "grove",
+            "--recipe",
+            "bugfix",
+            "--var",
+            "issue=#42",
+            "--var",
+            "area=auth = sessions",
+        ])
+        .expect("recipe create should parse");
+
+        assert!(matches!(
+            parsed.command,
+            TaskCommand::Create {
+                recipe: Some(ref recipe),
+                ref variables,
+                ..
+            } if recipe == "bugfix"
+                && variables
+                    == &BTreeMap::from([
+                        ("area".to_string(), "auth = sessions".to_string()),
+                        ("issue".to_string(), "#42".to_string()),
+                    ])
+        ));
+        assert!(parse(&["create", "t", "--project", "grove", "--var", "issue=1"]).is_err());
+        assert!(
+            parse(&[
+                "create",
+                "t",
+                "--project",
+                "grove",
+                "--recipe",
+                "b",
+                "--var",
+                "x"
+            ])
+            .is_err()
+        );
+        assert!(
+            parse(&[
+                "create",
+                "--project",
+                "grove",
+                "--base",
+                "--recipe",
+                "bugfix"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_task_create_rejects_missing_name_or_project() {
+        assert!(parse(&["create", "--project", "grove"]).is_err());
+        assert!(parse(&["create", "flaky-tests"]).is_err());
+        assert!(
+            parse(&[
+                "create",
+                "--project",
+                "a",
+                "--project",
+                "b",
+                "--pr",
+                "https://github.com/acme/grove/pull/7",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_task_delete_and_add_worktree() {
+        assert_eq!(
+            parse(&["delete", "flaky-tests", "--keep-branch"])
+                .expect("delete should parse")
+                .command,
+            TaskCommand::Delete {
+                task: "flaky-tests".to_string(),
+                keep_branch: true,
+            }
+        );
+        assert_eq!(
+            parse(&["add-worktree", "flaky-tests", "--project", "site"])
+                .expect("add-worktree should parse")
+                .command,
+            TaskCommand::AddWorktree {
+                task: "flaky-tests".to_string(),
+                project: "site".to_string(),
+                agent: None,
+            }
+        );
+        assert!(parse(&["add-worktree", "flaky-tests"]).is_err());
+        assert!(parse(&["delete"]).is_err());
+    }
+
+    #[test]
+    fn parse_task_archive_restore_and_archives() {
+        assert_eq!(
+            parse(&["archive", "flaky-tests"])
+                .expect("archive should parse")
+                .command,
+            TaskCommand::Archive {
+                task: "flaky-tests".to_string(),
+            }
+        );
+        assert_eq!(
+            parse(&["restore", "flaky-tests", "--json"]).expect("restore should parse"),
+            TaskCliArgs {
+                command: TaskCommand::Restore {
+                    task: "flaky-tests".to_string(),
+                },
+                json_output: true,
+            }
+        );
+        assert_eq!(
+            parse(&["archives"]).expect("archives should parse").command,
+            TaskCommand::Archives
+        );
+        assert!(parse(&["archive"]).is_err());
+        assert!(parse(&["archive", "flaky-tests", "--keep-branch"]).is_err());
+        assert!(parse(&["archives", "flaky-tests"]).is_err());
+    }
+
+    #[test]
+    fn parse_task_rejects_unknown_actions_and_flags() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["rename", "a"]).is_err());
+        assert!(parse(&["list", "--force"]).is_err());
+        assert!(parse(&["list", "--keep-branch"]).is_err());
+    }
+
+    #[test]
+    fn lifecycle_errors_map_to_distinct_exit_codes() {
+        let errors = [
+            TaskLifecycleError::EmptyTaskName,
+            TaskLifecycleError::InvalidTaskName,
+            TaskLifecycleError::EmptyRepositories,
+            TaskLifecycleError::HomeDirectoryUnavailable,
+            TaskLifecycleError::RepositoryNameUnavailable,
+            TaskLifecycleError::BaseBranchDetectionFailed(String::new()),
+            TaskLifecycleError::TaskInvalid(String::new()),
+            TaskLifecycleError::TaskManifest(String::new()),
+            TaskLifecycleError::BaseTaskCannotAddWorktrees,
+            TaskLifecycleError::TaskAlreadyHasRepository(String::new()),
+            TaskLifecycleError::GitCommandFailed(String::new()),
+            TaskLifecycleError::Io(String::new()),
+            TaskLifecycleError::HookVetoed(String::new()),
+        ];
+        let codes = errors
+            .iter()
+            .map(task_lifecycle_exit_code)
+            .collect::<HashSet<_>>();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|code| *code >= 10));
+        assert_eq!(
+            TaskCliFailure::NotFound("missing".to_string()).exit_code(),
+            3
+        );
+    }
+
+    fn fixture_task() -> Task {
+        let worktree = Worktree::try_new(
+            "grove".to_string(),
+            PathBuf::from("/repos/grove"),
+            PathBuf::from("/tmp/tasks/flaky/grove"),
+            "flaky".to_string(),
+            AgentType::Claude,
+            WorkspaceStatus::Idle,
+        )
+        .expect("worktree should be valid");
+        Task::try_new(
+            "flaky".to_string(),
+            "flaky".to_string(),
+            PathBuf::from("/tmp/tasks/flaky"),
+            "flaky".to_string(),
+            vec![worktree],
+        )
+        .expect("task should be valid")
+    }
+
+    #[test]
+    fn task_view_includes_session_names() {
+        let task = fixture_task();
+
+        let view = TaskView::from_task(&task);
+        let encoded = serde_json::to_value(&view).expect("view should encode");
+
+        assert_eq!(encoded["slug"], "flaky");
+        assert_eq!(encoded["worktrees"][0]["agent"], "claude");
+        assert_eq!(encoded["worktrees"][0]["status"], "idle");
+        assert_eq!(
+            encoded["worktrees"][0]["session_name"],
+            "grove-wt-flaky-grove"
+        );
+    }
+
+    #[test]
+    fn vetoing_pre_delete_hook_blocks_task_delete() {
+        let project = |command: &str| ProjectConfig {
+            name: "grove".to_string(),
+            path: PathBuf::from("/repos/grove"),
+            defaults: ProjectDefaults {
+                hooks: vec![HookConfig {
+                    event: HookEvent::PreDelete,
+                    command: command.to_string(),
+                    timeout_secs: 5,
+                }],
+                ..ProjectDefaults::default()
+            },
+        };
+        let request = || DeleteTaskRequest {
+            task: fixture_task(),
+            delete_local_branch: true,
+            kill_tmux_sessions: true,
+        };
+        let deleted = Cell::new(false);
+        let delete = |_: DeleteTaskRequest| {
+            deleted.set(true);
+            (Ok(()), Vec::new())
+        };
+
+        let failure = delete_task_with_hooks(
+            &[project("echo 'branch is frozen' >&2; exit 1")],
+            request(),
+            &CommandHookRunner,
+            delete,
+        )
+        .expect_err("veto should block the delete");
+        assert!(!deleted.get());
+        assert_eq!(failure.code(), "hook_vetoed");
+        assert!(
+            failure.message().contains("branch is frozen"),
+            "{failure:?}"
+        );
+
+        delete_task_with_hooks(&[project("true")], request(), &CommandHookRunner, delete)
+            .expect("delete should run");
+        assert!(deleted.get());
+    }
+}
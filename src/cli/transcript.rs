@@ -0,0 +1,309 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::application::agent_runtime::transcript::{
+    TranscriptFile, TranscriptFormat, html_document, list_transcripts, read_transcript,
+    render_transcript,
+};
+use crate::application::task_lifecycle::task_lifecycle_error_message;
+use crate::infrastructure::event_log::now_millis;
+
+use super::{EXIT_FAILURE, EXIT_NOT_FOUND, format_age, invalid_input, required_value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TranscriptCliArgs {
+    pub(crate) task: String,
+    /// Exports only this tmux session, every recorded session when unset.
+    pub(crate) session: Option<String>,
+    pub(crate) format: TranscriptFormat,
+    /// Writes the export here instead of stdout.
+    pub(crate) output_path: Option<PathBuf>,
+    pub(crate) list: bool,
+    pub(crate) json_output: bool,
+}
+
+pub(crate) fn parse_transcript_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<TranscriptCliArgs> {
+    let mut task = None;
+    let mut session = None;
+    let mut format = TranscriptFormat::Text;
+    let mut output_path = None;
+    let mut list = false;
+    let mut json_output = false;
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--list" => list = true,
+            "--session" => session = Some(required_value(args, "--session", "a session name")?),
+            "--format" => {
+                let raw = required_value(args, "--format", "text, ansi or html")?;
+                format = TranscriptFormat::parse(raw.as_str()).ok_or_else(|| {
+                    invalid_input(format!("--format expects text, ansi or html, got `{raw}`"))
+                })?;
+            }
+            "--output" => {
+                output_path = Some(PathBuf::from(required_value(
+                    args,
+                    "--output",
+                    "a file path",
+                )?));
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!("unknown transcript flag `{value}`")));
+            }
+            _ if task.is_some() => {
+                return Err(invalid_input("transcript accepts a single task"));
+            }
+            _ => task = Some(argument),
+        }
+    }
+
+    let Some(task) = task else {
+        return Err(invalid_input("transcript requires a task"));
+    };
+    if list && (session.is_some() || output_path.is_some()) {
+        return Err(invalid_input(
+            "--list cannot be combined with --session or --output",
+        ));
+    }
+    Ok(TranscriptCliArgs {
+        task,
+        session,
+        format,
+        output_path,
+        list,
+        json_output,
+    })
+}
+
+enum TranscriptCliFailure {
+    UnknownTask(String),
+    UnknownSession(String),
+    NoTranscripts(String),
+    Other(String),
+}
+
+impl TranscriptCliFailure {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownTask(_) => "unknown_task",
+            Self::UnknownSession(_) => "unknown_session",
+            Self::NoTranscripts(_) => "no_transcripts",
+            Self::Other(_) => "failed",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::UnknownTask(task) => format!("unknown task '{task}'"),
+            Self::UnknownSession(session) => format!("no transcript for session '{session}'"),
+            Self::NoTranscripts(task) => format!(
+                "no transcripts recorded for '{task}', enable them with [transcripts] enabled = true"
+            ),
+            Self::Other(message) => message.clone(),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::UnknownTask(_) | Self::UnknownSession(_) | Self::NoTranscripts(_) => {
+                EXIT_NOT_FOUND
+            }
+            Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TranscriptListOutput {
+    task: String,
+    transcripts: Vec<TranscriptView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TranscriptView {
+    session_name: String,
+    path: String,
+    size_bytes: u64,
+    modified_unix_secs: Option<i64>,
+}
+
+impl TranscriptView {
+    fn from_file(file: &TranscriptFile) -> Self {
+        Self {
+            session_name: file.session_name.clone(),
+            path: file.path.to_string_lossy().into_owned(),
+            size_bytes: file.size_bytes,
+            modified_unix_secs: file.modified_unix_secs,
+        }
+    }
+}
+
+pub(crate) fn run_transcript(cli: &TranscriptCliArgs) -> std::io::Result<()> {
+    match execute_transcript_command(cli) {
+        Ok(()) => Ok(()),
+        Err(failure) => super::exit_with_failure(
+            cli.json_output,
+            "transcript",
+            failure.code(),
+            failure.message().as_str(),
+            failure.exit_code(),
+        ),
+    }
+}
+
+fn execute_transcript_command(cli: &TranscriptCliArgs) -> Result<(), TranscriptCliFailure> {
+    let loaded = super::load_cli_config().map_err(TranscriptCliFailure::Other)?;
+    let tasks = super::load_tasks(&loaded.agents)
+        .map_err(|error| TranscriptCliFailure::Other(task_lifecycle_error_message(&error)))?;
+    let Some(task) = tasks
+        .iter()
+        .find(|task| task.slug == cli.task)
+        .or_else(|| tasks.iter().find(|task| task.name == cli.task))
+    else {
+        return Err(TranscriptCliFailure::UnknownTask(cli.task.clone()));
+    };
+
+    let mut transcripts = list_transcripts(task.root_path.as_path());
+    if cli.list {
+        print_transcript_list(cli.json_output, task.slug.as_str(), &transcripts);
+        return Ok(());
+    }
+    if let Some(session) = cli.session.as_deref() {
+        transcripts.retain(|transcript| transcript.session_name == session);
+        if transcripts.is_empty() {
+            return Err(TranscriptCliFailure::UnknownSession(session.to_string()));
+        }
+    }
+    if transcripts.is_empty() {
+        return Err(TranscriptCliFailure::NoTranscripts(task.slug.clone()));
+    }
+
+    let mut sections = Vec::with_capacity(transcripts.len());
+    for transcript in &transcripts {
+        let raw =
+            read_transcript(transcript.path.as_path()).map_err(TranscriptCliFailure::Other)?;
+        sections.push((
+            transcript.session_name.clone(),
+            render_transcript(raw.as_str(), cli.format),
+        ));
+    }
+    let rendered = match cli.format {
+        TranscriptFormat::Html => html_document(task.name.as_str(), &sections),
+        TranscriptFormat::Text | TranscriptFormat::Ansi if sections.len() == 1 => {
+            sections.remove(0).1
+        }
+        TranscriptFormat::Text | TranscriptFormat::Ansi => sections
+            .iter()
+            .map(|(session_name, body)| format!("==> {session_name} <==\n{body}"))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    };
+
+    match cli.output_path.as_ref() {
+        Some(path) => fs::write(path, rendered).map_err(|error| {
+            TranscriptCliFailure::Other(format!(
+                "transcript export to {} failed: {error}",
+                path.display()
+            ))
+        }),
+        None => {
+            print!("{rendered}");
+            Ok(())
+        }
+    }
+}
+
+fn print_transcript_list(json_output: bool, task_slug: &str, transcripts: &[TranscriptFile]) {
+    if json_output {
+        let output = TranscriptListOutput {
+            task: task_slug.to_string(),
+            transcripts: transcripts.iter().map(TranscriptView::from_file).collect(),
+        };
+        if let Ok(encoded) = serde_json::to_string_pretty(&output) {
+            println!("{encoded}");
+        }
+        return;
+    }
+    println!("transcripts for {task_slug}");
+    if transcripts.is_empty() {
+        println!("  none");
+        return;
+    }
+    let now_secs = now_millis() / 1000;
+    for transcript in transcripts {
+        let age_secs = transcript
+            .modified_unix_secs
+            .and_then(|modified| u64::try_from(modified).ok())
+            .map(|modified| now_secs.saturating_sub(modified));
+        println!(
+            "  {}  {} KiB  updated {} ago",
+            transcript.session_name,
+            transcript.size_bytes.div_ceil(1024),
+            format_age(age_secs)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{TranscriptCliArgs, parse_transcript_args};
+    use crate::application::agent_runtime::transcript::TranscriptFormat;
+
+    fn parse(args: &[&str]) -> std::io::Result<TranscriptCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_transcript_args(&mut args)
+    }
+
+    #[test]
+    fn parse_transcript_reads_task_session_format_and_output() {
+        assert_eq!(
+            parse(&["feature-a"]).expect("transcript should parse"),
+            TranscriptCliArgs {
+                task: "feature-a".to_string(),
+                session: None,
+                format: TranscriptFormat::Text,
+                output_path: None,
+                list: false,
+                json_output: false,
+            }
+        );
+        assert_eq!(
+            parse(&[
+                "feature-a",
+                "--session",
+                "grove-wt-feature-a-grove",
+                "--format",
+                "html",
+                "--output",
+                "/tmp/feature-a.html",
+            ])
+            .expect("transcript should parse"),
+            TranscriptCliArgs {
+                task: "feature-a".to_string(),
+                session: Some("grove-wt-feature-a-grove".to_string()),
+                format: TranscriptFormat::Html,
+                output_path: Some(PathBuf::from("/tmp/feature-a.html")),
+                list: false,
+                json_output: false,
+            }
+        );
+        assert!(parse(&["feature-a", "--list", "--json"]).is_ok_and(|args| args.list));
+    }
+
+    #[test]
+    fn parse_transcript_rejects_bad_input() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["a", "b"]).is_err());
+        assert!(parse(&["a", "--format", "pdf"]).is_err());
+        assert!(parse(&["a", "--session"]).is_err());
+        assert!(parse(&["a", "--list", "--session", "x"]).is_err());
+        assert!(parse(&["a", "--verbose"]).is_err());
+    }
+}
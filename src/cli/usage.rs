@@ -0,0 +1,358 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::application::agent_runtime::usage::{
+    UsagePriceTable, UsageTotals, compact_token_count, usage_samples_in_home,
+};
+use crate::application::task_lifecycle::task_lifecycle_error_message;
+use crate::domain::{AgentRegistry, Task};
+use crate::infrastructure::event_log::now_millis;
+
+use super::history::parse_window;
+use super::{EXIT_FAILURE, EXIT_NOT_FOUND, format_age, invalid_input, required_value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UsageCliArgs {
+    pub(crate) task: Option<String>,
+    /// Only count usage from this many seconds back, all recorded usage when unset.
+    pub(crate) since_secs: Option<u64>,
+    pub(crate) json_output: bool,
+}
+
+pub(crate) fn parse_usage_args(
+    args: &mut impl Iterator<Item = String>,
+) -> std::io::Result<UsageCliArgs> {
+    let mut task = None;
+    let mut since_secs = None;
+    let mut json_output = false;
+
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--json" => json_output = true,
+            "--since" => {
+                let raw = required_value(args, "--since", "a duration like 90m, 24h or 7d")?;
+                since_secs = Some(parse_window(raw.as_str()).ok_or_else(|| {
+                    invalid_input(format!(
+                        "--since expects a duration like 90m, 24h or 7d, got `{raw}`"
+                    ))
+                })?);
+            }
+            value if value.starts_with("--") => {
+                return Err(invalid_input(format!("unknown usage flag `{value}`")));
+            }
+            _ if task.is_some() => {
+                return Err(invalid_input("usage accepts a single task"));
+            }
+            _ => task = Some(argument),
+        }
+    }
+
+    Ok(UsageCliArgs {
+        task,
+        since_secs,
+        json_output,
+    })
+}
+
+enum UsageCliFailure {
+    UnknownTask(String),
+    Other(String),
+}
+
+impl UsageCliFailure {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownTask(_) => "unknown_task",
+            Self::Other(_) => "failed",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::UnknownTask(task) => format!("unknown task '{task}'"),
+            Self::Other(message) => message.clone(),
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::UnknownTask(_) => EXIT_NOT_FOUND,
+            Self::Other(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct UsageOutput {
+    since_ms: Option<u64>,
+    tasks: Vec<TaskUsageView>,
+    total: UsageView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TaskUsageView {
+    task: String,
+    usage: UsageView,
+    worktrees: Vec<WorktreeUsageView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct WorktreeUsageView {
+    path: String,
+    usage: UsageView,
+    models: Vec<ModelUsageView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ModelUsageView {
+    model: String,
+    usage: UsageView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct UsageView {
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_write_tokens: u64,
+    cost_usd: f64,
+    unpriced_models: Vec<String>,
+}
+
+impl UsageView {
+    fn from_totals(totals: &UsageTotals, prices: &UsagePriceTable) -> Self {
+        let tokens = totals.tokens();
+        let cost = prices.estimate(totals);
+        Self {
+            input_tokens: tokens.input_tokens,
+            output_tokens: tokens.output_tokens,
+            cache_read_tokens: tokens.cache_read_tokens,
+            cache_write_tokens: tokens.cache_write_tokens,
+            cost_usd: cost.usd,
+            unpriced_models: cost.unpriced_models,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let suffix = if self.unpriced_models.is_empty() {
+            String::new()
+        } else {
+            format!(" (no price for {})", self.unpriced_models.join(", "))
+        };
+        format!(
+            "in {} out {} cache read {} cache write {} ${:.2}{suffix}",
+            compact_token_count(self.input_tokens),
+            compact_token_count(self.output_tokens),
+            compact_token_count(self.cache_read_tokens),
+            compact_token_count(self.cache_write_tokens),
+            self.cost_usd,
+        )
+    }
+}
+
+pub(crate) fn run_usage(cli: &UsageCliArgs) -> std::io::Result<()> {
+    let output = match load_usage(cli) {
+        Ok(output) => output,
+        Err(failure) => super::exit_with_failure(
+            cli.json_output,
+            "usage",
+            failure.code(),
+            failure.message().as_str(),
+            failure.exit_code(),
+        ),
+    };
+    if cli.json_output {
+        let encoded = serde_json::to_string_pretty(&output).map_err(std::io::Error::other)?;
+        println!("{encoded}");
+        return Ok(());
+    }
+    print_usage(&output, cli.since_secs);
+    Ok(())
+}
+
+/// Totals for one worktree across every configured agent, since agents can share a worktree.
+fn worktree_usage_totals(
+    agents: &AgentRegistry,
+    workspace_path: &Path,
+    home_dir: &Path,
+    since_ms: Option<u64>,
+) -> UsageTotals {
+    let mut totals = UsageTotals::default();
+    for agent in agents.available() {
+        for sample in usage_samples_in_home(&agent, workspace_path, home_dir) {
+            if since_ms.is_some_and(|since_ms| sample.ts_ms.is_none_or(|ts_ms| ts_ms < since_ms)) {
+                continue;
+            }
+            totals.record(sample.model.as_str(), sample.tokens);
+        }
+    }
+    totals
+}
+
+fn task_usage(
+    agents: &AgentRegistry,
+    task: &Task,
+    home_dir: &Path,
+    since_ms: Option<u64>,
+    prices: &UsagePriceTable,
+) -> (UsageTotals, TaskUsageView) {
+    let mut task_totals = UsageTotals::default();
+    let mut worktrees = Vec::new();
+    for worktree in &task.worktrees {
+        let totals = worktree_usage_totals(agents, worktree.path.as_path(), home_dir, since_ms);
+        if totals.is_empty() {
+            continue;
+        }
+        task_totals.merge(&totals);
+        worktrees.push(WorktreeUsageView {
+            path: worktree.path.to_string_lossy().into_owned(),
+            usage: UsageView::from_totals(&totals, prices),
+            models: totals
+                .by_model
+                .iter()
+                .filter(|(_, tokens)| !tokens.is_empty())
+                .map(|(model, tokens)| {
+                    let mut model_totals = UsageTotals::default();
+                    model_totals.record(model, *tokens);
+                    ModelUsageView {
+                        model: model.clone(),
+                        usage: UsageView::from_totals(&model_totals, prices),
+                    }
+                })
+                .collect(),
+        });
+    }
+    let view = TaskUsageView {
+        task: task.slug.clone(),
+        usage: UsageView::from_totals(&task_totals, prices),
+        worktrees,
+    };
+    (task_totals, view)
+}
+
+fn load_usage(cli: &UsageCliArgs) -> Result<UsageOutput, UsageCliFailure> {
+    let loaded = super::load_cli_config().map_err(UsageCliFailure::Other)?;
+    let prices = UsagePriceTable::from_config(&loaded.config.usage);
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| UsageCliFailure::Other("home directory unavailable".to_string()))?;
+    let tasks = super::load_tasks(&loaded.agents)
+        .map_err(|error| UsageCliFailure::Other(task_lifecycle_error_message(&error)))?;
+    let tasks = match cli.task.as_deref() {
+        Some(wanted) => {
+            let Some(task) = tasks
+                .iter()
+                .position(|task| task.slug == wanted)
+                .or_else(|| tasks.iter().position(|task| task.name == wanted))
+            else {
+                return Err(UsageCliFailure::UnknownTask(wanted.to_string()));
+            };
+            vec![tasks[task].clone()]
+        }
+        None => tasks,
+    };
+    let since_ms = cli
+        .since_secs
+        .map(|since_secs| now_millis().saturating_sub(since_secs.saturating_mul(1000)));
+
+    let mut total = UsageTotals::default();
+    let mut task_views = Vec::new();
+    for task in &tasks {
+        let (task_totals, view) =
+            task_usage(&loaded.agents, task, home_dir.as_path(), since_ms, &prices);
+        if task_totals.is_empty() && cli.task.is_none() {
+            continue;
+        }
+        total.merge(&task_totals);
+        task_views.push(view);
+    }
+
+    Ok(UsageOutput {
+        since_ms,
+        tasks: task_views,
+        total: UsageView::from_totals(&total, &prices),
+    })
+}
+
+fn print_usage(output: &UsageOutput, since_secs: Option<u64>) {
+    match since_secs {
+        Some(since_secs) => println!("usage for the last {}", format_age(Some(since_secs))),
+        None => println!("usage, all recorded sessions"),
+    }
+    if output.tasks.is_empty() {
+        println!("  none");
+        return;
+    }
+    for task in &output.tasks {
+        println!("{}: {}", task.task, task.usage.summary());
+        for worktree in &task.worktrees {
+            println!("  {}: {}", worktree.path, worktree.usage.summary());
+            for model in &worktree.models {
+                println!("    {}: {}", model.model, model.usage.summary());
+            }
+        }
+    }
+    println!("total: {}", output.total.summary());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UsageCliArgs, UsageView, parse_usage_args};
+    use crate::application::agent_runtime::usage::{TokenUsage, UsagePriceTable, UsageTotals};
+
+    fn parse(args: &[&str]) -> std::io::Result<UsageCliArgs> {
+        let mut args = args.iter().map(|value| value.to_string());
+        parse_usage_args(&mut args)
+    }
+
+    #[test]
+    fn parse_usage_reads_task_window_and_json() {
+        assert_eq!(
+            parse(&[]).expect("usage should parse"),
+            UsageCliArgs {
+                task: None,
+                since_secs: None,
+                json_output: false,
+            }
+        );
+        assert_eq!(
+            parse(&["feature-a", "--since", "24h", "--json"]).expect("usage should parse"),
+            UsageCliArgs {
+                task: Some("feature-a".to_string()),
+                since_secs: Some(24 * 60 * 60),
+                json_output: true,
+            }
+        );
+        assert!(parse(&["a", "b"]).is_err());
+        assert!(parse(&["--since"]).is_err());
+        assert!(parse(&["--since", "later"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn usage_view_summary_lists_unpriced_models() {
+        let mut totals = UsageTotals::default();
+        totals.record(
+            "claude-sonnet-4-5",
+            TokenUsage {
+                input_tokens: 2_000_000,
+                output_tokens: 1_500,
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
+            },
+        );
+        totals.record(
+            "local-model",
+            TokenUsage {
+                output_tokens: 12,
+                ..TokenUsage::default()
+            },
+        );
+
+        let view = UsageView::from_totals(&totals, &UsagePriceTable::default());
+        assert_eq!(
+            view.summary(),
+            "in 2.0M out 1.5k cache read 0 cache write 0 $6.02 (no price for local-model)"
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::BTreeSet;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
@@ -12,18 +14,12 @@ pub enum PermissionMode {
 }
 
 impl PermissionMode {
-    pub const fn next_for_agent(self, agent: AgentType) -> Self {
-        match agent {
-            AgentType::Claude => match self {
-                Self::Default => Self::Auto,
-                Self::Auto => Self::Unsafe,
-                Self::Unsafe => Self::Default,
-            },
-            AgentType::Codex => match self {
-                Self::Default => Self::Unsafe,
-                Self::Auto | Self::Unsafe => Self::Default,
-            },
-        }
+    pub fn next_for_agent(self, agent: &AgentType) -> Self {
+        let modes = agent.permission_modes();
+        let Some(index) = modes.iter().position(|mode| *mode == self) else {
+            return Self::Default;
+        };
+        modes[(index + 1) % modes.len()]
     }
 
     pub const fn next_global(self) -> Self {
@@ -68,10 +64,204 @@ impl PermissionMode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How a workspace branch lands on its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeStrategy {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Merge => Self::Squash,
+            Self::Squash => Self::Rebase,
+            Self::Rebase => Self::Merge,
+        }
+    }
+
+    pub const fn previous(self) -> Self {
+        match self {
+            Self::Merge => Self::Rebase,
+            Self::Squash => Self::Merge,
+            Self::Rebase => Self::Squash,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
+/// How a workspace branch picks up new commits from its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateStrategy {
+    #[default]
+    Merge,
+    Rebase,
+}
+
+impl UpdateStrategy {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Merge => Self::Rebase,
+            Self::Rebase => Self::Merge,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AgentType {
     Claude,
     Codex,
+    /// Agent defined in config.
+    Custom(CustomAgent),
+}
+
+/// A config-defined agent: its identity, how it launches and restarts, and
+/// where it logs its session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomAgentProfile {
+    pub marker: String,
+    pub label: String,
+    /// Empty for an agent the config no longer defines; such agents never launch.
+    pub command: String,
+    pub auto_flag: Option<String>,
+    pub unsafe_flag: Option<String>,
+    pub resume_pattern: Option<String>,
+    pub resume_command: Option<String>,
+    pub exit_input: Option<String>,
+    pub exit_key: Option<String>,
+    pub session_log: Option<CustomAgentSessionLog>,
+}
+
+/// Where a config-defined agent writes its jsonl session log and how its
+/// records name the user and the assistant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomAgentSessionLog {
+    pub dir: String,
+    pub role_field: String,
+    pub user_value: String,
+    pub assistant_value: String,
+}
+
+/// Shared handle to a config-defined agent's profile. Handles with the same
+/// marker are the same agent.
+#[derive(Debug, Clone)]
+pub struct CustomAgent(Arc<CustomAgentProfile>);
+
+impl CustomAgent {
+    pub fn new(profile: CustomAgentProfile) -> Self {
+        Self(Arc::new(profile))
+    }
+
+    /// An agent the config does not define, e.g. one named by a recorded replay.
+    pub fn undefined(marker: &str) -> Self {
+        Self::new(CustomAgentProfile {
+            marker: marker.to_string(),
+            ..CustomAgentProfile::default()
+        })
+    }
+
+    pub fn marker(&self) -> &str {
+        self.0.marker.as_str()
+    }
+
+    pub fn profile(&self) -> &CustomAgentProfile {
+        &self.0
+    }
+}
+
+impl PartialEq for CustomAgent {
+    fn eq(&self, other: &Self) -> bool {
+        self.marker() == other.marker()
+    }
+}
+
+impl Eq for CustomAgent {}
+
+impl std::hash::Hash for CustomAgent {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.marker().hash(state);
+    }
+}
+
+pub fn is_valid_custom_agent_marker(value: &str) -> bool {
+    !value.is_empty()
+        && AgentType::ALL.iter().all(|agent| agent.marker() != value)
+        && value.chars().all(|character| {
+            character.is_ascii_lowercase()
+                || character.is_ascii_digit()
+                || character == '-'
+                || character == '_'
+        })
+}
+
+/// The agents Grove can launch: the built-ins followed by the config-defined
+/// ones, in config order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentRegistry {
+    custom: Vec<CustomAgent>,
+}
+
+impl AgentRegistry {
+    pub fn new(custom: Vec<CustomAgent>) -> Result<Self, String> {
+        let mut seen = BTreeSet::new();
+        for agent in &custom {
+            if !is_valid_custom_agent_marker(agent.marker()) {
+                return Err(format!("invalid agent id '{}'", agent.marker()));
+            }
+            if !seen.insert(agent.marker()) {
+                return Err(format!("duplicate agent id '{}'", agent.marker()));
+            }
+        }
+        Ok(Self { custom })
+    }
+
+    pub fn available(&self) -> Vec<AgentType> {
+        AgentType::ALL
+            .into_iter()
+            .chain(self.custom.iter().cloned().map(AgentType::Custom))
+            .collect()
+    }
+
+    pub fn custom(&self, marker: &str) -> Option<CustomAgent> {
+        self.custom
+            .iter()
+            .find(|agent| agent.marker() == marker)
+            .cloned()
+    }
+
+    pub fn from_marker(&self, value: &str) -> Option<AgentType> {
+        match value {
+            "claude" => Some(AgentType::Claude),
+            "codex" => Some(AgentType::Codex),
+            _ => self.custom(value).map(AgentType::Custom),
+        }
+    }
+
+    pub fn next(&self, agent: &AgentType) -> AgentType {
+        cycle_agent(self.available().as_slice(), agent, true)
+    }
+
+    pub fn previous(&self, agent: &AgentType) -> AgentType {
+        cycle_agent(self.available().as_slice(), agent, false)
+    }
 }
 
 impl AgentType {
@@ -81,48 +271,87 @@ impl AgentType {
         &Self::ALL
     }
 
-    pub const fn label(self) -> &'static str {
+    pub const fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
+    pub fn label(&self) -> &str {
         match self {
             Self::Claude => "Claude",
             Self::Codex => "Codex",
+            Self::Custom(agent) => {
+                let label = agent.profile().label.as_str();
+                if label.is_empty() {
+                    agent.marker()
+                } else {
+                    label
+                }
+            }
         }
     }
 
-    pub const fn marker(self) -> &'static str {
+    pub fn marker(&self) -> &str {
         match self {
             Self::Claude => "claude",
             Self::Codex => "codex",
+            Self::Custom(agent) => agent.marker(),
         }
     }
 
-    pub const fn command_override_env_var(self) -> &'static str {
+    pub fn command_override_env_var(&self) -> String {
         match self {
-            Self::Claude => "GROVE_CLAUDE_CMD",
-            Self::Codex => "GROVE_CODEX_CMD",
+            Self::Claude => "GROVE_CLAUDE_CMD".to_string(),
+            Self::Codex => "GROVE_CODEX_CMD".to_string(),
+            Self::Custom(agent) => {
+                let name = agent
+                    .marker()
+                    .chars()
+                    .map(|character| {
+                        if character.is_ascii_alphanumeric() {
+                            character.to_ascii_uppercase()
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>();
+                format!("GROVE_{name}_CMD")
+            }
         }
     }
 
-    pub fn from_marker(value: &str) -> Option<Self> {
-        match value {
-            "claude" => Some(Self::Claude),
-            "codex" => Some(Self::Codex),
-            _ => None,
-        }
-    }
-
-    pub const fn next(self) -> Self {
+    pub fn permission_modes(&self) -> Vec<PermissionMode> {
         match self {
-            Self::Claude => Self::Codex,
-            Self::Codex => Self::Claude,
+            Self::Claude => vec![
+                PermissionMode::Default,
+                PermissionMode::Auto,
+                PermissionMode::Unsafe,
+            ],
+            Self::Codex => vec![PermissionMode::Default, PermissionMode::Unsafe],
+            Self::Custom(agent) => {
+                let profile = agent.profile();
+                let mut modes = vec![PermissionMode::Default];
+                if profile.auto_flag.is_some() {
+                    modes.push(PermissionMode::Auto);
+                }
+                if profile.unsafe_flag.is_some() {
+                    modes.push(PermissionMode::Unsafe);
+                }
+                modes
+            }
         }
     }
+}
 
-    pub const fn previous(self) -> Self {
-        match self {
-            Self::Claude => Self::Codex,
-            Self::Codex => Self::Claude,
-        }
-    }
+fn cycle_agent(agents: &[AgentType], current: &AgentType, forward: bool) -> AgentType {
+    let Some(index) = agents.iter().position(|agent| agent == current) else {
+        return agents.first().cloned().unwrap_or_else(|| current.clone());
+    };
+    let next_index = if forward {
+        (index + 1) % agents.len()
+    } else {
+        (index + agents.len() - 1) % agents.len()
+    };
+    agents[next_index].clone()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -158,11 +387,46 @@ pub enum PullRequestStatus {
     Closed,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestReview {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestChecks {
+    Pending,
+    Passing,
+    Failing,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PullRequest {
     pub number: u64,
     pub url: String,
     pub status: PullRequestStatus,
+    /// Review decision reported by the forge, if any.
+    pub review: Option<PullRequestReview>,
+    /// Rolled-up CI check state, if the pull request has checks.
+    pub checks: Option<PullRequestChecks>,
+}
+
+/// Consecutive ports reserved for one worktree, starting at `base`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortRange {
+    pub base: u16,
+    pub count: u16,
+}
+
+impl PortRange {
+    pub fn last(self) -> u16 {
+        self.base.saturating_add(self.count.saturating_sub(1))
+    }
+
+    pub fn overlaps(self, other: PortRange) -> bool {
+        self.base <= other.last() && other.base <= self.last()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -181,6 +445,7 @@ pub struct Workspace {
     pub is_orphaned: bool,
     pub supported_agent: bool,
     pub pull_requests: Vec<PullRequest>,
+    pub ports: Option<PortRange>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -196,6 +461,8 @@ pub struct Worktree {
     pub is_orphaned: bool,
     pub supported_agent: bool,
     pub pull_requests: Vec<PullRequest>,
+    /// Ports exported to the worktree's sessions, when the project allocates them.
+    pub ports: Option<PortRange>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -205,6 +472,8 @@ pub struct Task {
     pub root_path: PathBuf,
     pub branch: String,
     pub worktrees: Vec<Worktree>,
+    /// Recipe the task was created from, if any.
+    pub recipe: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -270,6 +539,7 @@ impl Workspace {
             is_orphaned: false,
             supported_agent: true,
             pull_requests: Vec::new(),
+            ports: None,
         })
     }
 
@@ -303,6 +573,35 @@ impl Workspace {
         self.pull_requests = pull_requests;
         self
     }
+
+    pub fn from_task_worktree(task: &Task, worktree: &Worktree) -> Self {
+        let is_main = worktree.is_main_checkout();
+        Self {
+            name: if task.worktrees.len() == 1 {
+                task.name.clone()
+            } else {
+                worktree.repository_name.clone()
+            },
+            task_slug: Some(task.slug.clone()),
+            path: worktree.path.clone(),
+            project_name: Some(worktree.repository_name.clone()),
+            project_path: Some(worktree.repository_path.clone()),
+            branch: worktree.branch.clone(),
+            base_branch: worktree.base_branch.clone(),
+            last_activity_unix_secs: worktree.last_activity_unix_secs,
+            agent: worktree.agent.clone(),
+            status: if is_main {
+                WorkspaceStatus::Main
+            } else {
+                worktree.status
+            },
+            is_main,
+            is_orphaned: worktree.is_orphaned,
+            supported_agent: worktree.supported_agent,
+            pull_requests: worktree.pull_requests.clone(),
+            ports: worktree.ports,
+        }
+    }
 }
 
 impl Worktree {
@@ -343,6 +642,7 @@ impl Worktree {
             is_orphaned: false,
             supported_agent: true,
             pull_requests: Vec::new(),
+            ports: None,
         })
     }
 
@@ -370,6 +670,11 @@ impl Worktree {
         self.pull_requests = pull_requests;
         self
     }
+
+    pub fn with_ports(mut self, ports: Option<PortRange>) -> Self {
+        self.ports = ports;
+        self
+    }
 }
 
 impl Task {
@@ -406,15 +711,22 @@ impl Task {
             root_path,
             branch,
             worktrees,
+            recipe: None,
         })
     }
+
+    pub fn with_recipe(mut self, recipe: Option<String>) -> Self {
+        self.recipe = recipe;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        AgentType, PermissionMode, PullRequest, PullRequestStatus, Task, TaskValidationError,
-        Workspace, WorkspaceStatus, WorkspaceValidationError, Worktree, WorktreeValidationError,
+        AgentRegistry, AgentType, CustomAgent, CustomAgentProfile, PermissionMode, PullRequest,
+        PullRequestStatus, Task, TaskValidationError, Workspace, WorkspaceStatus,
+        WorkspaceValidationError, Worktree, WorktreeValidationError, is_valid_custom_agent_marker,
     };
     use std::path::PathBuf;
 
@@ -515,6 +827,8 @@ mod tests {
             number: 42,
             url: "https://github.com/acme/grove/pull/42".to_string(),
             status: PullRequestStatus::Merged,
+            review: None,
+            checks: None,
         }]);
 
         assert_eq!(workspace.pull_requests.len(), 1);
@@ -522,30 +836,112 @@ mod tests {
         assert_eq!(workspace.pull_requests[0].status, PullRequestStatus::Merged);
     }
 
+    fn custom_agent(marker: &str, label: &str) -> CustomAgent {
+        CustomAgent::new(CustomAgentProfile {
+            marker: marker.to_string(),
+            label: label.to_string(),
+            command: marker.to_string(),
+            unsafe_flag: Some("--yes".to_string()),
+            ..CustomAgentProfile::default()
+        })
+    }
+
     #[test]
     fn agent_type_metadata_roundtrips_marker() {
+        let registry = AgentRegistry::default();
         for agent in AgentType::all() {
-            assert_eq!(AgentType::from_marker(agent.marker()), Some(*agent));
+            assert_eq!(registry.from_marker(agent.marker()), Some(agent.clone()));
             assert!(!agent.label().is_empty());
             assert!(!agent.command_override_env_var().is_empty());
         }
     }
 
+    #[test]
+    fn custom_agent_markers_roundtrip_through_the_registry() {
+        let aider = custom_agent("aider", "Aider");
+        let registry = AgentRegistry::new(vec![aider.clone()]).expect("registry should build");
+
+        let agent = registry
+            .from_marker("aider")
+            .expect("registered marker should resolve");
+        assert_eq!(agent, AgentType::Custom(aider));
+        assert_eq!(agent.marker(), "aider");
+        assert_eq!(agent.label(), "Aider");
+        assert!(agent.is_custom());
+        assert_eq!(
+            agent.permission_modes(),
+            vec![PermissionMode::Default, PermissionMode::Unsafe]
+        );
+        assert_eq!(registry.from_marker("gemini"), None);
+        assert_eq!(AgentRegistry::default().from_marker("aider"), None);
+    }
+
     #[test]
     fn agent_type_cycles_all_variants() {
+        let registry = AgentRegistry::default();
         let mut forward = AgentType::Claude;
         for _ in 0..AgentType::all().len() {
-            forward = forward.next();
+            forward = registry.next(&forward);
         }
         assert_eq!(forward, AgentType::Claude);
 
         let mut backward = AgentType::Claude;
         for _ in 0..AgentType::all().len() {
-            backward = backward.previous();
+            backward = registry.previous(&backward);
         }
         assert_eq!(backward, AgentType::Claude);
     }
 
+    #[test]
+    fn agent_type_cycle_includes_custom_agents() {
+        let aider = AgentType::Custom(custom_agent("aider", "Aider"));
+        let gemini = AgentType::Custom(custom_agent("gemini", ""));
+        let registry = AgentRegistry::new(vec![
+            custom_agent("aider", "Aider"),
+            custom_agent("gemini", ""),
+        ])
+        .expect("registry should build");
+
+        assert_eq!(registry.next(&AgentType::Codex), aider);
+        assert_eq!(registry.next(&aider), gemini);
+        assert_eq!(registry.next(&gemini), AgentType::Claude);
+        assert_eq!(registry.previous(&AgentType::Claude), gemini);
+        assert_eq!(registry.previous(&aider), AgentType::Codex);
+        assert_eq!(
+            AgentRegistry::default().next(&aider),
+            AgentType::Claude,
+            "unregistered agents restart the cycle"
+        );
+    }
+
+    #[test]
+    fn custom_agent_metadata_derives_from_marker() {
+        let agent = AgentType::Custom(custom_agent("gemini-cli", ""));
+        assert_eq!(agent.marker(), "gemini-cli");
+        assert_eq!(agent.label(), "gemini-cli");
+        assert_eq!(agent.command_override_env_var(), "GROVE_GEMINI_CLI_CMD");
+        assert_eq!(agent.permission_modes()[0], PermissionMode::Default);
+    }
+
+    #[test]
+    fn agent_registry_rejects_invalid_and_duplicate_markers() {
+        assert!(AgentRegistry::new(vec![custom_agent("claude", "")]).is_err());
+        assert!(AgentRegistry::new(vec![custom_agent("Aider", "")]).is_err());
+        assert!(
+            AgentRegistry::new(vec![custom_agent("aider", ""), custom_agent("aider", "")]).is_err()
+        );
+    }
+
+    #[test]
+    fn custom_agent_markers_reject_builtins_and_invalid_characters() {
+        assert!(is_valid_custom_agent_marker("aider"));
+        assert!(is_valid_custom_agent_marker("open_code-2"));
+        assert!(!is_valid_custom_agent_marker("claude"));
+        assert!(!is_valid_custom_agent_marker("Aider"));
+        assert!(!is_valid_custom_agent_marker("ai der"));
+        assert!(!is_valid_custom_agent_marker(""));
+    }
+
     #[test]
     fn worktree_requires_repository_name_and_paths() {
         assert_eq!(
@@ -677,20 +1073,20 @@ mod tests {
     #[test]
     fn permission_mode_cycles_claude_through_three_states() {
         let mut mode = PermissionMode::Default;
-        mode = mode.next_for_agent(AgentType::Claude);
+        mode = mode.next_for_agent(&AgentType::Claude);
         assert_eq!(mode, PermissionMode::Auto);
-        mode = mode.next_for_agent(AgentType::Claude);
+        mode = mode.next_for_agent(&AgentType::Claude);
         assert_eq!(mode, PermissionMode::Unsafe);
-        mode = mode.next_for_agent(AgentType::Claude);
+        mode = mode.next_for_agent(&AgentType::Claude);
         assert_eq!(mode, PermissionMode::Default);
     }
 
     #[test]
     fn permission_mode_cycles_codex_through_two_states() {
         let mut mode = PermissionMode::Default;
-        mode = mode.next_for_agent(AgentType::Codex);
+        mode = mode.next_for_agent(&AgentType::Codex);
         assert_eq!(mode, PermissionMode::Unsafe);
-        mode = mode.next_for_agent(AgentType::Codex);
+        mode = mode.next_for_agent(&AgentType::Codex);
         assert_eq!(mode, PermissionMode::Default);
     }
 
@@ -698,7 +1094,7 @@ mod tests {
     fn permission_mode_codex_auto_falls_back_to_default() {
         let mode = PermissionMode::Auto;
         assert_eq!(
-            mode.next_for_agent(AgentType::Codex),
+            mode.next_for_agent(&AgentType::Codex),
             PermissionMode::Default
         );
     }
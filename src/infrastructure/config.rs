@@ -1,13 +1,14 @@
+use std::collections::BTreeMap;
 use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::domain::PermissionMode;
+use crate::domain::{AgentType, MergeStrategy, PermissionMode, UpdateStrategy, WorkspaceStatus};
+use crate::infrastructure::themes::CustomThemeRef;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ThemeName {
     Monokai,
     CatppuccinLatte,
@@ -18,10 +19,23 @@ pub enum ThemeName {
     RosePine,
     RosePineMoon,
     RosePineDawn,
+    /// Theme file from the themes directory, keyed by its file stem.
+    Custom(CustomThemeRef),
 }
 
 impl ThemeName {
-    pub const fn config_key(self) -> &'static str {
+    pub const BUILT_IN: [Self; 8] = [
+        Self::Monokai,
+        Self::CatppuccinLatte,
+        Self::CatppuccinFrappe,
+        Self::CatppuccinMacchiato,
+        Self::CatppuccinMocha,
+        Self::RosePine,
+        Self::RosePineMoon,
+        Self::RosePineDawn,
+    ];
+
+    pub fn config_key(&self) -> &str {
         match self {
             Self::Monokai => "monokai",
             Self::CatppuccinLatte => "catppuccin-latte",
@@ -31,7 +45,47 @@ impl ThemeName {
             Self::RosePine => "rose-pine",
             Self::RosePineMoon => "rose-pine-moon",
             Self::RosePineDawn => "rose-pine-dawn",
+            Self::Custom(theme) => theme.key(),
+        }
+    }
+}
+
+/// Theme as written in the config file; resolved against the loaded themes at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeKey(String);
+
+impl ThemeKey {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Default for ThemeKey {
+    fn default() -> Self {
+        Self::from(ThemeName::default())
+    }
+}
+
+impl From<ThemeName> for ThemeKey {
+    fn from(theme: ThemeName) -> Self {
+        Self(theme.config_key().to_string())
+    }
+}
+
+impl Serialize for ThemeKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(serde::de::Error::custom("theme name must not be empty"));
         }
+        Ok(Self(key.to_string()))
     }
 }
 
@@ -40,7 +94,7 @@ pub struct GroveConfig {
     #[serde(default = "default_sidebar_width_pct")]
     pub sidebar_width_pct: u16,
     #[serde(default)]
-    pub theme: ThemeName,
+    pub theme: ThemeKey,
     #[serde(default)]
     pub projects: Vec<ProjectConfig>,
     #[serde(default)]
@@ -51,6 +105,22 @@ pub struct GroveConfig {
     pub hidden_base_project_paths: Vec<PathBuf>,
     #[serde(default)]
     pub launch_permission_mode: PermissionMode,
+    #[serde(default)]
+    pub agents: Vec<AgentDefinitionConfig>,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub pull_request_sync: PullRequestSyncConfig,
+    #[serde(default)]
+    pub keybindings: KeybindingsConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
+    #[serde(default)]
+    pub transcripts: TranscriptsConfig,
+    #[serde(default)]
+    pub status_rules: Vec<StatusRuleConfig>,
+    #[serde(default)]
+    pub status_hooks: StatusHooksConfig,
 }
 
 const fn default_sidebar_width_pct() -> u16 {
@@ -61,12 +131,20 @@ impl Default for GroveConfig {
     fn default() -> Self {
         Self {
             sidebar_width_pct: default_sidebar_width_pct(),
-            theme: ThemeName::default(),
+            theme: ThemeKey::default(),
             projects: Vec::new(),
             task_order: Vec::new(),
             attention_acks: Vec::new(),
             hidden_base_project_paths: Vec::new(),
             launch_permission_mode: PermissionMode::Default,
+            agents: Vec::new(),
+            notifications: NotificationsConfig::default(),
+            pull_request_sync: PullRequestSyncConfig::default(),
+            keybindings: KeybindingsConfig::default(),
+            usage: UsageConfig::default(),
+            transcripts: TranscriptsConfig::default(),
+            status_rules: Vec::new(),
+            status_hooks: StatusHooksConfig::default(),
         }
     }
 }
@@ -75,12 +153,408 @@ impl GroveConfig {
     pub fn global_settings(&self) -> GlobalSettings {
         GlobalSettings {
             sidebar_width_pct: self.sidebar_width_pct,
-            theme: self.theme,
+            theme: self.theme.clone(),
             launch_permission_mode: self.launch_permission_mode,
+            agents: self.agents.clone(),
+            notifications: self.notifications.clone(),
+            pull_request_sync: self.pull_request_sync.clone(),
+            keybindings: self.keybindings.clone(),
+            usage: self.usage.clone(),
+            transcripts: self.transcripts.clone(),
+            status_rules: self.status_rules.clone(),
+            status_hooks: self.status_hooks.clone(),
+        }
+    }
+}
+
+/// Agent launched from a config-defined command instead of a built-in integration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AgentDefinitionConfig {
+    pub id: String,
+    #[serde(default)]
+    pub label: String,
+    pub command: String,
+    #[serde(default)]
+    pub permission_flags: AgentPermissionFlagsConfig,
+    /// Regex matched against pane output after exit. Its `session` group (or
+    /// first group) fills `{session}` in `resume_command`; without a template
+    /// the whole match is used as the resume command.
+    #[serde(default)]
+    pub resume_pattern: Option<String>,
+    #[serde(default)]
+    pub resume_command: Option<String>,
+    /// Literal text typed (followed by Enter) to exit the agent before a restart.
+    #[serde(default)]
+    pub exit_input: Option<String>,
+    /// Named tmux key sent to exit the agent, used when `exit_input` is unset.
+    #[serde(default)]
+    pub exit_key: Option<String>,
+    #[serde(default)]
+    pub session_log: Option<AgentSessionLogConfig>,
+}
+
+/// Delivery of attention events outside the TUI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub sinks: Vec<NotificationSinkConfig>,
+    /// Reasons delivered to every sink, empty means all of them.
+    #[serde(default)]
+    pub reasons: Vec<NotificationReason>,
+    /// Minimum gap between two notifications for the same worktree and reason.
+    #[serde(default = "default_notification_debounce_secs")]
+    pub debounce_secs: u64,
+    /// Local time window such as `22:00-07:00` during which nothing is sent.
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+}
+
+const fn default_notification_debounce_secs() -> u64 {
+    300
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            reasons: Vec::new(),
+            debounce_secs: default_notification_debounce_secs(),
+            quiet_hours: None,
+        }
+    }
+}
+
+impl NotificationsConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSinkConfig {
+    pub kind: NotificationSinkKind,
+    /// Shell command run for `command` sinks.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Endpoint for `webhook` sinks.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Narrows `NotificationsConfig::reasons` for this sink, empty means no extra filter.
+    #[serde(default)]
+    pub reasons: Vec<NotificationReason>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationSinkKind {
+    Command,
+    Desktop,
+    Terminal,
+    Webhook,
+}
+
+impl NotificationSinkKind {
+    pub const fn config_key(self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Desktop => "desktop",
+            Self::Terminal => "terminal",
+            Self::Webhook => "webhook",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationReason {
+    BlockedOnQuestion,
+    PermissionWall,
+    SessionEnded,
+    Finished,
+    Stalled,
+    ChecksFailed,
+    PullRequestMerged,
+}
+
+impl NotificationReason {
+    pub const fn config_key(self) -> &'static str {
+        match self {
+            Self::BlockedOnQuestion => "blocked-on-question",
+            Self::PermissionWall => "permission-wall",
+            Self::SessionEnded => "session-ended",
+            Self::Finished => "finished",
+            Self::Stalled => "stalled",
+            Self::ChecksFailed => "checks-failed",
+            Self::PullRequestMerged => "pull-request-merged",
+        }
+    }
+}
+
+/// Background refresh of pull request state from a code forge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequestSyncConfig {
+    #[serde(default)]
+    pub forge: PullRequestForgeKind,
+    /// Seconds between two sync passes over every task worktree.
+    #[serde(default = "default_pull_request_sync_interval_secs")]
+    pub interval_secs: u64,
+}
+
+const fn default_pull_request_sync_interval_secs() -> u64 {
+    120
+}
+
+impl Default for PullRequestSyncConfig {
+    fn default() -> Self {
+        Self {
+            forge: PullRequestForgeKind::default(),
+            interval_secs: default_pull_request_sync_interval_secs(),
+        }
+    }
+}
+
+impl PullRequestSyncConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Key sequences replacing the built-in bindings of commands, keyed by palette id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct KeybindingsConfig {
+    /// Key substituted for `<leader>` in sequences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    #[serde(flatten)]
+    pub commands: BTreeMap<String, KeybindingSequences>,
+}
+
+impl KeybindingsConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// One key sequence, or several alternatives, bound to a single command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeybindingSequences {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeybindingSequences {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(sequence) => std::slice::from_ref(sequence),
+            Self::Many(sequences) => sequences.as_slice(),
+        }
+    }
+}
+
+/// Token accounting read from agent session logs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct UsageConfig {
+    /// USD per million tokens keyed by model name prefix, overriding the built-in estimates.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prices: BTreeMap<String, ModelPriceConfig>,
+}
+
+impl UsageConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Continuous recording of agent and shell pane output under each task root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TranscriptsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl TranscriptsConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Hooks Grove adds to agent settings so status comes from the agent itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusHooksConfig {
+    #[serde(default = "default_status_hooks_enabled")]
+    pub enabled: bool,
+}
+
+const fn default_status_hooks_enabled() -> bool {
+    true
+}
+
+impl Default for StatusHooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_status_hooks_enabled(),
+        }
+    }
+}
+
+impl StatusHooksConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// User-defined status detection, checked with the built-in heuristics in
+/// priority order. Matches `pane_regex` against the pane tail, or every
+/// `jsonl` predicate against the latest record of the agent's session log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusRuleConfig {
+    pub name: String,
+    /// Agent id the rule applies to, every agent when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Higher runs first. Built-in rules have priority 0.
+    #[serde(default = "default_status_rule_priority")]
+    pub priority: i32,
+    pub status: StatusRuleStatus,
+    /// `blocked-on-question` or `permission-wall`, for `waiting` rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attention: Option<NotificationReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane_regex: Option<String>,
+    /// Trailing pane lines `pane_regex` is matched against.
+    #[serde(default = "default_status_rule_tail_lines")]
+    pub tail_lines: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jsonl: Vec<StatusRuleFieldConfig>,
+}
+
+const fn default_status_rule_priority() -> i32 {
+    10
+}
+
+const fn default_status_rule_tail_lines() -> usize {
+    8
+}
+
+/// Predicate on a dot-separated field of a session-log record. Without
+/// `equals` or `matches` the field only has to be present.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusRuleFieldConfig {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusRuleStatus {
+    Idle,
+    Active,
+    Thinking,
+    Waiting,
+    Done,
+    Error,
+}
+
+impl StatusRuleStatus {
+    pub const fn config_key(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Active => "active",
+            Self::Thinking => "thinking",
+            Self::Waiting => "waiting",
+            Self::Done => "done",
+            Self::Error => "error",
+        }
+    }
+
+    pub const fn workspace_status(self) -> WorkspaceStatus {
+        match self {
+            Self::Idle => WorkspaceStatus::Idle,
+            Self::Active => WorkspaceStatus::Active,
+            Self::Thinking => WorkspaceStatus::Thinking,
+            Self::Waiting => WorkspaceStatus::Waiting,
+            Self::Done => WorkspaceStatus::Done,
+            Self::Error => WorkspaceStatus::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct ModelPriceConfig {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+}
+
+impl PartialEq for ModelPriceConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.input.to_bits() == other.input.to_bits()
+            && self.output.to_bits() == other.output.to_bits()
+            && self.cache_read.to_bits() == other.cache_read.to_bits()
+            && self.cache_write.to_bits() == other.cache_write.to_bits()
+    }
+}
+
+impl Eq for ModelPriceConfig {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PullRequestForgeKind {
+    #[default]
+    Github,
+    None,
+}
+
+impl PullRequestForgeKind {
+    pub const fn config_key(self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::None => "none",
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AgentPermissionFlagsConfig {
+    #[serde(default)]
+    pub auto: Option<String>,
+    #[serde(default, rename = "unsafe")]
+    pub unsafe_mode: Option<String>,
+}
+
+/// Location and shape of an agent's jsonl session logs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentSessionLogConfig {
+    /// Directory holding session files. `~` expands to the home directory and
+    /// `{workspace}` / `{workspace_dir_name}` expand to the worktree path.
+    pub dir: String,
+    #[serde(default = "default_session_log_role_field")]
+    pub role_field: String,
+    #[serde(default = "default_session_log_user_value")]
+    pub user_value: String,
+    #[serde(default = "default_session_log_assistant_value")]
+    pub assistant_value: String,
+}
+
+fn default_session_log_role_field() -> String {
+    "type".to_string()
+}
+
+fn default_session_log_user_value() -> String {
+    "user".to_string()
+}
+
+fn default_session_log_assistant_value() -> String {
+    "assistant".to_string()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorkspaceAttentionAckConfig {
     pub workspace_path: PathBuf,
@@ -105,16 +579,172 @@ pub struct ProjectDefaults {
     pub workspace_init_command: String,
     #[serde(default)]
     pub agent_env: AgentEnvDefaults,
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+    #[serde(default)]
+    pub update_strategy: UpdateStrategy,
+    /// Leave a conflicted merge, squash or rebase in place for resolution
+    /// instead of aborting it.
+    #[serde(default)]
+    pub keep_conflicts: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookConfig>,
+    /// Runs agents inside bubblewrap or a container instead of on the host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<SandboxConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command_tabs: Vec<CommandTabConfig>,
+    /// Reserves a port range for each new worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<PortsConfig>,
 }
 
 pub type RepositoryDefaults = ProjectDefaults;
 
+/// Tab running a long-lived command in a worktree, e.g. a dev server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandTabConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restart: CommandTabRestart,
+    /// Opens the tab in every worktree created for the project.
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommandTabRestart {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortsConfig {
+    /// Ports reserved per worktree, exported from `PORT` upwards.
+    #[serde(default = "default_port_count")]
+    pub count: u16,
+}
+
+impl Default for PortsConfig {
+    fn default() -> Self {
+        Self {
+            count: default_port_count(),
+        }
+    }
+}
+
+const fn default_port_count() -> u16 {
+    10
+}
+
+/// Shell command run on a lifecycle event, with a JSON context on stdin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    pub event: HookEvent,
+    pub command: String,
+    /// Seconds before the hook is killed, a timed out `pre-*` hook vetoes.
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_hook_timeout_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    PreCreate,
+    PostCreate,
+    PreAgentStart,
+    PostAgentStop,
+    PreMerge,
+    PostMerge,
+    PreDelete,
+    OnAttention,
+}
+
+impl HookEvent {
+    pub const fn config_key(self) -> &'static str {
+        match self {
+            Self::PreCreate => "pre-create",
+            Self::PostCreate => "post-create",
+            Self::PreAgentStart => "pre-agent-start",
+            Self::PostAgentStop => "post-agent-stop",
+            Self::PreMerge => "pre-merge",
+            Self::PostMerge => "post-merge",
+            Self::PreDelete => "pre-delete",
+            Self::OnAttention => "on-attention",
+        }
+    }
+
+    /// `pre-*` hooks can veto the operation by failing.
+    pub const fn can_veto(self) -> bool {
+        matches!(
+            self,
+            Self::PreCreate | Self::PreAgentStart | Self::PreMerge | Self::PreDelete
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    #[serde(flatten)]
+    pub backend: SandboxBackend,
+    #[serde(default)]
+    pub network: SandboxNetwork,
+    /// Extra host paths mounted read-write, such as the agent's config dir.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mounts: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub read_only_mounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "kebab-case")]
+pub enum SandboxBackend {
+    Bubblewrap,
+    Podman { image: String },
+    Docker { image: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxNetwork {
+    #[default]
+    Host,
+    None,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct AgentEnvDefaults {
     #[serde(default)]
     pub claude: Vec<String>,
     #[serde(default)]
     pub codex: Vec<String>,
+    /// Entries for config-defined agents, keyed by agent id.
+    #[serde(default, flatten)]
+    pub custom: BTreeMap<String, Vec<String>>,
+}
+
+impl AgentEnvDefaults {
+    pub fn entries_for(&self, agent: &AgentType) -> &[String] {
+        match agent {
+            AgentType::Claude => &self.claude,
+            AgentType::Codex => &self.codex,
+            AgentType::Custom(agent) => self
+                .custom
+                .get(agent.marker())
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -128,17 +758,41 @@ pub struct GlobalSettings {
     #[serde(default = "default_sidebar_width_pct")]
     pub sidebar_width_pct: u16,
     #[serde(default)]
-    pub theme: ThemeName,
+    pub theme: ThemeKey,
     #[serde(default)]
     pub launch_permission_mode: PermissionMode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<AgentDefinitionConfig>,
+    #[serde(default, skip_serializing_if = "NotificationsConfig::is_default")]
+    pub notifications: NotificationsConfig,
+    #[serde(default, skip_serializing_if = "PullRequestSyncConfig::is_default")]
+    pub pull_request_sync: PullRequestSyncConfig,
+    #[serde(default, skip_serializing_if = "KeybindingsConfig::is_default")]
+    pub keybindings: KeybindingsConfig,
+    #[serde(default, skip_serializing_if = "UsageConfig::is_default")]
+    pub usage: UsageConfig,
+    #[serde(default, skip_serializing_if = "TranscriptsConfig::is_default")]
+    pub transcripts: TranscriptsConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_rules: Vec<StatusRuleConfig>,
+    #[serde(default, skip_serializing_if = "StatusHooksConfig::is_default")]
+    pub status_hooks: StatusHooksConfig,
 }
 
 impl Default for GlobalSettings {
     fn default() -> Self {
         Self {
             sidebar_width_pct: default_sidebar_width_pct(),
-            theme: ThemeName::default(),
+            theme: ThemeKey::default(),
             launch_permission_mode: PermissionMode::Default,
+            agents: Vec::new(),
+            notifications: NotificationsConfig::default(),
+            pull_request_sync: PullRequestSyncConfig::default(),
+            keybindings: KeybindingsConfig::default(),
+            usage: UsageConfig::default(),
+            transcripts: TranscriptsConfig::default(),
+            status_rules: Vec::new(),
+            status_hooks: StatusHooksConfig::default(),
         }
     }
 }
@@ -167,6 +821,15 @@ pub fn config_path() -> Option<PathBuf> {
     config_directory().map(|path| path.join("config.toml"))
 }
 
+/// Per-user task recipes, one `<name>.toml` each.
+pub fn recipes_dir() -> Option<PathBuf> {
+    config_directory().map(|path| path.join("recipes"))
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    config_directory().map(|path| path.join("themes"))
+}
+
 pub fn projects_path() -> Option<PathBuf> {
     config_path().map(|path| projects_path_for(path.as_path()))
 }
@@ -204,6 +867,14 @@ pub fn load_from_path(path: &Path) -> Result<GroveConfig, String> {
         attention_acks: projects.attention_acks,
         hidden_base_project_paths: projects.hidden_base_project_paths,
         launch_permission_mode: settings.launch_permission_mode,
+        agents: settings.agents,
+        notifications: settings.notifications,
+        pull_request_sync: settings.pull_request_sync,
+        keybindings: settings.keybindings,
+        usage: settings.usage,
+        transcripts: settings.transcripts,
+        status_rules: settings.status_rules,
+        status_hooks: settings.status_hooks,
     })
 }
 
@@ -291,10 +962,18 @@ pub fn save_to_path(path: &Path, config: &GroveConfig) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        AgentEnvDefaults, GlobalSettings, GroveConfig, PermissionMode, ProjectConfig,
-        ProjectDefaults, RepositoryConfig, RepositoryDefaults, ThemeName, load_from_path,
-        projects_path_for, save_global_to_path, save_projects_to_path, save_to_path,
+        AgentEnvDefaults, CommandTabConfig, CommandTabRestart, GlobalSettings, GroveConfig,
+        HookConfig, HookEvent, KeybindingSequences, KeybindingsConfig, MergeStrategy,
+        ModelPriceConfig, NotificationReason, NotificationSinkConfig, NotificationSinkKind,
+        NotificationsConfig, PermissionMode, PortsConfig, ProjectConfig, ProjectDefaults,
+        PullRequestForgeKind, PullRequestSyncConfig, RepositoryConfig, RepositoryDefaults,
+        SandboxBackend, SandboxConfig, SandboxNetwork, StatusHooksConfig, StatusRuleConfig,
+        StatusRuleFieldConfig, StatusRuleStatus, ThemeKey, ThemeName, TranscriptsConfig,
+        UpdateStrategy, UsageConfig, load_from_path, projects_path_for, save_global_to_path,
+        save_projects_to_path, save_to_path,
     };
+    use crate::infrastructure::themes::ThemeRegistry;
+    use std::collections::BTreeMap;
     use std::fs;
     use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -321,12 +1000,20 @@ mod tests {
             config,
             GroveConfig {
                 sidebar_width_pct: 33,
-                theme: ThemeName::CatppuccinMocha,
+                theme: ThemeName::CatppuccinMocha.into(),
                 projects: Vec::new(),
                 task_order: Vec::new(),
                 attention_acks: Vec::new(),
                 hidden_base_project_paths: Vec::new(),
                 launch_permission_mode: PermissionMode::Default,
+                agents: Vec::new(),
+                notifications: NotificationsConfig::default(),
+                pull_request_sync: PullRequestSyncConfig::default(),
+                keybindings: KeybindingsConfig::default(),
+                usage: UsageConfig::default(),
+                transcripts: TranscriptsConfig::default(),
+                status_rules: Vec::new(),
+                status_hooks: StatusHooksConfig::default(),
             }
         );
     }
@@ -340,6 +1027,13 @@ mod tests {
                 base_branch: "main".to_string(),
                 workspace_init_command: "direnv allow".to_string(),
                 agent_env: AgentEnvDefaults::default(),
+                merge_strategy: MergeStrategy::Merge,
+                update_strategy: UpdateStrategy::Merge,
+                keep_conflicts: false,
+                hooks: Vec::new(),
+                sandbox: None,
+                command_tabs: Vec::new(),
+                ports: None,
             },
         };
 
@@ -353,7 +1047,7 @@ mod tests {
         let path = unique_temp_path("roundtrip");
         let config = GroveConfig {
             sidebar_width_pct: 52,
-            theme: ThemeName::Monokai,
+            theme: ThemeName::Monokai.into(),
             projects: vec![ProjectConfig {
                 name: "grove".to_string(),
                 path: PathBuf::from("/repos/grove"),
@@ -363,13 +1057,128 @@ mod tests {
                     agent_env: AgentEnvDefaults {
                         claude: vec!["CLAUDE_CONFIG_DIR=~/.claude-work".to_string()],
                         codex: vec!["CODEX_CONFIG_DIR=~/.codex-work".to_string()],
+                        custom: [("aider".to_string(), vec!["AIDER_MODEL=sonnet".to_string()])]
+                            .into_iter()
+                            .collect(),
                     },
+                    merge_strategy: MergeStrategy::Squash,
+                    update_strategy: UpdateStrategy::Rebase,
+                    keep_conflicts: true,
+                    hooks: vec![
+                        HookConfig {
+                            event: HookEvent::PreMerge,
+                            command: "make check".to_string(),
+                            timeout_secs: 120,
+                        },
+                        HookConfig {
+                            event: HookEvent::OnAttention,
+                            command: "./notify.sh".to_string(),
+                            timeout_secs: 60,
+                        },
+                    ],
+                    sandbox: Some(SandboxConfig {
+                        backend: SandboxBackend::Podman {
+                            image: "ghcr.io/acme/agent:latest".to_string(),
+                        },
+                        network: SandboxNetwork::None,
+                        mounts: vec!["~/.claude".to_string()],
+                        read_only_mounts: vec!["~/.gitconfig".to_string()],
+                    }),
+                    command_tabs: vec![CommandTabConfig {
+                        name: "dev server".to_string(),
+                        command: "npm run dev".to_string(),
+                        env: [("PORT".to_string(), "3000".to_string())]
+                            .into_iter()
+                            .collect(),
+                        restart: CommandTabRestart::OnFailure,
+                        auto_start: true,
+                    }],
+                    ports: Some(PortsConfig { count: 4 }),
                 },
             }],
             task_order: vec!["grove".to_string(), "task-workflow".to_string()],
             attention_acks: Vec::new(),
             hidden_base_project_paths: vec![PathBuf::from("/repos/hidden")],
             launch_permission_mode: PermissionMode::Unsafe,
+            agents: Vec::new(),
+            notifications: NotificationsConfig {
+                sinks: vec![
+                    NotificationSinkConfig {
+                        kind: NotificationSinkKind::Webhook,
+                        command: None,
+                        url: Some("http://127.0.0.1:9000/grove".to_string()),
+                        reasons: vec![NotificationReason::BlockedOnQuestion],
+                    },
+                    NotificationSinkConfig {
+                        kind: NotificationSinkKind::Desktop,
+                        command: None,
+                        url: None,
+                        reasons: Vec::new(),
+                    },
+                ],
+                reasons: vec![
+                    NotificationReason::BlockedOnQuestion,
+                    NotificationReason::Finished,
+                ],
+                debounce_secs: 60,
+                quiet_hours: Some("22:00-07:00".to_string()),
+            },
+            pull_request_sync: PullRequestSyncConfig {
+                forge: PullRequestForgeKind::None,
+                interval_secs: 300,
+            },
+            keybindings: KeybindingsConfig {
+                leader: Some("space".to_string()),
+                commands: BTreeMap::from([
+                    (
+                        "palette:toggle_sidebar".to_string(),
+                        KeybindingSequences::One("<leader> b".to_string()),
+                    ),
+                    (
+                        "palette:quit".to_string(),
+                        KeybindingSequences::Many(vec!["q".to_string(), "ctrl+q".to_string()]),
+                    ),
+                ]),
+            },
+            usage: UsageConfig {
+                prices: BTreeMap::from([(
+                    "claude-sonnet-4".to_string(),
+                    ModelPriceConfig {
+                        input: 3.0,
+                        output: 15.0,
+                        cache_read: 0.3,
+                        cache_write: 3.75,
+                    },
+                )]),
+            },
+            transcripts: TranscriptsConfig { enabled: true },
+            status_rules: vec![
+                StatusRuleConfig {
+                    name: "aider-confirm".to_string(),
+                    agent: Some("aider".to_string()),
+                    priority: 20,
+                    status: StatusRuleStatus::Waiting,
+                    attention: Some(NotificationReason::PermissionWall),
+                    pane_regex: Some(r"\(Y\)es/\(N\)o".to_string()),
+                    tail_lines: 4,
+                    jsonl: Vec::new(),
+                },
+                StatusRuleConfig {
+                    name: "codex-approval".to_string(),
+                    agent: None,
+                    priority: 10,
+                    status: StatusRuleStatus::Waiting,
+                    attention: None,
+                    pane_regex: None,
+                    tail_lines: 8,
+                    jsonl: vec![StatusRuleFieldConfig {
+                        field: "payload.type".to_string(),
+                        equals: Some("exec_approval_request".to_string()),
+                        matches: None,
+                    }],
+                },
+            ],
+            status_hooks: StatusHooksConfig { enabled: false },
         };
         save_to_path(&path, &config).expect("config should save");
 
@@ -386,10 +1195,123 @@ mod tests {
 
         let loaded = load_from_path(&path).expect("legacy config should load");
         assert_eq!(loaded.sidebar_width_pct, 33);
-        assert_eq!(loaded.theme, ThemeName::CatppuccinMocha);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::CatppuccinMocha));
         assert_eq!(loaded.projects, Vec::<ProjectConfig>::new());
         assert_eq!(loaded.task_order, Vec::<String>::new());
         assert_eq!(loaded.hidden_base_project_paths, Vec::<PathBuf>::new());
+        assert!(loaded.status_hooks.enabled);
+
+        cleanup_files(path.as_path());
+    }
+
+    #[test]
+    fn load_reads_notification_sinks_from_global_config() {
+        let path = unique_temp_path("notifications");
+        fs::write(
+            &path,
+            "[notifications]\nreasons = [\"blocked-on-question\", \"permission-wall\"]\nquiet_hours = \"22:00-07:00\"\n\n[[notifications.sinks]]\nkind = \"command\"\ncommand = \"say done\"\nreasons = [\"finished\"]\n\n[[notifications.sinks]]\nkind = \"terminal\"\n",
+        )
+        .expect("fixture should write");
+
+        let loaded = load_from_path(&path).expect("notifications config should load");
+        assert_eq!(
+            loaded.notifications.reasons,
+            vec![
+                NotificationReason::BlockedOnQuestion,
+                NotificationReason::PermissionWall
+            ]
+        );
+        assert_eq!(loaded.notifications.debounce_secs, 300);
+        assert_eq!(
+            loaded.notifications.quiet_hours.as_deref(),
+            Some("22:00-07:00")
+        );
+        assert_eq!(
+            loaded.notifications.sinks,
+            vec![
+                NotificationSinkConfig {
+                    kind: NotificationSinkKind::Command,
+                    command: Some("say done".to_string()),
+                    url: None,
+                    reasons: vec![NotificationReason::Finished],
+                },
+                NotificationSinkConfig {
+                    kind: NotificationSinkKind::Terminal,
+                    command: None,
+                    url: None,
+                    reasons: Vec::new(),
+                },
+            ]
+        );
+
+        cleanup_files(path.as_path());
+    }
+
+    #[test]
+    fn load_reads_keybindings_with_leader_and_alternatives() {
+        let path = unique_temp_path("keybindings");
+        fs::write(
+            &path,
+            "[keybindings]\nleader = \"ctrl+a\"\n\"palette:toggle_sidebar\" = \"<leader> b\"\n\"palette:quit\" = [\"q\", \"ctrl+q\"]\n",
+        )
+        .expect("fixture should write");
+
+        let loaded = load_from_path(&path).expect("keybindings config should load");
+        assert_eq!(loaded.keybindings.leader.as_deref(), Some("ctrl+a"));
+        assert_eq!(
+            loaded
+                .keybindings
+                .commands
+                .get("palette:toggle_sidebar")
+                .map(KeybindingSequences::as_slice),
+            Some(["<leader> b".to_string()].as_slice())
+        );
+        assert_eq!(
+            loaded
+                .keybindings
+                .commands
+                .get("palette:quit")
+                .map(KeybindingSequences::as_slice),
+            Some(["q".to_string(), "ctrl+q".to_string()].as_slice())
+        );
+
+        cleanup_files(path.as_path());
+    }
+
+    #[test]
+    fn load_keeps_theme_key_until_resolved_against_loaded_themes() {
+        let path = unique_temp_path("custom-theme");
+        fs::write(&path, "theme = \" gruvbox-dark \"\n").expect("fixture should write");
+
+        let loaded = load_from_path(&path).expect("custom theme config should load");
+        assert_eq!(loaded.theme.as_str(), "gruvbox-dark");
+        let registry = ThemeRegistry::default();
+        assert_eq!(
+            registry.resolve(&loaded.theme),
+            Err("unknown theme 'gruvbox-dark'".to_string())
+        );
+        assert_eq!(
+            registry.resolve(&ThemeKey::from(ThemeName::RosePine)),
+            Ok(ThemeName::RosePine)
+        );
+        fs::write(&path, "theme = \"  \"\n").expect("fixture should write");
+        assert!(load_from_path(&path).is_err());
+
+        cleanup_files(path.as_path());
+    }
+
+    #[test]
+    fn load_rejects_container_sandbox_without_image() {
+        let path = unique_temp_path("sandbox-image");
+        let projects_path = projects_path_for(path.as_path());
+        fs::write(
+            &projects_path,
+            "[[projects]]\nname = \"grove\"\npath = \"/repos/grove\"\n\n[projects.defaults.sandbox]\nbackend = \"docker\"\n",
+        )
+        .expect("fixture should write");
+
+        let error = load_from_path(&path).expect_err("docker sandbox needs an image");
+        assert!(error.contains("image"), "unexpected error: {error}");
 
         cleanup_files(path.as_path());
     }
@@ -407,7 +1329,7 @@ mod tests {
         let loaded = load_from_path(&path).expect("legacy project config should load");
         assert_eq!(loaded.projects.len(), 1);
         assert_eq!(loaded.sidebar_width_pct, 33);
-        assert_eq!(loaded.theme, ThemeName::CatppuccinMocha);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::CatppuccinMocha));
         assert_eq!(loaded.attention_acks, Vec::new());
         assert_eq!(loaded.task_order, Vec::<String>::new());
         assert_eq!(loaded.hidden_base_project_paths, Vec::<PathBuf>::new());
@@ -418,6 +1340,15 @@ mod tests {
             loaded.projects[0].defaults.agent_env,
             AgentEnvDefaults::default()
         );
+        assert_eq!(
+            loaded.projects[0].defaults.merge_strategy,
+            MergeStrategy::Merge
+        );
+        assert_eq!(
+            loaded.projects[0].defaults.update_strategy,
+            UpdateStrategy::Merge
+        );
+        assert!(!loaded.projects[0].defaults.keep_conflicts);
 
         cleanup_files(path.as_path());
     }
@@ -428,7 +1359,7 @@ mod tests {
         let projects_path = projects_path_for(path.as_path());
         let initial = GroveConfig {
             sidebar_width_pct: 33,
-            theme: ThemeName::CatppuccinMocha,
+            theme: ThemeName::CatppuccinMocha.into(),
             projects: vec![ProjectConfig {
                 name: "grove".to_string(),
                 path: PathBuf::from("/repos/grove"),
@@ -438,6 +1369,14 @@ mod tests {
             attention_acks: Vec::new(),
             hidden_base_project_paths: vec![PathBuf::from("/repos/hidden")],
             launch_permission_mode: PermissionMode::Default,
+            agents: Vec::new(),
+            notifications: NotificationsConfig::default(),
+            pull_request_sync: PullRequestSyncConfig::default(),
+            keybindings: KeybindingsConfig::default(),
+            usage: UsageConfig::default(),
+            transcripts: TranscriptsConfig::default(),
+            status_rules: Vec::new(),
+            status_hooks: StatusHooksConfig::default(),
         };
         save_projects_to_path(
             &projects_path,
@@ -449,14 +1388,22 @@ mod tests {
         .expect("projects should save");
         let updated = GlobalSettings {
             sidebar_width_pct: 48,
-            theme: ThemeName::CatppuccinLatte,
+            theme: ThemeName::CatppuccinLatte.into(),
             launch_permission_mode: PermissionMode::Unsafe,
+            agents: Vec::new(),
+            notifications: NotificationsConfig::default(),
+            pull_request_sync: PullRequestSyncConfig::default(),
+            keybindings: KeybindingsConfig::default(),
+            usage: UsageConfig::default(),
+            transcripts: TranscriptsConfig::default(),
+            status_rules: Vec::new(),
+            status_hooks: StatusHooksConfig::default(),
         };
         save_global_to_path(&path, &updated).expect("global settings should save");
 
         let loaded = load_from_path(&path).expect("combined config should load");
         assert_eq!(loaded.sidebar_width_pct, 48);
-        assert_eq!(loaded.theme, ThemeName::CatppuccinLatte);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::CatppuccinLatte));
         assert_eq!(loaded.launch_permission_mode, PermissionMode::Unsafe);
         assert_eq!(loaded.projects.len(), 1);
         assert_eq!(loaded.projects[0].name, "grove");
@@ -471,8 +1418,16 @@ mod tests {
         let projects_path = projects_path_for(path.as_path());
         let settings = GlobalSettings {
             sidebar_width_pct: 61,
-            theme: ThemeName::CatppuccinFrappe,
+            theme: ThemeName::CatppuccinFrappe.into(),
             launch_permission_mode: PermissionMode::Unsafe,
+            agents: Vec::new(),
+            notifications: NotificationsConfig::default(),
+            pull_request_sync: PullRequestSyncConfig::default(),
+            keybindings: KeybindingsConfig::default(),
+            usage: UsageConfig::default(),
+            transcripts: TranscriptsConfig::default(),
+            status_rules: Vec::new(),
+            status_hooks: StatusHooksConfig::default(),
         };
         save_global_to_path(&path, &settings).expect("global settings should save");
         let projects = vec![ProjectConfig {
@@ -486,7 +1441,7 @@ mod tests {
 
         let loaded = load_from_path(&path).expect("combined config should load");
         assert_eq!(loaded.sidebar_width_pct, 61);
-        assert_eq!(loaded.theme, ThemeName::CatppuccinFrappe);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::CatppuccinFrappe));
         assert_eq!(loaded.launch_permission_mode, PermissionMode::Unsafe);
         assert_eq!(loaded.projects.len(), 1);
         assert_eq!(loaded.projects[0].name, "grove");
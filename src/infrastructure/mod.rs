@@ -4,4 +4,6 @@ pub mod event_log;
 pub mod paths;
 pub mod process;
 pub mod process_metrics;
+pub mod state_store;
 pub mod task_manifest;
+pub mod themes;
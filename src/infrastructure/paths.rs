@@ -11,13 +11,29 @@ pub(crate) fn tasks_root() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".grove").join("tasks"))
 }
 
+/// Archived tasks, one directory per task slug.
+pub(crate) fn archive_root() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".grove").join("archive"))
+}
+
+/// Copies of files `doctor --fix` changed, one directory per run.
+pub(crate) fn doctor_backup_root() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".grove").join("doctor-backups"))
+}
+
+pub(crate) fn state_db_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))
+        .map(|state| state.join("grove").join("grove.db"))
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
     use std::path::PathBuf;
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    use super::{refer_to_same_location, tasks_root};
+    use super::{refer_to_same_location, state_db_path, tasks_root};
 
     #[derive(Debug)]
     struct TestDir {
@@ -85,4 +101,13 @@ mod tests {
 
         assert_eq!(actual, home.join(".grove").join("tasks"));
     }
+
+    #[test]
+    fn state_db_path_lives_in_grove_state_directory() {
+        let Some(actual) = state_db_path() else {
+            return;
+        };
+
+        assert!(actual.ends_with("grove/grove.db"));
+    }
 }
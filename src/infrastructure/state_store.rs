@@ -0,0 +1,667 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::domain::WorkspaceStatus;
+use crate::infrastructure::paths::state_db_path;
+use crate::infrastructure::task_manifest::{parse_workspace_status, workspace_status_name};
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Schema migrations, applied in order. The index plus one is the
+/// `user_version` a database reaches once the migration has run.
+const MIGRATIONS: &[&str] = &["
+    CREATE TABLE task_events (
+        id INTEGER PRIMARY KEY,
+        ts_ms INTEGER NOT NULL,
+        task_slug TEXT NOT NULL,
+        event TEXT NOT NULL,
+        worktree_path TEXT,
+        detail TEXT
+    );
+    CREATE INDEX task_events_by_task ON task_events (task_slug, ts_ms);
+
+    CREATE TABLE status_transitions (
+        id INTEGER PRIMARY KEY,
+        ts_ms INTEGER NOT NULL,
+        workspace_path TEXT NOT NULL,
+        task_slug TEXT,
+        from_status TEXT NOT NULL,
+        to_status TEXT NOT NULL
+    );
+    CREATE INDEX status_transitions_by_workspace
+        ON status_transitions (workspace_path, ts_ms);
+
+    CREATE TABLE attention_items (
+        workspace_path TEXT NOT NULL,
+        fingerprint TEXT NOT NULL,
+        task_slug TEXT,
+        reason TEXT NOT NULL,
+        summary TEXT NOT NULL,
+        first_seen_ms INTEGER NOT NULL,
+        last_seen_ms INTEGER NOT NULL,
+        acknowledged_ms INTEGER,
+        cleared_ms INTEGER,
+        PRIMARY KEY (workspace_path, fingerprint)
+    );
+
+    CREATE TABLE task_manifests (
+        task_root TEXT PRIMARY KEY,
+        manifest TEXT NOT NULL,
+        updated_ms INTEGER NOT NULL
+    );
+"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskEventRecord {
+    pub ts_ms: u64,
+    pub task_slug: String,
+    pub event: String,
+    pub worktree_path: Option<PathBuf>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttentionRecord {
+    pub workspace_path: PathBuf,
+    pub fingerprint: String,
+    pub task_slug: Option<String>,
+    pub reason: String,
+    pub summary: String,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    pub acknowledged_ms: Option<u64>,
+    pub cleared_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedWorkspace {
+    pub workspace_path: PathBuf,
+    pub task_slug: Option<String>,
+}
+
+/// Indexed history of task lifecycle events, status transitions and
+/// attention items, plus a cache of the last readable task manifests.
+/// Manifests stay the source of truth for task layout.
+pub struct StateStore {
+    connection: Connection,
+    path: Option<PathBuf>,
+}
+
+impl StateStore {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("state store directory create failed: {error}"))?;
+        }
+        let connection =
+            Connection::open(path).map_err(|error| format!("state store open failed: {error}"))?;
+        connection
+            .busy_timeout(BUSY_TIMEOUT)
+            .map_err(|error| format!("state store configure failed: {error}"))?;
+        connection
+            .query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .map_err(|error| format!("state store configure failed: {error}"))?;
+        Self::with_connection(connection, Some(path.to_path_buf()))
+    }
+
+    pub fn open_default() -> Result<Self, String> {
+        let path = state_db_path().ok_or_else(|| "home directory unavailable".to_string())?;
+        Self::open(&path)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        let connection = Connection::open_in_memory()
+            .map_err(|error| format!("state store open failed: {error}"))?;
+        Self::with_connection(connection, None)
+    }
+
+    fn with_connection(mut connection: Connection, path: Option<PathBuf>) -> Result<Self, String> {
+        migrate(&mut connection)?;
+        Ok(Self { connection, path })
+    }
+
+    /// Database file backing this store, `None` for in-memory stores.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn schema_version(&self) -> Result<usize, String> {
+        user_version(&self.connection)
+    }
+
+    pub fn record_task_event(
+        &self,
+        ts_ms: u64,
+        task_slug: &str,
+        event: &str,
+        worktree_path: Option<&Path>,
+        detail: Option<&str>,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT INTO task_events (ts_ms, task_slug, event, worktree_path, detail)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    to_sql_ms(ts_ms),
+                    task_slug,
+                    event,
+                    worktree_path.map(path_text),
+                    detail
+                ],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("task event record failed: {error}"))
+    }
+
+    pub fn record_status_transition(
+        &self,
+        ts_ms: u64,
+        workspace_path: &Path,
+        task_slug: Option<&str>,
+        from_status: WorkspaceStatus,
+        to_status: WorkspaceStatus,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT INTO status_transitions
+                    (ts_ms, workspace_path, task_slug, from_status, to_status)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    to_sql_ms(ts_ms),
+                    path_text(workspace_path),
+                    task_slug,
+                    workspace_status_name(from_status),
+                    workspace_status_name(to_status)
+                ],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("status transition record failed: {error}"))
+    }
+
+    /// Records an attention item as raised. Raising it again after it was
+    /// cleared reopens the same row.
+    pub fn record_attention_raised(&self, record: &AttentionRecord) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT INTO attention_items (
+                    workspace_path, fingerprint, task_slug, reason, summary,
+                    first_seen_ms, last_seen_ms, acknowledged_ms, cleared_ms
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL, NULL)
+                 ON CONFLICT (workspace_path, fingerprint) DO UPDATE SET
+                    last_seen_ms = excluded.last_seen_ms,
+                    cleared_ms = NULL",
+                params![
+                    path_text(&record.workspace_path),
+                    record.fingerprint,
+                    record.task_slug,
+                    record.reason,
+                    record.summary,
+                    to_sql_ms(record.first_seen_ms),
+                    to_sql_ms(record.last_seen_ms)
+                ],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("attention record failed: {error}"))
+    }
+
+    pub fn record_attention_acknowledged(
+        &self,
+        ts_ms: u64,
+        workspace_path: &Path,
+        fingerprint: &str,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "UPDATE attention_items
+                 SET acknowledged_ms = ?1, last_seen_ms = MAX(last_seen_ms, ?1)
+                 WHERE workspace_path = ?2 AND fingerprint = ?3",
+                params![to_sql_ms(ts_ms), path_text(workspace_path), fingerprint],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("attention ack record failed: {error}"))
+    }
+
+    pub fn record_attention_cleared(
+        &self,
+        ts_ms: u64,
+        workspace_path: &Path,
+        fingerprint: &str,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "UPDATE attention_items
+                 SET cleared_ms = ?1, last_seen_ms = MAX(last_seen_ms, ?1)
+                 WHERE workspace_path = ?2 AND fingerprint = ?3 AND cleared_ms IS NULL",
+                params![to_sql_ms(ts_ms), path_text(workspace_path), fingerprint],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("attention clear record failed: {error}"))
+    }
+
+    /// Remembers the raw manifest of a task so discovery can fall back to it
+    /// if the file on disk later fails to parse.
+    pub fn cache_task_manifest(
+        &self,
+        ts_ms: u64,
+        task_root: &Path,
+        manifest: &str,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT INTO task_manifests (task_root, manifest, updated_ms)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT (task_root) DO UPDATE SET
+                    manifest = excluded.manifest,
+                    updated_ms = excluded.updated_ms
+                 WHERE manifest != excluded.manifest",
+                params![path_text(task_root), manifest, to_sql_ms(ts_ms)],
+            )
+            .map(|_| ())
+            .map_err(|error| format!("task manifest cache failed: {error}"))
+    }
+
+    pub fn cached_task_manifest(&self, task_root: &Path) -> Result<Option<String>, String> {
+        self.connection
+            .query_row(
+                "SELECT manifest FROM task_manifests WHERE task_root = ?1",
+                params![path_text(task_root)],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|error| format!("task manifest cache read failed: {error}"))
+    }
+
+    pub fn task_events(
+        &self,
+        task_slug: Option<&str>,
+        since_ms: u64,
+    ) -> Result<Vec<TaskEventRecord>, String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT ts_ms, task_slug, event, worktree_path, detail FROM task_events
+                 WHERE ts_ms >= ?1 AND (?2 IS NULL OR task_slug = ?2)
+                 ORDER BY ts_ms, id",
+            )
+            .map_err(|error| format!("task events query failed: {error}"))?;
+        let rows = statement
+            .query_map(params![to_sql_ms(since_ms), task_slug], |row| {
+                Ok(TaskEventRecord {
+                    ts_ms: from_sql_ms(row.get(0)?),
+                    task_slug: row.get(1)?,
+                    event: row.get(2)?,
+                    worktree_path: row.get::<_, Option<String>>(3)?.map(PathBuf::from),
+                    detail: row.get(4)?,
+                })
+            })
+            .map_err(|error| format!("task events query failed: {error}"))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("task events query failed: {error}"))
+    }
+
+    pub fn attention_history(
+        &self,
+        task_slug: Option<&str>,
+        since_ms: u64,
+    ) -> Result<Vec<AttentionRecord>, String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT workspace_path, fingerprint, task_slug, reason, summary,
+                        first_seen_ms, last_seen_ms, acknowledged_ms, cleared_ms
+                 FROM attention_items
+                 WHERE last_seen_ms >= ?1 AND (?2 IS NULL OR task_slug = ?2)
+                 ORDER BY first_seen_ms, workspace_path",
+            )
+            .map_err(|error| format!("attention query failed: {error}"))?;
+        let rows = statement
+            .query_map(params![to_sql_ms(since_ms), task_slug], |row| {
+                Ok(AttentionRecord {
+                    workspace_path: PathBuf::from(row.get::<_, String>(0)?),
+                    fingerprint: row.get(1)?,
+                    task_slug: row.get(2)?,
+                    reason: row.get(3)?,
+                    summary: row.get(4)?,
+                    first_seen_ms: from_sql_ms(row.get(5)?),
+                    last_seen_ms: from_sql_ms(row.get(6)?),
+                    acknowledged_ms: row.get::<_, Option<i64>>(7)?.map(from_sql_ms),
+                    cleared_ms: row.get::<_, Option<i64>>(8)?.map(from_sql_ms),
+                })
+            })
+            .map_err(|error| format!("attention query failed: {error}"))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("attention query failed: {error}"))
+    }
+
+    /// Workspaces with at least one recorded status transition.
+    pub fn tracked_workspaces(
+        &self,
+        task_slug: Option<&str>,
+    ) -> Result<Vec<TrackedWorkspace>, String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT workspace_path, MAX(task_slug) FROM status_transitions
+                 WHERE ?1 IS NULL OR task_slug = ?1
+                 GROUP BY workspace_path
+                 ORDER BY workspace_path",
+            )
+            .map_err(|error| format!("tracked workspaces query failed: {error}"))?;
+        let rows = statement
+            .query_map(params![task_slug], |row| {
+                Ok(TrackedWorkspace {
+                    workspace_path: PathBuf::from(row.get::<_, String>(0)?),
+                    task_slug: row.get(1)?,
+                })
+            })
+            .map_err(|error| format!("tracked workspaces query failed: {error}"))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("tracked workspaces query failed: {error}"))
+    }
+
+    /// Time spent in each status between `since_ms` and `until_ms`, longest
+    /// first. The status in effect at `since_ms` comes from the last
+    /// transition before the window.
+    pub fn status_durations(
+        &self,
+        workspace_path: &Path,
+        since_ms: u64,
+        until_ms: u64,
+    ) -> Result<Vec<(WorkspaceStatus, u64)>, String> {
+        let workspace_path = path_text(workspace_path);
+        let query_error = |error: rusqlite::Error| format!("status history query failed: {error}");
+        let initial_status = self
+            .connection
+            .query_row(
+                "SELECT to_status FROM status_transitions
+                 WHERE workspace_path = ?1 AND ts_ms <= ?2
+                 ORDER BY ts_ms DESC, id DESC LIMIT 1",
+                params![workspace_path, to_sql_ms(since_ms)],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(query_error)?;
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT ts_ms, to_status FROM status_transitions
+                 WHERE workspace_path = ?1 AND ts_ms > ?2 AND ts_ms <= ?3
+                 ORDER BY ts_ms, id",
+            )
+            .map_err(query_error)?;
+        let transitions = statement
+            .query_map(
+                params![workspace_path, to_sql_ms(since_ms), to_sql_ms(until_ms)],
+                |row| Ok((from_sql_ms(row.get(0)?), row.get::<_, String>(1)?)),
+            )
+            .map_err(query_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(query_error)?;
+
+        let mut durations: Vec<(WorkspaceStatus, u64)> = Vec::new();
+        let mut add = |status: Option<&str>, elapsed_ms: u64| {
+            let Some(status) = status.and_then(parse_workspace_status) else {
+                return;
+            };
+            if elapsed_ms == 0 {
+                return;
+            }
+            match durations.iter_mut().find(|(known, _)| *known == status) {
+                Some((_, total)) => *total = total.saturating_add(elapsed_ms),
+                None => durations.push((status, elapsed_ms)),
+            }
+        };
+        let mut cursor_ms = since_ms;
+        let mut current_status = initial_status;
+        for (ts_ms, status) in transitions {
+            add(current_status.as_deref(), ts_ms.saturating_sub(cursor_ms));
+            cursor_ms = ts_ms;
+            current_status = Some(status);
+        }
+        add(
+            current_status.as_deref(),
+            until_ms.saturating_sub(cursor_ms),
+        );
+
+        durations.sort_by_key(|(_, elapsed_ms)| std::cmp::Reverse(*elapsed_ms));
+        Ok(durations)
+    }
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), String> {
+    let current = user_version(connection)?;
+    if current > MIGRATIONS.len() {
+        return Err(format!(
+            "state store schema v{current} is newer than this grove (v{})",
+            MIGRATIONS.len()
+        ));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current) {
+        let version = index + 1;
+        let transaction = connection
+            .transaction()
+            .map_err(|error| format!("state store migration v{version} failed: {error}"))?;
+        transaction
+            .execute_batch(migration)
+            .map_err(|error| format!("state store migration v{version} failed: {error}"))?;
+        transaction
+            .pragma_update(None, "user_version", version)
+            .map_err(|error| format!("state store migration v{version} failed: {error}"))?;
+        transaction
+            .commit()
+            .map_err(|error| format!("state store migration v{version} failed: {error}"))?;
+    }
+    Ok(())
+}
+
+fn user_version(connection: &Connection) -> Result<usize, String> {
+    connection
+        .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map(|version| usize::try_from(version).unwrap_or(0))
+        .map_err(|error| format!("state store version read failed: {error}"))
+}
+
+fn path_text(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn to_sql_ms(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn from_sql_ms(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttentionRecord, MIGRATIONS, StateStore, TrackedWorkspace};
+    use crate::domain::WorkspaceStatus;
+    use crate::test_support::unique_test_dir;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn unique_db_path(label: &str) -> PathBuf {
+        unique_test_dir(&format!("grove-state-store-{label}")).join("grove.db")
+    }
+
+    fn cleanup(path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::remove_dir_all(parent);
+        }
+    }
+
+    fn fixture_attention(fingerprint: &str, seen_ms: u64) -> AttentionRecord {
+        AttentionRecord {
+            workspace_path: PathBuf::from("/tmp/.grove/tasks/feature-a/grove"),
+            fingerprint: fingerprint.to_string(),
+            task_slug: Some("feature-a".to_string()),
+            reason: "needs_input".to_string(),
+            summary: "needs your input".to_string(),
+            first_seen_ms: seen_ms,
+            last_seen_ms: seen_ms,
+            acknowledged_ms: None,
+            cleared_ms: None,
+        }
+    }
+
+    #[test]
+    fn open_migrates_new_database_and_is_idempotent() {
+        let path = unique_db_path("migrate");
+        let store = StateStore::open(&path).expect("state store should open");
+        assert_eq!(store.schema_version(), Ok(MIGRATIONS.len()));
+        assert_eq!(store.path(), Some(path.as_path()));
+        store
+            .record_task_event(10, "feature-a", "created", None, None)
+            .expect("event should record");
+        drop(store);
+
+        let reopened = StateStore::open(&path).expect("state store should reopen");
+        assert_eq!(reopened.schema_version(), Ok(MIGRATIONS.len()));
+        assert_eq!(
+            reopened.task_events(None, 0).map(|events| events.len()),
+            Ok(1)
+        );
+        drop(reopened);
+        cleanup(&path);
+    }
+
+    #[test]
+    fn open_rejects_database_from_newer_grove() {
+        let path = unique_db_path("newer");
+        drop(StateStore::open(&path).expect("state store should open"));
+        let connection = rusqlite::Connection::open(&path).expect("database should open");
+        connection
+            .pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .expect("version should update");
+        drop(connection);
+
+        let error = StateStore::open(&path)
+            .err()
+            .expect("newer schema should be rejected");
+        assert!(error.contains("newer than this grove"));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn task_events_filter_by_task_and_time() {
+        let store = StateStore::open_in_memory().expect("state store should open");
+        let worktree = Path::new("/tmp/.grove/tasks/feature-a/grove");
+        store
+            .record_task_event(100, "feature-a", "created", None, None)
+            .expect("event should record");
+        store
+            .record_task_event(200, "feature-b", "created", None, None)
+            .expect("event should record");
+        store
+            .record_task_event(
+                300,
+                "feature-a",
+                "merged",
+                Some(worktree),
+                Some("into main"),
+            )
+            .expect("event should record");
+
+        let events = store
+            .task_events(Some("feature-a"), 150)
+            .expect("events should load");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "merged");
+        assert_eq!(events[0].worktree_path.as_deref(), Some(worktree));
+        assert_eq!(events[0].detail.as_deref(), Some("into main"));
+        assert_eq!(store.task_events(None, 0).map(|events| events.len()), Ok(3));
+    }
+
+    #[test]
+    fn status_durations_split_window_by_transitions() {
+        let store = StateStore::open_in_memory().expect("state store should open");
+        let workspace = Path::new("/tmp/.grove/tasks/feature-a/grove");
+        for (ts_ms, from, to) in [
+            (50, WorkspaceStatus::Idle, WorkspaceStatus::Active),
+            (200, WorkspaceStatus::Active, WorkspaceStatus::Waiting),
+            (500, WorkspaceStatus::Waiting, WorkspaceStatus::Active),
+            (700, WorkspaceStatus::Active, WorkspaceStatus::Waiting),
+        ] {
+            store
+                .record_status_transition(ts_ms, workspace, Some("feature-a"), from, to)
+                .expect("transition should record");
+        }
+
+        let durations = store
+            .status_durations(workspace, 100, 1_000)
+            .expect("durations should load");
+
+        assert_eq!(
+            durations,
+            vec![
+                (WorkspaceStatus::Waiting, 600),
+                (WorkspaceStatus::Active, 300),
+            ]
+        );
+        assert_eq!(
+            store.tracked_workspaces(Some("feature-a")),
+            Ok(vec![TrackedWorkspace {
+                workspace_path: workspace.to_path_buf(),
+                task_slug: Some("feature-a".to_string()),
+            }])
+        );
+        assert_eq!(store.tracked_workspaces(Some("feature-b")), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn attention_items_track_acknowledgement_and_reopen() {
+        let store = StateStore::open_in_memory().expect("state store should open");
+        let record = fixture_attention("needs-input:1", 100);
+        store
+            .record_attention_raised(&record)
+            .expect("attention should record");
+        store
+            .record_attention_acknowledged(150, &record.workspace_path, "needs-input:1")
+            .expect("ack should record");
+        store
+            .record_attention_cleared(160, &record.workspace_path, "needs-input:1")
+            .expect("clear should record");
+
+        let history = store
+            .attention_history(None, 0)
+            .expect("history should load");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].acknowledged_ms, Some(150));
+        assert_eq!(history[0].cleared_ms, Some(160));
+        assert_eq!(history[0].last_seen_ms, 160);
+
+        store
+            .record_attention_raised(&fixture_attention("needs-input:1", 400))
+            .expect("attention should reopen");
+        let history = store
+            .attention_history(None, 0)
+            .expect("history should load");
+        assert_eq!(history[0].first_seen_ms, 100);
+        assert_eq!(history[0].last_seen_ms, 400);
+        assert_eq!(history[0].cleared_ms, None);
+    }
+
+    #[test]
+    fn task_manifest_cache_keeps_latest_manifest() {
+        let store = StateStore::open_in_memory().expect("state store should open");
+        let task_root = Path::new("/tmp/.grove/tasks/feature-a");
+        assert_eq!(store.cached_task_manifest(task_root), Ok(None));
+
+        store
+            .cache_task_manifest(10, task_root, "name = \"a\"")
+            .expect("manifest should cache");
+        store
+            .cache_task_manifest(20, task_root, "name = \"b\"")
+            .expect("manifest should cache");
+
+        assert_eq!(
+            store.cached_task_manifest(task_root),
+            Ok(Some("name = \"b\"".to_string()))
+        );
+    }
+}
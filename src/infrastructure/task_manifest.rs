@@ -1,4 +1,7 @@
-use crate::domain::{AgentType, PullRequest, PullRequestStatus, Task, WorkspaceStatus, Worktree};
+use crate::domain::{
+    AgentRegistry, PortRange, PullRequest, PullRequestChecks, PullRequestReview, PullRequestStatus,
+    Task, WorkspaceStatus, Worktree,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -8,6 +11,8 @@ struct TaskManifest {
     root_path: String,
     branch: String,
     worktrees: Vec<TaskManifestWorktree>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recipe: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +28,14 @@ struct TaskManifestWorktree {
     is_orphaned: bool,
     supported_agent: bool,
     pull_requests: Vec<TaskManifestPullRequest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ports: Option<TaskManifestPorts>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TaskManifestPorts {
+    base: u16,
+    count: u16,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,6 +43,10 @@ struct TaskManifestPullRequest {
     number: u64,
     url: String,
     status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    review: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checks: Option<String>,
 }
 
 pub fn encode_task_manifest(task: &Task) -> Result<String, String> {
@@ -59,23 +76,35 @@ pub fn encode_task_manifest(task: &Task) -> Result<String, String> {
                         number: pull_request.number,
                         url: pull_request.url.clone(),
                         status: pull_request_status_name(pull_request.status).to_string(),
+                        review: pull_request
+                            .review
+                            .map(|review| pull_request_review_name(review).to_string()),
+                        checks: pull_request
+                            .checks
+                            .map(|checks| pull_request_checks_name(checks).to_string()),
                     })
                     .collect(),
+                ports: worktree.ports.map(|ports| TaskManifestPorts {
+                    base: ports.base,
+                    count: ports.count,
+                }),
             })
             .collect(),
+        recipe: task.recipe.clone(),
     };
 
     toml::to_string_pretty(&manifest)
         .map_err(|error| format!("task manifest encode failed: {error}"))
 }
 
-pub fn decode_task_manifest(raw: &str) -> Result<Task, String> {
+/// Decodes a manifest, resolving worktree agents against `agents`.
+pub fn decode_task_manifest(raw: &str, agents: &AgentRegistry) -> Result<Task, String> {
     let manifest = toml::from_str::<TaskManifest>(raw)
         .map_err(|error| format!("task manifest parse failed: {error}"))?;
     let worktrees = manifest
         .worktrees
         .into_iter()
-        .map(decode_worktree)
+        .map(|worktree| decode_worktree(worktree, agents))
         .collect::<Result<Vec<Worktree>, String>>()?;
 
     Task::try_new(
@@ -85,11 +114,31 @@ pub fn decode_task_manifest(raw: &str) -> Result<Task, String> {
         manifest.branch,
         worktrees,
     )
+    .map(|task| task.with_recipe(manifest.recipe))
     .map_err(|error| format!("task manifest invalid: {error:?}"))
 }
 
-fn decode_worktree(manifest: TaskManifestWorktree) -> Result<Worktree, String> {
-    let agent = AgentType::from_marker(manifest.agent.as_str())
+fn decode_worktree(
+    manifest: TaskManifestWorktree,
+    agents: &AgentRegistry,
+) -> Result<Worktree, String> {
+    let ports = manifest
+        .ports
+        .map(|ports| {
+            if ports.count == 0 || ports.base.checked_add(ports.count - 1).is_none() {
+                return Err(format!(
+                    "invalid port range {} + {}",
+                    ports.base, ports.count
+                ));
+            }
+            Ok(PortRange {
+                base: ports.base,
+                count: ports.count,
+            })
+        })
+        .transpose()?;
+    let agent = agents
+        .from_marker(manifest.agent.as_str())
         .ok_or_else(|| format!("unsupported agent '{}'", manifest.agent))?;
     let status = parse_workspace_status(manifest.status.as_str())
         .ok_or_else(|| format!("unsupported workspace status '{}'", manifest.status))?;
@@ -101,10 +150,26 @@ fn decode_worktree(manifest: TaskManifestWorktree) -> Result<Worktree, String> {
                 parse_pull_request_status(pull_request.status.as_str()).ok_or_else(|| {
                     format!("unsupported pull request status '{}'", pull_request.status)
                 })?;
+            let review = pull_request
+                .review
+                .map(|review| {
+                    parse_pull_request_review(review.as_str())
+                        .ok_or_else(|| format!("unsupported pull request review '{review}'"))
+                })
+                .transpose()?;
+            let checks = pull_request
+                .checks
+                .map(|checks| {
+                    parse_pull_request_checks(checks.as_str())
+                        .ok_or_else(|| format!("unsupported pull request checks '{checks}'"))
+                })
+                .transpose()?;
             Ok(PullRequest {
                 number: pull_request.number,
                 url: pull_request.url,
                 status,
+                review,
+                checks,
             })
         })
         .collect::<Result<Vec<PullRequest>, String>>()?;
@@ -124,10 +189,11 @@ fn decode_worktree(manifest: TaskManifestWorktree) -> Result<Worktree, String> {
         .with_last_activity_unix_secs(manifest.last_activity_unix_secs)
         .with_orphaned(manifest.is_orphaned)
         .with_supported_agent(manifest.supported_agent)
-        .with_pull_requests(pull_requests))
+        .with_pull_requests(pull_requests)
+        .with_ports(ports))
 }
 
-fn workspace_status_name(status: WorkspaceStatus) -> &'static str {
+pub(crate) fn workspace_status_name(status: WorkspaceStatus) -> &'static str {
     match status {
         WorkspaceStatus::Main => "main",
         WorkspaceStatus::Idle => "idle",
@@ -141,7 +207,7 @@ fn workspace_status_name(status: WorkspaceStatus) -> &'static str {
     }
 }
 
-fn parse_workspace_status(value: &str) -> Option<WorkspaceStatus> {
+pub(crate) fn parse_workspace_status(value: &str) -> Option<WorkspaceStatus> {
     match value {
         "main" => Some(WorkspaceStatus::Main),
         "idle" => Some(WorkspaceStatus::Idle),
@@ -173,10 +239,47 @@ fn parse_pull_request_status(value: &str) -> Option<PullRequestStatus> {
     }
 }
 
+fn pull_request_review_name(review: PullRequestReview) -> &'static str {
+    match review {
+        PullRequestReview::Approved => "approved",
+        PullRequestReview::ChangesRequested => "changes_requested",
+        PullRequestReview::ReviewRequired => "review_required",
+    }
+}
+
+fn parse_pull_request_review(value: &str) -> Option<PullRequestReview> {
+    match value {
+        "approved" => Some(PullRequestReview::Approved),
+        "changes_requested" => Some(PullRequestReview::ChangesRequested),
+        "review_required" => Some(PullRequestReview::ReviewRequired),
+        _ => None,
+    }
+}
+
+fn pull_request_checks_name(checks: PullRequestChecks) -> &'static str {
+    match checks {
+        PullRequestChecks::Pending => "pending",
+        PullRequestChecks::Passing => "passing",
+        PullRequestChecks::Failing => "failing",
+    }
+}
+
+fn parse_pull_request_checks(value: &str) -> Option<PullRequestChecks> {
+    match value {
+        "pending" => Some(PullRequestChecks::Pending),
+        "passing" => Some(PullRequestChecks::Passing),
+        "failing" => Some(PullRequestChecks::Failing),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{decode_task_manifest, encode_task_manifest};
-    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::domain::{
+        AgentRegistry, AgentType, PortRange, PullRequest, PullRequestChecks, PullRequestReview,
+        PullRequestStatus, Task, WorkspaceStatus, Worktree,
+    };
     use std::path::PathBuf;
 
     fn fixture_task() -> Task {
@@ -189,7 +292,18 @@ mod tests {
             WorkspaceStatus::Active,
         )
         .expect("worktree should be valid")
-        .with_base_branch(Some("main".to_string()));
+        .with_base_branch(Some("main".to_string()))
+        .with_pull_requests(vec![PullRequest {
+            number: 12,
+            url: "https://github.com/acme/flohome/pull/12".to_string(),
+            status: PullRequestStatus::Open,
+            review: Some(PullRequestReview::ChangesRequested),
+            checks: Some(PullRequestChecks::Failing),
+        }])
+        .with_ports(Some(PortRange {
+            base: 20_010,
+            count: 10,
+        }));
         let infra_worktree = Worktree::try_new(
             "terraform-fastly".to_string(),
             PathBuf::from("/repos/terraform-fastly"),
@@ -208,6 +322,7 @@ mod tests {
             vec![app_worktree, infra_worktree],
         )
         .expect("task should be valid")
+        .with_recipe(Some("bugfix".to_string()))
     }
 
     #[test]
@@ -215,7 +330,8 @@ mod tests {
         let task = fixture_task();
 
         let encoded = encode_task_manifest(&task).expect("manifest should encode");
-        let decoded = decode_task_manifest(&encoded).expect("manifest should decode");
+        let decoded = decode_task_manifest(&encoded, &AgentRegistry::default())
+            .expect("manifest should decode");
 
         assert_eq!(decoded, task);
     }
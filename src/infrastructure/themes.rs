@@ -0,0 +1,597 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::infrastructure::config::{ThemeKey, ThemeName};
+
+/// 24-bit color as `(red, green, blue)`.
+pub type ThemeRgb = (u8, u8, u8);
+
+/// Every color slot Grove draws with; tmux session styling reads the same slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemePalette {
+    pub background: ThemeRgb,
+    pub surface: ThemeRgb,
+    pub overlay: ThemeRgb,
+    pub text: ThemeRgb,
+    pub text_muted: ThemeRgb,
+    pub text_subtle: ThemeRgb,
+    pub primary: ThemeRgb,
+    pub secondary: ThemeRgb,
+    pub accent: ThemeRgb,
+    pub info: ThemeRgb,
+    pub success: ThemeRgb,
+    pub warning: ThemeRgb,
+    pub error: ThemeRgb,
+    pub border: ThemeRgb,
+    pub border_focused: ThemeRgb,
+    pub selection_bg: ThemeRgb,
+    pub selection_fg: ThemeRgb,
+    pub scrollbar_track: ThemeRgb,
+    pub scrollbar_thumb: ThemeRgb,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomTheme {
+    pub key: String,
+    pub display_name: String,
+    pub palette: ThemePalette,
+}
+
+/// Palette slots read from a theme file before the optional ones are derived.
+#[derive(Debug, Clone, Copy, Default)]
+struct PaletteSlots {
+    background: Option<ThemeRgb>,
+    surface: Option<ThemeRgb>,
+    overlay: Option<ThemeRgb>,
+    text: Option<ThemeRgb>,
+    text_muted: Option<ThemeRgb>,
+    text_subtle: Option<ThemeRgb>,
+    primary: Option<ThemeRgb>,
+    secondary: Option<ThemeRgb>,
+    accent: Option<ThemeRgb>,
+    info: Option<ThemeRgb>,
+    success: Option<ThemeRgb>,
+    warning: Option<ThemeRgb>,
+    error: Option<ThemeRgb>,
+    border: Option<ThemeRgb>,
+    border_focused: Option<ThemeRgb>,
+    selection_bg: Option<ThemeRgb>,
+    selection_fg: Option<ThemeRgb>,
+    scrollbar_track: Option<ThemeRgb>,
+    scrollbar_thumb: Option<ThemeRgb>,
+}
+
+impl PaletteSlots {
+    fn slot_mut(&mut self, name: &str) -> Option<&mut Option<ThemeRgb>> {
+        let slot = match name.replace('-', "_").as_str() {
+            "background" => &mut self.background,
+            "surface" => &mut self.surface,
+            "overlay" => &mut self.overlay,
+            "text" => &mut self.text,
+            "text_muted" => &mut self.text_muted,
+            "text_subtle" => &mut self.text_subtle,
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "accent" => &mut self.accent,
+            "info" => &mut self.info,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "border" => &mut self.border,
+            "border_focused" => &mut self.border_focused,
+            "selection_bg" => &mut self.selection_bg,
+            "selection_fg" => &mut self.selection_fg,
+            "scrollbar_track" => &mut self.scrollbar_track,
+            "scrollbar_thumb" => &mut self.scrollbar_thumb,
+            _ => return None,
+        };
+        Some(slot)
+    }
+
+    fn resolve(self) -> Result<ThemePalette, String> {
+        let required = |value: Option<ThemeRgb>, name: &str| {
+            value.ok_or_else(|| format!("missing color '{name}'"))
+        };
+        let background = required(self.background, "background")?;
+        let text = required(self.text, "text")?;
+        let primary = required(self.primary, "primary")?;
+        let success = required(self.success, "success")?;
+        let warning = required(self.warning, "warning")?;
+        let error = required(self.error, "error")?;
+        let surface = self.surface.unwrap_or(mix(background, text, 0.08));
+        let overlay = self.overlay.unwrap_or(mix(background, text, 0.2));
+
+        Ok(ThemePalette {
+            background,
+            surface,
+            overlay,
+            text,
+            text_muted: self.text_muted.unwrap_or(mix(text, background, 0.2)),
+            text_subtle: self.text_subtle.unwrap_or(mix(text, background, 0.4)),
+            primary,
+            secondary: self.secondary.unwrap_or(primary),
+            accent: self.accent.unwrap_or(warning),
+            info: self.info.unwrap_or(primary),
+            success,
+            warning,
+            error,
+            border: self.border.unwrap_or(mix(background, text, 0.35)),
+            border_focused: self.border_focused.unwrap_or(primary),
+            selection_bg: self.selection_bg.unwrap_or(overlay),
+            selection_fg: self.selection_fg.unwrap_or(text),
+            scrollbar_track: self.scrollbar_track.unwrap_or(background),
+            scrollbar_thumb: self.scrollbar_thumb.unwrap_or(surface),
+        })
+    }
+}
+
+fn mix(from: ThemeRgb, to: ThemeRgb, amount: f32) -> ThemeRgb {
+    let channel = |from: u8, to: u8| {
+        let value = f32::from(from) + (f32::from(to) - f32::from(from)) * amount;
+        value.round().clamp(0.0, 255.0) as u8
+    };
+    (
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+fn parse_hex_color(value: &str) -> Result<ThemeRgb, String> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err(format!("invalid color '{value}', expected #rrggbb"));
+    }
+    let channel = |range: std::ops::Range<usize>| {
+        u8::from_str_radix(&hex[range], 16)
+            .map_err(|error| format!("invalid color '{value}': {error}"))
+    };
+    Ok((channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GroveThemeFile {
+    #[serde(default)]
+    name: Option<String>,
+    colors: BTreeMap<String, String>,
+}
+
+/// Grove's own format: an optional `name` and a `[colors]` table keyed by slot.
+fn parse_grove_theme(raw: &str) -> Result<(Option<String>, ThemePalette), String> {
+    let file = toml::from_str::<GroveThemeFile>(raw).map_err(|error| error.to_string())?;
+    let mut slots = PaletteSlots::default();
+    for (name, value) in &file.colors {
+        let slot = slots
+            .slot_mut(name)
+            .ok_or_else(|| format!("unknown color '{name}'"))?;
+        *slot = Some(parse_hex_color(value).map_err(|error| format!("{name}: {error}"))?);
+    }
+    Ok((file.name, slots.resolve()?))
+}
+
+fn yaml_scalar(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            return rest.split(quote).next().unwrap_or_default();
+        }
+    }
+    value.split(" #").next().unwrap_or_default().trim()
+}
+
+/// Base16 scheme files (`base00`..`base0F` plus `scheme`/`name`), flat or under `palette:`.
+fn parse_base16_theme(raw: &str) -> Result<(Option<String>, ThemePalette), String> {
+    let mut name = None;
+    let mut colors = BTreeMap::new();
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = yaml_scalar(value);
+        match key.as_str() {
+            "scheme" | "name" if !value.is_empty() => name = Some(value.to_string()),
+            _ if key.len() == 6 && key.starts_with("base0") => {
+                let color = parse_hex_color(value).map_err(|error| format!("{key}: {error}"))?;
+                colors.insert(key, color);
+            }
+            _ => {}
+        }
+    }
+
+    let base = |index: &str| colors.get(format!("base0{index}").as_str()).copied();
+    let slots = PaletteSlots {
+        background: base("0"),
+        surface: base("1"),
+        overlay: base("2"),
+        text: base("5"),
+        text_muted: base("4"),
+        text_subtle: base("3"),
+        primary: base("d"),
+        secondary: base("e"),
+        accent: base("9"),
+        info: base("c"),
+        success: base("b"),
+        warning: base("a"),
+        error: base("8"),
+        border: base("3"),
+        border_focused: base("d"),
+        selection_bg: base("2"),
+        selection_fg: base("5"),
+        scrollbar_track: base("0"),
+        scrollbar_thumb: base("1"),
+    };
+    Ok((name, slots.resolve()?))
+}
+
+/// Ghostty theme files: `key = value` lines with `palette = N=#rrggbb` entries.
+fn parse_ghostty_theme(raw: &str) -> Result<(Option<String>, ThemePalette), String> {
+    let mut slots = PaletteSlots::default();
+    let mut palette = BTreeMap::new();
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "palette" => {
+                let Some((index, color)) = value.split_once('=') else {
+                    return Err(format!("invalid palette entry '{value}'"));
+                };
+                let index = index
+                    .trim()
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid palette index in '{value}'"))?;
+                palette.insert(index, parse_hex_color(color)?);
+            }
+            "background" => slots.background = Some(parse_hex_color(value)?),
+            "foreground" => slots.text = Some(parse_hex_color(value)?),
+            "selection-background" => slots.selection_bg = Some(parse_hex_color(value)?),
+            "selection-foreground" => slots.selection_fg = Some(parse_hex_color(value)?),
+            _ => {}
+        }
+    }
+
+    let color = |index: u8| palette.get(&index).copied();
+    slots.error = color(1);
+    slots.success = color(2);
+    slots.warning = color(3);
+    slots.primary = color(4);
+    slots.secondary = color(5);
+    slots.info = color(6);
+    slots.text_subtle = color(8);
+    slots.border = color(8);
+    Ok((None, slots.resolve()?))
+}
+
+/// Parses one theme file; the format follows the extension.
+pub fn parse_theme_file(path: &Path) -> Result<CustomTheme, String> {
+    let key = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_ascii_lowercase)
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| "invalid theme file name".to_string())?;
+    if ThemeName::BUILT_IN
+        .iter()
+        .any(|theme| theme.config_key() == key)
+    {
+        return Err(format!("'{key}' shadows a built-in theme"));
+    }
+    let raw = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    let (name, palette) = match extension.as_deref() {
+        Some("toml") => parse_grove_theme(raw.as_str()),
+        Some("yaml" | "yml") => parse_base16_theme(raw.as_str()),
+        _ => parse_ghostty_theme(raw.as_str()),
+    }?;
+    let display_name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| key.clone());
+    Ok(CustomTheme {
+        key,
+        display_name,
+        palette,
+    })
+}
+
+/// Loads every theme file in `dir`, returning the valid themes and one error per broken file.
+pub fn load_custom_themes(dir: &Path) -> (Vec<CustomTheme>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !name.starts_with('.'))
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut seen = BTreeSet::new();
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match parse_theme_file(path.as_path()) {
+            Ok(theme) if !seen.insert(theme.key.clone()) => {
+                errors.push(format!("{file_name}: duplicate theme '{}'", theme.key));
+            }
+            Ok(theme) => themes.push(theme),
+            Err(error) => errors.push(format!("{file_name}: {error}")),
+        }
+    }
+    (themes, errors)
+}
+
+/// Handle to a loaded custom theme; two handles are equal when their keys match.
+#[derive(Debug, Clone)]
+pub struct CustomThemeRef(Arc<CustomTheme>);
+
+impl CustomThemeRef {
+    pub fn key(&self) -> &str {
+        self.0.key.as_str()
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.0.display_name.as_str()
+    }
+
+    pub fn palette(&self) -> ThemePalette {
+        self.0.palette
+    }
+}
+
+impl PartialEq for CustomThemeRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for CustomThemeRef {}
+
+/// Built-in themes plus the custom themes loaded from the themes directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThemeRegistry {
+    custom: Vec<CustomThemeRef>,
+}
+
+impl ThemeRegistry {
+    pub fn new(themes: &[CustomTheme]) -> Self {
+        Self {
+            custom: themes
+                .iter()
+                .map(|theme| CustomThemeRef(Arc::new(theme.clone())))
+                .collect(),
+        }
+    }
+
+    /// Built-in themes followed by every custom theme.
+    pub fn available(&self) -> Vec<ThemeName> {
+        let mut themes = ThemeName::BUILT_IN.to_vec();
+        themes.extend(self.custom.iter().cloned().map(ThemeName::Custom));
+        themes
+    }
+
+    pub fn from_key(&self, key: &str) -> Option<ThemeName> {
+        self.available()
+            .into_iter()
+            .find(|theme| theme.config_key() == key)
+    }
+
+    /// Resolves a configured theme key, rejecting keys no loaded theme defines.
+    pub fn resolve(&self, key: &ThemeKey) -> Result<ThemeName, String> {
+        self.from_key(key.as_str())
+            .ok_or_else(|| format!("unknown theme '{}'", key.as_str()))
+    }
+
+    pub fn next(&self, theme: &ThemeName) -> ThemeName {
+        let themes = self.available();
+        let index = themes
+            .iter()
+            .position(|candidate| candidate == theme)
+            .unwrap_or(0);
+        themes[(index + 1) % themes.len()].clone()
+    }
+
+    pub fn previous(&self, theme: &ThemeName) -> ThemeName {
+        let themes = self.available();
+        let index = themes
+            .iter()
+            .position(|candidate| candidate == theme)
+            .unwrap_or(0);
+        themes[(index + themes.len() - 1) % themes.len()].clone()
+    }
+}
+
+/// Loads the themes directory; valid themes load even when others fail.
+pub fn load_theme_registry() -> (ThemeRegistry, Vec<String>) {
+    let Some(dir) = crate::infrastructure::config::themes_dir() else {
+        return (ThemeRegistry::default(), Vec::new());
+    };
+    let (themes, errors) = load_custom_themes(dir.as_path());
+    (ThemeRegistry::new(&themes), errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CustomTheme, ThemeRegistry, load_custom_themes, parse_base16_theme, parse_ghostty_theme,
+        parse_grove_theme,
+    };
+    use crate::infrastructure::config::{ThemeKey, ThemeName};
+    use crate::test_support::unique_test_dir;
+    use std::fs;
+
+    #[test]
+    fn grove_theme_derives_optional_slots() {
+        let (name, palette) = parse_grove_theme(
+            r##"
+name = "Paper"
+
+[colors]
+background = "#ffffff"
+text = "#000000"
+primary = "#0000ff"
+success = "#00ff00"
+warning = "#ffff00"
+error = "#ff0000"
+border-focused = "#123456"
+"##,
+        )
+        .expect("theme should parse");
+
+        assert_eq!(name.as_deref(), Some("Paper"));
+        assert_eq!(palette.border_focused, (0x12, 0x34, 0x56));
+        assert_eq!(palette.secondary, (0, 0, 255));
+        assert_eq!(palette.accent, (255, 255, 0));
+        assert_eq!(palette.text_muted, (51, 51, 51));
+        assert_eq!(palette.scrollbar_track, (255, 255, 255));
+    }
+
+    #[test]
+    fn grove_theme_rejects_unknown_and_missing_colors() {
+        let unknown = parse_grove_theme("[colors]\nbackgrund = \"#000000\"\n");
+        assert_eq!(unknown.err().as_deref(), Some("unknown color 'backgrund'"));
+
+        let missing = parse_grove_theme("[colors]\nbackground = \"#000000\"\n");
+        assert_eq!(missing.err().as_deref(), Some("missing color 'text'"));
+
+        let invalid = parse_grove_theme("[colors]\nbackground = \"black\"\n");
+        assert_eq!(
+            invalid.err().as_deref(),
+            Some("background: invalid color 'black', expected #rrggbb")
+        );
+    }
+
+    #[test]
+    fn base16_theme_maps_scheme_slots() {
+        let (name, palette) = parse_base16_theme(
+            r#"
+scheme: Gruvbox dark # unquoted
+author: "someone"
+base00: "282828" # background
+base01: "3c3836"
+base02: "504945"
+base03: "665c54"
+base04: "bdae93"
+base05: "d5c4a1"
+base06: "ebdbb2"
+base07: "fbf1c7"
+base08: "fb4934"
+base09: "fe8019"
+base0A: "fabd2f"
+base0B: "b8bb26"
+base0C: "8ec07c"
+base0D: "83a598"
+base0E: "d3869b"
+base0F: "d65d0e"
+"#,
+        )
+        .expect("theme should parse");
+
+        assert_eq!(name.as_deref(), Some("Gruvbox dark"));
+        assert_eq!(palette.background, (0x28, 0x28, 0x28));
+        assert_eq!(palette.text, (0xd5, 0xc4, 0xa1));
+        assert_eq!(palette.warning, (0xfa, 0xbd, 0x2f));
+        assert_eq!(palette.primary, (0x83, 0xa5, 0x98));
+        assert_eq!(palette.selection_bg, (0x50, 0x49, 0x45));
+    }
+
+    #[test]
+    fn ghostty_theme_reads_ansi_palette() {
+        let (name, palette) = parse_ghostty_theme(
+            "# comment\npalette = 1=#ff0000\npalette = 2=#00ff00\npalette = 3=#ffff00\npalette = 4=#0000ff\nbackground = #101010\nforeground = #f0f0f0\nselection-background = #333333\n",
+        )
+        .expect("theme should parse");
+
+        assert_eq!(name, None);
+        assert_eq!(palette.background, (0x10, 0x10, 0x10));
+        assert_eq!(palette.error, (255, 0, 0));
+        assert_eq!(palette.primary, (0, 0, 255));
+        assert_eq!(palette.selection_bg, (0x33, 0x33, 0x33));
+        assert_eq!(palette.secondary, (0, 0, 255));
+    }
+
+    #[test]
+    fn load_custom_themes_keeps_valid_files_and_reports_broken_ones() {
+        let dir = unique_test_dir("grove-themes-load");
+        fs::write(
+            dir.join("Paper.toml"),
+            "[colors]\nbackground = \"#ffffff\"\ntext = \"#000000\"\nprimary = \"#0000ff\"\nsuccess = \"#00ff00\"\nwarning = \"#ffff00\"\nerror = \"#ff0000\"\n",
+        )
+        .expect("theme should be written");
+        fs::write(dir.join("broken.toml"), "[colors]\n").expect("theme should be written");
+        fs::write(dir.join("monokai.toml"), "").expect("theme should be written");
+        fs::write(dir.join(".hidden"), "junk").expect("file should be written");
+
+        let (themes, errors) = load_custom_themes(dir.as_path());
+
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0].key, "paper");
+        assert_eq!(themes[0].display_name, "paper");
+        assert_eq!(
+            errors,
+            vec![
+                "broken.toml: missing color 'background'".to_string(),
+                "monokai.toml: 'monokai' shadows a built-in theme".to_string(),
+            ]
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn theme_registry_cycles_custom_themes_after_built_ins() {
+        let (_, palette) = parse_grove_theme(
+            "[colors]\nbackground = \"#101112\"\ntext = \"#f0f1f2\"\nprimary = \"#0a0b0c\"\nsuccess = \"#00ff00\"\nwarning = \"#ffff00\"\nerror = \"#ff0000\"\n",
+        )
+        .expect("theme should parse");
+        let registry = ThemeRegistry::new(&[CustomTheme {
+            key: "ember".to_string(),
+            display_name: "Ember".to_string(),
+            palette,
+        }]);
+
+        let ember = registry
+            .from_key("ember")
+            .expect("custom theme should resolve");
+        let ThemeName::Custom(handle) = &ember else {
+            panic!("expected a custom theme, got {ember:?}");
+        };
+        assert_eq!(handle.display_name(), "Ember");
+        assert_eq!(handle.palette().background, (0x10, 0x11, 0x12));
+        assert_eq!(registry.next(&ThemeName::RosePineDawn), ember);
+        assert_eq!(registry.next(&ember), ThemeName::Monokai);
+        assert_eq!(registry.previous(&ThemeName::Monokai), ember);
+        assert_eq!(
+            registry.resolve(&ThemeKey::from(ember.clone())),
+            Ok(ember),
+            "a saved custom theme key resolves back to the same theme"
+        );
+        assert_eq!(ThemeRegistry::default().from_key("ember"), None);
+    }
+}
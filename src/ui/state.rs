@@ -1,4 +1,4 @@
-use crate::domain::{Task, Workspace, WorkspaceStatus, Worktree};
+use crate::domain::{Task, Workspace, Worktree};
 use crate::infrastructure::paths::refer_to_same_location;
 use std::path::Path;
 
@@ -135,39 +135,11 @@ fn flatten_tasks_as_workspaces(tasks: &[Task]) -> Vec<Workspace> {
         .flat_map(|task| {
             task.worktrees
                 .iter()
-                .map(|worktree| workspace_from_task_worktree(task, worktree))
+                .map(|worktree| Workspace::from_task_worktree(task, worktree))
         })
         .collect()
 }
 
-fn workspace_from_task_worktree(task: &Task, worktree: &Worktree) -> Workspace {
-    let is_main = worktree.is_main_checkout();
-    Workspace {
-        name: if task.worktrees.len() == 1 {
-            task.name.clone()
-        } else {
-            worktree.repository_name.clone()
-        },
-        task_slug: Some(task.slug.clone()),
-        path: worktree.path.clone(),
-        project_name: Some(worktree.repository_name.clone()),
-        project_path: Some(worktree.repository_path.clone()),
-        branch: worktree.branch.clone(),
-        base_branch: worktree.base_branch.clone(),
-        last_activity_unix_secs: worktree.last_activity_unix_secs,
-        agent: worktree.agent,
-        status: if is_main {
-            WorkspaceStatus::Main
-        } else {
-            worktree.status
-        },
-        is_main,
-        is_orphaned: worktree.is_orphaned,
-        supported_agent: worktree.supported_agent,
-        pull_requests: worktree.pull_requests.clone(),
-    }
-}
-
 fn selection_for_flat_index(tasks: &[Task], selected_index: usize) -> Option<(usize, usize)> {
     let mut flat_index = 0usize;
 
@@ -1,3 +1,4 @@
+use crate::application::agent_runtime::{build_agent_registry, compile_status_rules};
 use crate::application::task_discovery::{TaskBootstrapData, TaskDiscoveryState};
 use crate::infrastructure::config::ProjectConfig;
 use crate::infrastructure::paths::tasks_root;
@@ -8,7 +9,7 @@ use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 
-use super::bootstrap_config::{AppDependencies, load_runtime_config};
+use super::bootstrap_config::{AppDependencies, RuntimeConfig, load_runtime_config};
 use super::bootstrap_discovery::bootstrap_task_data_for_root;
 use super::*;
 use crate::ui::mouse::clamp_sidebar_ratio;
@@ -26,13 +27,18 @@ impl GroveApp {
             .collect::<HashMap<PathBuf, WorkspaceTabsState>>();
         let last_agent_selection = workspaces
             .iter()
-            .map(|workspace| (workspace.path.clone(), workspace.agent))
+            .map(|workspace| (workspace.path.clone(), workspace.agent.clone()))
             .collect::<HashMap<PathBuf, AgentType>>();
         (workspace_tabs, last_agent_selection)
     }
 
     pub(super) fn new(event_log: Box<dyn EventLogger>, debug_record_start_ts: Option<u64>) -> Self {
-        let (config, config_path, _config_error) = load_runtime_config();
+        let RuntimeConfig {
+            config,
+            path: config_path,
+            agents,
+            error: _config_error,
+        } = load_runtime_config();
         let projects = config.projects;
         let hidden_base_project_paths = config.hidden_base_project_paths;
         let dependencies = AppDependencies {
@@ -43,24 +49,29 @@ impl GroveApp {
             debug_record_start_ts,
         };
 
+        let state_store = StateStore::open_default().ok();
         let bootstrap = tasks_root()
             .map(|tasks_root| {
                 bootstrap_task_data_for_root(
                     tasks_root.as_path(),
                     &projects,
                     &hidden_base_project_paths,
+                    state_store.as_ref(),
+                    &agents,
                 )
             })
             .unwrap_or(TaskBootstrapData {
                 tasks: Vec::new(),
                 discovery_state: TaskDiscoveryState::Empty,
             });
-        Self::from_task_parts_with_clipboard_and_projects(
+        let mut app = Self::from_task_parts_with_clipboard_and_projects(
             bootstrap,
             projects,
             hidden_base_project_paths,
             dependencies,
-        )
+        );
+        app.telemetry.state_store = state_store;
+        app
     }
 
     pub(super) fn from_task_parts_with_clipboard_and_projects(
@@ -107,9 +118,50 @@ impl GroveApp {
         let persisted_config = crate::infrastructure::config::load_from_path(&config_path)
             .unwrap_or_else(|_| GroveConfig::default());
         let sidebar_width_pct = clamp_sidebar_ratio(persisted_config.sidebar_width_pct);
-        let theme_name = persisted_config.theme;
+        #[cfg(not(test))]
+        let (themes, themes_errors) = crate::infrastructure::themes::load_theme_registry();
+        #[cfg(test)]
+        let (themes, themes_errors) = (ThemeRegistry::default(), Vec::<String>::new());
+        let themes_error = (!themes_errors.is_empty()).then(|| themes_errors.join("; "));
+        let (theme_name, theme_error) = match themes.resolve(&persisted_config.theme) {
+            Ok(theme_name) => (theme_name, None),
+            Err(error) => (ThemeName::default(), Some(error)),
+        };
         let launch_permission_mode = persisted_config.launch_permission_mode;
         let task_order = persisted_config.task_order;
+        let (attention_notifier, notifier_error) =
+            match NotificationDispatcher::new(persisted_config.notifications) {
+                Ok(dispatcher) => (dispatcher, None),
+                Err(error) => (NotificationDispatcher::default(), Some(error)),
+            };
+        let (keymap, keymap_error) = match Keymap::from_config(&persisted_config.keybindings) {
+            Ok(keymap) => (keymap, None),
+            Err(error) => (Keymap::default(), Some(error)),
+        };
+        let (agents, agents_error) = match build_agent_registry(&persisted_config.agents) {
+            Ok(agents) => (agents, None),
+            Err(error) => (AgentRegistry::default(), Some(error)),
+        };
+        let (status_rules, status_rules_error) =
+            match compile_status_rules(&persisted_config.status_rules, &agents) {
+                Ok(status_rules) => (status_rules, None),
+                Err(error) => (StatusRules::default(), Some(error)),
+            };
+        #[cfg(not(test))]
+        let pull_request_forge = crate::application::pull_request_sync::forge_for_kind(
+            persisted_config.pull_request_sync.forge,
+        );
+        #[cfg(test)]
+        let pull_request_forge = None;
+        let usage_prices = UsagePriceTable::from_config(&persisted_config.usage);
+        let transcripts_enabled = persisted_config.transcripts.enabled;
+        let status_hooks_enabled = persisted_config.status_hooks.enabled;
+        #[cfg(not(test))]
+        let usage_home_dir = dirs::home_dir();
+        #[cfg(test)]
+        let usage_home_dir = None;
+        let pull_request_sync_interval =
+            Duration::from_secs(persisted_config.pull_request_sync.interval_secs.max(1));
         let workspace_attention_ack_markers = persisted_config
             .attention_acks
             .into_iter()
@@ -153,6 +205,10 @@ impl GroveApp {
                     .dedup_window_ms(0),
             ),
             action_mapper: ActionMapper::new(mapper_config),
+            keymap,
+            agents: Arc::new(agents),
+            status_rules: Arc::new(status_rules),
+            pending_key_sequence: PendingKeySequence::default(),
             dialogs: DialogState {
                 active_dialog: None,
                 keybind_help_open: false,
@@ -167,9 +223,11 @@ impl GroveApp {
                 delete_in_flight_workspace: None,
                 pending_delete_workspaces: VecDeque::new(),
                 delete_requested_workspaces: HashSet::new(),
+                archive_in_flight: false,
                 merge_in_flight: false,
                 update_from_base_in_flight: false,
                 pull_upstream_in_flight: false,
+                conflict_action_in_flight: false,
                 create_in_flight: false,
                 start_in_flight: false,
                 stop_in_flight: false,
@@ -205,7 +263,9 @@ impl GroveApp {
                 #[cfg(test)]
                 workspace_status_observation_overrides: HashMap::new(),
                 workspace_waiting_prompts: HashMap::new(),
+                workspace_waiting_attention: HashMap::new(),
                 workspace_idle_polls_since_output: HashMap::new(),
+                prompt_queue_sent: HashMap::new(),
                 next_tick_due_at: None,
                 next_tick_interval_ms: None,
                 next_tick_source: None,
@@ -223,6 +283,13 @@ impl GroveApp {
                 last_diff_stat_poll_at: None,
                 diff_capture_in_flight: false,
                 diff_stat_in_flight: false,
+                last_pull_request_sync_at: None,
+                pull_request_sync_in_flight: false,
+                last_home_dashboard_poll_at: None,
+                home_dashboard_poll_in_flight: false,
+                last_usage_poll_at: None,
+                last_command_tab_poll_at: None,
+                usage_poll_in_flight: false,
                 preview_stream: PreviewStreamState::default(),
             },
             workspace_attention: HashMap::new(),
@@ -230,6 +297,20 @@ impl GroveApp {
             attention_observations: HashMap::new(),
             attention_items: Vec::new(),
             selected_attention_item: None,
+            attention_notifier,
+            pull_request_forge,
+            pull_request_sync_interval,
+            pull_request_alerts: HashMap::new(),
+            home_dashboard: HomeDashboardState::default(),
+            usage_tracker: Arc::new(Mutex::new(UsageTracker::default())),
+            usage_prices,
+            usage_home_dir,
+            worktree_usage: HashMap::new(),
+            transcripts_enabled,
+            status_hooks_enabled,
+            pane_search_reveal: None,
+            broadcast_targets: HashSet::new(),
+            pending_command_tab_autostart: Vec::new(),
             interactive_preview_reset_pending: false,
             startup_attention_focus_pending: true,
             #[cfg(test)]
@@ -239,6 +320,7 @@ impl GroveApp {
             sidebar_width_pct,
             panes: panes::GrovePaneModel::canonical(sidebar_width_pct),
             theme_name,
+            themes,
             sidebar_hidden: false,
             mouse_capture_enabled: true,
             launch_permission_mode,
@@ -258,6 +340,7 @@ impl GroveApp {
                 last_frame_hash: RefCell::new(0),
                 input_seq_counter: 1,
                 deferred_cmds: Vec::new(),
+                state_store: None,
             },
             performance: PerformanceState {
                 redraw_timing: RefCell::new(DurationWindow::new(64)),
@@ -273,6 +356,7 @@ impl GroveApp {
             sidebar_list_state: RefCell::new(VirtualizedListState::new().with_overscan(0)),
             last_sidebar_mouse_scroll_at: None,
             workspace_diff_stats: HashMap::new(),
+            workspace_conflicts: HashMap::new(),
             last_sidebar_mouse_scroll_delta: 0,
             focus_manager: Self::build_main_focus_manager(),
             #[cfg(test)]
@@ -280,6 +364,48 @@ impl GroveApp {
             #[cfg(test)]
             pull_request_branch_name_override: None,
         };
+        if let Some(error) = notifier_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("notifications", "config_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("notifications disabled: {error}"));
+        }
+        if let Some(error) = keymap_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("keybindings", "config_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("custom keybindings ignored: {error}"));
+        }
+        if let Some(error) = agents_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("agents", "config_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("custom agents ignored: {error}"));
+        }
+        if let Some(error) = status_rules_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("status_rules", "config_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("status rules ignored: {error}"));
+        }
+        if let Some(error) = themes_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("theme", "themes_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("themes invalid: {error}"));
+        }
+        if let Some(error) = theme_error {
+            app.telemetry.event_log.log(
+                LogEvent::new("theme", "config_invalid")
+                    .with_data("error", Value::from(error.clone())),
+            );
+            app.session.last_tmux_error = Some(format!("theme ignored: {error}"));
+        }
         app.reconcile_task_order();
         app.reorder_tasks_for_task_order();
         app.sync_workspace_tab_maps();
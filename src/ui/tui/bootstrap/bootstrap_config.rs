@@ -3,6 +3,10 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use super::*;
+use crate::application::workspace_lifecycle::{
+    WORKSPACE_INIT_COMMAND_FILE, WORKSPACE_LAUNCH_PROMPT_FILE, WORKSPACE_PERMISSION_MODE_FILE,
+};
+use crate::domain::AgentRegistry;
 use crate::infrastructure::config::{GroveConfig, ProjectConfig};
 use crate::infrastructure::paths::refer_to_same_location;
 
@@ -72,26 +76,49 @@ fn ensure_current_repo_project(config: &mut GroveConfig, config_path: &Path) ->
     .err()
 }
 
-pub(super) fn load_runtime_config() -> (GroveConfig, PathBuf, Option<String>) {
-    let (mut config, config_path, load_error) = match crate::infrastructure::config::load() {
-        Ok(loaded) => (loaded.config, loaded.path, None),
-        Err(error) => (GroveConfig::default(), default_config_path(), Some(error)),
-    };
-    let startup_error = ensure_current_repo_project(&mut config, &config_path);
-    let error = match (load_error, startup_error) {
-        (Some(load_error), Some(startup_error)) => Some(format!(
-            "{load_error}; startup project add failed: {startup_error}"
-        )),
-        (Some(load_error), None) => Some(load_error),
-        (None, Some(startup_error)) => Some(format!("startup project add failed: {startup_error}")),
-        (None, None) => None,
+pub(super) struct RuntimeConfig {
+    pub(super) config: GroveConfig,
+    pub(super) path: PathBuf,
+    pub(super) agents: AgentRegistry,
+    pub(super) error: Option<String>,
+}
+
+/// The value of `result`, recording its error under `label` on failure.
+fn collect_error<T>(errors: &mut Vec<String>, label: &str, result: Result<T, String>) -> Option<T> {
+    result
+        .map_err(|error| errors.push(format!("{label}: {error}")))
+        .ok()
+}
+
+pub(super) fn load_runtime_config() -> RuntimeConfig {
+    let mut errors = Vec::new();
+    let (mut config, path) = match crate::infrastructure::config::load() {
+        Ok(loaded) => (loaded.config, loaded.path),
+        Err(error) => {
+            errors.push(error);
+            (GroveConfig::default(), default_config_path())
+        }
     };
+    if let Some(error) = ensure_current_repo_project(&mut config, &path) {
+        errors.push(format!("startup project add failed: {error}"));
+    }
+    let agents = collect_error(
+        &mut errors,
+        "agents invalid",
+        crate::application::agent_runtime::build_agent_registry(&config.agents),
+    )
+    .unwrap_or_default();
 
-    (config, config_path, error)
+    RuntimeConfig {
+        config,
+        path,
+        agents,
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+    }
 }
 
 pub(super) fn read_workspace_launch_prompt(workspace_path: &Path) -> Option<String> {
-    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_LAUNCH_PROMPT_FILENAME)).ok()?;
+    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_LAUNCH_PROMPT_FILE)).ok()?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -101,7 +128,7 @@ pub(super) fn read_workspace_launch_prompt(workspace_path: &Path) -> Option<Stri
 }
 
 pub(super) fn read_workspace_init_command(workspace_path: &Path) -> Option<String> {
-    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_INIT_COMMAND_FILENAME)).ok()?;
+    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_INIT_COMMAND_FILE)).ok()?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -114,7 +141,7 @@ pub(super) fn write_workspace_init_command(
     workspace_path: &Path,
     init_command: Option<&str>,
 ) -> Result<(), String> {
-    let marker_path = workspace_path.join(WORKSPACE_INIT_COMMAND_FILENAME);
+    let marker_path = workspace_path.join(WORKSPACE_INIT_COMMAND_FILE);
     let Some(parent) = marker_path.parent() else {
         return Err(format!(
             "workspace init marker has no parent: {}",
@@ -140,7 +167,7 @@ pub(super) fn write_workspace_init_command(
 pub(super) fn read_workspace_permission_mode(
     workspace_path: &Path,
 ) -> Option<crate::domain::PermissionMode> {
-    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_SKIP_PERMISSIONS_FILENAME)).ok()?;
+    let raw = fs::read_to_string(workspace_path.join(WORKSPACE_PERMISSION_MODE_FILE)).ok()?;
     crate::domain::PermissionMode::from_marker(raw.trim())
 }
 
@@ -148,7 +175,7 @@ pub(super) fn write_workspace_permission_mode(
     workspace_path: &Path,
     permission_mode: crate::domain::PermissionMode,
 ) -> Result<(), String> {
-    let marker_path = workspace_path.join(WORKSPACE_SKIP_PERMISSIONS_FILENAME);
+    let marker_path = workspace_path.join(WORKSPACE_PERMISSION_MODE_FILE);
     let Some(parent) = marker_path.parent() else {
         return Err(format!(
             "workspace permission-mode marker has no parent: {}",
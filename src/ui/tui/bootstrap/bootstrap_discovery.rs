@@ -2,21 +2,34 @@ use std::collections::HashSet;
 use std::process::Command;
 
 use crate::application::task_discovery::{
-    TaskBootstrapData, TaskDiscoveryState,
-    bootstrap_task_data_for_root_with_sessions as discover_task_bootstrap_for_root,
+    TaskBootstrapData, TaskDiscoveryState, bootstrap_task_data_for_root_with_sessions,
+    bootstrap_task_data_for_root_with_state_store,
 };
 use crate::application::task_lifecycle::materialize_base_task_manifest_for_project_in_root;
+use crate::domain::AgentRegistry;
 use crate::infrastructure::config::ProjectConfig;
 use crate::infrastructure::paths::refer_to_same_location;
+use crate::infrastructure::state_store::StateStore;
 use std::path::{Path, PathBuf};
 
 pub(super) fn bootstrap_task_data_for_root(
     tasks_root: &Path,
     projects: &[ProjectConfig],
     hidden_base_project_paths: &[PathBuf],
+    state_store: Option<&StateStore>,
+    agents: &AgentRegistry,
 ) -> TaskBootstrapData {
     let running_sessions = running_task_sessions();
-    let bootstrap = discover_task_bootstrap_for_root(tasks_root, &running_sessions);
+    let discover = |running_sessions: &HashSet<String>| match state_store {
+        Some(state_store) => bootstrap_task_data_for_root_with_state_store(
+            tasks_root,
+            running_sessions,
+            state_store,
+            agents,
+        ),
+        None => bootstrap_task_data_for_root_with_sessions(tasks_root, running_sessions, agents),
+    };
+    let bootstrap = discover(&running_sessions);
     if matches!(bootstrap.discovery_state, TaskDiscoveryState::Error(_)) {
         return bootstrap;
     }
@@ -42,7 +55,7 @@ pub(super) fn bootstrap_task_data_for_root(
         return bootstrap;
     }
 
-    discover_task_bootstrap_for_root(tasks_root, &running_sessions)
+    discover(&running_sessions)
 }
 
 fn running_task_sessions() -> HashSet<String> {
@@ -67,6 +80,7 @@ fn running_task_sessions() -> HashSet<String> {
 #[cfg(test)]
 mod tests {
     use super::bootstrap_task_data_for_root;
+    use crate::domain::AgentRegistry;
     use crate::infrastructure::config::ProjectConfig;
     use std::fs;
     use std::path::PathBuf;
@@ -105,7 +119,13 @@ mod tests {
         let tasks_root = temp.path.join("tasks");
         fs::create_dir_all(&tasks_root).expect("tasks root should exist");
 
-        let bootstrap = bootstrap_task_data_for_root(tasks_root.as_path(), &[], &[]);
+        let bootstrap = bootstrap_task_data_for_root(
+            tasks_root.as_path(),
+            &[],
+            &[],
+            None,
+            &AgentRegistry::default(),
+        );
 
         assert!(bootstrap.tasks.is_empty());
         assert_eq!(
@@ -133,7 +153,13 @@ mod tests {
         )
         .expect("task manifest should write");
 
-        let bootstrap = bootstrap_task_data_for_root(tasks_root.as_path(), &[], &[]);
+        let bootstrap = bootstrap_task_data_for_root(
+            tasks_root.as_path(),
+            &[],
+            &[],
+            None,
+            &AgentRegistry::default(),
+        );
 
         assert_eq!(bootstrap.tasks.len(), 1);
         assert_eq!(bootstrap.tasks[0].slug, "feature-a");
@@ -191,6 +217,8 @@ mod tests {
                 defaults: Default::default(),
             }],
             &[],
+            None,
+            &AgentRegistry::default(),
         );
 
         assert_eq!(bootstrap.tasks.len(), 1);
@@ -256,6 +284,8 @@ mod tests {
                 defaults: Default::default(),
             }],
             std::slice::from_ref(&repo_root),
+            None,
+            &AgentRegistry::default(),
         );
 
         assert!(bootstrap.tasks.is_empty());
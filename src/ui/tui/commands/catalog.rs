@@ -53,6 +53,17 @@ pub(super) enum UiCommand {
     Quit,
     OpenPerformance,
     OpenWorkspaceJump,
+    HandConflictToAgent,
+    ContinueConflict,
+    AbortConflict,
+    ArchiveTask,
+    RestoreTask,
+    OpenTranscript,
+    OpenPaneSearch,
+    ToggleBroadcastTarget,
+    OpenBroadcast,
+    OpenPromptQueue,
+    OpenCommandTab,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -119,6 +130,38 @@ pub(super) struct KeybindingSpec {
     pub(super) modifiers: KeyModifiersMatch,
 }
 
+/// One key press of a user-configured key sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct KeyStroke {
+    pub(super) code: KeyCodeMatch,
+    pub(super) modifiers: Modifiers,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CustomKeybinding {
+    pub(super) command: UiCommand,
+    pub(super) sequence: Vec<KeyStroke>,
+}
+
+/// Keybindings from `[keybindings]`; listed commands lose their built-in bindings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Keymap {
+    pub(super) bindings: Vec<CustomKeybinding>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum KeySequenceMatch {
+    NoMatch,
+    Pending,
+    Command(UiCommand),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct PendingKeySequence {
+    pub(super) keys: Vec<KeyEvent>,
+    pub(super) started_at: Option<Instant>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) struct UiCommandMeta {
     pub(super) palette: Option<PaletteCommandSpec>,
@@ -127,7 +170,7 @@ pub(super) struct UiCommandMeta {
 }
 
 impl KeyCodeMatch {
-    fn matches(self, key_event: &KeyEvent) -> bool {
+    pub(super) fn matches(self, key_event: &KeyEvent) -> bool {
         match self {
             Self::Char(expected) => {
                 matches!(key_event.code, KeyCode::Char(actual) if actual == expected)
@@ -173,7 +216,7 @@ impl KeyModifiersMatch {
 }
 
 impl KeybindingSpec {
-    fn matches(self, key_event: &KeyEvent) -> bool {
+    pub(super) fn matches(self, key_event: &KeyEvent) -> bool {
         self.code.matches(key_event)
             && (matches!(self.code, KeyCodeMatch::CtrlChar(_))
                 || self.modifiers.matches(key_event.modifiers))
@@ -193,7 +236,7 @@ fn control_character_for(character: char) -> Option<char> {
 }
 
 impl UiCommand {
-    pub(super) const ALL: [UiCommand; 51] = [
+    pub(super) const ALL: [UiCommand; 62] = [
         UiCommand::ToggleFocus,
         UiCommand::ToggleSidebar,
         UiCommand::OpenPreview,
@@ -245,6 +288,17 @@ impl UiCommand {
         UiCommand::Quit,
         UiCommand::OpenPerformance,
         UiCommand::OpenWorkspaceJump,
+        UiCommand::HandConflictToAgent,
+        UiCommand::ContinueConflict,
+        UiCommand::AbortConflict,
+        UiCommand::ArchiveTask,
+        UiCommand::RestoreTask,
+        UiCommand::OpenTranscript,
+        UiCommand::OpenPaneSearch,
+        UiCommand::ToggleBroadcastTarget,
+        UiCommand::OpenBroadcast,
+        UiCommand::OpenPromptQueue,
+        UiCommand::OpenCommandTab,
     ];
 
     pub(super) fn all() -> &'static [UiCommand] {
@@ -0,0 +1,295 @@
+use super::*;
+use crate::infrastructure::config::KeybindingsConfig;
+
+const LEADER_TOKEN: &str = "<leader>";
+
+impl KeyStroke {
+    fn parse(token: &str) -> Result<Self, String> {
+        let (modifier_names, key_name) = match token.rsplit_once('+') {
+            Some((modifiers, key)) if !modifiers.is_empty() && !key.is_empty() => (modifiers, key),
+            _ => ("", token),
+        };
+        let mut modifiers = Modifiers::empty();
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CTRL,
+                "alt" | "meta" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{name}' in '{token}'")),
+            };
+        }
+
+        let mut characters = key_name.chars();
+        let code = match (characters.next(), characters.next()) {
+            (Some(character), None) => KeyCodeMatch::Char(character),
+            _ => match key_name.to_ascii_lowercase().as_str() {
+                "space" => KeyCodeMatch::Char(' '),
+                "enter" | "return" => KeyCodeMatch::Enter,
+                "tab" => KeyCodeMatch::Tab,
+                "esc" | "escape" => KeyCodeMatch::Escape,
+                "up" => KeyCodeMatch::Up,
+                "down" => KeyCodeMatch::Down,
+                "left" => KeyCodeMatch::Left,
+                "right" => KeyCodeMatch::Right,
+                "pageup" | "pgup" => KeyCodeMatch::PageUp,
+                "pagedown" | "pgdn" => KeyCodeMatch::PageDown,
+                "end" => KeyCodeMatch::End,
+                _ => return Err(format!("unknown key '{key_name}' in '{token}'")),
+            },
+        };
+        Ok(Self::normalized(code, modifiers))
+    }
+
+    fn normalized(code: KeyCodeMatch, mut modifiers: Modifiers) -> Self {
+        let KeyCodeMatch::Char(character) = code else {
+            return Self { code, modifiers };
+        };
+        let character = if modifiers.contains(Modifiers::SHIFT) {
+            modifiers.remove(Modifiers::SHIFT);
+            character.to_ascii_uppercase()
+        } else {
+            character
+        };
+        if modifiers == Modifiers::CTRL {
+            return Self {
+                code: KeyCodeMatch::CtrlChar(character.to_ascii_lowercase()),
+                modifiers: Modifiers::empty(),
+            };
+        }
+        Self {
+            code: KeyCodeMatch::Char(character),
+            modifiers,
+        }
+    }
+
+    fn matches(self, key_event: &KeyEvent) -> bool {
+        if !self.code.matches(key_event) {
+            return false;
+        }
+        match self.code {
+            KeyCodeMatch::CtrlChar(_) => true,
+            KeyCodeMatch::Char(_) => {
+                key_event.modifiers.difference(Modifiers::SHIFT) == self.modifiers
+            }
+            _ => key_event.modifiers == self.modifiers,
+        }
+    }
+
+    fn key_event(self) -> KeyEvent {
+        let code = match self.code {
+            KeyCodeMatch::Char(character) => KeyCode::Char(character),
+            KeyCodeMatch::CtrlChar(character) => {
+                return KeyEvent::new(KeyCode::Char(character))
+                    .with_modifiers(Modifiers::CTRL)
+                    .with_kind(KeyEventKind::Press);
+            }
+            KeyCodeMatch::Enter => KeyCode::Enter,
+            KeyCodeMatch::Tab => KeyCode::Tab,
+            KeyCodeMatch::Escape => KeyCode::Escape,
+            KeyCodeMatch::Up => KeyCode::Up,
+            KeyCodeMatch::Down => KeyCode::Down,
+            KeyCodeMatch::Left => KeyCode::Left,
+            KeyCodeMatch::Right => KeyCode::Right,
+            KeyCodeMatch::PageUp => KeyCode::PageUp,
+            KeyCodeMatch::PageDown => KeyCode::PageDown,
+            KeyCodeMatch::End => KeyCode::End,
+        };
+        KeyEvent::new(code)
+            .with_modifiers(self.modifiers)
+            .with_kind(KeyEventKind::Press)
+    }
+
+    fn label(self) -> String {
+        let key = match self.code {
+            KeyCodeMatch::Char(' ') => "Space".to_string(),
+            KeyCodeMatch::Char(character) => character.to_string(),
+            KeyCodeMatch::CtrlChar(character) => {
+                return format!("Ctrl+{}", character.to_ascii_uppercase());
+            }
+            KeyCodeMatch::Enter => "Enter".to_string(),
+            KeyCodeMatch::Tab => "Tab".to_string(),
+            KeyCodeMatch::Escape => "Esc".to_string(),
+            KeyCodeMatch::Up => "Up".to_string(),
+            KeyCodeMatch::Down => "Down".to_string(),
+            KeyCodeMatch::Left => "Left".to_string(),
+            KeyCodeMatch::Right => "Right".to_string(),
+            KeyCodeMatch::PageUp => "PgUp".to_string(),
+            KeyCodeMatch::PageDown => "PgDn".to_string(),
+            KeyCodeMatch::End => "End".to_string(),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(Modifiers::CTRL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(Modifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label.push_str(key.as_str());
+        label
+    }
+}
+
+fn parse_key_sequence(raw: &str, leader: Option<&[KeyStroke]>) -> Result<Vec<KeyStroke>, String> {
+    let mut sequence = Vec::new();
+    for token in raw.split_whitespace() {
+        if token.eq_ignore_ascii_case(LEADER_TOKEN) {
+            let Some(leader) = leader else {
+                return Err(format!("'{raw}' uses {LEADER_TOKEN} but no leader is set"));
+            };
+            sequence.extend_from_slice(leader);
+            continue;
+        }
+        sequence.push(KeyStroke::parse(token)?);
+    }
+    if sequence.is_empty() {
+        return Err("empty key sequence".to_string());
+    }
+    Ok(sequence)
+}
+
+fn key_sequence_label(sequence: &[KeyStroke]) -> String {
+    sequence
+        .iter()
+        .map(|stroke| stroke.label())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Whether a custom key would hide `spec`; modifier chords only shadow exact defaults.
+fn shadows_default(spec: &KeybindingSpec, key_event: &KeyEvent) -> bool {
+    spec.matches(key_event)
+        && (matches!(spec.code, KeyCodeMatch::CtrlChar(_))
+            || spec.modifiers != KeyModifiersMatch::Any
+            || key_event.modifiers.difference(Modifiers::SHIFT).is_empty())
+}
+
+fn command_config_name(command: UiCommand) -> String {
+    command
+        .palette_spec()
+        .map(|spec| spec.id.to_string())
+        .unwrap_or_else(|| format!("{command:?}"))
+}
+
+impl Keymap {
+    pub(super) fn from_config(config: &KeybindingsConfig) -> Result<Self, String> {
+        let leader = config
+            .leader
+            .as_deref()
+            .map(|leader| parse_key_sequence(leader, None))
+            .transpose()
+            .map_err(|error| format!("leader: {error}"))?;
+        let mut bindings = Vec::new();
+        for (id, sequences) in &config.commands {
+            let command = UiCommand::from_palette_id(id)
+                .or_else(|| UiCommand::from_palette_id(format!("palette:{id}").as_str()))
+                .ok_or_else(|| format!("unknown command '{id}'"))?;
+            if sequences.as_slice().is_empty() {
+                return Err(format!("{id}: no key sequence"));
+            }
+            for raw in sequences.as_slice() {
+                let sequence = parse_key_sequence(raw, leader.as_deref())
+                    .map_err(|error| format!("{id}: {error}"))?;
+                bindings.push(CustomKeybinding { command, sequence });
+            }
+        }
+
+        let keymap = Self { bindings };
+        keymap.validate()?;
+        Ok(keymap)
+    }
+
+    /// Rejects sequences that shadow each other or a built-in binding that is still active.
+    fn validate(&self) -> Result<(), String> {
+        for (index, binding) in self.bindings.iter().enumerate() {
+            let label = key_sequence_label(&binding.sequence);
+            let name = command_config_name(binding.command);
+            for other in &self.bindings[index + 1..] {
+                if other.command == binding.command && other.sequence == binding.sequence {
+                    continue;
+                }
+                let shared = binding.sequence.len().min(other.sequence.len());
+                if binding.sequence[..shared] == other.sequence[..shared] {
+                    return Err(format!(
+                        "'{label}' for {name} conflicts with '{}' for {}",
+                        key_sequence_label(&other.sequence),
+                        command_config_name(other.command),
+                    ));
+                }
+            }
+
+            let first_key = binding.sequence[0].key_event();
+            if let Some(default_owner) = UiCommand::all().iter().find(|command| {
+                !self.overrides(**command)
+                    && command
+                        .keybindings()
+                        .iter()
+                        .any(|spec| shadows_default(spec, &first_key))
+            }) {
+                return Err(format!(
+                    "'{label}' for {name} conflicts with the default binding of {}, remap that command too",
+                    command_config_name(*default_owner),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    pub(super) fn overrides(&self, command: UiCommand) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.command == command)
+    }
+
+    pub(super) fn resolve(&self, keys: &[KeyEvent]) -> KeySequenceMatch {
+        let mut pending = false;
+        for binding in &self.bindings {
+            if keys.len() > binding.sequence.len()
+                || !binding
+                    .sequence
+                    .iter()
+                    .zip(keys)
+                    .all(|(stroke, key)| stroke.matches(key))
+            {
+                continue;
+            }
+            if keys.len() == binding.sequence.len() {
+                return KeySequenceMatch::Command(binding.command);
+            }
+            pending = true;
+        }
+        if pending {
+            KeySequenceMatch::Pending
+        } else {
+            KeySequenceMatch::NoMatch
+        }
+    }
+
+    /// Keys for `command` in help and palette style, alternatives joined by `/`.
+    pub(super) fn key_label(&self, command: UiCommand) -> Option<String> {
+        let labels = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.command == command)
+            .map(|binding| key_sequence_label(&binding.sequence))
+            .collect::<Vec<String>>();
+        if labels.is_empty() {
+            return None;
+        }
+        Some(labels.join("/"))
+    }
+
+    pub(super) fn pending_label(&self, keys: &[KeyEvent]) -> Option<String> {
+        self.bindings.iter().find_map(|binding| {
+            let prefix = binding.sequence.get(..keys.len())?;
+            prefix
+                .iter()
+                .zip(keys)
+                .all(|(stroke, key)| stroke.matches(key))
+                .then(|| key_sequence_label(prefix))
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-static COMMAND_META: [UiCommandMeta; 51] = [
+static COMMAND_META: [UiCommandMeta; 62] = [
     UiCommandMeta {
         palette: Some(PaletteCommandSpec {
             id: "palette:toggle_focus",
@@ -1399,6 +1399,161 @@ static COMMAND_META: [UiCommandMeta; 51] = [
             modifiers: KeyModifiersMatch::Any,
         }],
     },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:hand_conflict_to_agent",
+            title: "Hand Conflict to Agent",
+            description: "Send the conflicted files to the running agent to resolve",
+            tags: &["conflict", "merge", "rebase", "resolve", "agent"],
+            category: "Worktree",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:continue_conflict",
+            title: "Continue After Conflict",
+            description: "Stage resolved files and continue the merge, squash or rebase",
+            tags: &["conflict", "merge", "rebase", "continue", "resolve"],
+            category: "Worktree",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:abort_conflict",
+            title: "Abort Conflicted Operation",
+            description: "Abort the conflicted merge, squash or rebase",
+            tags: &["conflict", "merge", "rebase", "abort"],
+            category: "Worktree",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:archive_task",
+            title: "Archive Task",
+            description: "Save the task's changes and tabs, then remove its worktrees",
+            tags: &["archive", "task", "shelve", "park"],
+            category: "Task",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:restore_task",
+            title: "Restore Archived Task",
+            description: "Recreate an archived task's worktrees and resume its agents",
+            tags: &["restore", "archive", "task", "unarchive", "resume"],
+            category: "Task",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:open_transcript",
+            title: "Session Transcripts",
+            description: "Search the recorded output of the selected task's sessions",
+            tags: &[
+                "transcript",
+                "history",
+                "log",
+                "scrollback",
+                "search",
+                "output",
+            ],
+            category: "Task",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:search_panes",
+            title: "Search All Sessions",
+            description: "Find text in the output of every live session and jump to it",
+            tags: &["search", "find", "grep", "output", "scrollback", "sessions"],
+            category: "Navigation",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:toggle_broadcast_target",
+            title: "Mark for Broadcast",
+            description: "Mark or unmark the selected workspace as a broadcast target (v)",
+            tags: &["broadcast", "mark", "select", "multi", "fan-out", "v"],
+            category: "Workspace",
+        }),
+        help_hints: &[HelpHintSpec {
+            context: HelpHintContext::List,
+            label: "v mark for broadcast",
+            key: "v",
+            action: "mark for broadcast",
+        }],
+        keybindings: &[KeybindingSpec {
+            scope: KeybindingScope::NonInteractive,
+            code: KeyCodeMatch::Char('v'),
+            modifiers: KeyModifiersMatch::Any,
+        }],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:broadcast_prompt",
+            title: "Broadcast Prompt",
+            description: "Send the same prompt to every marked agent (V)",
+            tags: &["broadcast", "prompt", "send", "agents", "fan-out", "V"],
+            category: "Workspace",
+        }),
+        help_hints: &[HelpHintSpec {
+            context: HelpHintContext::List,
+            label: "V broadcast prompt",
+            key: "V",
+            action: "broadcast prompt",
+        }],
+        keybindings: &[KeybindingSpec {
+            scope: KeybindingScope::NonInteractive,
+            code: KeyCodeMatch::Char('V'),
+            modifiers: KeyModifiersMatch::Any,
+        }],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:queue_prompt",
+            title: "Queue Follow-up Prompt",
+            description: "Queue prompts that are sent when the agent is back at its input (Q)",
+            tags: &["queue", "follow-up", "prompt", "next", "idle", "Q"],
+            category: "Workspace",
+        }),
+        help_hints: &[HelpHintSpec {
+            context: HelpHintContext::List,
+            label: "Q queue follow-up",
+            key: "Q",
+            action: "queue follow-up",
+        }],
+        keybindings: &[KeybindingSpec {
+            scope: KeybindingScope::NonInteractive,
+            code: KeyCodeMatch::Char('Q'),
+            modifiers: KeyModifiersMatch::Any,
+        }],
+    },
+    UiCommandMeta {
+        palette: Some(PaletteCommandSpec {
+            id: "palette:open_command_tab",
+            title: "Open Command Tab",
+            description: "Start or focus one of the project's command tabs, e.g. a dev server",
+            tags: &["command", "tab", "dev", "server", "watcher", "run"],
+            category: "Workspace",
+        }),
+        help_hints: &[],
+        keybindings: &[],
+    },
 ];
 
 impl UiCommand {
@@ -1455,6 +1610,17 @@ impl UiCommand {
             UiCommand::OpenPerformance => &COMMAND_META[48],
             UiCommand::OpenRepository => &COMMAND_META[49],
             UiCommand::OpenWorkspaceJump => &COMMAND_META[50],
+            UiCommand::HandConflictToAgent => &COMMAND_META[51],
+            UiCommand::ContinueConflict => &COMMAND_META[52],
+            UiCommand::AbortConflict => &COMMAND_META[53],
+            UiCommand::ArchiveTask => &COMMAND_META[54],
+            UiCommand::RestoreTask => &COMMAND_META[55],
+            UiCommand::OpenTranscript => &COMMAND_META[56],
+            UiCommand::OpenPaneSearch => &COMMAND_META[57],
+            UiCommand::ToggleBroadcastTarget => &COMMAND_META[58],
+            UiCommand::OpenBroadcast => &COMMAND_META[59],
+            UiCommand::OpenPromptQueue => &COMMAND_META[60],
+            UiCommand::OpenCommandTab => &COMMAND_META[61],
         }
     }
 }
@@ -356,10 +356,11 @@ fn delete_dialog_initial_focus_id(dialog: &DeleteDialogState) -> u64 {
     }
 }
 
-fn merge_dialog_focus_ids() -> [u64; 4] {
+fn merge_dialog_focus_ids() -> [u64; 5] {
     [
         FOCUS_ID_MERGE_CLEANUP_WORKSPACE,
         FOCUS_ID_MERGE_CLEANUP_LOCAL_BRANCH,
+        FOCUS_ID_MERGE_STRATEGY,
         FOCUS_ID_MERGE_CONFIRM_BUTTON,
         FOCUS_ID_MERGE_CANCEL_BUTTON,
     ]
@@ -369,6 +370,7 @@ fn merge_dialog_focus_field(focus_id: Option<u64>) -> Option<MergeDialogField> {
     match focus_id {
         Some(FOCUS_ID_MERGE_CLEANUP_WORKSPACE) => Some(MergeDialogField::CleanupWorkspace),
         Some(FOCUS_ID_MERGE_CLEANUP_LOCAL_BRANCH) => Some(MergeDialogField::CleanupLocalBranch),
+        Some(FOCUS_ID_MERGE_STRATEGY) => Some(MergeDialogField::Strategy),
         Some(FOCUS_ID_MERGE_CONFIRM_BUTTON) => Some(MergeDialogField::MergeButton),
         Some(FOCUS_ID_MERGE_CANCEL_BUTTON) => Some(MergeDialogField::CancelButton),
         _ => None,
@@ -379,15 +381,20 @@ fn merge_dialog_initial_focus_id() -> u64 {
     FOCUS_ID_MERGE_CLEANUP_WORKSPACE
 }
 
-fn update_from_base_dialog_focus_ids() -> [u64; 2] {
-    [
-        FOCUS_ID_UPDATE_FROM_BASE_CONFIRM_BUTTON,
-        FOCUS_ID_UPDATE_FROM_BASE_CANCEL_BUTTON,
-    ]
+fn update_from_base_dialog_focus_ids(dialog: &UpdateFromBaseDialogState) -> Vec<u64> {
+    let mut ids = Vec::with_capacity(3);
+    // The base workspace always pulls --ff-only, so there is no strategy to pick.
+    if !dialog.is_main_workspace {
+        ids.push(FOCUS_ID_UPDATE_FROM_BASE_STRATEGY);
+    }
+    ids.push(FOCUS_ID_UPDATE_FROM_BASE_CONFIRM_BUTTON);
+    ids.push(FOCUS_ID_UPDATE_FROM_BASE_CANCEL_BUTTON);
+    ids
 }
 
 fn update_from_base_dialog_focus_field(focus_id: Option<u64>) -> Option<UpdateFromBaseDialogField> {
     match focus_id {
+        Some(FOCUS_ID_UPDATE_FROM_BASE_STRATEGY) => Some(UpdateFromBaseDialogField::Strategy),
         Some(FOCUS_ID_UPDATE_FROM_BASE_CONFIRM_BUTTON) => {
             Some(UpdateFromBaseDialogField::UpdateButton)
         }
@@ -544,7 +551,7 @@ fn create_dialog_focus_ids(dialog: &CreateDialogState) -> Vec<u64> {
         ];
     }
 
-    match dialog.tab {
+    let mut members = match dialog.tab {
         CreateDialogTab::Manual => {
             let mut members = Vec::new();
             if !dialog.register_as_base {
@@ -552,17 +559,24 @@ fn create_dialog_focus_ids(dialog: &CreateDialogState) -> Vec<u64> {
             }
             members.push(FOCUS_ID_CREATE_REGISTER_AS_BASE);
             members.push(FOCUS_ID_CREATE_PROJECT);
-            members.push(FOCUS_ID_CREATE_CREATE_BUTTON);
-            members.push(FOCUS_ID_CREATE_CANCEL_BUTTON);
             members
         }
-        CreateDialogTab::PullRequest => vec![
-            FOCUS_ID_CREATE_PROJECT,
-            FOCUS_ID_CREATE_PULL_REQUEST_URL,
-            FOCUS_ID_CREATE_CREATE_BUTTON,
-            FOCUS_ID_CREATE_CANCEL_BUTTON,
-        ],
+        CreateDialogTab::PullRequest => {
+            vec![FOCUS_ID_CREATE_PROJECT, FOCUS_ID_CREATE_PULL_REQUEST_URL]
+        }
+    };
+    if dialog.shows_recipe_fields() {
+        members.push(FOCUS_ID_CREATE_RECIPE);
+        if dialog
+            .selected_recipe()
+            .is_some_and(|recipe| !recipe.variables.is_empty())
+        {
+            members.push(FOCUS_ID_CREATE_RECIPE_VARIABLES);
+        }
     }
+    members.push(FOCUS_ID_CREATE_CREATE_BUTTON);
+    members.push(FOCUS_ID_CREATE_CANCEL_BUTTON);
+    members
 }
 
 pub(super) fn create_dialog_focus_id(field: CreateDialogField) -> u64 {
@@ -571,6 +585,8 @@ pub(super) fn create_dialog_focus_id(field: CreateDialogField) -> u64 {
         CreateDialogField::RegisterAsBase => FOCUS_ID_CREATE_REGISTER_AS_BASE,
         CreateDialogField::PullRequestUrl => FOCUS_ID_CREATE_PULL_REQUEST_URL,
         CreateDialogField::Project => FOCUS_ID_CREATE_PROJECT,
+        CreateDialogField::Recipe => FOCUS_ID_CREATE_RECIPE,
+        CreateDialogField::RecipeVariables => FOCUS_ID_CREATE_RECIPE_VARIABLES,
         CreateDialogField::CreateButton => FOCUS_ID_CREATE_CREATE_BUTTON,
         CreateDialogField::CancelButton => FOCUS_ID_CREATE_CANCEL_BUTTON,
     }
@@ -582,6 +598,8 @@ fn create_dialog_focus_field(focus_id: Option<u64>) -> Option<CreateDialogField>
         Some(FOCUS_ID_CREATE_REGISTER_AS_BASE) => Some(CreateDialogField::RegisterAsBase),
         Some(FOCUS_ID_CREATE_PULL_REQUEST_URL) => Some(CreateDialogField::PullRequestUrl),
         Some(FOCUS_ID_CREATE_PROJECT) => Some(CreateDialogField::Project),
+        Some(FOCUS_ID_CREATE_RECIPE) => Some(CreateDialogField::Recipe),
+        Some(FOCUS_ID_CREATE_RECIPE_VARIABLES) => Some(CreateDialogField::RecipeVariables),
         Some(FOCUS_ID_CREATE_CREATE_BUTTON) => Some(CreateDialogField::CreateButton),
         Some(FOCUS_ID_CREATE_CANCEL_BUTTON) => Some(CreateDialogField::CancelButton),
         _ => None,
@@ -1020,8 +1038,8 @@ impl GroveApp {
                     merge_dialog_initial_focus_id(),
                 );
             }
-            ActiveDialog::UpdateFromBase(_) => {
-                let members = update_from_base_dialog_focus_ids();
+            ActiveDialog::UpdateFromBase(dialog) => {
+                let members = update_from_base_dialog_focus_ids(dialog);
                 self.activate_focus_trap(
                     FOCUS_GROUP_UPDATE_FROM_BASE_DIALOG,
                     &members,
@@ -1105,8 +1123,8 @@ impl GroveApp {
                 let members = merge_dialog_focus_ids();
                 self.deactivate_focus_trap(&members);
             }
-            ActiveDialog::UpdateFromBase(_) => {
-                let members = update_from_base_dialog_focus_ids();
+            ActiveDialog::UpdateFromBase(dialog) => {
+                let members = update_from_base_dialog_focus_ids(dialog);
                 self.deactivate_focus_trap(&members);
             }
             ActiveDialog::PullUpstream(_) => {
@@ -1199,6 +1217,10 @@ impl GroveApp {
             Some(ActiveDialog::Project(_)) => Some("project"),
             Some(ActiveDialog::Settings(_)) => Some("settings"),
             Some(ActiveDialog::Performance(_)) => Some("performance"),
+            Some(ActiveDialog::Transcript(_)) => Some("transcript"),
+            Some(ActiveDialog::PaneSearch(_)) => Some("pane_search"),
+            Some(ActiveDialog::Broadcast(_)) => Some("broadcast"),
+            Some(ActiveDialog::PromptQueue(_)) => Some("prompt_queue"),
             None => None,
         }
     }
@@ -1243,8 +1265,9 @@ impl GroveApp {
         MergeDialogState
     );
     active_dialog_take_accessor!(take_merge_dialog, Merge, MergeDialogState);
-    active_dialog_read_accessors!(
+    active_dialog_accessors!(
         update_from_base_dialog,
+        update_from_base_dialog_mut,
         set_update_from_base_dialog,
         UpdateFromBase,
         UpdateFromBaseDialogState
@@ -1305,6 +1328,82 @@ impl GroveApp {
         self.dialogs.active_dialog = Some(ActiveDialog::Performance(dialog));
     }
 
+    pub(super) fn transcript_dialog(&self) -> Option<&TranscriptDialogState> {
+        match self.dialogs.active_dialog.as_ref() {
+            Some(ActiveDialog::Transcript(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn transcript_dialog_mut(&mut self) -> Option<&mut TranscriptDialogState> {
+        match self.dialogs.active_dialog.as_mut() {
+            Some(ActiveDialog::Transcript(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn set_transcript_dialog(&mut self, dialog: TranscriptDialogState) {
+        self.clear_active_dialog_focus_trap();
+        self.dialogs.active_dialog = Some(ActiveDialog::Transcript(dialog));
+    }
+
+    pub(super) fn pane_search_dialog(&self) -> Option<&PaneSearchDialogState> {
+        match self.dialogs.active_dialog.as_ref() {
+            Some(ActiveDialog::PaneSearch(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn pane_search_dialog_mut(&mut self) -> Option<&mut PaneSearchDialogState> {
+        match self.dialogs.active_dialog.as_mut() {
+            Some(ActiveDialog::PaneSearch(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn set_pane_search_dialog(&mut self, dialog: PaneSearchDialogState) {
+        self.clear_active_dialog_focus_trap();
+        self.dialogs.active_dialog = Some(ActiveDialog::PaneSearch(dialog));
+    }
+
+    pub(super) fn broadcast_dialog(&self) -> Option<&BroadcastDialogState> {
+        match self.dialogs.active_dialog.as_ref() {
+            Some(ActiveDialog::Broadcast(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn broadcast_dialog_mut(&mut self) -> Option<&mut BroadcastDialogState> {
+        match self.dialogs.active_dialog.as_mut() {
+            Some(ActiveDialog::Broadcast(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn set_broadcast_dialog(&mut self, dialog: BroadcastDialogState) {
+        self.clear_active_dialog_focus_trap();
+        self.dialogs.active_dialog = Some(ActiveDialog::Broadcast(dialog));
+    }
+
+    pub(super) fn prompt_queue_dialog(&self) -> Option<&PromptQueueDialogState> {
+        match self.dialogs.active_dialog.as_ref() {
+            Some(ActiveDialog::PromptQueue(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn prompt_queue_dialog_mut(&mut self) -> Option<&mut PromptQueueDialogState> {
+        match self.dialogs.active_dialog.as_mut() {
+            Some(ActiveDialog::PromptQueue(dialog)) => Some(dialog),
+            _ => None,
+        }
+    }
+
+    pub(super) fn set_prompt_queue_dialog(&mut self, dialog: PromptQueueDialogState) {
+        self.clear_active_dialog_focus_trap();
+        self.dialogs.active_dialog = Some(ActiveDialog::PromptQueue(dialog));
+    }
+
     pub(super) fn allows_text_input_modifiers(modifiers: Modifiers) -> bool {
         modifiers.is_empty() || modifiers == Modifiers::SHIFT
     }
@@ -0,0 +1,194 @@
+use super::*;
+
+impl GroveApp {
+    pub(super) fn toggle_broadcast_target(&mut self) {
+        let Some(workspace) = self.state.selected_workspace() else {
+            return;
+        };
+        let workspace_path = workspace.path.clone();
+        let workspace_name = workspace.name.clone();
+        let marked = self.broadcast_targets.insert(workspace_path.clone());
+        if !marked {
+            self.broadcast_targets.remove(&workspace_path);
+        }
+        self.show_info_toast(format!(
+            "{workspace_name} {}, {} marked for broadcast",
+            if marked { "marked" } else { "unmarked" },
+            self.broadcast_targets.len()
+        ));
+    }
+
+    pub(super) fn open_broadcast_dialog(&mut self) {
+        self.broadcast_targets.retain(|path| {
+            self.state
+                .workspaces
+                .iter()
+                .any(|workspace| &workspace.path == path)
+        });
+        let workspaces = if self.broadcast_targets.is_empty() {
+            self.state.selected_workspace().into_iter().collect()
+        } else {
+            self.state
+                .workspaces
+                .iter()
+                .filter(|workspace| self.broadcast_targets.contains(&workspace.path))
+                .collect::<Vec<&Workspace>>()
+        };
+        let targets = workspaces
+            .into_iter()
+            .map(|workspace| BroadcastTarget {
+                workspace_name: workspace.name.clone(),
+                workspace_path: workspace.path.clone(),
+                session_name: self.workspace_running_agent_session_for_status_poll(
+                    workspace.path.as_path(),
+                    None,
+                ),
+                status: workspace.status,
+                delivery: None,
+            })
+            .collect::<Vec<BroadcastTarget>>();
+        if targets.is_empty() {
+            self.show_info_toast("no workspace selected to broadcast to");
+            return;
+        }
+
+        let target_count = targets.len();
+        self.set_broadcast_dialog(BroadcastDialogState {
+            targets,
+            ..BroadcastDialogState::default()
+        });
+        self.log_dialog_event_with_fields(
+            "broadcast",
+            "dialog_opened",
+            [(
+                "targets".to_string(),
+                Value::from(usize_to_u64(target_count)),
+            )],
+        );
+    }
+
+    pub(super) fn clear_broadcast_prompt(&mut self) {
+        if let Some(dialog) = self.broadcast_dialog_mut() {
+            dialog.prompt.clear();
+        }
+    }
+
+    pub(super) fn apply_paste_to_broadcast_dialog(&mut self, text: &str) -> bool {
+        let Some(dialog) = self.broadcast_dialog_mut() else {
+            return false;
+        };
+        dialog.prompt.push_str(text);
+        reset_broadcast_deliveries(dialog);
+        true
+    }
+
+    pub(super) fn handle_broadcast_dialog_key(&mut self, key_event: KeyEvent) {
+        if key_event.code == KeyCode::Enter {
+            let (prompt_empty, sent) = match self.broadcast_dialog() {
+                Some(dialog) => (dialog.prompt.trim().is_empty(), dialog.sent),
+                None => return,
+            };
+            if !prompt_empty {
+                self.send_broadcast();
+            } else if sent {
+                self.close_active_dialog();
+            }
+            return;
+        }
+
+        let Some(dialog) = self.broadcast_dialog_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Tab | KeyCode::BackTab => dialog.force = !dialog.force,
+            KeyCode::Backspace => {
+                dialog.prompt.pop();
+                reset_broadcast_deliveries(dialog);
+            }
+            KeyCode::Char(character)
+                if Self::allows_text_input_modifiers(key_event.modifiers)
+                    && !character.is_control() =>
+            {
+                dialog.prompt.push(character);
+                reset_broadcast_deliveries(dialog);
+            }
+            _ => {}
+        }
+    }
+
+    fn send_broadcast(&mut self) {
+        let Some(dialog) = self.broadcast_dialog() else {
+            return;
+        };
+        let prompt = dialog.prompt.clone();
+        let force = dialog.force;
+        let targets = dialog.targets.clone();
+
+        let mut deliveries = Vec::with_capacity(targets.len());
+        for target in &targets {
+            let delivery = match target.session_name.as_deref() {
+                None => BroadcastDelivery::Skipped("no running agent".to_string()),
+                Some(_) if !force && !broadcast_accepts_status(target.status) => {
+                    BroadcastDelivery::Skipped(format!(
+                        "agent is {}, Tab to force",
+                        super::performance::workspace_status_label(target.status)
+                    ))
+                }
+                Some(session_name) => submit_prompt_commands(session_name, prompt.as_str())
+                    .iter()
+                    .try_for_each(|command| self.execute_tmux_command(command))
+                    .map_or_else(
+                        |error| BroadcastDelivery::Failed(error.to_string()),
+                        |()| BroadcastDelivery::Sent,
+                    ),
+            };
+            deliveries.push(delivery);
+        }
+
+        let sent = deliveries
+            .iter()
+            .filter(|delivery| **delivery == BroadcastDelivery::Sent)
+            .count();
+        let failed = deliveries
+            .iter()
+            .filter(|delivery| matches!(delivery, BroadcastDelivery::Failed(_)))
+            .count();
+        let skipped = deliveries.len().saturating_sub(sent + failed);
+        if let Some(dialog) = self.broadcast_dialog_mut() {
+            for (target, delivery) in dialog.targets.iter_mut().zip(deliveries) {
+                target.delivery = Some(delivery);
+            }
+            dialog.prompt.clear();
+            dialog.sent = true;
+        }
+
+        let summary = format!("broadcast sent to {sent}, skipped {skipped}, failed {failed}");
+        if failed > 0 {
+            self.show_error_toast(summary);
+        } else if sent == 0 {
+            self.show_info_toast(summary);
+        } else {
+            self.show_success_toast(summary);
+        }
+        self.log_dialog_event_with_fields(
+            "broadcast",
+            "sent",
+            [
+                ("sent".to_string(), Value::from(usize_to_u64(sent))),
+                ("skipped".to_string(), Value::from(usize_to_u64(skipped))),
+                ("failed".to_string(), Value::from(usize_to_u64(failed))),
+                ("force".to_string(), Value::from(force)),
+            ],
+        );
+    }
+}
+
+fn reset_broadcast_deliveries(dialog: &mut BroadcastDialogState) {
+    if !dialog.sent {
+        return;
+    }
+    dialog.sent = false;
+    for target in &mut dialog.targets {
+        target.delivery = None;
+    }
+}
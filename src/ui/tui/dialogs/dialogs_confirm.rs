@@ -28,6 +28,8 @@ impl GroveApp {
     fn confirm_dialog_target(action: &ConfirmDialogAction) -> &'static str {
         match action {
             ConfirmDialogAction::CloseActiveTab { .. } => "close_active_tab",
+            ConfirmDialogAction::ArchiveTask { .. } => "archive_task",
+            ConfirmDialogAction::ResumeArchivedAgents { .. } => "resume_archived_agents",
             ConfirmDialogAction::QuitApp => "quit_app",
         }
     }
@@ -51,6 +53,12 @@ impl GroveApp {
             } => {
                 self.force_close_active_tab_and_session(&workspace_path, tab_id, &session_name);
             }
+            ConfirmDialogAction::ArchiveTask { task } => {
+                self.start_archive_task(task);
+            }
+            ConfirmDialogAction::ResumeArchivedAgents { tabs, .. } => {
+                self.resume_archived_agents(tabs);
+            }
             ConfirmDialogAction::QuitApp => {
                 self.queue_cmd(Cmd::Quit);
             }
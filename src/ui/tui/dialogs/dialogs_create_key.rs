@@ -202,7 +202,9 @@ impl GroveApp {
                     CreateDialogField::WorkspaceName
                     | CreateDialogField::RegisterAsBase
                     | CreateDialogField::PullRequestUrl
-                    | CreateDialogField::Project => EnterAction::AdvanceField,
+                    | CreateDialogField::Project
+                    | CreateDialogField::Recipe
+                    | CreateDialogField::RecipeVariables => EnterAction::AdvanceField,
                 };
 
                 match action {
@@ -222,18 +224,36 @@ impl GroveApp {
             KeyCode::BackTab => {
                 self.create_dialog_focus_previous();
             }
+            KeyCode::Left | KeyCode::Right if focused_field == CreateDialogField::Recipe => {
+                if let Some(dialog) = self.create_dialog_mut() {
+                    dialog.cycle_recipe(key_event.code == KeyCode::Right);
+                }
+                self.refresh_create_dialog_focus_trap();
+            }
             KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {}
             KeyCode::Char(' ') if key_event.modifiers.is_empty() => {
                 let mut refresh_focus = false;
-                if let Some(dialog) = self.create_dialog_mut()
-                    && focused_field == CreateDialogField::RegisterAsBase
-                {
-                    dialog.register_as_base = !dialog.register_as_base;
-                    if dialog.register_as_base {
-                        dialog.task_name.clear();
-                        dialog.selected_repository_indices = vec![dialog.project_index];
+                if let Some(dialog) = self.create_dialog_mut() {
+                    match focused_field {
+                        CreateDialogField::RegisterAsBase => {
+                            dialog.register_as_base = !dialog.register_as_base;
+                            if dialog.register_as_base {
+                                dialog.task_name.clear();
+                                dialog.selected_repository_indices = vec![dialog.project_index];
+                            }
+                            refresh_focus = true;
+                        }
+                        CreateDialogField::Recipe => {
+                            dialog.cycle_recipe(true);
+                            refresh_focus = true;
+                        }
+                        CreateDialogField::RecipeVariables => dialog.recipe_variables.push(' '),
+                        CreateDialogField::WorkspaceName
+                        | CreateDialogField::PullRequestUrl
+                        | CreateDialogField::Project
+                        | CreateDialogField::CreateButton
+                        | CreateDialogField::CancelButton => {}
                     }
-                    refresh_focus = true;
                 }
                 if refresh_focus {
                     self.refresh_create_dialog_focus_trap();
@@ -255,9 +275,13 @@ impl GroveApp {
                         CreateDialogField::PullRequestUrl => {
                             dialog.pr_url.pop();
                         }
+                        CreateDialogField::RecipeVariables => {
+                            dialog.recipe_variables.pop();
+                        }
                         CreateDialogField::WorkspaceName
                         | CreateDialogField::RegisterAsBase
                         | CreateDialogField::Project
+                        | CreateDialogField::Recipe
                         | CreateDialogField::CreateButton
                         | CreateDialogField::CancelButton => {}
                     }
@@ -291,9 +315,15 @@ impl GroveApp {
                                 dialog.pr_url.push(character);
                             }
                         }
+                        CreateDialogField::RecipeVariables => {
+                            if !character.is_control() {
+                                dialog.recipe_variables.push(character);
+                            }
+                        }
                         CreateDialogField::WorkspaceName
                         | CreateDialogField::RegisterAsBase
-                        | CreateDialogField::Project => {}
+                        | CreateDialogField::Project
+                        | CreateDialogField::Recipe => {}
                         CreateDialogField::CreateButton | CreateDialogField::CancelButton => {}
                     }
                 }
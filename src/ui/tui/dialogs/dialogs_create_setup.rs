@@ -230,6 +230,10 @@ impl GroveApp {
         }
 
         let project_index = self.selected_project_index();
+        let recipes = load_recipes(&self.projects, &self.agents).unwrap_or_else(|error| {
+            self.show_error_toast(error);
+            Vec::new()
+        });
         self.set_create_dialog(CreateDialogState {
             mode: CreateDialogMode::NewTask,
             tab: CreateDialogTab::Manual,
@@ -239,6 +243,9 @@ impl GroveApp {
             project_index,
             selected_repository_indices: vec![project_index],
             project_picker: None,
+            recipes,
+            recipe_index: None,
+            recipe_variables: String::new(),
         });
         self.log_dialog_event("create", "dialog_opened");
         self.session.last_tmux_error = None;
@@ -279,6 +286,9 @@ impl GroveApp {
             project_index,
             selected_repository_indices: vec![project_index],
             project_picker: None,
+            recipes: Vec::new(),
+            recipe_index: None,
+            recipe_variables: String::new(),
         });
         self.log_dialog_event("create", "dialog_opened");
         self.session.last_tmux_error = None;
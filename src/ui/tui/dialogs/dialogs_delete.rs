@@ -1,4 +1,5 @@
 use super::*;
+use crate::application::hooks::run_between_hooks;
 
 impl GroveApp {
     pub(super) fn workspace_delete_requested(&self, workspace_path: &Path) -> bool {
@@ -50,13 +51,21 @@ impl GroveApp {
         let requested_workspace_paths = queued_delete.requested_workspace_paths;
         let deleted_task = queued_delete.deleted_task;
         let removed_base_task = queued_delete.removed_base_task;
+        let pre_delete_hooks =
+            self.hook_invocation_for_paths(HookEvent::PreDelete, &requested_workspace_paths);
+        let agents = Arc::clone(&self.agents);
         self.dialogs.delete_in_flight = true;
         self.dialogs.delete_in_flight_workspace = Some(workspace_path.clone());
         self.queue_cmd(Cmd::task(move || {
-            let (result, warnings) = match request {
-                QueuedDeleteRequest::Task(request) => delete_task(request),
-                QueuedDeleteRequest::Worktree(request) => delete_workspace(request),
-            };
+            let ((result, warnings), outcomes) = run_between_hooks(
+                pre_delete_hooks,
+                || match request {
+                    QueuedDeleteRequest::Task(request) => delete_task(request),
+                    QueuedDeleteRequest::Worktree(request) => delete_workspace(request, &agents),
+                },
+                |veto| (Err(veto), Vec::new()),
+                |_| None,
+            );
             Msg::DeleteWorkspaceCompleted(DeleteWorkspaceCompletion {
                 workspace_name,
                 workspace_path,
@@ -66,6 +75,7 @@ impl GroveApp {
                 result,
                 warnings,
             })
+            .with_hook_outcomes(outcomes)
         }));
     }
 
@@ -404,7 +414,7 @@ impl GroveApp {
         if !self.tmux_input.supports_background_launch() {
             let (result, warnings) = match request.clone() {
                 QueuedDeleteRequest::Task(request) => delete_task(request),
-                QueuedDeleteRequest::Worktree(request) => delete_workspace(request),
+                QueuedDeleteRequest::Worktree(request) => delete_workspace(request, &self.agents),
             };
             self.apply_delete_workspace_completion(DeleteWorkspaceCompletion {
                 workspace_name,
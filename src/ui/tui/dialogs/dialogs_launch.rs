@@ -18,7 +18,7 @@ impl GroveApp {
         let agent = self.task_agent_for_selected_task();
         self.set_launch_dialog(LaunchDialogState {
             target: LaunchDialogTarget::ParentTask(task.clone()),
-            agent,
+            agent: agent.clone(),
             start_config: StartAgentConfigState::new(
                 String::new(),
                 prompt.clone(),
@@ -109,17 +109,19 @@ impl GroveApp {
                 }
             }
             KeyCode::Left => {
+                let agents = Arc::clone(&self.agents);
                 if let Some(dialog) = self.launch_dialog_mut()
                     && focused_field == LaunchDialogField::Agent
                 {
-                    dialog.agent = dialog.agent.previous();
+                    dialog.agent = agents.previous(&dialog.agent);
                 }
             }
             KeyCode::Right => {
+                let agents = Arc::clone(&self.agents);
                 if let Some(dialog) = self.launch_dialog_mut()
                     && focused_field == LaunchDialogField::Agent
                 {
-                    dialog.agent = dialog.agent.next();
+                    dialog.agent = agents.next(&dialog.agent);
                 }
             }
             KeyCode::Char(character) if Self::allows_text_input_modifiers(key_event.modifiers) => {
@@ -135,13 +137,14 @@ impl GroveApp {
                     return;
                 }
 
+                let agents = Arc::clone(&self.agents);
                 if let Some(dialog) = self.launch_dialog_mut() {
                     match focused_field {
                         LaunchDialogField::Agent => {
                             if character == 'j' || character == 'l' {
-                                dialog.agent = dialog.agent.next();
+                                dialog.agent = agents.next(&dialog.agent);
                             } else if character == 'k' || character == 'h' {
-                                dialog.agent = dialog.agent.previous();
+                                dialog.agent = agents.previous(&dialog.agent);
                             }
                         }
                         LaunchDialogField::StartConfig(field) => match field {
@@ -154,7 +157,7 @@ impl GroveApp {
                             }
                             StartAgentConfigField::Unsafe => {
                                 if character == ' ' || character == 'j' || character == 'k' {
-                                    dialog.start_config.cycle_permission_mode(dialog.agent);
+                                    dialog.start_config.cycle_permission_mode(&dialog.agent);
                                 }
                             }
                         },
@@ -182,11 +185,11 @@ impl GroveApp {
         let agent = self
             .last_agent_selection
             .get(workspace.path.as_path())
-            .copied()
-            .unwrap_or(workspace.agent);
+            .cloned()
+            .unwrap_or_else(|| workspace.agent.clone());
         self.set_launch_dialog(LaunchDialogState {
             target: LaunchDialogTarget::WorkspaceTab,
-            agent,
+            agent: agent.clone(),
             start_config: StartAgentConfigState::new(
                 String::new(),
                 prompt.clone(),
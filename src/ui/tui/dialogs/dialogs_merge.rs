@@ -1,4 +1,5 @@
 use super::*;
+use crate::application::hooks::run_between_hooks;
 
 impl GroveApp {
     pub(super) fn handle_merge_dialog_key(&mut self, key_event: KeyEvent) {
@@ -47,6 +48,11 @@ impl GroveApp {
                         dialog.cleanup_local_branch = !dialog.cleanup_local_branch;
                     }
                 }
+                MergeDialogField::Strategy => {
+                    if let Some(dialog) = self.merge_dialog_mut() {
+                        dialog.strategy = dialog.strategy.next();
+                    }
+                }
                 MergeDialogField::MergeButton => {
                     confirm_merge = true;
                 }
@@ -83,8 +89,25 @@ impl GroveApp {
                         dialog.cleanup_local_branch = !dialog.cleanup_local_branch;
                     }
                 }
+                MergeDialogField::Strategy => {
+                    if let Some(dialog) = self.merge_dialog_mut() {
+                        dialog.strategy = dialog.strategy.next();
+                    }
+                }
                 MergeDialogField::MergeButton | MergeDialogField::CancelButton => {}
             },
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l')
+                if no_modifiers && focused_field == MergeDialogField::Strategy =>
+            {
+                let next = matches!(key_event.code, KeyCode::Right | KeyCode::Char('l'));
+                if let Some(dialog) = self.merge_dialog_mut() {
+                    dialog.strategy = if next {
+                        dialog.strategy.next()
+                    } else {
+                        dialog.strategy.previous()
+                    };
+                }
+            }
             KeyCode::Char(character) if no_modifiers => {
                 if (focused_field == MergeDialogField::MergeButton
                     || focused_field == MergeDialogField::CancelButton)
@@ -139,6 +162,15 @@ impl GroveApp {
             return;
         }
 
+        let (strategy, keep_conflicts) = self
+            .project_for_workspace(&workspace)
+            .map(|project| {
+                (
+                    project.defaults.merge_strategy,
+                    project.defaults.keep_conflicts,
+                )
+            })
+            .unwrap_or_default();
         self.set_merge_dialog(MergeDialogState {
             task_slug: workspace.task_slug.clone(),
             project_name: workspace.project_name.clone(),
@@ -147,6 +179,8 @@ impl GroveApp {
             workspace_branch: workspace.branch.clone(),
             workspace_path: workspace.path.clone(),
             base_branch,
+            strategy,
+            keep_conflicts,
             cleanup_workspace: true,
             cleanup_local_branch: true,
         });
@@ -201,6 +235,7 @@ impl GroveApp {
                     "base_branch".to_string(),
                     Value::from(dialog.base_branch.clone()),
                 ),
+                ("strategy".to_string(), Value::from(dialog.strategy.label())),
                 (
                     "cleanup_workspace".to_string(),
                     Value::from(dialog.cleanup_workspace),
@@ -216,6 +251,13 @@ impl GroveApp {
         let workspace_path = dialog.workspace_path.clone();
         let workspace_branch = dialog.workspace_branch.clone();
         let base_branch = dialog.base_branch.clone();
+        let conflict_paths = [
+            Some(dialog.workspace_path.clone()),
+            dialog.project_path.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<PathBuf>>();
         let request = MergeWorkspaceRequest {
             task_slug: dialog.task_slug,
             project_name: dialog.project_name,
@@ -224,13 +266,30 @@ impl GroveApp {
             workspace_branch: dialog.workspace_branch,
             workspace_path: dialog.workspace_path,
             base_branch: dialog.base_branch,
+            strategy: dialog.strategy,
+            keep_conflicts: dialog.keep_conflicts,
             cleanup_workspace: dialog.cleanup_workspace,
             cleanup_local_branch: dialog.cleanup_local_branch,
         };
 
+        let keep_conflicts = request.keep_conflicts;
+        let hook_paths = std::slice::from_ref(&workspace_path);
+        let pre_merge_hooks = self.hook_invocation_for_paths(HookEvent::PreMerge, hook_paths);
+        let post_merge_hooks = self.hook_invocation_for_paths(HookEvent::PostMerge, hook_paths);
+        let merge = move || {
+            run_between_hooks(
+                pre_merge_hooks,
+                || merge_workspace_with_terminator(request, &RuntimeSessionTerminator),
+                |veto| (Err(veto), Vec::new()),
+                |(result, _)| post_merge_hooks.filter(|_| result.is_ok()),
+            )
+        };
+
         if !self.tmux_input.supports_background_launch() {
-            let (result, warnings) =
-                merge_workspace_with_terminator(request, &RuntimeSessionTerminator);
+            let ((result, warnings), outcomes) = merge();
+            self.log_hook_outcomes(&outcomes);
+            let conflict =
+                Self::conflict_left_in_place(&result, keep_conflicts, conflict_paths.as_slice());
             self.apply_merge_workspace_completion(MergeWorkspaceCompletion {
                 workspace_name,
                 workspace_path,
@@ -238,14 +297,16 @@ impl GroveApp {
                 base_branch,
                 result,
                 warnings,
+                conflict,
             });
             return;
         }
 
         self.dialogs.merge_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
-            let (result, warnings) =
-                merge_workspace_with_terminator(request, &RuntimeSessionTerminator);
+            let ((result, warnings), outcomes) = merge();
+            let conflict =
+                Self::conflict_left_in_place(&result, keep_conflicts, conflict_paths.as_slice());
             Msg::MergeWorkspaceCompleted(MergeWorkspaceCompletion {
                 workspace_name,
                 workspace_path,
@@ -253,7 +314,9 @@ impl GroveApp {
                 base_branch,
                 result,
                 warnings,
+                conflict,
             })
+            .with_hook_outcomes(outcomes)
         }));
     }
 }
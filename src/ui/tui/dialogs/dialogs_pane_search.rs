@@ -0,0 +1,225 @@
+use super::*;
+
+/// Lines kept above a hit when the preview scrolls to it.
+const PANE_SEARCH_REVEAL_CONTEXT_LINES: usize = 3;
+
+impl GroveApp {
+    pub(super) fn open_pane_search_dialog(&mut self) {
+        let rows = match self.tmux_input.list_sessions_with_tab_metadata() {
+            Ok(rows) => rows,
+            Err(error) => {
+                self.show_error_toast(format!("session query failed: {error}"));
+                return;
+            }
+        };
+
+        let mut sources = Vec::new();
+        for session_name in crate::application::agent_runtime::grove_managed_tmux_sessions(&rows) {
+            let Some((workspace_path, group, label)) =
+                self.pane_search_target(session_name.as_str())
+            else {
+                continue;
+            };
+            let Ok(output) = self.tmux_input.capture_output(
+                session_name.as_str(),
+                LIVE_PREVIEW_FULL_SCROLLBACK_LINES,
+                false,
+            ) else {
+                continue;
+            };
+            sources.push(PaneSearchSource {
+                session_name,
+                workspace_path,
+                group,
+                label,
+                lines: pane_search_lines(output.as_str()),
+            });
+        }
+        if sources.is_empty() {
+            self.show_info_toast("no live sessions to search");
+            return;
+        }
+
+        let pane_count = sources.len();
+        self.set_pane_search_dialog(PaneSearchDialogState {
+            sources,
+            ..PaneSearchDialogState::default()
+        });
+        self.log_dialog_event_with_fields(
+            "pane_search",
+            "dialog_opened",
+            [("panes".to_string(), Value::from(usize_to_u64(pane_count)))],
+        );
+    }
+
+    /// Workspace, task name and tab title of a session shown in a workspace tab.
+    fn pane_search_target(&self, session_name: &str) -> Option<(PathBuf, String, String)> {
+        let workspace_path = self.workspace_path_for_session(session_name)?;
+        let workspace = self
+            .state
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.path == workspace_path)?;
+        let group = workspace
+            .task_slug
+            .as_deref()
+            .and_then(|task_slug| self.state.tasks.iter().find(|task| task.slug == task_slug))
+            .map_or_else(|| workspace.name.clone(), |task| task.name.clone());
+        let label = self
+            .workspace_tabs
+            .get(workspace_path.as_path())?
+            .tabs
+            .iter()
+            .find(|tab| tab.session_name.as_deref() == Some(session_name))?
+            .title
+            .clone();
+        Some((workspace_path, group, label))
+    }
+
+    pub(super) fn pane_search_page_hits(&self) -> usize {
+        usize::from(self.viewport_height.saturating_sub(20)).max(1)
+    }
+
+    pub(super) fn clear_pane_search_query(&mut self) {
+        if let Some(dialog) = self.pane_search_dialog_mut() {
+            dialog.query.clear();
+            dialog.groups.clear();
+            dialog.selected = 0;
+        }
+    }
+
+    pub(super) fn handle_pane_search_dialog_key(&mut self, key_event: KeyEvent) {
+        if key_event.code == KeyCode::Enter {
+            self.jump_to_selected_pane_search_hit();
+            return;
+        }
+
+        let page = self.pane_search_page_hits();
+        let Some(dialog) = self.pane_search_dialog_mut() else {
+            return;
+        };
+        let last_hit = dialog.hit_count().saturating_sub(1);
+        let ctrl_n = key_event.modifiers == Modifiers::CTRL
+            && matches!(key_event.code, KeyCode::Char('n') | KeyCode::Char('N'));
+        let ctrl_p = key_event.modifiers == Modifiers::CTRL
+            && matches!(key_event.code, KeyCode::Char('p') | KeyCode::Char('P'));
+
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => {
+                dialog.selected = dialog.selected.saturating_add(1).min(last_hit);
+            }
+            KeyCode::Up | KeyCode::BackTab => dialog.selected = dialog.selected.saturating_sub(1),
+            KeyCode::Char(_) if ctrl_n => {
+                dialog.selected = dialog.selected.saturating_add(1).min(last_hit);
+            }
+            KeyCode::Char(_) if ctrl_p => dialog.selected = dialog.selected.saturating_sub(1),
+            KeyCode::PageDown => {
+                dialog.selected = dialog.selected.saturating_add(page).min(last_hit)
+            }
+            KeyCode::PageUp => dialog.selected = dialog.selected.saturating_sub(page),
+            KeyCode::Home => dialog.selected = 0,
+            KeyCode::End => dialog.selected = last_hit,
+            KeyCode::Backspace => {
+                dialog.query.pop();
+                refresh_pane_search_hits(dialog);
+            }
+            KeyCode::Char(character)
+                if Self::allows_text_input_modifiers(key_event.modifiers)
+                    && !character.is_control() =>
+            {
+                dialog.query.push(character);
+                refresh_pane_search_hits(dialog);
+            }
+            _ => {}
+        }
+    }
+
+    fn jump_to_selected_pane_search_hit(&mut self) {
+        let Some((source, hit)) = self.pane_search_dialog().and_then(|dialog| {
+            let hit = dialog.selected_hit()?;
+            Some((dialog.sources.get(hit.source)?.clone(), hit))
+        }) else {
+            return;
+        };
+        self.close_active_dialog();
+
+        let tab_id = self
+            .workspace_tabs
+            .get(source.workspace_path.as_path())
+            .and_then(|tabs| {
+                tabs.tabs
+                    .iter()
+                    .find(|tab| tab.session_name.as_deref() == Some(source.session_name.as_str()))
+            })
+            .map(|tab| tab.id);
+        let Some(tab_id) = tab_id else {
+            self.show_info_toast(format!(
+                "session '{}' is no longer open",
+                source.session_name
+            ));
+            return;
+        };
+        if !self
+            .state
+            .select_workspace_path(source.workspace_path.as_path())
+        {
+            return;
+        }
+        if let Some(tabs) = self.workspace_tabs.get_mut(source.workspace_path.as_path()) {
+            tabs.set_active(tab_id);
+        }
+
+        self.selected_attention_item = None;
+        self.pane_search_reveal = Some(PaneSearchReveal {
+            session_name: source.session_name.clone(),
+            line: source.lines.get(hit.line).cloned().unwrap_or_default(),
+            bottom_gap: source
+                .lines
+                .len()
+                .saturating_sub(hit.line.saturating_add(1)),
+        });
+        let _ = self.focus_main_pane(FOCUS_ID_PREVIEW);
+        self.handle_workspace_selection_changed();
+        self.log_dialog_event_with_fields(
+            "pane_search",
+            "jumped",
+            [("session".to_string(), Value::from(source.session_name))],
+        );
+    }
+
+    /// Scrolls the preview to a pending search hit once its session has been captured.
+    pub(super) fn reveal_pane_search_hit(&mut self, session_name: &str) {
+        let Some(reveal) = self.pane_search_reveal.take() else {
+            return;
+        };
+        if reveal.session_name != session_name {
+            return;
+        }
+        let Some((_, preview_height)) = self.preview_output_dimensions() else {
+            return;
+        };
+
+        let total_lines = self.preview_line_count();
+        let expected_line = total_lines.saturating_sub(reveal.bottom_gap.saturating_add(1));
+        let Some(line) = (0..total_lines)
+            .filter(|index| {
+                self.preview_plain_line(*index).as_deref() == Some(reveal.line.as_str())
+            })
+            .min_by_key(|index| index.abs_diff(expected_line))
+        else {
+            return;
+        };
+
+        let mut preview_scroll = self.preview_scroll.borrow_mut();
+        preview_scroll.set_external_len(total_lines);
+        let _ = preview_scroll.visible_range(preview_height);
+        preview_scroll.scroll_to(line.saturating_sub(PANE_SEARCH_REVEAL_CONTEXT_LINES));
+        let at_bottom = preview_scroll.is_at_bottom();
+        preview_scroll.set_follow(at_bottom);
+    }
+}
+
+fn refresh_pane_search_hits(dialog: &mut PaneSearchDialogState) {
+    dialog.groups = search_panes(&dialog.sources, dialog.query.as_str());
+    dialog.selected = 0;
+}
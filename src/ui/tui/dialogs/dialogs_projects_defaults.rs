@@ -75,6 +75,7 @@ impl GroveApp {
             project.defaults.agent_env = AgentEnvDefaults {
                 claude: claude_env,
                 codex: codex_env,
+                custom: std::mem::take(&mut project.defaults.agent_env.custom),
             };
             project.name.clone()
         };
@@ -0,0 +1,171 @@
+use super::*;
+
+impl GroveApp {
+    pub(super) fn open_prompt_queue_dialog(&mut self) {
+        let Some(workspace) = self.state.selected_workspace() else {
+            self.show_info_toast("no workspace selected");
+            return;
+        };
+        let workspace_name = workspace.name.clone();
+        let workspace_path = workspace.path.clone();
+        let queue = match load_prompt_queue(workspace_path.as_path()) {
+            Ok(queue) => queue,
+            Err(error) => {
+                self.show_error_toast(format!("prompt queue unavailable: {error}"));
+                return;
+            }
+        };
+
+        let queued = queue.prompts.len();
+        self.set_prompt_queue_dialog(PromptQueueDialogState {
+            workspace_name,
+            workspace_path,
+            queue,
+            input: String::new(),
+            selected_index: 0,
+        });
+        self.log_dialog_event_with_fields(
+            "prompt_queue",
+            "dialog_opened",
+            [("queued".to_string(), Value::from(usize_to_u64(queued)))],
+        );
+    }
+
+    /// Keeps an open dialog in sync after the poller sent or paused its queue.
+    pub(super) fn refresh_prompt_queue_dialog(
+        &mut self,
+        workspace_path: &Path,
+        queue: PromptQueue,
+    ) {
+        let Some(dialog) = self.prompt_queue_dialog_mut() else {
+            return;
+        };
+        if dialog.workspace_path != workspace_path {
+            return;
+        }
+        dialog.selected_index = dialog
+            .selected_index
+            .min(queue.prompts.len().saturating_sub(1));
+        dialog.queue = queue;
+    }
+
+    pub(super) fn apply_paste_to_prompt_queue_dialog(&mut self, text: &str) -> bool {
+        let Some(dialog) = self.prompt_queue_dialog_mut() else {
+            return false;
+        };
+        dialog.input.push_str(text);
+        true
+    }
+
+    pub(super) fn handle_prompt_queue_dialog_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => {
+                let input_empty = self
+                    .prompt_queue_dialog()
+                    .is_none_or(|dialog| dialog.input.trim().is_empty());
+                if input_empty {
+                    self.close_active_dialog();
+                } else {
+                    self.enqueue_prompt_from_dialog();
+                }
+            }
+            KeyCode::Delete => {
+                self.update_prompt_queue_from_dialog("removed", |queue, selected_index| {
+                    if selected_index < queue.prompts.len() {
+                        queue.prompts.remove(selected_index);
+                    }
+                });
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                let Some(workspace_path) = self
+                    .prompt_queue_dialog()
+                    .map(|dialog| dialog.workspace_path.clone())
+                else {
+                    return;
+                };
+                self.polling.prompt_queue_sent.remove(&workspace_path);
+                self.update_prompt_queue_from_dialog("pause_toggled", |queue, _| {
+                    queue.paused = match queue.paused {
+                        Some(_) => None,
+                        None => Some("by user".to_string()),
+                    };
+                });
+            }
+            KeyCode::Up => {
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.selected_index = dialog.selected_index.saturating_sub(1);
+                }
+            }
+            KeyCode::Down => {
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.selected_index = dialog
+                        .selected_index
+                        .saturating_add(1)
+                        .min(dialog.queue.prompts.len().saturating_sub(1));
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.input.pop();
+                }
+            }
+            KeyCode::Char(character)
+                if Self::allows_text_input_modifiers(key_event.modifiers)
+                    && !character.is_control() =>
+            {
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.input.push(character);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn enqueue_prompt_from_dialog(&mut self) {
+        let Some(dialog) = self.prompt_queue_dialog() else {
+            return;
+        };
+        let workspace_path = dialog.workspace_path.clone();
+        match enqueue_prompt(workspace_path.as_path(), dialog.input.as_str()) {
+            Ok(queue) => {
+                let queued = queue.prompts.len();
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.input.clear();
+                    dialog.selected_index = queued.saturating_sub(1);
+                    dialog.queue = queue;
+                }
+                self.log_dialog_event_with_fields(
+                    "prompt_queue",
+                    "enqueued",
+                    [("queued".to_string(), Value::from(usize_to_u64(queued)))],
+                );
+            }
+            Err(error) => self.show_error_toast(format!("queue prompt failed: {error}")),
+        }
+    }
+
+    /// Applies `change` to the queue on disk, which the CLI may have changed
+    /// since the dialog opened.
+    fn update_prompt_queue_from_dialog(
+        &mut self,
+        action: &str,
+        change: impl FnOnce(&mut PromptQueue, usize),
+    ) {
+        let Some(dialog) = self.prompt_queue_dialog() else {
+            return;
+        };
+        let workspace_path = dialog.workspace_path.clone();
+        let selected_index = dialog.selected_index;
+        let result = load_prompt_queue(workspace_path.as_path()).and_then(|mut queue| {
+            change(&mut queue, selected_index);
+            save_prompt_queue(workspace_path.as_path(), &queue).map(|()| queue)
+        });
+        match result {
+            Ok(queue) => {
+                self.refresh_prompt_queue_dialog(workspace_path.as_path(), queue);
+                self.log_dialog_event("prompt_queue", action);
+            }
+            Err(error) => self.show_error_toast(format!("prompt queue update failed: {error}")),
+        }
+    }
+}
@@ -197,6 +197,8 @@ impl GroveApp {
             workspace_branch: dialog.base_branch.clone(),
             workspace_path: dialog.workspace_path,
             base_branch: dialog.base_branch,
+            strategy: UpdateStrategy::default(),
+            keep_conflicts: false,
         };
 
         if !self.tmux_input.supports_background_launch() {
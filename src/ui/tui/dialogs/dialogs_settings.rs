@@ -1,7 +1,7 @@
 use super::*;
 
 impl GroveApp {
-    fn retheme_grove_tmux_sessions(&mut self, theme: ThemeName) -> Result<(), String> {
+    fn retheme_grove_tmux_sessions(&mut self, theme: &ThemeName) -> Result<(), String> {
         let rows = self
             .tmux_input
             .list_sessions_with_tab_metadata()
@@ -19,40 +19,38 @@ impl GroveApp {
     }
 
     fn cycle_settings_theme(&mut self, next: bool) {
-        let focused_theme =
-            self.current_settings_dialog_focus_field() == Some(SettingsDialogField::Theme);
-        let Some(next_theme) = ({
-            let Some(dialog) = self.settings_dialog_mut() else {
-                return;
-            };
-            if !focused_theme {
-                return;
-            }
-
-            let next_theme = if next {
-                next_theme_name(dialog.theme)
-            } else {
-                previous_theme_name(dialog.theme)
-            };
-            dialog.theme = next_theme;
-            Some(next_theme)
-        }) else {
+        if self.current_settings_dialog_focus_field() != Some(SettingsDialogField::Theme) {
+            return;
+        }
+        let Some(theme) = self.settings_dialog().map(|dialog| &dialog.theme) else {
             return;
         };
+
+        let next_theme = if next {
+            self.themes.next(theme)
+        } else {
+            self.themes.previous(theme)
+        };
+        if let Some(dialog) = self.settings_dialog_mut() {
+            dialog.theme = next_theme.clone();
+        }
         self.theme_name = next_theme;
     }
 
     pub(super) fn cancel_settings_dialog(&mut self) {
-        let Some(initial_theme) = self.settings_dialog().map(|dialog| dialog.initial_theme) else {
+        let Some(initial_theme) = self
+            .settings_dialog()
+            .map(|dialog| dialog.initial_theme.clone())
+        else {
             return;
         };
         self.theme_name = initial_theme;
         self.close_active_dialog();
     }
 
-    fn save_theme_to_global_settings(&self, theme: ThemeName) -> Result<(), String> {
+    fn save_theme_to_global_settings(&self, theme: &ThemeName) -> Result<(), String> {
         let mut global = crate::infrastructure::config::load_global_from_path(&self.config_path)?;
-        global.theme = theme;
+        global.theme = theme.clone().into();
         crate::infrastructure::config::save_global_to_path(&self.config_path, &global)
     }
 
@@ -116,25 +114,34 @@ impl GroveApp {
         if self.modal_open() {
             return;
         }
+        // Pick up theme files added or edited since startup.
+        #[cfg(not(test))]
+        {
+            let (themes, errors) = crate::infrastructure::themes::load_theme_registry();
+            self.themes = themes;
+            if !errors.is_empty() {
+                self.show_warning_toast(format!("themes invalid: {}", errors.join("; ")));
+            }
+        }
         self.set_settings_dialog(SettingsDialogState {
-            initial_theme: self.theme_name,
-            theme: self.theme_name,
+            initial_theme: self.theme_name.clone(),
+            theme: self.theme_name.clone(),
         });
     }
 
     pub(super) fn apply_settings_dialog_save(&mut self) {
-        let Some(theme) = self.settings_dialog().map(|dialog| dialog.theme) else {
+        let Some(theme) = self.settings_dialog().map(|dialog| dialog.theme.clone()) else {
             return;
         };
 
-        if let Err(error) = self.save_theme_to_global_settings(theme) {
+        if let Err(error) = self.save_theme_to_global_settings(&theme) {
             self.show_error_toast(format!("settings save failed: {error}"));
             return;
         }
 
-        self.theme_name = theme;
+        self.theme_name = theme.clone();
         self.close_active_dialog();
-        match self.retheme_grove_tmux_sessions(theme) {
+        match self.retheme_grove_tmux_sessions(&theme) {
             Ok(()) => self.show_success_toast(format!("theme saved: {}", theme.config_key())),
             Err(error) => {
                 self.show_warning_toast(format!("theme saved, tmux retheme failed: {error}"));
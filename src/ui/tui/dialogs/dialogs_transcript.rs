@@ -0,0 +1,176 @@
+use super::*;
+
+const TRANSCRIPT_ACTION_PREFIX: &str = "transcript:";
+/// Lines kept above a search match when scrolling to it.
+const TRANSCRIPT_MATCH_CONTEXT_LINES: usize = 3;
+
+impl GroveApp {
+    pub(super) fn open_transcript_palette(&mut self) {
+        let Some(task) = self.state.selected_task().cloned() else {
+            self.show_info_toast("no task selected");
+            return;
+        };
+        let mut transcripts = list_transcripts(task.root_path.as_path());
+        if transcripts.is_empty() {
+            if self.transcripts_enabled {
+                self.show_info_toast(format!("no transcripts recorded for '{}'", task.name));
+            } else {
+                self.show_info_toast("transcripts are off, set [transcripts] enabled = true");
+            }
+            return;
+        }
+
+        let active_session = self
+            .selected_active_tab()
+            .and_then(|tab| tab.session_name.clone());
+        transcripts.sort_by_key(|transcript| {
+            (
+                active_session.as_deref() != Some(transcript.session_name.as_str()),
+                std::cmp::Reverse(transcript.modified_unix_secs),
+            )
+        });
+        let actions = transcripts
+            .iter()
+            .map(|transcript| {
+                Self::palette_action(
+                    format!("{TRANSCRIPT_ACTION_PREFIX}{}", transcript.session_name),
+                    transcript.session_name.clone(),
+                    self.transcript_action_description(transcript),
+                    &[],
+                    task.name.clone(),
+                )
+            })
+            .collect();
+        self.open_shared_palette(PaletteMode::Transcript, actions);
+    }
+
+    fn transcript_action_description(&self, transcript: &TranscriptFile) -> String {
+        let size_kib = transcript.size_bytes.div_ceil(1024);
+        match transcript.modified_unix_secs {
+            Some(modified) => format!(
+                "{size_kib} KiB, updated {} ago",
+                self.relative_age_label(Some(modified))
+            ),
+            None => format!("{size_kib} KiB"),
+        }
+    }
+
+    pub(super) fn execute_transcript_action(&mut self, id: &str) -> bool {
+        let Some(session_name) = id.strip_prefix(TRANSCRIPT_ACTION_PREFIX) else {
+            return false;
+        };
+        let Some(task) = self.state.selected_task().cloned() else {
+            return false;
+        };
+        let Some(transcript) = list_transcripts(task.root_path.as_path())
+            .into_iter()
+            .find(|transcript| transcript.session_name == session_name)
+        else {
+            self.show_error_toast(format!("transcript '{session_name}' no longer exists"));
+            return false;
+        };
+        let raw = match read_transcript(transcript.path.as_path()) {
+            Ok(raw) => raw,
+            Err(error) => {
+                self.show_error_toast(error);
+                return false;
+            }
+        };
+
+        let lines = transcript_lines(raw.as_str(), false);
+        let scroll = lines.len().saturating_sub(self.transcript_page_lines());
+        self.set_transcript_dialog(TranscriptDialogState {
+            task_name: task.name.clone(),
+            session_name: transcript.session_name,
+            lines,
+            scroll,
+            ..TranscriptDialogState::default()
+        });
+        self.log_dialog_event_with_fields(
+            "transcript",
+            "dialog_opened",
+            [("task".to_string(), Value::from(task.name))],
+        );
+        false
+    }
+
+    pub(super) fn transcript_page_lines(&self) -> usize {
+        usize::from(self.viewport_height.saturating_sub(10)).max(1)
+    }
+
+    pub(super) fn clear_transcript_query(&mut self) {
+        if let Some(dialog) = self.transcript_dialog_mut() {
+            dialog.query.clear();
+            dialog.matches.clear();
+            dialog.selected_match = 0;
+        }
+    }
+
+    pub(super) fn handle_transcript_dialog_key(&mut self, key_event: KeyEvent) {
+        let page = self.transcript_page_lines();
+        let Some(dialog) = self.transcript_dialog_mut() else {
+            return;
+        };
+        let last_line = dialog.lines.len().saturating_sub(1);
+        let ctrl_n = key_event.modifiers == Modifiers::CTRL
+            && matches!(key_event.code, KeyCode::Char('n') | KeyCode::Char('N'));
+        let ctrl_p = key_event.modifiers == Modifiers::CTRL
+            && matches!(key_event.code, KeyCode::Char('p') | KeyCode::Char('P'));
+
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Down | KeyCode::Tab if !dialog.matches.is_empty() => {
+                dialog.selected_match = (dialog.selected_match + 1) % dialog.matches.len();
+                scroll_to_selected_match(dialog);
+            }
+            KeyCode::Up | KeyCode::BackTab if !dialog.matches.is_empty() => {
+                dialog.selected_match = dialog
+                    .selected_match
+                    .checked_sub(1)
+                    .unwrap_or(dialog.matches.len() - 1);
+                scroll_to_selected_match(dialog);
+            }
+            KeyCode::Char(_) if ctrl_n && !dialog.matches.is_empty() => {
+                dialog.selected_match = (dialog.selected_match + 1) % dialog.matches.len();
+                scroll_to_selected_match(dialog);
+            }
+            KeyCode::Char(_) if ctrl_p && !dialog.matches.is_empty() => {
+                dialog.selected_match = dialog
+                    .selected_match
+                    .checked_sub(1)
+                    .unwrap_or(dialog.matches.len() - 1);
+                scroll_to_selected_match(dialog);
+            }
+            KeyCode::Down => dialog.scroll = dialog.scroll.saturating_add(1).min(last_line),
+            KeyCode::Up => dialog.scroll = dialog.scroll.saturating_sub(1),
+            KeyCode::PageDown => dialog.scroll = dialog.scroll.saturating_add(page).min(last_line),
+            KeyCode::PageUp => dialog.scroll = dialog.scroll.saturating_sub(page),
+            KeyCode::Home => dialog.scroll = 0,
+            KeyCode::End => dialog.scroll = dialog.lines.len().saturating_sub(page),
+            KeyCode::Backspace => {
+                dialog.query.pop();
+                refresh_transcript_matches(dialog);
+            }
+            KeyCode::Char(character)
+                if Self::allows_text_input_modifiers(key_event.modifiers)
+                    && !character.is_control() =>
+            {
+                dialog.query.push(character);
+                refresh_transcript_matches(dialog);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn refresh_transcript_matches(dialog: &mut TranscriptDialogState) {
+    dialog.matches = search_transcript_lines(&dialog.lines, dialog.query.as_str());
+    // Searching starts from the newest output, like scrolling back through a pane.
+    dialog.selected_match = dialog.matches.len().saturating_sub(1);
+    scroll_to_selected_match(dialog);
+}
+
+fn scroll_to_selected_match(dialog: &mut TranscriptDialogState) {
+    if let Some(line) = dialog.selected_line() {
+        dialog.scroll = line.saturating_sub(TRANSCRIPT_MATCH_CONTEXT_LINES);
+    }
+}
@@ -37,6 +37,11 @@ impl GroveApp {
 
         match key_event.code {
             KeyCode::Enter => match focused_field {
+                UpdateFromBaseDialogField::Strategy => {
+                    if let Some(dialog) = self.update_from_base_dialog_mut() {
+                        dialog.strategy = dialog.strategy.next();
+                    }
+                }
                 UpdateFromBaseDialogField::UpdateButton => {
                     confirm_update = true;
                 }
@@ -62,6 +67,17 @@ impl GroveApp {
             KeyCode::Down | KeyCode::Char('j') if no_modifiers => {
                 self.focus_next_dialog_field();
             }
+            KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Char('h')
+            | KeyCode::Char('l')
+            | KeyCode::Char(' ')
+                if no_modifiers && focused_field == UpdateFromBaseDialogField::Strategy =>
+            {
+                if let Some(dialog) = self.update_from_base_dialog_mut() {
+                    dialog.strategy = dialog.strategy.next();
+                }
+            }
             KeyCode::Char(character) if no_modifiers => {
                 if (focused_field == UpdateFromBaseDialogField::UpdateButton
                     || focused_field == UpdateFromBaseDialogField::CancelButton)
@@ -119,6 +135,15 @@ impl GroveApp {
             base_branch
         };
 
+        let (strategy, keep_conflicts) = self
+            .project_for_workspace(&workspace)
+            .map(|project| {
+                (
+                    project.defaults.update_strategy,
+                    project.defaults.keep_conflicts,
+                )
+            })
+            .unwrap_or_default();
         self.set_update_from_base_dialog(UpdateFromBaseDialogState {
             task_slug: workspace.task_slug.clone(),
             project_name: workspace.project_name.clone(),
@@ -128,6 +153,8 @@ impl GroveApp {
             workspace_branch: workspace.branch.clone(),
             workspace_path: workspace.path.clone(),
             base_branch: base_branch.clone(),
+            strategy,
+            keep_conflicts,
         });
         self.log_dialog_event_with_fields(
             "update_from_base",
@@ -180,6 +207,7 @@ impl GroveApp {
                     "is_main_workspace".to_string(),
                     Value::from(dialog.is_main_workspace),
                 ),
+                ("strategy".to_string(), Value::from(dialog.strategy.label())),
             ],
         );
 
@@ -195,11 +223,19 @@ impl GroveApp {
             workspace_branch: dialog.workspace_branch,
             workspace_path: dialog.workspace_path,
             base_branch: dialog.base_branch,
+            strategy: dialog.strategy,
+            keep_conflicts: dialog.keep_conflicts,
         };
+        let keep_conflicts = request.keep_conflicts;
 
         if !self.tmux_input.supports_background_launch() {
             let (result, warnings) =
                 update_workspace_from_base_with_terminator(request, &RuntimeSessionTerminator);
+            let conflict = Self::conflict_left_in_place(
+                &result,
+                keep_conflicts,
+                std::slice::from_ref(&workspace_path),
+            );
             self.apply_update_from_base_completion(UpdateWorkspaceFromBaseCompletion {
                 workspace_name,
                 workspace_path,
@@ -207,6 +243,7 @@ impl GroveApp {
                 base_branch,
                 result,
                 warnings,
+                conflict,
             });
             return;
         }
@@ -215,6 +252,11 @@ impl GroveApp {
         self.queue_cmd(Cmd::task(move || {
             let (result, warnings) =
                 update_workspace_from_base_with_terminator(request, &RuntimeSessionTerminator);
+            let conflict = Self::conflict_left_in_place(
+                &result,
+                keep_conflicts,
+                std::slice::from_ref(&workspace_path),
+            );
             Msg::UpdateWorkspaceFromBaseCompleted(UpdateWorkspaceFromBaseCompletion {
                 workspace_name,
                 workspace_path,
@@ -222,6 +264,7 @@ impl GroveApp {
                 base_branch,
                 result,
                 warnings,
+                conflict,
             })
         }));
     }
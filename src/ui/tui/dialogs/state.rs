@@ -1,5 +1,6 @@
 use super::*;
 use crate::domain::{PermissionMode, Worktree};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct StartAgentConfigState {
@@ -15,6 +16,8 @@ pub(super) struct StartOptions {
     pub(super) prompt: Option<String>,
     pub(super) init_command: Option<String>,
     pub(super) permission_mode: PermissionMode,
+    /// Saved conversation to resume, from a restored task archive.
+    pub(super) resume_command: Option<String>,
 }
 
 impl StartAgentConfigState {
@@ -42,6 +45,7 @@ impl StartAgentConfigState {
             prompt: trimmed_nonempty(&self.prompt),
             init_command: trimmed_nonempty(&self.init_command),
             permission_mode: self.permission_mode,
+            resume_command: None,
         }
     }
 
@@ -72,7 +76,7 @@ impl StartAgentConfigState {
         }
     }
 
-    pub(super) fn cycle_permission_mode(&mut self, agent: AgentType) {
+    pub(super) fn cycle_permission_mode(&mut self, agent: &AgentType) {
         self.permission_mode = self.permission_mode.next_for_agent(agent);
     }
 }
@@ -104,6 +108,13 @@ pub(super) enum ConfirmDialogAction {
         tab_id: u64,
         session_name: String,
     },
+    ArchiveTask {
+        task: Task,
+    },
+    ResumeArchivedAgents {
+        task_name: String,
+        tabs: Vec<(PathBuf, ArchivedTab)>,
+    },
     QuitApp,
 }
 
@@ -166,6 +177,8 @@ pub(super) struct MergeDialogState {
     pub(super) workspace_branch: String,
     pub(super) workspace_path: PathBuf,
     pub(super) base_branch: String,
+    pub(super) strategy: MergeStrategy,
+    pub(super) keep_conflicts: bool,
     pub(super) cleanup_workspace: bool,
     pub(super) cleanup_local_branch: bool,
 }
@@ -180,6 +193,8 @@ pub(super) struct UpdateFromBaseDialogState {
     pub(super) workspace_branch: String,
     pub(super) workspace_path: PathBuf,
     pub(super) base_branch: String,
+    pub(super) strategy: UpdateStrategy,
+    pub(super) keep_conflicts: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -214,12 +229,14 @@ pub(super) enum SessionCleanupDialogField {
 pub(super) enum MergeDialogField {
     CleanupWorkspace,
     CleanupLocalBranch,
+    Strategy,
     MergeButton,
     CancelButton,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum UpdateFromBaseDialogField {
+    Strategy,
     UpdateButton,
     CancelButton,
 }
@@ -328,6 +345,10 @@ pub(super) struct CreateDialogState {
     pub(super) project_index: usize,
     pub(super) selected_repository_indices: Vec<usize>,
     pub(super) project_picker: Option<CreateProjectPickerState>,
+    pub(super) recipes: Vec<Recipe>,
+    pub(super) recipe_index: Option<usize>,
+    /// `name=value` pairs separated by `;`.
+    pub(super) recipe_variables: String,
 }
 
 impl CreateDialogState {
@@ -342,6 +363,67 @@ impl CreateDialogState {
         }
     }
 
+    pub(super) fn selected_recipe(&self) -> Option<&Recipe> {
+        self.recipe_index.and_then(|index| self.recipes.get(index))
+    }
+
+    pub(super) fn shows_recipe_fields(&self) -> bool {
+        !self.is_add_worktree_mode() && !self.register_as_base && !self.recipes.is_empty()
+    }
+
+    /// Steps through "no recipe" and every loaded recipe, prefilling the
+    /// variables field with the new recipe's defaults.
+    pub(super) fn cycle_recipe(&mut self, forward: bool) {
+        let count = self.recipes.len();
+        if count == 0 {
+            return;
+        }
+        self.recipe_index = match (self.recipe_index, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(index), true) if index + 1 < count => Some(index + 1),
+            (Some(index), false) if index > 0 => Some(index - 1),
+            (Some(_), _) => None,
+        };
+        self.recipe_variables = self
+            .selected_recipe()
+            .map(|recipe| {
+                recipe
+                    .variables
+                    .iter()
+                    .map(|variable| {
+                        format!(
+                            "{}={}",
+                            variable.name,
+                            variable.default.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            })
+            .unwrap_or_default();
+    }
+
+    /// Parses the variables field, leaving empty values to recipe defaults.
+    pub(super) fn recipe_variable_values(&self) -> Result<BTreeMap<String, String>, String> {
+        let mut values = BTreeMap::new();
+        for pair in self
+            .recipe_variables
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let Some((name, value)) = pair.split_once('=') else {
+                return Err(format!("recipe variable '{pair}' needs name=value"));
+            };
+            let value = value.trim();
+            if !value.is_empty() {
+                values.insert(name.trim().to_string(), value.to_string());
+            }
+        }
+        Ok(values)
+    }
+
     pub(super) fn first_field(&self) -> CreateDialogField {
         if self.is_add_worktree_mode() {
             return CreateDialogField::Project;
@@ -408,6 +490,8 @@ pub(super) enum CreateDialogField {
     RegisterAsBase,
     PullRequestUrl,
     Project,
+    Recipe,
+    RecipeVariables,
     CreateButton,
     CancelButton,
 }
@@ -470,6 +554,8 @@ impl CreateDialogField {
             Self::RegisterAsBase => "register_as_base",
             Self::PullRequestUrl => "pr_url",
             Self::Project => "project",
+            Self::Recipe => "recipe",
+            Self::RecipeVariables => "recipe_variables",
             Self::CreateButton => "create",
             Self::CancelButton => "cancel",
         }
@@ -586,6 +672,95 @@ pub(super) struct SettingsDialogState {
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub(super) struct PerformanceDialogState;
 
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct TranscriptDialogState {
+    pub(super) task_name: String,
+    pub(super) session_name: String,
+    pub(super) lines: Vec<String>,
+    pub(super) query: String,
+    /// Indexes into `lines` that contain `query`.
+    pub(super) matches: Vec<usize>,
+    pub(super) selected_match: usize,
+    /// First visible line.
+    pub(super) scroll: usize,
+}
+
+impl TranscriptDialogState {
+    pub(super) fn selected_line(&self) -> Option<usize> {
+        self.matches.get(self.selected_match).copied()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct PaneSearchDialogState {
+    pub(super) query: String,
+    /// Panes captured when the dialog opened.
+    pub(super) sources: Vec<PaneSearchSource>,
+    pub(super) groups: Vec<PaneSearchGroup>,
+    /// Index into the hits of all groups, in display order.
+    pub(super) selected: usize,
+}
+
+impl PaneSearchDialogState {
+    pub(super) fn hits(&self) -> impl Iterator<Item = &PaneSearchHit> {
+        self.groups.iter().flat_map(|group| group.hits.iter())
+    }
+
+    pub(super) fn hit_count(&self) -> usize {
+        self.groups.iter().map(|group| group.hits.len()).sum()
+    }
+
+    pub(super) fn selected_hit(&self) -> Option<PaneSearchHit> {
+        self.hits().nth(self.selected).copied()
+    }
+}
+
+/// Preview line to scroll to once the jumped-to session is captured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PaneSearchReveal {
+    pub(super) session_name: String,
+    pub(super) line: String,
+    /// Lines below the hit when it was found.
+    pub(super) bottom_gap: usize,
+}
+
+/// Outcome of sending the broadcast prompt to one target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BroadcastDelivery {
+    Sent,
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BroadcastTarget {
+    pub(super) workspace_name: String,
+    pub(super) workspace_path: PathBuf,
+    /// Running agent session, if the workspace has one.
+    pub(super) session_name: Option<String>,
+    pub(super) status: WorkspaceStatus,
+    pub(super) delivery: Option<BroadcastDelivery>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct BroadcastDialogState {
+    pub(super) prompt: String,
+    /// Sends to agents that are busy too, not only Waiting or Idle ones.
+    pub(super) force: bool,
+    pub(super) targets: Vec<BroadcastTarget>,
+    /// Set once the current delivery results belong to a sent prompt.
+    pub(super) sent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PromptQueueDialogState {
+    pub(super) workspace_name: String,
+    pub(super) workspace_path: PathBuf,
+    pub(super) queue: PromptQueue,
+    pub(super) input: String,
+    pub(super) selected_index: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum SettingsDialogField {
     Theme,
@@ -44,17 +44,16 @@ impl HelpCatalogEntry {
     }
 }
 
-fn format_help_hint(hint: &HelpHintSpec) -> String {
-    format!("{} {}", hint.key, hint.action)
-}
-
 impl GroveApp {
+    fn format_help_hint(&self, command: UiCommand, hint: &HelpHintSpec) -> String {
+        match self.keymap.key_label(command) {
+            Some(keys) => format!("{keys} {}", hint.action),
+            None => format!("{} {}", hint.key, hint.action),
+        }
+    }
+
     fn command_help_labels(&self, context: HelpHintContext) -> Vec<String> {
-        UiCommand::help_hints_for(context)
-            .iter()
-            .filter_map(|command| command.help_hint(context))
-            .map(format_help_hint)
-            .collect()
+        self.command_help_labels_for(context, &UiCommand::help_hints_for(context))
     }
 
     fn command_help_labels_for(
@@ -64,8 +63,10 @@ impl GroveApp {
     ) -> Vec<String> {
         commands
             .iter()
-            .filter_map(|command| command.help_hint(context))
-            .map(format_help_hint)
+            .filter_map(|command| {
+                let hint = command.help_hint(context)?;
+                Some(self.format_help_hint(*command, hint))
+            })
             .collect()
     }
 
@@ -81,7 +82,6 @@ impl GroveApp {
     pub(super) fn help_catalog_entries(&self) -> Vec<HelpCatalogEntry> {
         let global = self.command_help_labels(HelpHintContext::Global);
         let workspace = self.command_help_labels(HelpHintContext::Workspace);
-        let list = self.command_help_labels(HelpHintContext::List);
         let preview_agent = self.command_help_labels(HelpHintContext::PreviewAgent);
         let preview_shell = self.command_help_labels(HelpHintContext::PreviewShell);
         let preview_git = self.command_help_labels(HelpHintContext::PreviewGit);
@@ -143,9 +143,39 @@ impl GroveApp {
                 "Move",
                 format!(
                     "{}, i focus needs you inbox, a acknowledge attention item",
-                    list.join(", ")
+                    self.command_help_labels_for(
+                        HelpHintContext::List,
+                        &[UiCommand::MoveSelectionDown, UiCommand::ReorderTasks],
+                    )
+                    .join(", ")
+                ),
+            ),
+            HelpCatalogEntry::new(
+                HelpSection::List,
+                "Broadcast",
+                self.command_help_labels_for(
+                    HelpHintContext::List,
+                    &[UiCommand::ToggleBroadcastTarget, UiCommand::OpenBroadcast],
+                )
+                .join(", "),
+            ),
+            HelpCatalogEntry::new(
+                HelpSection::List,
+                "Queue",
+                format!(
+                    "{}, sent when the agent is back at its input",
+                    self.command_help_labels_for(
+                        HelpHintContext::List,
+                        &[UiCommand::OpenPromptQueue],
+                    )
+                    .join(", ")
                 ),
             ),
+            HelpCatalogEntry::new(
+                HelpSection::Preview,
+                "Home tab",
+                "Task dashboard, 1-9 jump to worktree, tab or PR",
+            ),
             HelpCatalogEntry::new(HelpSection::Preview, "Agent tab", preview_agent.join(", ")),
             HelpCatalogEntry::new(HelpSection::Preview, "Shell tab", preview_shell.join(", ")),
             HelpCatalogEntry::new(HelpSection::Preview, "Git tab", preview_git.join(", ")),
@@ -170,7 +200,7 @@ impl GroveApp {
             HelpCatalogEntry::new(
                 HelpSection::Modals,
                 "Create",
-                "Tab/S-Tab/C-n/C-p fields, click mode tabs or Alt+[/Alt+], Enter browse projects, picker supports filter + Up/Down + Space toggle, base branch comes from Project Defaults or git, Enter/Esc",
+                "Tab/S-Tab/C-n/C-p fields, click mode tabs or Alt+[/Alt+], Enter browse projects, picker supports filter + Up/Down + Space toggle, Left/Right pick a recipe and fill its Vars, base branch comes from Project Defaults, the recipe or git, Enter/Esc",
             ),
             HelpCatalogEntry::new(
                 HelpSection::Modals,
@@ -87,6 +87,8 @@ impl GroveApp {
             Msg::RefreshWorkspacesCompleted(_) => "refresh_workspaces_completed",
             Msg::DeleteProjectCompleted(_) => "delete_project_completed",
             Msg::DeleteWorkspaceCompleted(_) => "delete_workspace_completed",
+            Msg::TaskArchived(_) => "task_archived",
+            Msg::TaskRestored(_) => "task_restored",
             Msg::MergeWorkspaceCompleted(_) => "merge_workspace_completed",
             Msg::UpdateWorkspaceFromBaseCompleted(_) => "update_workspace_from_base_completed",
             Msg::PullUpstreamCompleted(_) => "pull_upstream_completed",
@@ -97,6 +99,12 @@ impl GroveApp {
             Msg::InteractiveSendCompleted(_) => "interactive_send_completed",
             Msg::DiffCaptureCompleted(_) => "diff_capture_completed",
             Msg::DiffStatCompleted(_) => "diff_stat_completed",
+            Msg::WorkspaceConflictActionCompleted(_) => "workspace_conflict_action_completed",
+            Msg::PullRequestsSynced(_) => "pull_requests_synced",
+            Msg::HomeDashboardPolled(_) => "home_dashboard_polled",
+            Msg::UsagePolled(_) => "usage_polled",
+            Msg::NotificationsDelivered(_) => "notifications_delivered",
+            Msg::HooksCompleted(_) => "hooks_completed",
             Msg::Noop => "noop",
         }
     }
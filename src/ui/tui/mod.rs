@@ -15,12 +15,16 @@ mod app;
 mod commands;
 #[path = "commands/help.rs"]
 mod commands_hints;
+#[path = "commands/keymap.rs"]
+mod commands_keymap;
 #[path = "commands/meta.rs"]
 mod commands_meta;
 #[path = "commands/palette.rs"]
 mod commands_palette;
 #[path = "dialogs/dialogs.rs"]
 mod dialogs;
+#[path = "dialogs/dialogs_broadcast.rs"]
+mod dialogs_broadcast;
 #[path = "dialogs/dialogs_confirm.rs"]
 mod dialogs_confirm;
 #[path = "dialogs/dialogs_create_key.rs"]
@@ -35,6 +39,8 @@ mod dialogs_edit;
 mod dialogs_launch;
 #[path = "dialogs/dialogs_merge.rs"]
 mod dialogs_merge;
+#[path = "dialogs/dialogs_pane_search.rs"]
+mod dialogs_pane_search;
 #[path = "dialogs/dialogs_performance.rs"]
 mod dialogs_performance;
 #[path = "dialogs/dialogs_projects_crud.rs"]
@@ -47,6 +53,8 @@ mod dialogs_projects_key;
 mod dialogs_projects_search;
 #[path = "dialogs/dialogs_projects_state.rs"]
 mod dialogs_projects_state;
+#[path = "dialogs/dialogs_prompt_queue.rs"]
+mod dialogs_prompt_queue;
 #[path = "dialogs/dialogs_pull_upstream.rs"]
 mod dialogs_pull_upstream;
 #[path = "dialogs/dialogs_rename_tab.rs"]
@@ -59,6 +67,8 @@ mod dialogs_settings;
 mod dialogs_state;
 #[path = "dialogs/dialogs_stop.rs"]
 mod dialogs_stop;
+#[path = "dialogs/dialogs_transcript.rs"]
+mod dialogs_transcript;
 #[path = "dialogs/dialogs_update_from_base.rs"]
 mod dialogs_update_from_base;
 #[path = "help_catalog.rs"]
@@ -95,16 +105,24 @@ mod update_input_key_events;
 mod update_input_keybinding;
 #[path = "update/update_input_mouse.rs"]
 mod update_input_mouse;
+#[path = "update/update_lifecycle_archive.rs"]
+mod update_lifecycle_archive;
 #[path = "update/update_lifecycle_create.rs"]
 mod update_lifecycle_create;
+#[path = "update/update_lifecycle_hooks.rs"]
+mod update_lifecycle_hooks;
 #[path = "update/update_lifecycle_start.rs"]
 mod update_lifecycle_start;
 #[path = "update/update_lifecycle_stop.rs"]
 mod update_lifecycle_stop;
 #[path = "update/update_lifecycle_workspace_completion.rs"]
 mod update_lifecycle_workspace_completion;
+#[path = "update/update_lifecycle_workspace_conflict.rs"]
+mod update_lifecycle_workspace_conflict;
 #[path = "update/update_lifecycle_workspace_refresh.rs"]
 mod update_lifecycle_workspace_refresh;
+#[path = "update/update_navigation_command_tabs.rs"]
+mod update_navigation_command_tabs;
 #[path = "update/update_navigation_commands.rs"]
 mod update_navigation_commands;
 #[path = "update/update_navigation_palette.rs"]
@@ -125,8 +143,22 @@ mod update_polling_capture_live;
 mod update_polling_capture_task;
 #[path = "update/update_polling_capture_workspace.rs"]
 mod update_polling_capture_workspace;
+#[path = "update/update_polling_command_tabs.rs"]
+mod update_polling_command_tabs;
+#[path = "update/update_polling_history.rs"]
+mod update_polling_history;
+#[path = "update/update_polling_home.rs"]
+mod update_polling_home;
+#[path = "update/update_polling_notifications.rs"]
+mod update_polling_notifications;
+#[path = "update/update_polling_prompt_queue.rs"]
+mod update_polling_prompt_queue;
+#[path = "update/update_polling_pull_requests.rs"]
+mod update_polling_pull_requests;
 #[path = "update/update_polling_state.rs"]
 mod update_polling_state;
+#[path = "update/update_polling_usage.rs"]
+mod update_polling_usage;
 #[path = "update/prelude.rs"]
 mod update_prelude;
 #[path = "update/update_tick.rs"]
@@ -143,6 +175,8 @@ mod view_chrome_shared;
 mod view_chrome_sidebar;
 #[path = "view/view_layout.rs"]
 mod view_layout;
+#[path = "view/view_overlays_broadcast.rs"]
+mod view_overlays_broadcast;
 #[path = "view/view_overlays_confirm.rs"]
 mod view_overlays_confirm;
 #[path = "view/view_overlays_create.rs"]
@@ -151,10 +185,14 @@ mod view_overlays_create;
 mod view_overlays_edit;
 #[path = "view/view_overlays_help.rs"]
 mod view_overlays_help;
+#[path = "view/view_overlays_pane_search.rs"]
+mod view_overlays_pane_search;
 #[path = "view/view_overlays_performance.rs"]
 mod view_overlays_performance;
 #[path = "view/view_overlays_projects.rs"]
 mod view_overlays_projects;
+#[path = "view/view_overlays_prompt_queue.rs"]
+mod view_overlays_prompt_queue;
 #[path = "view/view_overlays_pull_upstream.rs"]
 mod view_overlays_pull_upstream;
 #[path = "view/view_overlays_rename_tab.rs"]
@@ -163,6 +201,8 @@ mod view_overlays_rename_tab;
 mod view_overlays_session_cleanup;
 #[path = "view/view_overlays_settings.rs"]
 mod view_overlays_settings;
+#[path = "view/view_overlays_transcript.rs"]
+mod view_overlays_transcript;
 #[path = "view/view_overlays_workspace_delete.rs"]
 mod view_overlays_workspace_delete;
 #[path = "view/view_overlays_workspace_launch.rs"]
@@ -179,6 +219,8 @@ mod view_prelude;
 mod view_preview;
 #[path = "view/view_preview_content.rs"]
 mod view_preview_content;
+#[path = "view/view_preview_home.rs"]
+mod view_preview_home;
 #[path = "view/view_preview_shell.rs"]
 mod view_preview_shell;
 #[path = "view/view_selection_interaction.rs"]
@@ -228,43 +270,55 @@ mod tests {
     };
     use self::support::logging::{RecordedEvents, RecordingEventLogger};
     use super::{
-        AppDependencies, AttentionItem, AttentionReason, ClipboardAccess, CommandTmuxInput,
-        CreateDialogField, CreateDialogMode, CreateDialogState, CreateDialogTab,
-        CreateWorkspaceCompletion, CreateWorkspaceRequest, CreateWorkspaceResult, CursorCapture,
-        DeleteDialogField, DeleteProjectCompletion, DeleteWorkspaceCompletion, EditDialogField,
-        FOCUS_ID_CONFIRM_CANCEL_BUTTON, FOCUS_ID_CONFIRM_CONFIRM_BUTTON, FOCUS_ID_PREVIEW,
-        FOCUS_ID_PROJECT_ADD_CANCEL_BUTTON, FOCUS_ID_PROJECT_ADD_NAME_INPUT,
-        FOCUS_ID_PROJECT_ADD_PATH_INPUT, FOCUS_ID_PROJECT_DEFAULTS_BASE_BRANCH_INPUT,
-        FOCUS_ID_PROJECT_DEFAULTS_CANCEL_BUTTON, FOCUS_ID_PROJECT_DEFAULTS_CODEX_ENV_INPUT,
-        FOCUS_ID_PROJECT_DEFAULTS_INIT_COMMAND_INPUT, FOCUS_ID_PROJECT_DIALOG_FILTER_INPUT,
-        FOCUS_ID_WORKSPACE_LIST, GroveApp, HIT_ID_CREATE_DIALOG_TAB, HIT_ID_HEADER, HIT_ID_PREVIEW,
-        HIT_ID_PROJECT_ADD_RESULTS_LIST, HIT_ID_PROJECT_DIALOG_LIST, HIT_ID_STATUS,
-        HIT_ID_WORKSPACE_LIST, HIT_ID_WORKSPACE_PR_LINK, HIT_ID_WORKSPACE_ROW, HelpHintContext,
-        LaunchDialogState, LaunchDialogTarget, LazygitLaunchCompletion, LivePreviewCapture,
+        AppDependencies, AttentionItem, AttentionReason, BranchDivergence, BroadcastDelivery,
+        ClipboardAccess, CommandTmuxInput, CreateDialogField, CreateDialogMode, CreateDialogState,
+        CreateDialogTab, CreateWorkspaceCompletion, CreateWorkspaceRequest, CreateWorkspaceResult,
+        CursorCapture, DeleteDialogField, DeleteProjectCompletion, DeleteWorkspaceCompletion,
+        DiffStatCompletion, EditDialogField, FOCUS_ID_CONFIRM_CANCEL_BUTTON,
+        FOCUS_ID_CONFIRM_CONFIRM_BUTTON, FOCUS_ID_PREVIEW, FOCUS_ID_PROJECT_ADD_CANCEL_BUTTON,
+        FOCUS_ID_PROJECT_ADD_NAME_INPUT, FOCUS_ID_PROJECT_ADD_PATH_INPUT,
+        FOCUS_ID_PROJECT_DEFAULTS_BASE_BRANCH_INPUT, FOCUS_ID_PROJECT_DEFAULTS_CANCEL_BUTTON,
+        FOCUS_ID_PROJECT_DEFAULTS_CODEX_ENV_INPUT, FOCUS_ID_PROJECT_DEFAULTS_INIT_COMMAND_INPUT,
+        FOCUS_ID_PROJECT_DIALOG_FILTER_INPUT, FOCUS_ID_WORKSPACE_LIST, GroveApp,
+        HIT_ID_CREATE_DIALOG_TAB, HIT_ID_HEADER, HIT_ID_PREVIEW, HIT_ID_PROJECT_ADD_RESULTS_LIST,
+        HIT_ID_PROJECT_DIALOG_LIST, HIT_ID_STATUS, HIT_ID_WORKSPACE_LIST, HIT_ID_WORKSPACE_PR_LINK,
+        HIT_ID_WORKSPACE_ROW, HelpHintContext, HomeDashboardCompletion, HomeDashboardWorktreeStats,
+        Keymap, LaunchDialogState, LaunchDialogTarget, LazygitLaunchCompletion, LivePreviewCapture,
         MergeDialogField, MergeWorkspaceCompletion, Msg, PREVIEW_METADATA_ROWS, PaletteMode,
         PendingResizeVerification, PreviewPollCompletion, PreviewSessionGeometry,
         PreviewStreamConnected, PreviewStreamDisconnected, PreviewStreamEvent, PreviewStreamOutput,
         PreviewStreamSource, PreviewTab, ProjectAddDialogField, ProjectDefaultsDialogField,
         PullUpstreamDialogField, RefreshWorkspacesCompletion, SettingsDialogField,
         StartAgentCompletion, StartAgentConfigState, StopAgentCompletion, StopDialogField,
-        TextSelectionPoint, TmuxInput, UiCommand, UpdateFromBaseDialogField, WorkspaceAttention,
+        TaskArchiveCompletion, TaskRestoreCompletion, TextSelectionPoint, TmuxInput, UiCommand,
+        UpdateFromBaseDialogField, UsagePollCompletion, WorkspaceAttention,
         WorkspaceShellLaunchCompletion, WorkspaceStatusCapture, WorkspaceTab, WorkspaceTabKind,
         WorkspaceTabRuntimeState, decode_create_dialog_tab_hit_data, decode_workspace_pr_hit_data,
-        packed, parse_cursor_metadata, ui_theme, ui_theme_for, usize_to_u64,
+        packed, parse_cursor_metadata, theme_display_name, ui_theme, ui_theme_for, usize_to_u64,
     };
     use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+    use crate::application::agent_runtime::usage::{TokenUsage, UsageTotals};
     use crate::application::agent_runtime::workspace_status_targets_for_polling_with_live_preview;
     use crate::application::interactive::InteractiveState;
+    use crate::application::notifications::NotificationDispatcher;
+    use crate::application::pull_request_sync::FakePullRequestForge;
     use crate::application::task_lifecycle::{
-        CreateTaskRequest, CreateTaskResult, TaskBranchSource,
+        ArchivedTab, CreateTaskRequest, CreateTaskResult, TaskBranchSource,
     };
+    use crate::application::workspace_lifecycle::{IntegrationOperation, WorkspaceConflict};
     use crate::domain::{
-        AgentType, PermissionMode, PullRequest, PullRequestStatus, Task, Workspace,
-        WorkspaceStatus, Worktree,
+        AgentRegistry, AgentType, MergeStrategy, PermissionMode, PortRange, PullRequest,
+        PullRequestChecks, PullRequestStatus, Task, UpdateStrategy, Workspace, WorkspaceStatus,
+        Worktree,
     };
     use crate::infrastructure::adapters::DiscoveryState;
-    use crate::infrastructure::config::{ProjectConfig, ProjectDefaults, ThemeName};
+    use crate::infrastructure::config::{
+        HookConfig, HookEvent, KeybindingSequences, KeybindingsConfig, NotificationReason,
+        NotificationSinkConfig, NotificationSinkKind, NotificationsConfig, ProjectConfig,
+        ProjectDefaults, ThemeKey, ThemeName,
+    };
     use crate::infrastructure::event_log::{Event as LoggedEvent, NullEventLogger};
+    use crate::infrastructure::state_store::StateStore;
     use crate::ui::state::{Action, UiMode, reduce};
     use ftui::core::event::{
         Event, KeyCode, KeyEvent, KeyEventKind, Modifiers, MouseButton, MouseEvent, MouseEventKind,
@@ -279,6 +333,7 @@ mod tests {
     use proptest::prelude::*;
     use serde_json::Value;
     use std::cell::RefCell;
+    use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
     use std::rc::Rc;
@@ -849,6 +904,26 @@ mod tests {
         KeyEvent::new(code).with_kind(KeyEventKind::Press)
     }
 
+    fn keymap_from(leader: Option<&str>, bindings: &[(&str, &[&str])]) -> Result<Keymap, String> {
+        Keymap::from_config(&KeybindingsConfig {
+            leader: leader.map(str::to_string),
+            commands: bindings
+                .iter()
+                .map(|(id, sequences)| {
+                    (
+                        id.to_string(),
+                        KeybindingSequences::Many(
+                            sequences
+                                .iter()
+                                .map(|sequence| sequence.to_string())
+                                .collect(),
+                        ),
+                    )
+                })
+                .collect::<BTreeMap<String, KeybindingSequences>>(),
+        })
+    }
+
     fn preview_output_height(app: &GroveApp) -> usize {
         app.preview_output_dimensions()
             .map_or(1, |(_, height)| usize::from(height))
@@ -904,7 +979,8 @@ mod tests {
                     kind: WorkspaceTabKind::Agent,
                     title: format!("{} 1", workspace.agent.label()),
                     session_name: Some(session_name.clone()),
-                    agent_type: Some(workspace.agent),
+                    agent_type: Some(workspace.agent.clone()),
+                    command_template: None,
                     state: WorkspaceTabRuntimeState::Running,
                 })
             }
@@ -960,6 +1036,7 @@ mod tests {
             title: title.to_string(),
             session_name: Some(session_name.to_string()),
             agent_type: Some(workspace.agent),
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         })
     }
@@ -984,6 +1061,7 @@ mod tests {
             title: title.to_string(),
             session_name: Some(session_name.to_string()),
             agent_type: None,
+            command_template: None,
             state,
         })
     }
@@ -998,6 +1076,11 @@ mod tests {
         app.polling.last_diff_stat_poll_at = Some(Instant::now());
     }
 
+    fn suppress_home_dashboard_poll(app: &mut GroveApp) {
+        app.polling.last_home_dashboard_poll_at = Some(Instant::now());
+        app.home_dashboard.task_slug = app.state.selected_task().map(|task| task.slug.clone());
+    }
+
     fn seed_running_agent_tabs_for_running_workspaces(app: &mut GroveApp) {
         let workspaces = app.state.workspaces.clone();
         for workspace in workspaces {
@@ -1027,6 +1110,7 @@ mod tests {
                 title: format!("{} 1", workspace.agent.label()),
                 session_name: Some(session_name.clone()),
                 agent_type: Some(workspace.agent),
+                command_template: None,
                 state: WorkspaceTabRuntimeState::Running,
             });
             tabs.active_tab_id = tab_id;
@@ -2154,6 +2238,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn startup_restores_failed_command_tab_from_tmux_metadata() {
+        let rows = format!(
+            "{}-cmd-dev-server\t{}\tcommand\tdev server\t\t9\t2\tdev server\t1\n",
+            feature_workspace_session(),
+            feature_workspace_path().display(),
+        );
+        let app = GroveApp::from_task_state(
+            "grove".to_string(),
+            crate::ui::state::AppState::new(fixture_tasks(WorkspaceStatus::Idle)),
+            DiscoveryState::Ready,
+            fixture_projects(),
+            AppDependencies {
+                tmux_input: Box::new(RestoreMetadataTmuxInput { rows }),
+                clipboard: test_clipboard(),
+                config_path: unique_config_path("restore-command-tab"),
+                event_log: Box::new(NullEventLogger),
+                debug_record_start_ts: None,
+            },
+        );
+
+        let workspace_path = feature_workspace_path();
+        let tab = app
+            .workspace_tabs
+            .get(workspace_path.as_path())
+            .and_then(|tabs| tabs.tab_by_id(9))
+            .expect("command tab should be restored");
+        let session_name = format!("{}-cmd-dev-server", feature_workspace_session());
+        assert_eq!(tab.kind, WorkspaceTabKind::Command);
+        assert_eq!(tab.command_template.as_deref(), Some("dev server"));
+        assert_eq!(tab.state, WorkspaceTabRuntimeState::Failed);
+        assert_eq!(tab.bar_label(), " dev server ✗ ");
+        assert!(app.session.shell_sessions.is_ready(session_name.as_str()));
+    }
+
     #[test]
     fn startup_ignores_malformed_tmux_tab_metadata_rows() {
         let rows = format!(
@@ -2198,10 +2317,8 @@ mod tests {
             vec![feature_agent_tab_session(1)],
         );
         assert_eq!(
-            app.last_agent_selection
-                .get(workspace_path.as_path())
-                .copied(),
-            Some(AgentType::Codex),
+            app.last_agent_selection.get(workspace_path.as_path()),
+            Some(&AgentType::Codex),
         );
     }
 
@@ -2479,6 +2596,7 @@ mod tests {
                     title: "Codex 3".to_string(),
                     session_name: Some(numbered_session.clone()),
                     agent_type: Some(AgentType::Codex),
+                    command_template: None,
                     state: WorkspaceTabRuntimeState::Running,
                 });
                 true
@@ -2844,6 +2962,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
         focus_agent_preview_tab(&mut app);
@@ -2875,7 +2994,7 @@ mod tests {
         let mut app = fixture_app();
         select_workspace(&mut app, 1);
         app.state.workspaces[1].status = WorkspaceStatus::Thinking;
-        let expected_color = app.workspace_agent_color(app.state.workspaces[1].agent);
+        let expected_color = app.workspace_agent_color(&app.state.workspaces[1].agent);
 
         let layout = app.panes.test_rects(140, 24);
         let sidebar_x_start = layout.sidebar.x.saturating_add(1);
@@ -2990,16 +3109,22 @@ mod tests {
                 number: 101,
                 url: "https://github.com/acme/grove/pull/101".to_string(),
                 status: PullRequestStatus::Open,
+                review: None,
+                checks: Some(PullRequestChecks::Failing),
             },
             PullRequest {
                 number: 102,
                 url: "https://github.com/acme/grove/pull/102".to_string(),
                 status: PullRequestStatus::Merged,
+                review: None,
+                checks: None,
             },
             PullRequest {
                 number: 103,
                 url: "https://github.com/acme/grove/pull/103".to_string(),
                 status: PullRequestStatus::Closed,
+                review: None,
+                checks: None,
             },
         ];
 
@@ -3018,7 +3143,7 @@ mod tests {
                 "row should not include PR label, got: {row_text}"
             );
             assert!(
-                row_text.contains(" #101")
+                row_text.contains(" #101 ✗")
                     && row_text.contains(" #102")
                     && row_text.contains(" #103"),
                 "row should include PR ids with status icons, got: {row_text}"
@@ -3066,6 +3191,8 @@ mod tests {
             number: 321,
             url: "https://github.com/acme/grove/pull/321".to_string(),
             status: PullRequestStatus::Open,
+            review: None,
+            checks: None,
         }];
 
         let layout = app.panes.test_rects(120, 24);
@@ -3094,6 +3221,8 @@ mod tests {
             number: 777,
             url: "https://github.com/acme/grove/pull/777".to_string(),
             status: PullRequestStatus::Open,
+            review: None,
+            checks: None,
         }];
 
         let layout = app.panes.test_rects(120, 24);
@@ -3273,6 +3402,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
         assert!(app.status_is_visually_working(Some(app.state.workspaces[1].path.as_path()), true));
@@ -3360,6 +3490,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
 
@@ -3783,6 +3914,278 @@ mod tests {
         );
     }
 
+    #[test]
+    fn status_and_attention_history_is_recorded_in_state_store() {
+        let (mut app, _commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux(WorkspaceStatus::Active, Vec::new());
+        app.telemetry.state_store =
+            Some(StateStore::open_in_memory().expect("state store should open"));
+        app.clear_startup_attention_focus_pending();
+        seed_feature_finished_attention(&mut app);
+
+        let workspace_path = feature_workspace_path();
+        app.clear_attention_for_workspace_path(workspace_path.as_path());
+
+        let state_store = app
+            .telemetry
+            .state_store
+            .as_ref()
+            .expect("state store should be set");
+        let durations = state_store
+            .status_durations(
+                workspace_path.as_path(),
+                0,
+                crate::infrastructure::event_log::now_millis() + 1_000,
+            )
+            .expect("durations should load");
+        assert_eq!(
+            durations
+                .iter()
+                .map(|(status, _)| *status)
+                .collect::<Vec<_>>(),
+            vec![WorkspaceStatus::Done]
+        );
+        let attention = state_store
+            .attention_history(None, 0)
+            .expect("attention should load");
+        assert_eq!(attention.len(), 1);
+        assert_eq!(attention[0].workspace_path, workspace_path);
+        assert!(attention[0].acknowledged_ms.is_some());
+    }
+
+    #[test]
+    fn raised_attention_is_sent_to_notification_sinks_once() {
+        let (mut app, _commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux(WorkspaceStatus::Active, Vec::new());
+        let output_path = unique_temp_workspace_dir("notify").join("sent.txt");
+        app.attention_notifier = NotificationDispatcher::new(NotificationsConfig {
+            sinks: vec![NotificationSinkConfig {
+                kind: NotificationSinkKind::Command,
+                command: Some(format!(
+                    "echo \"$GROVE_NOTIFY_REASON $GROVE_NOTIFY_WORKSPACE\" >> '{}'",
+                    output_path.display()
+                )),
+                url: None,
+                reasons: Vec::new(),
+            }],
+            ..NotificationsConfig::default()
+        })
+        .expect("notification config should be valid");
+        app.clear_startup_attention_focus_pending();
+
+        seed_feature_finished_attention(&mut app);
+        seed_feature_finished_attention(&mut app);
+
+        let sent = fs::read_to_string(&output_path).expect("notification should be sent");
+        assert_eq!(sent, "finished feature-a\n");
+        let _ = fs::remove_dir_all(output_path.parent().expect("output has a parent"));
+    }
+
+    #[test]
+    fn pull_request_sync_updates_status_and_raises_merged_attention() {
+        let mut app = fixture_app();
+        app.clear_startup_attention_focus_pending();
+        let open = PullRequest {
+            number: 42,
+            url: "https://github.com/acme/grove/pull/42".to_string(),
+            status: PullRequestStatus::Open,
+            review: None,
+            checks: None,
+        };
+        let feature_task_index = app
+            .state
+            .tasks
+            .iter()
+            .position(|task| task.slug == "feature-a")
+            .expect("feature task should exist");
+        app.state.tasks[feature_task_index].worktrees[0].pull_requests = vec![open.clone()];
+        app.state.workspaces[1].pull_requests = vec![open.clone()];
+        let mut forge = FakePullRequestForge::default();
+        forge.pull_requests.insert(
+            "feature-a".to_string(),
+            vec![PullRequest {
+                status: PullRequestStatus::Merged,
+                ..open
+            }],
+        );
+        app.pull_request_forge = Some(Arc::new(forge));
+
+        app.maybe_sync_pull_requests();
+        app.refresh_attention_items();
+
+        assert_eq!(
+            app.state.workspaces[1].pull_requests[0].status,
+            PullRequestStatus::Merged
+        );
+        assert_eq!(
+            app.state.tasks[feature_task_index].worktrees[0].pull_requests[0].status,
+            PullRequestStatus::Merged
+        );
+        assert_eq!(
+            app.attention_items
+                .iter()
+                .map(|item| (item.reason, item.summary.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(
+                AttentionReason::PullRequestMerged,
+                "pull request merged, #42"
+            )]
+        );
+
+        app.clear_attention_for_workspace_path(feature_workspace_path().as_path());
+
+        assert!(app.pull_request_alerts.is_empty());
+        assert!(app.attention_items.is_empty());
+    }
+
+    #[test]
+    fn home_tab_renders_task_dashboard_and_jumps_with_number_keys() {
+        let mut app = fixture_app();
+        select_workspace(&mut app, 1);
+        assert_eq!(app.preview_tab, PreviewTab::Home);
+        app.state.workspaces[1].pull_requests = vec![PullRequest {
+            number: 7,
+            url: "https://github.com/acme/grove/pull/7".to_string(),
+            status: PullRequestStatus::Open,
+            review: None,
+            checks: Some(PullRequestChecks::Failing),
+        }];
+        app.polling.workspace_waiting_prompts.insert(
+            feature_workspace_path(),
+            "Allow edit to src/main.rs?".to_string(),
+        );
+
+        ftui::Model::update(
+            &mut app,
+            Msg::HomeDashboardPolled(HomeDashboardCompletion {
+                task_slug: "feature-a".to_string(),
+                worktrees: vec![HomeDashboardWorktreeStats {
+                    worktree_path: feature_workspace_path(),
+                    divergence: Some(BranchDivergence {
+                        ahead: 2,
+                        behind: 1,
+                    }),
+                    files_changed: 3,
+                    insertions: 10,
+                    deletions: 4,
+                }],
+                session_started_at: Vec::new(),
+            }),
+        );
+
+        let lines = app
+            .home_dashboard_lines(40)
+            .iter()
+            .map(ftui::text::Line::to_plain_text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "[1] feature-a · feature-a → main · ↑2 ↓1 · 3 files +10 -4"
+        );
+        assert!(
+            lines[1].contains("agent idle · Allow edit to src/main.rs?"),
+            "{lines:?}"
+        );
+        assert!(
+            lines.iter().any(|line| line.contains("PRs [2] ")),
+            "{lines:?}"
+        );
+        assert!(lines.iter().any(|line| line.contains("#7 ✗")), "{lines:?}");
+
+        ftui::Model::update(
+            &mut app,
+            Msg::Key(KeyEvent::new(KeyCode::Char('2')).with_kind(KeyEventKind::Press)),
+        );
+        assert_eq!(
+            app.opened_urls,
+            vec!["https://github.com/acme/grove/pull/7".to_string()]
+        );
+
+        ftui::Model::update(
+            &mut app,
+            Msg::Key(KeyEvent::new(KeyCode::Char('9')).with_kind(KeyEventKind::Press)),
+        );
+        assert_eq!(app.opened_urls.len(), 1);
+        assert_eq!(app.state.selected_index, 1);
+    }
+
+    #[test]
+    fn home_tab_lists_allocated_port_urls() {
+        let mut app = fixture_app();
+        select_workspace(&mut app, 1);
+        app.state.workspaces[1].ports = Some(PortRange {
+            base: 20_010,
+            count: 10,
+        });
+
+        let lines = app
+            .home_dashboard_lines(40)
+            .iter()
+            .map(ftui::text::Line::to_plain_text)
+            .collect::<Vec<_>>();
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("ports http://localhost:20010 · 20010-20019")),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn usage_poll_shows_cost_in_sidebar_and_home_dashboard() {
+        let mut app = fixture_app();
+        select_workspace(&mut app, 1);
+        let mut totals = UsageTotals::default();
+        totals.record(
+            "claude-sonnet-4-5",
+            TokenUsage {
+                input_tokens: 1_000_000,
+                output_tokens: 100_000,
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
+            },
+        );
+
+        ftui::Model::update(
+            &mut app,
+            Msg::UsagePolled(UsagePollCompletion {
+                worktrees: vec![(feature_workspace_path(), totals)],
+            }),
+        );
+
+        let lines = app
+            .home_dashboard_lines(40)
+            .iter()
+            .map(ftui::text::Line::to_plain_text)
+            .collect::<Vec<_>>();
+        assert!(
+            lines.contains(&"    usage in 1.0M · out 100.0k · cache 0 · ~$4.50".to_string()),
+            "{lines:?}"
+        );
+        assert!(
+            lines.iter().any(|line| line.starts_with("    task total ")),
+            "{lines:?}"
+        );
+
+        let layout = app.panes.test_rects(160, 24);
+        let x_start = layout.sidebar.x.saturating_add(1);
+        let x_end = layout.sidebar.right().saturating_sub(1);
+        with_rendered_frame(&app, 160, 24, |frame| {
+            let Some(task_row) = find_row_containing(frame, "[1] · $4.50", x_start, x_end) else {
+                panic!("task header should show the estimated cost");
+            };
+            assert!(row_text(frame, task_row, x_start, x_end).contains("feature-a"));
+        });
+
+        ftui::Model::update(
+            &mut app,
+            Msg::UsagePolled(UsagePollCompletion {
+                worktrees: vec![(feature_workspace_path(), UsageTotals::default())],
+            }),
+        );
+        assert!(app.worktree_usage.is_empty());
+    }
+
     #[test]
     fn focus_attention_inbox_key_selects_first_attention_item_from_list() {
         let (mut app, _commands, _captures, _cursor_captures) =
@@ -3901,6 +4304,8 @@ mod tests {
             number: 101,
             url: "https://github.com/acme/grove/pull/101".to_string(),
             status: PullRequestStatus::Open,
+            review: None,
+            checks: None,
         }];
 
         let layout = app.panes.test_rects(160, 24);
@@ -3962,6 +4367,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn status_rule_attention_overrides_waiting_prompt_text() {
+        let mut app = fixture_app();
+        let workspace_path = feature_workspace_path();
+        app.state.workspaces[1].status = WorkspaceStatus::Waiting;
+        app.attention_marker_overrides
+            .insert(workspace_path.clone(), Some("marker".to_string()));
+        app.record_workspace_status_observation(
+            workspace_path.as_path(),
+            &WorkspaceStatusObservation {
+                status: WorkspaceStatus::Waiting,
+                recent_activity: false,
+                waiting_excerpt: Some("(Y)es/(N)o".to_string()),
+                waiting_attention: Some(NotificationReason::PermissionWall),
+            },
+        );
+
+        app.refresh_attention_items();
+        app.refresh_attention_items();
+
+        assert_eq!(
+            app.attention_items.first().map(|item| item.reason),
+            Some(AttentionReason::PermissionWall)
+        );
+    }
+
     #[test]
     fn startup_selects_attention_inbox_when_first_item_becomes_visible() {
         let mut app = fixture_app();
@@ -4251,6 +4682,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
 
@@ -4317,6 +4749,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: false,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
 
@@ -4334,6 +4767,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
 
@@ -4351,6 +4785,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
         app.record_workspace_poll_state(
@@ -4375,6 +4810,7 @@ mod tests {
                 status: WorkspaceStatus::Active,
                 recent_activity: true,
                 waiting_excerpt: None,
+                waiting_attention: None,
             },
         );
         app.polling.workspace_status_observation_overrides.insert(
@@ -4383,6 +4819,7 @@ mod tests {
                 status: WorkspaceStatus::Waiting,
                 recent_activity: false,
                 waiting_excerpt: Some("approve command".to_string()),
+                waiting_attention: None,
             },
         );
         app.apply_workspace_status_capture(WorkspaceStatusCapture {
@@ -4414,6 +4851,7 @@ mod tests {
                 status: WorkspaceStatus::Waiting,
                 recent_activity: true,
                 waiting_excerpt: Some("waiting".to_string()),
+                waiting_attention: None,
             },
         );
 
@@ -5487,6 +5925,107 @@ mod tests {
         assert!(app.dialogs.keybind_help_open);
     }
 
+    #[test]
+    fn custom_leader_sequence_replaces_default_binding_and_updates_hints() {
+        let mut app = fixture_app();
+        app.keymap = keymap_from(
+            Some("space"),
+            &[("palette:toggle_sidebar", &["<leader> b"])],
+        )
+        .expect("keymap should load");
+        let ctrl_b = KeyEvent::new(KeyCode::Char('b'))
+            .with_modifiers(Modifiers::CTRL)
+            .with_kind(KeyEventKind::Press);
+
+        let _ = app.handle_key(ctrl_b);
+        assert!(!app.sidebar_hidden);
+
+        let _ = app.handle_key(key_press(KeyCode::Char(' ')));
+        assert_eq!(app.pending_key_sequence_label().as_deref(), Some("Space"));
+        assert!(!app.sidebar_hidden);
+
+        let _ = app.handle_key(key_press(KeyCode::Char('b')));
+        assert!(app.sidebar_hidden);
+        assert_eq!(app.pending_key_sequence_label(), None);
+
+        assert!(
+            app.help_catalog_entries()
+                .iter()
+                .any(|entry| entry.action.contains("Space b toggle sidebar"))
+        );
+        let description = app
+            .build_command_palette_actions()
+            .into_iter()
+            .find(|action| action.id == "palette:toggle_sidebar")
+            .and_then(|action| action.description);
+        assert_eq!(
+            description.as_deref(),
+            Some("Show or hide workspace sidebar (Space b)")
+        );
+    }
+
+    #[test]
+    fn broken_custom_sequence_falls_back_to_default_keys() {
+        let mut app = fixture_app();
+        app.keymap = keymap_from(Some("ctrl+a"), &[("toggle_sidebar", &["<leader> b"])])
+            .expect("keymap should load");
+        let selected_before = app.state.selected_index;
+
+        let _ = app.handle_key(
+            KeyEvent::new(KeyCode::Char('a'))
+                .with_modifiers(Modifiers::CTRL)
+                .with_kind(KeyEventKind::Press),
+        );
+        assert_eq!(app.pending_key_sequence_label().as_deref(), Some("Ctrl+A"));
+
+        let _ = app.handle_key(key_press(KeyCode::Char('j')));
+        assert_ne!(app.state.selected_index, selected_before);
+        assert_eq!(app.pending_key_sequence_label(), None);
+        assert!(!app.sidebar_hidden);
+    }
+
+    #[test]
+    fn custom_keybindings_reject_unknown_ids_and_conflicts() {
+        let unknown = keymap_from(None, &[("palette:nope", &["x"])]).expect_err("unknown id");
+        assert!(unknown.contains("unknown command 'palette:nope'"));
+
+        let default_clash =
+            keymap_from(None, &[("palette:new_workspace", &["j"])]).expect_err("default clash");
+        assert!(default_clash.contains("palette:move_selection_down"));
+
+        let prefix_clash = keymap_from(
+            Some("space"),
+            &[
+                ("palette:new_workspace", &["<leader> n"]),
+                ("palette:open_settings", &["<leader>"]),
+            ],
+        )
+        .expect_err("prefix clash");
+        assert!(prefix_clash.contains("conflicts with"));
+
+        let missing_leader =
+            keymap_from(None, &[("palette:new_workspace", &["<leader> n"])]).expect_err("leader");
+        assert!(missing_leader.contains("no leader is set"));
+
+        let bad_key = keymap_from(None, &[("palette:new_workspace", &["hyper+n"])])
+            .expect_err("bad modifier");
+        assert!(bad_key.contains("unknown modifier 'hyper'"));
+
+        let swapped = keymap_from(
+            None,
+            &[
+                ("palette:open_settings", &["n"]),
+                ("palette:new_workspace", &["ctrl+n", "alt+n"]),
+            ],
+        )
+        .expect("remapping both commands should load");
+        assert!(swapped.overrides(UiCommand::NewWorkspace));
+        assert_eq!(
+            swapped.key_label(UiCommand::NewWorkspace).as_deref(),
+            Some("Ctrl+N/Alt+n")
+        );
+    }
+
     #[test]
     fn ctrl_b_toggles_sidebar_visibility_and_backslash_is_noop() {
         let mut app = fixture_app();
@@ -6289,6 +6828,9 @@ mod tests {
             project_index: 0,
             selected_repository_indices: vec![0],
             project_picker: None,
+            recipes: Vec::new(),
+            recipe_index: None,
+            recipe_variables: String::new(),
         });
         app.focus_dialog_field(crate::ui::tui::FOCUS_ID_CREATE_PULL_REQUEST_URL);
 
@@ -7264,14 +7806,14 @@ mod tests {
                 .iter()
                 .filter(|command| command.meta().palette.is_some())
                 .count(),
-            47
+            58
         );
         assert_eq!(UiCommand::help_hints_for(HelpHintContext::Global).len(), 16);
         assert_eq!(
             UiCommand::help_hints_for(HelpHintContext::Workspace).len(),
             18
         );
-        assert_eq!(UiCommand::help_hints_for(HelpHintContext::List).len(), 2);
+        assert_eq!(UiCommand::help_hints_for(HelpHintContext::List).len(), 5);
         assert_eq!(
             UiCommand::help_hints_for(HelpHintContext::PreviewAgent).len(),
             14
@@ -7459,7 +8001,7 @@ mod tests {
 
         let loaded = crate::infrastructure::config::load_from_path(&app.config_path)
             .expect("config should load");
-        assert_eq!(loaded.theme, ThemeName::RosePine);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::RosePine));
     }
 
     #[test]
@@ -7471,7 +8013,7 @@ mod tests {
         let _ = app.handle_key(KeyEvent::new(KeyCode::Right).with_kind(KeyEventKind::Press));
 
         assert_eq!(
-            app.settings_dialog().map(|dialog| dialog.theme),
+            app.settings_dialog().map(|dialog| dialog.theme.clone()),
             Some(ThemeName::RosePine)
         );
         assert_eq!(app.theme_name, ThemeName::RosePine);
@@ -7492,7 +8034,48 @@ mod tests {
 
         let loaded = crate::infrastructure::config::load_from_path(&app.config_path)
             .expect("config should load");
-        assert_eq!(loaded.theme, ThemeName::CatppuccinMocha);
+        assert_eq!(loaded.theme, ThemeKey::from(ThemeName::CatppuccinMocha));
+    }
+
+    #[test]
+    fn settings_dialog_cycles_into_installed_custom_theme() {
+        let dir = crate::test_support::unique_test_dir("grove-custom-theme-cycle");
+        fs::write(
+            dir.join("ember.toml"),
+            "name = \"Ember\"\n\n[colors]\nbackground = \"#101112\"\ntext = \"#f0f1f2\"\nprimary = \"#0a0b0c\"\nsuccess = \"#00ff00\"\nwarning = \"#ffff00\"\nerror = \"#ff0000\"\n",
+        )
+        .expect("theme should be written");
+        let (themes, errors) = crate::infrastructure::themes::load_custom_themes(&dir);
+        assert!(errors.is_empty(), "{errors:?}");
+        let registry = crate::infrastructure::themes::ThemeRegistry::new(&themes);
+        let custom = registry
+            .from_key("ember")
+            .expect("custom theme should resolve");
+
+        let mut app = fixture_app();
+        app.themes = registry;
+        app.theme_name = ThemeName::RosePineDawn;
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('S')).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Right).with_kind(KeyEventKind::Press));
+
+        assert_eq!(app.theme_name, custom);
+        assert_eq!(theme_display_name(&custom), "Ember");
+        assert_eq!(
+            packed(ui_theme_for(&custom).background),
+            PackedRgba::rgb(0x10, 0x11, 0x12)
+        );
+        let commands =
+            crate::application::agent_runtime::tmux_theme_commands("grove-ws-main", &custom);
+        assert!(commands.iter().any(|command| {
+            command[4] == "display-panes-active-colour" && command[5] == "#0a0b0c"
+        }));
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Tab).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        let loaded = crate::infrastructure::config::load_from_path(&app.config_path)
+            .expect("config should load");
+        assert_eq!(loaded.theme, ThemeKey::from(custom));
+        let _ = fs::remove_dir_all(dir);
     }
 
     #[test]
@@ -7561,16 +8144,16 @@ mod tests {
 
         with_rendered_frame(&monokai_app, 80, 24, |frame| {
             let header_bg = frame.buffer.get(0, 0).expect("header cell should exist").bg;
-            assert_eq!(header_bg, packed(ui_theme_for(ThemeName::Monokai).overlay));
+            assert_eq!(header_bg, packed(ui_theme_for(&ThemeName::Monokai).overlay));
         });
 
         with_rendered_frame(&latte_app, 80, 24, |frame| {
             let header_bg = frame.buffer.get(0, 0).expect("header cell should exist").bg;
             assert_eq!(
                 header_bg,
-                packed(ui_theme_for(ThemeName::CatppuccinLatte).overlay)
+                packed(ui_theme_for(&ThemeName::CatppuccinLatte).overlay)
             );
-            assert_ne!(header_bg, packed(ui_theme_for(ThemeName::Monokai).overlay));
+            assert_ne!(header_bg, packed(ui_theme_for(&ThemeName::Monokai).overlay));
         });
     }
 
@@ -7597,11 +8180,11 @@ mod tests {
                 .expect("preview content cell should exist");
             assert_eq!(
                 cell.bg,
-                packed(ui_theme_for(ThemeName::CatppuccinLatte).background)
+                packed(ui_theme_for(&ThemeName::CatppuccinLatte).background)
             );
             assert_eq!(
                 cell.fg,
-                packed(ui_theme_for(ThemeName::CatppuccinLatte).text)
+                packed(ui_theme_for(&ThemeName::CatppuccinLatte).text)
             );
         });
     }
@@ -7890,6 +8473,351 @@ mod tests {
         });
     }
 
+    #[test]
+    fn transcript_dialog_searches_lines_and_esc_clears_query_before_closing() {
+        let mut app = fixture_app();
+        app.set_transcript_dialog(crate::ui::tui::TranscriptDialogState {
+            task_name: "feature-a".to_string(),
+            session_name: "grove-wt-feature-a-grove".to_string(),
+            lines: (0..40)
+                .map(|index| {
+                    if index == 5 || index == 30 {
+                        format!("error: build {index} failed")
+                    } else {
+                        format!("line {index}")
+                    }
+                })
+                .collect(),
+            ..Default::default()
+        });
+
+        for character in "ERROR".chars() {
+            let _ = app
+                .handle_key(KeyEvent::new(KeyCode::Char(character)).with_kind(KeyEventKind::Press));
+        }
+        let Some(dialog) = app.transcript_dialog() else {
+            panic!("transcript dialog should be open");
+        };
+        assert_eq!(dialog.matches, vec![5, 30]);
+        assert_eq!(dialog.selected_line(), Some(30));
+        assert_eq!(dialog.scroll, 27);
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        assert_eq!(
+            app.transcript_dialog()
+                .and_then(|dialog| dialog.selected_line()),
+            Some(5)
+        );
+        with_rendered_frame(&app, 120, 30, |frame| {
+            let text = (0..frame.height())
+                .map(|row| row_text(frame, row, 0, frame.width()))
+                .collect::<Vec<String>>()
+                .join("\n");
+            assert!(text.contains("match 1/2"));
+            assert!(text.contains("error: build 5 failed"));
+            assert!(text.contains("Dialog: Transcript"));
+        });
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Escape).with_kind(KeyEventKind::Press));
+        assert_eq!(
+            app.transcript_dialog().map(|dialog| dialog.query.as_str()),
+            Some("")
+        );
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Escape).with_kind(KeyEventKind::Press));
+        assert!(app.transcript_dialog().is_none());
+    }
+
+    #[test]
+    fn pane_search_ranks_live_session_hits_and_jumps_preview_to_match() {
+        let output = (0..100)
+            .map(|index| {
+                if index == 10 {
+                    "thread 'main' panicked at src/lib.rs:7".to_string()
+                } else {
+                    format!("line {index}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        let session_rows = format!(
+            "{}\t\t\t\t\t\t\nscratch\t\t\t\t\t\t\n",
+            feature_workspace_session()
+        );
+        let (mut app, _commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux_and_session_rows(
+                WorkspaceStatus::Active,
+                vec![Ok(output.clone()), Ok(output)],
+                Vec::new(),
+                session_rows,
+            );
+
+        app.execute_command_palette_action("palette:search_panes");
+        assert_eq!(app.active_dialog_kind(), Some("pane_search"));
+        for character in "panicked".chars() {
+            let _ = app
+                .handle_key(KeyEvent::new(KeyCode::Char(character)).with_kind(KeyEventKind::Press));
+        }
+        let Some(dialog) = app.pane_search_dialog() else {
+            panic!("pane search dialog should be open");
+        };
+        assert_eq!(dialog.sources.len(), 1);
+        assert_eq!(dialog.groups.len(), 1);
+        assert_eq!(dialog.groups[0].group, "feature-a");
+        assert_eq!(dialog.selected_hit().map(|hit| hit.line), Some(10));
+        with_rendered_frame(&app, 120, 40, |frame| {
+            let text = (0..frame.height())
+                .map(|row| row_text(frame, row, 0, frame.width()))
+                .collect::<Vec<String>>()
+                .join("\n");
+            assert!(text.contains("match 1/1 in 1 session"));
+            assert!(text.contains("feature-a (1)"));
+            assert!(text.contains("Dialog: Search"));
+        });
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+
+        assert!(app.pane_search_dialog().is_none());
+        assert_eq!(
+            app.state
+                .selected_workspace()
+                .map(|workspace| workspace.path.clone()),
+            Some(feature_workspace_path())
+        );
+        assert_eq!(app.preview_tab, PreviewTab::Agent);
+        assert!(app.pane_search_reveal.is_none());
+        assert_eq!(preview_scroll_offset(&app), 7);
+        assert!(!preview_auto_scroll(&app));
+    }
+
+    #[test]
+    fn broadcast_sends_prompt_to_marked_agents_and_reports_each_target() {
+        let (mut app, commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux(WorkspaceStatus::Waiting, Vec::new());
+        select_workspace(&mut app, 1);
+        focus_agent_preview_tab(&mut app);
+        let _ = app.focus_manager.focus(FOCUS_ID_WORKSPACE_LIST);
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('v')).with_kind(KeyEventKind::Press));
+        select_workspace(&mut app, 0);
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('v')).with_kind(KeyEventKind::Press));
+        assert_eq!(app.broadcast_targets.len(), 2);
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('V')).with_kind(KeyEventKind::Press));
+        assert_eq!(app.active_dialog_kind(), Some("broadcast"));
+        for character in "run tests".chars() {
+            let _ = app
+                .handle_key(KeyEvent::new(KeyCode::Char(character)).with_kind(KeyEventKind::Press));
+        }
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+
+        let session = feature_workspace_session();
+        assert!(commands.borrow().iter().any(|command| {
+            command
+                == &vec![
+                    "tmux".to_string(),
+                    "send-keys".to_string(),
+                    "-l".to_string(),
+                    "-t".to_string(),
+                    session.clone(),
+                    "run tests".to_string(),
+                ]
+        }));
+        let Some(dialog) = app.broadcast_dialog() else {
+            panic!("broadcast dialog should stay open with results");
+        };
+        let deliveries = dialog
+            .targets
+            .iter()
+            .map(|target| target.delivery.clone())
+            .collect::<Vec<Option<BroadcastDelivery>>>();
+        assert_eq!(
+            deliveries,
+            vec![
+                Some(BroadcastDelivery::Skipped("no running agent".to_string())),
+                Some(BroadcastDelivery::Sent),
+            ]
+        );
+        with_rendered_frame(&app, 120, 40, |frame| {
+            let text = (0..frame.height())
+                .map(|row| row_text(frame, row, 0, frame.width()))
+                .collect::<Vec<String>>()
+                .join("\n");
+            assert!(text.contains("Dialog: Broadcast"));
+            assert!(text.contains("skipped: no running agent"));
+        });
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        assert!(app.broadcast_dialog().is_none());
+
+        app.state.workspaces[1].status = WorkspaceStatus::Thinking;
+        let sent_before = commands.borrow().len();
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('V')).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('x')).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        assert_eq!(commands.borrow().len(), sent_before);
+        assert_eq!(
+            app.broadcast_dialog()
+                .and_then(|dialog| dialog.targets[1].delivery.clone()),
+            Some(BroadcastDelivery::Skipped(
+                "agent is thinking, Tab to force".to_string()
+            ))
+        );
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Tab).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('x')).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        assert_eq!(commands.borrow().len(), sent_before + 2);
+    }
+
+    #[test]
+    fn prompt_queue_sends_next_prompt_at_input_and_pauses_on_permission_wall() {
+        let (mut app, commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux(WorkspaceStatus::Thinking, Vec::new());
+        let worktree = crate::test_support::unique_test_dir("grove-ui-prompt-queue");
+        app.state.workspaces[1].path = worktree.clone();
+        select_workspace(&mut app, 1);
+        focus_agent_preview_tab(&mut app);
+        let _ = app.focus_manager.focus(FOCUS_ID_WORKSPACE_LIST);
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('Q')).with_kind(KeyEventKind::Press));
+        assert_eq!(app.active_dialog_kind(), Some("prompt_queue"));
+        for prompt in ["run tests", "fix lint"] {
+            for character in prompt.chars() {
+                let _ = app.handle_key(
+                    KeyEvent::new(KeyCode::Char(character)).with_kind(KeyEventKind::Press),
+                );
+            }
+            let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        }
+        assert_eq!(
+            app.prompt_queue_dialog()
+                .map(|dialog| dialog.queue.prompts.clone()),
+            Some(vec!["run tests".to_string(), "fix lint".to_string()])
+        );
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press));
+        assert!(app.prompt_queue_dialog().is_none());
+
+        let observe = |app: &mut GroveApp, status, waiting_excerpt: Option<&str>| {
+            app.apply_workspace_status_observation(
+                worktree.as_path(),
+                WorkspaceStatusObservation {
+                    status,
+                    recent_activity: false,
+                    waiting_excerpt: waiting_excerpt.map(str::to_string),
+                    waiting_attention: None,
+                },
+            );
+        };
+        let sent_literal = |commands: &[Vec<String>], text: &str| {
+            commands
+                .iter()
+                .filter(|command| {
+                    command.first().map(String::as_str) == Some("tmux")
+                        && command.get(2).map(String::as_str) == Some("-l")
+                        && command.last().map(String::as_str) == Some(text)
+                })
+                .count()
+        };
+
+        observe(&mut app, WorkspaceStatus::Waiting, Some("? for shortcuts"));
+        observe(&mut app, WorkspaceStatus::Waiting, Some("? for shortcuts"));
+        assert_eq!(sent_literal(&commands.borrow(), "run tests"), 1);
+        assert_eq!(
+            crate::application::prompt_queue::load_prompt_queue(worktree.as_path())
+                .map(|queue| queue.prompts),
+            Ok(vec!["fix lint".to_string()])
+        );
+
+        observe(&mut app, WorkspaceStatus::Thinking, None);
+        observe(
+            &mut app,
+            WorkspaceStatus::Waiting,
+            Some("Do you want to make this edit?"),
+        );
+        assert_eq!(sent_literal(&commands.borrow(), "fix lint"), 0);
+        let paused = crate::application::prompt_queue::load_prompt_queue(worktree.as_path())
+            .expect("queue should load")
+            .paused;
+        assert_eq!(
+            paused.as_deref(),
+            Some("permission wall: Do you want to make this edit?")
+        );
+
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Char('Q')).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Tab).with_kind(KeyEventKind::Press));
+        let _ = app.handle_key(KeyEvent::new(KeyCode::Escape).with_kind(KeyEventKind::Press));
+        observe(&mut app, WorkspaceStatus::Waiting, Some("? for shortcuts"));
+        assert_eq!(sent_literal(&commands.borrow(), "fix lint"), 1);
+        assert!(!crate::application::prompt_queue::prompt_queue_path(worktree.as_path()).exists());
+        let _ = fs::remove_dir_all(worktree);
+    }
+
+    #[test]
+    fn prompt_queue_sends_again_after_a_turn_finished_between_polls() {
+        let (mut app, commands, _captures, _cursor_captures) =
+            fixture_app_with_tmux(WorkspaceStatus::Thinking, Vec::new());
+        let worktree = crate::test_support::unique_test_dir("grove-ui-prompt-queue-turn");
+        app.state.workspaces[1].path = worktree.clone();
+        select_workspace(&mut app, 1);
+        focus_agent_preview_tab(&mut app);
+        for prompt in ["run tests", "fix lint", "commit"] {
+            crate::application::prompt_queue::enqueue_prompt(worktree.as_path(), prompt)
+                .expect("prompt should queue");
+        }
+
+        let observe = |app: &mut GroveApp| {
+            app.apply_workspace_status_observation(
+                worktree.as_path(),
+                WorkspaceStatusObservation {
+                    status: WorkspaceStatus::Waiting,
+                    recent_activity: false,
+                    waiting_excerpt: Some("? for shortcuts".to_string()),
+                    waiting_attention: None,
+                },
+            );
+        };
+        let sent_literal = |commands: &[Vec<String>], text: &str| {
+            commands
+                .iter()
+                .filter(|command| {
+                    command.first().map(String::as_str) == Some("tmux")
+                        && command.get(2).map(String::as_str) == Some("-l")
+                        && command.last().map(String::as_str) == Some(text)
+                })
+                .count()
+        };
+        let set_output_changing = |app: &mut GroveApp, changing: bool| {
+            app.polling
+                .workspace_output_changing
+                .insert(worktree.clone(), changing);
+        };
+
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "run tests"), 1);
+        set_output_changing(&mut app, true);
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "fix lint"), 0);
+
+        set_output_changing(&mut app, false);
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "fix lint"), 0);
+        set_output_changing(&mut app, true);
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "fix lint"), 1);
+
+        set_output_changing(&mut app, false);
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "commit"), 0);
+        fs::create_dir_all(worktree.join(".grove")).expect("grove dir should exist");
+        fs::write(
+            worktree.join(".grove/agent-event"),
+            "turn_complete 1700000000\n{}\n",
+        )
+        .expect("hook event should be written");
+        observe(&mut app);
+        assert_eq!(sent_literal(&commands.borrow(), "commit"), 1);
+        let _ = fs::remove_dir_all(worktree);
+    }
+
     #[test]
     fn rendered_frames_update_performance_snapshot() {
         let app = fixture_app();
@@ -9692,6 +10620,8 @@ second row\n",
             number: 500,
             url: "https://github.com/acme/grove/pull/500".to_string(),
             status: PullRequestStatus::Open,
+            review: None,
+            checks: None,
         }];
 
         let layout = app.panes.test_rects(120, 24);
@@ -10663,6 +11593,54 @@ second row\n",
                 }));
             }
 
+            #[test]
+            fn pre_agent_start_hook_veto_blocks_agent_launch() {
+                let (mut app, commands, _captures, _cursor_captures, events) =
+                    fixture_app_with_tmux_and_events(WorkspaceStatus::Idle, Vec::new(), Vec::new());
+                app.projects[0].defaults.hooks = vec![HookConfig {
+                    event: HookEvent::PreAgentStart,
+                    command: "echo 'tests are red' >&2; exit 1".to_string(),
+                    timeout_secs: 5,
+                }];
+                select_workspace(&mut app, 1);
+                app.preview_tab = PreviewTab::Shell;
+                app.set_launch_dialog(LaunchDialogState {
+                    target: LaunchDialogTarget::WorkspaceTab,
+                    agent: AgentType::Codex,
+                    start_config: StartAgentConfigState::new(
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                        PermissionMode::Default,
+                    ),
+                });
+
+                app.confirm_start_dialog();
+
+                assert!(
+                    !commands
+                        .borrow()
+                        .iter()
+                        .any(|command| command.get(1).map(String::as_str) == Some("new-session"))
+                );
+                assert_eq!(
+                    app.session.last_tmux_error.as_deref(),
+                    Some("pre-agent-start hook exited with 1: tests are red")
+                );
+                let failed = recorded_events(&events)
+                    .into_iter()
+                    .find(|event| event.event == "hooks" && event.kind == "hook_failed")
+                    .expect("hook failure should be logged");
+                assert_eq!(
+                    failed.data.get("event").and_then(Value::as_str),
+                    Some("pre-agent-start")
+                );
+                assert_eq!(
+                    failed.data.get("stderr").and_then(Value::as_str),
+                    Some("tests are red")
+                );
+            }
+
             #[test]
             fn start_dialog_launches_numbered_agent_session_for_base_worktree() {
                 let (mut app, commands, _captures, _cursor_captures) =
@@ -12973,6 +13951,7 @@ second row\n",
                         status: WorkspaceStatus::Waiting,
                         recent_activity: false,
                         waiting_excerpt: Some("approve command".to_string()),
+                        waiting_attention: None,
                     },
                 );
 
@@ -14646,9 +15625,7 @@ second row\n",
                     } => {
                         assert_eq!(session_name, feature_workspace_session().as_str());
                     }
-                    crate::ui::tui::ConfirmDialogAction::QuitApp => {
-                        panic!("expected close-tab confirm action")
-                    }
+                    _ => panic!("expected close-tab confirm action"),
                 }
                 assert!(!commands.borrow().iter().any(|command| {
                     command
@@ -15720,6 +16697,7 @@ second row\n",
                 );
                 select_workspace(&mut app, 1);
                 suppress_diff_stat_poll(&mut app);
+                suppress_home_dashboard_poll(&mut app);
                 force_tick_due(&mut app);
 
                 let cmd = ftui::Model::update(&mut app, Msg::Tick);
@@ -15744,6 +16722,7 @@ second row\n",
                 );
                 select_workspace(&mut app, 0);
                 suppress_diff_stat_poll(&mut app);
+                suppress_home_dashboard_poll(&mut app);
                 force_tick_due(&mut app);
 
                 let cmd = ftui::Model::update(&mut app, Msg::Tick);
@@ -16396,6 +17375,7 @@ second row\n",
                             title: "Codex 2".to_string(),
                             session_name: Some(session_name.clone()),
                             agent_type: Some(AgentType::Codex),
+                            command_template: None,
                             state: WorkspaceTabRuntimeState::Running,
                         })
                     })
@@ -16451,6 +17431,7 @@ second row\n",
                             title: "Codex 2".to_string(),
                             session_name: Some(stale_session.clone()),
                             agent_type: Some(AgentType::Codex),
+                            command_template: None,
                             state: WorkspaceTabRuntimeState::Running,
                         });
                         let active_tab_id = tabs.insert_tab_adjacent(WorkspaceTab {
@@ -16460,6 +17441,7 @@ second row\n",
                             title: "Codex 3".to_string(),
                             session_name: Some(active_session.clone()),
                             agent_type: Some(AgentType::Codex),
+                            command_template: None,
                             state: WorkspaceTabRuntimeState::Running,
                         });
                         (stale_tab_id, active_tab_id)
@@ -17516,6 +18498,7 @@ second row\n",
                         status: WorkspaceStatus::Waiting,
                         recent_activity: false,
                         waiting_excerpt: Some("approve command".to_string()),
+                        waiting_attention: None,
                     },
                 );
                 app.apply_workspace_status_observation(
@@ -17524,6 +18507,7 @@ second row\n",
                         status: WorkspaceStatus::Waiting,
                         recent_activity: false,
                         waiting_excerpt: Some("approve command".to_string()),
+                        waiting_attention: None,
                     },
                 );
 
@@ -18005,6 +18989,79 @@ second row\n",
                 );
             }
 
+            #[test]
+            fn create_dialog_recipe_seeds_prompt_and_records_recipe() {
+                let mut app = fixture_app();
+                let tasks_root = unique_temp_workspace_dir("create-recipe-task-root");
+                let flohome_repo = init_git_repo("create-recipe-flohome", "main");
+                fs::create_dir_all(flohome_repo.join(".grove/recipes"))
+                    .expect("recipes dir should exist");
+                fs::write(
+                    flohome_repo.join(".grove/recipes/bugfix.toml"),
+                    "description = \"Fix a bug\"\nprompt = \"Fix {{issue}} in {{area}}\"\ninit_command = \"make setup\"\n\n[[variables]]\nname = \"issue\"\n\n[[variables]]\nname = \"area\"\ndefault = \"auth\"\n",
+                )
+                .expect("recipe should write");
+                app.projects = vec![ProjectConfig {
+                    name: "flohome".to_string(),
+                    path: flohome_repo.clone(),
+                    defaults: Default::default(),
+                }];
+                app.task_root_override = Some(tasks_root.clone());
+
+                app.open_create_dialog();
+                if let Some(dialog) = app.create_dialog_mut() {
+                    dialog.task_name = "fix-login".to_string();
+                    dialog.recipe_index = dialog
+                        .recipes
+                        .iter()
+                        .position(|recipe| recipe.name == "bugfix")
+                        .and_then(|index| index.checked_sub(1));
+                }
+                app.focus_dialog_field(crate::ui::tui::FOCUS_ID_CREATE_RECIPE);
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(KeyEvent::new(KeyCode::Right).with_kind(KeyEventKind::Press)),
+                );
+                assert_eq!(
+                    app.create_dialog()
+                        .map(|dialog| dialog.recipe_variables.as_str()),
+                    Some("issue=; area=auth")
+                );
+                if let Some(dialog) = app.create_dialog_mut() {
+                    dialog.recipe_variables = "issue=#42; area=auth".to_string();
+                }
+                app.focus_dialog_field(crate::ui::tui::FOCUS_ID_CREATE_CREATE_BUTTON);
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press)),
+                );
+
+                assert!(app.create_dialog().is_none());
+                assert_eq!(
+                    app.state
+                        .selected_task()
+                        .and_then(|task| task.recipe.as_deref()),
+                    Some("bugfix")
+                );
+                let repo_dir_name = flohome_repo
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .expect("repo dir name");
+                let worktree_path = tasks_root.join("fix-login").join(repo_dir_name);
+                assert_eq!(
+                    fs::read_to_string(worktree_path.join(".grove/prompt")).ok(),
+                    Some("Fix #42 in auth\n".to_string())
+                );
+                assert_eq!(
+                    fs::read_to_string(worktree_path.join(".grove/init_command")).ok(),
+                    Some("make setup\n".to_string())
+                );
+                assert!(
+                    fs::read_to_string(tasks_root.join("fix-login").join(".grove/task.toml"))
+                        .is_ok_and(|manifest| manifest.contains("recipe = \"bugfix\""))
+                );
+            }
+
             #[test]
             fn create_dialog_pr_mode_creates_task_with_single_repository() {
                 let mut app = fixture_app();
@@ -18231,6 +19288,7 @@ second row\n",
                     is_orphaned: false,
                     supported_agent: true,
                     pull_requests: Vec::new(),
+                    ports: None,
                 });
 
                 app.open_create_dialog();
@@ -18284,6 +19342,7 @@ second row\n",
                         is_orphaned: false,
                         supported_agent: true,
                         pull_requests: Vec::new(),
+                        ports: None,
                     });
                 }
 
@@ -18322,10 +19381,12 @@ second row\n",
                     }],
                     agent: AgentType::Codex,
                     branch_source: TaskBranchSource::BaseBranch,
+                    recipe: None,
                 };
                 let created = crate::application::task_lifecycle::create_task_in_root(
                     tasks_root.as_path(),
                     &create_request,
+                    &AgentRegistry::default(),
                     &crate::application::workspace_lifecycle::CommandGitRunner,
                     &crate::application::workspace_lifecycle::CommandSetupScriptRunner,
                     &crate::application::workspace_lifecycle::CommandSetupCommandRunner,
@@ -19948,6 +21009,7 @@ second row\n",
                     }],
                     agent: AgentType::Codex,
                     branch_source: TaskBranchSource::BaseBranch,
+                    recipe: None,
                 };
                 let result = CreateTaskResult {
                     task_root: PathBuf::from("/tasks/feature-x"),
@@ -20289,6 +21351,83 @@ second row\n",
                 );
             }
 
+            #[test]
+            fn archive_task_command_confirms_before_archiving() {
+                let mut app = fixture_background_app(WorkspaceStatus::Idle);
+                select_workspace(&mut app, 1);
+                let task_name = app.state.tasks[1].name.clone();
+
+                assert!(app.palette_command_enabled(UiCommand::ArchiveTask));
+                let _ = app.execute_ui_command(UiCommand::ArchiveTask);
+
+                let Some(dialog) = app.confirm_dialog() else {
+                    panic!("archive confirm should be open");
+                };
+                match &dialog.action {
+                    crate::ui::tui::ConfirmDialogAction::ArchiveTask { task } => {
+                        assert_eq!(task.name, task_name);
+                    }
+                    _ => panic!("expected archive confirm action"),
+                }
+                assert!(!app.dialogs.archive_in_flight);
+            }
+
+            #[test]
+            fn task_archive_completion_clears_in_flight_and_reports_failure() {
+                let mut app = fixture_background_app(WorkspaceStatus::Idle);
+                app.dialogs.archive_in_flight = true;
+
+                let _ = ftui::Model::update(
+                    &mut app,
+                    Msg::TaskArchived(TaskArchiveCompletion {
+                        task_name: "feature-a".to_string(),
+                        task_slug: "feature-a".to_string(),
+                        result: Err("worktree is locked".to_string()),
+                        warnings: Vec::new(),
+                    }),
+                );
+
+                assert!(!app.dialogs.archive_in_flight);
+                assert!(app.status_bar_line().contains("task archive failed"));
+            }
+
+            #[test]
+            fn task_restore_completion_offers_to_resume_agent_tabs() {
+                let mut app = fixture_background_app(WorkspaceStatus::Idle);
+                app.dialogs.archive_in_flight = true;
+                let workspace_path = app.state.tasks[1].worktrees[0].path.clone();
+                let tab = ArchivedTab {
+                    kind: "agent".to_string(),
+                    title: "Claude 1".to_string(),
+                    agent: Some("claude".to_string()),
+                    resume_command: Some("claude --resume abc-123".to_string()),
+                };
+
+                let _ = ftui::Model::update(
+                    &mut app,
+                    Msg::TaskRestored(TaskRestoreCompletion {
+                        task_name: "feature-a".to_string(),
+                        task_slug: "feature-a".to_string(),
+                        workspace_path: Some(workspace_path.clone()),
+                        resumable_tabs: vec![(workspace_path.clone(), tab.clone())],
+                        result: Ok(()),
+                        warnings: Vec::new(),
+                    }),
+                );
+
+                assert!(!app.dialogs.archive_in_flight);
+                let Some(dialog) = app.confirm_dialog() else {
+                    panic!("resume confirm should be open");
+                };
+                assert_eq!(
+                    dialog.action,
+                    crate::ui::tui::ConfirmDialogAction::ResumeArchivedAgents {
+                        task_name: "feature-a".to_string(),
+                        tabs: vec![(workspace_path, tab)],
+                    }
+                );
+            }
+
             #[test]
             fn delete_base_task_completion_keeps_matching_project_config() {
                 let mut app = fixture_background_app(WorkspaceStatus::Idle);
@@ -20425,6 +21564,73 @@ second row\n",
                 );
             }
 
+            #[test]
+            fn merge_dialog_strategy_field_cycles_strategies() {
+                let mut app = fixture_app();
+                select_workspace(&mut app, 1);
+                app.open_merge_dialog();
+                assert_eq!(
+                    app.merge_dialog().map(|dialog| dialog.strategy),
+                    Some(MergeStrategy::Merge)
+                );
+
+                for _ in 0..2 {
+                    ftui::Model::update(
+                        &mut app,
+                        Msg::Key(
+                            KeyEvent::new(KeyCode::Char('n'))
+                                .with_modifiers(Modifiers::CTRL)
+                                .with_kind(KeyEventKind::Press),
+                        ),
+                    );
+                }
+                assert_eq!(
+                    app.current_merge_dialog_focus_field(),
+                    Some(MergeDialogField::Strategy)
+                );
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(KeyEvent::new(KeyCode::Char(' ')).with_kind(KeyEventKind::Press)),
+                );
+                assert_eq!(
+                    app.merge_dialog().map(|dialog| dialog.strategy),
+                    Some(MergeStrategy::Squash)
+                );
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(KeyEvent::new(KeyCode::Char('h')).with_kind(KeyEventKind::Press)),
+                );
+                assert_eq!(
+                    app.merge_dialog().map(|dialog| dialog.strategy),
+                    Some(MergeStrategy::Merge)
+                );
+            }
+
+            #[test]
+            fn merge_completion_strategy_conflict_error_is_shown_verbatim() {
+                let mut app = fixture_app();
+                select_workspace(&mut app, 1);
+
+                ftui::Model::update(
+                    &mut app,
+                    Msg::MergeWorkspaceCompleted(MergeWorkspaceCompletion {
+                        workspace_name: "feature-a".to_string(),
+                        workspace_path: PathBuf::from("/repos/grove-feature-a"),
+                        workspace_branch: "feature-a".to_string(),
+                        base_branch: "main".to_string(),
+                        result: Err(
+                            "rebase conflict in src/a.rs; rebase aborted, workspace branch unchanged"
+                                .to_string(),
+                        ),
+                        warnings: Vec::new(),
+                        conflict: None,
+                    }),
+                );
+
+                let status = app.status_bar_line();
+                assert!(status.contains("rebase conflict in src/a.rs"));
+            }
+
             #[test]
             fn merge_completion_conflict_error_shows_compact_conflict_summary() {
                 let mut app = fixture_app();
@@ -20442,6 +21648,7 @@ second row\n",
                                 .to_string(),
                         ),
                         warnings: Vec::new(),
+                        conflict: None,
                     }),
                 );
 
@@ -20450,6 +21657,134 @@ second row\n",
                 assert!(status.contains("resolve in base worktree"));
             }
 
+            fn fixture_feature_conflict() -> WorkspaceConflict {
+                WorkspaceConflict {
+                    operation: IntegrationOperation::Rebase,
+                    worktree_path: feature_workspace_path(),
+                    files: vec!["src/a.rs".to_string()],
+                }
+            }
+
+            #[test]
+            fn merge_completion_with_kept_conflict_marks_workspace_conflicted() {
+                let (mut app, _commands, _captures, _cursor_captures) =
+                    fixture_app_with_tmux(WorkspaceStatus::Idle, Vec::new());
+                select_workspace(&mut app, 1);
+                app.sidebar_width_pct = 80;
+
+                ftui::Model::update(
+                    &mut app,
+                    Msg::MergeWorkspaceCompleted(MergeWorkspaceCompletion {
+                        workspace_name: "feature-a".to_string(),
+                        workspace_path: feature_workspace_path(),
+                        workspace_branch: "feature-a".to_string(),
+                        base_branch: "main".to_string(),
+                        result: Err("rebase conflict in src/a.rs; left in place".to_string()),
+                        warnings: Vec::new(),
+                        conflict: Some(fixture_feature_conflict()),
+                    }),
+                );
+
+                assert_eq!(
+                    app.workspace_conflicts.get(&feature_workspace_path()),
+                    Some(&fixture_feature_conflict())
+                );
+                let palette_ids: Vec<String> = app
+                    .build_command_palette_actions()
+                    .into_iter()
+                    .map(|action| action.id)
+                    .collect();
+                assert!(palette_ids.contains(&"palette:continue_conflict".to_string()));
+                assert!(palette_ids.contains(&"palette:abort_conflict".to_string()));
+
+                let layout = app.panes.test_rects(160, 24);
+                let x_start = layout.sidebar.x.saturating_add(1);
+                let x_end = layout.sidebar.right().saturating_sub(1);
+                with_rendered_frame(&app, 160, 24, |frame| {
+                    let Some(selected_row) = find_workspace_row(frame, 1, x_start, x_end) else {
+                        panic!("selected workspace row should be rendered");
+                    };
+                    let row = row_text(frame, selected_row, x_start, x_end);
+                    assert!(
+                        row.contains("CONFLICTED"),
+                        "conflicted workspace should render CONFLICTED label, got: {row}"
+                    );
+                });
+            }
+
+            #[test]
+            fn diff_stat_completion_without_conflict_clears_conflict_marker() {
+                let mut app = fixture_app();
+                app.workspace_conflicts
+                    .insert(feature_workspace_path(), fixture_feature_conflict());
+
+                ftui::Model::update(
+                    &mut app,
+                    Msg::DiffStatCompleted(DiffStatCompletion {
+                        workspace_path: feature_workspace_path(),
+                        insertions: 0,
+                        deletions: 0,
+                        conflict: None,
+                    }),
+                );
+
+                assert!(app.workspace_conflicts.is_empty());
+            }
+
+            #[test]
+            fn hand_conflict_to_agent_pastes_resolution_prompt_into_running_agent() {
+                let (mut app, commands, _captures, _cursor_captures) =
+                    fixture_app_with_tmux(WorkspaceStatus::Active, Vec::new());
+                select_workspace(&mut app, 1);
+                app.workspace_conflicts
+                    .insert(feature_workspace_path(), fixture_feature_conflict());
+
+                app.execute_ui_command(UiCommand::HandConflictToAgent);
+
+                let session = feature_workspace_session();
+                let commands = commands.borrow();
+                let Some(paste) = commands
+                    .iter()
+                    .find(|command| command.get(1).is_some_and(|arg| arg == "paste-buffer"))
+                else {
+                    panic!("conflict prompt should be pasted, got: {commands:?}");
+                };
+                assert_eq!(paste.get(3), Some(&session));
+                assert!(
+                    paste
+                        .get(4)
+                        .is_some_and(|prompt| prompt.contains("- src/a.rs"))
+                );
+                assert!(commands.contains(&vec![
+                    "tmux".to_string(),
+                    "send-keys".to_string(),
+                    "-t".to_string(),
+                    session,
+                    "Enter".to_string(),
+                ]));
+            }
+
+            #[test]
+            fn hand_conflict_to_agent_without_running_agent_shows_toast() {
+                let mut app = fixture_app();
+                select_workspace(&mut app, 1);
+                app.workspace_conflicts
+                    .insert(feature_workspace_path(), fixture_feature_conflict());
+
+                let palette_ids: Vec<String> = app
+                    .build_command_palette_actions()
+                    .into_iter()
+                    .map(|action| action.id)
+                    .collect();
+                assert!(!palette_ids.contains(&"palette:hand_conflict_to_agent".to_string()));
+                app.execute_ui_command(UiCommand::HandConflictToAgent);
+
+                assert!(
+                    app.status_bar_line()
+                        .contains("no running agent in this workspace")
+                );
+            }
+
             #[test]
             fn update_key_opens_update_from_base_dialog_for_selected_workspace() {
                 let mut app = fixture_app();
@@ -20544,6 +21879,34 @@ second row\n",
                 );
             }
 
+            #[test]
+            fn update_dialog_strategy_field_toggles_merge_and_rebase() {
+                let mut app = fixture_app();
+                select_workspace(&mut app, 1);
+                app.open_update_from_base_dialog();
+
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(
+                        KeyEvent::new(KeyCode::Char('p'))
+                            .with_modifiers(Modifiers::CTRL)
+                            .with_kind(KeyEventKind::Press),
+                    ),
+                );
+                assert_eq!(
+                    app.current_update_from_base_dialog_focus_field(),
+                    Some(UpdateFromBaseDialogField::Strategy)
+                );
+                ftui::Model::update(
+                    &mut app,
+                    Msg::Key(KeyEvent::new(KeyCode::Enter).with_kind(KeyEventKind::Press)),
+                );
+                assert_eq!(
+                    app.update_from_base_dialog().map(|dialog| dialog.strategy),
+                    Some(UpdateStrategy::Rebase)
+                );
+            }
+
             #[test]
             fn settings_dialog_ctrl_n_and_ctrl_p_cycle_fields() {
                 let mut app = fixture_app();
@@ -20861,6 +22224,7 @@ second row\n",
                 is_orphaned: false,
                 supported_agent: true,
                 pull_requests: Vec::new(),
+                ports: None,
             });
 
             // Add a workspace from a different repo (should NOT be counted).
@@ -20879,6 +22243,7 @@ second row\n",
                 is_orphaned: false,
                 supported_agent: true,
                 pull_requests: Vec::new(),
+                ports: None,
             });
 
             // Select the base workspace.
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet, VecDeque, hash_map::DefaultHasher};
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use ftui::core::event::{
@@ -43,10 +44,22 @@ use serde_json::Value;
 use crate::application::agent_runtime::capture::{
     evaluate_capture_change, tmux_capture_error_indicates_missing_session,
 };
+use crate::application::agent_runtime::command_tabs::{
+    command_tab_session_name, command_tab_shell_command, parse_command_tab_exit,
+};
+use crate::application::agent_runtime::pane_search::{
+    PaneSearchGroup, PaneSearchHit, PaneSearchSource, pane_search_lines, search_panes,
+};
 use crate::application::agent_runtime::status::WorkspaceStatusObservation;
+use crate::application::agent_runtime::{AgentHookEvent, read_status_event};
+use crate::application::agent_runtime::transcript::{
+    TranscriptFile, list_transcripts, read_transcript, search_transcript_lines,
+    transcript_lines, transcripts_dir,
+};
+use crate::application::agent_runtime::usage::{UsagePriceTable, UsageTotals, UsageTracker};
 use crate::application::agent_runtime::{
     CommandExecutionMode, LivePreviewTarget, OutputDigest, SessionActivity, ShellLaunchRequest,
-    TaskLaunchRequest, WorkspaceStatusTarget, execute_command_with, git_session_name_for_workspace,
+    StatusRules, TaskLaunchRequest, WorkspaceStatusTarget, execute_command_with, git_session_name_for_workspace,
     infer_workspace_permission_mode, poll_interval,
     restart_workspace_in_pane_with_io, session_name_for_task, session_name_for_workspace_ref,
     shell_session_name_for_workspace, tmux_launch_error_indicates_duplicate_session,
@@ -59,36 +72,59 @@ use crate::application::agent_runtime::{
     execute_stop_task_with_result_for_mode, execute_stop_workspace_with_result_for_mode,
     execute_task_launch_request_with_result_for_mode, latest_assistant_attention_marker,
     launch_request_for_workspace, shell_launch_request_for_workspace,
-    status::detect_waiting_prompt,
+    status::{detect_waiting_rule, waiting_is_permission_wall},
 };
+use crate::application::agent_control::project_sandbox_for_workspace;
+use crate::application::hooks::{HookContext, HookInvocation, HookOutcome};
 use crate::application::interactive::{
-    InteractiveAction, InteractiveKey, InteractiveState, encode_paste_payload,
-    multiplexer_send_input_command,
+    InteractiveAction, InteractiveKey, InteractiveState, broadcast_accepts_status,
+    submit_prompt_commands, encode_paste_payload, multiplexer_send_input_command,
+};
+use crate::application::pull_request_sync::{
+    PullRequestEventKind, PullRequestForge, PullRequestSyncOutcome, apply_pull_request_updates,
+    persist_pull_request_updates, sync_task_pull_requests,
+};
+use crate::application::notifications::{
+    Notification, NotificationDispatcher, deliver as deliver_notification, local_minute_of_day,
 };
 use crate::application::preview::PreviewState;
+use crate::application::prompt_queue::{
+    PromptQueue, PromptQueueTrigger, enqueue_prompt, load_prompt_queue, prompt_queue_trigger,
+    save_prompt_queue,
+};
+use crate::application::recipes::{Recipe, load_recipes, write_recipe_markers};
 use crate::application::session_cleanup::{
     SessionCleanupOptions, SessionCleanupPlan, SessionCleanupReason, apply_session_cleanup,
 };
 use crate::application::task_lifecycle::{
-    AddWorktreeToTaskRequest, AddWorktreeToTaskResult, CreateTaskRequest, CreateTaskResult,
-    DeleteTaskRequest, TaskLifecycleError, create_task, create_task_in_root, delete_task,
+    AddWorktreeToTaskRequest, AddWorktreeToTaskResult, ArchivedTab, CreateTaskRequest,
+    CreateTaskResult, DeleteTaskRequest, TaskLifecycleError, archive_task, create_task,
+    create_task_in_root, delete_task, list_task_archives, restore_task,
     task_lifecycle_error_message,
 };
 use crate::application::workspace_lifecycle::{
     CommandGitRunner, CommandSetupCommandRunner, CommandSetupScriptRunner, DeleteWorkspaceRequest,
-    MergeWorkspaceRequest, RuntimeSessionTerminator, UpdateWorkspaceFromBaseRequest,
-    WorkspaceLifecycleError, delete_workspace, merge_workspace_with_terminator,
-    update_workspace_from_base_with_terminator, workspace_lifecycle_error_message,
-    write_workspace_base_marker,
+    IntegrationOperation, MergeWorkspaceRequest, RuntimeSessionTerminator,
+    UpdateWorkspaceFromBaseRequest, WorkspaceConflict, WorkspaceLifecycleError,
+    abort_workspace_conflict, conflict_resolution_prompt,
+    continue_workspace_conflict_with_terminator, delete_workspace, detect_workspace_conflict,
+    merge_workspace_with_terminator, update_workspace_from_base_with_terminator,
+    workspace_lifecycle_error_message, write_workspace_base_marker,
+};
+use crate::domain::{
+    AgentRegistry, AgentType, MergeStrategy, PermissionMode, PullRequestChecks, PullRequestStatus, Task,
+    UpdateStrategy, Workspace, WorkspaceStatus,
 };
-use crate::domain::{AgentType, PermissionMode, Task, Workspace, WorkspaceStatus};
 use crate::infrastructure::adapters::DiscoveryState;
 use crate::infrastructure::config::{
-    AgentEnvDefaults, GroveConfig, ProjectConfig, ThemeName, WorkspaceAttentionAckConfig,
+    AgentEnvDefaults, CommandTabConfig, GroveConfig, HookEvent, NotificationReason, NotificationSinkConfig,
+    ProjectConfig, SandboxConfig, ThemeName, WorkspaceAttentionAckConfig,
 };
 use crate::infrastructure::event_log::{Event as LogEvent, EventLogger, now_millis};
 use crate::infrastructure::paths::refer_to_same_location;
 use crate::infrastructure::process_metrics::{ProcessMetricsSampler, ProcessMetricsSnapshot};
+use crate::infrastructure::state_store::{AttentionRecord, StateStore, TaskEventRecord};
+use crate::infrastructure::themes::ThemeRegistry;
 use crate::ui::mouse::{clamp_sidebar_ratio, ratio_from_drag};
 use crate::ui::state::{Action, AppState, UiMode};
 use performance::DurationWindow;
@@ -143,6 +179,8 @@ enum AttentionReason {
     SessionEnded,
     Finished,
     Stalled,
+    ChecksFailed,
+    PullRequestMerged,
 }
 
 impl AttentionReason {
@@ -153,6 +191,8 @@ impl AttentionReason {
             Self::SessionEnded => 2,
             Self::Finished => 3,
             Self::Stalled => 4,
+            Self::ChecksFailed => 5,
+            Self::PullRequestMerged => 6,
         }
     }
 
@@ -163,6 +203,20 @@ impl AttentionReason {
             Self::SessionEnded => "session ended unexpectedly",
             Self::Finished => "finished, awaiting review",
             Self::Stalled => "stalled, no output",
+            Self::ChecksFailed => "CI checks failing",
+            Self::PullRequestMerged => "pull request merged",
+        }
+    }
+
+    const fn notification_reason(self) -> NotificationReason {
+        match self {
+            Self::BlockedOnQuestion => NotificationReason::BlockedOnQuestion,
+            Self::PermissionWall => NotificationReason::PermissionWall,
+            Self::SessionEnded => NotificationReason::SessionEnded,
+            Self::Finished => NotificationReason::Finished,
+            Self::Stalled => NotificationReason::Stalled,
+            Self::ChecksFailed => NotificationReason::ChecksFailed,
+            Self::PullRequestMerged => NotificationReason::PullRequestMerged,
         }
     }
 }
@@ -259,12 +313,19 @@ enum ActiveDialog {
     Project(Box<ProjectDialogState>),
     Settings(SettingsDialogState),
     Performance(PerformanceDialogState),
+    Transcript(TranscriptDialogState),
+    PaneSearch(PaneSearchDialogState),
+    Broadcast(BroadcastDialogState),
+    PromptQueue(PromptQueueDialogState),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PaletteMode {
     Command,
     WorkspaceJump,
+    RestoreArchive,
+    Transcript,
+    CommandTab,
 }
 
 struct SessionState {
@@ -281,6 +342,12 @@ struct SessionState {
     pending_restart_workspace_path: Option<PathBuf>,
 }
 
+/// What a worktree looked like when it was sent a queued prompt.
+struct PromptQueueSend {
+    hook_event: Option<AgentHookEvent>,
+    polls_since: u8,
+}
+
 struct PollingState {
     output_changing: bool,
     agent_output_changing: bool,
@@ -295,7 +362,11 @@ struct PollingState {
     #[cfg(test)]
     workspace_status_observation_overrides: HashMap<PathBuf, WorkspaceStatusObservation>,
     workspace_waiting_prompts: HashMap<PathBuf, String>,
+    /// Attention set by the status rule behind a waiting prompt.
+    workspace_waiting_attention: HashMap<PathBuf, NotificationReason>,
     workspace_idle_polls_since_output: HashMap<PathBuf, u8>,
+    /// Worktrees that were sent a queued prompt and have not started a new turn since.
+    prompt_queue_sent: HashMap<PathBuf, PromptQueueSend>,
     next_tick_due_at: Option<Instant>,
     next_tick_interval_ms: Option<u64>,
     next_tick_source: Option<String>,
@@ -313,6 +384,13 @@ struct PollingState {
     last_diff_stat_poll_at: Option<Instant>,
     diff_capture_in_flight: bool,
     diff_stat_in_flight: bool,
+    last_pull_request_sync_at: Option<Instant>,
+    pull_request_sync_in_flight: bool,
+    last_home_dashboard_poll_at: Option<Instant>,
+    home_dashboard_poll_in_flight: bool,
+    last_usage_poll_at: Option<Instant>,
+    last_command_tab_poll_at: Option<Instant>,
+    usage_poll_in_flight: bool,
     preview_stream: PreviewStreamState,
 }
 
@@ -322,6 +400,22 @@ struct DiffStatBadge {
     deletions: usize,
 }
 
+/// Data behind the Home tab dashboard, refreshed while the tab is shown.
+#[derive(Debug, Default)]
+struct HomeDashboardState {
+    task_slug: Option<String>,
+    worktree_stats: HashMap<PathBuf, HomeDashboardWorktreeStats>,
+    session_started_at: HashMap<String, i64>,
+    recent_events: Vec<TaskEventRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HomeDashboardTarget {
+    Worktree(PathBuf),
+    Tab { workspace_path: PathBuf, tab_id: u64 },
+    PullRequest(String),
+}
+
 struct PerformanceState {
     redraw_timing: RefCell<DurationWindow>,
     draw_timing: RefCell<DurationWindow>,
@@ -346,9 +440,11 @@ struct DialogState {
     delete_in_flight_workspace: Option<PathBuf>,
     pending_delete_workspaces: VecDeque<QueuedDeleteWorkspace>,
     delete_requested_workspaces: HashSet<PathBuf>,
+    archive_in_flight: bool,
     merge_in_flight: bool,
     update_from_base_in_flight: bool,
     pull_upstream_in_flight: bool,
+    conflict_action_in_flight: bool,
     create_in_flight: bool,
     start_in_flight: bool,
     stop_in_flight: bool,
@@ -363,6 +459,7 @@ struct TelemetryState {
     last_frame_hash: RefCell<u64>,
     input_seq_counter: u64,
     deferred_cmds: Vec<Cmd<Msg>>,
+    state_store: Option<StateStore>,
 }
 
 struct GroveApp {
@@ -380,6 +477,12 @@ struct GroveApp {
     preview: PreviewState,
     notifications: NotificationQueue,
     action_mapper: ActionMapper,
+    keymap: Keymap,
+    /// Built-in and config-defined agents, shared with background tasks.
+    agents: Arc<AgentRegistry>,
+    /// Config status rules and the built-ins, shared with background tasks.
+    status_rules: Arc<StatusRules>,
+    pending_key_sequence: PendingKeySequence,
     dialogs: DialogState,
     tmux_input: Box<dyn TmuxInput>,
     config_path: PathBuf,
@@ -391,6 +494,24 @@ struct GroveApp {
     attention_observations: HashMap<PathBuf, AttentionObservation>,
     attention_items: Vec<AttentionItem>,
     selected_attention_item: Option<usize>,
+    attention_notifier: NotificationDispatcher,
+    pull_request_forge: Option<Arc<dyn PullRequestForge>>,
+    pull_request_sync_interval: Duration,
+    pull_request_alerts: HashMap<PathBuf, AttentionItem>,
+    home_dashboard: HomeDashboardState,
+    usage_tracker: Arc<Mutex<UsageTracker>>,
+    usage_prices: UsagePriceTable,
+    /// Where agent session logs are read from; unset disables usage polling.
+    usage_home_dir: Option<PathBuf>,
+    worktree_usage: HashMap<PathBuf, UsageTotals>,
+    transcripts_enabled: bool,
+    /// Whether launches install the agent hooks that report status.
+    status_hooks_enabled: bool,
+    pane_search_reveal: Option<PaneSearchReveal>,
+    /// Workspaces marked in the sidebar as broadcast targets.
+    broadcast_targets: HashSet<PathBuf>,
+    /// Created worktrees whose `auto_start` command tabs launch after the next refresh.
+    pending_command_tab_autostart: Vec<PathBuf>,
     interactive_preview_reset_pending: bool,
     startup_attention_focus_pending: bool,
     #[cfg(test)]
@@ -400,6 +521,8 @@ struct GroveApp {
     sidebar_width_pct: u16,
     panes: panes::GrovePaneModel,
     theme_name: ThemeName,
+    /// Built-in and custom themes the settings dialog cycles through.
+    themes: ThemeRegistry,
     sidebar_hidden: bool,
     mouse_capture_enabled: bool,
     launch_permission_mode: PermissionMode,
@@ -417,6 +540,7 @@ struct GroveApp {
     sidebar_list_state: RefCell<VirtualizedListState>,
     last_sidebar_mouse_scroll_at: Option<Instant>,
     workspace_diff_stats: HashMap<PathBuf, DiffStatBadge>,
+    workspace_conflicts: HashMap<PathBuf, WorkspaceConflict>,
     last_sidebar_mouse_scroll_delta: i8,
     focus_manager: FocusManager,
     #[cfg(test)]
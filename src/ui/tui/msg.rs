@@ -19,6 +19,8 @@ pub(super) enum Msg {
     RefreshWorkspacesCompleted(RefreshWorkspacesCompletion),
     DeleteProjectCompleted(DeleteProjectCompletion),
     DeleteWorkspaceCompleted(DeleteWorkspaceCompletion),
+    TaskArchived(TaskArchiveCompletion),
+    TaskRestored(TaskRestoreCompletion),
     MergeWorkspaceCompleted(MergeWorkspaceCompletion),
     UpdateWorkspaceFromBaseCompleted(UpdateWorkspaceFromBaseCompletion),
     PullUpstreamCompleted(PullUpstreamCompletion),
@@ -29,14 +31,76 @@ pub(super) enum Msg {
     InteractiveSendCompleted(InteractiveSendCompletion),
     DiffCaptureCompleted(DiffCaptureCompletion),
     DiffStatCompleted(DiffStatCompletion),
+    WorkspaceConflictActionCompleted(WorkspaceConflictActionCompletion),
+    PullRequestsSynced(PullRequestSyncOutcome),
+    HomeDashboardPolled(HomeDashboardCompletion),
+    UsagePolled(UsagePollCompletion),
+    NotificationsDelivered(NotificationDeliveryCompletion),
+    HooksCompleted(Box<HooksCompletion>),
     Noop,
 }
 
+impl Msg {
+    /// Logs `outcomes` before handling this message, unchanged when there are none.
+    pub(super) fn with_hook_outcomes(self, outcomes: Vec<HookOutcome>) -> Self {
+        if outcomes.is_empty() {
+            return self;
+        }
+        Self::HooksCompleted(Box::new(HooksCompletion {
+            outcomes,
+            next: Some(self),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HooksCompletion {
+    pub(super) outcomes: Vec<HookOutcome>,
+    pub(super) next: Option<Msg>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HomeDashboardCompletion {
+    pub(super) task_slug: String,
+    pub(super) worktrees: Vec<HomeDashboardWorktreeStats>,
+    /// Session name and tmux creation time, in unix seconds.
+    pub(super) session_started_at: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BranchDivergence {
+    pub(super) ahead: usize,
+    pub(super) behind: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HomeDashboardWorktreeStats {
+    pub(super) worktree_path: PathBuf,
+    pub(super) divergence: Option<BranchDivergence>,
+    pub(super) files_changed: usize,
+    pub(super) insertions: usize,
+    pub(super) deletions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct UsagePollCompletion {
+    pub(super) worktrees: Vec<(PathBuf, UsageTotals)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NotificationDeliveryCompletion {
+    pub(super) workspace_path: PathBuf,
+    pub(super) reason: String,
+    pub(super) sinks: Vec<String>,
+    pub(super) failures: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct DiffStatCompletion {
     pub(super) workspace_path: PathBuf,
     pub(super) insertions: usize,
     pub(super) deletions: usize,
+    pub(super) conflict: Option<WorkspaceConflict>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -135,6 +199,25 @@ pub(super) struct DeleteWorkspaceCompletion {
     pub(super) warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TaskArchiveCompletion {
+    pub(super) task_name: String,
+    pub(super) task_slug: String,
+    pub(super) result: Result<(), String>,
+    pub(super) warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TaskRestoreCompletion {
+    pub(super) task_name: String,
+    pub(super) task_slug: String,
+    pub(super) workspace_path: Option<PathBuf>,
+    /// Agent tabs that were open at archive time and can resume their conversation.
+    pub(super) resumable_tabs: Vec<(PathBuf, ArchivedTab)>,
+    pub(super) result: Result<(), String>,
+    pub(super) warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct DeleteProjectCompletion {
     pub(super) project_name: String,
@@ -152,6 +235,7 @@ pub(super) struct MergeWorkspaceCompletion {
     pub(super) base_branch: String,
     pub(super) result: Result<(), String>,
     pub(super) warnings: Vec<String>,
+    pub(super) conflict: Option<WorkspaceConflict>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -162,6 +246,7 @@ pub(super) struct UpdateWorkspaceFromBaseCompletion {
     pub(super) base_branch: String,
     pub(super) result: Result<(), String>,
     pub(super) warnings: Vec<String>,
+    pub(super) conflict: Option<WorkspaceConflict>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -215,6 +300,21 @@ pub(super) struct RestartAgentCompletion {
     pub(super) result: Result<(), String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WorkspaceConflictAction {
+    Continue,
+    Abort,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct WorkspaceConflictActionCompletion {
+    pub(super) workspace_name: String,
+    pub(super) workspace_path: PathBuf,
+    pub(super) action: WorkspaceConflictAction,
+    pub(super) result: Result<IntegrationOperation, String>,
+    pub(super) warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct DiffCaptureCompletion {
     pub(super) workspace_path: PathBuf,
@@ -11,11 +11,16 @@ use serde_json::Value;
 
 use super::bootstrap_config::AppDependencies;
 use super::*;
+use crate::application::agent_runtime::usage::TokenUsage;
+use crate::application::pull_request_sync::{PullRequestEvent, PullRequestSyncUpdate};
 use crate::application::task_lifecycle::{
     CreateTaskRequest, CreateTaskResult, TaskBranchSource, TaskLifecycleError,
 };
-use crate::domain::{PermissionMode, PullRequest, PullRequestStatus, Task, Worktree};
-use crate::infrastructure::config::ThemeName;
+use crate::domain::{
+    CustomAgent, PermissionMode, PullRequest, PullRequestChecks, PullRequestReview,
+    PullRequestStatus, Task, Worktree,
+};
+use crate::infrastructure::config::ThemeKey;
 
 const REPLAY_SCHEMA_VERSION: u64 = 1;
 const REPLAY_FIXTURE_DIRECTORY: &str = "tests/fixtures/replay";
@@ -102,6 +107,7 @@ mod tests {
                         pull_requests: Vec::new(),
                     },
                 ],
+                recipe: None,
             }],
             selected_task_index: 0,
             selected_worktree_index: 0,
@@ -114,7 +120,7 @@ mod tests {
             sidebar_hidden: false,
             mouse_capture_enabled: true,
             launch_permission_mode: PermissionMode::Default,
-            theme_name: ThemeName::CatppuccinMocha,
+            theme_name: ThemeName::CatppuccinMocha.into(),
         }
     }
 
@@ -16,7 +16,7 @@ struct ReplayBootstrapSnapshot {
     mouse_capture_enabled: bool,
     launch_permission_mode: PermissionMode,
     #[serde(default)]
-    theme_name: ThemeName,
+    theme_name: ThemeKey,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -56,6 +56,10 @@ struct ReplayPullRequest {
     number: u64,
     url: String,
     status: ReplayPullRequestStatus,
+    #[serde(default)]
+    review: Option<ReplayPullRequestReview>,
+    #[serde(default)]
+    checks: Option<ReplayPullRequestChecks>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -68,9 +72,26 @@ enum ReplayPullRequestStatus {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+enum ReplayPullRequestReview {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ReplayPullRequestChecks {
+    Pending,
+    Passing,
+    Failing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 enum ReplayAgentType {
     Claude,
     Codex,
+    Custom(String),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -105,7 +126,7 @@ impl ReplayBootstrapSnapshot {
             sidebar_hidden: app.sidebar_hidden,
             mouse_capture_enabled: app.mouse_capture_enabled,
             launch_permission_mode: app.launch_permission_mode,
-            theme_name: app.theme_name,
+            theme_name: app.theme_name.clone().into(),
         }
     }
 
@@ -189,17 +210,19 @@ impl ReplayDiscoveryState {
 }
 
 impl ReplayAgentType {
-    fn from_agent_type(agent: AgentType) -> Self {
+    fn from_agent_type(agent: &AgentType) -> Self {
         match agent {
             AgentType::Claude => Self::Claude,
             AgentType::Codex => Self::Codex,
+            AgentType::Custom(agent) => Self::Custom(agent.marker().to_string()),
         }
     }
 
-    fn to_agent_type(self) -> AgentType {
+    fn to_agent_type(&self) -> AgentType {
         match self {
             Self::Claude => AgentType::Claude,
             Self::Codex => AgentType::Codex,
+            Self::Custom(marker) => AgentType::Custom(CustomAgent::undefined(marker)),
         }
     }
 }
@@ -252,12 +275,54 @@ impl ReplayPullRequestStatus {
     }
 }
 
+impl ReplayPullRequestReview {
+    fn from_pull_request_review(review: PullRequestReview) -> Self {
+        match review {
+            PullRequestReview::Approved => Self::Approved,
+            PullRequestReview::ChangesRequested => Self::ChangesRequested,
+            PullRequestReview::ReviewRequired => Self::ReviewRequired,
+        }
+    }
+
+    fn to_pull_request_review(self) -> PullRequestReview {
+        match self {
+            Self::Approved => PullRequestReview::Approved,
+            Self::ChangesRequested => PullRequestReview::ChangesRequested,
+            Self::ReviewRequired => PullRequestReview::ReviewRequired,
+        }
+    }
+}
+
+impl ReplayPullRequestChecks {
+    fn from_pull_request_checks(checks: PullRequestChecks) -> Self {
+        match checks {
+            PullRequestChecks::Pending => Self::Pending,
+            PullRequestChecks::Passing => Self::Passing,
+            PullRequestChecks::Failing => Self::Failing,
+        }
+    }
+
+    fn to_pull_request_checks(self) -> PullRequestChecks {
+        match self {
+            Self::Pending => PullRequestChecks::Pending,
+            Self::Passing => PullRequestChecks::Passing,
+            Self::Failing => PullRequestChecks::Failing,
+        }
+    }
+}
+
 impl ReplayPullRequest {
     fn from_pull_request(pull_request: &PullRequest) -> Self {
         Self {
             number: pull_request.number,
             url: pull_request.url.clone(),
             status: ReplayPullRequestStatus::from_pull_request_status(pull_request.status),
+            review: pull_request
+                .review
+                .map(ReplayPullRequestReview::from_pull_request_review),
+            checks: pull_request
+                .checks
+                .map(ReplayPullRequestChecks::from_pull_request_checks),
         }
     }
 
@@ -266,6 +331,12 @@ impl ReplayPullRequest {
             number: self.number,
             url: self.url.clone(),
             status: self.status.to_pull_request_status(),
+            review: self
+                .review
+                .map(ReplayPullRequestReview::to_pull_request_review),
+            checks: self
+                .checks
+                .map(ReplayPullRequestChecks::to_pull_request_checks),
         }
     }
 }
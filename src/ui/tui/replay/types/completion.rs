@@ -83,6 +83,25 @@ struct ReplayDeleteWorkspaceCompletion {
     warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayTaskArchiveCompletion {
+    task_name: String,
+    task_slug: String,
+    result: ReplayUnitResult,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayTaskRestoreCompletion {
+    task_name: String,
+    task_slug: String,
+    workspace_path: Option<PathBuf>,
+    #[serde(default)]
+    resumable_tabs: Vec<(PathBuf, ArchivedTab)>,
+    result: ReplayUnitResult,
+    warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 struct ReplayMergeWorkspaceCompletion {
     workspace_name: String,
@@ -91,6 +110,8 @@ struct ReplayMergeWorkspaceCompletion {
     base_branch: String,
     result: ReplayUnitResult,
     warnings: Vec<String>,
+    #[serde(default)]
+    conflict: Option<ReplayWorkspaceConflict>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -101,6 +122,115 @@ struct ReplayUpdateWorkspaceFromBaseCompletion {
     base_branch: String,
     result: ReplayUnitResult,
     warnings: Vec<String>,
+    #[serde(default)]
+    conflict: Option<ReplayWorkspaceConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayWorkspaceConflict {
+    operation: ReplayIntegrationOperation,
+    worktree_path: PathBuf,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ReplayIntegrationOperation {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ReplayWorkspaceConflictAction {
+    Continue,
+    Abort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum ReplayIntegrationOperationResult {
+    Ok { operation: ReplayIntegrationOperation },
+    Err { error: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayWorkspaceConflictActionCompletion {
+    workspace_name: String,
+    workspace_path: PathBuf,
+    action: ReplayWorkspaceConflictAction,
+    result: ReplayIntegrationOperationResult,
+    #[serde(default)]
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayPullRequestSyncCompletion {
+    checked: usize,
+    updates: Vec<ReplayPullRequestSyncUpdate>,
+    events: Vec<ReplayPullRequestEvent>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayPullRequestSyncUpdate {
+    task_root: PathBuf,
+    worktree_path: PathBuf,
+    pull_requests: Vec<ReplayPullRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayPullRequestEvent {
+    worktree_path: PathBuf,
+    kind: ReplayPullRequestEventKind,
+    pull_request: ReplayPullRequest,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ReplayPullRequestEventKind {
+    Merged,
+    ChecksFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayHomeDashboardCompletion {
+    task_slug: String,
+    worktrees: Vec<ReplayHomeDashboardWorktreeStats>,
+    session_started_at: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayHomeDashboardWorktreeStats {
+    worktree_path: PathBuf,
+    #[serde(default)]
+    ahead: Option<usize>,
+    #[serde(default)]
+    behind: Option<usize>,
+    files_changed: usize,
+    insertions: usize,
+    deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayUsagePollCompletion {
+    worktrees: Vec<ReplayWorktreeUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayWorktreeUsage {
+    worktree_path: PathBuf,
+    models: Vec<ReplayModelUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayModelUsage {
+    model: String,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_write_tokens: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -131,6 +261,8 @@ struct ReplayCreateWorkspaceRequest {
     repositories: Vec<ProjectConfig>,
     agent: ReplayAgentType,
     branch_source: ReplayTaskBranchSource,
+    #[serde(default)]
+    recipe: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -189,6 +321,7 @@ enum ReplayTaskLifecycleError {
     TaskAlreadyHasRepository(String),
     GitCommandFailed(String),
     Io(String),
+    HookVetoed(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -198,6 +331,8 @@ struct ReplayTask {
     root_path: PathBuf,
     branch: String,
     worktrees: Vec<ReplayWorktree>,
+    #[serde(default)]
+    recipe: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -223,6 +358,19 @@ struct ReplaySessionCompletion {
     result: ReplayUnitResult,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct ReplayHookOutcome {
+    event: HookEvent,
+    project: String,
+    command: String,
+    exit_code: Option<i32>,
+    timed_out: bool,
+    duration_ms: u64,
+    stdout: String,
+    stderr: String,
+    error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 struct ReplayInteractiveSendCompletion {
     send: ReplayQueuedInteractiveSend,
@@ -459,6 +607,50 @@ impl ReplayDeleteProjectCompletion {
     }
 }
 
+impl ReplayTaskArchiveCompletion {
+    fn from_completion(completion: &TaskArchiveCompletion) -> Self {
+        Self {
+            task_name: completion.task_name.clone(),
+            task_slug: completion.task_slug.clone(),
+            result: ReplayUnitResult::from_result(&completion.result),
+            warnings: completion.warnings.clone(),
+        }
+    }
+
+    fn to_completion(&self) -> TaskArchiveCompletion {
+        TaskArchiveCompletion {
+            task_name: self.task_name.clone(),
+            task_slug: self.task_slug.clone(),
+            result: self.result.to_result(),
+            warnings: self.warnings.clone(),
+        }
+    }
+}
+
+impl ReplayTaskRestoreCompletion {
+    fn from_completion(completion: &TaskRestoreCompletion) -> Self {
+        Self {
+            task_name: completion.task_name.clone(),
+            task_slug: completion.task_slug.clone(),
+            workspace_path: completion.workspace_path.clone(),
+            resumable_tabs: completion.resumable_tabs.clone(),
+            result: ReplayUnitResult::from_result(&completion.result),
+            warnings: completion.warnings.clone(),
+        }
+    }
+
+    fn to_completion(&self) -> TaskRestoreCompletion {
+        TaskRestoreCompletion {
+            task_name: self.task_name.clone(),
+            task_slug: self.task_slug.clone(),
+            workspace_path: self.workspace_path.clone(),
+            resumable_tabs: self.resumable_tabs.clone(),
+            result: self.result.to_result(),
+            warnings: self.warnings.clone(),
+        }
+    }
+}
+
 impl ReplayDeleteWorkspaceCompletion {
     fn from_completion(completion: &DeleteWorkspaceCompletion) -> Self {
         Self {
@@ -494,6 +686,10 @@ impl ReplayMergeWorkspaceCompletion {
             base_branch: completion.base_branch.clone(),
             result: ReplayUnitResult::from_result(&completion.result),
             warnings: completion.warnings.clone(),
+            conflict: completion
+                .conflict
+                .as_ref()
+                .map(ReplayWorkspaceConflict::from_conflict),
         }
     }
 
@@ -505,6 +701,10 @@ impl ReplayMergeWorkspaceCompletion {
             base_branch: self.base_branch.clone(),
             result: self.result.to_result(),
             warnings: self.warnings.clone(),
+            conflict: self
+                .conflict
+                .as_ref()
+                .map(ReplayWorkspaceConflict::to_conflict),
         }
     }
 }
@@ -518,6 +718,10 @@ impl ReplayUpdateWorkspaceFromBaseCompletion {
             base_branch: completion.base_branch.clone(),
             result: ReplayUnitResult::from_result(&completion.result),
             warnings: completion.warnings.clone(),
+            conflict: completion
+                .conflict
+                .as_ref()
+                .map(ReplayWorkspaceConflict::from_conflict),
         }
     }
 
@@ -529,6 +733,10 @@ impl ReplayUpdateWorkspaceFromBaseCompletion {
             base_branch: self.base_branch.clone(),
             result: self.result.to_result(),
             warnings: self.warnings.clone(),
+            conflict: self
+                .conflict
+                .as_ref()
+                .map(ReplayWorkspaceConflict::to_conflict),
         }
     }
 }
@@ -577,6 +785,7 @@ impl ReplayCreateWorkspaceCompletion {
                     repositories: Vec::new(),
                     agent: ReplayAgentType::Codex,
                     branch_source: ReplayTaskBranchSource::BaseBranch,
+                    recipe: None,
                 },
                 result: ReplayCreateWorkspaceResult::Err {
                     error: ReplayTaskLifecycleError::TaskInvalid(
@@ -606,8 +815,9 @@ impl ReplayCreateWorkspaceRequest {
         Self {
             task_name: request.task_name.clone(),
             repositories: request.repositories.clone(),
-            agent: ReplayAgentType::from_agent_type(request.agent),
+            agent: ReplayAgentType::from_agent_type(&request.agent),
             branch_source: ReplayTaskBranchSource::from_branch_source(&request.branch_source),
+            recipe: request.recipe.clone(),
         }
     }
 
@@ -617,6 +827,7 @@ impl ReplayCreateWorkspaceRequest {
             repositories: self.repositories.clone(),
             agent: self.agent.to_agent_type(),
             branch_source: self.branch_source.to_branch_source(),
+            recipe: self.recipe.clone(),
         }
     }
 }
@@ -626,7 +837,7 @@ impl ReplayAddWorktreeRequest {
         Self {
             task: ReplayTask::from_task(&request.task),
             repository: request.repository.clone(),
-            agent: ReplayAgentType::from_agent_type(request.agent),
+            agent: ReplayAgentType::from_agent_type(&request.agent),
         }
     }
 
@@ -726,6 +937,36 @@ impl ReplayAddWorktreeResult {
     }
 }
 
+impl ReplayHookOutcome {
+    fn from_outcome(outcome: &HookOutcome) -> Self {
+        Self {
+            event: outcome.event,
+            project: outcome.project.clone(),
+            command: outcome.command.clone(),
+            exit_code: outcome.exit_code,
+            timed_out: outcome.timed_out,
+            duration_ms: outcome.duration_ms,
+            stdout: outcome.stdout.clone(),
+            stderr: outcome.stderr.clone(),
+            error: outcome.error.clone(),
+        }
+    }
+
+    fn to_outcome(&self) -> HookOutcome {
+        HookOutcome {
+            event: self.event,
+            project: self.project.clone(),
+            command: self.command.clone(),
+            exit_code: self.exit_code,
+            timed_out: self.timed_out,
+            duration_ms: self.duration_ms,
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
 impl ReplayTaskLifecycleError {
     fn from_error(error: &TaskLifecycleError) -> Self {
         match error {
@@ -747,6 +988,7 @@ impl ReplayTaskLifecycleError {
                 Self::GitCommandFailed(message.clone())
             }
             TaskLifecycleError::Io(message) => Self::Io(message.clone()),
+            TaskLifecycleError::HookVetoed(message) => Self::HookVetoed(message.clone()),
         }
     }
 
@@ -770,6 +1012,7 @@ impl ReplayTaskLifecycleError {
                 TaskLifecycleError::GitCommandFailed(message.clone())
             }
             Self::Io(message) => TaskLifecycleError::Io(message.clone()),
+            Self::HookVetoed(message) => TaskLifecycleError::HookVetoed(message.clone()),
         }
     }
 }
@@ -786,6 +1029,7 @@ impl ReplayTask {
                 .iter()
                 .map(ReplayWorktree::from_worktree)
                 .collect(),
+            recipe: task.recipe.clone(),
         }
     }
 
@@ -801,6 +1045,7 @@ impl ReplayTask {
                 .collect(),
         )
         .expect("replay task should decode")
+        .with_recipe(self.recipe.clone())
     }
 }
 
@@ -813,7 +1058,7 @@ impl ReplayWorktree {
             branch: worktree.branch.clone(),
             base_branch: worktree.base_branch.clone(),
             last_activity_unix_secs: worktree.last_activity_unix_secs,
-            agent: ReplayAgentType::from_agent_type(worktree.agent),
+            agent: ReplayAgentType::from_agent_type(&worktree.agent),
             status: ReplayWorkspaceStatus::from_workspace_status(worktree.status),
             is_orphaned: worktree.is_orphaned,
             supported_agent: worktree.supported_agent,
@@ -969,6 +1214,240 @@ impl ReplayInputTraceContext {
     }
 }
 
+impl ReplayWorkspaceConflict {
+    fn from_conflict(conflict: &WorkspaceConflict) -> Self {
+        Self {
+            operation: ReplayIntegrationOperation::from_operation(conflict.operation),
+            worktree_path: conflict.worktree_path.clone(),
+            files: conflict.files.clone(),
+        }
+    }
+
+    fn to_conflict(&self) -> WorkspaceConflict {
+        WorkspaceConflict {
+            operation: self.operation.to_operation(),
+            worktree_path: self.worktree_path.clone(),
+            files: self.files.clone(),
+        }
+    }
+}
+
+impl ReplayIntegrationOperation {
+    fn from_operation(operation: IntegrationOperation) -> Self {
+        match operation {
+            IntegrationOperation::Merge => Self::Merge,
+            IntegrationOperation::Squash => Self::Squash,
+            IntegrationOperation::Rebase => Self::Rebase,
+        }
+    }
+
+    fn to_operation(self) -> IntegrationOperation {
+        match self {
+            Self::Merge => IntegrationOperation::Merge,
+            Self::Squash => IntegrationOperation::Squash,
+            Self::Rebase => IntegrationOperation::Rebase,
+        }
+    }
+}
+
+impl ReplayWorkspaceConflictActionCompletion {
+    fn from_completion(completion: &WorkspaceConflictActionCompletion) -> Self {
+        Self {
+            workspace_name: completion.workspace_name.clone(),
+            workspace_path: completion.workspace_path.clone(),
+            action: match completion.action {
+                WorkspaceConflictAction::Continue => ReplayWorkspaceConflictAction::Continue,
+                WorkspaceConflictAction::Abort => ReplayWorkspaceConflictAction::Abort,
+            },
+            result: match &completion.result {
+                Ok(operation) => ReplayIntegrationOperationResult::Ok {
+                    operation: ReplayIntegrationOperation::from_operation(*operation),
+                },
+                Err(error) => ReplayIntegrationOperationResult::Err {
+                    error: error.clone(),
+                },
+            },
+            warnings: completion.warnings.clone(),
+        }
+    }
+
+    fn to_completion(&self) -> WorkspaceConflictActionCompletion {
+        WorkspaceConflictActionCompletion {
+            workspace_name: self.workspace_name.clone(),
+            workspace_path: self.workspace_path.clone(),
+            action: match self.action {
+                ReplayWorkspaceConflictAction::Continue => WorkspaceConflictAction::Continue,
+                ReplayWorkspaceConflictAction::Abort => WorkspaceConflictAction::Abort,
+            },
+            result: match &self.result {
+                ReplayIntegrationOperationResult::Ok { operation } => Ok(operation.to_operation()),
+                ReplayIntegrationOperationResult::Err { error } => Err(error.clone()),
+            },
+            warnings: self.warnings.clone(),
+        }
+    }
+}
+
+impl ReplayPullRequestSyncCompletion {
+    fn from_outcome(outcome: &PullRequestSyncOutcome) -> Self {
+        Self {
+            checked: outcome.checked,
+            updates: outcome
+                .updates
+                .iter()
+                .map(|update| ReplayPullRequestSyncUpdate {
+                    task_root: update.task_root.clone(),
+                    worktree_path: update.worktree_path.clone(),
+                    pull_requests: update
+                        .pull_requests
+                        .iter()
+                        .map(ReplayPullRequest::from_pull_request)
+                        .collect(),
+                })
+                .collect(),
+            events: outcome
+                .events
+                .iter()
+                .map(|event| ReplayPullRequestEvent {
+                    worktree_path: event.worktree_path.clone(),
+                    kind: match event.kind {
+                        PullRequestEventKind::Merged => ReplayPullRequestEventKind::Merged,
+                        PullRequestEventKind::ChecksFailed => {
+                            ReplayPullRequestEventKind::ChecksFailed
+                        }
+                    },
+                    pull_request: ReplayPullRequest::from_pull_request(&event.pull_request),
+                })
+                .collect(),
+            errors: outcome.errors.clone(),
+        }
+    }
+
+    fn to_outcome(&self) -> PullRequestSyncOutcome {
+        PullRequestSyncOutcome {
+            checked: self.checked,
+            updates: self
+                .updates
+                .iter()
+                .map(|update| PullRequestSyncUpdate {
+                    task_root: update.task_root.clone(),
+                    worktree_path: update.worktree_path.clone(),
+                    pull_requests: update
+                        .pull_requests
+                        .iter()
+                        .map(ReplayPullRequest::to_pull_request)
+                        .collect(),
+                })
+                .collect(),
+            events: self
+                .events
+                .iter()
+                .map(|event| PullRequestEvent {
+                    worktree_path: event.worktree_path.clone(),
+                    kind: match event.kind {
+                        ReplayPullRequestEventKind::Merged => PullRequestEventKind::Merged,
+                        ReplayPullRequestEventKind::ChecksFailed => {
+                            PullRequestEventKind::ChecksFailed
+                        }
+                    },
+                    pull_request: event.pull_request.to_pull_request(),
+                })
+                .collect(),
+            errors: self.errors.clone(),
+        }
+    }
+}
+
+impl ReplayHomeDashboardCompletion {
+    fn from_completion(completion: &HomeDashboardCompletion) -> Self {
+        Self {
+            task_slug: completion.task_slug.clone(),
+            worktrees: completion
+                .worktrees
+                .iter()
+                .map(|stats| ReplayHomeDashboardWorktreeStats {
+                    worktree_path: stats.worktree_path.clone(),
+                    ahead: stats.divergence.map(|divergence| divergence.ahead),
+                    behind: stats.divergence.map(|divergence| divergence.behind),
+                    files_changed: stats.files_changed,
+                    insertions: stats.insertions,
+                    deletions: stats.deletions,
+                })
+                .collect(),
+            session_started_at: completion.session_started_at.clone(),
+        }
+    }
+
+    fn to_completion(&self) -> HomeDashboardCompletion {
+        HomeDashboardCompletion {
+            task_slug: self.task_slug.clone(),
+            worktrees: self
+                .worktrees
+                .iter()
+                .map(|stats| HomeDashboardWorktreeStats {
+                    worktree_path: stats.worktree_path.clone(),
+                    divergence: stats.ahead.zip(stats.behind).map(|(ahead, behind)| {
+                        BranchDivergence { ahead, behind }
+                    }),
+                    files_changed: stats.files_changed,
+                    insertions: stats.insertions,
+                    deletions: stats.deletions,
+                })
+                .collect(),
+            session_started_at: self.session_started_at.clone(),
+        }
+    }
+}
+
+impl ReplayUsagePollCompletion {
+    fn from_completion(completion: &UsagePollCompletion) -> Self {
+        Self {
+            worktrees: completion
+                .worktrees
+                .iter()
+                .map(|(worktree_path, totals)| ReplayWorktreeUsage {
+                    worktree_path: worktree_path.clone(),
+                    models: totals
+                        .by_model
+                        .iter()
+                        .map(|(model, tokens)| ReplayModelUsage {
+                            model: model.clone(),
+                            input_tokens: tokens.input_tokens,
+                            output_tokens: tokens.output_tokens,
+                            cache_read_tokens: tokens.cache_read_tokens,
+                            cache_write_tokens: tokens.cache_write_tokens,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn to_completion(&self) -> UsagePollCompletion {
+        UsagePollCompletion {
+            worktrees: self
+                .worktrees
+                .iter()
+                .map(|worktree| {
+                    let mut totals = UsageTotals::default();
+                    for model in &worktree.models {
+                        totals.record(
+                            model.model.as_str(),
+                            TokenUsage {
+                                input_tokens: model.input_tokens,
+                                output_tokens: model.output_tokens,
+                                cache_read_tokens: model.cache_read_tokens,
+                                cache_write_tokens: model.cache_write_tokens,
+                            },
+                        );
+                    }
+                    (worktree.worktree_path.clone(), totals)
+                })
+                .collect(),
+        }
+    }
+}
+
 impl ReplayStringResult {
     fn from_result(result: &Result<String, String>) -> Self {
         match result {
@@ -36,6 +36,12 @@ enum ReplayMsg {
     DeleteWorkspaceCompleted {
         completion: ReplayDeleteWorkspaceCompletion,
     },
+    TaskArchived {
+        completion: ReplayTaskArchiveCompletion,
+    },
+    TaskRestored {
+        completion: ReplayTaskRestoreCompletion,
+    },
     MergeWorkspaceCompleted {
         completion: ReplayMergeWorkspaceCompletion,
     },
@@ -69,6 +75,29 @@ enum ReplayMsg {
         workspace_path: String,
         insertions: usize,
         deletions: usize,
+        #[serde(default)]
+        conflict: Option<ReplayWorkspaceConflict>,
+    },
+    WorkspaceConflictActionCompleted {
+        completion: ReplayWorkspaceConflictActionCompletion,
+    },
+    PullRequestsSynced {
+        completion: ReplayPullRequestSyncCompletion,
+    },
+    HomeDashboardPolled {
+        completion: ReplayHomeDashboardCompletion,
+    },
+    UsagePolled {
+        completion: ReplayUsagePollCompletion,
+    },
+    NotificationsDelivered {
+        workspace_path: String,
+        reason: String,
+        sinks: Vec<String>,
+        failures: Vec<String>,
+    },
+    HooksCompleted {
+        outcomes: Vec<ReplayHookOutcome>,
     },
     Noop,
 }
@@ -199,6 +228,8 @@ impl ReplayMsg {
             Self::RefreshWorkspacesCompleted { .. } => "refresh_workspaces_completed",
             Self::DeleteProjectCompleted { .. } => "delete_project_completed",
             Self::DeleteWorkspaceCompleted { .. } => "delete_workspace_completed",
+            Self::TaskArchived { .. } => "task_archived",
+            Self::TaskRestored { .. } => "task_restored",
             Self::MergeWorkspaceCompleted { .. } => "merge_workspace_completed",
             Self::UpdateWorkspaceFromBaseCompleted { .. } => "update_workspace_from_base_completed",
             Self::PullUpstreamCompleted { .. } => "pull_upstream_completed",
@@ -209,6 +240,12 @@ impl ReplayMsg {
             Self::InteractiveSendCompleted { .. } => "interactive_send_completed",
             Self::DiffCaptureCompleted { .. } => "diff_capture_completed",
             Self::DiffStatCompleted { .. } => "diff_stat_completed",
+            Self::WorkspaceConflictActionCompleted { .. } => "workspace_conflict_action_completed",
+            Self::PullRequestsSynced { .. } => "pull_requests_synced",
+            Self::HomeDashboardPolled { .. } => "home_dashboard_polled",
+            Self::UsagePolled { .. } => "usage_polled",
+            Self::NotificationsDelivered { .. } => "notifications_delivered",
+            Self::HooksCompleted { .. } => "hooks_completed",
             Self::Noop => "noop",
         }
     }
@@ -250,6 +287,12 @@ impl ReplayMsg {
             Msg::DeleteWorkspaceCompleted(completion) => Self::DeleteWorkspaceCompleted {
                 completion: ReplayDeleteWorkspaceCompletion::from_completion(completion),
             },
+            Msg::TaskArchived(completion) => Self::TaskArchived {
+                completion: ReplayTaskArchiveCompletion::from_completion(completion),
+            },
+            Msg::TaskRestored(completion) => Self::TaskRestored {
+                completion: ReplayTaskRestoreCompletion::from_completion(completion),
+            },
             Msg::MergeWorkspaceCompleted(completion) => Self::MergeWorkspaceCompleted {
                 completion: ReplayMergeWorkspaceCompletion::from_completion(completion),
             },
@@ -287,6 +330,40 @@ impl ReplayMsg {
                 workspace_path: completion.workspace_path.to_string_lossy().to_string(),
                 insertions: completion.insertions,
                 deletions: completion.deletions,
+                conflict: completion
+                    .conflict
+                    .as_ref()
+                    .map(ReplayWorkspaceConflict::from_conflict),
+            },
+            Msg::WorkspaceConflictActionCompleted(completion) => {
+                Self::WorkspaceConflictActionCompleted {
+                    completion: ReplayWorkspaceConflictActionCompletion::from_completion(
+                        completion,
+                    ),
+                }
+            }
+            Msg::PullRequestsSynced(outcome) => Self::PullRequestsSynced {
+                completion: ReplayPullRequestSyncCompletion::from_outcome(outcome),
+            },
+            Msg::HomeDashboardPolled(completion) => Self::HomeDashboardPolled {
+                completion: ReplayHomeDashboardCompletion::from_completion(completion),
+            },
+            Msg::UsagePolled(completion) => Self::UsagePolled {
+                completion: ReplayUsagePollCompletion::from_completion(completion),
+            },
+            Msg::NotificationsDelivered(completion) => Self::NotificationsDelivered {
+                workspace_path: completion.workspace_path.to_string_lossy().to_string(),
+                reason: completion.reason.clone(),
+                sinks: completion.sinks.clone(),
+                failures: completion.failures.clone(),
+            },
+            // The wrapped completion is recorded on its own once dispatched.
+            Msg::HooksCompleted(completion) => Self::HooksCompleted {
+                outcomes: completion
+                    .outcomes
+                    .iter()
+                    .map(ReplayHookOutcome::from_outcome)
+                    .collect(),
             },
             Msg::Noop => Self::Noop,
         }
@@ -321,6 +398,8 @@ impl ReplayMsg {
             Self::DeleteWorkspaceCompleted { completion } => {
                 Msg::DeleteWorkspaceCompleted(completion.to_completion())
             }
+            Self::TaskArchived { completion } => Msg::TaskArchived(completion.to_completion()),
+            Self::TaskRestored { completion } => Msg::TaskRestored(completion.to_completion()),
             Self::MergeWorkspaceCompleted { completion } => {
                 Msg::MergeWorkspaceCompleted(completion.to_completion())
             }
@@ -358,11 +437,38 @@ impl ReplayMsg {
                 workspace_path,
                 insertions,
                 deletions,
+                conflict,
             } => Msg::DiffStatCompleted(DiffStatCompletion {
                 workspace_path: PathBuf::from(workspace_path),
                 insertions: *insertions,
                 deletions: *deletions,
+                conflict: conflict.as_ref().map(ReplayWorkspaceConflict::to_conflict),
+            }),
+            Self::WorkspaceConflictActionCompleted { completion } => {
+                Msg::WorkspaceConflictActionCompleted(completion.to_completion())
+            }
+            Self::PullRequestsSynced { completion } => {
+                Msg::PullRequestsSynced(completion.to_outcome())
+            }
+            Self::HomeDashboardPolled { completion } => {
+                Msg::HomeDashboardPolled(completion.to_completion())
+            }
+            Self::UsagePolled { completion } => Msg::UsagePolled(completion.to_completion()),
+            Self::NotificationsDelivered {
+                workspace_path,
+                reason,
+                sinks,
+                failures,
+            } => Msg::NotificationsDelivered(NotificationDeliveryCompletion {
+                workspace_path: PathBuf::from(workspace_path),
+                reason: reason.clone(),
+                sinks: sinks.clone(),
+                failures: failures.clone(),
             }),
+            Self::HooksCompleted { outcomes } => Msg::HooksCompleted(Box::new(HooksCompletion {
+                outcomes: outcomes.iter().map(ReplayHookOutcome::to_outcome).collect(),
+                next: None,
+            })),
             Self::Noop => Msg::Noop,
         }
     }
@@ -3,11 +3,9 @@ use std::time::Instant;
 
 use crate::domain::AgentType;
 use crate::infrastructure::config::ThemeName;
+use crate::infrastructure::themes::ThemePalette;
 use ftui::{Color, PackedRgba, ResolvedTheme, Theme, ThemeBuilder};
 
-pub(super) const WORKSPACE_LAUNCH_PROMPT_FILENAME: &str = ".grove/prompt";
-pub(super) const WORKSPACE_INIT_COMMAND_FILENAME: &str = ".grove/init_command";
-pub(super) const WORKSPACE_SKIP_PERMISSIONS_FILENAME: &str = ".grove/skip_permissions";
 pub(super) const HEADER_HEIGHT: u16 = 1;
 pub(super) const STATUS_HEIGHT: u16 = 1;
 pub(super) const DIVIDER_WIDTH: u16 = 1;
@@ -59,8 +57,10 @@ pub(super) const FOCUS_ID_MERGE_CLEANUP_WORKSPACE: u64 = 1601;
 pub(super) const FOCUS_ID_MERGE_CLEANUP_LOCAL_BRANCH: u64 = 1602;
 pub(super) const FOCUS_ID_MERGE_CONFIRM_BUTTON: u64 = 1603;
 pub(super) const FOCUS_ID_MERGE_CANCEL_BUTTON: u64 = 1604;
+pub(super) const FOCUS_ID_MERGE_STRATEGY: u64 = 1605;
 pub(super) const FOCUS_ID_UPDATE_FROM_BASE_CONFIRM_BUTTON: u64 = 1701;
 pub(super) const FOCUS_ID_UPDATE_FROM_BASE_CANCEL_BUTTON: u64 = 1702;
+pub(super) const FOCUS_ID_UPDATE_FROM_BASE_STRATEGY: u64 = 1703;
 pub(super) const FOCUS_ID_PULL_UPSTREAM_CONFIRM_BUTTON: u64 = 1801;
 pub(super) const FOCUS_ID_PULL_UPSTREAM_CANCEL_BUTTON: u64 = 1802;
 pub(super) const FOCUS_ID_SETTINGS_THEME: u64 = 1901;
@@ -85,6 +85,8 @@ pub(super) const FOCUS_ID_CREATE_PULL_REQUEST_URL: u64 = 2303;
 pub(super) const FOCUS_ID_CREATE_PROJECT: u64 = 2304;
 pub(super) const FOCUS_ID_CREATE_CREATE_BUTTON: u64 = 2305;
 pub(super) const FOCUS_ID_CREATE_CANCEL_BUTTON: u64 = 2306;
+pub(super) const FOCUS_ID_CREATE_RECIPE: u64 = 2307;
+pub(super) const FOCUS_ID_CREATE_RECIPE_VARIABLES: u64 = 2308;
 pub(super) const HIT_ID_HEADER: u32 = 1;
 pub(super) const HIT_ID_WORKSPACE_LIST: u32 = 2;
 pub(super) const HIT_ID_PREVIEW: u32 = 3;
@@ -112,6 +114,10 @@ pub(super) const HIT_ID_PROJECT_DIALOG_LIST: u32 = 24;
 pub(super) const HIT_ID_PROJECT_ADD_RESULTS_LIST: u32 = 25;
 pub(super) const HIT_ID_PULL_UPSTREAM_DIALOG: u32 = 26;
 pub(super) const HIT_ID_PERFORMANCE_DIALOG: u32 = 27;
+pub(super) const HIT_ID_TRANSCRIPT_DIALOG: u32 = 28;
+pub(super) const HIT_ID_PANE_SEARCH_DIALOG: u32 = 29;
+pub(super) const HIT_ID_BROADCAST_DIALOG: u32 = 30;
+pub(super) const HIT_ID_PROMPT_QUEUE_DIALOG: u32 = 31;
 pub(super) const MAX_PENDING_INPUT_TRACES: usize = 256;
 pub(super) const INTERACTIVE_KEYSTROKE_DEBOUNCE_MS: u64 = 20;
 pub(super) const FAST_ANIMATION_INTERVAL_MS: u64 = 100;
@@ -124,7 +130,6 @@ pub(super) const LIVE_PREVIEW_FULL_SCROLLBACK_LINES: usize = 0;
 pub(super) const LAZYGIT_COMMAND: &str = "lazygit";
 pub(super) const WORKING_STATUS_HOLD_MS: u64 = 3_000;
 pub(super) const WORKING_IDLE_POLLS_TO_CLEAR: u8 = 2;
-pub(super) const AGENT_ENV_SEPARATOR: char = ';';
 
 pub(super) fn usize_to_u64(value: usize) -> u64 {
     u64::try_from(value).unwrap_or(u64::MAX)
@@ -170,29 +175,11 @@ pub(super) struct AgentEnvVar {
 }
 
 pub(super) fn parse_agent_env_vars(raw: &str) -> Result<Vec<AgentEnvVar>, String> {
-    let mut parsed = Vec::new();
-    for segment in raw
-        .split([AGENT_ENV_SEPARATOR, '\n'])
-        .map(str::trim)
-        .filter(|segment| !segment.is_empty())
-    {
-        let Some((raw_key, raw_value)) = segment.split_once('=') else {
-            return Err(format!("'{segment}' must be KEY=VALUE"));
-        };
-        let key = raw_key.trim();
-        if !env_var_key_is_valid(key) {
-            return Err(format!("invalid env key '{key}'"));
-        }
-        let value = raw_value.trim();
-        if value.is_empty() {
-            return Err(format!("env '{key}' cannot have empty value"));
-        }
-        parsed.push(AgentEnvVar {
-            key: key.to_string(),
-            value: value.to_string(),
-        });
-    }
-    Ok(parsed)
+    crate::application::agent_control::parse_agent_env_vars(raw).map(|vars| {
+        vars.into_iter()
+            .map(|(key, value)| AgentEnvVar { key, value })
+            .collect()
+    })
 }
 
 pub(super) fn parse_agent_env_vars_from_entries(
@@ -218,18 +205,7 @@ pub(super) fn encode_agent_env_vars(raw: &str) -> Result<Vec<String>, String> {
     })
 }
 
-fn env_var_key_is_valid(key: &str) -> bool {
-    let mut chars = key.chars();
-    let Some(first) = chars.next() else {
-        return false;
-    };
-    if !(first.is_ascii_alphabetic() || first == '_') {
-        return false;
-    }
-    chars.all(|character| character.is_ascii_alphanumeric() || character == '_')
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct ThemePreset {
     name: ThemeName,
     display_name: &'static str,
@@ -274,8 +250,34 @@ const fn rgb(r: u8, g: u8, b: u8) -> Color {
     Color::rgb(r, g, b)
 }
 
-fn build_theme(theme_name: ThemeName) -> Theme {
+fn custom_theme(palette: ThemePalette) -> Theme {
+    let color = |(r, g, b): (u8, u8, u8)| rgb(r, g, b);
+    ThemeBuilder::new()
+        .background(color(palette.background))
+        .surface(color(palette.surface))
+        .overlay(color(palette.overlay))
+        .text(color(palette.text))
+        .text_muted(color(palette.text_muted))
+        .text_subtle(color(palette.text_subtle))
+        .primary(color(palette.primary))
+        .secondary(color(palette.secondary))
+        .accent(color(palette.accent))
+        .info(color(palette.info))
+        .success(color(palette.success))
+        .warning(color(palette.warning))
+        .error(color(palette.error))
+        .border(color(palette.border))
+        .border_focused(color(palette.border_focused))
+        .selection_bg(color(palette.selection_bg))
+        .selection_fg(color(palette.selection_fg))
+        .scrollbar_track(color(palette.scrollbar_track))
+        .scrollbar_thumb(color(palette.scrollbar_thumb))
+        .build()
+}
+
+fn build_theme(theme_name: &ThemeName) -> Theme {
     match theme_name {
+        ThemeName::Custom(theme) => custom_theme(theme.palette()),
         ThemeName::Monokai => ThemeBuilder::new()
             .background(rgb(39, 40, 34))
             .surface(rgb(62, 61, 50))
@@ -452,46 +454,32 @@ pub(crate) fn packed(color: Color) -> PackedRgba {
     PackedRgba::rgb(rgb.r, rgb.g, rgb.b)
 }
 
-fn theme_preset(theme_name: ThemeName) -> ThemePreset {
+fn theme_preset(theme_name: &ThemeName) -> &'static ThemePreset {
     THEME_PRESETS
         .iter()
-        .copied()
-        .find(|preset| preset.name == theme_name)
-        .unwrap_or(THEME_PRESETS[4])
-}
-
-pub(super) fn theme_display_name(theme_name: ThemeName) -> &'static str {
-    theme_preset(theme_name).display_name
-}
-
-pub(super) fn next_theme_name(theme_name: ThemeName) -> ThemeName {
-    let index = THEME_PRESETS
-        .iter()
-        .position(|preset| preset.name == theme_name)
-        .unwrap_or(0);
-    THEME_PRESETS[(index + 1) % THEME_PRESETS.len()].name
+        .find(|preset| preset.name == *theme_name)
+        .unwrap_or(&THEME_PRESETS[4])
 }
 
-pub(super) fn previous_theme_name(theme_name: ThemeName) -> ThemeName {
-    let index = THEME_PRESETS
-        .iter()
-        .position(|preset| preset.name == theme_name)
-        .unwrap_or(0);
-    THEME_PRESETS[(index + THEME_PRESETS.len() - 1) % THEME_PRESETS.len()].name
+pub(super) fn theme_display_name(theme_name: &ThemeName) -> &str {
+    match theme_name {
+        ThemeName::Custom(theme) => theme.display_name(),
+        _ => theme_preset(theme_name).display_name,
+    }
 }
 
-pub(crate) fn ui_theme_for(theme_name: ThemeName) -> ResolvedTheme {
+pub(crate) fn ui_theme_for(theme_name: &ThemeName) -> ResolvedTheme {
     build_theme(theme_name).resolve(Theme::detect_dark_mode())
 }
 
 #[cfg_attr(not(test), allow(dead_code))]
 pub(super) fn ui_theme() -> ResolvedTheme {
-    ui_theme_for(ThemeName::default())
+    ui_theme_for(&ThemeName::default())
 }
 
 impl super::GroveApp {
     pub(super) fn active_ui_theme(&self) -> ResolvedTheme {
-        ui_theme_for(self.theme_name)
+        ui_theme_for(&self.theme_name)
     }
 }
 
@@ -535,6 +523,8 @@ pub(super) enum WorkspaceTabKind {
     Shell,
     Git,
     Diff,
+    /// Runs a project-defined command template, e.g. a dev server.
+    Command,
 }
 
 impl WorkspaceTabKind {
@@ -545,6 +535,7 @@ impl WorkspaceTabKind {
             Self::Shell => "Shell",
             Self::Git => "Git",
             Self::Diff => "Diff",
+            Self::Command => "Command",
         }
     }
 }
@@ -554,7 +545,7 @@ impl From<WorkspaceTabKind> for PreviewTab {
         match value {
             WorkspaceTabKind::Home => PreviewTab::Home,
             WorkspaceTabKind::Agent => PreviewTab::Agent,
-            WorkspaceTabKind::Shell => PreviewTab::Shell,
+            WorkspaceTabKind::Shell | WorkspaceTabKind::Command => PreviewTab::Shell,
             WorkspaceTabKind::Git => PreviewTab::Git,
             WorkspaceTabKind::Diff => PreviewTab::Diff,
         }
@@ -569,6 +560,18 @@ pub(super) enum WorkspaceTabRuntimeState {
     Failed,
 }
 
+impl WorkspaceTabRuntimeState {
+    /// State of a command tab whose command last exited with `exit`, `None`
+    /// while it runs.
+    pub(super) fn from_command_exit(exit: Option<i32>) -> Self {
+        match exit {
+            None => Self::Running,
+            Some(0) => Self::Stopped,
+            Some(_) => Self::Failed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct WorkspaceTab {
     pub(super) id: u64,
@@ -577,6 +580,8 @@ pub(super) struct WorkspaceTab {
     pub(super) title: String,
     pub(super) session_name: Option<String>,
     pub(super) agent_type: Option<AgentType>,
+    /// Name of the project command template a command tab runs.
+    pub(super) command_template: Option<String>,
     pub(super) state: WorkspaceTabRuntimeState,
 }
 
@@ -589,9 +594,19 @@ impl WorkspaceTab {
             title: WorkspaceTabKind::Home.label().to_string(),
             session_name: None,
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Stopped,
         }
     }
+
+    /// Text shown for the tab in the preview tab bar.
+    pub(super) fn bar_label(&self) -> String {
+        if self.kind == WorkspaceTabKind::Command && self.state == WorkspaceTabRuntimeState::Failed
+        {
+            return format!(" {} ✗ ", self.title);
+        }
+        format!(" {} ", self.title)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -924,6 +939,7 @@ mod tests {
             title: String::new(),
             session_name: Some(session_name.to_string()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         }
     }
@@ -936,6 +952,7 @@ mod tests {
             title: String::new(),
             session_name: Some(session_name.to_string()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         }
     }
@@ -948,6 +965,7 @@ mod tests {
             title: String::new(),
             session_name: Some(session_name.to_string()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         }
     }
@@ -1042,6 +1060,7 @@ mod tests {
             title: "Agent 1".to_string(),
             session_name: Some("ws-agent-1".to_string()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         };
         assert!(tabs.insert_restored_tab(tab1));
@@ -1053,6 +1072,7 @@ mod tests {
             title: "Agent 2".to_string(),
             session_name: Some("ws-agent-1".to_string()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Running,
         };
         assert!(!tabs.insert_restored_tab(tab2));
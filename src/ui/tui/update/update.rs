@@ -5,7 +5,7 @@ use serde_json::Value;
 
 use crate::infrastructure::event_log::Event as LogEvent;
 
-use super::{GroveApp, Msg};
+use super::{GroveApp, HooksCompletion, Msg};
 
 impl GroveApp {
     pub(super) fn update_model(&mut self, msg: Msg) -> Cmd<Msg> {
@@ -87,6 +87,14 @@ impl GroveApp {
                 self.apply_delete_workspace_completion(completion);
                 Cmd::None
             }
+            Msg::TaskArchived(completion) => {
+                self.apply_task_archive_completion(completion);
+                Cmd::None
+            }
+            Msg::TaskRestored(completion) => {
+                self.apply_task_restore_completion(completion);
+                Cmd::None
+            }
             Msg::MergeWorkspaceCompleted(completion) => {
                 self.apply_merge_workspace_completion(completion);
                 Cmd::None
@@ -126,6 +134,31 @@ impl GroveApp {
                 self.handle_diff_stat_completed(completion);
                 Cmd::None
             }
+            Msg::WorkspaceConflictActionCompleted(completion) => {
+                self.apply_workspace_conflict_action_completion(completion);
+                Cmd::None
+            }
+            Msg::PullRequestsSynced(outcome) => {
+                self.handle_pull_requests_synced(outcome);
+                Cmd::None
+            }
+            Msg::HomeDashboardPolled(completion) => {
+                self.handle_home_dashboard_polled(completion);
+                Cmd::None
+            }
+            Msg::UsagePolled(completion) => {
+                self.handle_usage_polled(completion);
+                Cmd::None
+            }
+            Msg::NotificationsDelivered(completion) => {
+                self.apply_notification_delivery_completion(completion);
+                Cmd::None
+            }
+            Msg::HooksCompleted(completion) => {
+                let HooksCompletion { outcomes, next } = *completion;
+                self.log_hook_outcomes(&outcomes);
+                next.map_or(Cmd::None, Cmd::msg)
+            }
             Msg::Noop => {
                 self.interactive_preview_reset_pending = false;
                 Cmd::None
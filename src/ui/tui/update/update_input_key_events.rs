@@ -1,5 +1,7 @@
 use super::update_prelude::*;
 
+const KEY_SEQUENCE_TIMEOUT_MS: u64 = 1_500;
+
 impl GroveApp {
     fn is_ctrl_modal_nav_key(key_event: &KeyEvent) -> bool {
         key_event.modifiers == Modifiers::CTRL
@@ -38,9 +40,11 @@ impl GroveApp {
         key_event
     }
 
-    fn global_workspace_navigation_command(key_event: &KeyEvent) -> Option<UiCommand> {
+    fn global_workspace_navigation_command(&self, key_event: &KeyEvent) -> Option<UiCommand> {
         for command in UiCommand::all() {
-            if command.matches_keybinding(key_event, KeybindingScope::GlobalNavigation) {
+            if !self.keymap.overrides(*command)
+                && command.matches_keybinding(key_event, KeybindingScope::GlobalNavigation)
+            {
                 return Some(*command);
             }
         }
@@ -69,10 +73,19 @@ impl GroveApp {
                         }
                     }
                 }
+                Some(CreateDialogField::RecipeVariables) => {
+                    handled = true;
+                    for character in text.chars() {
+                        if !character.is_control() {
+                            dialog.recipe_variables.push(character);
+                        }
+                    }
+                }
                 Some(
                     CreateDialogField::WorkspaceName
                     | CreateDialogField::RegisterAsBase
                     | CreateDialogField::Project
+                    | CreateDialogField::Recipe
                     | CreateDialogField::CreateButton
                     | CreateDialogField::CancelButton,
                 )
@@ -156,6 +169,12 @@ impl GroveApp {
         if self.apply_paste_to_project_dialog(&paste_event.text) {
             return Cmd::None;
         }
+        if self.apply_paste_to_broadcast_dialog(&paste_event.text) {
+            return Cmd::None;
+        }
+        if self.apply_paste_to_prompt_queue_dialog(&paste_event.text) {
+            return Cmd::None;
+        }
 
         let input_seq = self.next_input_seq();
         let received_at = Instant::now();
@@ -207,13 +226,71 @@ impl GroveApp {
         }
     }
 
+    /// Feeds a key into the configured key sequences; `None` leaves it to the default bindings.
+    fn handle_custom_key_sequence(&mut self, key_event: KeyEvent) -> Option<bool> {
+        if self.keymap.is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        if self.pending_key_sequence_expired(now) {
+            self.pending_key_sequence = PendingKeySequence::default();
+        }
+        let had_pending = !self.pending_key_sequence.keys.is_empty();
+        if had_pending && key_event.code == KeyCode::Escape {
+            self.pending_key_sequence = PendingKeySequence::default();
+            return Some(false);
+        }
+
+        self.pending_key_sequence.keys.push(key_event);
+        match self.keymap.resolve(&self.pending_key_sequence.keys) {
+            KeySequenceMatch::Pending => {
+                self.pending_key_sequence.started_at.get_or_insert(now);
+                Some(false)
+            }
+            KeySequenceMatch::Command(command) => {
+                self.pending_key_sequence = PendingKeySequence::default();
+                if !self.palette_command_enabled(command) {
+                    return Some(false);
+                }
+                Some(self.execute_ui_command(command))
+            }
+            KeySequenceMatch::NoMatch => {
+                self.pending_key_sequence = PendingKeySequence::default();
+                if had_pending {
+                    return self.handle_custom_key_sequence(key_event);
+                }
+                None
+            }
+        }
+    }
+
+    fn pending_key_sequence_expired(&self, now: Instant) -> bool {
+        self.pending_key_sequence
+            .started_at
+            .is_some_and(|started_at| {
+                now.saturating_duration_since(started_at)
+                    > Duration::from_millis(KEY_SEQUENCE_TIMEOUT_MS)
+            })
+    }
+
+    pub(super) fn pending_key_sequence_label(&self) -> Option<String> {
+        if self.pending_key_sequence.keys.is_empty()
+            || self.pending_key_sequence_expired(Instant::now())
+        {
+            return None;
+        }
+        self.keymap.pending_label(&self.pending_key_sequence.keys)
+    }
+
     fn non_interactive_command_for_key(&self, key_event: &KeyEvent) -> Option<UiCommand> {
         let in_preview_focus = self.preview_focused();
         let in_preview_scroll = in_preview_focus && self.active_tab_is_scrollable();
         let can_enter_interactive = self.can_enter_interactive_session();
 
         for command in UiCommand::all() {
-            if !command.matches_keybinding(key_event, KeybindingScope::NonInteractive) {
+            if self.keymap.overrides(*command)
+                || !command.matches_keybinding(key_event, KeybindingScope::NonInteractive)
+            {
                 continue;
             }
             if self.non_interactive_command_enabled(
@@ -255,7 +332,7 @@ impl GroveApp {
             UiCommand::AddWorktree | UiCommand::DeleteWorkspace | UiCommand::DeleteWorktree => {
                 self.workspace_list_focused()
             }
-            UiCommand::OpenRepository => {
+            UiCommand::OpenRepository | UiCommand::ToggleBroadcastTarget => {
                 self.workspace_list_focused() && self.state.selected_workspace().is_some()
             }
             UiCommand::OpenDiffTab => in_preview_focus,
@@ -317,6 +394,10 @@ impl GroveApp {
             self.acknowledge_selected_attention_item();
             return false;
         }
+        if let Some(index) = self.home_dashboard_hotkey(&key_event) {
+            self.jump_to_home_dashboard_target(index);
+            return false;
+        }
         let Some(command) = self.non_interactive_command_for_key(&key_event) else {
             return false;
         };
@@ -376,6 +457,22 @@ impl GroveApp {
             self.handle_settings_dialog_key(*key_event);
             return true;
         }
+        if self.transcript_dialog().is_some() {
+            self.handle_transcript_dialog_key(*key_event);
+            return true;
+        }
+        if self.pane_search_dialog().is_some() {
+            self.handle_pane_search_dialog_key(*key_event);
+            return true;
+        }
+        if self.broadcast_dialog().is_some() {
+            self.handle_broadcast_dialog_key(*key_event);
+            return true;
+        }
+        if self.prompt_queue_dialog().is_some() {
+            self.handle_prompt_queue_dialog_key(*key_event);
+            return true;
+        }
 
         false
     }
@@ -447,7 +544,14 @@ impl GroveApp {
         }
 
         if !self.modal_open()
-            && let Some(command) = Self::global_workspace_navigation_command(&key_event)
+            && self.session.interactive.is_none()
+            && let Some(handled) = self.handle_custom_key_sequence(key_event)
+        {
+            return (handled, Cmd::None);
+        }
+
+        if !self.modal_open()
+            && let Some(command) = self.global_workspace_navigation_command(&key_event)
         {
             if self.session.interactive.is_some()
                 && matches!(
@@ -8,6 +8,7 @@ impl GroveApp {
             || self.dialogs.merge_in_flight
             || self.dialogs.update_from_base_in_flight
             || self.dialogs.pull_upstream_in_flight
+            || self.dialogs.conflict_action_in_flight
             || self.dialogs.create_in_flight
             || self.dialogs.start_in_flight
             || self.dialogs.stop_in_flight
@@ -21,6 +22,18 @@ impl GroveApp {
         if let Some(dialog) = self.create_dialog() {
             return !dialog.task_name.is_empty() || !dialog.pr_url.is_empty();
         }
+        if let Some(dialog) = self.transcript_dialog() {
+            return !dialog.query.is_empty();
+        }
+        if let Some(dialog) = self.pane_search_dialog() {
+            return !dialog.query.is_empty();
+        }
+        if let Some(dialog) = self.broadcast_dialog() {
+            return !dialog.prompt.is_empty();
+        }
+        if let Some(dialog) = self.prompt_queue_dialog() {
+            return !dialog.input.is_empty();
+        }
         if let Some(project_dialog) = self.project_dialog() {
             if !project_dialog.filter().is_empty() {
                 return true;
@@ -69,6 +82,22 @@ impl GroveApp {
                 false
             }
             KeybindingAction::ClearInput => {
+                if self.transcript_dialog().is_some() {
+                    self.clear_transcript_query();
+                    return false;
+                }
+                if self.pane_search_dialog().is_some() {
+                    self.clear_pane_search_query();
+                    return false;
+                }
+                if self.broadcast_dialog().is_some() {
+                    self.clear_broadcast_prompt();
+                    return false;
+                }
+                if let Some(dialog) = self.prompt_queue_dialog_mut() {
+                    dialog.input.clear();
+                    return false;
+                }
                 let launch_focus = self.current_launch_dialog_focus_field();
                 if let Some(dialog) = self.launch_dialog_mut() {
                     match launch_focus {
@@ -88,10 +117,14 @@ impl GroveApp {
                             dialog.task_name.clear();
                         }
                         Some(CreateDialogField::PullRequestUrl) => dialog.pr_url.clear(),
+                        Some(CreateDialogField::RecipeVariables) => {
+                            dialog.recipe_variables.clear();
+                        }
                         Some(
                             CreateDialogField::WorkspaceName
                             | CreateDialogField::RegisterAsBase
                             | CreateDialogField::Project
+                            | CreateDialogField::Recipe
                             | CreateDialogField::CreateButton
                             | CreateDialogField::CancelButton,
                         )
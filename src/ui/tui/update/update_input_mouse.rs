@@ -31,7 +31,7 @@ impl GroveApp {
             if index > 0 {
                 tab_x = tab_x.saturating_add(1);
             }
-            let label = format!(" {} ", tab.title);
+            let label = tab.bar_label();
             let Some(tab_width) = u16::try_from(text_display_width(label.as_str())).ok() else {
                 continue;
             };
@@ -341,7 +341,7 @@ impl GroveApp {
         }
     }
 
-    fn open_url_in_browser(&mut self, url: &str) -> Result<(), String> {
+    pub(super) fn open_url_in_browser(&mut self, url: &str) -> Result<(), String> {
         #[cfg(test)]
         {
             self.opened_urls.push(url.to_string());
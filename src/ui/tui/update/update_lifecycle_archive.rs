@@ -0,0 +1,267 @@
+use super::update_prelude::*;
+
+const RESTORE_ARCHIVE_ACTION_PREFIX: &str = "restore-archive:";
+
+impl GroveApp {
+    pub(super) fn open_archive_task_dialog(&mut self) {
+        if self.modal_open() {
+            return;
+        }
+        if self.dialogs.archive_in_flight {
+            self.show_info_toast("task archive already in progress");
+            return;
+        }
+        let Some(task) = self.state.selected_task().cloned() else {
+            self.show_info_toast("no task selected");
+            return;
+        };
+        if task.has_base_worktree() {
+            self.show_info_toast("base tasks cannot be archived");
+            return;
+        }
+
+        let task_name = task.name.clone();
+        self.set_confirm_dialog(ConfirmDialogState {
+            action: ConfirmDialogAction::ArchiveTask { task },
+        });
+        self.log_dialog_event_with_fields(
+            "confirm",
+            "dialog_opened",
+            [
+                (
+                    "target".to_string(),
+                    Value::from("archive_task".to_string()),
+                ),
+                ("task".to_string(), Value::from(task_name)),
+            ],
+        );
+    }
+
+    pub(super) fn start_archive_task(&mut self, task: Task) {
+        if self.dialogs.archive_in_flight {
+            return;
+        }
+        let agents = Arc::clone(&self.agents);
+        self.dialogs.archive_in_flight = true;
+        self.queue_cmd(Cmd::task(move || {
+            let (result, warnings) = match archive_task(&task, &agents) {
+                Ok(archived) => (Ok(()), archived.warnings),
+                Err(error) => (Err(task_lifecycle_error_message(&error)), Vec::new()),
+            };
+            Msg::TaskArchived(TaskArchiveCompletion {
+                task_name: task.name,
+                task_slug: task.slug,
+                result,
+                warnings,
+            })
+        }));
+    }
+
+    pub(super) fn apply_task_archive_completion(&mut self, completion: TaskArchiveCompletion) {
+        self.dialogs.archive_in_flight = false;
+        match completion.result {
+            Ok(()) => {
+                self.record_task_history_event(completion.task_slug.as_str(), "archived", None);
+                self.telemetry.event_log.log(
+                    LogEvent::new("task_lifecycle", "task_archived")
+                        .with_data("task", Value::from(completion.task_name.clone()))
+                        .with_data(
+                            "warning_count",
+                            Value::from(usize_to_u64(completion.warnings.len())),
+                        ),
+                );
+                self.refresh_workspaces(None);
+                match completion.warnings.first() {
+                    Some(first_warning) => self.show_info_toast(format!(
+                        "task '{}' archived, warning: {first_warning}",
+                        completion.task_name
+                    )),
+                    None => {
+                        self.show_success_toast(format!("task '{}' archived", completion.task_name))
+                    }
+                }
+            }
+            Err(error) => {
+                self.telemetry.event_log.log(
+                    LogEvent::new("task_lifecycle", "task_archive_failed")
+                        .with_data("task", Value::from(completion.task_name))
+                        .with_data("error", Value::from(error.clone())),
+                );
+                self.show_error_toast(format!("task archive failed: {error}"));
+            }
+        }
+    }
+
+    pub(super) fn open_restore_archive_palette(&mut self) {
+        if self.dialogs.archive_in_flight {
+            self.show_info_toast("task archive already in progress");
+            return;
+        }
+        let archives = match list_task_archives() {
+            Ok(archives) => archives,
+            Err(error) => {
+                self.show_error_toast(format!(
+                    "archive list failed: {}",
+                    task_lifecycle_error_message(&error)
+                ));
+                return;
+            }
+        };
+        if archives.is_empty() {
+            self.show_info_toast("no archived tasks");
+            return;
+        }
+
+        let actions = archives
+            .iter()
+            .map(|archive| {
+                let archived_secs = i64::try_from(archive.archived_at_ms / 1000).ok();
+                let repositories = archive
+                    .worktrees
+                    .iter()
+                    .map(|worktree| worktree.repository_name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ");
+                Self::palette_action(
+                    format!("{RESTORE_ARCHIVE_ACTION_PREFIX}{}", archive.slug),
+                    archive.name.clone(),
+                    format!(
+                        "archived {} ago, {repositories}",
+                        self.relative_age_label(archived_secs)
+                    ),
+                    &[],
+                    "Archive",
+                )
+            })
+            .collect();
+        self.open_shared_palette(PaletteMode::RestoreArchive, actions);
+    }
+
+    pub(super) fn execute_restore_archive_action(&mut self, id: &str) -> bool {
+        let Some(slug) = id.strip_prefix(RESTORE_ARCHIVE_ACTION_PREFIX) else {
+            return false;
+        };
+        if self.dialogs.archive_in_flight {
+            return false;
+        }
+        let slug = slug.to_string();
+        let agents = Arc::clone(&self.agents);
+        self.dialogs.archive_in_flight = true;
+        self.queue_cmd(Cmd::task(move || {
+            match restore_task(slug.as_str(), &agents) {
+                Ok(restored) => Msg::TaskRestored(TaskRestoreCompletion {
+                    task_name: restored.task.name.clone(),
+                    task_slug: restored.task.slug.clone(),
+                    workspace_path: restored
+                        .task
+                        .worktrees
+                        .first()
+                        .map(|worktree| worktree.path.clone()),
+                    resumable_tabs: restored.archive.resumable_tabs(&agents),
+                    result: Ok(()),
+                    warnings: restored.warnings,
+                }),
+                Err(error) => Msg::TaskRestored(TaskRestoreCompletion {
+                    task_name: slug.clone(),
+                    task_slug: slug,
+                    workspace_path: None,
+                    resumable_tabs: Vec::new(),
+                    result: Err(task_lifecycle_error_message(&error)),
+                    warnings: Vec::new(),
+                }),
+            }
+        }));
+        false
+    }
+
+    pub(super) fn apply_task_restore_completion(&mut self, completion: TaskRestoreCompletion) {
+        self.dialogs.archive_in_flight = false;
+        if let Err(error) = completion.result {
+            self.telemetry.event_log.log(
+                LogEvent::new("task_lifecycle", "task_restore_failed")
+                    .with_data("task", Value::from(completion.task_slug))
+                    .with_data("error", Value::from(error.clone())),
+            );
+            self.show_error_toast(format!("task restore failed: {error}"));
+            return;
+        }
+
+        self.record_task_history_event(completion.task_slug.as_str(), "restored", None);
+        self.telemetry.event_log.log(
+            LogEvent::new("task_lifecycle", "task_restored")
+                .with_data("task", Value::from(completion.task_name.clone()))
+                .with_data(
+                    "resumable_tab_count",
+                    Value::from(usize_to_u64(completion.resumable_tabs.len())),
+                )
+                .with_data(
+                    "warning_count",
+                    Value::from(usize_to_u64(completion.warnings.len())),
+                ),
+        );
+        self.refresh_workspaces(completion.workspace_path);
+        match completion.warnings.first() {
+            Some(first_warning) => self.show_info_toast(format!(
+                "task '{}' restored, warning: {first_warning}",
+                completion.task_name
+            )),
+            None => self.show_success_toast(format!("task '{}' restored", completion.task_name)),
+        }
+
+        if completion.resumable_tabs.is_empty() || self.modal_open() {
+            return;
+        }
+        self.set_confirm_dialog(ConfirmDialogState {
+            action: ConfirmDialogAction::ResumeArchivedAgents {
+                task_name: completion.task_name.clone(),
+                tabs: completion.resumable_tabs,
+            },
+        });
+        self.log_dialog_event_with_fields(
+            "confirm",
+            "dialog_opened",
+            [
+                (
+                    "target".to_string(),
+                    Value::from("resume_archived_agents".to_string()),
+                ),
+                ("task".to_string(), Value::from(completion.task_name)),
+            ],
+        );
+    }
+
+    /// Reopens each archived agent tab in its restored worktree, resuming its saved conversation.
+    pub(super) fn resume_archived_agents(&mut self, tabs: Vec<(PathBuf, ArchivedTab)>) {
+        let mut resumed = 0usize;
+        let mut failures = Vec::new();
+        for (workspace_path, tab) in tabs {
+            let Some(agent) = tab.agent_type(&self.agents) else {
+                continue;
+            };
+            if !self.state.select_workspace_path(workspace_path.as_path()) {
+                failures.push(format!("{}: worktree not found", tab.title));
+                continue;
+            }
+            let options = StartOptions {
+                name: Some(tab.title.clone()),
+                prompt: None,
+                init_command: None,
+                permission_mode: self.launch_permission_mode,
+                resume_command: tab.resume_command.clone(),
+            };
+            match self.launch_new_agent_tab(agent, options) {
+                Ok(()) => resumed += 1,
+                Err(error) => failures.push(format!("{}: {error}", tab.title)),
+            }
+        }
+        self.handle_workspace_selection_changed();
+
+        match failures.first() {
+            Some(first_failure) => self.show_error_toast(format!(
+                "resumed {resumed} agent tab(s), {} failed, {first_failure}",
+                failures.len()
+            )),
+            None => self.show_success_toast(format!("resumed {resumed} agent tab(s)")),
+        }
+    }
+}
@@ -1,17 +1,12 @@
 use super::update_prelude::*;
+use crate::application::hooks::create_with_hooks;
 use crate::application::task_lifecycle::{
-    AddWorktreeToTaskRequest, AddWorktreeToTaskResult, CreateBaseTaskRequest, TaskBranchSource,
-    add_worktree_to_task, add_worktree_to_task_in_root, create_base_task, create_base_task_in_root,
+    AddWorktreeToTaskRequest, AddWorktreeToTaskResult, CreateBaseTaskRequest,
+    ParsedGitHubPullRequest, TaskBranchSource, add_worktree_to_task, add_worktree_to_task_in_root,
+    create_base_task, create_base_task_in_root, ensure_project_matches_pull_request,
+    parse_github_pull_request_url, resolve_pull_request_branch_name_with_gh,
 };
 use crate::infrastructure::paths::refer_to_same_location;
-use crate::infrastructure::process::stderr_trimmed;
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ParsedGitHubPullRequest {
-    owner: String,
-    repo: String,
-    number: u64,
-}
 
 impl GroveApp {
     fn create_task_root_override(&self) -> Option<PathBuf> {
@@ -102,14 +97,18 @@ impl GroveApp {
                 agent: self
                     .state
                     .selected_workspace()
-                    .map(|workspace| workspace.agent)
+                    .map(|workspace| workspace.agent.clone())
                     .unwrap_or(AgentType::Codex),
                 base_branch,
             };
             let task_root_override = self.create_task_root_override();
             if !self.tmux_input.supports_background_launch() {
-                let result =
-                    execute_create_base_task_request(&base_request, task_root_override.as_deref());
+                let (result, outcomes) = execute_create_base_task_request(
+                    &base_request,
+                    &repo_name,
+                    task_root_override.as_deref(),
+                );
+                self.log_hook_outcomes(&outcomes);
                 let request = shim_create_task_request(repo_name, project, base_request);
                 self.apply_create_workspace_completion(CreateWorkspaceCompletion {
                     request: CreateWorkspaceRequest::CreateTask(request),
@@ -119,13 +118,17 @@ impl GroveApp {
             }
             self.dialogs.create_in_flight = true;
             self.queue_cmd(Cmd::task(move || {
-                let result =
-                    execute_create_base_task_request(&base_request, task_root_override.as_deref());
+                let (result, outcomes) = execute_create_base_task_request(
+                    &base_request,
+                    &repo_name,
+                    task_root_override.as_deref(),
+                );
                 let request = shim_create_task_request(repo_name, project, base_request);
                 Msg::CreateWorkspaceCompleted(Box::new(CreateWorkspaceCompletion {
                     request: CreateWorkspaceRequest::CreateTask(request),
                     result: CreateWorkspaceResult::CreateTask(result),
                 }))
+                .with_hook_outcomes(outcomes)
             }));
             return;
         }
@@ -190,15 +193,23 @@ impl GroveApp {
                 ),
             ],
         );
-        let request = CreateTaskRequest {
+        let mut request = CreateTaskRequest {
             task_name: task_name.clone(),
             repositories,
             agent: self
                 .state
                 .selected_workspace()
-                .map(|workspace| workspace.agent)
+                .map(|workspace| workspace.agent.clone())
                 .unwrap_or(AgentType::Codex),
             branch_source,
+            recipe: None,
+        };
+        let recipe = match apply_create_dialog_recipe(&dialog, &mut request, &self.agents) {
+            Ok(recipe) => recipe,
+            Err(message) => {
+                self.show_info_toast(message);
+                return;
+            }
         };
 
         if let Err(error) = request.validate() {
@@ -208,7 +219,13 @@ impl GroveApp {
 
         let task_root_override = self.create_task_root_override();
         if !self.tmux_input.supports_background_launch() {
-            let result = execute_create_task_request(&request, task_root_override.as_deref());
+            let (result, outcomes) = execute_create_task_request(
+                &request,
+                &self.agents,
+                recipe.as_ref(),
+                task_root_override.as_deref(),
+            );
+            self.log_hook_outcomes(&outcomes);
             self.apply_create_workspace_completion(CreateWorkspaceCompletion {
                 request: CreateWorkspaceRequest::CreateTask(request),
                 result: CreateWorkspaceResult::CreateTask(result),
@@ -216,13 +233,20 @@ impl GroveApp {
             return;
         }
 
+        let agents = Arc::clone(&self.agents);
         self.dialogs.create_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
-            let result = execute_create_task_request(&request, task_root_override.as_deref());
+            let (result, outcomes) = execute_create_task_request(
+                &request,
+                &agents,
+                recipe.as_ref(),
+                task_root_override.as_deref(),
+            );
             Msg::CreateWorkspaceCompleted(Box::new(CreateWorkspaceCompletion {
                 request: CreateWorkspaceRequest::CreateTask(request),
                 result: CreateWorkspaceResult::CreateTask(result),
             }))
+            .with_hook_outcomes(outcomes)
         }));
     }
 
@@ -251,12 +275,14 @@ impl GroveApp {
             agent: self
                 .state
                 .selected_workspace()
-                .map(|workspace| workspace.agent)
+                .map(|workspace| workspace.agent.clone())
                 .unwrap_or(AgentType::Codex),
         };
         let task_root_override = self.create_task_root_override();
         if !self.tmux_input.supports_background_launch() {
-            let result = execute_add_worktree_request(&request, task_root_override.as_deref());
+            let (result, outcomes) =
+                execute_add_worktree_request(&request, &self.agents, task_root_override.as_deref());
+            self.log_hook_outcomes(&outcomes);
             self.apply_create_workspace_completion(CreateWorkspaceCompletion {
                 request: CreateWorkspaceRequest::AddWorktree(Box::new(request)),
                 result: CreateWorkspaceResult::AddWorktree(result),
@@ -264,13 +290,16 @@ impl GroveApp {
             return;
         }
 
+        let agents = Arc::clone(&self.agents);
         self.dialogs.create_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
-            let result = execute_add_worktree_request(&request, task_root_override.as_deref());
+            let (result, outcomes) =
+                execute_add_worktree_request(&request, &agents, task_root_override.as_deref());
             Msg::CreateWorkspaceCompleted(Box::new(CreateWorkspaceCompletion {
                 request: CreateWorkspaceRequest::AddWorktree(Box::new(request)),
                 result: CreateWorkspaceResult::AddWorktree(result),
             }))
+            .with_hook_outcomes(outcomes)
         }));
     }
 
@@ -300,7 +329,15 @@ impl GroveApp {
                             ));
                         }
                     }
+                    self.record_task_history_event(&result.task.slug, "created", None);
                     self.close_active_dialog();
+                    self.pending_command_tab_autostart.extend(
+                        result
+                            .task
+                            .worktrees
+                            .iter()
+                            .map(|worktree| worktree.path.clone()),
+                    );
                     let preferred_workspace_path = result
                         .task
                         .worktrees
@@ -329,7 +366,14 @@ impl GroveApp {
                 CreateWorkspaceResult::AddWorktree(result),
             ) => match result {
                 Ok(result) => {
+                    self.record_task_history_event(
+                        &request.task.slug,
+                        "worktree_added",
+                        Some(result.added_worktree_path.as_path()),
+                    );
                     self.close_active_dialog();
+                    self.pending_command_tab_autostart
+                        .push(result.added_worktree_path.clone());
                     self.refresh_workspaces(Some(result.added_worktree_path));
                     let _ = self.focus_main_pane(FOCUS_ID_WORKSPACE_LIST);
                     if result.warnings.is_empty() {
@@ -361,26 +405,61 @@ impl GroveApp {
 
 fn execute_create_base_task_request(
     request: &CreateBaseTaskRequest,
+    task_name: &str,
     tasks_root_override: Option<&Path>,
-) -> Result<CreateTaskResult, TaskLifecycleError> {
-    if let Some(tasks_root) = tasks_root_override {
-        return create_base_task_in_root(tasks_root, request);
-    }
-    create_base_task(request)
+) -> (
+    Result<CreateTaskResult, TaskLifecycleError>,
+    Vec<HookOutcome>,
+) {
+    let context = HookContext {
+        task_name: Some(task_name.to_string()),
+        branch: Some(request.base_branch.clone()),
+        agent: Some(request.agent.marker().to_string()),
+        ..HookContext::default()
+    };
+    create_with_hooks(
+        std::slice::from_ref(&request.repository),
+        context,
+        || {
+            if let Some(tasks_root) = tasks_root_override {
+                return create_base_task_in_root(tasks_root, request);
+            }
+            create_base_task(request)
+        },
+        |result| &result.task,
+    )
 }
 
 fn execute_add_worktree_request(
     request: &AddWorktreeToTaskRequest,
+    agents: &AgentRegistry,
     tasks_root_override: Option<&Path>,
-) -> Result<AddWorktreeToTaskResult, TaskLifecycleError> {
-    let git = CommandGitRunner;
-    let setup = CommandSetupScriptRunner;
-    let setup_command = CommandSetupCommandRunner;
-    if let Some(tasks_root) = tasks_root_override {
-        return add_worktree_to_task_in_root(tasks_root, request, &git, &setup, &setup_command);
-    }
+) -> (
+    Result<AddWorktreeToTaskResult, TaskLifecycleError>,
+    Vec<HookOutcome>,
+) {
+    create_with_hooks(
+        std::slice::from_ref(&request.repository),
+        HookContext::for_task(&request.task),
+        || {
+            let git = CommandGitRunner;
+            let setup = CommandSetupScriptRunner;
+            let setup_command = CommandSetupCommandRunner;
+            if let Some(tasks_root) = tasks_root_override {
+                return add_worktree_to_task_in_root(
+                    tasks_root,
+                    request,
+                    agents,
+                    &git,
+                    &setup,
+                    &setup_command,
+                );
+            }
 
-    add_worktree_to_task(request, &git, &setup, &setup_command)
+            add_worktree_to_task(request, agents, &git, &setup, &setup_command)
+        },
+        |result| &result.task,
+    )
 }
 
 fn shim_create_task_request(
@@ -393,105 +472,58 @@ fn shim_create_task_request(
         repositories: vec![project],
         agent: base_request.agent,
         branch_source: TaskBranchSource::BaseBranch,
+        recipe: None,
     }
 }
 
-fn execute_create_task_request(
-    request: &CreateTaskRequest,
-    tasks_root_override: Option<&Path>,
-) -> Result<CreateTaskResult, TaskLifecycleError> {
-    let git = CommandGitRunner;
-    let setup = CommandSetupScriptRunner;
-    let setup_command = CommandSetupCommandRunner;
-    if let Some(tasks_root) = tasks_root_override {
-        return create_task_in_root(tasks_root, request, &git, &setup, &setup_command);
-    }
-
-    create_task(request, &git, &setup, &setup_command)
-}
-
-fn resolve_pull_request_branch_name_with_gh(
-    pull_request: &ParsedGitHubPullRequest,
-) -> Result<String, String> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!(
-                "repos/{}/{}/pulls/{}",
-                pull_request.owner, pull_request.repo, pull_request.number
-            ),
-        ])
-        .output()
-        .map_err(|error| format!("gh api failed: {error}"))?;
-    if !output.status.success() {
-        return Err(stderr_trimmed(&output));
-    }
-
-    parse_pull_request_head_branch_name(&output.stdout)
-}
-
-fn parse_pull_request_head_branch_name(stdout: &[u8]) -> Result<String, String> {
-    let payload: Value =
-        serde_json::from_slice(stdout).map_err(|error| format!("invalid gh response: {error}"))?;
-    let Some(branch_name) = payload
-        .get("head")
-        .and_then(|head| head.get("ref"))
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
+/// Applies the dialog's recipe to `request`, returning it with its rendered prompt.
+fn apply_create_dialog_recipe(
+    dialog: &CreateDialogState,
+    request: &mut CreateTaskRequest,
+    agents: &AgentRegistry,
+) -> Result<Option<(Recipe, String)>, String> {
+    let Some(recipe) = dialog
+        .selected_recipe()
+        .filter(|_| dialog.shows_recipe_fields())
     else {
-        return Err("pull request head branch is missing".to_string());
+        return Ok(None);
     };
-
-    Ok(branch_name.to_string())
-}
-
-fn parse_github_pull_request_url(url: &str) -> Result<ParsedGitHubPullRequest, String> {
-    let trimmed = url.trim();
-    let trimmed = trimmed.trim_end_matches('/');
-    let parts = trimmed.split('/').collect::<Vec<&str>>();
-    if parts.len() < 7 {
-        return Err("GitHub pull request URL is invalid".to_string());
-    }
-
-    let owner = parts[3].trim();
-    let repo = parts[4].trim();
-    let kind = parts[5].trim();
-    let number = parts[6].trim();
-    if owner.is_empty() || repo.is_empty() || kind != "pull" {
-        return Err("GitHub pull request URL is invalid".to_string());
-    }
-    let number = number
-        .parse::<u64>()
-        .map_err(|_| "GitHub pull request number is invalid".to_string())?;
-
-    Ok(ParsedGitHubPullRequest {
-        owner: owner.to_string(),
-        repo: repo.to_string(),
-        number,
-    })
+    let prompt = recipe.render_prompt(&dialog.recipe_variable_values()?)?;
+    recipe.apply_to_request(request, agents)?;
+    Ok(Some((recipe.clone(), prompt)))
 }
 
-fn ensure_project_matches_pull_request(
-    project_root: &Path,
-    pull_request: &ParsedGitHubPullRequest,
-) -> Result<(), String> {
-    let output = Command::new("git")
-        .current_dir(project_root)
-        .args(["remote", "get-url", "origin"])
-        .output()
-        .map_err(|error| format!("git remote get-url origin failed: {error}"))?;
-    if !output.status.success() {
-        return Err(stderr_trimmed(&output));
-    }
-
-    let origin = String::from_utf8(output.stdout)
-        .map_err(|error| format!("origin URL was invalid UTF-8: {error}"))?;
-    let normalized = origin.trim().replace(':', "/");
-    let expected_fragment = format!("{}/{}", pull_request.owner, pull_request.repo);
-    if normalized.contains(expected_fragment.as_str()) {
-        return Ok(());
-    }
-
-    Err("selected project does not match pull request repository".to_string())
+fn execute_create_task_request(
+    request: &CreateTaskRequest,
+    agents: &AgentRegistry,
+    recipe: Option<&(Recipe, String)>,
+    tasks_root_override: Option<&Path>,
+) -> (
+    Result<CreateTaskResult, TaskLifecycleError>,
+    Vec<HookOutcome>,
+) {
+    create_with_hooks(
+        &request.repositories,
+        HookContext::for_create_request(request),
+        || {
+            let git = CommandGitRunner;
+            let setup = CommandSetupScriptRunner;
+            let setup_command = CommandSetupCommandRunner;
+            let result = match tasks_root_override {
+                Some(tasks_root) => {
+                    create_task_in_root(tasks_root, request, agents, &git, &setup, &setup_command)
+                }
+                None => create_task(request, agents, &git, &setup, &setup_command),
+            };
+            result.map(|mut created| {
+                if let Some((recipe, prompt)) = recipe {
+                    created
+                        .warnings
+                        .extend(write_recipe_markers(&created.task, recipe, prompt));
+                }
+                created
+            })
+        },
+        |result| &result.task,
+    )
 }
@@ -0,0 +1,122 @@
+use super::update_prelude::*;
+
+impl GroveApp {
+    /// Hooks for `event` declared by the projects owning `worktree_paths`.
+    pub(super) fn hook_invocation_for_paths(
+        &self,
+        event: HookEvent,
+        worktree_paths: &[PathBuf],
+    ) -> Option<HookInvocation> {
+        let workspaces = worktree_paths
+            .iter()
+            .filter_map(|path| {
+                self.state
+                    .workspaces
+                    .iter()
+                    .find(|workspace| &workspace.path == path)
+            })
+            .collect::<Vec<&Workspace>>();
+        let mut projects = Vec::<&ProjectConfig>::new();
+        for workspace in &workspaces {
+            if let Some(project) = self.project_for_workspace(workspace)
+                && !projects
+                    .iter()
+                    .any(|known| refer_to_same_location(&known.path, &project.path))
+            {
+                projects.push(project);
+            }
+        }
+        let first = workspaces.first()?;
+        let context = HookContext {
+            worktree_paths: worktree_paths.to_vec(),
+            ..HookContext::for_workspace(first, self.task_name_for_workspace(first))
+        };
+        HookInvocation::for_projects(event, projects, context)
+    }
+
+    pub(super) fn hook_invocation_for_workspace(
+        &self,
+        event: HookEvent,
+        workspace: &Workspace,
+    ) -> Option<HookInvocation> {
+        HookInvocation::for_workspace(
+            event,
+            &self.projects,
+            workspace,
+            self.task_name_for_workspace(workspace),
+        )
+    }
+
+    pub(super) fn hook_invocation_for_task(
+        &self,
+        event: HookEvent,
+        task: &Task,
+    ) -> Option<HookInvocation> {
+        HookInvocation::for_task(event, &self.projects, task)
+    }
+
+    fn task_name_for_workspace(&self, workspace: &Workspace) -> Option<String> {
+        let task_slug = workspace.task_slug.as_deref()?;
+        self.state
+            .tasks
+            .iter()
+            .find(|task| task.slug == task_slug)
+            .map(|task| task.name.clone())
+    }
+
+    pub(super) fn run_attention_hooks(&mut self, raised: &[&AttentionItem]) {
+        for item in raised {
+            let Some(mut invocation) = self.hook_invocation_for_paths(
+                HookEvent::OnAttention,
+                std::slice::from_ref(&item.workspace_path),
+            ) else {
+                continue;
+            };
+            invocation.context.reason =
+                Some(item.reason.notification_reason().config_key().to_string());
+            self.run_detached_hooks(invocation);
+        }
+    }
+
+    /// Writes hook results to the event log. Failed hooks that could not veto
+    /// surface in the status bar, vetoes surface through the operation itself.
+    pub(super) fn log_hook_outcomes(&mut self, outcomes: &[HookOutcome]) {
+        for outcome in outcomes {
+            let kind = if outcome.succeeded() {
+                "hook_completed"
+            } else {
+                "hook_failed"
+            };
+            let mut event = LogEvent::new("hooks", kind)
+                .with_data("event", Value::from(outcome.event.config_key()))
+                .with_data("project", Value::from(outcome.project.clone()))
+                .with_data("command", Value::from(outcome.command.clone()))
+                .with_data("exit_code", Value::from(outcome.exit_code))
+                .with_data("timed_out", Value::from(outcome.timed_out))
+                .with_data("duration_ms", Value::from(outcome.duration_ms))
+                .with_data("stdout", Value::from(outcome.stdout.clone()))
+                .with_data("stderr", Value::from(outcome.stderr.clone()));
+            if let Some(error) = outcome.error.as_ref() {
+                event = event.with_data("error", Value::from(error.clone()));
+            }
+            self.telemetry.event_log.log(event);
+            if !outcome.succeeded() && !outcome.event.can_veto() {
+                self.session.last_tmux_error = Some(outcome.failure_message());
+            }
+        }
+    }
+
+    pub(super) fn run_detached_hooks(&mut self, invocation: HookInvocation) {
+        if !self.tmux_input.supports_background_launch() {
+            let report = invocation.run();
+            self.log_hook_outcomes(&report.outcomes);
+            return;
+        }
+        self.queue_cmd(Cmd::task(move || {
+            Msg::HooksCompleted(Box::new(HooksCompletion {
+                outcomes: invocation.run().outcomes,
+                next: None,
+            }))
+        }));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::domain::PermissionMode;
 
 use super::update_prelude::*;
+use crate::application::hooks::run_between_hooks;
 
 impl GroveApp {
     pub(super) fn selected_task_supports_parent_agent(&self) -> bool {
@@ -20,7 +21,7 @@ impl GroveApp {
     pub(super) fn task_agent_for_selected_task(&self) -> AgentType {
         self.state
             .selected_worktree()
-            .map(|worktree| worktree.agent)
+            .map(|worktree| worktree.agent.clone())
             .unwrap_or(AgentType::Codex)
     }
 
@@ -46,10 +47,7 @@ impl GroveApp {
         else {
             return Ok(Vec::new());
         };
-        let entries = match workspace.agent {
-            AgentType::Claude => &project.defaults.agent_env.claude,
-            AgentType::Codex => &project.defaults.agent_env.codex,
-        };
+        let entries = project.defaults.agent_env.entries_for(&workspace.agent);
         parse_agent_env_vars_from_entries(entries).map(|vars| {
             vars.into_iter()
                 .map(|entry| (entry.key, entry.value))
@@ -57,15 +55,42 @@ impl GroveApp {
         })
     }
 
+    pub(super) fn project_for_workspace(&self, workspace: &Workspace) -> Option<&ProjectConfig> {
+        let workspace_project_path = workspace.project_path.as_ref()?;
+        self.projects
+            .iter()
+            .find(|project| refer_to_same_location(&project.path, workspace_project_path))
+    }
+
+    /// Task agents run from the task root, so the first worktree project with
+    /// a sandbox decides how they are wrapped.
+    pub(super) fn project_sandbox_for_task(&self, task: &Task) -> Option<SandboxConfig> {
+        task.worktrees.iter().find_map(|worktree| {
+            self.projects
+                .iter()
+                .find(|project| refer_to_same_location(&project.path, &worktree.repository_path))
+                .and_then(|project| project.defaults.sandbox.clone())
+        })
+    }
+
+    /// Where a session's transcript is recorded, when transcripts are enabled.
+    pub(super) fn transcript_dir_for_workspace(&self, workspace: &Workspace) -> Option<PathBuf> {
+        if !self.transcripts_enabled {
+            return None;
+        }
+        let task_slug = workspace.task_slug.as_deref()?;
+        self.state
+            .tasks
+            .iter()
+            .find(|task| task.slug == task_slug)
+            .map(|task| transcripts_dir(task.root_path.as_path()))
+    }
+
     pub(super) fn project_workspace_init_command_for_workspace(
         &self,
         workspace: &Workspace,
     ) -> Option<String> {
-        let workspace_project_path = workspace.project_path.as_ref()?;
-        let project = self
-            .projects
-            .iter()
-            .find(|project| refer_to_same_location(&project.path, workspace_project_path))?;
+        let project = self.project_for_workspace(workspace)?;
         trimmed_nonempty(project.defaults.workspace_init_command.as_str())
     }
 
@@ -85,7 +110,7 @@ impl GroveApp {
             return permission_mode;
         }
         if let Some(permission_mode) =
-            infer_workspace_permission_mode(workspace.agent, &workspace.path)
+            infer_workspace_permission_mode(&workspace.agent, &workspace.path)
         {
             return permission_mode;
         }
@@ -120,37 +145,67 @@ impl GroveApp {
             task_slug: task.slug.clone(),
             task_root: task.root_path.clone(),
             agent,
-            theme_name: self.theme_name,
+            theme_name: self.theme_name.clone(),
             prompt,
             workspace_init_command: init_command.or_else(|| self.task_init_command_for_task(&task)),
             permission_mode,
             agent_env: Vec::new(),
+            sandbox: self.project_sandbox_for_task(&task),
             capture_cols: Some(capture_cols),
             capture_rows: Some(capture_rows),
+            transcript_dir: self
+                .transcripts_enabled
+                .then(|| transcripts_dir(task.root_path.as_path())),
+            status_hooks: self.status_hooks_enabled,
+        };
+
+        let pre_start_hooks = self.hook_invocation_for_task(HookEvent::PreAgentStart, &task);
+        let vetoed_completion = move |veto: String| StartAgentCompletion {
+            session_name: session_name_for_task(&task.slug),
+            workspace_name: task.name,
+            workspace_path: task.root_path,
+            result: Err(veto),
         };
 
         if !self.tmux_input.supports_background_launch() {
-            let completion = execute_task_launch_request_with_result_for_mode(
-                &request,
-                CommandExecutionMode::Delegating(&mut |command| self.execute_tmux_command(command)),
+            let (completion, outcomes) = run_between_hooks(
+                pre_start_hooks,
+                || {
+                    StartAgentCompletion::from(execute_task_launch_request_with_result_for_mode(
+                        &request,
+                        CommandExecutionMode::Delegating(&mut |command| {
+                            self.execute_tmux_command(command)
+                        }),
+                    ))
+                },
+                vetoed_completion,
+                |_| None,
             );
+            self.log_hook_outcomes(&outcomes);
             if let Some(error) = completion.result.as_ref().err() {
                 self.session.last_tmux_error = Some(error.clone());
                 self.show_error_toast("agent start failed");
                 return;
             }
 
-            self.apply_start_agent_completion(completion.into());
+            self.apply_start_agent_completion(completion);
             return;
         }
 
         self.dialogs.start_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
-            let completion = execute_task_launch_request_with_result_for_mode(
-                &request,
-                CommandExecutionMode::Process,
+            let (completion, outcomes) = run_between_hooks(
+                pre_start_hooks,
+                || {
+                    StartAgentCompletion::from(execute_task_launch_request_with_result_for_mode(
+                        &request,
+                        CommandExecutionMode::Process,
+                    ))
+                },
+                vetoed_completion,
+                |_| None,
             );
-            Msg::StartAgentCompleted(completion.into())
+            Msg::StartAgentCompleted(completion).with_hook_outcomes(outcomes)
         }));
     }
 
@@ -188,38 +243,67 @@ impl GroveApp {
         let (capture_cols, capture_rows) = self.capture_dimensions();
         let workspace_init_command =
             init_command.or_else(|| self.workspace_init_command_for_workspace(&workspace));
-        let request = launch_request_for_workspace(
+        let mut request = launch_request_for_workspace(
             &workspace,
             prompt,
-            self.theme_name,
+            self.theme_name.clone(),
             workspace_init_command,
             permission_mode,
             agent_env,
             Some((capture_cols, capture_rows)),
         );
+        request.sandbox = project_sandbox_for_workspace(&self.projects, &workspace);
+        request.transcript_dir = self.transcript_dir_for_workspace(&workspace);
+        request.status_hooks = self.status_hooks_enabled;
+
+        let pre_start_hooks =
+            self.hook_invocation_for_workspace(HookEvent::PreAgentStart, &workspace);
+        let vetoed_completion = move |veto: String| StartAgentCompletion {
+            session_name: session_name_for_workspace_ref(&workspace),
+            workspace_name: workspace.name,
+            workspace_path: workspace.path,
+            result: Err(veto),
+        };
 
         if !self.tmux_input.supports_background_launch() {
-            let completion = execute_launch_request_with_result_for_mode(
-                &request,
-                CommandExecutionMode::Delegating(&mut |command| self.execute_tmux_command(command)),
+            let (completion, outcomes) = run_between_hooks(
+                pre_start_hooks,
+                || {
+                    StartAgentCompletion::from(execute_launch_request_with_result_for_mode(
+                        &request,
+                        CommandExecutionMode::Delegating(&mut |command| {
+                            self.execute_tmux_command(command)
+                        }),
+                    ))
+                },
+                vetoed_completion,
+                |_| None,
             );
+            self.log_hook_outcomes(&outcomes);
             if let Some(error) = completion.result.as_ref().err() {
                 self.session.last_tmux_error = Some(error.clone());
                 self.show_error_toast("agent start failed");
                 return;
             }
 
-            self.apply_start_agent_completion(completion.into());
+            self.apply_start_agent_completion(completion);
             return;
         }
 
         self.dialogs.start_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
-            let completion = execute_launch_request_with_result_for_mode(
-                &request,
-                CommandExecutionMode::Process,
+            let (completion, outcomes) = run_between_hooks(
+                pre_start_hooks,
+                || {
+                    StartAgentCompletion::from(execute_launch_request_with_result_for_mode(
+                        &request,
+                        CommandExecutionMode::Process,
+                    ))
+                },
+                vetoed_completion,
+                |_| None,
             );
-            Msg::StartAgentCompleted(completion.into())
+            Msg::StartAgentCompleted(completion).with_hook_outcomes(outcomes)
         }));
     }
 
@@ -280,6 +364,7 @@ impl GroveApp {
                     false,
                 );
             }
+            self.record_workspace_history_event(&completion.workspace_path, "agent_started");
             let mut event = LogEvent::new("agent_lifecycle", "agent_started")
                 .with_data("workspace", Value::from(completion.workspace_name))
                 .with_data("session", Value::from(completion.session_name));
@@ -341,12 +426,14 @@ impl GroveApp {
             prompt,
             init_command,
             permission_mode,
+            resume_command,
         } = dialog.start_config.parse_start_options();
         let options = StartOptions {
             name,
             prompt,
             init_command,
             permission_mode,
+            resume_command,
         };
         match dialog.target {
             LaunchDialogTarget::WorkspaceTab => {
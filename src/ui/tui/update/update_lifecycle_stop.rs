@@ -15,6 +15,7 @@ impl GroveApp {
             return;
         }
 
+        let post_stop_hooks = self.hook_invocation_for_task(HookEvent::PostAgentStop, &task);
         if !self.tmux_input.supports_background_launch() {
             let completion = execute_stop_task_with_result_for_mode(
                 &task.name,
@@ -29,6 +30,9 @@ impl GroveApp {
             }
 
             self.apply_stop_agent_completion(completion.into());
+            if let Some(hooks) = post_stop_hooks {
+                self.run_detached_hooks(hooks);
+            }
             return;
         }
 
@@ -40,7 +44,11 @@ impl GroveApp {
                 &task.slug,
                 CommandExecutionMode::Process,
             );
-            Msg::StopAgentCompleted(completion.into())
+            let hooks = post_stop_hooks
+                .filter(|_| completion.result.is_ok())
+                .map(|hooks| hooks.run())
+                .unwrap_or_default();
+            Msg::StopAgentCompleted(completion.into()).with_hook_outcomes(hooks.outcomes)
         }));
     }
 
@@ -99,6 +107,8 @@ impl GroveApp {
                 return;
             }
         };
+        let sandbox = project_sandbox_for_workspace(&self.projects, &workspace);
+        let status_hooks = self.status_hooks_enabled;
         if !self.tmux_input.supports_background_launch() {
             let session_name = session_name_for_workspace_ref(&workspace);
             let completion = RestartAgentCompletion {
@@ -109,6 +119,8 @@ impl GroveApp {
                     &workspace,
                     permission_mode,
                     &agent_env,
+                    sandbox.as_ref(),
+                    status_hooks,
                     |command| self.tmux_input.execute(command),
                     |target_session, scrollback_lines, include_escape_sequences| {
                         self.tmux_input.capture_output(
@@ -129,6 +141,8 @@ impl GroveApp {
                 &workspace,
                 permission_mode,
                 agent_env,
+                sandbox,
+                status_hooks,
             );
             Msg::RestartAgentCompleted(completion.into())
         }));
@@ -144,6 +158,8 @@ impl GroveApp {
             return;
         }
 
+        let post_stop_hooks =
+            self.hook_invocation_for_workspace(HookEvent::PostAgentStop, &workspace);
         if !self.tmux_input.supports_background_launch() {
             let completion = execute_stop_workspace_with_result_for_mode(
                 &workspace,
@@ -157,6 +173,9 @@ impl GroveApp {
             }
 
             self.apply_stop_agent_completion(completion.into());
+            if let Some(hooks) = post_stop_hooks {
+                self.run_detached_hooks(hooks);
+            }
             return;
         }
 
@@ -166,7 +185,11 @@ impl GroveApp {
                 &workspace,
                 CommandExecutionMode::Process,
             );
-            Msg::StopAgentCompleted(completion.into())
+            let hooks = post_stop_hooks
+                .filter(|_| completion.result.is_ok())
+                .map(|hooks| hooks.run())
+                .unwrap_or_default();
+            Msg::StopAgentCompleted(completion.into()).with_hook_outcomes(hooks.outcomes)
         }));
     }
 
@@ -223,6 +246,7 @@ impl GroveApp {
                 self.clear_agent_activity_tracking();
                 let _ = self.focus_main_pane(FOCUS_ID_WORKSPACE_LIST);
                 self.refresh_preview_summary();
+                self.record_workspace_history_event(&completion.workspace_path, "agent_stopped");
                 self.telemetry.event_log.log(
                     LogEvent::new("agent_lifecycle", "agent_stopped")
                         .with_data("workspace", Value::from(completion.workspace_name))
@@ -269,6 +293,7 @@ impl GroveApp {
                         false,
                     );
                 }
+                self.record_workspace_history_event(&completion.workspace_path, "agent_restarted");
                 self.telemetry.event_log.log(
                     LogEvent::new("agent_lifecycle", "agent_restarted")
                         .with_data("workspace", Value::from(completion.workspace_name))
@@ -2,6 +2,13 @@ use super::update_prelude::*;
 
 impl GroveApp {
     fn summarize_merge_failure(error: &str) -> String {
+        if ["merge", "squash", "rebase"]
+            .iter()
+            .any(|operation| error.starts_with(format!("{operation} conflict in ").as_str()))
+        {
+            return error.to_string();
+        }
+
         let conflict_prefix = "CONFLICT (content): Merge conflict in ";
         let conflict_files = error
             .lines()
@@ -96,6 +103,14 @@ impl GroveApp {
                         ));
                     }
                 }
+                self.record_workspace_history_event(
+                    &completion.workspace_path,
+                    if completion.deleted_task {
+                        "deleted"
+                    } else {
+                        "worktree_deleted"
+                    },
+                );
                 self.telemetry.event_log.log(if completion.deleted_task {
                     LogEvent::new("task_lifecycle", "task_deleted")
                         .with_data("task", Value::from(completion.workspace_name.clone()))
@@ -161,6 +176,7 @@ impl GroveApp {
         self.dialogs.merge_in_flight = false;
         match completion.result {
             Ok(()) => {
+                self.record_workspace_history_event(&completion.workspace_path, "merged");
                 self.telemetry.event_log.log(
                     LogEvent::new("workspace_lifecycle", "workspace_merged")
                         .with_data("workspace", Value::from(completion.workspace_name.clone()))
@@ -202,6 +218,10 @@ impl GroveApp {
                         )
                         .with_data("error", Value::from(error.clone())),
                 );
+                if let Some(conflict) = completion.conflict {
+                    self.workspace_conflicts
+                        .insert(completion.workspace_path, conflict);
+                }
                 self.session.last_tmux_error = Some(error.clone());
                 self.show_error_toast(Self::summarize_merge_failure(&error));
             }
@@ -215,6 +235,10 @@ impl GroveApp {
         self.dialogs.update_from_base_in_flight = false;
         match completion.result {
             Ok(()) => {
+                self.record_workspace_history_event(
+                    &completion.workspace_path,
+                    "updated_from_base",
+                );
                 self.telemetry.event_log.log(
                     LogEvent::new("workspace_lifecycle", "workspace_updated_from_base")
                         .with_data("workspace", Value::from(completion.workspace_name.clone()))
@@ -256,6 +280,10 @@ impl GroveApp {
                         )
                         .with_data("error", Value::from(error.clone())),
                 );
+                if let Some(conflict) = completion.conflict {
+                    self.workspace_conflicts
+                        .insert(completion.workspace_path, conflict);
+                }
                 self.session.last_tmux_error = Some(error.clone());
                 self.show_error_toast(format!("workspace update failed: {error}"));
             }
@@ -0,0 +1,182 @@
+use super::update_prelude::*;
+
+impl GroveApp {
+    pub(super) fn conflict_probe_path(&self, workspace: &Workspace) -> PathBuf {
+        self.workspace_conflicts
+            .get(&workspace.path)
+            .map(|conflict| conflict.worktree_path.clone())
+            .unwrap_or_else(|| workspace.path.clone())
+    }
+
+    pub(super) fn conflict_left_in_place(
+        result: &Result<(), String>,
+        keep_conflicts: bool,
+        worktree_paths: &[PathBuf],
+    ) -> Option<WorkspaceConflict> {
+        if !keep_conflicts || result.is_ok() {
+            return None;
+        }
+        worktree_paths
+            .iter()
+            .find_map(|path| detect_workspace_conflict(path))
+    }
+
+    pub(super) fn selected_workspace_conflict(&self) -> Option<(Workspace, WorkspaceConflict)> {
+        let workspace = self.state.selected_workspace()?;
+        let conflict = self.workspace_conflicts.get(&workspace.path)?;
+        Some((workspace.clone(), conflict.clone()))
+    }
+
+    pub(super) fn hand_selected_conflict_to_agent(&mut self) {
+        let Some((workspace, conflict)) = self.selected_workspace_conflict() else {
+            self.show_info_toast("no conflict to resolve");
+            return;
+        };
+        let Some(session_name) =
+            self.workspace_running_agent_session_for_status_poll(&workspace.path, None)
+        else {
+            self.show_info_toast("no running agent in this workspace");
+            return;
+        };
+
+        let prompt = conflict_resolution_prompt(&conflict);
+        if let Err(error) = self.tmux_input.paste_buffer(&session_name, &prompt) {
+            self.session.last_tmux_error = Some(error.to_string());
+            self.show_error_toast(format!("conflict hand-off failed: {error}"));
+            return;
+        }
+        let send_enter = vec![
+            "tmux".to_string(),
+            "send-keys".to_string(),
+            "-t".to_string(),
+            session_name.clone(),
+            "Enter".to_string(),
+        ];
+        if let Err(error) = self.execute_tmux_command(&send_enter) {
+            self.session.last_tmux_error = Some(error.to_string());
+            self.show_error_toast(format!("conflict hand-off failed: {error}"));
+            return;
+        }
+
+        self.telemetry.event_log.log(
+            LogEvent::new("workspace_lifecycle", "conflict_handed_to_agent")
+                .with_data("workspace", Value::from(workspace.name.clone()))
+                .with_data("session", Value::from(session_name))
+                .with_data("operation", Value::from(conflict.operation.name()))
+                .with_data(
+                    "file_count",
+                    Value::from(usize_to_u64(conflict.files.len())),
+                ),
+        );
+        self.show_success_toast(format!(
+            "{} conflict handed to agent in '{}'",
+            conflict.operation.name(),
+            workspace.name
+        ));
+    }
+
+    pub(super) fn continue_selected_conflict(&mut self) {
+        self.run_selected_conflict_action(WorkspaceConflictAction::Continue);
+    }
+
+    pub(super) fn abort_selected_conflict(&mut self) {
+        self.run_selected_conflict_action(WorkspaceConflictAction::Abort);
+    }
+
+    fn run_selected_conflict_action(&mut self, action: WorkspaceConflictAction) {
+        if self.dialogs.conflict_action_in_flight {
+            self.show_info_toast("conflict action already in progress");
+            return;
+        }
+        let Some((workspace, conflict)) = self.selected_workspace_conflict() else {
+            self.show_info_toast("no conflict to resolve");
+            return;
+        };
+
+        let workspace_name = workspace.name;
+        let workspace_path = workspace.path;
+        let worktree_path = conflict.worktree_path;
+        let run = move || match action {
+            WorkspaceConflictAction::Continue => continue_workspace_conflict_with_terminator(
+                &worktree_path,
+                &RuntimeSessionTerminator,
+            ),
+            WorkspaceConflictAction::Abort => {
+                (abort_workspace_conflict(&worktree_path), Vec::new())
+            }
+        };
+
+        if !self.tmux_input.supports_background_launch() {
+            let (result, warnings) = run();
+            self.apply_workspace_conflict_action_completion(WorkspaceConflictActionCompletion {
+                workspace_name,
+                workspace_path,
+                action,
+                result,
+                warnings,
+            });
+            return;
+        }
+
+        self.dialogs.conflict_action_in_flight = true;
+        self.queue_cmd(Cmd::task(move || {
+            let (result, warnings) = run();
+            Msg::WorkspaceConflictActionCompleted(WorkspaceConflictActionCompletion {
+                workspace_name,
+                workspace_path,
+                action,
+                result,
+                warnings,
+            })
+        }));
+    }
+
+    pub(super) fn apply_workspace_conflict_action_completion(
+        &mut self,
+        completion: WorkspaceConflictActionCompletion,
+    ) {
+        self.dialogs.conflict_action_in_flight = false;
+        let verb = match completion.action {
+            WorkspaceConflictAction::Continue => "continued",
+            WorkspaceConflictAction::Abort => "aborted",
+        };
+        match completion.result {
+            Ok(operation) => {
+                self.workspace_conflicts.remove(&completion.workspace_path);
+                self.telemetry.event_log.log(
+                    LogEvent::new("workspace_lifecycle", format!("conflict_{verb}"))
+                        .with_data("workspace", Value::from(completion.workspace_name.clone()))
+                        .with_data("operation", Value::from(operation.name()))
+                        .with_data(
+                            "warning_count",
+                            Value::from(usize_to_u64(completion.warnings.len())),
+                        ),
+                );
+                self.session.last_tmux_error = None;
+                self.refresh_workspaces(Some(completion.workspace_path));
+                match completion.warnings.first() {
+                    Some(first_warning) => self.show_info_toast(format!(
+                        "{} {verb} in '{}', warning: {first_warning}",
+                        operation.name(),
+                        completion.workspace_name
+                    )),
+                    None => self.show_success_toast(format!(
+                        "{} {verb} in '{}'",
+                        operation.name(),
+                        completion.workspace_name
+                    )),
+                }
+            }
+            Err(error) => {
+                self.telemetry.event_log.log(
+                    LogEvent::new("workspace_lifecycle", "conflict_action_failed")
+                        .with_data("workspace", Value::from(completion.workspace_name))
+                        .with_data("action", Value::from(verb))
+                        .with_data("error", Value::from(error.clone())),
+                );
+                self.session.last_tmux_error = Some(error.clone());
+                self.show_error_toast(format!("conflict not {verb}: {error}"));
+            }
+        }
+    }
+}
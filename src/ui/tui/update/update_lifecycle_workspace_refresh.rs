@@ -15,10 +15,18 @@ fn refreshed_app_state(
     tasks_root_path: Option<&Path>,
     projects: &[ProjectConfig],
     hidden_base_project_paths: &[PathBuf],
+    state_store: Option<&StateStore>,
+    agents: &AgentRegistry,
 ) -> RefreshedAppState {
     let bootstrap = tasks_root_path
         .map(|tasks_root| {
-            bootstrap_task_data_for_root(tasks_root, projects, hidden_base_project_paths)
+            bootstrap_task_data_for_root(
+                tasks_root,
+                projects,
+                hidden_base_project_paths,
+                state_store,
+                agents,
+            )
         })
         .unwrap_or_else(|| crate::application::task_discovery::TaskBootstrapData {
             tasks: Vec::new(),
@@ -108,12 +116,24 @@ impl GroveApp {
         let tasks_root_path = self.resolved_tasks_root();
         let projects = self.projects.clone();
         let hidden_base_project_paths = self.hidden_base_project_paths_for_config();
+        let agents = Arc::clone(&self.agents);
+        let state_store_path = self
+            .telemetry
+            .state_store
+            .as_ref()
+            .and_then(StateStore::path)
+            .map(Path::to_path_buf);
         self.dialogs.refresh_in_flight = true;
         self.queue_cmd(Cmd::task(move || {
+            let state_store = state_store_path
+                .as_deref()
+                .and_then(|path| StateStore::open(path).ok());
             let refreshed = refreshed_app_state(
                 tasks_root_path.as_deref(),
                 &projects,
                 &hidden_base_project_paths,
+                state_store.as_ref(),
+                &agents,
             );
             Msg::RefreshWorkspacesCompleted(RefreshWorkspacesCompletion {
                 preferred_workspace_path: target_path,
@@ -135,6 +155,8 @@ impl GroveApp {
             tasks_root_path.as_deref(),
             &self.projects,
             &self.hidden_base_project_paths_for_config(),
+            self.telemetry.state_store.as_ref(),
+            &self.agents,
         );
 
         self.repo_name = refreshed.repo_name;
@@ -152,6 +174,7 @@ impl GroveApp {
         self.reconcile_workspace_attention_tracking();
         self.clear_agent_activity_tracking();
         self.clear_status_tracking();
+        self.start_pending_command_tabs();
         self.poll_preview();
         self.finalize_manual_workspace_refresh_feedback();
     }
@@ -178,6 +201,7 @@ impl GroveApp {
         self.reconcile_workspace_attention_tracking();
         self.clear_agent_activity_tracking();
         self.clear_status_tracking();
+        self.start_pending_command_tabs();
         self.poll_preview();
         self.finalize_manual_workspace_refresh_feedback();
     }
@@ -186,7 +210,7 @@ impl GroveApp {
 #[cfg(test)]
 mod tests {
     use super::refreshed_app_state;
-    use crate::domain::{AgentType, Task, WorkspaceStatus, Worktree};
+    use crate::domain::{AgentRegistry, AgentType, Task, WorkspaceStatus, Worktree};
     use crate::infrastructure::adapters::DiscoveryState;
     use crate::infrastructure::config::{ProjectConfig, ProjectDefaults};
     use crate::infrastructure::task_manifest::encode_task_manifest;
@@ -284,7 +308,13 @@ mod tests {
         let raw = encode_task_manifest(&task).expect("task manifest should encode");
         fs::write(task_dir.join("task.toml"), raw).expect("task manifest should write");
 
-        let refreshed = refreshed_app_state(Some(temp.path.as_path()), &[], &[]);
+        let refreshed = refreshed_app_state(
+            Some(temp.path.as_path()),
+            &[],
+            &[],
+            None,
+            &AgentRegistry::default(),
+        );
 
         assert_eq!(refreshed.repo_name, "1 tasks");
         assert_eq!(refreshed.discovery_state, DiscoveryState::Ready);
@@ -308,6 +338,8 @@ mod tests {
                 defaults: ProjectDefaults::default(),
             }],
             &[],
+            None,
+            &AgentRegistry::default(),
         );
 
         assert_eq!(refreshed.discovery_state, DiscoveryState::Ready);
@@ -341,6 +373,8 @@ mod tests {
                 defaults: ProjectDefaults::default(),
             }],
             std::slice::from_ref(&repo_path),
+            None,
+            &AgentRegistry::default(),
         );
 
         assert_eq!(refreshed.discovery_state, DiscoveryState::Empty);
@@ -0,0 +1,249 @@
+use super::update_prelude::*;
+
+const COMMAND_TAB_ACTION_PREFIX: &str = "command-tab:";
+
+impl GroveApp {
+    pub(super) fn command_tab_templates_for_workspace(
+        &self,
+        workspace: &Workspace,
+    ) -> &[CommandTabConfig] {
+        self.project_for_workspace(workspace)
+            .map_or(&[], |project| project.defaults.command_tabs.as_slice())
+    }
+
+    fn command_tab_for_template(&self, workspace_path: &Path, name: &str) -> Option<&WorkspaceTab> {
+        self.workspace_tabs
+            .get(workspace_path)?
+            .tabs
+            .iter()
+            .find(|tab| {
+                tab.kind == WorkspaceTabKind::Command
+                    && tab.command_template.as_deref() == Some(name)
+            })
+    }
+
+    pub(super) fn open_command_tab_palette(&mut self) {
+        let Some(workspace) = self.state.selected_workspace().cloned() else {
+            self.show_info_toast("no workspace selected");
+            return;
+        };
+        let Some(project) = self.project_for_workspace(&workspace) else {
+            self.show_info_toast("workspace has no project");
+            return;
+        };
+        if project.defaults.command_tabs.is_empty() {
+            self.show_info_toast(format!("no command tabs configured for '{}'", project.name));
+            return;
+        }
+
+        let actions = project
+            .defaults
+            .command_tabs
+            .iter()
+            .map(|template| {
+                let state =
+                    self.command_tab_for_template(workspace.path.as_path(), template.name.as_str())
+                        .map_or("not started", |tab| match tab.state {
+                            WorkspaceTabRuntimeState::Starting
+                            | WorkspaceTabRuntimeState::Running => "running",
+                            WorkspaceTabRuntimeState::Stopped => "stopped",
+                            WorkspaceTabRuntimeState::Failed => "failed",
+                        });
+                Self::palette_action(
+                    format!("{COMMAND_TAB_ACTION_PREFIX}{}", template.name),
+                    template.name.clone(),
+                    format!("{} ({state})", template.command),
+                    &[],
+                    project.name.clone(),
+                )
+            })
+            .collect();
+        self.open_shared_palette(PaletteMode::CommandTab, actions);
+    }
+
+    pub(super) fn execute_command_tab_action(&mut self, id: &str) -> bool {
+        let Some(name) = id.strip_prefix(COMMAND_TAB_ACTION_PREFIX) else {
+            return false;
+        };
+        self.open_or_focus_command_tab(name);
+        false
+    }
+
+    /// Focuses the template's tab while its command runs, otherwise (re)starts it.
+    pub(super) fn open_or_focus_command_tab(&mut self, name: &str) {
+        let Some(workspace) = self.state.selected_workspace().cloned() else {
+            self.show_info_toast("no workspace selected");
+            return;
+        };
+        let Some(template) = self
+            .command_tab_templates_for_workspace(&workspace)
+            .iter()
+            .find(|template| template.name == name)
+            .cloned()
+        else {
+            self.show_error_toast(format!("command tab '{name}' is not configured"));
+            return;
+        };
+
+        if let Some(tab) = self.command_tab_for_template(workspace.path.as_path(), name)
+            && tab.state == WorkspaceTabRuntimeState::Running
+            && let Some(session_name) = tab.session_name.clone()
+            && self.session_exists(session_name.as_str())
+        {
+            let tab_id = tab.id;
+            let _ = self.select_tab_by_id_for_selected_workspace(tab_id);
+            return;
+        }
+        if let Err(error) = self.launch_command_tab(&workspace, &template, true) {
+            self.show_error_toast(format!("command tab '{name}' failed to start: {error}"));
+        }
+    }
+
+    /// Starts `template` in its own session, reusing the template's tab when
+    /// one exists. `activate` selects the tab, otherwise the active tab stays.
+    fn launch_command_tab(
+        &mut self,
+        workspace: &Workspace,
+        template: &CommandTabConfig,
+        activate: bool,
+    ) -> Result<(), String> {
+        let session_name = command_tab_session_name(workspace, template.name.as_str());
+        let command = command_tab_shell_command(session_name.as_str(), template)?;
+
+        self.sync_workspace_tab_maps();
+        // A stopped command leaves its shell behind, so start from a fresh session.
+        if self.session_exists(session_name.as_str()) {
+            let kill_command = vec![
+                "tmux".to_string(),
+                "kill-session".to_string(),
+                "-t".to_string(),
+                session_name.clone(),
+            ];
+            let _ = self.execute_tmux_command(&kill_command);
+            self.session.shell_sessions.remove_ready(&session_name);
+        }
+        let existing_tab_id = self
+            .command_tab_for_template(workspace.path.as_path(), template.name.as_str())
+            .map(|tab| tab.id);
+        let Some(tabs) = self.workspace_tabs.get_mut(workspace.path.as_path()) else {
+            return Err("workspace tabs are unavailable".to_string());
+        };
+        let previous_active_tab_id = tabs.active_tab_id;
+        let tab_id = match existing_tab_id {
+            Some(tab_id) => {
+                if let Some(tab) = tabs.tab_by_id_mut(tab_id) {
+                    tab.session_name = Some(session_name.clone());
+                    tab.state = WorkspaceTabRuntimeState::Starting;
+                }
+                tab_id
+            }
+            None => tabs.insert_tab_adjacent(WorkspaceTab {
+                id: 0,
+                display_order: 0,
+                kind: WorkspaceTabKind::Command,
+                title: template.name.clone(),
+                session_name: Some(session_name.clone()),
+                agent_type: None,
+                command_template: Some(template.name.clone()),
+                state: WorkspaceTabRuntimeState::Starting,
+            }),
+        };
+        let _ = tabs.set_active(if activate {
+            tab_id
+        } else {
+            previous_active_tab_id
+        });
+        if self.selected_workspace_path().as_deref() == Some(workspace.path.as_path()) {
+            self.sync_preview_tab_from_active_workspace_tab();
+        }
+
+        self.session
+            .shell_sessions
+            .mark_in_flight(session_name.clone());
+        let (capture_cols, capture_rows) = self.capture_dimensions();
+        let workspace_init_command = self.workspace_init_command_for_workspace(workspace);
+        let mut request = shell_launch_request_for_workspace(
+            workspace,
+            session_name.clone(),
+            command,
+            self.theme_name.clone(),
+            workspace_init_command,
+            Some(capture_cols),
+            Some(capture_rows),
+        );
+        request.transcript_dir = self.transcript_dir_for_workspace(workspace);
+        let (_, result) = execute_shell_launch_request_for_mode(
+            &request,
+            CommandExecutionMode::Delegating(&mut |command| self.execute_tmux_command(command)),
+        );
+        if let Err(error) = result
+            && !tmux_launch_error_indicates_duplicate_session(&error)
+        {
+            self.session
+                .shell_sessions
+                .mark_failed(session_name.clone());
+            self.set_tab_state_by_id(&workspace.path, tab_id, WorkspaceTabRuntimeState::Failed);
+            self.session.last_tmux_error = Some(error.clone());
+            return Err(error);
+        }
+        self.session.shell_sessions.mark_ready(session_name.clone());
+        self.set_tab_state_by_id(&workspace.path, tab_id, WorkspaceTabRuntimeState::Running);
+        if let Some(tab) = self
+            .workspace_tabs
+            .get(workspace.path.as_path())
+            .and_then(|tabs| tabs.tab_by_id(tab_id))
+            .cloned()
+        {
+            self.write_tab_tmux_metadata(workspace.path.as_path(), &tab);
+        }
+        self.session.last_tmux_error = None;
+        self.log_event_with_fields(
+            "command_tab",
+            "started",
+            [
+                ("session".to_string(), Value::from(session_name)),
+                ("template".to_string(), Value::from(template.name.clone())),
+                ("activated".to_string(), Value::from(activate)),
+            ],
+        );
+        if activate {
+            self.poll_preview();
+        }
+        Ok(())
+    }
+
+    /// Launches `auto_start` templates for worktrees created since the last
+    /// refresh. Worktrees not discovered yet stay pending.
+    pub(super) fn start_pending_command_tabs(&mut self) {
+        if self.pending_command_tab_autostart.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending_command_tab_autostart);
+        for workspace_path in pending {
+            let Some(workspace) = self
+                .state
+                .workspaces
+                .iter()
+                .find(|workspace| refer_to_same_location(&workspace.path, &workspace_path))
+                .cloned()
+            else {
+                self.pending_command_tab_autostart.push(workspace_path);
+                continue;
+            };
+            let templates = self
+                .command_tab_templates_for_workspace(&workspace)
+                .iter()
+                .filter(|template| template.auto_start)
+                .cloned()
+                .collect::<Vec<CommandTabConfig>>();
+            for template in templates {
+                if let Err(error) = self.launch_command_tab(&workspace, &template, false) {
+                    self.show_error_toast(format!(
+                        "command tab '{}' failed to start: {error}",
+                        template.name
+                    ));
+                }
+            }
+        }
+    }
+}
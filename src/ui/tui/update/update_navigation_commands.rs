@@ -24,7 +24,7 @@ impl GroveApp {
         self.select_attention_item(0);
     }
 
-    fn select_workspace_by_path(&mut self, workspace_path: &Path) {
+    pub(super) fn select_workspace_by_path(&mut self, workspace_path: &Path) {
         if let Some(workspace_index) = self
             .state
             .workspaces
@@ -312,6 +312,39 @@ impl GroveApp {
             UiCommand::OpenPerformance => {
                 self.open_performance_dialog();
             }
+            UiCommand::HandConflictToAgent => {
+                self.hand_selected_conflict_to_agent();
+            }
+            UiCommand::ContinueConflict => {
+                self.continue_selected_conflict();
+            }
+            UiCommand::AbortConflict => {
+                self.abort_selected_conflict();
+            }
+            UiCommand::ArchiveTask => {
+                self.open_archive_task_dialog();
+            }
+            UiCommand::RestoreTask => {
+                self.open_restore_archive_palette();
+            }
+            UiCommand::OpenTranscript => {
+                self.open_transcript_palette();
+            }
+            UiCommand::OpenPaneSearch => {
+                self.open_pane_search_dialog();
+            }
+            UiCommand::ToggleBroadcastTarget => {
+                self.toggle_broadcast_target();
+            }
+            UiCommand::OpenBroadcast => {
+                self.open_broadcast_dialog();
+            }
+            UiCommand::OpenPromptQueue => {
+                self.open_prompt_queue_dialog();
+            }
+            UiCommand::OpenCommandTab => {
+                self.open_command_tab_palette();
+            }
         }
 
         false
@@ -50,6 +50,9 @@ impl GroveApp {
     pub(super) fn active_palette_label(&self) -> &'static str {
         match self.dialogs.palette_mode {
             Some(PaletteMode::WorkspaceJump) => "Jump",
+            Some(PaletteMode::RestoreArchive) => "Restore",
+            Some(PaletteMode::Transcript) => "Transcript",
+            Some(PaletteMode::CommandTab) => "Command Tab",
             _ => "Palette",
         }
     }
@@ -57,11 +60,14 @@ impl GroveApp {
     pub(super) fn active_palette_badge_label(&self) -> &'static str {
         match self.dialogs.palette_mode {
             Some(PaletteMode::WorkspaceJump) => "[Jump]",
+            Some(PaletteMode::RestoreArchive) => "[Restore]",
+            Some(PaletteMode::Transcript) => "[Transcript]",
+            Some(PaletteMode::CommandTab) => "[Command Tab]",
             _ => "[Palette]",
         }
     }
 
-    fn palette_action(
+    pub(super) fn palette_action(
         id: impl Into<String>,
         title: impl Into<String>,
         description: impl Into<String>,
@@ -188,10 +194,14 @@ impl GroveApp {
             let Some(spec) = command.palette_spec() else {
                 continue;
             };
+            let description = match self.keymap.key_label(*command) {
+                Some(keys) => Self::palette_description_with_keys(spec.description, keys.as_str()),
+                None => spec.description.to_string(),
+            };
             actions.push(Self::palette_action(
                 spec.id,
                 spec.title,
-                spec.description,
+                description,
                 spec.tags,
                 spec.category,
             ));
@@ -199,6 +209,15 @@ impl GroveApp {
         actions
     }
 
+    /// Swaps the trailing `(keys)` hint of a built-in description for remapped keys.
+    fn palette_description_with_keys(description: &str, keys: &str) -> String {
+        let base = description
+            .strip_suffix(')')
+            .and_then(|without_paren| without_paren.rsplit_once(" ("))
+            .map_or(description, |(base, _)| base);
+        format!("{base} ({keys})")
+    }
+
     pub(super) fn command_palette_max_visible_for_height(viewport_height: u16) -> usize {
         let top_offset = viewport_height / 6;
         usize::from(
@@ -214,7 +233,11 @@ impl GroveApp {
         Self::command_palette_max_visible_for_height(self.viewport_height)
     }
 
-    fn open_shared_palette(&mut self, palette_mode: PaletteMode, actions: Vec<PaletteActionItem>) {
+    pub(super) fn open_shared_palette(
+        &mut self,
+        palette_mode: PaletteMode,
+        actions: Vec<PaletteActionItem>,
+    ) {
         if !self.can_open_palette() {
             return;
         }
@@ -236,7 +259,7 @@ impl GroveApp {
         self.open_shared_palette(PaletteMode::WorkspaceJump, actions);
     }
 
-    fn palette_command_enabled(&self, command: UiCommand) -> bool {
+    pub(super) fn palette_command_enabled(&self, command: UiCommand) -> bool {
         if command.palette_spec().is_none() {
             return false;
         }
@@ -255,6 +278,7 @@ impl GroveApp {
             | UiCommand::ToggleUnsafe
             | UiCommand::CleanupSessions
             | UiCommand::OpenHelp
+            | UiCommand::OpenPaneSearch
             | UiCommand::Quit => true,
             UiCommand::OpenPreview => self.workspace_list_focused(),
             UiCommand::EnterInteractive => {
@@ -348,6 +372,36 @@ impl GroveApp {
                         .selected_workspace()
                         .is_some_and(|workspace| workspace.is_main)
             }
+            UiCommand::HandConflictToAgent => {
+                self.selected_workspace_conflict()
+                    .is_some_and(|(workspace, _)| {
+                        self.workspace_running_agent_session_for_status_poll(&workspace.path, None)
+                            .is_some()
+                    })
+            }
+            UiCommand::ContinueConflict | UiCommand::AbortConflict => {
+                !self.dialogs.conflict_action_in_flight
+                    && self.selected_workspace_conflict().is_some()
+            }
+            UiCommand::ArchiveTask => {
+                !self.dialogs.archive_in_flight
+                    && self.state.selected_task().is_some_and(|task| {
+                        !task.has_base_worktree() && !self.task_delete_requested(task)
+                    })
+            }
+            UiCommand::RestoreTask => !self.dialogs.archive_in_flight,
+            UiCommand::OpenTranscript => self.state.selected_task().is_some(),
+            UiCommand::ToggleBroadcastTarget | UiCommand::OpenPromptQueue => {
+                self.state.selected_workspace().is_some()
+            }
+            UiCommand::OpenCommandTab => self.state.selected_workspace().is_some_and(|workspace| {
+                !self
+                    .command_tab_templates_for_workspace(workspace)
+                    .is_empty()
+            }),
+            UiCommand::OpenBroadcast => {
+                !self.broadcast_targets.is_empty() || self.state.selected_workspace().is_some()
+            }
             UiCommand::RefreshWorkspaces => !self.dialogs.refresh_in_flight,
             UiCommand::FocusAttentionInbox => !self.attention_items.is_empty(),
             UiCommand::AcknowledgeAttention => self.selected_attention_item().is_some(),
@@ -395,6 +449,9 @@ impl GroveApp {
     pub(super) fn execute_visible_palette_action(&mut self, id: &str) -> bool {
         match self.dialogs.palette_mode {
             Some(PaletteMode::WorkspaceJump) => self.execute_workspace_jump_action(id),
+            Some(PaletteMode::RestoreArchive) => self.execute_restore_archive_action(id),
+            Some(PaletteMode::Transcript) => self.execute_transcript_action(id),
+            Some(PaletteMode::CommandTab) => self.execute_command_tab_action(id),
             Some(PaletteMode::Command) => self.execute_command_palette_action(id),
             None => false,
         }
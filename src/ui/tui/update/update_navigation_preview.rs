@@ -111,8 +111,10 @@ impl GroveApp {
                 .get(workspace.path.as_path())
                 .is_some_and(|tabs| {
                     tabs.tabs.iter().any(|tab| {
-                        tab.kind == WorkspaceTabKind::Shell
-                            && tab.session_name.as_deref() == Some(session_name)
+                        matches!(
+                            tab.kind,
+                            WorkspaceTabKind::Shell | WorkspaceTabKind::Command
+                        ) && tab.session_name.as_deref() == Some(session_name)
                     })
                 }),
         }
@@ -249,15 +251,19 @@ impl GroveApp {
 
         let (capture_cols, capture_rows) = self.capture_dimensions();
         let workspace_init_command = self.workspace_init_command_for_workspace(workspace);
-        let launch_request = shell_launch_request_for_workspace(
+        let mut launch_request = shell_launch_request_for_workspace(
             workspace,
             session_name.clone(),
             command,
-            self.theme_name,
+            self.theme_name.clone(),
             workspace_init_command,
             Some(capture_cols),
             Some(capture_rows),
         );
+        // Lazygit redraws a full-screen UI, which makes for an unreadable transcript.
+        if kind == SessionKind::WorkspaceShell {
+            launch_request.transcript_dir = self.transcript_dir_for_workspace(workspace);
+        }
         let async_launch = self.tmux_input.supports_background_launch();
         let mut started_fields = vec![
             ("session".to_string(), Value::from(session_name.clone())),
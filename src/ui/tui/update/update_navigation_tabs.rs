@@ -6,6 +6,9 @@ const TMUX_TAB_METADATA_TITLE_KEY: &str = "@grove_tab_title";
 const TMUX_TAB_METADATA_AGENT_KEY: &str = "@grove_tab_agent";
 const TMUX_TAB_METADATA_ID_KEY: &str = "@grove_tab_id";
 const TMUX_TAB_METADATA_ORDER_KEY: &str = "@grove_tab_order";
+const TMUX_TAB_METADATA_COMMAND_KEY: &str = "@grove_tab_command";
+/// Rows gained the order, command template and exit code columns over time.
+const TMUX_TAB_METADATA_FIELD_COUNTS: std::ops::RangeInclusive<usize> = 6..=9;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct RestoredTmuxTabMetadata {
@@ -16,6 +19,9 @@ struct RestoredTmuxTabMetadata {
     agent_type: Option<AgentType>,
     tab_id: u64,
     display_order: u64,
+    command_template: Option<String>,
+    /// Last exit code of a command tab's command, `None` while it runs.
+    command_exit: Option<i32>,
 }
 
 impl GroveApp {
@@ -71,7 +77,7 @@ impl GroveApp {
                 .ensure_home_tab();
             self.last_agent_selection
                 .entry(workspace.path.clone())
-                .or_insert(workspace.agent);
+                .or_insert_with(|| workspace.agent.clone());
         }
 
         self.sync_home_tab_titles();
@@ -95,8 +101,8 @@ impl GroveApp {
                     workspace.path.clone(),
                     previous_last_agent
                         .get(workspace.path.as_path())
-                        .copied()
-                        .unwrap_or(workspace.agent),
+                        .cloned()
+                        .unwrap_or_else(|| workspace.agent.clone()),
                 )
             })
             .collect::<std::collections::HashMap<PathBuf, AgentType>>();
@@ -135,7 +141,7 @@ impl GroveApp {
             if trimmed_nonempty(row).is_none() {
                 continue;
             }
-            let metadata = match Self::parse_tmux_tab_metadata_row(row) {
+            let metadata = match Self::parse_tmux_tab_metadata_row(row, &self.agents) {
                 Ok(parsed) => parsed,
                 Err(reason) => {
                     if let Some(recovered) = self.recover_legacy_git_tmux_tab_metadata_row(row) {
@@ -198,8 +204,9 @@ impl GroveApp {
                 kind: metadata.kind,
                 title: metadata.title,
                 session_name: Some(metadata.session_name.clone()),
-                agent_type: metadata.agent_type,
-                state: WorkspaceTabRuntimeState::Running,
+                agent_type: metadata.agent_type.clone(),
+                command_template: metadata.command_template,
+                state: WorkspaceTabRuntimeState::from_command_exit(metadata.command_exit),
             };
             let inserted = self
                 .workspace_tabs
@@ -239,7 +246,7 @@ impl GroveApp {
                         .agent_sessions
                         .mark_ready(metadata.session_name);
                 }
-                WorkspaceTabKind::Shell => {
+                WorkspaceTabKind::Shell | WorkspaceTabKind::Command => {
                     self.session
                         .shell_sessions
                         .mark_ready(metadata.session_name);
@@ -531,7 +538,8 @@ impl GroveApp {
         ordinal: u64,
     ) -> Option<String> {
         match kind {
-            WorkspaceTabKind::Home | WorkspaceTabKind::Diff => None,
+            // Command tabs are named after their template, see `command_tab_session_name`.
+            WorkspaceTabKind::Home | WorkspaceTabKind::Diff | WorkspaceTabKind::Command => None,
             WorkspaceTabKind::Git => Some(git_session_name_for_workspace(workspace)),
             WorkspaceTabKind::Agent => Some(format!(
                 "{}-agent-{ordinal}",
@@ -564,14 +572,14 @@ impl GroveApp {
                         let agent = self
                             .last_agent_selection
                             .get(workspace.path.as_path())
-                            .copied()
-                            .unwrap_or(workspace.agent);
+                            .unwrap_or(&workspace.agent);
                         format!("{} {ordinal}", agent.label())
                     }
                     WorkspaceTabKind::Shell => format!("Shell {ordinal}"),
                     WorkspaceTabKind::Git => "Git".to_string(),
                     WorkspaceTabKind::Diff => "Diff".to_string(),
                     WorkspaceTabKind::Home => "Home".to_string(),
+                    WorkspaceTabKind::Command => "Command".to_string(),
                 };
                 tabs.insert_tab_adjacent(WorkspaceTab {
                     id: 0,
@@ -580,6 +588,7 @@ impl GroveApp {
                     title,
                     session_name,
                     agent_type: None,
+                    command_template: None,
                     state: WorkspaceTabRuntimeState::Stopped,
                 })
             }
@@ -595,6 +604,7 @@ impl GroveApp {
             WorkspaceTabKind::Shell => "shell",
             WorkspaceTabKind::Git => "git",
             WorkspaceTabKind::Diff => "diff",
+            WorkspaceTabKind::Command => "command",
         }
     }
 
@@ -604,15 +614,19 @@ impl GroveApp {
             "shell" => Some(WorkspaceTabKind::Shell),
             "git" => Some(WorkspaceTabKind::Git),
             "diff" => Some(WorkspaceTabKind::Diff),
+            "command" => Some(WorkspaceTabKind::Command),
             "home" => Some(WorkspaceTabKind::Home),
             _ => None,
         }
     }
 
-    fn parse_tmux_tab_metadata_row(row: &str) -> Result<RestoredTmuxTabMetadata, String> {
+    fn parse_tmux_tab_metadata_row(
+        row: &str,
+        agents: &AgentRegistry,
+    ) -> Result<RestoredTmuxTabMetadata, String> {
         let segments = row.split('\t').collect::<Vec<&str>>();
-        if segments.len() != 6 && segments.len() != 7 {
-            return Err("expected six or seven tab metadata fields".to_string());
+        if !TMUX_TAB_METADATA_FIELD_COUNTS.contains(&segments.len()) {
+            return Err("expected six to nine tab metadata fields".to_string());
         }
 
         let session_name =
@@ -630,7 +644,8 @@ impl GroveApp {
             let agent_raw =
                 trimmed_nonempty(segments[4]).ok_or_else(|| "missing agent type".to_string())?;
             Some(
-                AgentType::from_marker(agent_raw.as_str())
+                agents
+                    .from_marker(agent_raw.as_str())
                     .ok_or_else(|| "invalid agent type".to_string())?,
             )
         } else {
@@ -643,7 +658,7 @@ impl GroveApp {
         if tab_id == 0 {
             return Err("invalid tab id".to_string());
         }
-        let display_order = if segments.len() == 7 {
+        let display_order = if segments.len() >= 7 {
             match trimmed_nonempty(segments[6]) {
                 Some(order) => order
                     .parse::<u64>()
@@ -653,6 +668,19 @@ impl GroveApp {
         } else {
             tab_id
         };
+        let command_template = if kind == WorkspaceTabKind::Command {
+            Some(
+                segments
+                    .get(7)
+                    .and_then(|value| trimmed_nonempty(value))
+                    .ok_or_else(|| "missing command template".to_string())?,
+            )
+        } else {
+            None
+        };
+        let command_exit = segments
+            .get(8)
+            .and_then(|value| parse_command_tab_exit(value));
 
         Ok(RestoredTmuxTabMetadata {
             session_name,
@@ -662,6 +690,8 @@ impl GroveApp {
             agent_type,
             tab_id,
             display_order,
+            command_template,
+            command_exit,
         })
     }
 
@@ -671,7 +701,7 @@ impl GroveApp {
     /// sessions that lost their metadata variables.
     fn has_no_grove_metadata(row: &str) -> bool {
         let segments: Vec<&str> = row.split('\t').collect();
-        (segments.len() == 6 || segments.len() == 7)
+        TMUX_TAB_METADATA_FIELD_COUNTS.contains(&segments.len())
             && trimmed_nonempty(segments[0]).is_some()
             && segments[1..].iter().all(|s| s.trim().is_empty())
     }
@@ -681,7 +711,7 @@ impl GroveApp {
         row: &str,
     ) -> Option<RestoredTmuxTabMetadata> {
         let segments = row.split('\t').collect::<Vec<&str>>();
-        if segments.len() != 6 && segments.len() != 7 {
+        if !TMUX_TAB_METADATA_FIELD_COUNTS.contains(&segments.len()) {
             return None;
         }
 
@@ -719,10 +749,12 @@ impl GroveApp {
             agent_type: None,
             tab_id,
             display_order: tab_id,
+            command_template: None,
+            command_exit: None,
         })
     }
 
-    fn write_tab_tmux_metadata(&mut self, workspace_path: &Path, tab: &WorkspaceTab) {
+    pub(super) fn write_tab_tmux_metadata(&mut self, workspace_path: &Path, tab: &WorkspaceTab) {
         let Some(session_name) = tab.session_name.as_deref() else {
             return;
         };
@@ -731,10 +763,12 @@ impl GroveApp {
         let title_value = tab.title.clone();
         let agent_value = tab
             .agent_type
+            .as_ref()
             .map(|agent| agent.marker().to_string())
             .unwrap_or_default();
         let tab_id_value = tab.id.to_string();
         let tab_order_value = tab.display_order.to_string();
+        let command_value = tab.command_template.clone().unwrap_or_default();
         let commands = vec![
             vec![
                 "tmux".to_string(),
@@ -784,6 +818,14 @@ impl GroveApp {
                 TMUX_TAB_METADATA_ORDER_KEY.to_string(),
                 tab_order_value,
             ],
+            vec![
+                "tmux".to_string(),
+                "set-option".to_string(),
+                "-t".to_string(),
+                session_name.to_string(),
+                TMUX_TAB_METADATA_COMMAND_KEY.to_string(),
+                command_value,
+            ],
         ];
 
         for command in commands {
@@ -861,7 +903,7 @@ impl GroveApp {
         self.poll_diff_for_selected_workspace();
     }
 
-    fn set_tab_state_by_id(
+    pub(super) fn set_tab_state_by_id(
         &mut self,
         workspace_path: &Path,
         tab_id: u64,
@@ -896,6 +938,7 @@ impl GroveApp {
             title: format!("Shell {ordinal}"),
             session_name: Some(session_name.clone()),
             agent_type: None,
+            command_template: None,
             state: WorkspaceTabRuntimeState::Starting,
         });
         self.sync_preview_tab_from_active_workspace_tab();
@@ -904,15 +947,16 @@ impl GroveApp {
             .mark_in_flight(session_name.clone());
         let (capture_cols, capture_rows) = self.capture_dimensions();
         let workspace_init_command = self.workspace_init_command_for_workspace(&workspace);
-        let request = shell_launch_request_for_workspace(
+        let mut request = shell_launch_request_for_workspace(
             &workspace,
             session_name.clone(),
             String::new(),
-            self.theme_name,
+            self.theme_name.clone(),
             workspace_init_command,
             Some(capture_cols),
             Some(capture_rows),
         );
+        request.transcript_dir = self.transcript_dir_for_workspace(&workspace);
         let (_, result) = execute_shell_launch_request_for_mode(
             &request,
             CommandExecutionMode::Delegating(&mut |command| self.execute_tmux_command(command)),
@@ -945,7 +989,7 @@ impl GroveApp {
     fn agent_env_for_workspace_agent(
         &self,
         workspace: &Workspace,
-        agent: AgentType,
+        agent: &AgentType,
     ) -> Result<Vec<(String, String)>, String> {
         let Some(workspace_project_path) = workspace.project_path.as_ref() else {
             return Ok(Vec::new());
@@ -957,10 +1001,7 @@ impl GroveApp {
         else {
             return Ok(Vec::new());
         };
-        let entries = match agent {
-            AgentType::Claude => &project.defaults.agent_env.claude,
-            AgentType::Codex => &project.defaults.agent_env.codex,
-        };
+        let entries = project.defaults.agent_env.entries_for(agent);
         parse_agent_env_vars_from_entries(entries).map(|vars| {
             vars.into_iter()
                 .map(|entry| (entry.key, entry.value))
@@ -977,8 +1018,19 @@ impl GroveApp {
         let Some(workspace) = self.state.selected_workspace().cloned() else {
             return Err("no workspace selected".to_string());
         };
+        // Tab launches run inline, so their pre-agent-start hooks do too.
+        if let Some(hooks) =
+            self.hook_invocation_for_workspace(HookEvent::PreAgentStart, &workspace)
+        {
+            let report = hooks.run();
+            self.log_hook_outcomes(&report.outcomes);
+            if let Some(veto) = report.veto {
+                self.session.last_tmux_error = Some(veto.clone());
+                return Err(veto);
+            }
+        }
         self.last_agent_selection
-            .insert(workspace.path.clone(), agent);
+            .insert(workspace.path.clone(), agent.clone());
         self.launch_permission_mode = options.permission_mode;
         let _ = write_workspace_permission_mode(&workspace.path, options.permission_mode);
         let _ = write_workspace_init_command(&workspace.path, options.init_command.as_deref());
@@ -1002,12 +1054,13 @@ impl GroveApp {
             kind: WorkspaceTabKind::Agent,
             title: tab_title,
             session_name: Some(session_name.clone()),
-            agent_type: Some(agent),
+            agent_type: Some(agent.clone()),
+            command_template: None,
             state: WorkspaceTabRuntimeState::Starting,
         });
         self.sync_preview_tab_from_active_workspace_tab();
 
-        let agent_env = self.agent_env_for_workspace_agent(&workspace, agent)?;
+        let agent_env = self.agent_env_for_workspace_agent(&workspace, &agent)?;
         let (capture_cols, capture_rows) = self.capture_dimensions();
         let mut launch_workspace = workspace.clone();
         launch_workspace.name = format!("{}-agent-{ordinal}", workspace.name);
@@ -1015,7 +1068,7 @@ impl GroveApp {
         let mut request = launch_request_for_workspace(
             &launch_workspace,
             options.prompt,
-            self.theme_name,
+            self.theme_name.clone(),
             options
                 .init_command
                 .or_else(|| self.workspace_init_command_for_workspace(&workspace)),
@@ -1024,6 +1077,10 @@ impl GroveApp {
             Some((capture_cols, capture_rows)),
         );
         request.session_name = Some(session_name.clone());
+        request.resume_command = options.resume_command;
+        request.sandbox = project_sandbox_for_workspace(&self.projects, &workspace);
+        request.transcript_dir = self.transcript_dir_for_workspace(&workspace);
+        request.status_hooks = self.status_hooks_enabled;
         self.session
             .agent_sessions
             .mark_in_flight(session_name.clone());
@@ -1052,7 +1109,7 @@ impl GroveApp {
         Ok(())
     }
 
-    fn session_exists(&self, session_name: &str) -> bool {
+    pub(super) fn session_exists(&self, session_name: &str) -> bool {
         let command = vec![
             "tmux".to_string(),
             "has-session".to_string(),
@@ -1068,7 +1125,10 @@ impl GroveApp {
 
     pub(super) fn selected_shell_tab_session_name(&self) -> Option<String> {
         let tab = self.selected_active_tab()?;
-        if tab.kind != WorkspaceTabKind::Shell {
+        if !matches!(
+            tab.kind,
+            WorkspaceTabKind::Shell | WorkspaceTabKind::Command
+        ) {
             return None;
         }
         tab.session_name.clone()
@@ -1079,6 +1139,9 @@ impl GroveApp {
             self.show_info_toast("home tab has no live session");
             return;
         };
+        let agent_tab = self
+            .selected_active_tab()
+            .is_some_and(|tab| tab.kind == WorkspaceTabKind::Agent);
         let command = vec![
             "tmux".to_string(),
             "kill-session".to_string(),
@@ -1098,6 +1161,13 @@ impl GroveApp {
             tab.state = WorkspaceTabRuntimeState::Stopped;
         }
         self.session.last_tmux_error = None;
+        if agent_tab
+            && let Some(hooks) = self.state.selected_workspace().and_then(|workspace| {
+                self.hook_invocation_for_workspace(HookEvent::PostAgentStop, workspace)
+            })
+        {
+            self.run_detached_hooks(hooks);
+        }
         self.poll_preview();
     }
 
@@ -1148,13 +1218,27 @@ impl GroveApp {
             "-t".to_string(),
             session_name.to_string(),
         ];
-        let _ = self.execute_tmux_command(&command);
+        let stopped = self.execute_tmux_command(&command).is_ok();
         self.session.agent_sessions.remove_ready(session_name);
         self.session.shell_sessions.remove_ready(session_name);
         self.session.lazygit_sessions.remove_ready(session_name);
+        let agent_tab = self
+            .workspace_tabs
+            .get(workspace_path)
+            .and_then(|tabs| tabs.tab_by_id(tab_id))
+            .is_some_and(|tab| tab.kind == WorkspaceTabKind::Agent);
         if let Some(tabs) = self.workspace_tabs.get_mut(workspace_path) {
             let _ = tabs.close_tab(tab_id);
         }
+        if stopped
+            && agent_tab
+            && let Some(hooks) = self.hook_invocation_for_paths(
+                HookEvent::PostAgentStop,
+                &[workspace_path.to_path_buf()],
+            )
+        {
+            self.run_detached_hooks(hooks);
+        }
         self.sync_preview_tab_from_active_workspace_tab();
         self.poll_preview();
     }
@@ -12,7 +12,7 @@ fn format_diff_summary(files_changed: usize, insertions: usize, deletions: usize
     format!("{files_changed} {file_word} changed, +{insertions} -{deletions}")
 }
 
-fn parse_diff_stat_summary(stat_output: &str) -> (usize, usize, usize) {
+pub(super) fn parse_diff_stat_summary(stat_output: &str) -> (usize, usize, usize) {
     let last_line = stat_output.lines().last().unwrap_or("");
     let mut files = 0usize;
     let mut insertions = 0usize;
@@ -38,8 +38,24 @@ fn parse_diff_stat_summary(stat_output: &str) -> (usize, usize, usize) {
     (files, insertions, deletions)
 }
 
-fn run_diff_capture(workspace_path: PathBuf) -> DiffCaptureCompletion {
+fn format_conflict_section(conflict: &WorkspaceConflict) -> String {
+    let mut section = format!(
+        "\n── conflicted, {} in progress at {} ──\n\n",
+        conflict.operation.name(),
+        conflict.worktree_path.display()
+    );
+    if conflict.files.is_empty() {
+        section.push_str("  (all conflicts resolved, continue to finish)\n");
+    }
+    for file in &conflict.files {
+        section.push_str(format!("  {file}\n").as_str());
+    }
+    section
+}
+
+fn run_diff_capture(workspace_path: PathBuf, conflict_path: PathBuf) -> DiffCaptureCompletion {
     let started_at = std::time::Instant::now();
+    let conflict = detect_workspace_conflict(&conflict_path);
 
     let (stat_result, diff_result, staged_result) = std::thread::scope(|s| {
         let wp = &workspace_path;
@@ -87,6 +103,10 @@ fn run_diff_capture(workspace_path: PathBuf) -> DiffCaptureCompletion {
         output.push_str(&summary);
         output.push('\n');
 
+        if let Some(conflict) = conflict.as_ref() {
+            output.push_str(&format_conflict_section(conflict));
+        }
+
         if !staged_str.is_empty() {
             output.push_str("\n── staged ──\n\n");
             output.push_str(&staged_str);
@@ -97,7 +117,7 @@ fn run_diff_capture(workspace_path: PathBuf) -> DiffCaptureCompletion {
             }
             output.push_str(&diff_str);
         }
-        if staged_str.is_empty() && diff_str.is_empty() {
+        if staged_str.is_empty() && diff_str.is_empty() && conflict.is_none() {
             output.push_str("\n(no changes)");
         }
         Ok(output)
@@ -123,8 +143,9 @@ impl GroveApp {
         };
         self.polling.diff_capture_in_flight = true;
         let workspace_path = workspace.path.clone();
+        let conflict_path = self.conflict_probe_path(workspace);
         self.queue_cmd(Cmd::task(move || {
-            Msg::DiffCaptureCompleted(run_diff_capture(workspace_path))
+            Msg::DiffCaptureCompleted(run_diff_capture(workspace_path, conflict_path))
         }));
     }
 
@@ -178,6 +199,15 @@ impl GroveApp {
 
     pub(super) fn handle_diff_stat_completed(&mut self, completion: DiffStatCompletion) {
         self.polling.diff_stat_in_flight = false;
+        match completion.conflict {
+            Some(conflict) => {
+                self.workspace_conflicts
+                    .insert(completion.workspace_path.clone(), conflict);
+            }
+            None => {
+                self.workspace_conflicts.remove(&completion.workspace_path);
+            }
+        }
         if completion.insertions > 0 || completion.deletions > 0 {
             self.workspace_diff_stats.insert(
                 completion.workspace_path,
@@ -230,6 +260,7 @@ impl GroveApp {
         self.polling.last_diff_stat_poll_at = Some(now);
         self.polling.diff_stat_in_flight = true;
         let workspace_path = workspace.path.clone();
+        let conflict_path = self.conflict_probe_path(workspace);
         self.queue_cmd(Cmd::task(move || {
             let result = std::process::Command::new("git")
                 .args(["diff", "HEAD", "--shortstat"])
@@ -247,6 +278,7 @@ impl GroveApp {
                 workspace_path,
                 insertions,
                 deletions,
+                conflict: detect_workspace_conflict(&conflict_path),
             })
         }));
     }
@@ -314,6 +346,19 @@ mod tests {
         assert_eq!((files, ins, del), (1, 5, 0));
     }
 
+    #[test]
+    fn format_conflict_section_lists_conflicted_files() {
+        let conflict = WorkspaceConflict {
+            operation: IntegrationOperation::Rebase,
+            worktree_path: PathBuf::from("/repos/grove-feature"),
+            files: vec!["README.md".to_string(), "src/lib.rs".to_string()],
+        };
+        assert_eq!(
+            format_conflict_section(&conflict),
+            "\n── conflicted, rebase in progress at /repos/grove-feature ──\n\n  README.md\n  src/lib.rs\n"
+        );
+    }
+
     #[test]
     fn parse_diff_stat_summary_deletions_only() {
         let stat = " 2 files changed, 8 deletions(-)";
@@ -158,6 +158,7 @@ impl GroveApp {
                 {
                     self.restore_manual_preview_viewport(anchor);
                 }
+                self.reveal_pane_search_hit(session_name);
                 let apply_capture_ms = Self::duration_millis(
                     Instant::now().saturating_duration_since(apply_started_at),
                 );
@@ -214,7 +215,7 @@ impl GroveApp {
                 if let Some(index) = selected_workspace_index {
                     let supported_agent = self.state.workspaces[index].supported_agent;
                     let workspace_path = self.state.workspaces[index].path.clone();
-                    let workspace_agent = self.state.workspaces[index].agent;
+                    let workspace_agent = self.state.workspaces[index].agent.clone();
                     let workspace_is_main = self.state.workspaces[index].is_main;
                     let previous_status = self.state.workspaces[index].status;
                     let previous_orphaned = self.state.workspaces[index].is_orphaned;
@@ -240,12 +241,13 @@ impl GroveApp {
                         SessionActivity::Idle
                     };
                     let resolved_status = detect_status_with_session_override(
+                        &self.status_rules,
                         update.cleaned_output.as_str(),
                         session_activity,
                         workspace_is_main,
                         true,
                         supported_agent,
-                        workspace_agent,
+                        &workspace_agent,
                         &workspace_path,
                         session_name,
                     );
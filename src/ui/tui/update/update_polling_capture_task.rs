@@ -2,7 +2,10 @@ use super::update_prelude::*;
 
 impl GroveApp {
     pub(super) fn live_preview_scrollback_lines(&self) -> usize {
-        if self.session.interactive.is_some() || self.preview_manual_scrollback_active() {
+        if self.session.interactive.is_some()
+            || self.pane_search_reveal.is_some()
+            || self.preview_manual_scrollback_active()
+        {
             return LIVE_PREVIEW_FULL_SCROLLBACK_LINES;
         }
 
@@ -49,7 +49,7 @@ impl GroveApp {
                 let workspace_path = self.state.workspaces[workspace_index].path.clone();
                 let previous_status = self.state.workspaces[workspace_index].status;
                 let previous_orphaned = self.state.workspaces[workspace_index].is_orphaned;
-                let workspace_agent = self.state.workspaces[workspace_index].agent;
+                let workspace_agent = self.state.workspaces[workspace_index].agent.clone();
                 let workspace_is_main = self.state.workspaces[workspace_index].is_main;
                 let status_detect_started_at = Instant::now();
                 let session_activity = if changed {
@@ -58,12 +58,13 @@ impl GroveApp {
                     SessionActivity::Idle
                 };
                 let next_status = detect_status_with_session_override(
+                    &self.status_rules,
                     cleaned_output.as_str(),
                     session_activity,
                     workspace_is_main,
                     true,
                     supported_agent,
-                    workspace_agent,
+                    &workspace_agent,
                     &workspace_path,
                     capture.session_name.as_str(),
                 );
@@ -0,0 +1,94 @@
+use super::update_prelude::*;
+
+const COMMAND_TAB_POLL_INTERVAL_MS: u64 = 2_000;
+/// Column of `@grove_tab_exit` in the tab metadata rows.
+const COMMAND_TAB_EXIT_COLUMN: usize = 8;
+
+impl GroveApp {
+    /// Follows command tab exit codes recorded by their run loop, warning
+    /// when a command fails.
+    pub(super) fn maybe_poll_command_tabs(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.polling.last_command_tab_poll_at
+            && now.saturating_duration_since(last)
+                < Duration::from_millis(COMMAND_TAB_POLL_INTERVAL_MS)
+        {
+            return;
+        }
+        let command_tabs = self
+            .workspace_tabs
+            .iter()
+            .flat_map(|(workspace_path, tabs)| {
+                tabs.tabs
+                    .iter()
+                    .filter(|tab| tab.kind == WorkspaceTabKind::Command)
+                    .filter_map(|tab| {
+                        Some((
+                            workspace_path.clone(),
+                            tab.id,
+                            tab.title.clone(),
+                            tab.session_name.clone()?,
+                            tab.state,
+                        ))
+                    })
+            })
+            .collect::<Vec<_>>();
+        if command_tabs.is_empty() {
+            return;
+        }
+        self.polling.last_command_tab_poll_at = Some(now);
+
+        let output = match self.tmux_input.list_sessions_with_tab_metadata() {
+            Ok(output) => output,
+            Err(error) => {
+                self.log_tmux_error(error.to_string());
+                return;
+            }
+        };
+        let exits = output
+            .lines()
+            .filter_map(|row| {
+                let segments = row.split('\t').collect::<Vec<&str>>();
+                let session_name = trimmed_nonempty(segments.first()?)?;
+                let exit = segments
+                    .get(COMMAND_TAB_EXIT_COLUMN)
+                    .and_then(|value| parse_command_tab_exit(value));
+                Some((session_name, exit))
+            })
+            .collect::<HashMap<String, Option<i32>>>();
+
+        for (workspace_path, tab_id, title, session_name, previous_state) in command_tabs {
+            if previous_state == WorkspaceTabRuntimeState::Starting {
+                continue;
+            }
+            let (state, exit) = match exits.get(&session_name) {
+                Some(exit) => (WorkspaceTabRuntimeState::from_command_exit(*exit), *exit),
+                None => {
+                    self.session.shell_sessions.remove_ready(&session_name);
+                    (WorkspaceTabRuntimeState::Stopped, None)
+                }
+            };
+            if state == previous_state {
+                continue;
+            }
+            self.set_tab_state_by_id(workspace_path.as_path(), tab_id, state);
+            if state == WorkspaceTabRuntimeState::Failed
+                && let Some(code) = exit
+            {
+                self.show_warning_toast(format!("'{title}' exited with status {code}"));
+            }
+            self.log_event_with_fields(
+                "command_tab",
+                "state_changed",
+                [
+                    ("session".to_string(), Value::from(session_name)),
+                    (
+                        "state".to_string(),
+                        Value::from(format!("{state:?}").to_ascii_lowercase()),
+                    ),
+                    ("exit_code".to_string(), Value::from(exit)),
+                ],
+            );
+        }
+    }
+}
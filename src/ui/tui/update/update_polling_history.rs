@@ -0,0 +1,129 @@
+use super::update_prelude::*;
+
+/// Items that appeared in `next_items` and items that left it, compared by
+/// worktree and fingerprint.
+pub(super) fn attention_changes<'a>(
+    previous_items: &'a [AttentionItem],
+    next_items: &'a [AttentionItem],
+) -> (Vec<&'a AttentionItem>, Vec<&'a AttentionItem>) {
+    let same_item = |left: &AttentionItem, right: &AttentionItem| {
+        left.workspace_path == right.workspace_path && left.fingerprint == right.fingerprint
+    };
+    let raised = next_items
+        .iter()
+        .filter(|item| {
+            !previous_items
+                .iter()
+                .any(|previous| same_item(previous, item))
+        })
+        .collect();
+    let cleared = previous_items
+        .iter()
+        .filter(|item| !next_items.iter().any(|next| same_item(next, item)))
+        .collect();
+    (raised, cleared)
+}
+
+// History recording is best-effort: a missing or failing state store never
+// interrupts the UI, it only leaves gaps in `grove history`.
+impl GroveApp {
+    fn workspace_task_slug(&self, workspace_path: &Path) -> Option<String> {
+        self.state
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.path == workspace_path)
+            .and_then(|workspace| workspace.task_slug.clone())
+            .or_else(|| {
+                self.state
+                    .tasks
+                    .iter()
+                    .find(|task| task.root_path == workspace_path)
+                    .map(|task| task.slug.clone())
+            })
+    }
+
+    pub(super) fn record_task_history_event(
+        &self,
+        task_slug: &str,
+        event: &str,
+        worktree_path: Option<&Path>,
+    ) {
+        if let Some(state_store) = self.telemetry.state_store.as_ref() {
+            let _ =
+                state_store.record_task_event(now_millis(), task_slug, event, worktree_path, None);
+        }
+    }
+
+    pub(super) fn record_workspace_history_event(&self, workspace_path: &Path, event: &str) {
+        if let Some(task_slug) = self.workspace_task_slug(workspace_path) {
+            self.record_task_history_event(task_slug.as_str(), event, Some(workspace_path));
+        }
+    }
+
+    pub(super) fn record_status_history(
+        &self,
+        workspace_path: &Path,
+        previous_status: WorkspaceStatus,
+        next_status: WorkspaceStatus,
+    ) {
+        if previous_status == next_status {
+            return;
+        }
+        let Some(state_store) = self.telemetry.state_store.as_ref() else {
+            return;
+        };
+        let task_slug = self.workspace_task_slug(workspace_path);
+        let _ = state_store.record_status_transition(
+            now_millis(),
+            workspace_path,
+            task_slug.as_deref(),
+            previous_status,
+            next_status,
+        );
+    }
+
+    pub(super) fn record_attention_history(
+        &self,
+        raised: &[&AttentionItem],
+        cleared: &[&AttentionItem],
+    ) {
+        let Some(state_store) = self.telemetry.state_store.as_ref() else {
+            return;
+        };
+        for item in raised {
+            let _ = state_store.record_attention_raised(&AttentionRecord {
+                workspace_path: item.workspace_path.clone(),
+                fingerprint: item.fingerprint.clone(),
+                task_slug: Some(item.task_slug.clone()),
+                reason: item.reason.summary().to_string(),
+                summary: item.summary.clone(),
+                first_seen_ms: item.first_seen_at_ms,
+                last_seen_ms: item.last_seen_at_ms,
+                acknowledged_ms: None,
+                cleared_ms: None,
+            });
+        }
+        let now_ms = now_millis();
+        for item in cleared {
+            let _ = state_store.record_attention_cleared(
+                now_ms,
+                item.workspace_path.as_path(),
+                item.fingerprint.as_str(),
+            );
+        }
+    }
+
+    pub(super) fn record_attention_acknowledged_history(
+        &self,
+        workspace_path: &Path,
+        fingerprint: &str,
+    ) {
+        if let Some(state_store) = self.telemetry.state_store.as_ref() {
+            let _ = state_store.record_attention_acknowledged(
+                now_millis(),
+                workspace_path,
+                fingerprint,
+            );
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use super::update_polling_capture_diff::parse_diff_stat_summary;
+use super::update_prelude::*;
+use crate::application::session_cleanup::list_tmux_sessions;
+
+const HOME_DASHBOARD_POLL_INTERVAL_MS: u64 = 5_000;
+const HOME_DASHBOARD_EVENT_WINDOW_MS: u64 = 7 * 24 * 60 * 60 * 1_000;
+const HOME_DASHBOARD_RECENT_EVENT_LIMIT: usize = 5;
+pub(super) const HOME_DASHBOARD_HOTKEY_COUNT: usize = 9;
+
+fn parse_left_right_count(output: &str) -> Option<BranchDivergence> {
+    let mut counts = output.split_whitespace().map(str::parse::<usize>);
+    let behind = counts.next()?.ok()?;
+    let ahead = counts.next()?.ok()?;
+    Some(BranchDivergence { ahead, behind })
+}
+
+fn branch_divergence(worktree_path: &Path, base_branch: &str) -> Option<BranchDivergence> {
+    let range = format!("{base_branch}...HEAD");
+    let output = std::process::Command::new("git")
+        .args(["rev-list", "--left-right", "--count", range.as_str()])
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_left_right_count(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn run_home_dashboard_poll(
+    task_slug: String,
+    worktrees: Vec<(PathBuf, Option<String>)>,
+) -> HomeDashboardCompletion {
+    let worktrees = worktrees
+        .into_iter()
+        .map(|(worktree_path, base_branch)| {
+            let divergence = base_branch
+                .as_deref()
+                .and_then(|base_branch| branch_divergence(&worktree_path, base_branch));
+            let (files_changed, insertions, deletions) = std::process::Command::new("git")
+                .args(["diff", "HEAD", "--shortstat"])
+                .current_dir(&worktree_path)
+                .output()
+                .map(|output| parse_diff_stat_summary(&String::from_utf8_lossy(&output.stdout)))
+                .unwrap_or((0, 0, 0));
+            HomeDashboardWorktreeStats {
+                worktree_path,
+                divergence,
+                files_changed,
+                insertions,
+                deletions,
+            }
+        })
+        .collect();
+    let session_started_at = list_tmux_sessions()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|session| {
+            let created = i64::try_from(session.created_unix_secs?).ok()?;
+            Some((session.name, created))
+        })
+        .collect();
+
+    HomeDashboardCompletion {
+        task_slug,
+        worktrees,
+        session_started_at,
+    }
+}
+
+impl GroveApp {
+    /// The dashboard replaces the Home tab whenever no parent agent output is shown.
+    pub(super) fn home_dashboard_visible(&self) -> bool {
+        self.preview_tab == PreviewTab::Home
+            && self.state.selected_task().is_some()
+            && self.selected_task_preview_session_if_ready().is_none()
+    }
+
+    pub(super) fn home_dashboard_workspaces(&self) -> Vec<&Workspace> {
+        let Some(task) = self.state.selected_task() else {
+            return Vec::new();
+        };
+        self.state
+            .workspaces
+            .iter()
+            .filter(|workspace| {
+                task.worktrees
+                    .iter()
+                    .any(|worktree| worktree.path == workspace.path)
+            })
+            .collect()
+    }
+
+    /// Jump targets in dashboard order; the first nine get number hotkeys.
+    pub(super) fn home_dashboard_targets(&self) -> Vec<HomeDashboardTarget> {
+        let mut targets = Vec::new();
+        for workspace in self.home_dashboard_workspaces() {
+            targets.push(HomeDashboardTarget::Worktree(workspace.path.clone()));
+            if let Some(tabs) = self.workspace_tabs.get(workspace.path.as_path()) {
+                targets.extend(
+                    tabs.tabs
+                        .iter()
+                        .filter(|tab| tab.kind != WorkspaceTabKind::Home)
+                        .map(|tab| HomeDashboardTarget::Tab {
+                            workspace_path: workspace.path.clone(),
+                            tab_id: tab.id,
+                        }),
+                );
+            }
+            targets.extend(
+                workspace
+                    .pull_requests
+                    .iter()
+                    .map(|pull_request| HomeDashboardTarget::PullRequest(pull_request.url.clone())),
+            );
+        }
+        targets
+    }
+
+    /// Seconds since the oldest running agent tab of `workspace` was created.
+    pub(super) fn home_dashboard_agent_uptime_secs(&self, workspace: &Workspace) -> Option<i64> {
+        let tabs = self.workspace_tabs.get(workspace.path.as_path())?;
+        let started_at = tabs
+            .tabs
+            .iter()
+            .filter(|tab| {
+                tab.kind == WorkspaceTabKind::Agent
+                    && tab.state == WorkspaceTabRuntimeState::Running
+            })
+            .filter_map(|tab| tab.session_name.as_deref())
+            .filter_map(|session_name| {
+                self.home_dashboard
+                    .session_started_at
+                    .get(session_name)
+                    .copied()
+            })
+            .min()?;
+        let now_secs = i64::try_from(now_millis() / 1_000).ok()?;
+        Some(now_secs.saturating_sub(started_at).max(0))
+    }
+
+    pub(super) fn maybe_poll_home_dashboard(&mut self) {
+        if self.preview_tab != PreviewTab::Home || self.polling.home_dashboard_poll_in_flight {
+            return;
+        }
+        let Some(task_slug) = self.state.selected_task().map(|task| task.slug.clone()) else {
+            return;
+        };
+        let now = Instant::now();
+        let task_changed = self.home_dashboard.task_slug.as_deref() != Some(task_slug.as_str());
+        if !task_changed
+            && let Some(last) = self.polling.last_home_dashboard_poll_at
+            && now.saturating_duration_since(last)
+                < Duration::from_millis(HOME_DASHBOARD_POLL_INTERVAL_MS)
+        {
+            return;
+        }
+        let worktrees = self
+            .home_dashboard_workspaces()
+            .into_iter()
+            .map(|workspace| {
+                let base_branch = workspace.base_branch.clone().filter(|_| !workspace.is_main);
+                (workspace.path.clone(), base_branch)
+            })
+            .collect::<Vec<_>>();
+        self.polling.last_home_dashboard_poll_at = Some(now);
+        self.polling.home_dashboard_poll_in_flight = true;
+        self.queue_cmd(Cmd::task(move || {
+            Msg::HomeDashboardPolled(run_home_dashboard_poll(task_slug, worktrees))
+        }));
+    }
+
+    pub(super) fn handle_home_dashboard_polled(&mut self, completion: HomeDashboardCompletion) {
+        self.polling.home_dashboard_poll_in_flight = false;
+        let since_ms = now_millis().saturating_sub(HOME_DASHBOARD_EVENT_WINDOW_MS);
+        let mut recent_events = self
+            .telemetry
+            .state_store
+            .as_ref()
+            .and_then(|state_store| {
+                state_store
+                    .task_events(Some(completion.task_slug.as_str()), since_ms)
+                    .ok()
+            })
+            .unwrap_or_default();
+        let keep_from = recent_events
+            .len()
+            .saturating_sub(HOME_DASHBOARD_RECENT_EVENT_LIMIT);
+        recent_events.drain(..keep_from);
+        recent_events.reverse();
+
+        self.home_dashboard = HomeDashboardState {
+            task_slug: Some(completion.task_slug),
+            worktree_stats: completion
+                .worktrees
+                .into_iter()
+                .map(|stats| (stats.worktree_path.clone(), stats))
+                .collect(),
+            session_started_at: completion.session_started_at.into_iter().collect(),
+            recent_events,
+        };
+    }
+
+    pub(super) fn home_dashboard_hotkey(&self, key_event: &KeyEvent) -> Option<usize> {
+        if !key_event.modifiers.is_empty() || !self.home_dashboard_visible() {
+            return None;
+        }
+        let KeyCode::Char(character) = key_event.code else {
+            return None;
+        };
+        let digit = usize::try_from(character.to_digit(10)?).ok()?;
+        if !(1..=HOME_DASHBOARD_HOTKEY_COUNT).contains(&digit) {
+            return None;
+        }
+        Some(digit - 1)
+    }
+
+    pub(super) fn jump_to_home_dashboard_target(&mut self, index: usize) {
+        let Some(target) = self.home_dashboard_targets().into_iter().nth(index) else {
+            return;
+        };
+        match target {
+            HomeDashboardTarget::Worktree(workspace_path) => {
+                self.select_workspace_by_path(workspace_path.as_path());
+                self.focus_selected_workspace_attention_tab();
+                self.refresh_preview_summary();
+                self.poll_preview();
+            }
+            HomeDashboardTarget::Tab {
+                workspace_path,
+                tab_id,
+            } => {
+                self.select_workspace_by_path(workspace_path.as_path());
+                self.select_tab_by_id_for_selected_workspace(tab_id);
+            }
+            HomeDashboardTarget::PullRequest(url) => {
+                if let Err(error) = self.open_url_in_browser(url.as_str()) {
+                    self.show_error_toast(error);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_left_right_count_reads_behind_then_ahead() {
+        assert_eq!(
+            parse_left_right_count("3\t5\n"),
+            Some(BranchDivergence {
+                ahead: 5,
+                behind: 3
+            })
+        );
+        assert_eq!(parse_left_right_count(""), None);
+        assert_eq!(parse_left_right_count("x\t1"), None);
+    }
+}
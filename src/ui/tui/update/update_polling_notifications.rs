@@ -0,0 +1,92 @@
+use super::update_prelude::*;
+
+impl GroveApp {
+    pub(super) fn notify_attention_raised(&mut self, raised: &[&AttentionItem]) {
+        if !self.attention_notifier.is_enabled() {
+            return;
+        }
+        let now_ms = now_millis();
+        for item in raised {
+            let workspace = self
+                .state
+                .workspaces
+                .iter()
+                .find(|workspace| workspace.path == item.workspace_path)
+                .map_or_else(
+                    || item.task_slug.clone(),
+                    |workspace| workspace.name.clone(),
+                );
+            let notification = Notification {
+                reason: item.reason.notification_reason(),
+                task: item.task_slug.clone(),
+                workspace,
+                workspace_path: item.workspace_path.clone(),
+                summary: item.summary.clone(),
+            };
+            let sinks = self
+                .attention_notifier
+                .route(&notification, now_ms, local_minute_of_day);
+            if sinks.is_empty() {
+                continue;
+            }
+            self.dispatch_notification(notification, sinks);
+        }
+    }
+
+    fn dispatch_notification(
+        &mut self,
+        notification: Notification,
+        sinks: Vec<NotificationSinkConfig>,
+    ) {
+        let run = move || {
+            let failures = sinks
+                .iter()
+                .filter_map(|sink| {
+                    deliver_notification(sink, &notification)
+                        .err()
+                        .map(|error| format!("{}: {error}", sink.kind.config_key()))
+                })
+                .collect();
+            NotificationDeliveryCompletion {
+                workspace_path: notification.workspace_path,
+                reason: notification.reason.config_key().to_string(),
+                sinks: sinks
+                    .iter()
+                    .map(|sink| sink.kind.config_key().to_string())
+                    .collect(),
+                failures,
+            }
+        };
+
+        if !self.tmux_input.supports_background_launch() {
+            let completion = run();
+            self.apply_notification_delivery_completion(completion);
+            return;
+        }
+        self.queue_cmd(Cmd::task(move || Msg::NotificationsDelivered(run())));
+    }
+
+    pub(super) fn apply_notification_delivery_completion(
+        &mut self,
+        completion: NotificationDeliveryCompletion,
+    ) {
+        let workspace_path = completion.workspace_path.display().to_string();
+        if completion.failures.is_empty() {
+            self.telemetry.event_log.log(
+                LogEvent::new("notifications", "notification_sent")
+                    .with_data("workspace_path", Value::from(workspace_path))
+                    .with_data("reason", Value::from(completion.reason))
+                    .with_data("sinks", Value::from(completion.sinks)),
+            );
+            return;
+        }
+        for failure in completion.failures {
+            self.telemetry.event_log.log(
+                LogEvent::new("notifications", "notification_failed")
+                    .with_data("workspace_path", Value::from(workspace_path.clone()))
+                    .with_data("reason", Value::from(completion.reason.clone()))
+                    .with_data("error", Value::from(failure)),
+            );
+        }
+    }
+}
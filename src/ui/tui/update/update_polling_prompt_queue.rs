@@ -0,0 +1,172 @@
+use super::update_prelude::*;
+
+impl GroveApp {
+    /// Sends the next queued prompt once the agent is back at its input, or
+    /// pauses the queue on a permission wall or error.
+    pub(super) fn advance_prompt_queue(&mut self, workspace_path: &Path, status: WorkspaceStatus) {
+        if self.prompt_queue_turn_started(workspace_path, status) {
+            self.polling.prompt_queue_sent.remove(workspace_path);
+        }
+        let trigger = prompt_queue_trigger(
+            status,
+            self.polling
+                .workspace_waiting_prompts
+                .get(workspace_path)
+                .map(String::as_str),
+            self.polling
+                .workspace_waiting_attention
+                .get(workspace_path)
+                .copied(),
+        );
+        let sent_at_this_prompt = self.polling.prompt_queue_sent.contains_key(workspace_path);
+        if trigger == PromptQueueTrigger::Hold
+            || (trigger == PromptQueueTrigger::Send && sent_at_this_prompt)
+        {
+            return;
+        }
+
+        let mut queue = match load_prompt_queue(workspace_path) {
+            Ok(queue) => queue,
+            Err(error) => {
+                self.log_prompt_queue_event(workspace_path, "load_failed", [("error", error)]);
+                return;
+            }
+        };
+        if queue.prompts.is_empty() || queue.paused.is_some() {
+            return;
+        }
+
+        match trigger {
+            PromptQueueTrigger::Pause(reason) => {
+                self.pause_prompt_queue(workspace_path, &mut queue, reason);
+            }
+            PromptQueueTrigger::Send => self.send_next_queued_prompt(workspace_path, queue),
+            PromptQueueTrigger::Hold => {}
+        }
+    }
+
+    /// Whether the agent took a turn since its last queued prompt. A turn can
+    /// finish between polls, so a new hook event or later output counts too.
+    fn prompt_queue_turn_started(
+        &mut self,
+        workspace_path: &Path,
+        status: WorkspaceStatus,
+    ) -> bool {
+        if status != WorkspaceStatus::Waiting {
+            return true;
+        }
+        let output_changed = self
+            .polling
+            .workspace_output_changing
+            .get(workspace_path)
+            .copied()
+            .unwrap_or(false);
+        let Some(sent) = self.polling.prompt_queue_sent.get_mut(workspace_path) else {
+            return false;
+        };
+        sent.polls_since = sent.polls_since.saturating_add(1);
+        // The first poll after sending sees the prompt being typed in.
+        (output_changed && sent.polls_since > 1)
+            || read_status_event(workspace_path) != sent.hook_event
+    }
+
+    fn send_next_queued_prompt(&mut self, workspace_path: &Path, mut queue: PromptQueue) {
+        let Some(session_name) =
+            self.workspace_running_agent_session_for_status_poll(workspace_path, None)
+        else {
+            return;
+        };
+        let prompt = queue.prompts.remove(0);
+        // Saved before sending so a crash never resends the same prompt.
+        if let Err(error) = save_prompt_queue(workspace_path, &queue) {
+            self.log_prompt_queue_event(workspace_path, "save_failed", [("error", error)]);
+            return;
+        }
+
+        let result = submit_prompt_commands(session_name.as_str(), prompt.as_str())
+            .iter()
+            .try_for_each(|command| self.execute_tmux_command(command));
+        if let Err(error) = result {
+            queue.prompts.insert(0, prompt);
+            self.pause_prompt_queue(workspace_path, &mut queue, format!("send failed: {error}"));
+            return;
+        }
+
+        self.polling.prompt_queue_sent.insert(
+            workspace_path.to_path_buf(),
+            PromptQueueSend {
+                hook_event: read_status_event(workspace_path),
+                polls_since: 0,
+            },
+        );
+        let remaining = queue.prompts.len();
+        self.log_event_with_fields(
+            "prompt_queue",
+            "sent",
+            [
+                (
+                    "workspace_path".to_string(),
+                    Value::from(workspace_path.display().to_string()),
+                ),
+                ("session".to_string(), Value::from(session_name)),
+                ("prompt".to_string(), Value::from(prompt)),
+                (
+                    "remaining".to_string(),
+                    Value::from(usize_to_u64(remaining)),
+                ),
+            ],
+        );
+        self.show_info_toast(format!(
+            "sent queued prompt to {}, {remaining} left",
+            self.workspace_display_name_for_path(workspace_path)
+        ));
+        self.refresh_prompt_queue_dialog(workspace_path, queue);
+    }
+
+    fn pause_prompt_queue(
+        &mut self,
+        workspace_path: &Path,
+        queue: &mut PromptQueue,
+        reason: String,
+    ) {
+        queue.paused = Some(reason.clone());
+        if let Err(error) = save_prompt_queue(workspace_path, queue) {
+            self.log_prompt_queue_event(workspace_path, "save_failed", [("error", error)]);
+        }
+        self.log_prompt_queue_event(workspace_path, "paused", [("reason", reason.clone())]);
+        self.show_error_toast(format!(
+            "prompt queue for {} paused: {reason}",
+            self.workspace_display_name_for_path(workspace_path)
+        ));
+        self.refresh_prompt_queue_dialog(workspace_path, queue.clone());
+    }
+
+    fn log_prompt_queue_event<const N: usize>(
+        &self,
+        workspace_path: &Path,
+        action: &str,
+        fields: [(&str, String); N],
+    ) {
+        let mut all_fields = vec![(
+            "workspace_path".to_string(),
+            Value::from(workspace_path.display().to_string()),
+        )];
+        all_fields.extend(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), Value::from(value))),
+        );
+        self.log_event_with_fields("prompt_queue", action, all_fields);
+    }
+
+    fn workspace_display_name_for_path(&self, workspace_path: &Path) -> String {
+        self.state
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.path == workspace_path)
+            .map_or_else(
+                || workspace_path.display().to_string(),
+                |workspace| workspace.name.clone(),
+            )
+    }
+}
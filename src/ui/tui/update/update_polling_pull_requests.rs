@@ -0,0 +1,146 @@
+use super::update_prelude::*;
+
+impl GroveApp {
+    pub(super) fn maybe_sync_pull_requests(&mut self) {
+        if self.polling.pull_request_sync_in_flight {
+            return;
+        }
+        let Some(forge) = self.pull_request_forge.clone() else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(last) = self.polling.last_pull_request_sync_at
+            && now.saturating_duration_since(last) < self.pull_request_sync_interval
+        {
+            return;
+        }
+        self.polling.last_pull_request_sync_at = Some(now);
+        if !self
+            .state
+            .tasks
+            .iter()
+            .flat_map(|task| task.worktrees.iter())
+            .any(|worktree| !worktree.is_main_checkout())
+        {
+            return;
+        }
+
+        let tasks = self.state.tasks.clone();
+        let agents = Arc::clone(&self.agents);
+        let run = move || {
+            let mut outcome = sync_task_pull_requests(&tasks, forge.as_ref());
+            outcome
+                .errors
+                .extend(persist_pull_request_updates(&outcome.updates, &agents));
+            outcome
+        };
+
+        if !self.tmux_input.supports_background_launch() {
+            let outcome = run();
+            self.handle_pull_requests_synced(outcome);
+            return;
+        }
+        self.polling.pull_request_sync_in_flight = true;
+        self.queue_cmd(Cmd::task(move || Msg::PullRequestsSynced(run())));
+    }
+
+    pub(super) fn handle_pull_requests_synced(&mut self, outcome: PullRequestSyncOutcome) {
+        self.polling.pull_request_sync_in_flight = false;
+        apply_pull_request_updates(&mut self.state.tasks, &outcome.updates);
+        for update in &outcome.updates {
+            if let Some(workspace) = self
+                .state
+                .workspaces
+                .iter_mut()
+                .find(|workspace| workspace.path == update.worktree_path)
+            {
+                workspace.pull_requests = update.pull_requests.clone();
+            }
+        }
+
+        let mut alerts_changed = self.clear_resolved_pull_request_alerts();
+        for event in &outcome.events {
+            let reason = match event.kind {
+                PullRequestEventKind::Merged => AttentionReason::PullRequestMerged,
+                PullRequestEventKind::ChecksFailed => AttentionReason::ChecksFailed,
+            };
+            let fingerprint_prefix = match event.kind {
+                PullRequestEventKind::Merged => "pull-request-merged",
+                PullRequestEventKind::ChecksFailed => "checks-failed",
+            };
+            let task_slug = self
+                .state
+                .workspaces
+                .iter()
+                .find(|workspace| workspace.path == event.worktree_path)
+                .and_then(|workspace| workspace.task_slug.clone())
+                .unwrap_or_default();
+            let now_ms = now_millis();
+            self.pull_request_alerts.insert(
+                event.worktree_path.clone(),
+                AttentionItem {
+                    fingerprint: format!("{fingerprint_prefix}:{}", event.pull_request.url),
+                    reason,
+                    summary: format!("{}, #{}", reason.summary(), event.pull_request.number),
+                    workspace_path: event.worktree_path.clone(),
+                    task_slug,
+                    first_seen_at_ms: now_ms,
+                    last_seen_at_ms: now_ms,
+                },
+            );
+            alerts_changed = true;
+        }
+
+        self.telemetry.event_log.log(
+            LogEvent::new("pull_request_sync", "sync_completed")
+                .with_data("checked", Value::from(usize_to_u64(outcome.checked)))
+                .with_data("updated", Value::from(usize_to_u64(outcome.updates.len())))
+                .with_data("events", Value::from(usize_to_u64(outcome.events.len())))
+                .with_data("errors", Value::from(usize_to_u64(outcome.errors.len()))),
+        );
+        for error in outcome.errors {
+            self.telemetry.event_log.log(
+                LogEvent::new("pull_request_sync", "sync_failed")
+                    .with_data("error", Value::from(error)),
+            );
+        }
+        if alerts_changed {
+            self.refresh_attention_items();
+        }
+    }
+
+    /// Drops CI alerts whose pull request is no longer open with failing checks.
+    fn clear_resolved_pull_request_alerts(&mut self) -> bool {
+        let before = self.pull_request_alerts.len();
+        let workspaces = &self.state.workspaces;
+        self.pull_request_alerts.retain(|workspace_path, alert| {
+            if alert.reason != AttentionReason::ChecksFailed {
+                return true;
+            }
+            workspaces
+                .iter()
+                .find(|workspace| &workspace.path == workspace_path)
+                .is_some_and(|workspace| {
+                    workspace.pull_requests.iter().any(|pull_request| {
+                        alert.fingerprint == format!("checks-failed:{}", pull_request.url)
+                            && pull_request.status == PullRequestStatus::Open
+                            && pull_request.checks == Some(PullRequestChecks::Failing)
+                    })
+                })
+        });
+        self.pull_request_alerts.len() != before
+    }
+
+    pub(super) fn pull_request_attention_item(
+        &self,
+        workspace: &Workspace,
+        now_ms: u64,
+    ) -> Option<AttentionItem> {
+        let alert = self.pull_request_alerts.get(workspace.path.as_path())?;
+        Some(AttentionItem {
+            first_seen_at_ms: now_ms,
+            last_seen_at_ms: now_ms,
+            ..alert.clone()
+        })
+    }
+}
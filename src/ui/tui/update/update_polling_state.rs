@@ -30,14 +30,7 @@ impl GroveApp {
             return None;
         }
 
-        latest_assistant_attention_marker(workspace.agent, workspace.path.as_path())
-    }
-
-    fn permission_wall_prompt(prompt: &str) -> bool {
-        let lower = prompt.to_ascii_lowercase();
-        ["approve", "allow", "confirm", "do you want"]
-            .iter()
-            .any(|pattern| lower.contains(pattern))
+        latest_assistant_attention_marker(&workspace.agent, workspace.path.as_path())
     }
 
     fn attention_item_for_workspace(
@@ -65,12 +58,17 @@ impl GroveApp {
                 .polling
                 .workspace_waiting_prompts
                 .get(workspace.path.as_path())
-                .cloned();
-            let reason = waiting_prompt
-                .as_deref()
-                .filter(|prompt| Self::permission_wall_prompt(prompt))
-                .map(|_| AttentionReason::PermissionWall)
-                .unwrap_or(AttentionReason::BlockedOnQuestion);
+                .map(String::as_str);
+            let waiting_attention = self
+                .polling
+                .workspace_waiting_attention
+                .get(workspace.path.as_path())
+                .copied();
+            let reason = if waiting_is_permission_wall(waiting_prompt, waiting_attention) {
+                AttentionReason::PermissionWall
+            } else {
+                AttentionReason::BlockedOnQuestion
+            };
             return Some(AttentionItem {
                 fingerprint: format!("{}:{marker}", reason.summary()),
                 reason,
@@ -85,7 +83,7 @@ impl GroveApp {
         if !workspace.supported_agent
             || !self.workspace_has_running_agent_tab(workspace.path.as_path())
         {
-            return None;
+            return self.pull_request_attention_item(workspace, now_ms);
         }
 
         if workspace.status == WorkspaceStatus::Done {
@@ -120,7 +118,7 @@ impl GroveApp {
             });
         }
 
-        None
+        self.pull_request_attention_item(workspace, now_ms)
     }
 
     fn current_attention_fingerprint_for_workspace_path(
@@ -148,6 +146,13 @@ impl GroveApp {
         let Some(fingerprint) = fingerprint else {
             return false;
         };
+        if self
+            .pull_request_alerts
+            .get(workspace_path)
+            .is_some_and(|alert| alert.fingerprint == fingerprint)
+        {
+            self.pull_request_alerts.remove(workspace_path);
+        }
         if self
             .workspace_attention_ack_markers
             .get(workspace_path)
@@ -156,6 +161,7 @@ impl GroveApp {
             return false;
         }
 
+        self.record_attention_acknowledged_history(workspace_path, fingerprint.as_str());
         self.workspace_attention_ack_markers
             .insert(workspace_path.to_path_buf(), fingerprint);
         true
@@ -195,10 +201,10 @@ impl GroveApp {
         paths
     }
 
-    fn refresh_attention_items(&mut self) {
+    pub(super) fn refresh_attention_items(&mut self) {
         let now_ms = now_millis();
-        let previous_items = self
-            .attention_items
+        let previous_items_in_order = self.attention_items.clone();
+        let previous_items = previous_items_in_order
             .iter()
             .cloned()
             .map(|item| (item.workspace_path.clone(), item))
@@ -299,6 +305,11 @@ impl GroveApp {
                 .then_with(|| left.workspace_path.cmp(&right.workspace_path))
         });
         self.attention_observations = next_observations;
+        let (raised, cleared) =
+            super::update_polling_history::attention_changes(&previous_items_in_order, &next_items);
+        self.record_attention_history(&raised, &cleared);
+        self.notify_attention_raised(&raised);
+        self.run_attention_hooks(&raised);
         self.attention_items = next_items;
         if self
             .selected_attention_item
@@ -338,11 +349,13 @@ impl GroveApp {
     pub(super) fn track_workspace_status_transition(
         &mut self,
         workspace_path: &Path,
-        _previous_status: WorkspaceStatus,
-        _next_status: WorkspaceStatus,
+        previous_status: WorkspaceStatus,
+        next_status: WorkspaceStatus,
         _previous_orphaned: bool,
         _next_orphaned: bool,
     ) {
+        self.record_status_history(workspace_path, previous_status, next_status);
+        self.advance_prompt_queue(workspace_path, next_status);
         self.refresh_attention_items();
         if self
             .selected_attention_item
@@ -350,17 +363,18 @@ impl GroveApp {
         {
             self.selected_attention_item = None;
         }
-        let _ = workspace_path;
     }
 
     pub(super) fn track_workspace_status_transition_deferred(
         &mut self,
-        _workspace_path: &Path,
-        _previous_status: WorkspaceStatus,
-        _next_status: WorkspaceStatus,
+        workspace_path: &Path,
+        previous_status: WorkspaceStatus,
+        next_status: WorkspaceStatus,
         _previous_orphaned: bool,
         _next_orphaned: bool,
     ) {
+        self.record_status_history(workspace_path, previous_status, next_status);
+        self.advance_prompt_queue(workspace_path, next_status);
     }
 
     pub(super) fn flush_deferred_attention_refresh(&mut self) {
@@ -391,6 +405,8 @@ impl GroveApp {
             .retain(|path, _| valid_paths.contains(path));
         self.attention_observations
             .retain(|path, _| valid_paths.contains(path));
+        self.pull_request_alerts
+            .retain(|path, _| valid_paths.contains(path));
         self.attention_items
             .retain(|item| valid_paths.contains(item.workspace_path.as_path()));
         self.refresh_attention_items();
@@ -450,21 +466,21 @@ impl GroveApp {
             .workspace_recent_activity
             .remove(workspace_path);
 
-        if status == WorkspaceStatus::Waiting {
-            if let Some(prompt) = detect_waiting_prompt(cleaned_output) {
-                self.polling
-                    .workspace_waiting_prompts
-                    .insert(workspace_path.to_path_buf(), prompt);
-            } else {
-                self.polling
-                    .workspace_waiting_prompts
-                    .remove(workspace_path);
-            }
-        } else {
-            self.polling
-                .workspace_waiting_prompts
-                .remove(workspace_path);
-        }
+        let waiting_rule = (status == WorkspaceStatus::Waiting)
+            .then(|| {
+                self.state
+                    .workspaces
+                    .iter()
+                    .find(|workspace| workspace.path == workspace_path)
+                    .and_then(|workspace| {
+                        detect_waiting_rule(&self.status_rules, &workspace.agent, cleaned_output)
+                    })
+            })
+            .flatten();
+        self.record_workspace_waiting_prompt(
+            workspace_path,
+            waiting_rule.map(|rule_match| (rule_match.matched, rule_match.attention)),
+        );
 
         if changed {
             self.polling
@@ -513,7 +529,8 @@ impl GroveApp {
 
         crate::application::agent_runtime::status::workspace_status_observation_in_home(
             crate::application::agent_runtime::status::BackgroundStatusObservationContext {
-                agent: workspace.agent,
+                rules: &self.status_rules,
+                agent: &workspace.agent,
                 workspace_path,
                 home_dir: Some(home_dir.as_path()),
                 activity_threshold: Duration::from_secs(30),
@@ -528,21 +545,11 @@ impl GroveApp {
         workspace_path: &Path,
         observation: &WorkspaceStatusObservation,
     ) {
-        if observation.status == WorkspaceStatus::Waiting {
-            if let Some(prompt) = &observation.waiting_excerpt {
-                self.polling
-                    .workspace_waiting_prompts
-                    .insert(workspace_path.to_path_buf(), prompt.clone());
-            } else {
-                self.polling
-                    .workspace_waiting_prompts
-                    .remove(workspace_path);
-            }
-        } else {
-            self.polling
-                .workspace_waiting_prompts
-                .remove(workspace_path);
-        }
+        let waiting_prompt = (observation.status == WorkspaceStatus::Waiting)
+            .then(|| observation.waiting_excerpt.clone())
+            .flatten()
+            .map(|prompt| (prompt, observation.waiting_attention));
+        self.record_workspace_waiting_prompt(workspace_path, waiting_prompt);
 
         self.polling
             .workspace_recent_activity
@@ -592,6 +599,37 @@ impl GroveApp {
         );
     }
 
+    fn record_workspace_waiting_prompt(
+        &mut self,
+        workspace_path: &Path,
+        waiting_prompt: Option<(String, Option<NotificationReason>)>,
+    ) {
+        let Some((prompt, attention)) = waiting_prompt else {
+            self.polling
+                .workspace_waiting_prompts
+                .remove(workspace_path);
+            self.polling
+                .workspace_waiting_attention
+                .remove(workspace_path);
+            return;
+        };
+        self.polling
+            .workspace_waiting_prompts
+            .insert(workspace_path.to_path_buf(), prompt);
+        match attention {
+            Some(attention) => {
+                self.polling
+                    .workspace_waiting_attention
+                    .insert(workspace_path.to_path_buf(), attention);
+            }
+            None => {
+                self.polling
+                    .workspace_waiting_attention
+                    .remove(workspace_path);
+            }
+        }
+    }
+
     pub(super) fn clear_status_tracking_for_workspace_path(&mut self, workspace_path: &Path) {
         self.polling.workspace_status_digests.remove(workspace_path);
         self.polling
@@ -604,12 +642,11 @@ impl GroveApp {
         self.polling
             .workspace_status_observation_overrides
             .remove(workspace_path);
-        self.polling
-            .workspace_waiting_prompts
-            .remove(workspace_path);
+        self.record_workspace_waiting_prompt(workspace_path, None);
         self.polling
             .workspace_idle_polls_since_output
             .remove(workspace_path);
+        self.polling.prompt_queue_sent.remove(workspace_path);
         self.attention_observations.remove(workspace_path);
     }
 
@@ -620,7 +657,9 @@ impl GroveApp {
         #[cfg(test)]
         self.polling.workspace_status_observation_overrides.clear();
         self.polling.workspace_waiting_prompts.clear();
+        self.polling.workspace_waiting_attention.clear();
         self.polling.workspace_idle_polls_since_output.clear();
+        self.polling.prompt_queue_sent.clear();
         self.attention_observations.clear();
     }
 
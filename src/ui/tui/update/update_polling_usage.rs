@@ -0,0 +1,99 @@
+use super::update_prelude::*;
+use crate::application::agent_runtime::usage::UsageCost;
+
+const USAGE_POLL_INTERVAL_MS: u64 = 30_000;
+
+impl GroveApp {
+    /// Agents whose session logs count toward `workspace`: its default agent plus any agent tabs.
+    fn usage_agents_for_workspace(&self, workspace: &Workspace) -> Vec<AgentType> {
+        let mut agents = vec![workspace.agent.clone()];
+        if let Some(tabs) = self.workspace_tabs.get(workspace.path.as_path()) {
+            for agent in tabs.tabs.iter().filter_map(|tab| tab.agent_type.as_ref()) {
+                if !agents.contains(agent) {
+                    agents.push(agent.clone());
+                }
+            }
+        }
+        agents
+    }
+
+    pub(super) fn maybe_poll_usage(&mut self) {
+        if self.polling.usage_poll_in_flight {
+            return;
+        }
+        let Some(home_dir) = self.usage_home_dir.clone() else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(last) = self.polling.last_usage_poll_at
+            && now.saturating_duration_since(last) < Duration::from_millis(USAGE_POLL_INTERVAL_MS)
+        {
+            return;
+        }
+        self.polling.last_usage_poll_at = Some(now);
+        let targets = self
+            .state
+            .workspaces
+            .iter()
+            .map(|workspace| {
+                (
+                    workspace.path.clone(),
+                    self.usage_agents_for_workspace(workspace),
+                )
+            })
+            .collect::<Vec<_>>();
+        if targets.is_empty() {
+            return;
+        }
+
+        let tracker = Arc::clone(&self.usage_tracker);
+        self.polling.usage_poll_in_flight = true;
+        self.queue_cmd(Cmd::task(move || {
+            let Ok(mut tracker) = tracker.lock() else {
+                return Msg::UsagePolled(UsagePollCompletion {
+                    worktrees: Vec::new(),
+                });
+            };
+            let worktrees = targets
+                .into_iter()
+                .map(|(workspace_path, agents)| {
+                    let mut totals = UsageTotals::default();
+                    for agent in agents {
+                        totals.merge(&tracker.refresh_worktree(
+                            &agent,
+                            workspace_path.as_path(),
+                            home_dir.as_path(),
+                        ));
+                    }
+                    (workspace_path, totals)
+                })
+                .collect();
+            Msg::UsagePolled(UsagePollCompletion { worktrees })
+        }));
+    }
+
+    pub(super) fn handle_usage_polled(&mut self, completion: UsagePollCompletion) {
+        self.polling.usage_poll_in_flight = false;
+        for (workspace_path, totals) in completion.worktrees {
+            if totals.is_empty() {
+                self.worktree_usage.remove(&workspace_path);
+            } else {
+                self.worktree_usage.insert(workspace_path, totals);
+            }
+        }
+    }
+
+    pub(super) fn task_usage_totals(&self, task: &Task) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for worktree in &task.worktrees {
+            if let Some(worktree_totals) = self.worktree_usage.get(worktree.path.as_path()) {
+                totals.merge(worktree_totals);
+            }
+        }
+        totals
+    }
+
+    pub(super) fn usage_cost(&self, totals: &UsageTotals) -> UsageCost {
+        self.usage_prices.estimate(totals)
+    }
+}
@@ -64,6 +64,10 @@ impl GroveApp {
             }
             self.poll_preview();
             self.maybe_poll_diff();
+            self.maybe_sync_pull_requests();
+            self.maybe_poll_home_dashboard();
+            self.maybe_poll_usage();
+            self.maybe_poll_command_tabs();
         }
 
         let pending_after = self.pending_input_depth();
@@ -95,6 +95,10 @@ impl GroveApp {
         self.render_pull_upstream_dialog_overlay(frame, area);
         self.render_settings_dialog_overlay(frame, area);
         self.render_performance_dialog_overlay(frame, area);
+        self.render_transcript_dialog_overlay(frame, area);
+        self.render_pane_search_dialog_overlay(frame, area);
+        self.render_broadcast_dialog_overlay(frame, area);
+        self.render_prompt_queue_dialog_overlay(frame, area);
         self.render_project_dialog_overlay(frame, area);
         self.render_keybind_help_overlay(frame, area);
         self.render_command_palette_overlay(frame, area);
@@ -28,11 +28,12 @@ impl GroveApp {
         Style::new().fg(packed(theme.border))
     }
 
-    pub(super) fn workspace_agent_color(&self, agent: AgentType) -> PackedRgba {
+    pub(super) fn workspace_agent_color(&self, agent: &AgentType) -> PackedRgba {
         let theme = self.active_ui_theme();
         match agent {
             AgentType::Claude => packed(theme.accent),
             AgentType::Codex => packed(theme.text),
+            AgentType::Custom(_) => packed(theme.secondary),
         }
     }
 
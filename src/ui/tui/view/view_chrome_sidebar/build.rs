@@ -1,5 +1,5 @@
 impl GroveApp {
-    fn pull_request_status_icon(status: crate::domain::PullRequestStatus) -> &'static str {
+    pub(super) fn pull_request_status_icon(status: crate::domain::PullRequestStatus) -> &'static str {
         match status {
             crate::domain::PullRequestStatus::Open => "",
             crate::domain::PullRequestStatus::Merged => "",
@@ -7,7 +7,19 @@ impl GroveApp {
         }
     }
 
-    fn pull_request_status_style(
+    pub(super) fn pull_request_checks_marker(pull_request: &crate::domain::PullRequest) -> &'static str {
+        if pull_request.status != crate::domain::PullRequestStatus::Open {
+            return "";
+        }
+        match pull_request.checks {
+            Some(crate::domain::PullRequestChecks::Passing) => " ✓",
+            Some(crate::domain::PullRequestChecks::Failing) => " ✗",
+            Some(crate::domain::PullRequestChecks::Pending) => " …",
+            None => "",
+        }
+    }
+
+    pub(super) fn pull_request_status_style(
         status: crate::domain::PullRequestStatus,
         secondary_style: Style,
         theme: ftui::ResolvedTheme,
@@ -162,7 +174,7 @@ impl GroveApp {
 
         let workspace_label_style = if is_working {
             primary_style
-                .fg(self.workspace_agent_color(workspace.agent))
+                .fg(self.workspace_agent_color(&workspace.agent))
                 .bold()
         } else {
             primary_style
@@ -180,12 +192,17 @@ impl GroveApp {
             String::new()
         };
 
-        let row_prefix = "   ";
+        let broadcast_marked = self.broadcast_targets.contains(&workspace.path);
+        let row_prefix = if broadcast_marked { " ✓ " } else { "   " };
         let row_attention_gap = " ";
         let mut leading_segments = vec![
             SidebarSegment {
                 text: row_prefix.to_string(),
-                style: primary_style,
+                style: if broadcast_marked {
+                    primary_style.fg(packed(theme.accent)).bold()
+                } else {
+                    primary_style
+                },
             },
             SidebarSegment {
                 text: attention_symbol.to_string(),
@@ -211,7 +228,12 @@ impl GroveApp {
         let mut trailing_width = 0usize;
         let mut pr_hits = Vec::new();
         let needs_attention = self.workspace_attention(workspace.path.as_path()).is_some();
-        if needs_attention {
+        if self.workspace_conflicts.contains_key(&workspace.path) {
+            trailing_segments.push(SidebarSegment {
+                text: "CONFLICTED".to_string(),
+                style: secondary_style.fg(packed(theme.error)).bold(),
+            });
+        } else if needs_attention {
             trailing_segments.push(SidebarSegment {
                 text: "WAITING".to_string(),
                 style: secondary_style.fg(packed(theme.warning)).bold(),
@@ -219,7 +241,7 @@ impl GroveApp {
         } else if is_working {
             trailing_segments.push(SidebarSegment {
                 text: "WORKING".to_string(),
-                style: secondary_style.fg(self.workspace_agent_color(workspace.agent)).bold(),
+                style: secondary_style.fg(self.workspace_agent_color(&workspace.agent)).bold(),
             });
         } else if self.dialogs.delete_requested_workspaces.contains(&workspace.path) {
             trailing_segments.push(SidebarSegment {
@@ -241,9 +263,10 @@ impl GroveApp {
                     trailing_width = trailing_width.saturating_add(1);
                 }
                 let pull_request_label = format!(
-                    "{} #{}",
+                    "{} #{}{}",
                     Self::pull_request_status_icon(pull_request.status),
-                    pull_request.number
+                    pull_request.number,
+                    Self::pull_request_checks_marker(pull_request)
                 );
                 let token_width = text_display_width(&pull_request_label);
                 if let Some(hit_data) =
@@ -289,7 +312,7 @@ impl GroveApp {
                 lines.push(SidebarListLine::project(Vec::new()));
             }
 
-            lines.push(SidebarListLine::project(vec![SidebarSegment {
+            let mut header_segments = vec![SidebarSegment {
                 text: format!(
                     "{} {} [{}]",
                     self.task_header_marker(task),
@@ -297,7 +320,15 @@ impl GroveApp {
                     task.worktrees.len()
                 ),
                 style: Style::new().fg(packed(theme.border)).bold(),
-            }]));
+            }];
+            let usage = self.task_usage_totals(task);
+            if !usage.is_empty() {
+                header_segments.push(SidebarSegment {
+                    text: format!(" · {}", self.usage_cost(&usage).label()),
+                    style: Style::new().fg(packed(theme.text_subtle)),
+                });
+            }
+            lines.push(SidebarListLine::project(header_segments));
 
             if task.worktrees.is_empty() {
                 lines.push(SidebarListLine::project(vec![SidebarSegment {
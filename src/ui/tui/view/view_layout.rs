@@ -89,7 +89,11 @@ impl GroveApp {
                 | HIT_ID_SESSION_CLEANUP_DIALOG
                 | HIT_ID_RENAME_TAB_DIALOG
                 | HIT_ID_KEYBIND_HELP_DIALOG
-                | HIT_ID_PERFORMANCE_DIALOG => HitRegion::Outside,
+                | HIT_ID_PERFORMANCE_DIALOG
+                | HIT_ID_TRANSCRIPT_DIALOG
+                | HIT_ID_PANE_SEARCH_DIALOG
+                | HIT_ID_BROADCAST_DIALOG
+                | HIT_ID_PROMPT_QUEUE_DIALOG => HitRegion::Outside,
                 _ => HitRegion::Outside,
             };
             let row_data = if id.id() == HIT_ID_WORKSPACE_ROW || id.id() == HIT_ID_WORKSPACE_PR_LINK
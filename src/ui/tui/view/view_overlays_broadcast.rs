@@ -0,0 +1,122 @@
+use super::view_prelude::*;
+use crate::ui::tui::performance::workspace_status_label;
+
+impl GroveApp {
+    pub(super) fn render_broadcast_dialog_overlay(&self, frame: &mut Frame, area: Rect) {
+        let Some(dialog) = self.broadcast_dialog() else {
+            return;
+        };
+        if area.width < 40 || area.height < 12 {
+            return;
+        }
+
+        let theme = self.active_ui_theme();
+        let dialog_width = area.width.saturating_sub(8).min(110);
+        let content_width = usize::from(dialog_width.saturating_sub(2));
+        let fit = |text: &str| {
+            let text = ftui::text::truncate_with_ellipsis(text, content_width, "…");
+            format!(
+                "{text}{}",
+                " ".repeat(content_width.saturating_sub(ftui::text::display_width(text.as_str())))
+            )
+        };
+
+        let agents = match dialog.targets.len() {
+            1 => "1 agent".to_string(),
+            count => format!("{count} agents"),
+        };
+        let mode = if dialog.force {
+            "forced, busy agents included"
+        } else {
+            "waiting and idle agents only"
+        };
+        let hints = modal_wrapped_hint_rows(
+            content_width,
+            theme,
+            "type or paste the prompt, Enter send, Tab force busy agents, Esc clear/close",
+        );
+        let mut lines = vec![
+            FtLine::from_spans(vec![FtSpan::styled(
+                fit(format!("Send to {agents} · {mode}").as_str()),
+                Style::new().fg(packed(theme.border)),
+            )]),
+            modal_labeled_input_row(
+                content_width,
+                theme,
+                "Prompt",
+                dialog.prompt.replace('\n', " ⏎ ").as_str(),
+                "Same text for every agent",
+                true,
+            ),
+            FtLine::raw(""),
+        ];
+
+        let name_width = dialog
+            .targets
+            .iter()
+            .map(|target| ftui::text::display_width(target.workspace_name.as_str()))
+            .max()
+            .unwrap_or(0);
+        let max_target_rows = usize::from(area.height.saturating_sub(6))
+            .saturating_sub(lines.len())
+            .saturating_sub(hints.len())
+            .max(1);
+        for target in dialog.targets.iter().take(max_target_rows) {
+            let (result, result_color) = match target.delivery.as_ref() {
+                Some(BroadcastDelivery::Sent) => ("sent".to_string(), theme.success),
+                Some(BroadcastDelivery::Skipped(reason)) => {
+                    (format!("skipped: {reason}"), theme.warning)
+                }
+                Some(BroadcastDelivery::Failed(error)) => (format!("failed: {error}"), theme.error),
+                None if target.session_name.is_none() => {
+                    ("no running agent".to_string(), theme.text_muted)
+                }
+                None => (
+                    workspace_status_label(target.status).to_string(),
+                    theme.text_subtle,
+                ),
+            };
+            let name = format!("  {:<name_width$}  ", target.workspace_name);
+            let result_width = content_width.saturating_sub(ftui::text::display_width(&name));
+            let result = ftui::text::truncate_with_ellipsis(result.as_str(), result_width, "…");
+            lines.push(FtLine::from_spans(vec![
+                FtSpan::styled(name, Style::new().fg(packed(theme.text))),
+                FtSpan::styled(
+                    format!(
+                        "{result}{}",
+                        " ".repeat(
+                            result_width.saturating_sub(ftui::text::display_width(result.as_str()))
+                        )
+                    ),
+                    Style::new().fg(packed(result_color)),
+                ),
+            ]));
+        }
+        if dialog.targets.len() > max_target_rows {
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                fit(format!("  +{} more", dialog.targets.len() - max_target_rows).as_str()),
+                Style::new().fg(packed(theme.text_muted)),
+            )]));
+        }
+        lines.push(FtLine::raw(""));
+        lines.extend(hints);
+
+        let dialog_height = u16::try_from(lines.len().saturating_add(2))
+            .unwrap_or(u16::MAX)
+            .min(area.height.saturating_sub(2));
+        let body = FtText::from_lines(lines);
+        render_modal_dialog(
+            frame,
+            area,
+            body,
+            ModalDialogSpec {
+                dialog_width,
+                dialog_height,
+                title: "Broadcast Prompt",
+                theme,
+                border_color: packed(theme.accent),
+                hit_id: HIT_ID_BROADCAST_DIALOG,
+            },
+        );
+    }
+}
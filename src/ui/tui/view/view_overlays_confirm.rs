@@ -30,6 +30,18 @@ impl GroveApp {
                 format!("Session '{session_name}' is still live in tmux"),
                 packed(theme.warning),
             ),
+            ConfirmDialogAction::ArchiveTask { task } => (
+                "Archive Task?",
+                format!("Archive '{}' and remove its worktrees?", task.name),
+                "Changes, tabs and agent conversations are saved, branches are kept".to_string(),
+                packed(theme.warning),
+            ),
+            ConfirmDialogAction::ResumeArchivedAgents { task_name, tabs } => (
+                "Resume Agents?",
+                format!("Resume {} agent tab(s) for '{task_name}'?", tabs.len()),
+                "Each agent picks its saved conversation back up".to_string(),
+                packed(theme.primary),
+            ),
             ConfirmDialogAction::QuitApp => (
                 "Are you sure?",
                 "Quit Grove now?".to_string(),
@@ -386,6 +386,42 @@ impl GroveApp {
                 }
             }
         }
+        if dialog.shows_recipe_fields() {
+            let recipe_label = dialog.selected_recipe().map_or_else(
+                || format!("(none)  Left/Right choose from {}", dialog.recipes.len()),
+                |recipe| format!("{}  Left/Right change", recipe.name),
+            );
+            lines.push(modal_focus_badged_row(
+                content_width,
+                theme,
+                "Recipe",
+                recipe_label.as_str(),
+                focused(CreateDialogField::Recipe),
+                packed(theme.primary),
+                packed(theme.text_subtle),
+            ));
+            if let Some(recipe) = dialog.selected_recipe() {
+                if !recipe.variables.is_empty() {
+                    lines.push(modal_labeled_input_row(
+                        content_width,
+                        theme,
+                        "Vars",
+                        dialog.recipe_variables.as_str(),
+                        "name=value; name=value",
+                        focused(CreateDialogField::RecipeVariables),
+                    ));
+                }
+                let summary = if recipe.description.trim().is_empty() {
+                    recipe.path.display().to_string()
+                } else {
+                    recipe.description.trim().to_string()
+                };
+                lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                    fit(format!("  [Recipe] {summary}").as_str()),
+                    Style::new().fg(packed(theme.border)),
+                )]));
+            }
+        }
         if focused(CreateDialogField::Project)
             && (dialog.is_add_worktree_mode()
                 || dialog.tab != CreateDialogTab::Manual
@@ -415,9 +451,9 @@ impl GroveApp {
         let hint_text = if dialog.is_add_worktree_mode() {
             "Tab/C-n next, S-Tab/C-p prev, Enter browse projects, Enter add worktree, Esc cancel"
         } else if dialog.tab == CreateDialogTab::Manual {
-            "Tab/C-n next, S-Tab/C-p prev, click mode tab or Alt+[/Alt+], Space toggle base, Enter browse projects, base branch comes from Project Defaults or git, Enter create, Esc cancel"
+            "Tab/C-n next, S-Tab/C-p prev, click mode tab or Alt+[/Alt+], Space toggle base, Enter browse projects, Left/Right choose recipe, base branch comes from Project Defaults, the recipe or git, Enter create, Esc cancel"
         } else {
-            "Tab/C-n next, S-Tab/C-p prev, click mode tab or Alt+[/Alt+], Enter browse projects, Enter create, Esc cancel"
+            "Tab/C-n next, S-Tab/C-p prev, click mode tab or Alt+[/Alt+], Enter browse projects, Left/Right choose recipe, Enter create, Esc cancel"
         };
         lines.extend(modal_wrapped_hint_rows(content_width, theme, hint_text));
         let body = FtText::from_lines(lines);
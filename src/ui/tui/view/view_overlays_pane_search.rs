@@ -0,0 +1,166 @@
+use super::view_prelude::*;
+
+/// Lines shown on each side of the selected hit.
+const PANE_SEARCH_CONTEXT_LINES: usize = 3;
+
+impl GroveApp {
+    pub(super) fn render_pane_search_dialog_overlay(&self, frame: &mut Frame, area: Rect) {
+        let Some(dialog) = self.pane_search_dialog() else {
+            return;
+        };
+        if area.width < 40 || area.height < 20 {
+            return;
+        }
+
+        let dialog_width = area.width.saturating_sub(6).min(180);
+        let dialog_height = area.height.saturating_sub(4);
+        let theme = self.active_ui_theme();
+        let content_width = usize::from(dialog_width.saturating_sub(2));
+        let fit = |text: &str| {
+            let text = ftui::text::truncate_with_ellipsis(text, content_width, "…");
+            format!(
+                "{text}{}",
+                " ".repeat(content_width.saturating_sub(ftui::text::display_width(text.as_str())))
+            )
+        };
+
+        let hit_count = dialog.hit_count();
+        let sessions = match dialog.sources.len() {
+            1 => "1 session".to_string(),
+            count => format!("{count} sessions"),
+        };
+        let summary = if dialog.query.trim().is_empty() {
+            format!("{sessions} captured")
+        } else if hit_count == 0 {
+            format!("no matches in {sessions}")
+        } else {
+            format!(
+                "match {}/{hit_count} in {sessions}",
+                dialog.selected.saturating_add(1)
+            )
+        };
+        let hints = modal_wrapped_hint_rows(
+            content_width,
+            theme,
+            "type to search, Up/Down select, PgUp/PgDn/Home/End jump, Enter open in preview, Esc clear/close",
+        );
+        let mut lines = vec![
+            FtLine::from_spans(vec![FtSpan::styled(
+                fit(summary.as_str()),
+                Style::new().fg(packed(theme.border)),
+            )]),
+            modal_labeled_input_row(
+                content_width,
+                theme,
+                "Search",
+                dialog.query.as_str(),
+                "Find in every live session",
+                true,
+            ),
+            FtLine::raw(""),
+        ];
+
+        let context_rows = PANE_SEARCH_CONTEXT_LINES * 2 + 1;
+        let header_rows = lines.len();
+        let list_rows = usize::from(dialog_height.saturating_sub(2))
+            .saturating_sub(header_rows)
+            .saturating_sub(context_rows + 1)
+            .saturating_sub(hints.len())
+            .max(1);
+
+        let label_width = dialog
+            .sources
+            .iter()
+            .map(|source| ftui::text::display_width(source.label.as_str()))
+            .max()
+            .unwrap_or(0);
+        let mut rows: Vec<(String, Option<usize>)> = Vec::new();
+        let mut hit_index = 0;
+        for group in &dialog.groups {
+            rows.push((format!("{} ({})", group.group, group.hits.len()), None));
+            for hit in &group.hits {
+                let Some(source) = dialog.sources.get(hit.source) else {
+                    continue;
+                };
+                let text = source.lines.get(hit.line).map_or("", |line| line.trim());
+                rows.push((
+                    format!("  {:<label_width$}  {text}", source.label),
+                    Some(hit_index),
+                ));
+                hit_index += 1;
+            }
+        }
+        let selected_row = rows
+            .iter()
+            .position(|(_, hit)| *hit == Some(dialog.selected))
+            .unwrap_or(0);
+        let first_row = selected_row.saturating_add(1).saturating_sub(list_rows);
+        for (text, hit) in rows.iter().skip(first_row).take(list_rows) {
+            let style = match hit {
+                Some(index) if *index == dialog.selected => Style::new()
+                    .fg(packed(theme.text))
+                    .bg(packed(theme.selection_bg)),
+                Some(_) => Style::new().fg(packed(theme.text)),
+                None => Style::new().fg(packed(theme.accent)).bold(),
+            };
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                fit(text.as_str()),
+                style,
+            )]));
+        }
+        while lines.len() < header_rows.saturating_add(list_rows) {
+            lines.push(FtLine::raw(""));
+        }
+
+        let selected = dialog
+            .selected_hit()
+            .and_then(|hit| dialog.sources.get(hit.source).map(|source| (hit, source)));
+        let context_title = selected.map_or_else(
+            || "Context".to_string(),
+            |(_, source)| format!("Context · {} · {}", source.label, source.session_name),
+        );
+        lines.push(FtLine::from_spans(vec![FtSpan::styled(
+            fit(context_title.as_str()),
+            Style::new().fg(packed(theme.border)),
+        )]));
+        if let Some((hit, source)) = selected {
+            let first_line = hit.line.saturating_sub(PANE_SEARCH_CONTEXT_LINES);
+            for (index, line) in source
+                .lines
+                .iter()
+                .enumerate()
+                .skip(first_line)
+                .take(context_rows)
+            {
+                let style = if index == hit.line {
+                    Style::new().fg(packed(theme.warning))
+                } else {
+                    Style::new().fg(packed(theme.text_subtle))
+                };
+                lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                    fit(line.as_str()),
+                    style,
+                )]));
+            }
+        }
+        while lines.len() < header_rows + list_rows + 1 + context_rows {
+            lines.push(FtLine::raw(""));
+        }
+        lines.extend(hints);
+        let body = FtText::from_lines(lines);
+
+        render_modal_dialog(
+            frame,
+            area,
+            body,
+            ModalDialogSpec {
+                dialog_width,
+                dialog_height,
+                title: "Search Sessions",
+                theme,
+                border_color: packed(theme.info),
+                hit_id: HIT_ID_PANE_SEARCH_DIALOG,
+            },
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::view_prelude::*;
+
+impl GroveApp {
+    pub(super) fn render_prompt_queue_dialog_overlay(&self, frame: &mut Frame, area: Rect) {
+        let Some(dialog) = self.prompt_queue_dialog() else {
+            return;
+        };
+        if area.width < 40 || area.height < 12 {
+            return;
+        }
+
+        let theme = self.active_ui_theme();
+        let dialog_width = area.width.saturating_sub(8).min(110);
+        let content_width = usize::from(dialog_width.saturating_sub(2));
+        let fit = |text: &str| {
+            let text = ftui::text::truncate_with_ellipsis(text, content_width, "…");
+            format!(
+                "{text}{}",
+                " ".repeat(content_width.saturating_sub(ftui::text::display_width(text.as_str())))
+            )
+        };
+
+        let queued = match dialog.queue.prompts.len() {
+            1 => "1 prompt queued".to_string(),
+            count => format!("{count} prompts queued"),
+        };
+        let (summary, summary_color) = match dialog.queue.paused.as_deref() {
+            Some(reason) => (
+                format!("{} · {queued} · paused: {reason}", dialog.workspace_name),
+                theme.warning,
+            ),
+            None => (
+                format!(
+                    "{} · {queued} · next is sent when the agent is back at its input",
+                    dialog.workspace_name
+                ),
+                theme.border,
+            ),
+        };
+        let hints = modal_wrapped_hint_rows(
+            content_width,
+            theme,
+            "Enter queue/close, Up/Down select, Del remove, Tab pause/resume, Esc clear/close",
+        );
+        let mut lines = vec![
+            FtLine::from_spans(vec![FtSpan::styled(
+                fit(summary.as_str()),
+                Style::new().fg(packed(summary_color)),
+            )]),
+            modal_labeled_input_row(
+                content_width,
+                theme,
+                "Prompt",
+                dialog.input.replace('\n', " ⏎ ").as_str(),
+                "Follow-up to send after the current turn",
+                true,
+            ),
+            FtLine::raw(""),
+        ];
+
+        let list_rows = usize::from(area.height.saturating_sub(6))
+            .saturating_sub(lines.len())
+            .saturating_sub(hints.len())
+            .max(1);
+        let first_row = dialog
+            .selected_index
+            .saturating_add(1)
+            .saturating_sub(list_rows);
+        if dialog.queue.prompts.is_empty() {
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                fit("  queue is empty"),
+                Style::new().fg(packed(theme.text_muted)),
+            )]));
+        }
+        for (index, prompt) in dialog
+            .queue
+            .prompts
+            .iter()
+            .enumerate()
+            .skip(first_row)
+            .take(list_rows)
+        {
+            let style = if index == dialog.selected_index {
+                Style::new()
+                    .fg(packed(theme.text))
+                    .bg(packed(theme.selection_bg))
+            } else {
+                Style::new().fg(packed(theme.text))
+            };
+            let text = format!("  {}. {}", index + 1, prompt.replace('\n', " ⏎ "));
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                fit(text.as_str()),
+                style,
+            )]));
+        }
+        lines.push(FtLine::raw(""));
+        lines.extend(hints);
+
+        let dialog_height = u16::try_from(lines.len().saturating_add(2))
+            .unwrap_or(u16::MAX)
+            .min(area.height.saturating_sub(2));
+        let body = FtText::from_lines(lines);
+        render_modal_dialog(
+            frame,
+            area,
+            body,
+            ModalDialogSpec {
+                dialog_width,
+                dialog_height,
+                title: "Prompt Queue",
+                theme,
+                border_color: packed(theme.accent),
+                hit_id: HIT_ID_PROMPT_QUEUE_DIALOG,
+            },
+        );
+    }
+}
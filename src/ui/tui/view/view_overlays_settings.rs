@@ -18,7 +18,7 @@ impl GroveApp {
         let cancel_focused = self.dialog_focus_is(FOCUS_ID_SETTINGS_CANCEL_BUTTON);
         let theme_value = format!(
             "{} ({})",
-            theme_display_name(dialog.theme),
+            theme_display_name(&dialog.theme),
             dialog.theme.config_key()
         );
         let fit = |text: &str| {
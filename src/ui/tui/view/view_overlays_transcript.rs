@@ -0,0 +1,110 @@
+use super::view_prelude::*;
+
+impl GroveApp {
+    pub(super) fn render_transcript_dialog_overlay(&self, frame: &mut Frame, area: Rect) {
+        let Some(dialog) = self.transcript_dialog() else {
+            return;
+        };
+        if area.width < 40 || area.height < 14 {
+            return;
+        }
+
+        let dialog_width = area.width.saturating_sub(6).min(180);
+        let dialog_height = area.height.saturating_sub(4);
+        let theme = self.active_ui_theme();
+        let content_width = usize::from(dialog_width.saturating_sub(2));
+        let fit = |text: &str| {
+            let text = ftui::text::truncate_with_ellipsis(text, content_width, "…");
+            format!(
+                "{text}{}",
+                " ".repeat(content_width.saturating_sub(ftui::text::display_width(text.as_str())))
+            )
+        };
+
+        let match_summary = if dialog.query.is_empty() {
+            format!("{} lines", dialog.lines.len())
+        } else if dialog.matches.is_empty() {
+            "no matches".to_string()
+        } else {
+            format!(
+                "match {}/{}",
+                dialog.selected_match.saturating_add(1),
+                dialog.matches.len()
+            )
+        };
+        let hints = modal_wrapped_hint_rows(
+            content_width,
+            theme,
+            "type to search, Enter/Down next match, Up prev, PgUp/PgDn/Home/End scroll, Esc clear/close",
+        );
+        let mut lines = vec![
+            FtLine::from_spans(vec![FtSpan::styled(
+                fit(format!(
+                    "{} · {} · {match_summary}",
+                    dialog.task_name, dialog.session_name
+                )
+                .as_str()),
+                Style::new().fg(packed(theme.border)),
+            )]),
+            modal_labeled_input_row(
+                content_width,
+                theme,
+                "Search",
+                dialog.query.as_str(),
+                "Find in transcript",
+                true,
+            ),
+            FtLine::raw(""),
+        ];
+
+        let header_rows = lines.len();
+        let visible_lines = usize::from(dialog_height.saturating_sub(2))
+            .saturating_sub(header_rows)
+            .saturating_sub(hints.len())
+            .max(1);
+        let first_line = dialog
+            .scroll
+            .min(dialog.lines.len().saturating_sub(visible_lines));
+        let selected_line = dialog.selected_line();
+        for (index, line) in dialog
+            .lines
+            .iter()
+            .enumerate()
+            .skip(first_line)
+            .take(visible_lines)
+        {
+            let style = if selected_line == Some(index) {
+                Style::new()
+                    .fg(packed(theme.text))
+                    .bg(packed(theme.selection_bg))
+            } else if dialog.matches.binary_search(&index).is_ok() {
+                Style::new().fg(packed(theme.warning))
+            } else {
+                Style::new().fg(packed(theme.text))
+            };
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                fit(line.as_str()),
+                style,
+            )]));
+        }
+        while lines.len() < header_rows.saturating_add(visible_lines) {
+            lines.push(FtLine::raw(""));
+        }
+        lines.extend(hints);
+        let body = FtText::from_lines(lines);
+
+        render_modal_dialog(
+            frame,
+            area,
+            body,
+            ModalDialogSpec {
+                dialog_width,
+                dialog_height,
+                title: "Transcript",
+                theme,
+                border_color: packed(theme.info),
+                hit_id: HIT_ID_TRANSCRIPT_DIALOG,
+            },
+        );
+    }
+}
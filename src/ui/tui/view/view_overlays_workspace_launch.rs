@@ -26,16 +26,16 @@ impl GroveApp {
                 " ".repeat(content_width.saturating_sub(ftui::text::display_width(text.as_str())))
             )
         };
-        let agent_row = |agent: AgentType| {
+        let agent_row = |agent: &AgentType| {
             let mut style = Style::new().fg(packed(theme.border));
             if self.dialog_focus_is(FOCUS_ID_LAUNCH_AGENT) {
                 style = style.bg(packed(theme.surface));
             }
-            let marker = if dialog.agent == agent { "●" } else { "○" };
+            let marker = if dialog.agent == *agent { "●" } else { "○" };
             let label = format!("{marker} {}", agent.label());
             FtLine::from_spans(vec![FtSpan::styled(
                 fit(label.as_str()),
-                if dialog.agent == agent {
+                if dialog.agent == *agent {
                     style.fg(self.workspace_agent_color(agent)).bold()
                 } else {
                     style
@@ -58,8 +58,8 @@ impl GroveApp {
                 fit("Agent"),
                 Style::new().fg(packed(theme.border)),
             )]),
-            agent_row(AgentType::Claude),
-            agent_row(AgentType::Codex),
+            agent_row(&AgentType::Claude),
+            agent_row(&AgentType::Codex),
             FtLine::raw(""),
             config_rows[0].clone(),
             config_rows[1].clone(),
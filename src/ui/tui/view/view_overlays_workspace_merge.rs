@@ -10,7 +10,7 @@ impl GroveApp {
         }
 
         let dialog_width = area.width.saturating_sub(8).min(98);
-        let dialog_height = 17u16;
+        let dialog_height = 18u16;
         let theme = self.active_ui_theme();
         let content_width = usize::from(dialog_width.saturating_sub(2));
         let cleanup_workspace_focused = self.dialog_focus_is(FOCUS_ID_MERGE_CLEANUP_WORKSPACE);
@@ -25,6 +25,12 @@ impl GroveApp {
         } else {
             "disabled, keep local branch".to_string()
         };
+        let strategy_focused = self.dialog_focus_is(FOCUS_ID_MERGE_STRATEGY);
+        let strategy_state = match dialog.strategy {
+            MergeStrategy::Merge => "merge, git merge --no-ff into base",
+            MergeStrategy::Squash => "squash, single commit with generated message",
+            MergeStrategy::Rebase => "rebase, rebase onto base then fast-forward",
+        };
         let merge_focused = self.dialog_focus_is(FOCUS_ID_MERGE_CONFIRM_BUTTON);
         let cancel_focused = self.dialog_focus_is(FOCUS_ID_MERGE_CANCEL_BUTTON);
         let path = dialog.workspace_path.display().to_string();
@@ -100,6 +106,15 @@ impl GroveApp {
                     packed(theme.text)
                 },
             ),
+            modal_focus_badged_row(
+                content_width,
+                theme,
+                "Strategy",
+                strategy_state,
+                strategy_focused,
+                packed(theme.accent),
+                packed(theme.text),
+            ),
             FtLine::raw(""),
             modal_actions_row(
                 content_width,
@@ -113,7 +128,7 @@ impl GroveApp {
         lines.extend(modal_wrapped_hint_rows(
             content_width,
             theme,
-            "Tab/C-n next, S-Tab/C-p prev, Space toggle cleanup, Enter or m merge, Esc cancel",
+            "Tab/C-n next, S-Tab/C-p prev, Space toggle cleanup or cycle strategy, Enter or m merge, Esc cancel",
         ));
         let body = FtText::from_lines(lines);
         render_modal_dialog(
@@ -15,21 +15,20 @@ impl GroveApp {
         let content_width = usize::from(dialog_width.saturating_sub(2));
         let update_focused = self.dialog_focus_is(FOCUS_ID_UPDATE_FROM_BASE_CONFIRM_BUTTON);
         let cancel_focused = self.dialog_focus_is(FOCUS_ID_UPDATE_FROM_BASE_CANCEL_BUTTON);
+        let strategy_focused = self.dialog_focus_is(FOCUS_ID_UPDATE_FROM_BASE_STRATEGY);
         let path = dialog.workspace_path.display().to_string();
-        let (title, strategy, base_label, base_value) = if dialog.is_main_workspace {
+        let (title, base_label, base_value) = if dialog.is_main_workspace {
             (
                 "Update From Upstream?",
-                "  Strategy: git pull --ff-only origin <branch> in base workspace",
                 "Upstream",
                 format!("origin/{}", dialog.base_branch),
             )
         } else {
-            (
-                "Update From Base?",
-                "  Strategy: git merge --no-ff <base> into workspace branch",
-                "Base",
-                dialog.base_branch.clone(),
-            )
+            ("Update From Base?", "Base", dialog.base_branch.clone())
+        };
+        let strategy_state = match dialog.strategy {
+            UpdateStrategy::Merge => "merge, git merge --no-ff <base> into workspace branch",
+            UpdateStrategy::Rebase => "rebase, replay workspace commits onto <base>",
         };
         let fit = |text: &str| {
             let text = ftui::text::truncate_with_ellipsis(text, content_width, "…");
@@ -77,13 +76,25 @@ impl GroveApp {
                 packed(theme.border),
             ),
         ];
-        lines.extend(modal_wrapped_rows(
-            content_width,
-            strategy,
-            Style::new()
-                .fg(packed(theme.text_subtle))
-                .bg(packed(theme.background)),
-        ));
+        if dialog.is_main_workspace {
+            lines.extend(modal_wrapped_rows(
+                content_width,
+                "  Strategy: git pull --ff-only origin <branch> in base workspace",
+                Style::new()
+                    .fg(packed(theme.text_subtle))
+                    .bg(packed(theme.background)),
+            ));
+        } else {
+            lines.push(modal_focus_badged_row(
+                content_width,
+                theme,
+                "Strategy",
+                strategy_state,
+                strategy_focused,
+                packed(theme.accent),
+                packed(theme.text),
+            ));
+        }
         lines.push(FtLine::raw(""));
         lines.push(modal_actions_row(
             content_width,
@@ -96,7 +107,7 @@ impl GroveApp {
         lines.extend(modal_wrapped_hint_rows(
             content_width,
             theme,
-            "Tab/C-n next, S-Tab/C-p prev, h/l switch buttons, Enter or u update, Esc cancel",
+            "Tab/C-n next, S-Tab/C-p prev, h/l switch buttons or strategy, Enter or u update, Esc cancel",
         ));
         let body = FtText::from_lines(lines);
         render_modal_dialog(
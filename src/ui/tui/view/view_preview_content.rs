@@ -132,7 +132,7 @@ impl GroveApp {
                 } else {
                     tab_inactive_style
                 };
-                tab_spans.push(FtSpan::styled(format!(" {} ", tab.title), style));
+                tab_spans.push(FtSpan::styled(tab.bar_label(), style));
             }
         }
         if self.preview_tab == PreviewTab::Home && self.selected_task_supports_parent_agent() {
@@ -263,13 +263,13 @@ impl GroveApp {
         );
 
         if visible_parsed_lines.is_empty() {
-            return vec![match self.preview_tab {
-                PreviewTab::Home => FtLine::raw("(home)"),
-                PreviewTab::Agent => FtLine::raw("(no preview output)"),
-                PreviewTab::Shell => self.preview_shell_fallback_line(selected_workspace),
-                PreviewTab::Git => self.preview_git_fallback_line(selected_workspace),
-                PreviewTab::Diff => FtLine::raw("(no diff output)"),
-            }];
+            return match self.preview_tab {
+                PreviewTab::Home => self.home_dashboard_lines(preview_height),
+                PreviewTab::Agent => vec![FtLine::raw("(no preview output)")],
+                PreviewTab::Shell => vec![self.preview_shell_fallback_line(selected_workspace)],
+                PreviewTab::Git => vec![self.preview_git_fallback_line(selected_workspace)],
+                PreviewTab::Diff => vec![FtLine::raw("(no diff output)")],
+            };
         }
 
         visible_parsed_lines
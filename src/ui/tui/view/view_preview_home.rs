@@ -0,0 +1,348 @@
+use super::update_polling_home::HOME_DASHBOARD_HOTKEY_COUNT;
+use super::view_prelude::*;
+use crate::application::agent_runtime::usage::{UsageTotals, compact_token_count};
+use crate::ui::tui::performance::workspace_status_label;
+
+const HOME_DASHBOARD_EXCERPT_MAX_CHARS: usize = 60;
+const HOME_DASHBOARD_INDENT: &str = "    ";
+
+fn uptime_label(secs: i64) -> String {
+    if secs < 60 {
+        return format!("{secs}s");
+    }
+    if secs < 3_600 {
+        return format!("{}m", secs / 60);
+    }
+    if secs < 86_400 {
+        return format!("{}h {}m", secs / 3_600, (secs % 3_600) / 60);
+    }
+    format!("{}d {}h", secs / 86_400, (secs % 86_400) / 3_600)
+}
+
+fn waiting_excerpt(prompt: &str) -> Option<String> {
+    let line = prompt
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())?;
+    if line.chars().count() <= HOME_DASHBOARD_EXCERPT_MAX_CHARS {
+        return Some(line.to_string());
+    }
+    let truncated = line
+        .chars()
+        .take(HOME_DASHBOARD_EXCERPT_MAX_CHARS.saturating_sub(1))
+        .collect::<String>();
+    Some(format!("{truncated}…"))
+}
+
+impl GroveApp {
+    fn home_dashboard_usage_spans(
+        &self,
+        label: &'static str,
+        totals: &UsageTotals,
+        label_style: Style,
+        value_style: Style,
+    ) -> Vec<FtSpan<'static>> {
+        let tokens = totals.tokens();
+        let cache_tokens = tokens
+            .cache_read_tokens
+            .saturating_add(tokens.cache_write_tokens);
+        vec![
+            FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+            FtSpan::styled(label, label_style),
+            FtSpan::styled(
+                format!(
+                    "in {} · out {} · cache {} · ~{}",
+                    compact_token_count(tokens.input_tokens),
+                    compact_token_count(tokens.output_tokens),
+                    compact_token_count(cache_tokens),
+                    self.usage_cost(totals).label(),
+                ),
+                value_style,
+            ),
+        ]
+    }
+
+    fn home_dashboard_hotkey_span(
+        targets: &[HomeDashboardTarget],
+        target: &HomeDashboardTarget,
+        style: Style,
+    ) -> FtSpan<'static> {
+        match targets
+            .iter()
+            .position(|candidate| candidate == target)
+            .filter(|index| *index < HOME_DASHBOARD_HOTKEY_COUNT)
+        {
+            Some(index) => FtSpan::styled(format!("[{}] ", index + 1), style),
+            None => FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+        }
+    }
+
+    /// Per-task overview rendered in the Home tab when no parent agent output is shown.
+    pub(super) fn home_dashboard_lines(&self, preview_height: usize) -> Vec<FtLine<'static>> {
+        let Some(task) = self.state.selected_task() else {
+            return vec![FtLine::raw("(no task selected)")];
+        };
+        let theme = self.active_ui_theme();
+        let separator_style = Style::new().fg(packed(theme.text_subtle));
+        let label_style = Style::new().fg(packed(theme.border));
+        let hotkey_style = Style::new().fg(packed(theme.accent)).bold();
+        let separator = || FtSpan::styled(" · ", separator_style);
+        let targets = self.home_dashboard_targets();
+        let stats_current = self.home_dashboard.task_slug.as_deref() == Some(task.slug.as_str());
+
+        let mut lines = Vec::new();
+        for workspace in self.home_dashboard_workspaces() {
+            if !lines.is_empty() {
+                lines.push(FtLine::raw(""));
+            }
+            let mut spans = vec![
+                Self::home_dashboard_hotkey_span(
+                    &targets,
+                    &HomeDashboardTarget::Worktree(workspace.path.clone()),
+                    hotkey_style,
+                ),
+                FtSpan::styled(
+                    Self::workspace_display_name(workspace),
+                    Style::new().fg(packed(theme.text)).bold(),
+                ),
+                separator(),
+                FtSpan::styled(
+                    workspace.branch.clone(),
+                    Style::new().fg(packed(theme.info)),
+                ),
+            ];
+            if !workspace.is_main
+                && let Some(base_branch) = workspace.base_branch.as_ref()
+            {
+                spans.push(FtSpan::styled(format!(" → {base_branch}"), label_style));
+            }
+            let stats = self
+                .home_dashboard
+                .worktree_stats
+                .get(workspace.path.as_path())
+                .filter(|_| stats_current);
+            if let Some(divergence) = stats.and_then(|stats| stats.divergence) {
+                spans.push(separator());
+                spans.push(FtSpan::styled(
+                    format!("↑{} ↓{}", divergence.ahead, divergence.behind),
+                    Style::new().fg(packed(theme.secondary)),
+                ));
+            }
+            if let Some(stats) = stats {
+                spans.push(separator());
+                if stats.files_changed == 0 {
+                    spans.push(FtSpan::styled("clean", label_style));
+                } else {
+                    let file_word = if stats.files_changed == 1 {
+                        "file"
+                    } else {
+                        "files"
+                    };
+                    spans.push(FtSpan::styled(
+                        format!("{} {file_word} ", stats.files_changed),
+                        label_style,
+                    ));
+                    spans.push(FtSpan::styled(
+                        format!("+{}", stats.insertions),
+                        Style::new().fg(packed(theme.success)).bold(),
+                    ));
+                    spans.push(FtSpan::styled(
+                        format!(" -{}", stats.deletions),
+                        Style::new().fg(packed(theme.error)).bold(),
+                    ));
+                }
+            }
+            lines.push(FtLine::from_spans(spans));
+
+            let is_selected = self
+                .state
+                .selected_workspace()
+                .is_some_and(|selected| selected.path == workspace.path);
+            let status_style =
+                if self.status_is_visually_working(Some(workspace.path.as_path()), is_selected) {
+                    Style::new().fg(packed(theme.primary)).bold()
+                } else {
+                    Style::new().fg(packed(theme.text))
+                };
+            let mut agent_spans = vec![
+                FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+                FtSpan::styled("agent ", label_style),
+                FtSpan::styled(workspace_status_label(workspace.status), status_style),
+            ];
+            if let Some(uptime_secs) = self.home_dashboard_agent_uptime_secs(workspace) {
+                agent_spans.push(separator());
+                agent_spans.push(FtSpan::styled(
+                    format!("up {}", uptime_label(uptime_secs)),
+                    label_style,
+                ));
+            }
+            if let Some(excerpt) = self
+                .polling
+                .workspace_waiting_prompts
+                .get(workspace.path.as_path())
+                .and_then(|prompt| waiting_excerpt(prompt))
+            {
+                agent_spans.push(separator());
+                agent_spans.push(FtSpan::styled(
+                    excerpt,
+                    Style::new().fg(packed(theme.warning)),
+                ));
+            }
+            lines.push(FtLine::from_spans(agent_spans));
+
+            if let Some(usage) = self
+                .worktree_usage
+                .get(workspace.path.as_path())
+                .filter(|usage| !usage.is_empty())
+            {
+                lines.push(FtLine::from_spans(self.home_dashboard_usage_spans(
+                    "usage ",
+                    usage,
+                    label_style,
+                    Style::new().fg(packed(theme.text)),
+                )));
+            }
+
+            if let Some(tabs) = self.workspace_tabs.get(workspace.path.as_path()) {
+                let mut tab_spans = vec![
+                    FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+                    FtSpan::styled("tabs ", label_style),
+                ];
+                for tab in tabs
+                    .tabs
+                    .iter()
+                    .filter(|tab| tab.kind != WorkspaceTabKind::Home)
+                {
+                    tab_spans.push(Self::home_dashboard_hotkey_span(
+                        &targets,
+                        &HomeDashboardTarget::Tab {
+                            workspace_path: workspace.path.clone(),
+                            tab_id: tab.id,
+                        },
+                        hotkey_style,
+                    ));
+                    tab_spans.push(FtSpan::styled(
+                        format!("{} ", tab.title),
+                        Style::new().fg(packed(theme.text)),
+                    ));
+                }
+                if tab_spans.len() > 2 {
+                    lines.push(FtLine::from_spans(tab_spans));
+                }
+            }
+
+            if let Some(ports) = workspace.ports {
+                lines.push(FtLine::from_spans(vec![
+                    FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+                    FtSpan::styled("ports ", label_style),
+                    FtSpan::styled(
+                        format!("http://localhost:{}", ports.base),
+                        Style::new().fg(packed(theme.info)),
+                    ),
+                    separator(),
+                    FtSpan::styled(format!("{}-{}", ports.base, ports.last()), label_style),
+                ]));
+            }
+
+            if !workspace.pull_requests.is_empty() {
+                let mut pull_request_spans = vec![
+                    FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+                    FtSpan::styled("PRs ", label_style),
+                ];
+                for pull_request in &workspace.pull_requests {
+                    pull_request_spans.push(Self::home_dashboard_hotkey_span(
+                        &targets,
+                        &HomeDashboardTarget::PullRequest(pull_request.url.clone()),
+                        hotkey_style,
+                    ));
+                    pull_request_spans.push(FtSpan::styled(
+                        format!(
+                            "{}#{}{} ",
+                            Self::pull_request_status_icon(pull_request.status),
+                            pull_request.number,
+                            Self::pull_request_checks_marker(pull_request),
+                        ),
+                        Self::pull_request_status_style(pull_request.status, Style::new(), theme),
+                    ));
+                }
+                lines.push(FtLine::from_spans(pull_request_spans));
+            }
+        }
+
+        let task_usage = self.task_usage_totals(task);
+        if !task_usage.is_empty() {
+            lines.push(FtLine::raw(""));
+            lines.push(FtLine::from_spans(self.home_dashboard_usage_spans(
+                "task total ",
+                &task_usage,
+                label_style,
+                Style::new().fg(packed(theme.text)).bold(),
+            )));
+        }
+
+        let recent_events: &[TaskEventRecord] = if stats_current {
+            self.home_dashboard.recent_events.as_slice()
+        } else {
+            &[]
+        };
+        if !recent_events.is_empty() {
+            lines.push(FtLine::raw(""));
+            lines.push(FtLine::from_spans(vec![FtSpan::styled(
+                "Recent events",
+                Style::new().fg(packed(theme.text)).bold(),
+            )]));
+            for event in recent_events {
+                let event_secs = i64::try_from(event.ts_ms / 1_000).unwrap_or(i64::MAX);
+                let mut event_spans = vec![
+                    FtSpan::raw(HOME_DASHBOARD_INDENT.to_string()),
+                    FtSpan::styled(
+                        format!("{:>3} ", self.relative_age_label(Some(event_secs))),
+                        label_style,
+                    ),
+                    FtSpan::styled(event.event.clone(), Style::new().fg(packed(theme.text))),
+                ];
+                if let Some(workspace) = event.worktree_path.as_ref().and_then(|path| {
+                    self.state
+                        .workspaces
+                        .iter()
+                        .find(|workspace| &workspace.path == path)
+                }) {
+                    event_spans.push(separator());
+                    event_spans.push(FtSpan::styled(
+                        Self::workspace_display_name(workspace),
+                        label_style,
+                    ));
+                }
+                lines.push(FtLine::from_spans(event_spans));
+            }
+        }
+        lines.truncate(preview_height);
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uptime_label_scales_units() {
+        assert_eq!(uptime_label(42), "42s");
+        assert_eq!(uptime_label(125), "2m");
+        assert_eq!(uptime_label(3_900), "1h 5m");
+        assert_eq!(uptime_label(90_000), "1d 1h");
+    }
+
+    #[test]
+    fn waiting_excerpt_uses_last_line_and_truncates() {
+        assert_eq!(
+            waiting_excerpt("Edit file?\n  Allow once  \n\n"),
+            Some("Allow once".to_string())
+        );
+        let long = "x".repeat(80);
+        let excerpt = waiting_excerpt(long.as_str()).expect("excerpt");
+        assert_eq!(excerpt.chars().count(), HOME_DASHBOARD_EXCERPT_MAX_CHARS);
+        assert!(excerpt.ends_with('…'));
+        assert_eq!(waiting_excerpt("  \n"), None);
+    }
+}
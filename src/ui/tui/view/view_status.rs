@@ -16,6 +16,10 @@ impl GroveApp {
             "project" => "Project",
             "settings" => "Settings",
             "performance" => "Performance",
+            "transcript" => "Transcript",
+            "pane_search" => "Search",
+            "broadcast" => "Broadcast",
+            "prompt_queue" => "Queue",
             _ => "Dialog",
         }
     }
@@ -48,6 +52,9 @@ impl GroveApp {
         if self.task_reorder_active() {
             return "Task Reorder".to_string();
         }
+        if let Some(keys) = self.pending_key_sequence_label() {
+            return format!("Keys: {keys} …");
+        }
 
         if self.preview_focused() {
             format!("Preview: {}", self.preview_tab.label())